@@ -0,0 +1,375 @@
+//! Agent adapters: what the dashboard knows about each coding agent.
+//!
+//! An [`AgentAdapter`] gives an agent's display name, its tag in session rows,
+//! how its lifecycle events map to statuses, and the command that resumes one
+//! of its sessions. Claude Code, Codex, Gemini CLI and Aider have adapters in
+//! code; other agents are declared in the config file:
+//!
+//! ```toml
+//! [[agents.custom]]
+//! name = "devbot"
+//! display_name = "DevBot"
+//! icon = "bot"
+//! resume = "devbot --resume {session_id}"
+//!
+//! [agents.custom.statuses]
+//! prompt = "working"
+//! done = "attention"
+//! exit = "closed"
+//! ```
+//!
+//! and report their sessions with
+//! `acd agent-hook --agent devbot <session-id> <event>`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::config::schema::{AgentsConfig, CustomAgentConfig};
+use crate::{AgentType, Session, Status};
+
+/// Everything the dashboard needs to know about one kind of agent.
+pub trait AgentAdapter: fmt::Debug + Send + Sync {
+    /// The agent type sessions of this agent are stored with.
+    fn agent_type(&self) -> AgentType;
+
+    /// Human-readable name for messages (e.g., "Claude Code").
+    fn display_name(&self) -> &str;
+
+    /// Tag shown before the directory in session rows, or `None` for no tag.
+    fn icon(&self) -> Option<&str>;
+
+    /// Status a lifecycle event moves a session to, or `None` if the event
+    /// does not change it.
+    fn status_for_event(&self, event: &str) -> Option<Status>;
+
+    /// Program and arguments that resume `session` in its working directory,
+    /// or `None` if the agent cannot resume sessions.
+    fn resume_command(&self, session: &Session) -> Option<Vec<String>>;
+}
+
+/// Adapter for an agent supported in code.
+#[derive(Debug)]
+struct BuiltinAgent(AgentType);
+
+impl AgentAdapter for BuiltinAgent {
+    fn agent_type(&self) -> AgentType {
+        self.0.clone()
+    }
+
+    fn display_name(&self) -> &str {
+        self.0.label()
+    }
+
+    fn icon(&self) -> Option<&str> {
+        match self.0 {
+            // The common case stays untagged
+            AgentType::ClaudeCode | AgentType::Custom(_) => None,
+            AgentType::Codex => Some("codex"),
+            AgentType::GeminiCli => Some("gemini"),
+            AgentType::Aider => Some("aider"),
+        }
+    }
+
+    fn status_for_event(&self, event: &str) -> Option<Status> {
+        let status = match (&self.0, event) {
+            // Hook events and notification types registered by `acd install`
+            (AgentType::ClaudeCode, "SessionStart" | "Stop" | "permission_prompt") => {
+                Status::Attention
+            }
+            (AgentType::ClaudeCode, "UserPromptSubmit" | "PostToolUse" | "PreCompact") => {
+                Status::Working
+            }
+            (AgentType::ClaudeCode, "elicitation_dialog" | "AskUserQuestion") => Status::Question,
+            (AgentType::ClaudeCode, "SessionEnd") => Status::Closed,
+            // `notify` events
+            (AgentType::Codex, "agent-turn-complete") => Status::Attention,
+            // Hook events, plus notification types for `Notification`
+            (AgentType::GeminiCli, "SessionStart" | "AfterAgent") => Status::Attention,
+            (AgentType::GeminiCli, "BeforeAgent") => Status::Working,
+            (AgentType::GeminiCli, "ToolPermission") => Status::Question,
+            (AgentType::GeminiCli, "SessionEnd") => Status::Closed,
+            // States observed by the `acd aider` wrapper
+            (AgentType::Aider, "prompt") => Status::Working,
+            (AgentType::Aider, "waiting") => Status::Attention,
+            (AgentType::Aider, "exit") => Status::Closed,
+            _ => return None,
+        };
+        Some(status)
+    }
+
+    fn resume_command(&self, session: &Session) -> Option<Vec<String>> {
+        let args: &[&str] = match self.0 {
+            AgentType::ClaudeCode => &["claude", "--resume", &session.session_id],
+            AgentType::Codex => &["codex", "resume", &session.session_id],
+            AgentType::GeminiCli => &["gemini", "--resume", &session.session_id],
+            // Aider has no session IDs; it restores the directory's chat history
+            AgentType::Aider => &["aider", "--restore-chat-history"],
+            AgentType::Custom(_) => return None,
+        };
+        Some(args.iter().map(|arg| arg.to_string()).collect())
+    }
+}
+
+/// Adapter for an agent declared under `[[agents.custom]]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAgent {
+    name: String,
+    display_name: String,
+    icon: String,
+    statuses: BTreeMap<String, Status>,
+    resume: Vec<String>,
+}
+
+impl CustomAgent {
+    /// Builds an adapter from its config entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the name is invalid or a status is unknown.
+    pub fn from_config(config: &CustomAgentConfig) -> Result<Self, String> {
+        if !is_valid_name(&config.name) {
+            return Err(format!(
+                "invalid agent name '{}' (use letters, digits, '-' and '_')",
+                config.name
+            ));
+        }
+        let statuses = config
+            .statuses
+            .iter()
+            .map(|(event, status)| {
+                status
+                    .parse()
+                    .map(|status| (event.clone(), status))
+                    .map_err(|e| format!("event '{}': {}", event, e))
+            })
+            .collect::<Result<_, _>>()?;
+        let or_name = |value: &str| {
+            if value.is_empty() {
+                config.name.clone()
+            } else {
+                value.to_string()
+            }
+        };
+        Ok(Self {
+            name: config.name.clone(),
+            display_name: or_name(&config.display_name),
+            icon: or_name(&config.icon),
+            statuses,
+            resume: config.resume.split_whitespace().map(String::from).collect(),
+        })
+    }
+
+    /// Adapter for sessions of an agent that is not configured here (e.g., a
+    /// hook from another machine's config): named and tagged, nothing else.
+    fn unconfigured(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            icon: name.to_string(),
+            statuses: BTreeMap::new(),
+            resume: Vec::new(),
+        }
+    }
+}
+
+impl AgentAdapter for CustomAgent {
+    fn agent_type(&self) -> AgentType {
+        AgentType::Custom(self.name.clone())
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some(&self.icon)
+    }
+
+    fn status_for_event(&self, event: &str) -> Option<Status> {
+        self.statuses.get(event).copied()
+    }
+
+    fn resume_command(&self, session: &Session) -> Option<Vec<String>> {
+        if self.resume.is_empty() {
+            return None;
+        }
+        let working_dir = session
+            .working_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default();
+        let command = self
+            .resume
+            .iter()
+            .map(|arg| {
+                arg.replace("{session_id}", &session.session_id)
+                    .replace("{working_dir}", &working_dir)
+            })
+            .collect();
+        Some(command)
+    }
+}
+
+/// The adapters of every known agent.
+#[derive(Debug, Clone)]
+pub struct AgentRegistry {
+    adapters: Vec<Arc<dyn AgentAdapter>>,
+}
+
+impl AgentRegistry {
+    /// Registry with only the built-in agents.
+    pub fn builtin() -> Self {
+        let adapters = [
+            AgentType::ClaudeCode,
+            AgentType::Codex,
+            AgentType::GeminiCli,
+            AgentType::Aider,
+        ]
+        .into_iter()
+        .map(|agent| Arc::new(BuiltinAgent(agent)) as Arc<dyn AgentAdapter>)
+        .collect();
+        Self { adapters }
+    }
+
+    /// Registry with the built-in agents and the `[[agents.custom]]` entries.
+    ///
+    /// Invalid entries, and entries reusing a built-in agent's name, are
+    /// skipped with a warning.
+    pub fn from_config(config: &AgentsConfig) -> Self {
+        let mut registry = Self::builtin();
+        for entry in &config.custom {
+            match CustomAgent::from_config(entry) {
+                Ok(agent) if !matches!(agent.name.parse(), Ok(AgentType::Custom(_))) => {
+                    tracing::warn!("ignoring custom agent '{}': name is built in", agent.name);
+                }
+                Ok(agent) => registry.adapters.push(Arc::new(agent)),
+                Err(e) => tracing::warn!("ignoring custom agent '{}': {}", entry.name, e),
+            }
+        }
+        registry
+    }
+
+    /// Iterates over the built-in adapters, then the configured ones.
+    pub fn adapters(&self) -> impl Iterator<Item = &dyn AgentAdapter> {
+        self.adapters.iter().map(|adapter| adapter.as_ref())
+    }
+
+    /// Returns the adapter for `agent`. Custom agents missing from the config
+    /// get a minimal adapter showing their name.
+    pub fn get(&self, agent: &AgentType) -> Arc<dyn AgentAdapter> {
+        self.adapters
+            .iter()
+            .find(|adapter| adapter.agent_type() == *agent)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(CustomAgent::unconfigured(agent.label())))
+    }
+}
+
+impl Default for AgentRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// Returns true if `name` can name a custom agent: non-empty ASCII letters,
+/// digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn devbot() -> CustomAgentConfig {
+        CustomAgentConfig {
+            name: "devbot".to_string(),
+            display_name: "DevBot".to_string(),
+            icon: String::new(),
+            statuses: BTreeMap::from([
+                ("prompt".to_string(), "working".to_string()),
+                ("done".to_string(), "attention".to_string()),
+            ]),
+            resume: "devbot --resume {session_id} --dir {working_dir}".to_string(),
+        }
+    }
+
+    fn session(agent_type: AgentType) -> Session {
+        Session::new(
+            "s-1".to_string(),
+            agent_type,
+            Some(PathBuf::from("/work/api")),
+        )
+    }
+
+    #[test]
+    fn test_builtin_resume_commands() {
+        let registry = AgentRegistry::builtin();
+        let resume = |agent: AgentType| registry.get(&agent).resume_command(&session(agent));
+        assert_eq!(
+            resume(AgentType::ClaudeCode),
+            Some(vec!["claude".into(), "--resume".into(), "s-1".into()])
+        );
+        assert_eq!(
+            resume(AgentType::Aider),
+            Some(vec!["aider".into(), "--restore-chat-history".into()])
+        );
+        assert_eq!(registry.get(&AgentType::ClaudeCode).icon(), None);
+        assert_eq!(registry.get(&AgentType::Codex).icon(), Some("codex"));
+    }
+
+    #[test]
+    fn test_custom_agent_from_config() {
+        let config = AgentsConfig {
+            custom: vec![devbot()],
+            ..AgentsConfig::default()
+        };
+        let registry = AgentRegistry::from_config(&config);
+        let agent = AgentType::Custom("devbot".to_string());
+        let adapter = registry.get(&agent);
+        assert_eq!(adapter.display_name(), "DevBot");
+        assert_eq!(adapter.icon(), Some("devbot"));
+        assert_eq!(adapter.status_for_event("prompt"), Some(Status::Working));
+        assert_eq!(adapter.status_for_event("unknown"), None);
+        assert_eq!(
+            adapter.resume_command(&session(agent)),
+            Some(vec![
+                "devbot".to_string(),
+                "--resume".to_string(),
+                "s-1".to_string(),
+                "--dir".to_string(),
+                "/work/api".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_invalid_custom_agents_are_skipped() {
+        let mut bad_status = devbot();
+        bad_status
+            .statuses
+            .insert("x".to_string(), "asleep".to_string());
+        assert!(CustomAgent::from_config(&bad_status)
+            .expect_err("unknown status")
+            .contains("asleep"));
+
+        let mut builtin_name = devbot();
+        builtin_name.name = "codex".to_string();
+        let config = AgentsConfig {
+            custom: vec![bad_status, builtin_name],
+            ..AgentsConfig::default()
+        };
+        let registry = AgentRegistry::from_config(&config);
+        // The built-in Codex adapter is untouched
+        assert_eq!(registry.get(&AgentType::Codex).display_name(), "Codex");
+        // An agent missing from the config still gets a name and tag
+        let unknown = registry.get(&AgentType::Custom("devbot".to_string()));
+        assert_eq!(unknown.display_name(), "devbot");
+        assert_eq!(unknown.resume_command(&session(AgentType::Aider)), None);
+    }
+}
@@ -0,0 +1,57 @@
+//! Generic agent hook tests.
+
+use crate::commands::agent_hook::agent_set_command;
+use agent_console_dashboard::agents::AgentRegistry;
+use agent_console_dashboard::config::schema::{AgentsConfig, CustomAgentConfig};
+use agent_console_dashboard::AgentType;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+fn registry() -> AgentRegistry {
+    AgentRegistry::from_config(&AgentsConfig {
+        custom: vec![CustomAgentConfig {
+            name: "devbot".to_string(),
+            statuses: BTreeMap::from([("done".to_string(), "attention".to_string())]),
+            ..CustomAgentConfig::default()
+        }],
+        ..AgentsConfig::default()
+    })
+}
+
+#[test]
+fn test_custom_agent_event_sets_status() {
+    let adapter = registry().get(&AgentType::Custom("devbot".to_string()));
+    let cmd = agent_set_command(
+        adapter.as_ref(),
+        "s-1",
+        "done",
+        Some(Path::new("/work/api")),
+        Some("  Ready for review\n"),
+    )
+    .expect("configured event sets a status");
+    assert_eq!(cmd.cmd, "SET");
+    assert_eq!(cmd.session_id.as_deref(), Some("s-1"));
+    assert_eq!(cmd.status.as_deref(), Some("attention"));
+    assert_eq!(cmd.working_dir.as_deref(), Some("/work/api"));
+    assert_eq!(cmd.last_message.as_deref(), Some("Ready for review"));
+    assert_eq!(cmd.agent_type.as_deref(), Some("devbot"));
+}
+
+#[test]
+fn test_unknown_events_are_ignored() {
+    let registry = registry();
+    let devbot = registry.get(&AgentType::Custom("devbot".to_string()));
+    assert!(agent_set_command(devbot.as_ref(), "s-1", "started", None, None).is_none());
+    // Agents missing from the config map no events
+    let other = registry.get(&AgentType::Custom("other".to_string()));
+    assert!(agent_set_command(other.as_ref(), "s-1", "done", None, None).is_none());
+}
+
+#[test]
+fn test_builtin_agent_events() {
+    let codex = AgentRegistry::builtin().get(&AgentType::Codex);
+    let cmd = agent_set_command(codex.as_ref(), "t-1", "agent-turn-complete", None, None)
+        .expect("Codex turn completion sets a status");
+    assert_eq!(cmd.status.as_deref(), Some("attention"));
+    assert_eq!(cmd.agent_type.as_deref(), Some("codex"));
+}
@@ -0,0 +1,93 @@
+//! Aider wrapper tests.
+
+use crate::commands::aider::{
+    aider_args, aider_session_id, aider_set_command, chat_history_path, has_new_prompt,
+};
+use agent_console_dashboard::Status;
+use std::path::{Path, PathBuf};
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_chat_history_path_defaults_to_git_root() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let nested = dir.path().join("src/deep");
+    std::fs::create_dir_all(&nested).expect("create nested dir");
+    std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+
+    assert_eq!(
+        chat_history_path(&[], &nested),
+        dir.path().join(".aider.chat.history.md")
+    );
+
+    let outside = tempfile::TempDir::new().expect("temp dir");
+    assert_eq!(
+        chat_history_path(&[], outside.path()),
+        outside.path().join(".aider.chat.history.md")
+    );
+}
+
+#[test]
+fn test_chat_history_path_honours_flag() {
+    let cwd = Path::new("/work/api");
+    assert_eq!(
+        chat_history_path(&strings(&["--chat-history-file", "notes.md"]), cwd),
+        PathBuf::from("/work/api/notes.md")
+    );
+    assert_eq!(
+        chat_history_path(
+            &strings(&["--model", "x", "--chat-history-file=/tmp/h.md"]),
+            cwd
+        ),
+        PathBuf::from("/tmp/h.md")
+    );
+}
+
+#[test]
+fn test_has_new_prompt() {
+    assert!(has_new_prompt("\n#### add a test for parse()\n"));
+    assert!(!has_new_prompt(
+        "I added the test.\n\n> Applied edit to src/lib.rs\n"
+    ));
+    assert!(!has_new_prompt(
+        "# aider chat started at 2025-01-01 10:00:00\n"
+    ));
+}
+
+#[test]
+fn test_aider_args_append_notifications_hook() {
+    let args = aider_args(
+        Path::new("/opt/my tools/acd"),
+        Path::new("/tmp/acd.sock"),
+        "aider-1",
+        &strings(&["--model", "sonnet"]),
+    );
+    assert_eq!(
+        args,
+        strings(&[
+            "--model",
+            "sonnet",
+            "--notifications",
+            "--notifications-command",
+            "'/opt/my tools/acd' 'aider-hook' '--socket' '/tmp/acd.sock' 'aider-1'",
+        ])
+    );
+}
+
+#[test]
+fn test_aider_set_command() {
+    let cmd = aider_set_command("aider-1", Status::Working, Some(Path::new("/work/api")));
+    assert_eq!(cmd.cmd, "SET");
+    assert_eq!(cmd.status.as_deref(), Some("working"));
+    assert_eq!(cmd.working_dir.as_deref(), Some("/work/api"));
+    assert_eq!(cmd.agent_type.as_deref(), Some("aider"));
+}
+
+#[test]
+fn test_aider_session_ids_are_unique() {
+    let id = aider_session_id();
+    assert!(id.starts_with("aider-"));
+    assert_ne!(id, aider_session_id());
+}
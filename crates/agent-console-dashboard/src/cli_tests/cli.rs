@@ -1,5 +1,6 @@
 //! CLI argument parsing tests.
 
+use crate::commands::{ListFormat, MenubarFormat, StatusbarFormat};
 use crate::{Cli, Commands, ConfigAction, DaemonCommands, LayoutModeArg, SessionCommands};
 use clap::{CommandFactory, Parser};
 use std::path::PathBuf;
@@ -19,13 +20,13 @@ fn test_daemon_without_subcommand_fails() {
 
 #[test]
 fn test_default_socket_path() {
-    // Verify default socket path is /tmp/agent-console-dashboard.sock
+    // Verify the default socket path is the shared per-user default
     let cli = Cli::try_parse_from(["agent-console-dashboard", "daemon", "start"]).unwrap();
     match cli.command {
         Commands::Daemon {
             command: DaemonCommands::Start { socket, .. },
         } => {
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
         }
         _ => panic!("unexpected command variant"),
     }
@@ -232,7 +233,7 @@ fn test_claude_hook_working_parses() {
     match cli.command {
         Commands::ClaudeHook { status, socket } => {
             assert_eq!(status, agent_console_dashboard::Status::Working);
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
         }
         _ => panic!("expected ClaudeHook command"),
     }
@@ -314,7 +315,7 @@ fn test_daemon_dump_subcommand_parses() {
         Commands::Daemon {
             command: DaemonCommands::Dump { socket, format },
         } => {
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
             assert_eq!(format, "json");
         }
         _ => panic!("unexpected command variant"),
@@ -392,7 +393,7 @@ fn test_daemon_status_subcommand_parses() {
         Commands::Daemon {
             command: DaemonCommands::Status { socket },
         } => {
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
         }
         _ => panic!("unexpected command variant"),
     }
@@ -446,7 +447,7 @@ fn test_session_update_with_status() {
             assert_eq!(status, Some("working".to_string()));
             assert_eq!(priority, None);
             assert_eq!(working_dir, None);
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
         }
         _ => panic!("unexpected command variant"),
     }
@@ -542,7 +543,7 @@ fn test_session_update_with_all_fields() {
             assert_eq!(status, Some("attention".to_string()));
             assert_eq!(priority, Some(10));
             assert_eq!(working_dir, Some(PathBuf::from("/my/project")));
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
         }
         _ => panic!("unexpected command variant"),
     }
@@ -622,7 +623,7 @@ fn test_session_delete_parses() {
             command: SessionCommands::Delete { session_id, socket },
         } => {
             assert_eq!(session_id, "test-session-id");
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
         }
         _ => panic!("unexpected command variant"),
     }
@@ -759,7 +760,7 @@ fn test_daemon_restart_parses() {
         Commands::Daemon {
             command: DaemonCommands::Restart { socket, detach },
         } => {
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+            assert_eq!(socket, crate::default_socket());
             assert!(!detach);
         }
         _ => panic!("expected daemon restart command"),
@@ -849,14 +850,28 @@ fn test_tui_without_layout_flag() {
     // Verify tui command works without --layout (existing behavior)
     let cli = Cli::try_parse_from(["agent-console-dashboard", "tui"]).unwrap();
     match cli.command {
-        Commands::Tui { socket, layout } => {
-            assert_eq!(socket, PathBuf::from("/tmp/agent-console-dashboard.sock"));
+        Commands::Tui {
+            socket,
+            layout,
+            read_only,
+        } => {
+            assert_eq!(socket, crate::default_socket());
             assert_eq!(layout, None);
+            assert!(!read_only);
         }
         _ => panic!("unexpected command variant"),
     }
 }
 
+#[test]
+fn test_tui_with_read_only_flag() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "tui", "--read-only"]).unwrap();
+    match cli.command {
+        Commands::Tui { read_only, .. } => assert!(read_only),
+        _ => panic!("unexpected command variant"),
+    }
+}
+
 #[test]
 fn test_tui_with_layout_auto() {
     // Verify --layout auto flag works (explicit auto-detect)
@@ -881,6 +896,19 @@ fn test_tui_with_layout_large() {
     }
 }
 
+#[test]
+fn test_tui_with_layout_medium() {
+    // Verify --layout medium flag works
+    let cli =
+        Cli::try_parse_from(["agent-console-dashboard", "tui", "--layout", "medium"]).unwrap();
+    match cli.command {
+        Commands::Tui { layout, .. } => {
+            assert_eq!(layout, Some(LayoutModeArg::Medium));
+        }
+        _ => panic!("unexpected command variant"),
+    }
+}
+
 #[test]
 fn test_tui_with_layout_twoline() {
     // Verify --layout twoline flag works
@@ -944,3 +972,313 @@ fn test_tui_help_contains_layout_option() {
     let layout_arg = tui_cmd.get_arguments().find(|arg| arg.get_id() == "layout");
     assert!(layout_arg.is_some(), "--layout flag should exist");
 }
+
+// -- MCP subcommand -----------------------------------------------------
+
+#[test]
+fn test_mcp_parses_with_default_socket() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "mcp"]).expect("mcp should parse");
+    match cli.command {
+        Commands::Mcp { socket } => {
+            assert_eq!(socket, crate::default_socket());
+        }
+        _ => panic!("unexpected command variant"),
+    }
+}
+
+#[test]
+fn test_mcp_with_custom_socket() {
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "mcp",
+        "--socket",
+        "/custom/mcp.sock",
+    ])
+    .expect("mcp with custom socket should parse");
+    match cli.command {
+        Commands::Mcp { socket } => assert_eq!(socket, PathBuf::from("/custom/mcp.sock")),
+        _ => panic!("unexpected command variant"),
+    }
+}
+
+// -- RPC subcommand -----------------------------------------------------
+
+#[test]
+fn test_rpc_defaults_to_stdio() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "rpc"]).expect("rpc should parse");
+    match cli.command {
+        Commands::Rpc { socket, listen } => {
+            assert_eq!(socket, crate::default_socket());
+            assert_eq!(listen, None);
+        }
+        _ => panic!("unexpected command variant"),
+    }
+}
+
+#[test]
+fn test_rpc_listen_parses() {
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "rpc",
+        "--listen",
+        "/tmp/acd-rpc.sock",
+    ])
+    .expect("rpc --listen should parse");
+    match cli.command {
+        Commands::Rpc { listen, .. } => {
+            assert_eq!(listen, Some(PathBuf::from("/tmp/acd-rpc.sock")));
+        }
+        _ => panic!("unexpected command variant"),
+    }
+}
+
+// -- CodexHook subcommand -----------------------------------------------
+
+#[test]
+fn test_codex_hook_takes_payload_as_last_argument() {
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "codex-hook",
+        "--socket",
+        "/tmp/custom.sock",
+        r#"{"type":"agent-turn-complete"}"#,
+    ])
+    .expect("codex-hook should parse");
+    match cli.command {
+        Commands::CodexHook { payload, socket } => {
+            assert_eq!(payload, r#"{"type":"agent-turn-complete"}"#);
+            assert_eq!(socket, PathBuf::from("/tmp/custom.sock"));
+        }
+        _ => panic!("expected CodexHook command"),
+    }
+}
+
+// -- GeminiHook subcommand ----------------------------------------------
+
+#[test]
+fn test_gemini_hook_parses_with_default_socket() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "gemini-hook"])
+        .expect("gemini-hook should parse");
+    match cli.command {
+        Commands::GeminiHook { socket } => {
+            assert_eq!(socket, crate::default_socket());
+        }
+        _ => panic!("expected GeminiHook command"),
+    }
+}
+
+// -- Aider subcommands --------------------------------------------------
+
+#[test]
+fn test_aider_passes_through_hyphen_args() {
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "aider",
+        "--socket",
+        "/tmp/custom.sock",
+        "--model",
+        "sonnet",
+        "--yes-always",
+    ])
+    .expect("aider should parse");
+    match cli.command {
+        Commands::Aider { socket, args } => {
+            assert_eq!(socket, PathBuf::from("/tmp/custom.sock"));
+            assert_eq!(args, vec!["--model", "sonnet", "--yes-always"]);
+        }
+        _ => panic!("expected Aider command"),
+    }
+}
+
+#[test]
+fn test_aider_hook_requires_session_id() {
+    assert!(Cli::try_parse_from(["agent-console-dashboard", "aider-hook"]).is_err());
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "aider-hook", "aider-1"])
+        .expect("aider-hook should parse");
+    match cli.command {
+        Commands::AiderHook { session_id, .. } => assert_eq!(session_id, "aider-1"),
+        _ => panic!("expected AiderHook command"),
+    }
+}
+
+// -- AgentHook subcommand -----------------------------------------------
+
+#[test]
+fn test_agent_hook_parses() {
+    assert!(Cli::try_parse_from(["agent-console-dashboard", "agent-hook", "s-1", "done"]).is_err());
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "agent-hook",
+        "--agent",
+        "devbot",
+        "--cwd",
+        "/work/api",
+        "s-1",
+        "done",
+    ])
+    .expect("agent-hook should parse");
+    match cli.command {
+        Commands::AgentHook {
+            agent,
+            cwd,
+            message,
+            session_id,
+            event,
+            ..
+        } => {
+            assert_eq!(agent, "devbot");
+            assert_eq!(cwd, Some(PathBuf::from("/work/api")));
+            assert_eq!(message, None);
+            assert_eq!(session_id, "s-1");
+            assert_eq!(event, "done");
+        }
+        _ => panic!("expected AgentHook command"),
+    }
+}
+
+// -- PromptSegment subcommand -------------------------------------------
+
+#[test]
+fn test_prompt_segment_parses() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "prompt-segment", "--no-color"])
+        .expect("prompt-segment should parse");
+    match cli.command {
+        Commands::PromptSegment { socket, no_color } => {
+            assert_eq!(socket, crate::default_socket());
+            assert!(no_color);
+        }
+        _ => panic!("expected PromptSegment command"),
+    }
+}
+
+// -- Statusbar subcommand -----------------------------------------------
+
+#[test]
+fn test_statusbar_parses() {
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "statusbar",
+        "--format",
+        "waybar",
+        "--follow",
+    ])
+    .expect("statusbar should parse");
+    match cli.command {
+        Commands::Statusbar { format, follow, .. } => {
+            assert_eq!(format, StatusbarFormat::Waybar);
+            assert!(follow);
+        }
+        _ => panic!("expected Statusbar command"),
+    }
+    assert!(Cli::try_parse_from(["agent-console-dashboard", "statusbar"]).is_err());
+    assert!(
+        Cli::try_parse_from(["agent-console-dashboard", "statusbar", "--format", "i3"]).is_err()
+    );
+}
+
+// -- TmuxStatus subcommand ----------------------------------------------
+
+#[test]
+fn test_tmux_status_parses_max_age() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "tmux-status"])
+        .expect("tmux-status should parse");
+    match cli.command {
+        Commands::TmuxStatus { max_age, .. } => {
+            assert_eq!(max_age, std::time::Duration::from_secs(5));
+        }
+        _ => panic!("expected TmuxStatus command"),
+    }
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "tmux-status", "--max-age", "0s"])
+        .expect("tmux-status should parse");
+    assert!(matches!(
+        cli.command,
+        Commands::TmuxStatus { max_age, .. } if max_age.is_zero()
+    ));
+    assert!(Cli::try_parse_from([
+        "agent-console-dashboard",
+        "tmux-status",
+        "--max-age",
+        "soon"
+    ])
+    .is_err());
+}
+
+// -- Menubar and Resurrect subcommands ----------------------------------
+
+#[test]
+fn test_menubar_parses() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "menubar", "--format", "xbar"])
+        .expect("menubar should parse");
+    match cli.command {
+        Commands::Menubar { format, .. } => assert_eq!(format, MenubarFormat::Xbar),
+        _ => panic!("expected Menubar command"),
+    }
+    assert!(Cli::try_parse_from(["agent-console-dashboard", "menubar"]).is_err());
+}
+
+#[test]
+fn test_resurrect_parses() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "resurrect", "s-1"])
+        .expect("resurrect should parse");
+    match cli.command {
+        Commands::Resurrect {
+            session_id,
+            exec,
+            socket,
+        } => {
+            assert_eq!(session_id, "s-1");
+            assert!(!exec);
+            assert_eq!(socket, crate::default_socket());
+        }
+        _ => panic!("expected Resurrect command"),
+    }
+
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "resurrect", "--exec", "s-1"])
+        .expect("resurrect --exec should parse");
+    match cli.command {
+        Commands::Resurrect { exec, .. } => assert!(exec),
+        _ => panic!("expected Resurrect command"),
+    }
+}
+
+// -- List subcommand ----------------------------------------------------
+
+#[test]
+fn test_list_parses() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "list"]).expect("list should parse");
+    match cli.command {
+        Commands::List { format, .. } => assert_eq!(format, ListFormat::Text),
+        _ => panic!("expected List command"),
+    }
+    let cli = Cli::try_parse_from([
+        "agent-console-dashboard",
+        "list",
+        "--format",
+        "script-filter",
+    ])
+    .expect("list --format script-filter should parse");
+    match cli.command {
+        Commands::List { format, .. } => assert_eq!(format, ListFormat::ScriptFilter),
+        _ => panic!("expected List command"),
+    }
+}
+
+// -- Profiles -------------------------------------------------------------
+
+#[test]
+fn test_profile_flag_is_global() {
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "tui", "--profile", "work"])
+        .expect("--profile after the subcommand should parse");
+    assert_eq!(cli.profile.as_deref(), Some("work"));
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "--profile", "work", "list"])
+        .expect("--profile before the subcommand should parse");
+    assert_eq!(cli.profile.as_deref(), Some("work"));
+    let cli = Cli::try_parse_from(["agent-console-dashboard", "list"]).expect("list should parse");
+    assert_eq!(cli.profile, None);
+}
+
+#[test]
+fn test_profile_flag_rejects_path_like_names() {
+    let result = Cli::try_parse_from(["agent-console-dashboard", "--profile", "../x", "tui"]);
+    assert!(result.is_err(), "profile names must not contain '/'");
+}
@@ -0,0 +1,61 @@
+//! Codex notify hook tests.
+
+use crate::commands::codex_hook::{codex_set_command, CodexNotification};
+use crate::commands::hook::MAX_LAST_MESSAGE_CHARS;
+use std::path::Path;
+
+fn parse(payload: &str) -> CodexNotification {
+    serde_json::from_str(payload).expect("payload should parse")
+}
+
+#[test]
+fn test_codex_turn_complete_sets_attention() {
+    let notification = parse(
+        r#"{"type":"agent-turn-complete","thread-id":"t-1","turn-id":"12",
+            "cwd":"/home/user/api","input-messages":["fix it"],
+            "last-assistant-message":"  Fixed the failing test.\n"}"#,
+    );
+    let cmd = codex_set_command(&notification, Some(Path::new("/elsewhere")))
+        .expect("valid payload")
+        .expect("turn completion sets a status");
+    assert_eq!(cmd.cmd, "SET");
+    assert_eq!(cmd.session_id.as_deref(), Some("t-1"));
+    assert_eq!(cmd.status.as_deref(), Some("attention"));
+    assert_eq!(cmd.working_dir.as_deref(), Some("/home/user/api"));
+    assert_eq!(cmd.last_message.as_deref(), Some("Fixed the failing test."));
+    assert_eq!(cmd.agent_type.as_deref(), Some("codex"));
+}
+
+#[test]
+fn test_codex_payload_without_cwd_uses_fallback() {
+    let message = "x".repeat(MAX_LAST_MESSAGE_CHARS + 10);
+    let payload = serde_json::json!({
+        "type": "agent-turn-complete",
+        "thread-id": "t-1",
+        "last-assistant-message": message,
+    });
+    let notification = parse(&payload.to_string());
+    let cmd = codex_set_command(&notification, Some(Path::new("/home/user/api")))
+        .expect("valid payload")
+        .expect("turn completion sets a status");
+    assert_eq!(cmd.working_dir.as_deref(), Some("/home/user/api"));
+    assert_eq!(
+        cmd.last_message.map(|m| m.chars().count()),
+        Some(MAX_LAST_MESSAGE_CHARS)
+    );
+}
+
+#[test]
+fn test_codex_other_events_are_ignored() {
+    let notification = parse(r#"{"type":"approval-requested","thread-id":"t-1"}"#);
+    assert!(codex_set_command(&notification, None)
+        .expect("valid payload")
+        .is_none());
+}
+
+#[test]
+fn test_codex_payload_without_thread_id_is_an_error() {
+    let notification = parse(r#"{"type":"agent-turn-complete","turn-id":"12"}"#);
+    let err = codex_set_command(&notification, None).expect_err("no session to update");
+    assert!(err.contains("thread-id"));
+}
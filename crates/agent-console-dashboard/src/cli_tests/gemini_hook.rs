@@ -0,0 +1,54 @@
+//! Gemini CLI hook payload tests.
+
+use crate::commands::gemini_hook::{gemini_set_command, gemini_status, GeminiHookInput};
+use agent_console_dashboard::Status;
+
+fn input(event: &str) -> GeminiHookInput {
+    let payload = serde_json::json!({
+        "session_id": "9f3c0c2e-5a1b-4c8e-9d4f-2b7a6e1d0c3b",
+        "transcript_path": "/home/user/.gemini/tmp/abc/chats/session.json",
+        "cwd": "/home/user/api",
+        "hook_event_name": event,
+        "timestamp": "2025-11-12T10:00:00Z",
+    });
+    serde_json::from_value(payload).expect("payload should parse")
+}
+
+#[test]
+fn test_gemini_lifecycle_events_map_to_statuses() {
+    assert_eq!(
+        gemini_status(&input("SessionStart")),
+        Some(Status::Attention)
+    );
+    assert_eq!(gemini_status(&input("BeforeAgent")), Some(Status::Working));
+    assert_eq!(gemini_status(&input("AfterAgent")), Some(Status::Attention));
+    assert_eq!(gemini_status(&input("SessionEnd")), Some(Status::Closed));
+    assert_eq!(gemini_status(&input("BeforeTool")), None);
+    assert_eq!(gemini_status(&input("AfterModel")), None);
+}
+
+#[test]
+fn test_gemini_tool_permission_is_a_question() {
+    let mut notification = input("Notification");
+    assert_eq!(gemini_status(&notification), None);
+    notification.notification_type = Some("ToolPermission".to_string());
+    assert_eq!(gemini_status(&notification), Some(Status::Question));
+}
+
+#[test]
+fn test_gemini_set_command() {
+    let mut after = input("AfterAgent");
+    after.prompt_response = Some("Done, tests pass.\n".to_string());
+    let cmd = gemini_set_command(&after).expect("AfterAgent sets a status");
+    assert_eq!(cmd.cmd, "SET");
+    assert_eq!(
+        cmd.session_id.as_deref(),
+        Some("9f3c0c2e-5a1b-4c8e-9d4f-2b7a6e1d0c3b")
+    );
+    assert_eq!(cmd.status.as_deref(), Some("attention"));
+    assert_eq!(cmd.working_dir.as_deref(), Some("/home/user/api"));
+    assert_eq!(cmd.last_message.as_deref(), Some("Done, tests pass."));
+    assert_eq!(cmd.agent_type.as_deref(), Some("geminicli"));
+
+    assert!(gemini_set_command(&input("BeforeModel")).is_none());
+}
@@ -1,12 +1,16 @@
 //! Hook validation tests.
 
-use crate::commands::hook::{validate_hook_input, HookInput};
+use crate::commands::hook::{
+    last_assistant_message, validate_hook_input, HookInput, MAX_LAST_MESSAGE_CHARS,
+};
+use std::io::Write;
 
 #[test]
 fn test_validate_hook_input_valid() {
     let input = HookInput {
         session_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
         cwd: "/home/user/project".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert!(warnings.is_empty(), "valid input should have no warnings");
@@ -17,6 +21,7 @@ fn test_validate_hook_input_invalid_session_id_length() {
     let input = HookInput {
         session_id: "short".to_string(),
         cwd: "/home/user/project".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert_eq!(warnings.len(), 1);
@@ -29,6 +34,7 @@ fn test_validate_hook_input_invalid_session_id_chars() {
     let input = HookInput {
         session_id: "550e8400-e29b-41d4-a716-44665544000G".to_string(),
         cwd: "/home/user/project".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert_eq!(warnings.len(), 1);
@@ -40,6 +46,7 @@ fn test_validate_hook_input_empty_cwd() {
     let input = HookInput {
         session_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
         cwd: "".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert_eq!(warnings.len(), 1);
@@ -51,6 +58,7 @@ fn test_validate_hook_input_relative_cwd() {
     let input = HookInput {
         session_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
         cwd: "relative/path".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert_eq!(warnings.len(), 1);
@@ -63,6 +71,7 @@ fn test_validate_hook_input_multiple_invalid_fields() {
     let input = HookInput {
         session_id: "short".to_string(),
         cwd: "relative".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert_eq!(warnings.len(), 2);
@@ -75,6 +84,7 @@ fn test_validate_hook_input_uppercase_hex_valid() {
     let input = HookInput {
         session_id: "550E8400-E29B-41D4-A716-446655440000".to_string(),
         cwd: "/home/user/project".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert!(warnings.is_empty(), "uppercase hex should be valid");
@@ -85,6 +95,7 @@ fn test_validate_hook_input_all_dashes_weird_but_passes() {
     let input = HookInput {
         session_id: "------------------------------------".to_string(),
         cwd: "/home/user/project".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert!(warnings.is_empty(), "36 dashes passes charset validation");
@@ -95,7 +106,74 @@ fn test_validate_hook_input_cwd_with_spaces_valid() {
     let input = HookInput {
         session_id: "550e8400-e29b-41d4-a716-446655440000".to_string(),
         cwd: "/home/user/my project".to_string(),
+        transcript_path: None,
     };
     let warnings = validate_hook_input(&input);
     assert!(warnings.is_empty(), "absolute path with spaces is valid");
 }
+
+fn write_transcript(lines: &[&str]) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().expect("failed to create transcript");
+    for line in lines {
+        writeln!(file, "{}", line).expect("failed to write transcript");
+    }
+    file
+}
+
+#[test]
+fn test_last_assistant_message_skips_tool_only_entries() {
+    let file = write_transcript(&[
+        r#"{"type":"user","message":{"content":"fix the bug"}}"#,
+        r#"{"type":"assistant","message":{"content":[{"type":"text","text":"  Fixed it.  "},{"type":"tool_use","name":"Edit"}]}}"#,
+        r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash"}]}}"#,
+        r#"{"type":"user","message":{"content":"thanks"}}"#,
+    ]);
+    assert_eq!(
+        last_assistant_message(file.path()).as_deref(),
+        Some("Fixed it.")
+    );
+}
+
+#[test]
+fn test_last_assistant_message_string_content() {
+    let file = write_transcript(&[
+        r#"{"type":"assistant","message":{"content":"first"}}"#,
+        "not json",
+        r#"{"type":"assistant","message":{"content":"second"}}"#,
+    ]);
+    assert_eq!(
+        last_assistant_message(file.path()).as_deref(),
+        Some("second")
+    );
+}
+
+#[test]
+fn test_last_assistant_message_truncates_long_text() {
+    let long = "x".repeat(MAX_LAST_MESSAGE_CHARS + 50);
+    let line = format!(
+        r#"{{"type":"assistant","message":{{"content":"{}"}}}}"#,
+        long
+    );
+    let file = write_transcript(&[&line]);
+    let message = last_assistant_message(file.path()).expect("should find message");
+    assert_eq!(message.chars().count(), MAX_LAST_MESSAGE_CHARS);
+}
+
+#[test]
+fn test_last_assistant_message_missing_or_empty() {
+    assert_eq!(
+        last_assistant_message(std::path::Path::new("/nonexistent/transcript.jsonl")),
+        None
+    );
+    let file = write_transcript(&[r#"{"type":"user","message":{"content":"hi"}}"#]);
+    assert_eq!(last_assistant_message(file.path()), None);
+}
+
+#[test]
+fn test_hook_input_parses_transcript_path() {
+    let input: HookInput = serde_json::from_str(
+        r#"{"session_id":"abc","cwd":"/tmp","transcript_path":"/tmp/t.jsonl","hook_event_name":"Stop"}"#,
+    )
+    .expect("should parse");
+    assert_eq!(input.transcript_path.as_deref(), Some("/tmp/t.jsonl"));
+}
@@ -0,0 +1,94 @@
+//! Session list and Script Filter output tests.
+
+use crate::commands::list::{script_filter_output, text_output};
+use agent_console_dashboard::agents::AgentRegistry;
+use agent_console_dashboard::{AgentType, Session, SessionSnapshot, Status};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+const ACD: &str = "/usr/local/bin/acd";
+const SOCKET: &str = "/tmp/acd.sock";
+
+fn snapshot(id: &str, agent: AgentType, status: Status) -> SessionSnapshot {
+    let mut session = Session::new(
+        id.to_string(),
+        agent,
+        Some(PathBuf::from(format!("/work/{}", id))),
+    );
+    session.status = status;
+    session.closed = status == Status::Closed;
+    SessionSnapshot::from(&session)
+}
+
+fn sessions() -> Vec<SessionSnapshot> {
+    vec![
+        snapshot("web", AgentType::ClaudeCode, Status::Working),
+        snapshot("old", AgentType::ClaudeCode, Status::Closed),
+        snapshot("api", AgentType::ClaudeCode, Status::Attention),
+    ]
+}
+
+fn script_filter(sessions: Option<&[SessionSnapshot]>) -> Value {
+    let out = script_filter_output(
+        sessions,
+        &AgentRegistry::builtin(),
+        Path::new(ACD),
+        Path::new(SOCKET),
+    );
+    serde_json::from_str(&out).expect("valid JSON")
+}
+
+#[test]
+fn test_text_output_lists_open_sessions_by_name_then_closed() {
+    let out = text_output(&sessions());
+    let ids: Vec<&str> = out
+        .lines()
+        .map(|line| line.split_whitespace().last().expect("ID"))
+        .collect();
+    assert_eq!(ids, ["api", "web", "old"]);
+    assert!(out.starts_with("attention  api "));
+    assert_eq!(text_output(&[]), "");
+}
+
+#[test]
+fn test_script_filter_items() {
+    let json = script_filter(Some(&sessions()));
+    let items = json["items"].as_array().expect("items");
+    assert_eq!(items.len(), 3);
+
+    let api = &items[0];
+    assert_eq!(api["uid"], "api");
+    assert_eq!(api["title"], "api");
+    assert_eq!(api["subtitle"], "attention for 0s · /work/api");
+    assert_eq!(api["arg"], "cd '/work/api' && 'claude' '--resume' 'api'");
+    assert_eq!(api["valid"], true);
+    assert_eq!(api["variables"]["session_id"], "api");
+    assert!(api.get("mods").is_none());
+
+    let old = &items[2];
+    assert_eq!(old["uid"], "old");
+    assert_eq!(
+        old["mods"]["cmd"]["arg"],
+        "'/usr/local/bin/acd' 'resurrect' '--exec' '--socket' '/tmp/acd.sock' 'old'"
+    );
+}
+
+#[test]
+fn test_script_filter_item_without_resume_is_not_actionable() {
+    let agent = AgentType::Custom("devbot".to_string());
+    let json = script_filter(Some(&[snapshot("x", agent, Status::Working)]));
+    let item = &json["items"][0];
+    assert_eq!(item["arg"], "");
+    assert_eq!(item["valid"], false);
+}
+
+#[test]
+fn test_script_filter_messages() {
+    assert_eq!(
+        script_filter(None)["items"][0]["title"],
+        "acd daemon is not running"
+    );
+    let empty = script_filter(Some(&[]));
+    assert_eq!(empty["items"][0]["title"], "No sessions");
+    assert_eq!(empty["items"][0]["valid"], false);
+}
@@ -0,0 +1,88 @@
+//! Menu bar output tests.
+
+use crate::commands::menubar::{sketchybar_output, xbar_output};
+use agent_console_dashboard::{AgentType, Session, SessionSnapshot, Status};
+use std::path::{Path, PathBuf};
+
+const ACD: &str = "/usr/local/bin/acd";
+const SOCKET: &str = "/tmp/acd.sock";
+
+fn snapshot(id: &str, status: Status) -> SessionSnapshot {
+    let mut session = Session::new(
+        id.to_string(),
+        AgentType::ClaudeCode,
+        Some(PathBuf::from(format!("/work/{}", id))),
+    );
+    session.status = status;
+    session.closed = status == Status::Closed;
+    SessionSnapshot::from(&session)
+}
+
+fn sessions() -> Vec<SessionSnapshot> {
+    vec![
+        snapshot("web", Status::Working),
+        snapshot("api", Status::Attention),
+        snapshot("old", Status::Closed),
+    ]
+}
+
+#[test]
+fn test_xbar_output() {
+    let out = xbar_output(Some(&sessions()), Path::new(ACD), Path::new(SOCKET));
+    assert_eq!(
+        out.lines().collect::<Vec<_>>(),
+        [
+            "⚠1 ▶1 | color=#e5c07b",
+            "---",
+            "api: attention | color=#e5c07b",
+            "web: working | color=#98c379",
+            "---",
+            "Resurrect",
+            "--old | shell=/usr/local/bin/acd param1=resurrect param2=--exec \
+             param3=--socket param4=/tmp/acd.sock param5=old terminal=true refresh=true",
+            "---",
+            "Refresh | refresh=true",
+        ]
+    );
+}
+
+#[test]
+fn test_xbar_output_when_daemon_is_down() {
+    let out = xbar_output(None, Path::new(ACD), Path::new(SOCKET));
+    assert!(out.starts_with("acd\n---\nacd daemon is not running\n"));
+}
+
+#[test]
+fn test_xbar_quotes_paths_with_spaces() {
+    let out = xbar_output(
+        Some(&[snapshot("old", Status::Closed)]),
+        Path::new("/Users/me/my tools/acd"),
+        Path::new(SOCKET),
+    );
+    assert!(out.contains("shell=\"/Users/me/my tools/acd\""), "{}", out);
+}
+
+#[test]
+fn test_sketchybar_output() {
+    let out = sketchybar_output(Some(&sessions()), Path::new(ACD), Path::new(SOCKET));
+    let lines: Vec<_> = out.lines().collect();
+    assert_eq!(lines[0], r"sketchybar --remove '/acd\.session\..*/'");
+    assert_eq!(
+        lines[1],
+        "sketchybar --set acd label='⚠1 ▶1' label.color=0xffe5c07b"
+    );
+    assert_eq!(
+        lines[2],
+        "sketchybar --add item acd.session.0 popup.acd --set acd.session.0 \
+         label='api: attention' label.color=0xffe5c07b"
+    );
+    assert!(lines[4].contains("label='old (resurrect)' click_script='osascript -e "));
+    assert!(lines[4].contains("resurrect"));
+    assert_eq!(lines.len(), 5);
+}
+
+#[test]
+fn test_sketchybar_output_when_daemon_is_down() {
+    let out = sketchybar_output(None, Path::new(ACD), Path::new(SOCKET));
+    assert!(out.ends_with("sketchybar --set acd label='acd ✕'\n"));
+}
@@ -1,10 +1,28 @@
 //! Tests for the ACD CLI binary.
 //!
 //! Tests are organized by domain:
+//! - `agent_hook` - Generic agent hook tests
+//! - `aider` - Aider wrapper tests
 //! - `cli` - CLI argument parsing tests
+//! - `codex_hook` - Codex notify payload tests
+//! - `gemini_hook` - Gemini CLI hook payload tests
 //! - `hook` - Hook validation tests
 //! - `install` - Hook installation/definition tests
+//! - `list` - Session list and Script Filter output tests
+//! - `menubar` - xbar/SketchyBar output tests
+//! - `prompt_segment` - Shell prompt segment tests
+//! - `statusbar` - Waybar/Polybar output tests
+//! - `tmux_status` - tmux status line tests
 
+mod agent_hook;
+mod aider;
 mod cli;
+mod codex_hook;
+mod gemini_hook;
 mod hook;
 mod install;
+mod list;
+mod menubar;
+mod prompt_segment;
+mod statusbar;
+mod tmux_status;
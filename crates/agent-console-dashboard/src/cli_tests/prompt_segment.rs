@@ -0,0 +1,55 @@
+//! Prompt segment tests.
+
+use crate::commands::prompt_segment::{prompt_segment, run_prompt_segment_command};
+use agent_console_dashboard::{AgentType, Session, SessionSnapshot, Status};
+use std::path::Path;
+use std::process::ExitCode;
+
+fn snapshot(status: Status) -> SessionSnapshot {
+    let mut session = Session::new("s".to_string(), AgentType::ClaudeCode, None);
+    session.status = status;
+    session.closed = status == Status::Closed;
+    SessionSnapshot::from(&session)
+}
+
+#[test]
+fn test_prompt_segment_counts_waiting_and_working() {
+    let sessions = [
+        snapshot(Status::Attention),
+        snapshot(Status::Question),
+        snapshot(Status::Working),
+        snapshot(Status::Working),
+        snapshot(Status::Working),
+        snapshot(Status::Closed),
+    ];
+    assert_eq!(prompt_segment(&sessions, false), "⚠2 ▶3");
+}
+
+#[test]
+fn test_prompt_segment_omits_zero_counts() {
+    assert_eq!(prompt_segment(&[snapshot(Status::Working)], false), "▶1");
+    assert_eq!(prompt_segment(&[snapshot(Status::Closed)], false), "");
+    assert_eq!(prompt_segment(&[], false), "");
+}
+
+#[test]
+fn test_prompt_segment_skips_inactive_sessions() {
+    let mut idle = snapshot(Status::Attention);
+    idle.idle_seconds = 2 * 3600;
+    assert_eq!(prompt_segment(&[idle], false), "");
+}
+
+#[test]
+fn test_prompt_segment_colors() {
+    let sessions = [snapshot(Status::Attention), snapshot(Status::Working)];
+    assert_eq!(
+        prompt_segment(&sessions, true),
+        "\x1b[33m⚠1\x1b[0m \x1b[32m▶1\x1b[0m"
+    );
+}
+
+#[test]
+fn test_prompt_segment_is_silent_without_daemon() {
+    let exit = run_prompt_segment_command(Path::new("/nonexistent/acd-prompt.sock"), true);
+    assert_eq!(exit, ExitCode::SUCCESS);
+}
@@ -0,0 +1,73 @@
+//! Status bar output tests.
+
+use crate::commands::statusbar::{statusbar_line, worst_class, StatusbarFormat};
+use agent_console_dashboard::{AgentType, Session, SessionSnapshot, Status};
+use std::path::PathBuf;
+
+fn snapshot(id: &str, status: Status) -> SessionSnapshot {
+    let mut session = Session::new(
+        id.to_string(),
+        AgentType::ClaudeCode,
+        Some(PathBuf::from(format!("/work/{}", id))),
+    );
+    session.status = status;
+    session.closed = status == Status::Closed;
+    SessionSnapshot::from(&session)
+}
+
+fn waybar(sessions: Option<&[SessionSnapshot]>) -> serde_json::Value {
+    serde_json::from_str(&statusbar_line(sessions, StatusbarFormat::Waybar))
+        .expect("waybar output should be JSON")
+}
+
+#[test]
+fn test_worst_class() {
+    let working = snapshot("a", Status::Working);
+    let question = snapshot("b", Status::Question);
+    let attention = snapshot("c", Status::Attention);
+    let closed = snapshot("d", Status::Closed);
+    assert_eq!(worst_class(&[]), "idle");
+    assert_eq!(worst_class(std::slice::from_ref(&closed)), "idle");
+    assert_eq!(worst_class(&[working.clone(), closed]), "working");
+    assert_eq!(
+        worst_class(&[working.clone(), question.clone()]),
+        "question"
+    );
+    assert_eq!(worst_class(&[question, attention, working]), "attention");
+}
+
+#[test]
+fn test_waybar_line() {
+    let mut named = snapshot("api", Status::Attention);
+    named.display_name = Some("login & auth".to_string());
+    let sessions = [
+        named,
+        snapshot("web", Status::Working),
+        snapshot("old", Status::Closed),
+    ];
+    let value = waybar(Some(&sessions));
+    assert_eq!(value["text"], "⚠1 ▶1");
+    assert_eq!(value["class"], "attention");
+    assert_eq!(
+        value["tooltip"],
+        "attention: login &amp; auth\nworking: web"
+    );
+}
+
+#[test]
+fn test_waybar_line_when_daemon_is_down() {
+    let value = waybar(None);
+    assert_eq!(value["text"], "");
+    assert_eq!(value["class"], "down");
+}
+
+#[test]
+fn test_polybar_line() {
+    let sessions = [snapshot("web", Status::Working)];
+    assert_eq!(
+        statusbar_line(Some(&sessions), StatusbarFormat::Polybar),
+        "%{F#98c379}▶1%{F-}"
+    );
+    assert_eq!(statusbar_line(Some(&[]), StatusbarFormat::Polybar), "");
+    assert_eq!(statusbar_line(None, StatusbarFormat::Polybar), "");
+}
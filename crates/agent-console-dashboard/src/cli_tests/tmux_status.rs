@@ -0,0 +1,49 @@
+//! tmux status line tests.
+
+use crate::commands::tmux_status::{cache_path, read_cached, tmux_segment, write_cache};
+use agent_console_dashboard::{AgentType, Session, SessionSnapshot, Status};
+use std::path::Path;
+use std::time::Duration;
+
+fn snapshot(status: Status) -> SessionSnapshot {
+    let mut session = Session::new("s".to_string(), AgentType::ClaudeCode, None);
+    session.status = status;
+    session.closed = status == Status::Closed;
+    SessionSnapshot::from(&session)
+}
+
+#[test]
+fn test_tmux_segment_styles_counts() {
+    let sessions = [
+        snapshot(Status::Question),
+        snapshot(Status::Working),
+        snapshot(Status::Working),
+    ];
+    assert_eq!(
+        tmux_segment(&sessions),
+        "#[fg=yellow]⚠1#[default] #[fg=green]▶2#[default]"
+    );
+    assert_eq!(tmux_segment(&[snapshot(Status::Closed)]), "");
+}
+
+#[test]
+fn test_cache_is_reused_while_fresh() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let path = dir.path().join("status");
+    assert_eq!(read_cached(&path, Duration::from_secs(5)), None);
+
+    write_cache(&path, "#[fg=green]▶1#[default]").expect("cache should be written");
+    assert_eq!(
+        read_cached(&path, Duration::from_secs(60)).as_deref(),
+        Some("#[fg=green]▶1#[default]")
+    );
+    assert_eq!(read_cached(&path, Duration::ZERO), None);
+}
+
+#[test]
+fn test_cache_path_depends_on_socket() {
+    assert_ne!(
+        cache_path(Path::new("/tmp/a.sock")),
+        cache_path(Path::new("/tmp/b.sock"))
+    );
+}
@@ -213,6 +213,10 @@ const MAX_RETRIES: u32 = 10;
 /// }
 /// ```
 pub async fn connect_with_lazy_start(socket_path: &Path) -> ClientResult<Client> {
+    // Never talk to, or start, a daemon in a directory another user controls
+    crate::config::xdg::check_socket_dir(socket_path)
+        .map_err(|e| Box::new(ClientError::ConnectionFailed(e)))?;
+
     // Try to connect first (daemon might already be running)
     match UnixStream::connect(socket_path).await {
         Ok(stream) => {
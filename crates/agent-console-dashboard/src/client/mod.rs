@@ -43,6 +43,11 @@ pub mod connection;
 pub use connection::{connect_with_lazy_start, Client, ClientError};
 
 use std::error::Error;
+use std::path::Path;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::{IpcCommand, IpcResponse};
 
 /// Result type alias for client operations.
 ///
@@ -50,3 +55,23 @@ use std::error::Error;
 /// to be safely passed across thread boundaries, which is essential
 /// for async operations in multi-threaded runtimes.
 pub type ClientResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Sends one command over a fresh connection (starting the daemon if needed)
+/// and returns the daemon's response.
+///
+/// A response with `ok: false` is returned as-is; errors are connection or
+/// protocol failures.
+pub async fn request(socket_path: &Path, command: &IpcCommand) -> ClientResult<IpcResponse> {
+    let client = connect_with_lazy_start(socket_path).await?;
+    let (reader, mut writer) = client.into_stream().into_split();
+    let mut reader = BufReader::new(reader);
+
+    let json = serde_json::to_string(command)?;
+    writer.write_all(json.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    Ok(serde_json::from_str(line.trim())?)
+}
@@ -0,0 +1,75 @@
+//! Generic agent hook implementation.
+//!
+//! Handles the `agent-hook` command, which reports a lifecycle event of any
+//! agent with an adapter: a built-in one, or one declared under
+//! `[[agents.custom]]`:
+//!
+//! ```sh
+//! acd agent-hook --agent devbot --cwd "$PWD" <session-id> done
+//! ```
+//!
+//! The adapter maps the event to a status; events it does not know leave the
+//! session unchanged.
+
+use super::hook::{send_hook_command, MAX_LAST_MESSAGE_CHARS};
+use agent_console_dashboard::agents::{AgentAdapter, AgentRegistry};
+use agent_console_dashboard::config::loader::ConfigLoader;
+use agent_console_dashboard::{AgentType, IpcCommand, IpcCommandKind};
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Builds the SET command for `event` of session `session_id`, or `None` if
+/// `agent` does not map the event to a status.
+pub(crate) fn agent_set_command(
+    agent: &dyn AgentAdapter,
+    session_id: &str,
+    event: &str,
+    cwd: Option<&Path>,
+    message: Option<&str>,
+) -> Option<IpcCommand> {
+    let status = agent.status_for_event(event)?;
+    let mut cmd = IpcCommand::new(IpcCommandKind::Set, Some(session_id));
+    cmd.status = Some(status.to_string());
+    cmd.working_dir = cwd.map(|dir| dir.display().to_string());
+    cmd.last_message = message
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(|text| text.chars().take(MAX_LAST_MESSAGE_CHARS).collect());
+    cmd.agent_type = Some(agent.agent_type().to_string());
+    Some(cmd)
+}
+
+/// Reports `event` of an `agent` session to the daemon.
+///
+/// Unknown agent names exit with 2; unknown events are only noted on stderr.
+pub(crate) fn run_agent_hook_command(
+    socket: &Path,
+    agent: &str,
+    session_id: &str,
+    event: &str,
+    cwd: Option<&Path>,
+    message: Option<&str>,
+) -> ExitCode {
+    let agent_type: AgentType = match agent.parse() {
+        Ok(agent_type) => agent_type,
+        Err(e) => {
+            eprintln!("acd agent-hook: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let registry = ConfigLoader::load_default()
+        .map(|config| AgentRegistry::from_config(&config.agents))
+        .unwrap_or_default();
+    let adapter = registry.get(&agent_type);
+    let Some(cmd) = agent_set_command(adapter.as_ref(), session_id, event, cwd, message) else {
+        eprintln!(
+            "acd agent-hook: {} has no status for event '{}'",
+            adapter.display_name(),
+            event
+        );
+        return ExitCode::SUCCESS;
+    };
+
+    send_hook_command(socket, &cmd, "agent-hook");
+    ExitCode::SUCCESS
+}
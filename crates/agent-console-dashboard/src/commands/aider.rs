@@ -0,0 +1,223 @@
+//! Aider wrapper and hook implementations.
+//!
+//! Aider has no lifecycle hooks, so `acd aider [ARGS]...` runs it as a child
+//! process and reports its state:
+//!
+//! - waiting for input - Aider's `--notifications-command` runs
+//!   `acd aider-hook <id>`, which sets attention
+//! - working - a new `#### ` prompt line in the chat history file
+//! - closed - Aider exited
+//!
+//! Aider has no session IDs either; the wrapper makes one up per run, and a
+//! closed session resurrects with `aider --restore-chat-history`.
+
+use super::hook::send_hook_command;
+use agent_console_dashboard::{client, AgentType, IpcCommand, IpcCommandKind, Status};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::signal::unix::{signal, SignalKind};
+
+/// How often the chat history file is checked for new prompts.
+const HISTORY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Aider's default chat history file name.
+const DEFAULT_HISTORY_FILE: &str = ".aider.chat.history.md";
+
+/// Returns a session ID for a new Aider run.
+pub(crate) fn aider_session_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!("aider-{:x}-{}", nanos, std::process::id())
+}
+
+/// Returns the chat history file Aider writes for `args` started in `cwd`.
+///
+/// Honours `--chat-history-file`; the default file lives in the root of the
+/// enclosing git repository, else in `cwd`.
+pub(crate) fn chat_history_path(args: &[String], cwd: &Path) -> PathBuf {
+    let mut explicit = None;
+    for (i, arg) in args.iter().enumerate() {
+        if let Some(path) = arg.strip_prefix("--chat-history-file=") {
+            explicit = Some(path.to_string());
+        } else if arg == "--chat-history-file" {
+            explicit = args.get(i + 1).cloned();
+        }
+    }
+    if let Some(path) = explicit {
+        return cwd.join(path);
+    }
+    let root = cwd
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(cwd);
+    root.join(DEFAULT_HISTORY_FILE)
+}
+
+/// Returns true if text appended to the chat history contains a user prompt.
+///
+/// Aider records each prompt as a `#### ` line before it starts working.
+pub(crate) fn has_new_prompt(appended: &str) -> bool {
+    appended.lines().any(|line| line.starts_with("#### "))
+}
+
+/// Quotes for `sh`, which runs Aider notification commands and menu bar click
+/// scripts.
+pub(crate) use agent_console_dashboard::integrations::shell_quote;
+
+/// Arguments for the Aider child process: the user's `args` followed by the
+/// notification settings that call back into `acd aider-hook`.
+pub(crate) fn aider_args(
+    acd: &Path,
+    socket: &Path,
+    session_id: &str,
+    args: &[String],
+) -> Vec<String> {
+    let hook = [
+        acd.display().to_string(),
+        "aider-hook".to_string(),
+        "--socket".to_string(),
+        socket.display().to_string(),
+        session_id.to_string(),
+    ]
+    .iter()
+    .map(|part| shell_quote(part))
+    .collect::<Vec<_>>()
+    .join(" ");
+    let mut all = args.to_vec();
+    all.extend([
+        "--notifications".to_string(),
+        "--notifications-command".to_string(),
+        hook,
+    ]);
+    all
+}
+
+/// Builds a SET command for an Aider session.
+pub(crate) fn aider_set_command(
+    session_id: &str,
+    status: Status,
+    cwd: Option<&Path>,
+) -> IpcCommand {
+    let mut cmd = IpcCommand::new(IpcCommandKind::Set, Some(session_id));
+    cmd.status = Some(status.to_string());
+    cmd.working_dir = cwd.map(|dir| dir.display().to_string());
+    cmd.agent_type = Some(AgentType::Aider.to_string());
+    cmd
+}
+
+/// Sends `cmd`, returning the error message on failure.
+async fn report(socket: &Path, cmd: &IpcCommand) -> Result<(), String> {
+    match client::request(socket, cmd).await {
+        Ok(resp) if resp.ok => Ok(()),
+        Ok(resp) => Err(resp.error.unwrap_or_else(|| "unknown error".to_string())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Runs Aider with `args` and reports its session until it exits.
+///
+/// Returns Aider's exit code. Dashboard errors after startup are ignored so
+/// they do not garble Aider's terminal UI.
+pub(crate) fn run_aider_command(socket: &Path, args: &[String]) -> ExitCode {
+    let cwd = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("acd aider: cannot read current directory: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let acd = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("acd"));
+    let session_id = aider_session_id();
+    let history = chat_history_path(args, &cwd);
+
+    let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime for aider");
+    rt.block_on(async {
+        let start = aider_set_command(&session_id, Status::Attention, Some(&cwd));
+        if let Err(e) = report(socket, &start).await {
+            eprintln!("acd aider: session not tracked ({})", e);
+        }
+
+        let mut child = match tokio::process::Command::new("aider")
+            .args(aider_args(&acd, socket, &session_id, args))
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                eprintln!("acd aider: failed to run aider: {}", e);
+                let _ = report(
+                    socket,
+                    &IpcCommand::new(IpcCommandKind::Delete, Some(&session_id)),
+                )
+                .await;
+                return ExitCode::FAILURE;
+            }
+        };
+
+        // Ctrl+C is for Aider (it interrupts the current reply), not for us
+        let mut interrupts =
+            signal(SignalKind::interrupt()).expect("failed to install SIGINT handler");
+        let mut offset = std::fs::metadata(&history).map_or(0, |m| m.len());
+        let mut poll = tokio::time::interval(HISTORY_POLL_INTERVAL);
+        let status = loop {
+            tokio::select! {
+                status = child.wait() => break status,
+                _ = interrupts.recv() => {}
+                _ = poll.tick() => {
+                    if let Some(appended) = read_appended(&history, &mut offset) {
+                        if has_new_prompt(&appended) {
+                            let working = aider_set_command(&session_id, Status::Working, None);
+                            let _ = report(socket, &working).await;
+                        }
+                    }
+                }
+            }
+        };
+
+        let _ = report(
+            socket,
+            &IpcCommand::new(IpcCommandKind::Rm, Some(&session_id)),
+        )
+        .await;
+        match status {
+            Ok(status) => status
+                .code()
+                .map_or(ExitCode::FAILURE, |code| ExitCode::from(code as u8)),
+            Err(e) => {
+                eprintln!("acd aider: failed to wait for aider: {}", e);
+                ExitCode::FAILURE
+            }
+        }
+    })
+}
+
+/// Returns text appended to `path` since `offset` and advances `offset`.
+///
+/// A file that shrank (truncated or replaced) is read from the start.
+fn read_appended(path: &Path, offset: &mut u64) -> Option<String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    if len < *offset {
+        *offset = 0;
+    }
+    if len == *offset {
+        return None;
+    }
+    file.seek(SeekFrom::Start(*offset)).ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    *offset += bytes.len() as u64;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Marks an Aider session as waiting for input. Run by Aider as its
+/// notifications command.
+pub(crate) fn run_aider_hook_command(socket: &Path, session_id: &str) -> ExitCode {
+    let cmd = aider_set_command(session_id, Status::Attention, None);
+    send_hook_command(socket, &cmd, "aider-hook");
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,94 @@
+//! Codex CLI notify hook implementation.
+//!
+//! Handles the `codex-hook` command. Codex runs its `notify` program with a
+//! JSON payload as the last argument after every agent turn:
+//!
+//! ```toml
+//! # ~/.codex/config.toml
+//! notify = ["acd", "codex-hook"]
+//! ```
+//!
+//! Each `agent-turn-complete` event marks the Codex thread as needing
+//! attention, with the turn's final message as the preview.
+
+use super::hook::{send_hook_command, MAX_LAST_MESSAGE_CHARS};
+use agent_console_dashboard::{AgentType, IpcCommand, IpcCommandKind, Status};
+use std::path::Path;
+use std::process::ExitCode;
+
+/// JSON payload Codex passes to its `notify` program.
+///
+/// Only fields we need are declared; unknown fields are silently ignored.
+/// Older Codex versions omit `thread-id` and `cwd`.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CodexNotification {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub last_assistant_message: Option<String>,
+}
+
+/// Builds the SET command for a notification, or `None` for events that do
+/// not change the session status.
+///
+/// `fallback_cwd` is used when the payload has no `cwd` (Codex runs the hook
+/// in the session's directory).
+pub(crate) fn codex_set_command(
+    notification: &CodexNotification,
+    fallback_cwd: Option<&Path>,
+) -> Result<Option<IpcCommand>, String> {
+    let status = match notification.event_type.as_str() {
+        "agent-turn-complete" => Status::Attention,
+        _ => return Ok(None),
+    };
+    let thread_id = notification
+        .thread_id
+        .as_deref()
+        .ok_or("payload has no thread-id (Codex CLI too old?)")?;
+
+    let mut cmd = IpcCommand::new(IpcCommandKind::Set, Some(thread_id));
+    cmd.status = Some(status.to_string());
+    cmd.working_dir = notification
+        .cwd
+        .clone()
+        .or_else(|| fallback_cwd.map(|dir| dir.display().to_string()));
+    cmd.last_message = notification
+        .last_assistant_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(|text| text.chars().take(MAX_LAST_MESSAGE_CHARS).collect());
+    cmd.agent_type = Some(AgentType::Codex.to_string());
+    Ok(Some(cmd))
+}
+
+/// Parses the notify payload and sends the resulting SET command.
+///
+/// Codex ignores the exit status of its notify program, so failures are only
+/// reported on stderr; malformed payloads exit with 2 for manual testing.
+pub(crate) fn run_codex_hook_command(socket: &Path, payload: &str) -> ExitCode {
+    let notification: CodexNotification = match serde_json::from_str(payload) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("acd codex-hook: failed to parse notify payload: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let cwd = std::env::current_dir().ok();
+    let cmd = match codex_set_command(&notification, cwd.as_deref()) {
+        Ok(Some(cmd)) => cmd,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("acd codex-hook: {}", e);
+            return ExitCode::SUCCESS;
+        }
+    };
+
+    send_hook_command(socket, &cmd, "codex-hook");
+    ExitCode::SUCCESS
+}
@@ -17,8 +17,7 @@ use std::process::ExitCode;
 /// daemon. See the "Reusing existing daemon... no new daemon started" message in
 /// `acd daemon start`.
 pub(crate) fn is_daemon_running(socket: &std::path::Path) -> bool {
-    use std::os::unix::net::UnixStream;
-    UnixStream::connect(socket).is_ok()
+    agent_console_dashboard::config::xdg::connect(socket).is_ok()
 }
 
 /// Connects to daemon, sends STOP command, handles confirmation, and triggers shutdown.
@@ -26,10 +25,13 @@ pub(crate) fn is_daemon_running(socket: &std::path::Path) -> bool {
 /// If `force` is true, skips the confirmation prompt and stops the daemon immediately.
 pub(crate) fn run_daemon_stop_command(socket: &std::path::Path, force: bool) -> ExitCode {
     use std::io::{self, BufRead, BufReader, Write};
-    use std::os::unix::net::UnixStream;
 
-    let stream = match UnixStream::connect(socket) {
+    let stream = match agent_console_dashboard::config::xdg::connect(socket) {
         Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
         Err(_) => {
             eprintln!("Error: daemon not running (cannot connect to {:?})", socket);
             return ExitCode::FAILURE;
@@ -48,6 +50,10 @@ pub(crate) fn run_daemon_stop_command(socket: &std::path::Path, force: bool) ->
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let json = serde_json::to_string(&cmd).expect("failed to serialize STOP command");
     let line = format!("{}\n", json);
@@ -107,6 +113,10 @@ pub(crate) fn run_daemon_stop_command(socket: &std::path::Path, force: bool) ->
                             working_dir: None,
                             confirmed: Some(true),
                             priority: None,
+                            display_name: None,
+                            last_message: None,
+                            terminal_pane: None,
+                            agent_type: None,
                         };
                         let json_confirmed = serde_json::to_string(&cmd_confirmed)
                             .expect("failed to serialize STOP command");
@@ -172,10 +182,15 @@ pub(crate) fn run_daemon_stop_command(socket: &std::path::Path, force: bool) ->
 /// Opens the config file in the user's editor ($VISUAL or $EDITOR).
 ///
 /// Backs up the config before opening the editor. Returns error if config does not exist.
+///
+/// After the editor exits, the file is validated. If it is invalid, the errors are
+/// shown and the user can edit it again, revert it to the backup, or keep it as is.
 pub(crate) fn run_config_edit_command(
 ) -> Result<(), agent_console_dashboard::config::error::ConfigError> {
+    use agent_console_dashboard::config::loader::ConfigLoader;
     use agent_console_dashboard::config::{default, xdg};
     use std::fs;
+    use std::io::{self, Write};
     use std::path::PathBuf;
     use std::process::Command;
 
@@ -208,33 +223,77 @@ pub(crate) fn run_config_edit_command(
     })?;
 
     println!("Config backed up to: {}", backup_path.display());
-    println!("Opening {} in editor...", config_path.display());
-
-    // Open editor via the shell so that EDITOR values like `code-insiders --wait`
-    // or `vim -u NONE` are word-split correctly.  Direct Command::new() would
-    // look for a binary whose name is the entire EDITOR string (no word splitting
-    // outside of a shell).
-    let status = Command::new("sh")
-        .arg("-c")
-        .arg(format!("{} \"$1\"", &editor))
-        .arg("--") // $0 (argv[0] for the shell)
-        .arg(&config_path) // $1 — the file to edit
-        .status()
-        .map_err(
-            |e| agent_console_dashboard::config::error::ConfigError::EditorError {
-                editor: editor.clone(),
-                source: e,
-            },
-        )?;
 
-    if !status.success() {
-        return Err(
-            agent_console_dashboard::config::error::ConfigError::EditorFailed {
-                editor,
-                code: status.code(),
-            },
-        );
-    }
+    loop {
+        println!("Opening {} in editor...", config_path.display());
+
+        // Open editor via the shell so that EDITOR values like `code-insiders --wait`
+        // or `vim -u NONE` are word-split correctly.  Direct Command::new() would
+        // look for a binary whose name is the entire EDITOR string (no word splitting
+        // outside of a shell).
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$1\"", &editor))
+            .arg("--") // $0 (argv[0] for the shell)
+            .arg(&config_path) // $1 — the file to edit
+            .status()
+            .map_err(
+                |e| agent_console_dashboard::config::error::ConfigError::EditorError {
+                    editor: editor.clone(),
+                    source: e,
+                },
+            )?;
 
-    Ok(())
+        if !status.success() {
+            return Err(
+                agent_console_dashboard::config::error::ConfigError::EditorFailed {
+                    editor,
+                    code: status.code(),
+                },
+            );
+        }
+
+        let error = match ConfigLoader::load_from_path(&config_path) {
+            Ok(_) => {
+                println!("Configuration is valid");
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+        eprintln!("Config error: {error}");
+        loop {
+            print!("[e]dit again, [r]evert to backup, or [k]eep anyway? (E/r/k): ");
+            // Unwritable stdout or no input (EOF) keeps the file: there is
+            // nobody to ask
+            if io::stdout().flush().is_err() {
+                return Err(error);
+            }
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+                println!();
+                return Err(error);
+            }
+            match input.trim().to_ascii_lowercase().as_str() {
+                "" | "e" => break,
+                "r" => {
+                    fs::copy(&backup_path, &config_path).map_err(|e| {
+                        agent_console_dashboard::config::error::ConfigError::WriteError {
+                            path: config_path.clone(),
+                            source: e,
+                        }
+                    })?;
+                    println!("Reverted {} to the backup", config_path.display());
+                    return Ok(());
+                }
+                "k" => {
+                    println!(
+                        "Kept {}; acd reports this error until it is fixed",
+                        config_path.display()
+                    );
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+    }
 }
@@ -0,0 +1,76 @@
+//! Gemini CLI hook implementation.
+//!
+//! Handles the `gemini-hook` command. Gemini CLI hooks receive a JSON payload
+//! on stdin naming the lifecycle event, so one command registered for every
+//! event in `~/.gemini/settings.json` maps them all to session statuses:
+//!
+//! - `SessionStart`, `AfterAgent` - attention (waiting for a prompt)
+//! - `BeforeAgent` - working
+//! - `Notification` (tool permission) - question
+//! - `SessionEnd` - closed
+//!
+//! Sessions are keyed by Gemini's session ID, so a closed session resurrects
+//! with `gemini --resume <id>` in its working directory.
+
+use super::hook::{send_hook_command, MAX_LAST_MESSAGE_CHARS};
+use agent_console_dashboard::{AgentType, IpcCommand, IpcCommandKind, Status};
+use std::path::Path;
+use std::process::ExitCode;
+
+/// JSON payload from Gemini CLI hook stdin.
+///
+/// Only fields we need are declared; unknown fields are silently ignored.
+#[derive(serde::Deserialize)]
+pub(crate) struct GeminiHookInput {
+    pub session_id: String,
+    pub cwd: String,
+    pub hook_event_name: String,
+    /// Kind of `Notification` event (e.g. `ToolPermission`).
+    #[serde(default)]
+    pub notification_type: Option<String>,
+    /// The agent's reply, on `AfterAgent`.
+    #[serde(default)]
+    pub prompt_response: Option<String>,
+}
+
+/// Returns the status a hook event moves the session to, or `None` for events
+/// that do not change it (model and tool events).
+pub(crate) fn gemini_status(input: &GeminiHookInput) -> Option<Status> {
+    match input.hook_event_name.as_str() {
+        "SessionStart" | "AfterAgent" => Some(Status::Attention),
+        "BeforeAgent" => Some(Status::Working),
+        "Notification" if input.notification_type.as_deref() == Some("ToolPermission") => {
+            Some(Status::Question)
+        }
+        "SessionEnd" => Some(Status::Closed),
+        _ => None,
+    }
+}
+
+/// Builds the SET command for a hook event, or `None` if the event does not
+/// change the session status.
+pub(crate) fn gemini_set_command(input: &GeminiHookInput) -> Option<IpcCommand> {
+    let status = gemini_status(input)?;
+    let mut cmd = IpcCommand::new(IpcCommandKind::Set, Some(&input.session_id));
+    cmd.status = Some(status.to_string());
+    cmd.working_dir = Some(input.cwd.clone());
+    cmd.last_message = input
+        .prompt_response
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(|text| text.chars().take(MAX_LAST_MESSAGE_CHARS).collect());
+    cmd.agent_type = Some(AgentType::GeminiCli.to_string());
+    Some(cmd)
+}
+
+/// Sends the SET command for a hook payload.
+///
+/// Always exits 0 so the dashboard never blocks Gemini CLI; failures are
+/// reported on stderr.
+pub(crate) fn run_gemini_hook_command(socket: &Path, input: &GeminiHookInput) -> ExitCode {
+    if let Some(cmd) = gemini_set_command(input) {
+        send_hook_command(socket, &cmd, "gemini-hook");
+    }
+    ExitCode::SUCCESS
+}
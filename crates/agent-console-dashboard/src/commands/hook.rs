@@ -1,13 +1,25 @@
 //! Claude Code hook command implementations.
 //!
 //! Handles the `claude-hook` command that receives JSON from stdin and communicates
-//! with the daemon to update session status.
+//! with the daemon to update session status. Also holds helpers shared with the
+//! hooks of other agents.
 
 use agent_console_dashboard::{
-    client::connect_with_lazy_start, IpcCommand, IpcCommandKind, Status, IPC_VERSION,
+    client::{self, connect_with_lazy_start},
+    IpcCommand, IpcCommandKind, Status, TerminalPane, IPC_VERSION,
 };
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
 use std::process::ExitCode;
 
+/// Bytes read from the end of a transcript when looking for the last
+/// assistant message. Transcripts grow to many megabytes; the latest turn is
+/// near the end.
+const TRANSCRIPT_TAIL_BYTES: u64 = 256 * 1024;
+
+/// Maximum characters of the assistant message sent to the daemon.
+pub(crate) const MAX_LAST_MESSAGE_CHARS: usize = 1000;
+
 /// JSON payload from Claude Code hook stdin.
 ///
 /// Only fields we need are declared; unknown fields are silently ignored
@@ -16,6 +28,58 @@ use std::process::ExitCode;
 pub(crate) struct HookInput {
     pub session_id: String,
     pub cwd: String,
+    /// Path to the session's JSONL transcript, used for the last message preview.
+    #[serde(default)]
+    pub transcript_path: Option<String>,
+}
+
+/// Returns the text of the most recent assistant message in a JSONL transcript.
+///
+/// Only the last [`TRANSCRIPT_TAIL_BYTES`] of the file are read. Assistant
+/// entries without text (tool calls only) are skipped. The text is trimmed and
+/// capped at [`MAX_LAST_MESSAGE_CHARS`]. Returns `None` if the file cannot be
+/// read or holds no assistant text.
+pub(crate) fn last_assistant_message(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(TRANSCRIPT_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    let tail = String::from_utf8_lossy(&bytes);
+
+    // When starting mid-file, the first line is partial
+    let lines = tail.lines().skip(usize::from(start > 0));
+    let text = lines
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .find_map(assistant_text)?;
+    Some(text.chars().take(MAX_LAST_MESSAGE_CHARS).collect())
+}
+
+/// Extracts the trimmed text of an assistant transcript entry, if any.
+///
+/// Content is either a plain string or an array of blocks, of which only
+/// `text` blocks are kept.
+fn assistant_text(line: &str) -> Option<String> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    if entry.get("type")?.as_str()? != "assistant" {
+        return None;
+    }
+    let content = entry.get("message")?.get("content")?;
+    let text = match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
 }
 
 /// Validates HookInput fields. Returns warnings for invalid fields.
@@ -55,6 +119,10 @@ pub(crate) fn validate_hook_input(input: &HookInput) -> Vec<String> {
 
 /// Connects to daemon via lazy-start (spawning if needed), sends SET command as JSON.
 ///
+/// When the payload names a transcript, the latest assistant message is sent
+/// along for the dashboard's preview, and inside tmux or Zellij so is the
+/// pane the hook runs in (`TMUX_PANE` / `ZELLIJ_PANE_ID`).
+///
 /// Exit codes per Claude Code hook spec:
 /// - 0: success (outputs `{"continue": true}` on stdout)
 ///
@@ -88,6 +156,11 @@ pub(crate) async fn run_claude_hook_async(
         }
     };
 
+    let last_message = input
+        .transcript_path
+        .as_deref()
+        .and_then(|path| last_assistant_message(Path::new(path)));
+
     let stream = client.into_stream();
     let (reader, mut writer) = stream.into_split();
     let mut reader = BufReader::new(reader);
@@ -100,6 +173,10 @@ pub(crate) async fn run_claude_hook_async(
         working_dir: Some(input.cwd.clone()),
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message,
+        agent_type: None,
+        terminal_pane: TerminalPane::from_env(|name| std::env::var(name).ok()),
     };
     let cmd_json = serde_json::to_string(&cmd).expect("failed to serialize SET command");
     let cmd_line = format!("{}\n", cmd_json);
@@ -157,3 +234,23 @@ pub(crate) async fn run_claude_hook_async(
     }
     ExitCode::SUCCESS
 }
+
+/// Sends a SET command from an agent hook other than Claude Code's, with the
+/// multiplexer pane the hook runs in.
+///
+/// Failures are reported on stderr as `acd <hook_name>: ...` and never
+/// returned, so a dashboard problem does not interrupt the agent.
+pub(crate) fn send_hook_command(socket: &Path, cmd: &IpcCommand, hook_name: &str) {
+    let mut cmd = cmd.clone();
+    cmd.terminal_pane = TerminalPane::from_env(|name| std::env::var(name).ok());
+    let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime for hook");
+    match rt.block_on(client::request(socket, &cmd)) {
+        Ok(resp) if resp.ok => {}
+        Ok(resp) => eprintln!(
+            "acd {}: daemon error: {}",
+            hook_name,
+            resp.error.unwrap_or_else(|| "unknown error".to_string())
+        ),
+        Err(e) => eprintln!("acd {}: acd daemon not reachable ({})", hook_name, e),
+    }
+}
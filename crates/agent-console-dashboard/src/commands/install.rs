@@ -85,39 +85,64 @@ pub(crate) fn run_install_command() -> ExitCode {
         return ExitCode::FAILURE;
     }
 
-    // 3. Install each hook
-    let definitions = acd_hook_definitions();
-    let mut installed = 0u32;
+    // 3. Install every hook in one settings write. Hooks that already exist
+    //    (in settings or the registry) are skipped, retrying without them.
+    let mut pending = acd_hook_definitions();
     let mut skipped = 0u32;
     let mut errors = Vec::new();
+    let to_specs = |pending: &[(claude_hooks::HookEvent, &str, Option<String>)]| {
+        pending
+            .iter()
+            .map(|(event, command, matcher)| claude_hooks::HookSpec {
+                event: *event,
+                matcher: matcher.as_deref().map(claude_hooks::Matcher::from_settings),
+                handler: claude_hooks::HookHandler::Command(claude_hooks::CommandHandler {
+                    command: command.to_string(),
+                    timeout: Some(10),
+                    r#async: None,
+                    status_message: None,
+                }),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Other tools' hooks on the same events run alongside ours in no fixed
+    // order; warn but install anyway
+    if let Ok(conflicts) =
+        claude_hooks::check_install_conflicts(claude_hooks::Scope::User, &to_specs(&pending), "acd")
+    {
+        for conflict in &conflicts {
+            println!("  Warning: {}", conflict);
+        }
+    }
 
-    for (event, command, matcher) in &definitions {
-        let handler = claude_hooks::HookHandler {
-            r#type: "command".to_string(),
-            command: command.to_string(),
-            timeout: Some(10),
-            r#async: None,
-            status_message: None,
-        };
-
-        match claude_hooks::install(*event, handler, matcher.clone(), "acd") {
-            Ok(()) => {
-                installed += 1;
-                let matcher_str = matcher
-                    .as_ref()
-                    .map(|m| format!(" ({})", m))
-                    .unwrap_or_default();
-                println!("  Installed: {:?}{} -> {}", event, matcher_str, command);
-            }
-            Err(claude_hooks::Error::Hook(claude_hooks::HookError::AlreadyExists { .. })) => {
+    loop {
+        match claude_hooks::install_all(claude_hooks::Scope::User, to_specs(&pending), "acd") {
+            Ok(()) => break,
+            Err(claude_hooks::Error::Hook(claude_hooks::HookError::AlreadyExists {
+                event,
+                command,
+            })) => {
                 skipped += 1;
+                pending.retain(|(e, c, _)| !(*e == event && *c == command));
             }
             Err(e) => {
-                errors.push(format!("{:?} -> {}: {}", event, command, e));
+                errors.push(e.to_string());
+                pending.clear();
+                break;
             }
         }
     }
 
+    let installed = pending.len();
+    for (event, command, matcher) in &pending {
+        let matcher_str = matcher
+            .as_ref()
+            .map(|m| format!(" ({})", m))
+            .unwrap_or_default();
+        println!("  Installed: {:?}{} -> {}", event, matcher_str, command);
+    }
+
     // 4. Summary
     println!();
     println!(
@@ -145,45 +170,23 @@ pub(crate) fn run_install_command() -> ExitCode {
 
 /// Remove all ACD-managed hooks from ~/.claude/settings.json.
 pub(crate) fn run_uninstall_command() -> ExitCode {
-    // Step 1: Remove hooks
-    let definitions = acd_hook_definitions();
-    let mut removed = 0u32;
-    let mut skipped = 0u32;
-    let mut errors = Vec::new();
-
-    for (event, command, _matcher) in &definitions {
-        match claude_hooks::uninstall(*event, command) {
-            Ok(()) => {
-                removed += 1;
-                println!("  Removed: {:?} -> {}", event, command);
-            }
-            Err(claude_hooks::Error::Hook(claude_hooks::HookError::NotManaged { .. })) => {
-                skipped += 1;
-            }
-            Err(e) => {
-                errors.push(format!("{:?} -> {}: {}", event, command, e));
-            }
+    // Step 1: Remove every hook acd installed, in one settings write
+    let removed = match claude_hooks::uninstall_all_by(claude_hooks::Scope::User, "acd") {
+        Ok(removed) => removed,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
         }
+    };
+    for entry in &removed {
+        println!("  Removed: {:?} -> {}", entry.event, entry.command);
     }
 
     println!();
-    println!(
-        "Hooks: {} removed, {} not managed, {} errors",
-        removed,
-        skipped,
-        errors.len()
-    );
-
-    if !errors.is_empty() {
-        eprintln!();
-        for err in &errors {
-            eprintln!("  Error: {}", err);
-        }
-        return ExitCode::FAILURE;
-    }
+    println!("Hooks: {} removed", removed.len());
 
     // Step 2: Stop daemon if running
-    let socket_path = agent_console_dashboard::config::xdg::socket_path();
+    let socket_path = agent_console_dashboard::config::xdg::default_socket_path();
     if crate::commands::daemon::is_daemon_running(&socket_path) {
         // Stop with force=true (no confirmation prompt during uninstall)
         let stop_result = crate::commands::daemon::run_daemon_stop_command(&socket_path, true);
@@ -214,7 +217,7 @@ pub(crate) fn run_uninstall_command() -> ExitCode {
         println!("  To remove: rm {}", config_path.display());
     }
 
-    if removed > 0 {
+    if !removed.is_empty() {
         println!();
         println!("You may need to restart Claude Code for changes to take effect.");
     }
@@ -10,7 +10,7 @@ use agent_console_dashboard::{
     format_uptime, DaemonDump, HealthStatus, IpcCommand, IpcCommandKind, IpcResponse,
     SessionSnapshot, IPC_VERSION,
 };
-use std::path::PathBuf;
+use std::path::Path;
 use std::process::ExitCode;
 
 /// Connects to daemon, sends SET command as JSON to update session fields.
@@ -18,14 +18,13 @@ use std::process::ExitCode;
 /// At least one of status, working_dir, or priority should be provided.
 /// If none are provided, prints a warning and returns success.
 pub(crate) fn run_update_command(
-    socket: &PathBuf,
+    socket: &Path,
     session_id: &str,
     status: Option<&str>,
     working_dir: Option<&std::path::Path>,
     priority: Option<u64>,
 ) -> ExitCode {
     use std::io::{BufRead, BufReader, Write};
-    use std::os::unix::net::UnixStream;
 
     // Check if at least one field is provided
     if status.is_none() && working_dir.is_none() && priority.is_none() {
@@ -33,8 +32,12 @@ pub(crate) fn run_update_command(
         return ExitCode::SUCCESS;
     }
 
-    let stream = match UnixStream::connect(socket) {
+    let stream = match agent_console_dashboard::config::xdg::connect(socket) {
         Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
         Err(_) => {
             eprintln!("Error: daemon not running (cannot connect to {:?})", socket);
             return ExitCode::FAILURE;
@@ -54,6 +57,10 @@ pub(crate) fn run_update_command(
         working_dir: wd,
         confirmed: None,
         priority,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let json = serde_json::to_string(&cmd).expect("failed to serialize SET command");
     let line = format!("{}\n", json);
@@ -89,12 +96,15 @@ pub(crate) fn run_update_command(
 ///
 /// On success, prints the deleted SessionSnapshot to stdout as JSON.
 /// On failure, prints error message to stderr.
-pub(crate) fn run_delete_command(socket: &PathBuf, session_id: &str) -> ExitCode {
+pub(crate) fn run_delete_command(socket: &Path, session_id: &str) -> ExitCode {
     use std::io::{BufRead, BufReader, Write};
-    use std::os::unix::net::UnixStream;
 
-    let stream = match UnixStream::connect(socket) {
+    let stream = match agent_console_dashboard::config::xdg::connect(socket) {
         Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
         Err(_) => {
             eprintln!("Error: daemon not running (cannot connect to {:?})", socket);
             return ExitCode::FAILURE;
@@ -112,6 +122,10 @@ pub(crate) fn run_delete_command(socket: &PathBuf, session_id: &str) -> ExitCode
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let json = serde_json::to_string(&cmd).expect("failed to serialize DELETE command");
     let line = format!("{}\n", json);
@@ -166,12 +180,15 @@ pub(crate) fn run_delete_command(socket: &PathBuf, session_id: &str) -> ExitCode
 /// Connects to the daemon socket, sends STATUS as JSON, and displays health info.
 ///
 /// Returns `ExitCode::SUCCESS` if the daemon is running, `ExitCode::FAILURE` if unreachable.
-pub(crate) fn run_status_command(socket: &PathBuf) -> ExitCode {
+pub(crate) fn run_status_command(socket: &Path) -> ExitCode {
     use std::io::{BufRead, BufReader, Write};
-    use std::os::unix::net::UnixStream;
 
-    let stream = match UnixStream::connect(socket) {
+    let stream = match agent_console_dashboard::config::xdg::connect(socket) {
         Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
         Err(_) => {
             println!("Agent Console Daemon");
             println!("  Status:      not running");
@@ -190,6 +207,10 @@ pub(crate) fn run_status_command(socket: &PathBuf) -> ExitCode {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let json = serde_json::to_string(&cmd).expect("failed to serialize STATUS command");
     let line = format!("{}\n", json);
@@ -254,12 +275,15 @@ pub(crate) fn run_status_command(socket: &PathBuf) -> ExitCode {
 /// Connects to the daemon socket, sends DUMP as JSON, and prints raw JSON.
 ///
 /// Returns `ExitCode::SUCCESS` if the daemon responds, `ExitCode::FAILURE` if unreachable.
-pub(crate) fn run_dump_command(socket: &PathBuf) -> ExitCode {
+pub(crate) fn run_dump_command(socket: &Path) -> ExitCode {
     use std::io::{BufRead, BufReader, Write};
-    use std::os::unix::net::UnixStream;
 
-    let stream = match UnixStream::connect(socket) {
+    let stream = match agent_console_dashboard::config::xdg::connect(socket) {
         Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
         Err(_) => {
             eprintln!("Error: daemon not running (cannot connect to {:?})", socket);
             return ExitCode::FAILURE;
@@ -277,6 +301,10 @@ pub(crate) fn run_dump_command(socket: &PathBuf) -> ExitCode {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let json = serde_json::to_string(&cmd).expect("failed to serialize DUMP command");
     let line = format!("{}\n", json);
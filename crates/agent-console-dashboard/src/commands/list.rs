@@ -0,0 +1,185 @@
+//! Session list implementation.
+//!
+//! Handles the `list` command, which prints every session the daemon knows,
+//! open ones by name, then closed ones, most recently closed first:
+//!
+//! - `text` - one line per session: status, name, time in status and ID
+//! - `script-filter` - Alfred Script Filter JSON (also read by Raycast's
+//!   script filter extensions), with the command that resumes each session
+//!   as the item's `arg`; with ⌘ a closed session is resurrected instead
+
+use super::aider::shell_quote;
+use super::prompt_segment::list_sessions;
+use super::statusbar::session_label;
+use agent_console_dashboard::agents::AgentRegistry;
+use agent_console_dashboard::config::loader::ConfigLoader;
+use agent_console_dashboard::integrations::session_from_snapshot;
+use agent_console_dashboard::tui::views::dashboard::format_duration_secs;
+use agent_console_dashboard::SessionSnapshot;
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Longest the daemon may take to answer a LIST.
+const LIST_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Session list output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ListFormat {
+    /// One line per session.
+    Text,
+    /// Alfred/Raycast Script Filter JSON.
+    ScriptFilter,
+}
+
+/// Open sessions by name, then closed ones, most recently closed first.
+fn list_order(sessions: &[SessionSnapshot]) -> Vec<&SessionSnapshot> {
+    let mut open: Vec<_> = sessions.iter().filter(|s| !s.closed).collect();
+    open.sort_by(|a, b| session_label(a).cmp(session_label(b)));
+    let mut closed: Vec<_> = sessions.iter().filter(|s| s.closed).collect();
+    closed.sort_by_key(|s| s.elapsed_seconds);
+    open.extend(closed);
+    open
+}
+
+/// Shell command that resumes `snapshot` in its working directory, or `None`
+/// if its agent cannot resume sessions.
+pub(crate) fn resume_shell_command(
+    snapshot: &SessionSnapshot,
+    agents: &AgentRegistry,
+) -> Option<String> {
+    let session = session_from_snapshot(snapshot);
+    let resume = agents
+        .get(&session.agent_type)
+        .resume_command(&session)
+        .filter(|resume| !resume.is_empty())?;
+    let command = resume
+        .iter()
+        .map(|part| shell_quote(part))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(match &snapshot.working_dir {
+        Some(dir) => format!("cd {} && {}", shell_quote(dir), command),
+        None => command,
+    })
+}
+
+/// Builds the text listing: one line per session, nothing when there are
+/// none.
+pub(crate) fn text_output(sessions: &[SessionSnapshot]) -> String {
+    list_order(sessions)
+        .into_iter()
+        .map(|s| {
+            format!(
+                "{:<9}  {:<24}  {:>10}  {}\n",
+                s.status,
+                session_label(s),
+                format_duration_secs(s.elapsed_seconds),
+                s.session_id
+            )
+        })
+        .collect()
+}
+
+/// Single non-actionable Script Filter item.
+fn message_item(title: &str) -> Value {
+    json!({ "items": [{ "title": title, "valid": false }] })
+}
+
+/// Builds Script Filter JSON for `sessions`, or for a daemon that cannot be
+/// reached when `None`. Resurrect commands run the acd binary `acd` against
+/// `socket`.
+pub(crate) fn script_filter_output(
+    sessions: Option<&[SessionSnapshot]>,
+    agents: &AgentRegistry,
+    acd: &Path,
+    socket: &Path,
+) -> String {
+    let Some(sessions) = sessions else {
+        return message_item("acd daemon is not running").to_string();
+    };
+    if sessions.is_empty() {
+        return message_item("No sessions").to_string();
+    }
+    let items: Vec<Value> = list_order(sessions)
+        .into_iter()
+        .map(|s| {
+            let label = session_label(s);
+            let dir = s.working_dir.as_deref().unwrap_or("");
+            let resume = resume_shell_command(s, agents);
+            let mut item = json!({
+                "uid": s.session_id,
+                "title": label,
+                "subtitle": format!(
+                    "{} for {} · {}",
+                    s.status,
+                    format_duration_secs(s.elapsed_seconds),
+                    dir
+                ),
+                "arg": resume.clone().unwrap_or_default(),
+                "valid": resume.is_some(),
+                "autocomplete": label,
+                "match": format!("{} {} {}", label, s.status, dir),
+                "variables": {
+                    "session_id": s.session_id,
+                    "status": s.status,
+                    "working_dir": dir,
+                },
+            });
+            if s.closed {
+                let resurrect = [
+                    acd.display().to_string(),
+                    "resurrect".to_string(),
+                    "--exec".to_string(),
+                    "--socket".to_string(),
+                    socket.display().to_string(),
+                    s.session_id.clone(),
+                ]
+                .iter()
+                .map(|part| shell_quote(part))
+                .collect::<Vec<_>>()
+                .join(" ");
+                item["mods"] = json!({
+                    "cmd": {
+                        "arg": resurrect,
+                        "valid": true,
+                        "subtitle": "Reopen on the dashboard and resume",
+                    }
+                });
+            }
+            item
+        })
+        .collect();
+    json!({ "items": items }).to_string()
+}
+
+/// Prints every session in `format`.
+///
+/// The text format fails when the daemon cannot be reached; the Script
+/// Filter format shows it as an item instead.
+pub(crate) fn run_list_command(socket: &Path, format: ListFormat) -> ExitCode {
+    let sessions = list_sessions(socket, LIST_TIMEOUT);
+    match format {
+        ListFormat::Text => {
+            let Some(sessions) = sessions else {
+                eprintln!("Error: daemon not running (cannot connect to {:?})", socket);
+                return ExitCode::FAILURE;
+            };
+            print!("{}", text_output(&sessions));
+        }
+        ListFormat::ScriptFilter => {
+            let acd = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("acd"));
+            let agents = ConfigLoader::load_default().map_or_else(
+                |_| AgentRegistry::builtin(),
+                |config| AgentRegistry::from_config(&config.agents),
+            );
+            println!(
+                "{}",
+                script_filter_output(sessions.as_deref(), &agents, &acd, socket)
+            );
+        }
+    }
+    ExitCode::SUCCESS
+}
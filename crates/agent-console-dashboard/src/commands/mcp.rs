@@ -0,0 +1,37 @@
+//! MCP server command implementation.
+//!
+//! `acd mcp` serves the Model Context Protocol on stdio for MCP clients such
+//! as Claude Code. See `agent_console_dashboard::mcp`.
+
+use agent_console_dashboard::agents::AgentRegistry;
+use agent_console_dashboard::config::loader::ConfigLoader;
+use agent_console_dashboard::integrations;
+use agent_console_dashboard::mcp::McpServer;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Runs the MCP server on stdin/stdout until stdin closes.
+///
+/// Resurrected sessions open in the terminal backend detected from this
+/// process's environment (the MCP client's terminal), honouring
+/// `[integrations]` from the config file; custom agents resume with the
+/// commands from `[[agents.custom]]`.
+pub(crate) fn run_mcp_command(socket: &Path) -> ExitCode {
+    let config = ConfigLoader::load_default().ok();
+    let terminal = config
+        .as_ref()
+        .and_then(|config| integrations::detect(&config.integrations));
+    let agents = config.map_or_else(AgentRegistry::builtin, |config| {
+        AgentRegistry::from_config(&config.agents)
+    });
+    let server = McpServer::new(socket.to_path_buf(), terminal).with_agents(agents);
+
+    let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime for MCP");
+    match rt.block_on(server.serve_stdio()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("acd mcp: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! macOS menu bar output implementation.
+//!
+//! Handles the `menubar` command, which prints the active sessions for a menu
+//! bar tool, with a dropdown listing every session; closed sessions resurrect
+//! on click (`acd resurrect --exec`, in a Terminal window):
+//!
+//! - `xbar` - xbar/SwiftBar plugin output (title, `---`, menu items)
+//! - `sketchybar` - `sketchybar` commands for a plugin script to `eval`, which
+//!   update the `acd` item and rebuild its popup
+
+use super::aider::shell_quote;
+use super::prompt_segment::{is_active, list_sessions, prompt_segment};
+use super::statusbar::{class_color, session_label, worst_class};
+use agent_console_dashboard::SessionSnapshot;
+use clap::ValueEnum;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Longest the daemon may take to answer a LIST.
+const LIST_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Most closed sessions listed, most recently closed first.
+const MAX_CLOSED: usize = 10;
+
+/// SketchyBar item the output updates.
+const SKETCHYBAR_ITEM: &str = "acd";
+
+/// Menu bar tool to print for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum MenubarFormat {
+    /// SketchyBar commands.
+    Sketchybar,
+    /// xbar (or SwiftBar) plugin output.
+    Xbar,
+}
+
+/// Sessions of the dropdown: active ones by name, then the most recently
+/// closed ones.
+fn menu_sessions(sessions: &[SessionSnapshot]) -> (Vec<&SessionSnapshot>, Vec<&SessionSnapshot>) {
+    let mut active: Vec<_> = sessions.iter().filter(|s| is_active(s)).collect();
+    active.sort_by(|a, b| session_label(a).cmp(session_label(b)));
+    let mut closed: Vec<_> = sessions.iter().filter(|s| s.closed).collect();
+    closed.sort_by_key(|s| s.elapsed_seconds);
+    closed.truncate(MAX_CLOSED);
+    (active, closed)
+}
+
+/// Title text: the session counts, or `acd` when none is active.
+fn title(sessions: &[SessionSnapshot]) -> String {
+    let text = prompt_segment(sessions, false);
+    if text.is_empty() {
+        "acd".to_string()
+    } else {
+        text
+    }
+}
+
+/// Quotes an xbar parameter value if it contains spaces or quotes.
+fn xbar_param(value: &str) -> String {
+    if value.contains([' ', '"']) {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Builds xbar plugin output for `sessions`, or for a daemon that cannot be
+/// reached when `None`. Clicks run the acd binary `acd` against `socket`.
+pub(crate) fn xbar_output(
+    sessions: Option<&[SessionSnapshot]>,
+    acd: &Path,
+    socket: &Path,
+) -> String {
+    let Some(sessions) = sessions else {
+        return "acd\n---\nacd daemon is not running\n---\nRefresh | refresh=true\n".to_string();
+    };
+    // `|` separates an item from its parameters
+    let label = |s: &SessionSnapshot| session_label(s).replace('|', "¦");
+
+    let mut out = title(sessions);
+    if let Some(color) = class_color(worst_class(sessions)) {
+        out.push_str(&format!(" | color={}", color));
+    }
+    out.push_str("\n---\n");
+    let (active, closed) = menu_sessions(sessions);
+    for session in &active {
+        out.push_str(&format!("{}: {}", label(session), session.status));
+        if let Some(color) = class_color(&session.status) {
+            out.push_str(&format!(" | color={}", color));
+        }
+        out.push('\n');
+    }
+    if !closed.is_empty() {
+        if !active.is_empty() {
+            out.push_str("---\n");
+        }
+        out.push_str("Resurrect\n");
+        for session in closed {
+            out.push_str(&format!(
+                "--{} | shell={} param1=resurrect param2=--exec param3=--socket param4={} param5={} \
+                 terminal=true refresh=true\n",
+                label(session),
+                xbar_param(&acd.display().to_string()),
+                xbar_param(&socket.display().to_string()),
+                xbar_param(&session.session_id),
+            ));
+        }
+    }
+    out.push_str("---\nRefresh | refresh=true\n");
+    out
+}
+
+/// `sketchybar` color (`0xAARRGGBB`) for a `#rrggbb` color.
+fn sketchybar_color(hex: &str) -> String {
+    format!("0xff{}", hex.trim_start_matches('#'))
+}
+
+/// Shell command that opens a Terminal window running `acd resurrect --exec`.
+fn resurrect_in_terminal(acd: &Path, socket: &Path, session_id: &str) -> String {
+    let command = [
+        acd.display().to_string(),
+        "resurrect".to_string(),
+        "--exec".to_string(),
+        "--socket".to_string(),
+        socket.display().to_string(),
+        session_id.to_string(),
+    ]
+    .iter()
+    .map(|part| shell_quote(part))
+    .collect::<Vec<_>>()
+    .join(" ");
+    let script = format!(
+        "tell application \"Terminal\" to do script \"{}\"",
+        command.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    format!("osascript -e {}", shell_quote(&script))
+}
+
+/// Builds `sketchybar` commands for `sessions`, or for a daemon that cannot
+/// be reached when `None`. Clicks run the acd binary `acd` against `socket`.
+pub(crate) fn sketchybar_output(
+    sessions: Option<&[SessionSnapshot]>,
+    acd: &Path,
+    socket: &Path,
+) -> String {
+    let item = SKETCHYBAR_ITEM;
+    let mut out = format!("sketchybar --remove '/{}\\.session\\..*/'\n", item);
+    let Some(sessions) = sessions else {
+        out.push_str(&format!(
+            "sketchybar --set {} label={}\n",
+            item,
+            shell_quote("acd ✕")
+        ));
+        return out;
+    };
+
+    let color = class_color(worst_class(sessions)).unwrap_or("#ffffff");
+    out.push_str(&format!(
+        "sketchybar --set {} label={} label.color={}\n",
+        item,
+        shell_quote(&title(sessions)),
+        sketchybar_color(color)
+    ));
+    let (active, closed) = menu_sessions(sessions);
+    for (i, session) in active.iter().chain(&closed).enumerate() {
+        let name = format!("{}.session.{}", item, i);
+        let mut line = format!(
+            "sketchybar --add item {} popup.{} --set {}",
+            name, item, name
+        );
+        if session.closed {
+            line.push_str(&format!(
+                " label={} click_script={}",
+                shell_quote(&format!("{} (resurrect)", session_label(session))),
+                shell_quote(&resurrect_in_terminal(acd, socket, &session.session_id))
+            ));
+        } else {
+            line.push_str(&format!(
+                " label={}",
+                shell_quote(&format!("{}: {}", session_label(session), session.status))
+            ));
+            if let Some(color) = class_color(&session.status) {
+                line.push_str(&format!(" label.color={}", sketchybar_color(color)));
+            }
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Prints the menu bar output for `format`. Always succeeds; a daemon that
+/// cannot be reached is shown in the output.
+pub(crate) fn run_menubar_command(socket: &Path, format: MenubarFormat) -> ExitCode {
+    let acd = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("acd"));
+    let sessions = list_sessions(socket, LIST_TIMEOUT);
+    let output = match format {
+        MenubarFormat::Xbar => xbar_output(sessions.as_deref(), &acd, socket),
+        MenubarFormat::Sketchybar => sketchybar_output(sessions.as_deref(), &acd, socket),
+    };
+    print!("{}", output);
+    ExitCode::SUCCESS
+}
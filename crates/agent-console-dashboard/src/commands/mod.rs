@@ -1,17 +1,53 @@
 //! Command implementations for the ACD CLI.
 //!
 //! This module contains all command handler functions, organized by domain:
+//! - `agent_hook` - Generic hook for built-in and custom agents
+//! - `aider` - Aider wrapper and notifications hook
+//! - `codex_hook` - Codex CLI notify hook
 //! - `daemon` - Daemon lifecycle commands (start, stop)
+//! - `gemini_hook` - Gemini CLI hook
 //! - `hook` - Claude Code hook integration
 //! - `install` - Hook installation/uninstallation
 //! - `ipc` - IPC commands (update, status, dump)
+//! - `list` - Session list, including Alfred/Raycast Script Filter JSON
+//! - `mcp` - MCP server on stdio
+//! - `menubar` - xbar/SketchyBar menu bar output
+//! - `prompt_segment` - Session summary for shell prompts
+//! - `resurrect` - Reopen and resume a closed session
+//! - `rpc` - JSON-RPC bridge for editor extensions
+//! - `statusbar` - Waybar/Polybar status output
+//! - `tmux_status` - Cached tmux status line
 
+pub(crate) mod agent_hook;
+pub(crate) mod aider;
+pub(crate) mod codex_hook;
 pub(crate) mod daemon;
+pub(crate) mod gemini_hook;
 pub(crate) mod hook;
 pub(crate) mod install;
 pub(crate) mod ipc;
+pub(crate) mod list;
+pub(crate) mod mcp;
+pub(crate) mod menubar;
+pub(crate) mod prompt_segment;
+pub(crate) mod resurrect;
+pub(crate) mod rpc;
+pub(crate) mod statusbar;
+pub(crate) mod tmux_status;
 
+pub(crate) use agent_hook::*;
+pub(crate) use aider::*;
+pub(crate) use codex_hook::*;
 pub(crate) use daemon::*;
+pub(crate) use gemini_hook::*;
 pub(crate) use hook::*;
 pub(crate) use install::*;
 pub(crate) use ipc::*;
+pub(crate) use list::*;
+pub(crate) use mcp::*;
+pub(crate) use menubar::*;
+pub(crate) use prompt_segment::*;
+pub(crate) use resurrect::*;
+pub(crate) use rpc::*;
+pub(crate) use statusbar::*;
+pub(crate) use tmux_status::*;
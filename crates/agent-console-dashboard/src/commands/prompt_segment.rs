@@ -0,0 +1,106 @@
+//! Shell prompt segment implementation.
+//!
+//! Handles the `prompt-segment` command, which prints a compact summary of
+//! active sessions for a shell prompt, e.g. as a Starship custom command:
+//!
+//! ```toml
+//! [custom.acd]
+//! command = "acd prompt-segment"
+//! when = true
+//! ```
+//!
+//! It sends one LIST over the socket with short timeouts and prints nothing
+//! when the daemon is down or slow, so it never delays the prompt.
+
+use agent_console_dashboard::{
+    IpcCommand, IpcCommandKind, IpcResponse, SessionSnapshot, INACTIVE_SESSION_THRESHOLD,
+};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Longest the daemon may take to answer before the segment stays empty.
+const PROMPT_TIMEOUT: Duration = Duration::from_millis(10);
+
+/// Symbol for sessions waiting on the user (attention or question).
+pub(crate) const WAITING_SYMBOL: &str = "⚠";
+
+/// Symbol for working sessions.
+pub(crate) const WORKING_SYMBOL: &str = "▶";
+
+/// Counts the active sessions waiting on the user (attention or question)
+/// and working.
+pub(crate) fn active_counts(sessions: &[SessionSnapshot]) -> (usize, usize) {
+    let (mut waiting, mut working) = (0, 0);
+    for session in sessions.iter().filter(|s| is_active(s)) {
+        match session.status.as_str() {
+            "attention" | "question" => waiting += 1,
+            "working" => working += 1,
+            _ => {}
+        }
+    }
+    (waiting, working)
+}
+
+/// Builds the segment for `sessions`: waiting then working counts, each only
+/// when non-zero (`⚠2 ▶5`). Closed and inactive sessions are not counted.
+/// With `color`, waiting counts are yellow and working counts green.
+pub(crate) fn prompt_segment(sessions: &[SessionSnapshot], color: bool) -> String {
+    let (waiting, working) = active_counts(sessions);
+    let mut parts = Vec::new();
+    for (count, symbol, ansi) in [
+        (waiting, WAITING_SYMBOL, "33"),
+        (working, WORKING_SYMBOL, "32"),
+    ] {
+        if count == 0 {
+            continue;
+        }
+        parts.push(if color {
+            format!("\x1b[{}m{}{}\x1b[0m", ansi, symbol, count)
+        } else {
+            format!("{}{}", symbol, count)
+        });
+    }
+    parts.join(" ")
+}
+
+/// Returns true if `session` is neither closed nor inactive.
+pub(crate) fn is_active(session: &SessionSnapshot) -> bool {
+    !session.closed && session.idle_seconds < INACTIVE_SESSION_THRESHOLD.as_secs()
+}
+
+/// Lists sessions with a single LIST, or `None` on any failure or when the
+/// daemon takes longer than `timeout`.
+pub(crate) fn list_sessions(socket: &Path, timeout: Duration) -> Option<Vec<SessionSnapshot>> {
+    let stream = agent_console_dashboard::config::xdg::connect(socket).ok()?;
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream.set_write_timeout(Some(timeout)).ok()?;
+    let mut writer = stream.try_clone().ok()?;
+    let cmd = IpcCommand::new(IpcCommandKind::List, None);
+    let line = format!("{}\n", serde_json::to_string(&cmd).ok()?);
+    writer.write_all(line.as_bytes()).ok()?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response).ok()?;
+    let resp: IpcResponse = serde_json::from_str(response.trim()).ok()?;
+    if !resp.ok {
+        return None;
+    }
+    serde_json::from_value(resp.data?).ok()
+}
+
+/// Prints the prompt segment. Always succeeds, printing nothing when the
+/// daemon cannot be reached or no session is active.
+///
+/// Colors are off with `no_color` or when `NO_COLOR` is set.
+pub(crate) fn run_prompt_segment_command(socket: &Path, no_color: bool) -> ExitCode {
+    let color = !no_color && std::env::var_os("NO_COLOR").map_or(true, |v| v.is_empty());
+    if let Some(sessions) = list_sessions(socket, PROMPT_TIMEOUT) {
+        let segment = prompt_segment(&sessions, color);
+        if !segment.is_empty() {
+            println!("{}", segment);
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,119 @@
+//! Resurrect command implementation.
+//!
+//! Handles the `resurrect` command, which reopens a closed session and prints
+//! the command that resumes it. With `--exec` it runs that command: in a new
+//! pane of the detected terminal backend (Zellij, tmux, kitty, WezTerm), else
+//! in the current terminal. Menu bar and launcher items run it on click.
+
+use super::list::resume_shell_command;
+use agent_console_dashboard::agents::AgentRegistry;
+use agent_console_dashboard::config::loader::ConfigLoader;
+use agent_console_dashboard::integrations::{self, session_from_snapshot};
+use agent_console_dashboard::{client, IpcCommand, IpcCommandKind, SessionSnapshot};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Reopens the closed session `session_id` and prints its resume command, or
+/// with `exec` resumes it.
+///
+/// With a terminal backend the new pane is opened and the outcome reported.
+/// Without one the agent runs in the foreground, in the session's working
+/// directory, and its exit code is returned; outside a terminal that fails.
+pub(crate) fn run_resurrect_command(socket: &Path, session_id: &str, exec: bool) -> ExitCode {
+    let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime for resurrect");
+    let cmd = IpcCommand::new(IpcCommandKind::Reopen, Some(session_id));
+    let data = match rt.block_on(client::request(socket, &cmd)) {
+        Ok(resp) if resp.ok => resp.data,
+        Ok(resp) => {
+            eprintln!(
+                "Error: {}",
+                resp.error.unwrap_or_else(|| "unknown error".to_string())
+            );
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(snapshot) = data.and_then(|data| serde_json::from_value::<SessionSnapshot>(data).ok())
+    else {
+        eprintln!("Error: daemon returned no session");
+        return ExitCode::FAILURE;
+    };
+
+    let config = ConfigLoader::load_default().ok();
+    let agents = config
+        .as_ref()
+        .map_or_else(AgentRegistry::builtin, |config| {
+            AgentRegistry::from_config(&config.agents)
+        });
+    let session = session_from_snapshot(&snapshot);
+    let agent = agents.get(&session.agent_type);
+    let dir = snapshot.working_dir.as_deref().unwrap_or(".");
+    let (Some(resume), Some(shell_command)) = (
+        agent
+            .resume_command(&session)
+            .filter(|resume| !resume.is_empty()),
+        resume_shell_command(&snapshot, &agents),
+    ) else {
+        println!(
+            "Reopened session {}. {} cannot resume sessions; start it again in {}",
+            session_id,
+            agent.display_name(),
+            dir
+        );
+        return ExitCode::SUCCESS;
+    };
+    if !exec {
+        println!(
+            "Reopened session {}. Resume it with: {}",
+            session_id, shell_command
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let terminal = config
+        .as_ref()
+        .and_then(|config| integrations::detect(&config.integrations));
+    if let Some(terminal) = terminal {
+        let command = terminal.resurrect_command(&session, &resume);
+        return match integrations::run(command, terminal.name()) {
+            Ok(()) => {
+                println!(
+                    "Resumed session {} in a new {} pane",
+                    session_id,
+                    terminal.name()
+                );
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("Error: failed to open {} pane: {}", terminal.name(), e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if !std::io::stdin().is_terminal() {
+        eprintln!(
+            "Error: no terminal to resume session {} in (no tmux, Zellij, kitty or \
+             WezTerm detected). Resume it with: {}",
+            session_id, shell_command
+        );
+        return ExitCode::FAILURE;
+    }
+    match std::process::Command::new(&resume[0])
+        .args(&resume[1..])
+        .current_dir(dir)
+        .status()
+    {
+        Ok(status) => status
+            .code()
+            .map_or(ExitCode::FAILURE, |code| ExitCode::from(code as u8)),
+        Err(e) => {
+            eprintln!("Error: failed to run {}: {}", resume[0], e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! JSON-RPC bridge command implementation.
+//!
+//! `acd rpc` serves the editor bridge on stdio, or on a Unix socket with
+//! `--listen`. See `agent_console_dashboard::rpc`.
+
+use agent_console_dashboard::rpc::RpcBridge;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Runs the bridge until stdin closes, or forever when listening on a socket.
+pub(crate) fn run_rpc_command(socket: &Path, listen: Option<&Path>) -> ExitCode {
+    let bridge = RpcBridge::new(socket.to_path_buf());
+    let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime for RPC");
+    let result = rt.block_on(async {
+        match listen {
+            Some(path) => bridge.listen(path).await,
+            None => bridge.serve_stdio().await,
+        }
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("acd rpc: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Status bar output implementation.
+//!
+//! Handles the `statusbar` command, which prints the active sessions in the
+//! format of a status bar's custom module:
+//!
+//! - `waybar` - one JSON object per line with `text`, `tooltip` and `class`
+//!   (for `"return-type": "json"`)
+//! - `polybar` - one line of text, colored with polybar format tags
+//!
+//! `class` (and the polybar color) follows the worst active status:
+//! `attention`, `question`, `working`, else `idle`; `down` when the daemon
+//! cannot be reached. With `--follow` the command keeps a SUB connection and
+//! prints a new line after every daemon notification, and at least every 10
+//! seconds.
+
+use super::prompt_segment::{is_active, list_sessions, prompt_segment};
+use agent_console_dashboard::{IpcCommand, IpcCommandKind, IpcResponse, SessionSnapshot};
+use clap::ValueEnum;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Longest the daemon may take to answer a LIST.
+const LIST_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long follow mode waits before reconnecting to a daemon that is down.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Longest follow mode goes without a new line. New sessions are not
+/// notified, so they show up after at most this long.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Status bar output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum StatusbarFormat {
+    /// Waybar custom module JSON.
+    Waybar,
+    /// Polybar script text with format tags.
+    Polybar,
+}
+
+/// Class of the worst active status in `sessions`.
+pub(crate) fn worst_class(sessions: &[SessionSnapshot]) -> &'static str {
+    let active: Vec<&str> = sessions
+        .iter()
+        .filter(|s| is_active(s))
+        .map(|s| s.status.as_str())
+        .collect();
+    ["attention", "question", "working"]
+        .into_iter()
+        .find(|status| active.contains(status))
+        .unwrap_or("idle")
+}
+
+/// Hex color (`#rrggbb`) for a status class, or `None` for `idle`/`down`.
+pub(crate) fn class_color(class: &str) -> Option<&'static str> {
+    match class {
+        "attention" => Some("#e5c07b"),
+        "question" => Some("#c678dd"),
+        "working" => Some("#98c379"),
+        _ => None,
+    }
+}
+
+/// Name of `session` in tooltips: its display name, else its folder, else
+/// its ID.
+pub(crate) fn session_label(session: &SessionSnapshot) -> &str {
+    session
+        .display_name
+        .as_deref()
+        .or_else(|| {
+            session
+                .working_dir
+                .as_deref()
+                .and_then(|dir| Path::new(dir).file_name())
+                .and_then(|name| name.to_str())
+        })
+        .unwrap_or(&session.session_id)
+}
+
+/// Escapes text for Pango markup, which Waybar renders text and tooltips in.
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Builds one output line for `sessions`, or for a daemon that cannot be
+/// reached when `None`.
+pub(crate) fn statusbar_line(
+    sessions: Option<&[SessionSnapshot]>,
+    format: StatusbarFormat,
+) -> String {
+    let Some(sessions) = sessions else {
+        return match format {
+            StatusbarFormat::Waybar => serde_json::json!({
+                "text": "",
+                "tooltip": "acd daemon is not running",
+                "class": "down",
+            })
+            .to_string(),
+            StatusbarFormat::Polybar => String::new(),
+        };
+    };
+    let text = prompt_segment(sessions, false);
+    let class = worst_class(sessions);
+    match format {
+        StatusbarFormat::Waybar => {
+            let tooltip = sessions
+                .iter()
+                .filter(|s| is_active(s))
+                .map(|s| format!("{}: {}", s.status, escape_markup(session_label(s))))
+                .collect::<Vec<_>>()
+                .join("\n");
+            serde_json::json!({ "text": text, "tooltip": tooltip, "class": class }).to_string()
+        }
+        StatusbarFormat::Polybar => {
+            let Some(color) = class_color(class) else {
+                return text;
+            };
+            format!("%{{F{}}}{}%{{F-}}", color, text)
+        }
+    }
+}
+
+/// Opens a SUB connection and returns its notification lines once the
+/// daemon has acknowledged the subscription.
+fn subscribe(socket: &Path) -> Option<std::io::Lines<BufReader<UnixStream>>> {
+    let mut stream = agent_console_dashboard::config::xdg::connect(socket).ok()?;
+    stream.set_read_timeout(Some(REFRESH_INTERVAL)).ok()?;
+    let cmd = IpcCommand::new(IpcCommandKind::Sub, None);
+    let line = format!("{}\n", serde_json::to_string(&cmd).ok()?);
+    stream.write_all(line.as_bytes()).ok()?;
+
+    let mut lines = BufReader::new(stream).lines();
+    let ack: IpcResponse = serde_json::from_str(&lines.next()?.ok()?).ok()?;
+    ack.ok.then_some(lines)
+}
+
+/// Prints the current line for `format`, returning false once stdout is
+/// closed (the bar went away).
+fn emit(socket: &Path, format: StatusbarFormat) -> bool {
+    let sessions = list_sessions(socket, LIST_TIMEOUT);
+    let line = statusbar_line(sessions.as_deref(), format);
+    writeln!(std::io::stdout(), "{}", line).is_ok()
+}
+
+/// Prints the status bar line, once or (with `follow`) after every daemon
+/// notification until stdout closes.
+pub(crate) fn run_statusbar_command(
+    socket: &Path,
+    format: StatusbarFormat,
+    follow: bool,
+) -> ExitCode {
+    if !follow {
+        emit(socket, format);
+        return ExitCode::SUCCESS;
+    }
+    loop {
+        if let Some(notifications) = subscribe(socket) {
+            if !emit(socket, format) {
+                return ExitCode::SUCCESS;
+            }
+            for notification in notifications {
+                let timed_out = matches!(
+                    &notification,
+                    Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+                );
+                if (notification.is_err() && !timed_out) || !emit(socket, format) {
+                    break;
+                }
+            }
+        }
+        // The daemon is down, or went away
+        if !emit(socket, format) {
+            return ExitCode::SUCCESS;
+        }
+        std::thread::sleep(RECONNECT_INTERVAL);
+    }
+}
@@ -0,0 +1,87 @@
+//! tmux status line implementation.
+//!
+//! Handles the `tmux-status` command, which prints the active session counts
+//! with tmux style tags, for `status-right`:
+//!
+//! ```sh
+//! set -g status-right '#(acd tmux-status) %H:%M'
+//! ```
+//!
+//! tmux runs the command on every status refresh of every client, so the
+//! output is cached in the runtime directory and reused while younger than
+//! `--max-age`.
+
+use super::prompt_segment::{active_counts, list_sessions, WAITING_SYMBOL, WORKING_SYMBOL};
+use agent_console_dashboard::config::xdg;
+use agent_console_dashboard::SessionSnapshot;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+/// Longest the daemon may take to answer a LIST.
+const LIST_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Builds the status line for `sessions`: waiting counts in yellow, working
+/// counts in green, each only when non-zero.
+pub(crate) fn tmux_segment(sessions: &[SessionSnapshot]) -> String {
+    let (waiting, working) = active_counts(sessions);
+    let mut parts = Vec::new();
+    for (count, symbol, color) in [
+        (waiting, WAITING_SYMBOL, "yellow"),
+        (working, WORKING_SYMBOL, "green"),
+    ] {
+        if count > 0 {
+            parts.push(format!("#[fg={}]{}{}#[default]", color, symbol, count));
+        }
+    }
+    parts.join(" ")
+}
+
+/// Cache file for the status line of the daemon on `socket`.
+pub(crate) fn cache_path(socket: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    socket.hash(&mut hasher);
+    xdg::runtime_dir().join(format!("acd-tmux-status-{:016x}", hasher.finish()))
+}
+
+/// Cached status line at `path`, if written less than `max_age` ago.
+pub(crate) fn read_cached(path: &Path, max_age: Duration) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    if age >= max_age {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+/// Replaces the cached status line at `path`. Written to a temporary file
+/// first, so concurrent tmux clients never read half a line.
+pub(crate) fn write_cache(path: &Path, line: &str) -> std::io::Result<()> {
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+    std::fs::write(&tmp, line)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Prints the tmux status line, from the cache when it is fresh. Prints
+/// nothing when the daemon cannot be reached; always succeeds.
+pub(crate) fn run_tmux_status_command(socket: &Path, max_age: Duration) -> ExitCode {
+    let cache = cache_path(socket);
+    let line = match read_cached(&cache, max_age) {
+        Some(line) => line,
+        None => {
+            let line = list_sessions(socket, LIST_TIMEOUT)
+                .map(|sessions| tmux_segment(&sessions))
+                .unwrap_or_default();
+            if !max_age.is_zero() {
+                let _ = write_cache(&cache, &line);
+            }
+            line
+        }
+    };
+    if !line.is_empty() {
+        println!("{}", line);
+    }
+    ExitCode::SUCCESS
+}
@@ -27,7 +27,14 @@ pub fn generate_tinydate() -> String {
 /// A well-commented TOML template with all default values.
 ///
 /// Every value here must match `Config::default()` from `schema.rs`.
-/// Sections: `[tui]`, `[agents.claude-code]`, `[integrations.zellij]`, `[daemon]`.
+/// Sections: `version`, `[tui]`, `[tui.keys]` (commented out), `[tui.icons]`,
+/// `[themes.<name>]` and `[hooks.<name>]` (commented out),
+/// `[agents.claude-code]`, `[integrations]`,
+/// `[integrations.zellij]`, `[integrations.tmux]`, `[integrations.kitty]`,
+/// `[integrations.wezterm]`, `[notifications.desktop]`, `[notifications.slack]`, `[notifications.discord]`, `[notifications.ntfy]`,
+/// `[notifications.email]`, `[notifications.mqtt]`,
+/// `[[notifications.webhooks]]` and `[[notifications.hooks]]` (commented out),
+/// `[daemon]`.
 pub const DEFAULT_CONFIG_TEMPLATE: &str = r#"# Agent Console Dashboard Configuration
 #
 # This file was auto-generated with default values.
@@ -37,6 +44,11 @@ pub const DEFAULT_CONFIG_TEMPLATE: &str = r#"# Agent Console Dashboard Configura
 # Location: $XDG_CONFIG_HOME/agent-console-dashboard/config.toml
 # Reference: https://github.com/PabloLION/agent-console-dashboard
 
+# Format version of this file. `acd config migrate` (also run at daemon start
+# and TUI launch) upgrades older files after saving a backup. Do not change it
+# by hand.
+version = 1
+
 # ==============================================================================
 # TUI Configuration
 # ==============================================================================
@@ -60,11 +72,71 @@ widgets = ["session-status:two-line", "api-usage"]
 # Note: Changing this requires a restart (not hot-reloadable).
 tick_rate = "250ms"
 
+# Footer template shown on the bottom line of the dashboard.
+# An empty string keeps the built-in footer (key hints plus API usage).
+# Placeholders are re-rendered every frame:
+#   {keys_hint}       — built-in keybinding hints
+#   {session_count}   — number of open sessions
+#   {attention_count} — sessions needing attention
+#   {working_count}   — sessions currently working
+#   {question_count}  — sessions waiting on a question
+#   {usage_5h}        — 5-hour API usage (e.g. "42%", "--" if unavailable)
+#   {usage_7d}        — 7-day API usage
+#   {socket}          — daemon socket path
+#   {version}         — dashboard version (e.g. "v0.1.0")
+# Unknown placeholders are shown verbatim.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+# Example: footer = "{keys_hint}  |  ! {attention_count}  5h {usage_5h}"
+footer = ""
+
+# How elapsed times are shown in session rows and the detail panel.
+# Options: "relative", "absolute"
+#   relative - time since the last status change (e.g. "2m 34s")
+#   absolute - local clock time of the last status change (e.g. "since 14:32")
+# Press `t` in the dashboard to toggle between them at runtime.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+time_format = "relative"
+
+# Read-only mode: hooks, rename, remove, and resurrect are disabled and the
+# footer explains why. Useful for wall displays or dashboards shared over tmux.
+# `acd tui --read-only` enables it for a single run.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+read_only = false
+
+# Show a tooltip with the full working directory and session ID when the
+# mouse hovers a session row (or chip in the two-line layout). The hovered
+# row is highlighted either way.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+hover_tooltips = true
+
+# Resume closed sessions (Enter, double-click, `r`) inside the dashboard, in a
+# terminal pane below the session list, instead of in a tmux/Zellij/kitty/
+# WezTerm pane. Reopen hooks still take precedence. Ctrl+] switches keyboard
+# focus between the dashboard and the pane; the pane closes when the agent
+# exits, and quitting the dashboard stops it.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+embedded_terminal = false
+
+# Color theme. Either a built-in theme or the name of a [themes.<name>] section
+# (see the commented example after [tui.icons]).
+# Options: "default", "monochrome", or a user-defined theme name
+#   default    - the standard colors
+#   monochrome - no colors; statuses differ by bold, italic, and dim text
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+theme = "default"
+
+# Project roots whose .acd.toml may replace activate_hooks and reopen_hooks
+# below. Project hooks run arbitrary commands, so a .acd.toml anywhere else (a
+# cloned repository, a shared directory) only sets names, tags, and alerts.
+# Example: trusted_projects = ["~/src/api"]
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+trusted_projects = []
+
 # Hooks to run on double-click of an active session (activate action).
 # Fires when double-clicking a non-closed session.
 # Each hook is spawned via `sh -c` with session context. Hooks run in sequence.
 # An empty list means double-click has no effect.
-# Hot-reloadable: No (restart TUI to apply changes)
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
 #
 # Available environment variables set for each hook process:
 #   $ACD_SESSION_ID  — unique session identifier
@@ -75,6 +147,8 @@ tick_rate = "250ms"
 # Use `jq` or any JSON parser to access all fields.
 #
 # TOML syntax: use [[tui.activate_hooks]] (double brackets) for each hook entry.
+# Instead of `command`, an entry can set `hook = "<name>"` to run a named hook
+# from the [hooks.<name>] library below.
 # For commands with double quotes, use single-quoted TOML strings (no escaping needed):
 #   command = 'zellij action go-to-tab-name "$(basename "$ACD_WORKING_DIR")"'
 #
@@ -92,7 +166,7 @@ tick_rate = "250ms"
 # Fires when double-clicking a closed session.
 # Each hook is spawned via `sh -c` with session context. Hooks run in sequence.
 # An empty list means double-click has no effect.
-# Hot-reloadable: No (restart TUI to apply changes)
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
 #
 # Same environment variables and stdin JSON as activate_hooks above.
 #
@@ -103,6 +177,85 @@ tick_rate = "250ms"
 # command = 'zellij action new-tab --name "$(basename "$ACD_WORKING_DIR")" --cwd "$ACD_WORKING_DIR" --session "$ZELLIJ_SESSION_NAME"'
 # timeout = 5
 
+# Custom commands for the selected session, listed in the command palette (`:`).
+# Each runs via `sh -c` in a new pane of the terminal backend (tmux, Zellij,
+# kitty, WezTerm), in the session's working directory. Placeholders are
+# replaced with shell-quoted values: {session_id}, {name}, {status},
+# {working_dir}.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+#
+# Uncomment to enable — examples:
+# [[tui.commands]]
+# name = "lazygit"
+# command = "lazygit"
+#
+# [[tui.commands]]
+# name = "run tests"
+# command = "cargo test; read -r -p 'Press Enter to close'"
+
+# Extra dashboard keys, each running a named hook from the [hooks.<name>]
+# library for the selected session. Keys are single characters; keys with a
+# built-in action (q j k r R : a A u g t s S o d 1-4) cannot be rebound.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+#
+# Uncomment to enable — example:
+# [tui.keys]
+# e = "open-editor"
+
+# Status icons used in session rows, chips, the header, and the terminal title.
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+[tui.icons]
+
+# Built-in icon set.
+# Options: "ascii", "emoji", "nerd-font"
+#   ascii     - * ! ? x . (works in every terminal)
+#   emoji     - emoji glyphs (usually two cells wide)
+#   nerd-font - Nerd Font glyphs (requires a patched font)
+set = "ascii"
+
+# Use ASCII when the terminal does not appear to support Unicode
+# (non-UTF-8 locale, or TERM=linux/dumb/vt100/vt220).
+ascii_fallback = true
+
+# Per-status overrides. Unset keys use the icon from the chosen set.
+# Non-ASCII overrides are ignored when the ASCII fallback is active.
+# working = ">"
+# attention = "!"
+# question = "?"
+# closed = "x"
+# inactive = "."
+
+# User-defined color themes, selected with `theme = "<name>"` under [tui].
+# Each key is a style: a foreground color, `on <color>` for the background,
+# and any of bold, dim, italic, underlined, reversed. Colors are names (red,
+# light-blue), #rrggbb, or 0-255 indexes. Unset keys come from `base`.
+# Keys: working, attention, question, closed, inactive, accent, text, muted,
+# warning, error, success, selection, hover
+# Hot-reloadable: Yes (the TUI applies changes when the file is saved)
+#
+# Uncomment to enable — example:
+# [themes.solar]
+# base = "default"
+# attention = "light-yellow bold"
+# accent = "light-blue"
+# selection = "on 236"
+
+# Named hooks, defined once and referenced with `hook = "<name>"` from
+# tui.activate_hooks, tui.reopen_hooks, tui.keys, and notifications.hooks.
+# Each has a `command` and an optional `timeout` (seconds, default 5), and runs
+# like tui.activate_hooks. {session_id}, {name}, {status}, {working_dir} in
+# the command are replaced with shell-quoted session values; do not quote them
+# again. (Commands written directly in a hook list run as written.)
+# Hot-reloadable: Yes in the TUI; the daemon reads them at startup
+#
+# Uncomment to enable — examples:
+# [hooks.open-editor]
+# command = "code {working_dir}"
+#
+# [hooks.notify]
+# command = "notify-send {name} {status}"
+# timeout = 2
+
 # ==============================================================================
 # Agent Configuration
 # ==============================================================================
@@ -117,16 +270,267 @@ enabled = true
 # Tilde (~) is expanded to the user's home directory.
 hooks_path = "~/.claude/hooks"
 
+# Custom agents report sessions with
+#   acd agent-hook --agent <name> [--cwd <dir>] <session-id> <event>
+# which sets the status mapped to <event>. Repeat the section per agent.
+#
+# [[agents.custom]]
+# name = "devbot"
+# display_name = "DevBot"
+# # Tag shown before the directory in session rows (default: name)
+# icon = "bot"
+# # Resurrect command; {session_id} and {working_dir} are replaced
+# resume = "devbot --resume {session_id}"
+#
+# [agents.custom.statuses]
+# prompt = "working"
+# done = "attention"
+# ask = "question"
+# exit = "closed"
+
 # ==============================================================================
 # Integration Configuration
 # ==============================================================================
 
+[integrations]
+
+# Terminal backend used to focus and resurrect sessions when no hooks are set.
+# "auto" detects it from the environment (ZELLIJ, TMUX, TERM_PROGRAM, kitty's
+# KITTY_LISTEN_ON, WEZTERM_PANE). Set "zellij", "tmux", "kitty" or "wezterm"
+# to use that backend regardless, or "none" to disable terminal integration.
+terminal = "auto"
+
+# Bring the session's window to the front on activate (Enter/double-click):
+# inside tmux, find the pane in any tmux session and switch the client to it;
+# on macOS, also raise the terminal app. kitty always raises the OS window.
+raise_window = true
+
 [integrations.zellij]
 
 # Enable Zellij terminal multiplexer integration.
-# When enabled, supports session resurrection via Zellij panes.
+# When the TUI runs inside Zellij and no activate/reopen hooks are set, Enter
+# focuses the tab named after the session's folder, and resurrecting opens a
+# new pane there running `claude --resume <id>`.
+enabled = true
+
+[integrations.tmux]
+
+# Enable tmux integration, used inside tmux (TMUX is set) when not in Zellij.
+# Enter selects the window named after the session's folder in the current
+# tmux session; resurrecting opens a new window with that name there running
+# `claude --resume <id>`.
+enabled = true
+
+[integrations.kitty]
+
+# Enable kitty remote control (`kitty @`). Used when TERM=xterm-kitty and
+# remote control is reachable (KITTY_LISTEN_ON is set, e.g. with
+# `listen_on unix:/tmp/kitty` and `allow_remote_control yes` in kitty.conf).
+# Enter focuses the window running in the session's folder; resurrecting opens
+# a new kitty window there running `claude --resume <id>`.
+enabled = true
+
+[integrations.wezterm]
+
+# Enable the WezTerm integration (`wezterm cli`), used inside WezTerm panes.
+# Enter activates the pane running in the session's folder; resurrecting
+# spawns a new tab there running `claude --resume <id>`.
+enabled = true
+
+[integrations.github]
+
+# Show the pull request of the selected session's git branch (number, CI
+# checks, review decision) in the detail panel; `o` opens it in the browser.
+# Requires the GitHub CLI (`gh`), logged in with `gh auth login`.
 enabled = true
 
+# How often the selected session's pull request is looked up again.
+refresh_interval = "1m"
+
+# ==============================================================================
+# Notification Configuration
+# ==============================================================================
+
+[notifications.desktop]
+
+# Show a desktop notification when a session needs you, even if the TUI is
+# hidden. Uses notify-send on Linux, and terminal-notifier (or osascript) on
+# macOS. Sent by the daemon.
+# Hot-reloadable: No (restart daemon to apply changes)
+enabled = false
+
+# Statuses that trigger a notification when a session enters them.
+# Options: "working", "attention", "question", "closed"
+statuses = ["attention", "question"]
+
+# Minimum time between notifications for the same session. "0s" disables
+# rate limiting.
+min_interval = "1m"
+
+[notifications.slack]
+
+# Slack incoming webhook URL. Empty disables Slack notifications.
+# Create one at https://api.slack.com/messaging/webhooks
+# Hot-reloadable: No (restart daemon to apply changes)
+webhook_url = ""
+
+# Channel override, e.g. '#agents'. Empty uses the webhook's channel.
+channel = ""
+
+# Statuses that post a message when a session enters them.
+statuses = ["attention", "question"]
+
+# Message template (Slack mrkdwn). Placeholders:
+#   {name}        — display name, or the working directory's folder name
+#   {session_id}  — full session ID
+#   {status}      — new status (attention, question, ...)
+#   {event}       — what happened ("needs attention", "has a question", ...)
+#   {working_dir} — session working directory
+#   {elapsed}     — time spent in the previous status
+template = "*{name}* {event} (`{working_dir}`)"
+
+# Minimum time between messages for the same session.
+min_interval = "1m"
+
+# Post when 5-hour API usage crosses this percentage (0 disables).
+usage_threshold = 0
+
+[notifications.discord]
+
+# Discord channel webhook URL (Channel settings > Integrations > Webhooks).
+# Empty disables Discord notifications. Messages are embeds listing the
+# session ID, status, elapsed time and project folder.
+# Hot-reloadable: No (restart daemon to apply changes)
+webhook_url = ""
+
+# Name the webhook posts as. Empty uses the name set in Discord.
+username = "acd"
+
+# Statuses that post a message when a session enters them.
+statuses = ["attention", "question"]
+
+# Embed title template, with the same placeholders as the Slack template.
+template = "{name} {event}"
+
+# Minimum time between messages for the same session.
+min_interval = "1m"
+
+# Post when 5-hour API usage crosses this percentage (0 disables).
+usage_threshold = 0
+
+[notifications.ntfy]
+
+# ntfy server and topic. Subscribe to the topic in the ntfy phone app to get
+# push notifications. An empty topic disables ntfy notifications.
+# Hot-reloadable: No (restart daemon to apply changes)
+server = "https://ntfy.sh"
+topic = ""
+
+# Access token for protected topics (empty: publish anonymously).
+token = ""
+
+# Statuses that send a notification when a session enters them.
+statuses = ["attention", "question"]
+
+# Message template, with the same placeholders as the Slack template.
+template = "{name} {event}"
+
+# Priority per event (1 = min to 5 = max): a status name, or "usage" for
+# usage alerts. Unlisted events use priority 3.
+priorities = { attention = 4, question = 4, usage = 4 }
+
+# Minimum time between notifications for the same session.
+min_interval = "1m"
+
+# Notify when 5-hour API usage crosses this percentage (0 disables).
+usage_threshold = 0
+
+[notifications.email]
+
+# SMTP relay that sends email digests, e.g. for overnight unattended runs.
+# An empty host (or no recipients) disables email.
+# Hot-reloadable: No (restart daemon to apply changes)
+smtp_host = ""
+smtp_port = 587
+
+# Connection security: "starttls" (port 587), "tls" (port 465) or "none".
+security = "starttls"
+
+# Credentials for AUTH PLAIN. An empty username sends without logging in.
+username = ""
+password = ""
+
+# Sender (empty: the username) and recipients.
+from = ""
+to = []
+
+# Send a digest when sessions stay in one of these statuses for stuck_after.
+statuses = ["attention", "question"]
+stuck_after = "30m"
+
+# Send a digest of the remaining sessions before the idle daemon auto-stops
+# and drops them.
+on_auto_stop = true
+
+[notifications.mqtt]
+
+# MQTT broker URL: "mqtt://host:1883", or "mqtts://host:8883" for TLS.
+# Empty disables MQTT. Session statuses and usage are published for
+# home-automation setups, e.g. to flash a light when a session needs you.
+# Hot-reloadable: No (restart daemon to apply changes)
+broker = ""
+
+# Client ID presented to the broker.
+client_id = "acd"
+
+# Credentials. An empty username connects anonymously.
+username = ""
+password = ""
+
+# Topic a session's status ("working", "attention", ...) is published to,
+# with the same placeholders as the Slack template.
+status_topic = "acd/sessions/{session_id}/status"
+
+# Topic usage data is published to as JSON on every update. Empty disables
+# usage publishing.
+usage_topic = "acd/usage"
+
+# Statuses that publish when a session enters them (empty: every status).
+statuses = []
+
+# Quality of service (0, 1 or 2), and whether the broker keeps the last
+# message of each topic for new subscribers.
+qos = 1
+retain = true
+
+# Generic JSON webhooks. Repeat the [[notifications.webhooks]] table for
+# each endpoint; none are configured by default. Each POST body has an
+# "event", a "timestamp" and either a "session" snapshot or "usage" data.
+# Hot-reloadable: No (restart daemon to apply changes)
+#
+# [[notifications.webhooks]]
+# url = "https://example.com/acd"
+# # Any of "session_created", "status_changed", "usage_threshold".
+# events = ["status_changed"]
+# # Statuses that send status_changed (empty: every status).
+# statuses = []
+# # Only sessions in or below these directories (empty: every session).
+# working_dirs = []
+# # 5-hour usage percentage that sends usage_threshold.
+# usage_threshold = 80
+# # Extra HTTP headers.
+# headers = { Authorization = "Bearer <token>" }
+
+# Shell hooks run when a session enters one of `statuses` (empty: every
+# status). Repeat the [[notifications.hooks]] table for each hook; none are
+# configured by default. Set `hook` to a named hook, or `command` and
+# `timeout` as in tui.activate_hooks.
+# Hot-reloadable: No (restart daemon to apply changes)
+#
+# [[notifications.hooks]]
+# hook = "notify"
+# statuses = ["attention", "question"]
+
 # ==============================================================================
 # Daemon Configuration
 # ==============================================================================
@@ -161,6 +565,51 @@ log_level = "info"
 # Examples: "/var/log/agent-console-dashboard.log", "~/logs/acd-daemon.log"
 # Hot-reloadable: No (restart required)
 log_file = ""
+
+# ==============================================================================
+# Telemetry Configuration
+# ==============================================================================
+
+[telemetry]
+
+# Export daemon metrics (session counts, IPC command latencies, broadcast
+# fan-out) and IPC command spans to an OpenTelemetry collector over OTLP/HTTP.
+# Requires an acd build with the `telemetry` feature
+# (cargo install agent-console-dashboard --features telemetry).
+# Hot-reloadable: No (restart daemon to apply changes)
+enabled = false
+
+# Base URL of the collector; /v1/traces and /v1/metrics are appended.
+endpoint = "http://localhost:4318"
+
+# service.name resource attribute.
+service_name = "acd"
+
+# How often metrics are exported.
+export_interval = "30s"
+
+# Extra HTTP headers, e.g. for a hosted collector:
+# headers = { authorization = "Bearer <token>" }
+
+[statsd]
+
+# Send session counts, status transitions and API usage to a StatsD server
+# over UDP. Enabling it keeps the daemon fetching API usage.
+# Hot-reloadable: No (restart daemon to apply changes)
+enabled = false
+
+# host:port of the StatsD server.
+address = "127.0.0.1:8125"
+
+# Prefix of every metric name.
+prefix = "acd"
+
+# How often metrics are sent.
+interval = "10s"
+
+# Send statuses as DogStatsD tags (acd.sessions:2|g|#status:working) instead
+# of name segments (acd.sessions.working:2|g).
+dogstatsd = false
 "#;
 
 // ---------------------------------------------------------------------------
@@ -171,7 +620,7 @@ log_file = ""
 ///
 /// Returns `Ok(true)` if the file was created, `Ok(false)` if it already exists.
 /// Uses `xdg::config_path()` for the target location and creates parent
-/// directories via `xdg::ensure_config_dir()`.
+/// directories via `xdg::ensure_dir()`.
 pub fn create_default_config_if_missing() -> Result<bool, ConfigError> {
     let path = xdg::config_path();
 
@@ -217,7 +666,8 @@ pub fn create_default_config(force: bool) -> Result<PathBuf, ConfigError> {
 
 /// Writes the default template to `path`, creating parent dirs and setting 0600 permissions.
 fn write_default_config(path: &PathBuf) -> Result<(), ConfigError> {
-    xdg::ensure_config_dir().map_err(|e| ConfigError::WriteError {
+    let dir = path.parent().unwrap_or(path);
+    xdg::ensure_dir(dir).map_err(|e| ConfigError::WriteError {
         path: path.clone(),
         source: e,
     })?;
@@ -290,10 +740,54 @@ mod tests {
             DEFAULT_CONFIG_TEMPLATE.contains("[agents.claude-code]"),
             "missing [agents.claude-code] section"
         );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[integrations]\n"),
+            "missing [integrations] section"
+        );
         assert!(
             DEFAULT_CONFIG_TEMPLATE.contains("[integrations.zellij]"),
             "missing [integrations.zellij] section"
         );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[integrations.tmux]"),
+            "missing [integrations.tmux] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[integrations.kitty]"),
+            "missing [integrations.kitty] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[integrations.wezterm]"),
+            "missing [integrations.wezterm] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[notifications.desktop]"),
+            "missing [notifications.desktop] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[notifications.slack]"),
+            "missing [notifications.slack] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[notifications.discord]"),
+            "missing [notifications.discord] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[notifications.ntfy]"),
+            "missing [notifications.ntfy] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[notifications.email]"),
+            "missing [notifications.email] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[notifications.mqtt]"),
+            "missing [notifications.mqtt] section"
+        );
+        assert!(
+            DEFAULT_CONFIG_TEMPLATE.contains("[[notifications.webhooks]]"),
+            "missing [[notifications.webhooks]] example"
+        );
         assert!(
             DEFAULT_CONFIG_TEMPLATE.contains("[daemon]"),
             "missing [daemon] section"
@@ -16,8 +16,11 @@ pub enum ConfigError {
         source: std::io::Error,
     },
 
-    /// The TOML content could not be parsed.
-    #[error("Invalid configuration at {path}:{line}:{column}: {message}")]
+    /// The TOML content could not be parsed, or does not match the schema.
+    #[error(
+        "Invalid configuration at {path}:{line}:{column}{}: {message}",
+        key.as_ref().map(|key| format!(" (key `{key}`)")).unwrap_or_default()
+    )]
     ParseError {
         /// Path to the file containing the error.
         path: PathBuf,
@@ -25,6 +28,9 @@ pub enum ConfigError {
         line: usize,
         /// One-based column index of the error (0 if unknown).
         column: usize,
+        /// Dotted path of the key at the error, like `tui.tick_rate`, when
+        /// known.
+        key: Option<String>,
         /// Human-readable description of the parse failure.
         message: String,
     },
@@ -62,6 +68,16 @@ pub enum ConfigError {
         message: String,
     },
 
+    /// An `ACD_*` environment variable override names an unknown key or
+    /// holds a value of the wrong type.
+    #[error("Invalid environment override {name}: {message}")]
+    EnvOverride {
+        /// Name of the environment variable.
+        name: String,
+        /// Human-readable description of the problem.
+        message: String,
+    },
+
     /// No editor is configured ($VISUAL or $EDITOR not set).
     #[error("No editor configured. Set $EDITOR environment variable.")]
     EditorNotSet,
@@ -113,6 +129,7 @@ mod tests {
             path: PathBuf::from("config.toml"),
             line: 5,
             column: 12,
+            key: None,
             message: "expected `=`".to_string(),
         };
         let msg = err.to_string();
@@ -126,6 +143,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_parse_error_with_key() {
+        let err = ConfigError::ParseError {
+            path: PathBuf::from("config.toml"),
+            line: 2,
+            column: 1,
+            key: Some("tui.tick_ms".to_string()),
+            message: "unknown field `tick_ms`".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration at config.toml:2:1 (key `tui.tick_ms`): unknown field `tick_ms`"
+        );
+    }
+
     #[test]
     fn display_not_found_error() {
         let err = ConfigError::NotFound {
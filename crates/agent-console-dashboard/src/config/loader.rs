@@ -2,24 +2,172 @@
 //!
 //! Loads TOML configuration from a specific path or the default XDG location.
 //! When the default location has no file, returns `Config::default()`.
+//!
+//! Per-project overrides ([`ProjectConfig`]) are loaded from the nearest
+//! [`PROJECT_CONFIG_FILE`] at or above a session's working directory, up to
+//! its repository root or the home directory.
+//!
+//! Loading never rewrites a file. Files written for older versions are
+//! upgraded by [`ConfigLoader::migrate_file`], which daemon start, the TUI
+//! and `acd config migrate` call before loading (see
+//! [`crate::config::migrate`]).
+//!
+//! `ACD_<SECTION>__<KEY>` environment variables override keys of the loaded
+//! configuration (see [`ConfigLoader::apply_env`]), so containers and CI can
+//! configure the tool without writing a file.
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use serde::de::DeserializeOwned;
+
+use crate::config::default::generate_tinydate;
 use crate::config::error::ConfigError;
-use crate::config::schema::Config;
+use crate::config::migrate::{self, Migration, CONFIG_VERSION};
+use crate::config::schema::{Config, ProjectConfig};
 use crate::config::xdg;
 
+/// File name of per-project overrides, placed in a project's root.
+pub const PROJECT_CONFIG_FILE: &str = ".acd.toml";
+
+/// Prefix of environment variables that override configuration keys.
+pub const ENV_PREFIX: &str = "ACD_";
+
+/// Separator between the key segments of an override variable's name.
+const ENV_SEPARATOR: &str = "__";
+
 /// Stateless configuration loader.
 pub struct ConfigLoader;
 
 impl ConfigLoader {
     /// Load configuration from a specific path.
     ///
+    /// The file is never modified. A file written for an older version
+    /// usually fails with an unknown key; the error then suggests
+    /// `acd config migrate`.
+    ///
     /// Returns `ConfigError::NotFound` if the file does not exist, or
     /// `ConfigError::ReadError` for other I/O failures.
     pub fn load_from_path(path: &Path) -> Result<Config, ConfigError> {
-        let content = fs::read_to_string(path).map_err(|e| {
+        let content = Self::read(path)?;
+        Self::parse_toml(&content, path).map_err(|e| with_migrate_hint(e, &content))
+    }
+
+    /// Upgrade the file at `path` if it was written for an older version.
+    ///
+    /// The original is backed up to `<name>.<tinydate>.bak` first. The
+    /// upgraded file is written to a temporary file next to it and renamed
+    /// over it, so an interrupted upgrade leaves the old file intact. A
+    /// symlink is followed, so the file it points at is upgraded, and the
+    /// file's permissions carry over.
+    ///
+    /// Returns the applied migration and the backup's path, or `Ok(None)`
+    /// if the file is current.
+    ///
+    /// # Errors
+    ///
+    /// - `ConfigError::NotFound` / `ConfigError::ReadError` if the file
+    ///   cannot be read
+    /// - `ConfigError::ParseError` if its `version` is newer than this build
+    /// - `ConfigError::WriteError` if the backup or the upgraded file cannot
+    ///   be written
+    pub fn migrate_file(path: &Path) -> Result<Option<(Migration, PathBuf)>, ConfigError> {
+        let content = Self::read(path)?;
+        let migration = migrate::migrate(&content)
+            .map_err(|message| located_error(&content, path, "version".to_string(), message))?;
+        let Some(migration) = migration else {
+            return Ok(None);
+        };
+
+        // Replace the file a symlinked config points at, not the link, and
+        // keep its permissions on the backup and the upgraded file
+        let write_error = |source| ConfigError::WriteError {
+            path: path.to_path_buf(),
+            source,
+        };
+        let target = fs::canonicalize(path).map_err(write_error)?;
+        let permissions = fs::metadata(&target).map_err(write_error)?.permissions();
+
+        let backup = PathBuf::from(format!("{}.{}.bak", path.display(), generate_tinydate()));
+        fs::write(&backup, &content)
+            .and_then(|()| fs::set_permissions(&backup, permissions.clone()))
+            .map_err(|source| ConfigError::WriteError {
+                path: backup.clone(),
+                source,
+            })?;
+        let tmp = target.with_extension(format!("toml.{}.tmp", std::process::id()));
+        if let Err(source) = fs::write(&tmp, &migration.content)
+            .and_then(|()| fs::set_permissions(&tmp, permissions))
+            .and_then(|()| fs::rename(&tmp, &target))
+        {
+            let _ = fs::remove_file(&tmp);
+            return Err(ConfigError::WriteError {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+        Ok(Some((migration, backup)))
+    }
+
+    /// Upgrade the default configuration file if it is outdated, printing
+    /// what changed to stderr.
+    ///
+    /// Called at daemon start and TUI launch. Failures are printed and
+    /// otherwise ignored: loading reports what is wrong with the file.
+    pub fn migrate_default() {
+        let path = xdg::config_path();
+        if !path.exists() {
+            return;
+        }
+        match Self::migrate_file(&path) {
+            Ok(Some((migration, backup))) => {
+                eprintln!("{}", upgrade_summary(&path, &migration, &backup));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Failed to upgrade config {:?}: {}", path, e);
+                eprintln!("Could not upgrade {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Load the overrides of the project containing `dir`: the nearest
+    /// [`PROJECT_CONFIG_FILE`] in `dir` or its ancestors.
+    ///
+    /// The search stops at the first directory holding a `.git` entry, or at
+    /// the home directory, whichever comes first. Files owned by another
+    /// user are ignored, so a shared directory like `/tmp` cannot inject
+    /// settings.
+    ///
+    /// Returns `Ok(None)` if no ancestor has one. Files further up are not
+    /// merged in; the nearest one wins.
+    pub fn load_project(dir: &Path) -> Result<Option<ProjectConfig>, ConfigError> {
+        let home = dirs::home_dir();
+        let mut found = None;
+        for dir in dir.ancestors() {
+            let path = dir.join(PROJECT_CONFIG_FILE);
+            if path.is_file() {
+                if owned_by_current_user(&path) {
+                    found = Some(path);
+                    break;
+                }
+                tracing::warn!("ignoring {}: not owned by the current user", path.display());
+            }
+            if dir.join(".git").exists() || home.as_deref() == Some(dir) {
+                break;
+            }
+        }
+        let Some(path) = found else {
+            return Ok(None);
+        };
+        let mut project: ProjectConfig = Self::parse(&Self::read(&path)?, &path)?;
+        project.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(Some(project))
+    }
+
+    /// Read the file at `path`.
+    fn read(path: &Path) -> Result<String, ConfigError> {
+        fs::read_to_string(path).map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 ConfigError::NotFound {
                     path: path.to_path_buf(),
@@ -31,26 +179,80 @@ impl ConfigLoader {
                     source: e,
                 }
             }
-        })?;
-        Self::parse_toml(&content, path)
+        })
     }
 
-    /// Load configuration from the default XDG location.
+    /// Load configuration from the default XDG location, with the
+    /// environment's `ACD_*` overrides applied.
     ///
-    /// If no file exists at the default path, returns `Config::default()`
-    /// instead of an error.
+    /// If no file exists at the default path, the overrides apply to
+    /// `Config::default()` instead of an error.
     pub fn load_default() -> Result<Config, ConfigError> {
         let path = xdg::config_path();
-        if path.exists() {
-            Self::load_from_path(&path)
+        let config = if path.exists() {
+            Self::load_from_path(&path)?
         } else {
             tracing::debug!("No config file at {:?}, using defaults", path);
-            Ok(Config::default())
+            Config::default()
+        };
+        Self::apply_env(config, env_vars())
+    }
+
+    /// Apply `ACD_<SECTION>__<KEY>` overrides from `vars` to `config`.
+    ///
+    /// Name segments after the prefix are separated by `__` and matched
+    /// case-insensitively, with `_` also matching `-`:
+    /// `ACD_AGENTS__CLAUDE_CODE__ENABLED=false` sets
+    /// `agents.claude-code.enabled`. Values of string keys are taken
+    /// verbatim; other values are TOML (`100`, `true`, `["attention"]`).
+    /// Variables without `__`, like the hooks' `ACD_SESSION_ID`, are not
+    /// overrides.
+    ///
+    /// Returns `ConfigError::EnvOverride` for an unknown key or a value of
+    /// the wrong type.
+    pub fn apply_env<I>(config: Config, vars: I) -> Result<Config, ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut overrides: Vec<(String, String)> = vars
+            .into_iter()
+            .filter(|(name, _)| {
+                name.strip_prefix(ENV_PREFIX)
+                    .is_some_and(|key| key.contains(ENV_SEPARATOR))
+            })
+            .collect();
+        // Apply in a stable order, whatever the environment's
+        overrides.sort();
+
+        let mut config = config;
+        for (name, raw) in overrides {
+            let error = |message: String| ConfigError::EnvOverride {
+                name: name.clone(),
+                message,
+            };
+            let segments: Vec<String> = name[ENV_PREFIX.len()..]
+                .split(ENV_SEPARATOR)
+                .map(str::to_ascii_lowercase)
+                .collect();
+            let mut root = toml::Value::try_from(&config).map_err(|e| error(e.to_string()))?;
+            let key = set_override(&mut root, &segments, &raw).map_err(error)?;
+            config = root.try_into().map_err(|e: toml::de::Error| {
+                error(format!("{}: {}", key, with_hint(e.message())))
+            })?;
+            check(&config).map_err(|(key, message)| error(format!("{}: {}", key, message)))?;
         }
+        Ok(config)
     }
 
     /// Parse a TOML string into `Config` with position-aware error reporting.
-    fn parse_toml(content: &str, path: &Path) -> Result<Config, ConfigError> {
+    pub(crate) fn parse_toml(content: &str, path: &Path) -> Result<Config, ConfigError> {
+        let config: Config = Self::parse(content, path)?;
+        check(&config).map_err(|(key, message)| located_error(content, path, key, message))?;
+        Ok(config)
+    }
+
+    /// Parse a TOML string into `T` with position-aware error reporting.
+    fn parse<T: DeserializeOwned>(content: &str, path: &Path) -> Result<T, ConfigError> {
         toml::from_str(content).map_err(|e| {
             let (line, column) = e
                 .span()
@@ -68,12 +270,369 @@ impl ConfigLoader {
                 path: path.to_path_buf(),
                 line,
                 column,
-                message: e.message().to_string(),
+                key: e.span().and_then(|span| key_path(content, span.start)),
+                message: with_hint(e.message()),
             }
         })
     }
 }
 
+/// Dotted path of the key on the line at byte `offset` of `content`,
+/// qualified by the `[table]` header above it.
+///
+/// Returns the header itself when the line is one, and None for lines
+/// without a key, like the middle of a multi-line array.
+fn key_path(content: &str, offset: usize) -> Option<String> {
+    let header = |line: &str| {
+        let name = line.trim().strip_prefix('[')?.trim_start_matches('[');
+        Some(
+            name.split(']')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        )
+    };
+    let line_start = content[..offset].rfind('\n').map_or(0, |p| p + 1);
+    let line = content[line_start..].lines().next().unwrap_or_default();
+    if let Some(table) = header(line) {
+        return Some(table);
+    }
+    let key = line.split_once('=')?.0.trim();
+    match content[..line_start].lines().rev().find_map(header) {
+        Some(table) if !table.is_empty() => Some(format!("{}.{}", table, key)),
+        _ => Some(key.to_string()),
+    }
+}
+
+/// One-based line and column of the key with dotted path `key` in
+/// `content`, as [`key_path`] names keys.
+///
+/// A key in an array of tables, like `tui.activate_hooks[1].hook`, is looked
+/// up below that table's header; without the key there, the header is
+/// returned.
+fn locate_key(content: &str, key: &str) -> Option<(usize, usize)> {
+    let mut start = 0;
+    let mut lines = Vec::new();
+    for line in content.split('\n') {
+        lines.push((key_path(content, start), line));
+        start += line.len() + 1;
+    }
+    let position = |index: usize| {
+        let line = lines[index].1;
+        (index + 1, line.len() - line.trim_start().len() + 1)
+    };
+    let Some((table, rest)) = key.split_once('[') else {
+        let index = lines
+            .iter()
+            .position(|(path, _)| path.as_deref() == Some(key))?;
+        return Some(position(index));
+    };
+    let (n, field) = rest.split_once(']')?;
+    let header = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (path, _))| path.as_deref() == Some(table))
+        .nth(n.parse().ok()?)?
+        .0;
+    let field = format!("{}{}", table, field);
+    let index = lines[header + 1..]
+        .iter()
+        .take_while(|(path, line)| path.as_deref() != Some(table) && !line.trim().starts_with('['))
+        .position(|(path, _)| path.as_deref() == Some(field.as_str()))
+        .map_or(header, |offset| header + 1 + offset);
+    Some(position(index))
+}
+
+/// Whether the current user owns the file at `path`.
+fn owned_by_current_user(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).is_ok_and(|meta| meta.uid() == xdg::current_uid())
+}
+
+/// `ConfigError::ParseError` for the key with dotted path `key` in `content`.
+fn located_error(content: &str, path: &Path, key: String, message: String) -> ConfigError {
+    let (line, column) = locate_key(content, &key).unwrap_or((0, 0));
+    ConfigError::ParseError {
+        path: path.to_path_buf(),
+        line,
+        column,
+        key: Some(key),
+        message,
+    }
+}
+
+/// What [`ConfigLoader::migrate_file`] did to `path`, for printing: a
+/// heading line, then one `  - ` line per change.
+pub fn upgrade_summary(path: &Path, migration: &Migration, backup: &Path) -> String {
+    let mut summary = format!(
+        "Upgraded {} from config version {} to {} (backup: {}):",
+        path.display(),
+        migration.from,
+        CONFIG_VERSION,
+        backup.display()
+    );
+    for change in &migration.changes {
+        summary.push_str(&format!("\n  - {}", change));
+    }
+    summary
+}
+
+/// Points parse errors of a file written for an older version to
+/// `acd config migrate`.
+fn with_migrate_hint(error: ConfigError, content: &str) -> ConfigError {
+    let version =
+        content
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|table| match table.get("version") {
+                None => Some(0),
+                Some(version) => version.as_integer().and_then(|v| u32::try_from(v).ok()),
+            });
+    match error {
+        ConfigError::ParseError {
+            path,
+            line,
+            column,
+            key,
+            message,
+        } if version.is_some_and(|version| version < CONFIG_VERSION) => ConfigError::ParseError {
+            path,
+            line,
+            column,
+            key,
+            message: format!(
+                "{} (this file is config version {}; run `acd config migrate` to upgrade it)",
+                message,
+                version.unwrap_or_default()
+            ),
+        },
+        other => other,
+    }
+}
+
+/// Checks what parsing alone cannot: the format version, theme names and
+/// hook references.
+///
+/// Returns the dotted path of the offending key and a message.
+fn check(config: &Config) -> Result<(), (String, String)> {
+    if config.version > CONFIG_VERSION {
+        return Err((
+            "version".to_string(),
+            format!(
+                "config version {} is newer than this acd supports ({}); upgrade acd",
+                config.version, CONFIG_VERSION
+            ),
+        ));
+    }
+    check_themes(config)?;
+    check_hooks(config)
+}
+
+/// Checks the `[hooks]` library and every reference to it: `hook` in hook
+/// lists and `notifications.hooks`, and the `tui.keys` bindings.
+///
+/// Returns the dotted path of the offending key and a message.
+fn check_hooks(config: &Config) -> Result<(), (String, String)> {
+    use crate::tui::event::RESERVED_KEYS;
+
+    for (name, hook) in &config.hooks {
+        if !hook.hook.is_empty() {
+            return Err((
+                format!("hooks.{}.hook", name),
+                "library hooks cannot reference other hooks".to_string(),
+            ));
+        }
+        if hook.command.trim().is_empty() {
+            return Err((format!("hooks.{}", name), "missing `command`".to_string()));
+        }
+    }
+
+    let names = || config.hooks.keys().map(String::as_str);
+    let unknown = |name: &str| match closest(name, names()) {
+        Some(closest) => format!("unknown hook `{}`, did you mean `{}`?", name, closest),
+        None => format!(
+            "unknown hook `{}`, expected a [hooks.{}] section",
+            name, name
+        ),
+    };
+
+    let references = [
+        ("tui.activate_hooks", &config.tui.activate_hooks),
+        ("tui.reopen_hooks", &config.tui.reopen_hooks),
+    ]
+    .into_iter()
+    .flat_map(|(list, hooks)| {
+        hooks
+            .iter()
+            .enumerate()
+            .map(move |(i, hook)| (format!("{}[{}].hook", list, i), hook.hook.as_str()))
+    })
+    .chain(
+        config
+            .notifications
+            .hooks
+            .iter()
+            .enumerate()
+            .map(|(i, hook)| {
+                (
+                    format!("notifications.hooks[{}].hook", i),
+                    hook.hook.as_str(),
+                )
+            }),
+    );
+    for (key, name) in references {
+        if !name.is_empty() && !config.hooks.contains_key(name) {
+            return Err((key, unknown(name)));
+        }
+    }
+
+    for (key, name) in &config.tui.keys {
+        let path = format!("tui.keys.{}", key);
+        let mut chars = key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return Err((path, "keys must be a single character".to_string()));
+        };
+        if RESERVED_KEYS.contains(c) {
+            return Err((path, format!("`{}` is a built-in key", c)));
+        }
+        if !config.hooks.contains_key(name) {
+            return Err((path, unknown(name)));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `tui.theme` names a built-in or `[themes]` theme and that
+/// every theme's `base` is a built-in.
+///
+/// Returns the dotted path of the offending key and a message.
+fn check_themes(config: &Config) -> Result<(), (String, String)> {
+    use crate::tui::theme::BUILTIN_THEMES;
+
+    for (name, theme) in &config.themes {
+        if let Some(base) = theme.base.as_deref() {
+            if !BUILTIN_THEMES.contains(&base) {
+                let message = match closest(base, BUILTIN_THEMES.iter().copied()) {
+                    Some(closest) => {
+                        format!("unknown base theme `{}`, did you mean `{}`?", base, closest)
+                    }
+                    None => format!(
+                        "unknown base theme `{}`, expected one of `{}`",
+                        base,
+                        BUILTIN_THEMES.join("`, `")
+                    ),
+                };
+                return Err((format!("themes.{}.base", name), message));
+            }
+        }
+    }
+
+    let theme = config.tui.theme.as_str();
+    if BUILTIN_THEMES.contains(&theme) || config.themes.contains_key(theme) {
+        return Ok(());
+    }
+    let names = BUILTIN_THEMES
+        .iter()
+        .copied()
+        .chain(config.themes.keys().map(String::as_str));
+    let message = match closest(theme, names.clone()) {
+        Some(closest) => format!("unknown theme `{}`, did you mean `{}`?", theme, closest),
+        None => format!(
+            "unknown theme `{}`, expected a [themes.{}] section or one of `{}`",
+            theme,
+            theme,
+            names.collect::<Vec<_>>().join("`, `")
+        ),
+    };
+    Err(("tui.theme".to_string(), message))
+}
+
+/// The candidate closest to `name`, if one is close enough to be a typo.
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|(score, _)| *score >= 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate)
+}
+
+/// Rewrites serde's unknown field or variant `message` to suggest the
+/// closest expected name, if one is close enough to be a typo.
+///
+/// Other messages are returned as is.
+fn with_hint(message: &str) -> String {
+    let hint = ["unknown field ", "unknown variant "]
+        .iter()
+        .find_map(|prefix| message.strip_prefix(prefix))
+        .and_then(|rest| {
+            let (name, expected) = rest.split_once(", expected ")?;
+            let name = name.trim_matches('`');
+            let candidates = expected.split('`').skip(1).step_by(2);
+            Some((name, closest(name, candidates)?))
+        });
+    match hint {
+        Some((name, closest)) => {
+            let kind = message.split('`').next().unwrap_or_default().trim();
+            format!("{} `{}`, did you mean `{}`?", kind, name, closest)
+        }
+        None => message.to_string(),
+    }
+}
+
+/// The process environment, skipping variables that are not Unicode.
+pub(crate) fn env_vars() -> impl Iterator<Item = (String, String)> {
+    std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+}
+
+/// Key of `table` matching the lowercase env segment `segment`.
+fn find_key(table: &toml::Table, segment: &str) -> Option<String> {
+    table
+        .keys()
+        .find(|key| key.to_ascii_lowercase().replace('-', "_") == segment)
+        .cloned()
+}
+
+/// Sets the key at env `segments` in `root` to `raw` and returns its dotted
+/// path.
+///
+/// Every segment but the last must name an existing table. `raw` replaces a
+/// string as is, and is parsed as TOML otherwise, falling back to a string.
+fn set_override(root: &mut toml::Value, segments: &[String], raw: &str) -> Result<String, String> {
+    let (last, parents) = segments
+        .split_last()
+        .filter(|(last, _)| !last.is_empty())
+        .ok_or_else(|| "empty key".to_string())?;
+    let mut path = Vec::new();
+    let mut table = root
+        .as_table_mut()
+        .ok_or_else(|| "configuration is not a table".to_string())?;
+    for segment in parents {
+        let key = find_key(table, segment).ok_or_else(|| {
+            path.push(segment.as_str().to_string());
+            format!("unknown key `{}`", path.join("."))
+        })?;
+        path.push(key.clone());
+        table = table
+            .get_mut(&key)
+            .and_then(toml::Value::as_table_mut)
+            .ok_or_else(|| format!("`{}` is not a table", path.join(".")))?;
+    }
+    // Keys of unset optional values are missing until set
+    let key = find_key(table, last).unwrap_or_else(|| last.clone());
+    path.push(key.clone());
+    let value = match table.get(&key) {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        _ => toml::from_str::<toml::Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut parsed| parsed.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(raw.to_string())),
+    };
+    table.insert(key, value);
+    Ok(path.join("."))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +720,7 @@ log_level = "debug"
                 line,
                 column,
                 message,
+                ..
             } => {
                 assert_eq!(p, path);
                 assert!(line > 0, "line should be > 0 for known span");
@@ -187,6 +747,96 @@ log_level = "debug"
         );
     }
 
+    #[test]
+    fn load_from_path_does_not_upgrade_old_file() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file = dir.path().join("config.toml");
+        let old = "[tui]\ndouble_click_hook = \"true\"\n";
+        fs::write(&file, old).expect("failed to write temp file");
+
+        match ConfigLoader::load_from_path(&file).expect_err("should fail") {
+            ConfigError::ParseError { message, .. } => {
+                assert!(message.contains("acd config migrate"), "{message}");
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+        assert_eq!(fs::read_to_string(&file).expect("file is readable"), old);
+        assert_eq!(fs::read_dir(dir.path()).expect("dir").count(), 1);
+    }
+
+    #[test]
+    fn migrate_file_upgrades_old_file_with_backup() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file = dir.path().join("config.toml");
+        let old = "[tui]\ndouble_click_hook = \"true\"\n";
+        fs::write(&file, old).expect("failed to write temp file");
+
+        let (migration, backup) = ConfigLoader::migrate_file(&file)
+            .expect("should upgrade")
+            .expect("has changes");
+        assert_eq!(migration.from, 0);
+        assert_eq!(
+            fs::read_to_string(&backup).expect("backup is readable"),
+            old
+        );
+        let summary = upgrade_summary(&file, &migration, &backup);
+        assert!(summary.contains("from config version 0 to 1"), "{summary}");
+
+        let config = ConfigLoader::load_from_path(&file).expect("should load");
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.tui.activate_hooks.len(), 1);
+        assert_eq!(config.tui.activate_hooks[0].command, "true");
+
+        // Already upgraded: nothing changes, and no temporary file is left
+        assert!(ConfigLoader::migrate_file(&file)
+            .expect("should read")
+            .is_none());
+        assert_eq!(fs::read_dir(dir.path()).expect("dir").count(), 2);
+    }
+
+    #[test]
+    fn migrate_file_follows_symlink_and_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let real = dir.path().join("dotfiles.toml");
+        fs::write(&real, "[tui]\ndouble_click_hook = \"true\"\n").expect("failed to write");
+        fs::set_permissions(&real, fs::Permissions::from_mode(0o600))
+            .expect("failed to set permissions");
+        let link = dir.path().join("config.toml");
+        std::os::unix::fs::symlink(&real, &link).expect("failed to symlink");
+
+        let (_, backup) = ConfigLoader::migrate_file(&link)
+            .expect("should upgrade")
+            .expect("has changes");
+        assert!(fs::symlink_metadata(&link)
+            .expect("link exists")
+            .file_type()
+            .is_symlink());
+        let config = ConfigLoader::load_from_path(&real).expect("should load");
+        assert_eq!(config.version, CONFIG_VERSION);
+        for file in [&real, &backup] {
+            let mode = fs::metadata(file).expect("metadata").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{}", file.display());
+        }
+    }
+
+    #[test]
+    fn load_from_path_rejects_newer_version() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file = dir.path().join("config.toml");
+        fs::write(&file, "\nversion = 99\n").expect("failed to write temp file");
+        match ConfigLoader::load_from_path(&file).expect_err("should fail") {
+            ConfigError::ParseError {
+                line, key, message, ..
+            } => {
+                assert_eq!(key.as_deref(), Some("version"));
+                assert_eq!(line, 2);
+                assert!(message.contains("newer"), "{message}");
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+    }
+
     #[test]
     fn load_from_path_missing_file_returns_not_found() {
         let path = PathBuf::from("/tmp/nonexistent_acd_test_config.toml");
@@ -256,4 +906,332 @@ log_level = "debug"
         let err = ConfigLoader::parse_toml(toml_str, &path).expect_err("should fail");
         matches!(err, ConfigError::ParseError { .. });
     }
+
+    #[test]
+    fn parse_error_locates_unknown_key_and_suggests_name() {
+        let toml_str = "[daemon]\nlog_level = \"info\"\n\n[tui]\ntick_rat = \"1s\"\n";
+        let path = PathBuf::from("typo.toml");
+        let err = ConfigLoader::parse_toml(toml_str, &path).expect_err("should fail");
+        match err {
+            ConfigError::ParseError {
+                line,
+                column,
+                key,
+                message,
+                ..
+            } => {
+                assert_eq!((line, column), (5, 1));
+                assert_eq!(key.as_deref(), Some("tui.tick_rat"));
+                assert_eq!(
+                    message,
+                    "unknown field `tick_rat`, did you mean `tick_rate`?"
+                );
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_error_for_unknown_section_and_variant() {
+        let path = PathBuf::from("typo.toml");
+        let err = ConfigLoader::parse_toml("[deamon]\nlog_level = \"info\"\n", &path)
+            .expect_err("unknown section should fail");
+        match err {
+            ConfigError::ParseError {
+                line, key, message, ..
+            } => {
+                assert_eq!(line, 1);
+                assert_eq!(key.as_deref(), Some("deamon"));
+                assert!(message.contains("did you mean `daemon`?"), "{}", message);
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+
+        let err = ConfigLoader::parse_toml("[tui]\nlayout = \"compct\"\n", &path)
+            .expect_err("unknown variant should fail");
+        match err {
+            ConfigError::ParseError {
+                line, key, message, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(key.as_deref(), Some("tui.layout"));
+                assert_eq!(message, "unknown variant `compct`, did you mean `compact`?");
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_user_theme() {
+        let toml_str = "[tui]\ntheme = \"solar\"\n\n[themes.solar]\nbase = \"monochrome\"\naccent = \"#268bd2 bold\"\n";
+        let config =
+            ConfigLoader::parse_toml(toml_str, Path::new("x.toml")).expect("theme should parse");
+        assert_eq!(config.tui.theme, "solar");
+        assert_eq!(
+            config.themes["solar"].accent.as_deref(),
+            Some("#268bd2 bold")
+        );
+    }
+
+    #[test]
+    fn parse_error_for_bad_theme_color_and_name() {
+        let path = Path::new("x.toml");
+        let err = ConfigLoader::parse_toml("[themes.solar]\naccent = \"blu bold\"\n", path)
+            .expect_err("bad color should fail");
+        match err {
+            ConfigError::ParseError {
+                line, key, message, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(key.as_deref(), Some("themes.solar.accent"));
+                assert!(message.contains("invalid color `blu`"), "{}", message);
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+
+        let toml_str = "[tui]\nlayout = \"compact\"\ntheme = \"solr\"\n\n[themes.solar]\n";
+        let err = ConfigLoader::parse_toml(toml_str, path).expect_err("unknown theme");
+        match err {
+            ConfigError::ParseError {
+                line,
+                column,
+                key,
+                message,
+                ..
+            } => {
+                assert_eq!((line, column), (3, 1));
+                assert_eq!(key.as_deref(), Some("tui.theme"));
+                assert_eq!(message, "unknown theme `solr`, did you mean `solar`?");
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+
+        let err = ConfigLoader::parse_toml("[themes.solar]\nbase = \"dark\"\n", path)
+            .expect_err("unknown base");
+        match err {
+            ConfigError::ParseError { line, key, .. } => {
+                assert_eq!(line, 2);
+                assert_eq!(key.as_deref(), Some("themes.solar.base"));
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_hook_library_and_references() {
+        let toml_str = r#"
+[tui.keys]
+e = "open-editor"
+
+[[tui.activate_hooks]]
+hook = "open-editor"
+
+[[notifications.hooks]]
+hook = "notify"
+statuses = ["attention"]
+
+[hooks.open-editor]
+command = "code {working_dir}"
+
+[hooks.notify]
+command = "notify-send acd {name}"
+timeout = 2
+"#;
+        let config = ConfigLoader::parse_toml(toml_str, Path::new("x.toml"))
+            .expect("references should resolve");
+        assert_eq!(config.hooks.len(), 2);
+        assert_eq!(config.hooks["notify"].timeout, 2);
+        assert_eq!(config.tui.keys["e"], "open-editor");
+        assert_eq!(config.notifications.hooks[0].statuses, vec!["attention"]);
+        let resolved = config.tui.activate_hooks[0].resolve(&config.hooks);
+        assert_eq!(
+            resolved.map(|hook| hook.command.as_str()),
+            Some("code {working_dir}")
+        );
+    }
+
+    #[test]
+    fn parse_error_for_bad_hook_references() {
+        let path = Path::new("x.toml");
+        let toml_str = "[hooks.notify]\ncommand = \"true\"\n\n[[tui.activate_hooks]]\ncommand = \"true\"\n\n[[tui.activate_hooks]]\ntimeout = 3\nhook = \"notfy\"\n";
+        let err = ConfigLoader::parse_toml(toml_str, path).expect_err("unknown hook");
+        match err {
+            ConfigError::ParseError {
+                line, key, message, ..
+            } => {
+                assert_eq!(line, 9);
+                assert_eq!(key.as_deref(), Some("tui.activate_hooks[1].hook"));
+                assert_eq!(message, "unknown hook `notfy`, did you mean `notify`?");
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+
+        let toml_str = "[tui.keys]\nj = \"notify\"\n\n[hooks.notify]\ncommand = \"true\"\n";
+        let err = ConfigLoader::parse_toml(toml_str, path).expect_err("built-in key");
+        match err {
+            ConfigError::ParseError {
+                line, key, message, ..
+            } => {
+                assert_eq!(line, 2);
+                assert_eq!(key.as_deref(), Some("tui.keys.j"));
+                assert_eq!(message, "`j` is a built-in key");
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+
+        let err = ConfigLoader::parse_toml("[hooks.notify]\ntimeout = 3\n", path)
+            .expect_err("missing command");
+        match err {
+            ConfigError::ParseError { line, key, .. } => {
+                assert_eq!(line, 1);
+                assert_eq!(key.as_deref(), Some("hooks.notify"));
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_error_without_close_name_keeps_expected_list() {
+        let err = ConfigLoader::parse_toml("[tui]\nzzz = 1\n", Path::new("x.toml"))
+            .expect_err("should fail");
+        match err {
+            ConfigError::ParseError { message, .. } => {
+                assert!(
+                    message.starts_with("unknown field `zzz`, expected"),
+                    "{}",
+                    message
+                );
+                assert!(!message.contains("did you mean"), "{}", message);
+            }
+            other => panic!("expected ParseError, got: {other:?}"),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // load_project
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn load_project_finds_nearest_file() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let nested = dir.path().join("api/src/handlers");
+        fs::create_dir_all(&nested).expect("failed to create dirs");
+        assert_eq!(ConfigLoader::load_project(&nested).expect("no file"), None);
+
+        fs::write(dir.path().join(PROJECT_CONFIG_FILE), "tags = [\"outer\"]\n")
+            .expect("failed to write project config");
+        fs::write(
+            dir.path().join("api").join(PROJECT_CONFIG_FILE),
+            "display_name = \"api\"\nalerts = false\n\n[[activate_hooks]]\ncommand = \"true\"\n",
+        )
+        .expect("failed to write project config");
+        let project = ConfigLoader::load_project(&nested)
+            .expect("should load")
+            .expect("should find the api project");
+        assert_eq!(project.root, dir.path().join("api"));
+        assert_eq!(project.display_name.as_deref(), Some("api"));
+        assert!(!project.alerts);
+        assert!(project.tags.is_empty(), "outer file is not merged in");
+        assert_eq!(project.activate_hooks.map(|hooks| hooks.len()), Some(1));
+        assert_eq!(project.reopen_hooks, None);
+    }
+
+    #[test]
+    fn load_project_stops_at_repository_root() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let repo = dir.path().join("repo");
+        let nested = repo.join("src");
+        fs::create_dir_all(repo.join(".git")).expect("failed to create repo");
+        fs::create_dir_all(&nested).expect("failed to create dirs");
+        fs::write(dir.path().join(PROJECT_CONFIG_FILE), "tags = [\"outer\"]\n")
+            .expect("failed to write project config");
+        assert_eq!(ConfigLoader::load_project(&nested).expect("no file"), None);
+
+        fs::write(repo.join(PROJECT_CONFIG_FILE), "tags = [\"repo\"]\n")
+            .expect("failed to write project config");
+        let project = ConfigLoader::load_project(&nested)
+            .expect("should load")
+            .expect("should find the repo's file");
+        assert_eq!(project.tags, ["repo"]);
+    }
+
+    #[test]
+    fn load_project_reports_parse_errors() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::write(dir.path().join(PROJECT_CONFIG_FILE), "tags = \"x\"\n")
+            .expect("failed to write project config");
+        let err = ConfigLoader::load_project(dir.path()).expect_err("should fail");
+        assert!(matches!(err, ConfigError::ParseError { line: 1, .. }));
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn apply_env_overrides_keys() {
+        let config = ConfigLoader::apply_env(
+            Config::default(),
+            vars(&[
+                ("ACD_TUI__TICK_RATE", "100ms"),
+                ("ACD_AGENTS__CLAUDE_CODE__ENABLED", "false"),
+                ("ACD_DAEMON__LOG_LEVEL", "debug"),
+                ("ACD_NOTIFICATIONS__NTFY__STATUSES", "[\"attention\"]"),
+                ("ACD_NOTIFICATIONS__NTFY__PRIORITIES__ATTENTION", "5"),
+                ("ACD_SESSION_ID", "not-an-override"),
+                ("HOME", "/home/u"),
+            ]),
+        )
+        .expect("overrides should apply");
+        assert_eq!(config.tui.tick_rate, "100ms");
+        assert!(!config.agents.claude_code.enabled);
+        assert_eq!(
+            config.daemon.log_level,
+            crate::config::schema::LogLevel::Debug
+        );
+        assert_eq!(config.notifications.ntfy.statuses, vec!["attention"]);
+        assert_eq!(
+            config.notifications.ntfy.priorities.get("attention"),
+            Some(&5)
+        );
+    }
+
+    #[test]
+    fn apply_env_rejects_unknown_keys_and_bad_values() {
+        for (name, value, message) in [
+            (
+                "ACD_TUI__TICK_MS",
+                "100",
+                "tui.tick_ms: unknown field `tick_ms`, did you mean `tick_rate`?",
+            ),
+            ("ACD_NOPE__TICK_RATE", "1s", "unknown key `nope`"),
+            (
+                "ACD_TUI__TICK_RATE__MS",
+                "1",
+                "`tui.tick_rate` is not a table",
+            ),
+            ("ACD_DAEMON__LOG_LEVEL", "loud", "daemon.log_level"),
+            (
+                "ACD_AGENTS__CLAUDE_CODE__ENABLED",
+                "maybe",
+                "agents.claude-code.enabled",
+            ),
+        ] {
+            let err =
+                ConfigLoader::apply_env(Config::default(), vars(&[(name, value)])).expect_err(name);
+            match err {
+                ConfigError::EnvOverride {
+                    name: var,
+                    message: got,
+                } => {
+                    assert_eq!(var, name);
+                    assert!(got.contains(message), "{}: {}", name, got);
+                }
+                other => panic!("unexpected error for {}: {:?}", name, other),
+            }
+        }
+    }
 }
@@ -0,0 +1,294 @@
+//! Upgrades configuration files written for older versions of acd.
+//!
+//! The file's top-level `version` key records the format it was written in;
+//! files without one are version 0. [`migrate`] applies the steps between
+//! that version and [`CONFIG_VERSION`] to the file's text, keeping comments
+//! and layout, so renamed keys and moved sections keep their settings instead
+//! of failing as unknown keys.
+//!
+//! To change the format, append a list of steps to [`MIGRATIONS`]; the
+//! version follows from its length.
+
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
+
+/// Version of the configuration format this build reads.
+pub const CONFIG_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// One change to the format.
+enum Step {
+    /// Moves the top-level table `from` to `to`, merging it into `to` if the
+    /// file has both.
+    MoveTable {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Replaces the command string `table.from` with an entry of the hook list
+    /// `table.to`.
+    HookToList {
+        table: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+/// Steps upgrading each version to the next: `MIGRATIONS[n]` upgrades
+/// version `n`.
+const MIGRATIONS: &[&[Step]] = &[
+    // 0 -> 1: `[ui]` became `[tui]`, and the single `double_click_hook` /
+    // `activate_hook` command became the `[[tui.activate_hooks]]` list.
+    &[
+        Step::MoveTable {
+            from: "ui",
+            to: "tui",
+        },
+        Step::HookToList {
+            table: "tui",
+            from: "double_click_hook",
+            to: "activate_hooks",
+        },
+        Step::HookToList {
+            table: "tui",
+            from: "activate_hook",
+            to: "activate_hooks",
+        },
+    ],
+];
+
+/// An upgraded configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// Version the file was written in.
+    pub from: u32,
+    /// The upgraded file, with `version` set to [`CONFIG_VERSION`].
+    pub content: String,
+    /// What changed, one line per change, e.g. `moved [ui] to [tui]`.
+    pub changes: Vec<String>,
+}
+
+/// Upgrades `content` to [`CONFIG_VERSION`].
+///
+/// Returns `Ok(None)` if there is nothing to change, including for files
+/// that are not valid TOML or have a `version` of the wrong type: parsing
+/// reports those. Keys a step cannot convert, like a hook command that is
+/// not a string, are left for parsing to report too.
+///
+/// Returns the message for the `version` key if the file is newer than this
+/// build.
+pub fn migrate(content: &str) -> Result<Option<Migration>, String> {
+    let Ok(mut doc) = content.parse::<DocumentMut>() else {
+        return Ok(None);
+    };
+    let from = match doc.get("version") {
+        None => 0,
+        Some(item) => match item.as_integer().map(u32::try_from) {
+            Some(Ok(version)) => version,
+            _ => return Ok(None),
+        },
+    };
+    if from > CONFIG_VERSION {
+        return Err(format!(
+            "config version {} is newer than this acd supports ({}); upgrade acd",
+            from, CONFIG_VERSION
+        ));
+    }
+
+    let mut changes = Vec::new();
+    for step in MIGRATIONS[from as usize..].iter().copied().flatten() {
+        step.apply(&mut doc, &mut changes);
+    }
+    if changes.is_empty() {
+        return Ok(None);
+    }
+    doc.insert("version", toml_edit::value(i64::from(CONFIG_VERSION)));
+    Ok(Some(Migration {
+        from,
+        content: doc.to_string(),
+        changes,
+    }))
+}
+
+impl Step {
+    fn apply(&self, doc: &mut DocumentMut, changes: &mut Vec<String>) {
+        match *self {
+            Step::MoveTable { from, to } => move_table(doc, from, to, changes),
+            Step::HookToList { table, from, to } => {
+                if let Some(entries) = doc.get_mut(table).and_then(Item::as_table_mut) {
+                    hook_to_list(entries, table, from, to, changes);
+                }
+            }
+        }
+    }
+}
+
+/// Moves the top-level table `from` to `to`. Keys of `from` that `to`
+/// already has are dropped.
+fn move_table(doc: &mut DocumentMut, from: &str, to: &str, changes: &mut Vec<String>) {
+    if !doc.get(from).is_some_and(Item::is_table_like) {
+        return;
+    }
+    match doc.get(to) {
+        None => {
+            let old = doc.remove(from).expect("checked above");
+            doc.insert(to, old);
+            changes.push(format!("moved [{}] to [{}]", from, to));
+        }
+        Some(item) if item.is_table_like() => {
+            let old = doc.remove(from).expect("checked above");
+            let target = doc
+                .get_mut(to)
+                .and_then(Item::as_table_like_mut)
+                .expect("checked above");
+            for (key, item) in old.as_table_like().expect("checked above").iter() {
+                if target.contains_key(key) {
+                    changes.push(format!(
+                        "dropped {}.{}: {}.{} is already set",
+                        from, key, to, key
+                    ));
+                } else {
+                    target.insert(key, item.clone());
+                }
+            }
+            changes.push(format!("merged [{}] into [{}]", from, to));
+        }
+        // Not a table: parsing reports it
+        Some(_) => {}
+    }
+}
+
+/// Replaces the command string `from` of the table `name` with an entry of
+/// its hook list `to`. An empty command, which disabled the hook, is
+/// dropped.
+fn hook_to_list(table: &mut Table, name: &str, from: &str, to: &str, changes: &mut Vec<String>) {
+    let Some(command) = table.get(from).and_then(Item::as_str).map(str::to_string) else {
+        return;
+    };
+    if command.is_empty() {
+        table.remove(from);
+        changes.push(format!("removed empty {}.{}", name, from));
+        return;
+    }
+    match table.get_mut(to) {
+        None => {
+            let mut list = ArrayOfTables::new();
+            list.push(hook_table(&command));
+            table.insert(to, Item::ArrayOfTables(list));
+        }
+        Some(Item::ArrayOfTables(list)) => list.push(hook_table(&command)),
+        Some(Item::Value(Value::Array(list))) => {
+            let mut hook = InlineTable::new();
+            hook.insert("command", command.as_str().into());
+            list.push(hook);
+        }
+        // Not a list: parsing reports it
+        Some(_) => return,
+    }
+    table.remove(from);
+    changes.push(format!(
+        "replaced {}.{} with a [[{}.{}]] entry",
+        name, from, name, to
+    ));
+}
+
+/// A hook list entry running `command`.
+fn hook_table(command: &str) -> Table {
+    let mut hook = Table::new();
+    hook.insert("command", toml_edit::value(command));
+    hook
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_0_moves_ui_and_converts_hook_keeping_comments() {
+        let old = r#"# My config
+
+[ui]
+# the layout
+layout = "large"
+double_click_hook = "zellij action focus"
+
+[daemon]
+log_level = "info"
+"#;
+        let migration = migrate(old).expect("older file").expect("has changes");
+        assert_eq!(migration.from, 0);
+        assert_eq!(
+            migration.content,
+            r#"version = 1
+# My config
+
+[tui]
+# the layout
+layout = "large"
+
+[[tui.activate_hooks]]
+command = "zellij action focus"
+
+[daemon]
+log_level = "info"
+"#
+        );
+        assert_eq!(
+            migration.changes,
+            [
+                "moved [ui] to [tui]",
+                "replaced tui.double_click_hook with a [[tui.activate_hooks]] entry",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hook_is_appended_to_existing_list() {
+        let old = "[tui]\nactivate_hook = \"b\"\nactivate_hooks = [{ command = \"a\" }]\n";
+        let migration = migrate(old).expect("older file").expect("has changes");
+        assert_eq!(
+            migration.content,
+            "version = 1\n[tui]\nactivate_hooks = [{ command = \"a\" }, { command = \"b\" }]\n"
+        );
+    }
+
+    #[test]
+    fn test_merging_ui_into_tui_keeps_tui_values() {
+        let old = "[ui]\nlayout = \"large\"\ntheme = \"x\"\n\n[tui]\nlayout = \"compact\"\n";
+        let migration = migrate(old).expect("older file").expect("has changes");
+        assert_eq!(
+            migration.content,
+            "version = 1\n\n[tui]\nlayout = \"compact\"\ntheme = \"x\"\n"
+        );
+        assert_eq!(
+            migration.changes,
+            [
+                "dropped ui.layout: tui.layout is already set",
+                "merged [ui] into [tui]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_hook_is_removed() {
+        let migration = migrate("[tui]\ndouble_click_hook = \"\"\n")
+            .expect("older file")
+            .expect("has changes");
+        assert_eq!(migration.content, "version = 1\n[tui]\n");
+        assert_eq!(migration.changes, ["removed empty tui.double_click_hook"]);
+    }
+
+    #[test]
+    fn test_current_and_unparsable_files_are_left_alone() {
+        assert_eq!(migrate(""), Ok(None));
+        assert_eq!(migrate("[tui]\nlayout = \"large\"\n"), Ok(None));
+        assert_eq!(migrate("version = 1\n[ui]\nlayout = \"large\"\n"), Ok(None));
+        assert_eq!(migrate("[tui\n"), Ok(None));
+        assert_eq!(migrate("version = \"one\"\n[ui]\n"), Ok(None));
+        // Not a string: parsing reports it
+        assert_eq!(migrate("[tui]\ndouble_click_hook = 3\n"), Ok(None));
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let err = migrate("version = 99\n").expect_err("newer than this build");
+        assert!(err.contains("config version 99 is newer"), "{err}");
+    }
+}
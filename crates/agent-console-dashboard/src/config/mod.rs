@@ -7,8 +7,14 @@ pub mod error;
 /// Configuration file loader.
 pub mod loader;
 
+/// Upgrading configuration files written for older versions.
+pub mod migrate;
+
 /// TOML configuration schema types.
 pub mod schema;
 
 /// XDG Base Directory path resolution utilities.
 pub mod xdg;
+
+/// Watching the configuration file for changes.
+pub mod watch;
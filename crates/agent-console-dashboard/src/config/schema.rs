@@ -1,34 +1,49 @@
 //! TOML configuration schema types for the Agent Console Dashboard.
 //!
 //! All structs derive `Deserialize` and `Serialize` with sensible defaults via
-//! `#[serde(default)]`. Fields are annotated with hot-reload behavior in doc comments.
+//! `#[serde(default)]`, and reject unknown keys. Fields are annotated with
+//! hot-reload behavior in doc comments.
 //!
 //! Duration fields use human-readable strings (e.g. `"60m"`, `"3m"`, `"250ms"`)
 //! parsed by the `humantime` crate at the call site.
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use crate::config::migrate::CONFIG_VERSION;
+
 // ---------------------------------------------------------------------------
 // Hook types
 // ---------------------------------------------------------------------------
 
 /// A single hook command with an optional timeout.
 ///
-/// Used in `tui.activate_hooks` and `tui.reopen_hooks`. Each hook is spawned
-/// via `sh -c <command>` with session data available as environment variables
-/// (`ACD_SESSION_ID`, `ACD_WORKING_DIR`, `ACD_STATUS`) and as a JSON
-/// `SessionSnapshot` on stdin.
+/// Used in `tui.activate_hooks`, `tui.reopen_hooks`, and the `[hooks]`
+/// library. Each hook is spawned via `sh -c <command>` with session data
+/// available as environment variables (`ACD_SESSION_ID`, `ACD_WORKING_DIR`,
+/// `ACD_STATUS`) and as a JSON `SessionSnapshot` on stdin.
 ///
 /// Example TOML:
 /// ```toml
 /// [[tui.activate_hooks]]
 /// command = 'zellij action go-to-tab-name "$(basename "$ACD_WORKING_DIR")"'
 /// timeout = 5
+///
+/// [[tui.activate_hooks]]
+/// hook = "open-editor"
 /// ```
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct HookConfig {
-    /// Shell command to execute via `sh -c`.
+    /// Name of a `[hooks.<name>]` entry to run instead of `command`.
+    /// Empty means `command` runs.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hook: String,
+    /// Shell command to execute via `sh -c`. In `[hooks.<name>]` entries,
+    /// `{placeholder}` tokens are replaced with shell-quoted session values,
+    /// as in `tui.commands`; other hooks run the command as written.
     pub command: String,
     /// Maximum seconds to wait for the hook to complete.
     /// If the hook exceeds this duration it is killed.
@@ -39,12 +54,92 @@ pub struct HookConfig {
 impl Default for HookConfig {
     fn default() -> Self {
         Self {
+            hook: String::new(),
             command: String::new(),
             timeout: 5,
         }
     }
 }
 
+impl HookConfig {
+    /// The hook to run: the `library` entry named by `hook`, or this hook
+    /// itself when `hook` is empty. None if no entry has that name.
+    pub fn resolve<'a>(&'a self, library: &'a BTreeMap<String, HookConfig>) -> Option<&'a Self> {
+        if self.hook.is_empty() {
+            Some(self)
+        } else {
+            library.get(&self.hook)
+        }
+    }
+}
+
+/// A hook the daemon runs when a session changes status
+/// (`[[notifications.hooks]]`).
+///
+/// Runs like `tui.activate_hooks` entries, from the daemon's environment.
+///
+/// Example TOML:
+/// ```toml
+/// [[notifications.hooks]]
+/// hook = "notify"
+/// statuses = ["attention", "question"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransitionHookConfig {
+    /// Name of a `[hooks.<name>]` entry to run instead of `command`.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub hook: String,
+    /// Shell command to execute via `sh -c`.
+    pub command: String,
+    /// Maximum seconds to wait for the hook to complete. Default: 5 seconds.
+    pub timeout: u64,
+    /// Statuses that run the hook when entered. Empty means every status.
+    pub statuses: Vec<String>,
+}
+
+impl Default for TransitionHookConfig {
+    fn default() -> Self {
+        Self {
+            hook: String::new(),
+            command: String::new(),
+            timeout: 5,
+            statuses: Vec::new(),
+        }
+    }
+}
+
+impl TransitionHookConfig {
+    /// The hook part of this entry, without the status filter.
+    pub fn to_hook(&self) -> HookConfig {
+        HookConfig {
+            hook: self.hook.clone(),
+            command: self.command.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// A custom command in the TUI command palette (`[[tui.commands]]`).
+///
+/// Example TOML:
+/// ```toml
+/// [[tui.commands]]
+/// name = "lazygit"
+/// command = "lazygit"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionCommandConfig {
+    /// Label shown in the palette and used as the new pane's title.
+    pub name: String,
+    /// Shell command run via `sh -c` in a new terminal pane in the session's
+    /// working directory. `{placeholder}` tokens are replaced with
+    /// shell-quoted session values: `{session_id}`, `{name}`, `{status}`,
+    /// `{working_dir}`.
+    pub command: String,
+}
+
 // ---------------------------------------------------------------------------
 // Top-level Config
 // ---------------------------------------------------------------------------
@@ -53,22 +148,120 @@ impl Default for HookConfig {
 ///
 /// Corresponds to the full TOML file structure:
 /// ```toml
+/// version = 1
 /// [tui]
 /// [agents]
 /// [integrations]
+/// [notifications]
 /// [daemon]
 /// ```
-#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
+    /// Format version of the file. Older files are upgraded by
+    /// `acd config migrate` (see [`crate::config::migrate`]); a file without
+    /// one is version 0.
+    pub version: u32,
     /// TUI appearance and behavior settings.
     pub tui: TuiConfig,
     /// Agent-specific configuration.
     pub agents: AgentsConfig,
     /// Third-party integration settings.
     pub integrations: IntegrationsConfig,
+    /// Notifications sent by the daemon on session transitions.
+    pub notifications: NotificationsConfig,
     /// Daemon process settings.
     pub daemon: TomlDaemonConfig,
+    /// OTLP export of daemon metrics and traces.
+    pub telemetry: TelemetryConfig,
+    /// StatsD metrics emitter.
+    pub statsd: StatsdConfig,
+    /// User-defined color themes by name, selected with `tui.theme`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, ThemeConfig>,
+    /// Named hooks (`[hooks.<name>]`), referenced by `hook = "<name>"` from
+    /// hook lists, `tui.keys`, and `notifications.hooks`.
+    /// Hot-reloadable: Yes in the TUI; the daemon reads them at startup.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub hooks: BTreeMap<String, HookConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            tui: TuiConfig::default(),
+            agents: AgentsConfig::default(),
+            integrations: IntegrationsConfig::default(),
+            notifications: NotificationsConfig::default(),
+            daemon: TomlDaemonConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            statsd: StatsdConfig::default(),
+            themes: BTreeMap::new(),
+            hooks: BTreeMap::new(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Project overrides
+// ---------------------------------------------------------------------------
+
+/// Per-project overrides from a `.acd.toml` in a project's root directory.
+///
+/// Applies to sessions whose working directory is inside the project. Only
+/// the nearest `.acd.toml` counts; files further up are not merged in.
+/// Precedence, highest first: a session's rename, the project file, the user
+/// config, built-in defaults. Its hooks only run if the root is listed in
+/// `tui.trusted_projects`.
+///
+/// Hot-reloadable: Yes (re-read when the user config reloads).
+///
+/// ```toml
+/// display_name = "api"
+/// tags = ["backend"]
+/// alerts = false
+///
+/// [[activate_hooks]]
+/// command = 'code "$ACD_WORKING_DIR"'
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Directory containing the `.acd.toml`. Set by the loader.
+    #[serde(skip)]
+    pub root: PathBuf,
+    /// Name shown for the project's sessions unless they are renamed.
+    /// None uses the directory name.
+    pub display_name: Option<String>,
+    /// Tags shown in the project's session rows.
+    pub tags: Vec<String>,
+    /// Replaces `tui.activate_hooks` for the project's sessions. An empty
+    /// list disables them; None keeps the user config's.
+    pub activate_hooks: Option<Vec<HookConfig>>,
+    /// Replaces `tui.reopen_hooks` for the project's sessions, as
+    /// `activate_hooks`.
+    pub reopen_hooks: Option<Vec<HookConfig>>,
+    /// Whether `root` is listed in `tui.trusted_projects`, so the project's
+    /// hooks may run. Set by the TUI.
+    #[serde(skip)]
+    pub trusted: bool,
+    /// Record sticky alerts when the project's sessions need attention.
+    pub alerts: bool,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            display_name: None,
+            tags: Vec::new(),
+            activate_hooks: None,
+            reopen_hooks: None,
+            trusted: false,
+            alerts: true,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -79,7 +272,7 @@ pub struct Config {
 ///
 /// Hot-reloadable: Yes (all fields).
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct TuiConfig {
     /// Active layout preset. Hot-reloadable: Yes.
     pub layout: LayoutPreset,
@@ -87,8 +280,33 @@ pub struct TuiConfig {
     /// Hot-reloadable: Yes.
     pub widgets: Vec<String>,
     /// Render tick rate as a human-readable duration (e.g. `"250ms"`).
-    /// Hot-reloadable: No (restart required).
+    /// Hot-reloadable: Yes.
     pub tick_rate: String,
+    /// Footer template with `{placeholder}` tokens, re-rendered every frame.
+    ///
+    /// Placeholders: `{keys_hint}`, `{session_count}`, `{attention_count}`,
+    /// `{working_count}`, `{question_count}`, `{usage_5h}`, `{usage_7d}`,
+    /// `{socket}`, `{version}`. Unknown placeholders are shown verbatim.
+    /// An empty string keeps the built-in footer (key hints plus usage).
+    /// Hot-reloadable: Yes.
+    pub footer: String,
+    /// How elapsed times are shown in session rows and the detail panel.
+    /// Toggle at runtime with `t`.
+    /// Hot-reloadable: Yes.
+    pub time_format: TimeFormat,
+    /// Disable mutating actions (hooks, rename, remove, resurrect).
+    /// `acd tui --read-only` enables it for a single run.
+    /// Hot-reloadable: Yes.
+    pub read_only: bool,
+    /// Show a tooltip with the full working directory and session ID when the
+    /// mouse hovers a session row or chip.
+    /// Hot-reloadable: Yes.
+    pub hover_tooltips: bool,
+    /// Resume closed sessions in a terminal pane embedded below the dashboard
+    /// instead of through the terminal backend. `reopen_hooks` still take
+    /// precedence. Ctrl+] switches focus between the dashboard and the pane.
+    /// Hot-reloadable: Yes.
+    pub embedded_terminal: bool,
     /// Hooks to execute on double-click of a non-closed session (activate action).
     ///
     /// Hooks run sequentially in order. Each hook is spawned via `sh -c` with:
@@ -108,6 +326,26 @@ pub struct TuiConfig {
     /// An empty list means double-click has no effect.
     /// Hot-reloadable: Yes.
     pub reopen_hooks: Vec<HookConfig>,
+    /// Project roots whose `.acd.toml` may replace the hooks above. Other
+    /// projects' files only set names, tags and alerts, since their hooks
+    /// run arbitrary commands. A leading `~` is expanded.
+    /// Hot-reloadable: Yes.
+    pub trusted_projects: Vec<String>,
+    /// Custom per-session commands listed in the command palette (`:`).
+    /// Hot-reloadable: Yes.
+    pub commands: Vec<SessionCommandConfig>,
+    /// Status icon set and per-status overrides (`[tui.icons]`).
+    /// Hot-reloadable: Yes.
+    pub icons: IconsConfig,
+    /// Color theme: a built-in (`default`, `monochrome`) or the name of a
+    /// `[themes.<name>]` section.
+    /// Hot-reloadable: Yes.
+    pub theme: String,
+    /// Extra dashboard keys (`[tui.keys]`): a single character mapped to the
+    /// name of a `[hooks.<name>]` entry, run for the selected session.
+    /// Built-in keys cannot be rebound.
+    /// Hot-reloadable: Yes.
+    pub keys: BTreeMap<String, String>,
 }
 
 impl Default for TuiConfig {
@@ -119,12 +357,95 @@ impl Default for TuiConfig {
                 "api-usage".to_string(),
             ],
             tick_rate: "250ms".to_string(),
+            footer: String::new(),
+            time_format: TimeFormat::default(),
+            read_only: false,
+            hover_tooltips: true,
+            embedded_terminal: false,
             activate_hooks: Vec::new(),
             reopen_hooks: Vec::new(),
+            trusted_projects: Vec::new(),
+            commands: Vec::new(),
+            icons: IconsConfig::default(),
+            theme: "default".to_string(),
+            keys: BTreeMap::new(),
         }
     }
 }
 
+/// A user-defined color theme (`[themes.<name>]`).
+///
+/// Each key is a style: a foreground color, `on <color>` for the background,
+/// and modifiers (`bold`, `dim`, `italic`, `underlined`, `reversed`), e.g.
+/// `"cyan bold"` or `"on #303030"`. Colors are names (`red`, `light-blue`),
+/// `#rrggbb`, or 256-color indexes (`237`). Unset keys come from `base`.
+///
+/// Hot-reloadable: Yes.
+///
+/// ```toml
+/// [tui]
+/// theme = "solar"
+///
+/// [themes.solar]
+/// accent = "#268bd2 bold"
+/// attention = "#b58900"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// Built-in theme supplying the unset keys. None uses `default`.
+    pub base: Option<String>,
+    /// Working sessions.
+    #[serde(deserialize_with = "style")]
+    pub working: Option<String>,
+    /// Sessions needing attention.
+    #[serde(deserialize_with = "style")]
+    pub attention: Option<String>,
+    /// Sessions asking a question.
+    #[serde(deserialize_with = "style")]
+    pub question: Option<String>,
+    /// Closed sessions.
+    #[serde(deserialize_with = "style")]
+    pub closed: Option<String>,
+    /// Inactive and dimmed sessions.
+    #[serde(deserialize_with = "style")]
+    pub inactive: Option<String>,
+    /// Borders, titles, column headers, and key hints.
+    #[serde(deserialize_with = "style")]
+    pub accent: Option<String>,
+    /// Emphasized text, like input being typed.
+    #[serde(deserialize_with = "style")]
+    pub text: Option<String>,
+    /// Secondary text: descriptions, placeholders, separators.
+    #[serde(deserialize_with = "style")]
+    pub muted: Option<String>,
+    /// Status messages, badges, project tags, and warnings.
+    #[serde(deserialize_with = "style")]
+    pub warning: Option<String>,
+    /// Errors and destructive actions.
+    #[serde(deserialize_with = "style")]
+    pub error: Option<String>,
+    /// Success indicators, like passing checks.
+    #[serde(deserialize_with = "style")]
+    pub success: Option<String>,
+    /// The selected session row.
+    #[serde(deserialize_with = "style")]
+    pub selection: Option<String>,
+    /// Rows and chips under the mouse.
+    #[serde(deserialize_with = "style")]
+    pub hover: Option<String>,
+}
+
+/// Deserializes a theme style, rejecting strings that are not valid styles.
+fn style<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let spec = String::deserialize(deserializer)?;
+    crate::tui::theme::parse_style(&spec).map_err(serde::de::Error::custom)?;
+    Ok(Some(spec))
+}
+
 /// Layout preset variants.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
@@ -135,6 +456,94 @@ pub enum LayoutPreset {
     Compact,
 }
 
+/// Status icon configuration (`[tui.icons]`).
+///
+/// Selects a built-in icon set and optionally overrides individual glyphs.
+/// Icons are used in session rows, compact chips, the condensed header, and
+/// the terminal title.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct IconsConfig {
+    /// Built-in icon set. Default: `ascii`.
+    pub set: IconSet,
+    /// Fall back to ASCII when the terminal does not appear to support
+    /// Unicode (non-UTF-8 locale or a limited `TERM` such as `linux`).
+    /// Default: true.
+    pub ascii_fallback: bool,
+    /// Override glyph for working sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working: Option<String>,
+    /// Override glyph for sessions needing attention.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attention: Option<String>,
+    /// Override glyph for sessions asking a question.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    /// Override glyph for closed sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed: Option<String>,
+    /// Override glyph for inactive sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inactive: Option<String>,
+}
+
+impl Default for IconsConfig {
+    fn default() -> Self {
+        Self {
+            set: IconSet::Ascii,
+            ascii_fallback: true,
+            working: None,
+            attention: None,
+            question: None,
+            closed: None,
+            inactive: None,
+        }
+    }
+}
+
+/// Built-in status icon sets.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+    /// Plain ASCII (`*`, `!`, `?`, `x`, `.`). Works everywhere.
+    #[default]
+    Ascii,
+    /// Emoji glyphs (two cells wide in most terminals).
+    Emoji,
+    /// Nerd Font glyphs. Requires a patched font.
+    NerdFont,
+}
+
+/// Display modes for elapsed times.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeFormat {
+    /// Duration since the status change (e.g. `2m 34s`).
+    #[default]
+    Relative,
+    /// Local wall-clock time of the status change (e.g. `since 14:32`).
+    Absolute,
+}
+
+impl TimeFormat {
+    /// Returns the other format (used by the runtime toggle).
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Relative => Self::Absolute,
+            Self::Absolute => Self::Relative,
+        }
+    }
+}
+
+impl std::fmt::Display for TimeFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Relative => write!(f, "relative"),
+            Self::Absolute => write!(f, "absolute"),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Agents
 // ---------------------------------------------------------------------------
@@ -143,18 +552,40 @@ pub enum LayoutPreset {
 ///
 /// Each supported agent has its own sub-section (e.g. `[agents.claude-code]`).
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct AgentsConfig {
     /// Claude Code agent settings.
     #[serde(rename = "claude-code")]
     pub claude_code: ClaudeCodeConfig,
+    /// User-defined agents (`[[agents.custom]]`), see [`crate::agents`].
+    pub custom: Vec<CustomAgentConfig>,
+}
+
+/// An agent that is not built in, reporting sessions via `acd agent-hook`.
+///
+/// Hot-reloadable: Yes.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomAgentConfig {
+    /// Name sessions are reported with (letters, digits, `-` and `_`).
+    pub name: String,
+    /// Name used in messages about the agent. Empty uses `name`.
+    pub display_name: String,
+    /// Tag shown before the directory in session rows. Empty uses `name`.
+    pub icon: String,
+    /// Lifecycle event name to status (working, attention, question, closed).
+    pub statuses: BTreeMap<String, String>,
+    /// Command that resumes a session in its working directory, split on
+    /// whitespace, with `{session_id}` and `{working_dir}` placeholders.
+    /// Empty means sessions cannot be resurrected.
+    pub resume: String,
 }
 
 /// Configuration for the Claude Code agent integration.
 ///
 /// Hot-reloadable: No (restart required for both fields).
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ClaudeCodeConfig {
     /// Whether Claude Code integration is active.
     pub enabled: bool,
@@ -176,18 +607,81 @@ impl Default for ClaudeCodeConfig {
 // ---------------------------------------------------------------------------
 
 /// Third-party integration configuration.
-#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
 pub struct IntegrationsConfig {
+    /// Terminal backend to use: detected from the environment (`auto`), a
+    /// specific backend, or `none`. Hot-reloadable: Yes.
+    pub terminal: TerminalChoice,
+    /// Bring the session's terminal window to the front on activate: switch
+    /// the tmux client to the pane's tmux session, and on macOS raise the
+    /// terminal app. Hot-reloadable: Yes.
+    pub raise_window: bool,
     /// Zellij terminal multiplexer integration.
     pub zellij: ZellijConfig,
+    /// tmux terminal multiplexer integration.
+    pub tmux: TmuxConfig,
+    /// kitty terminal remote-control integration.
+    pub kitty: KittyConfig,
+    /// WezTerm CLI integration.
+    pub wezterm: WeztermConfig,
+    /// GitHub pull request lookup through the `gh` CLI.
+    pub github: GithubConfig,
+}
+
+impl Default for IntegrationsConfig {
+    fn default() -> Self {
+        Self {
+            terminal: TerminalChoice::default(),
+            raise_window: true,
+            zellij: ZellijConfig::default(),
+            tmux: TmuxConfig::default(),
+            kitty: KittyConfig::default(),
+            wezterm: WeztermConfig::default(),
+            github: GithubConfig::default(),
+        }
+    }
+}
+
+/// Which terminal backend the TUI, `acd resurrect --exec` and the MCP server
+/// use for focusing and resurrecting sessions.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalChoice {
+    /// Detect the multiplexer or emulator from the environment.
+    #[default]
+    Auto,
+    /// Zellij, even when `ZELLIJ` is not set.
+    Zellij,
+    /// tmux, even when `TMUX` is not set (uses the default server).
+    Tmux,
+    /// kitty, whenever `KITTY_LISTEN_ON` is set.
+    Kitty,
+    /// WezTerm, even when `WEZTERM_PANE` is not set.
+    Wezterm,
+    /// No terminal backend.
+    None,
+}
+
+impl std::fmt::Display for TerminalChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Auto => "auto",
+            Self::Zellij => "zellij",
+            Self::Tmux => "tmux",
+            Self::Kitty => "kitty",
+            Self::Wezterm => "wezterm",
+            Self::None => "none",
+        };
+        f.write_str(name)
+    }
 }
 
 /// Zellij integration configuration.
 ///
-/// Hot-reloadable: No (restart required).
+/// Hot-reloadable: Yes.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ZellijConfig {
     /// Whether Zellij integration is active.
     pub enabled: bool,
@@ -199,6 +693,410 @@ impl Default for ZellijConfig {
     }
 }
 
+/// tmux integration configuration.
+///
+/// Hot-reloadable: Yes.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TmuxConfig {
+    /// Whether tmux integration is active.
+    pub enabled: bool,
+}
+
+impl Default for TmuxConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// kitty integration configuration.
+///
+/// Hot-reloadable: Yes.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct KittyConfig {
+    /// Whether the kitty `kitty @` integration is active.
+    pub enabled: bool,
+}
+
+impl Default for KittyConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// WezTerm integration configuration.
+///
+/// Hot-reloadable: Yes.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeztermConfig {
+    /// Whether the `wezterm cli` integration is active.
+    pub enabled: bool,
+}
+
+impl Default for WeztermConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// GitHub integration configuration.
+///
+/// Hot-reloadable: Yes.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GithubConfig {
+    /// Whether the detail panel shows the pull request of the session's branch.
+    pub enabled: bool,
+    /// How often a selected session's pull request is looked up again, as a
+    /// human-readable duration (e.g. `"1m"`).
+    pub refresh_interval: String,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            refresh_interval: "1m".to_string(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Notifications
+// ---------------------------------------------------------------------------
+
+/// Notification channels the daemon sends to.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationsConfig {
+    /// Native desktop notifications.
+    pub desktop: DesktopNotificationsConfig,
+    /// Slack incoming-webhook notifications.
+    pub slack: SlackConfig,
+    /// Discord webhook notifications.
+    pub discord: DiscordConfig,
+    /// ntfy push notifications.
+    pub ntfy: NtfyConfig,
+    /// Email digests of stuck sessions through an SMTP relay.
+    pub email: EmailConfig,
+    /// Generic JSON webhooks (`[[notifications.webhooks]]`).
+    pub webhooks: Vec<WebhookConfig>,
+    /// MQTT publishing of session transitions and usage.
+    pub mqtt: MqttConfig,
+    /// Shell hooks run on session transitions (`[[notifications.hooks]]`).
+    pub hooks: Vec<TransitionHookConfig>,
+}
+
+/// Desktop notification configuration (`notify-send`, `terminal-notifier`
+/// or `osascript`).
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DesktopNotificationsConfig {
+    /// Whether desktop notifications are sent. Off by default.
+    pub enabled: bool,
+    /// Statuses that trigger a notification when a session enters them
+    /// (`"working"`, `"attention"`, `"question"`, `"closed"`).
+    pub statuses: Vec<String>,
+    /// Minimum time between two notifications for the same session, as a
+    /// human-readable duration (e.g. `"1m"`). `"0s"` disables rate limiting.
+    pub min_interval: String,
+}
+
+impl Default for DesktopNotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            statuses: vec!["attention".to_string(), "question".to_string()],
+            min_interval: "1m".to_string(),
+        }
+    }
+}
+
+/// Slack notification configuration.
+///
+/// Posts to an incoming webhook when a session enters one of `statuses`, and
+/// when 5-hour API usage crosses `usage_threshold`.
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SlackConfig {
+    /// Incoming webhook URL. Empty disables Slack notifications.
+    pub webhook_url: String,
+    /// Channel override (e.g. `"#agents"`). Empty posts to the webhook's
+    /// default channel.
+    pub channel: String,
+    /// Statuses that trigger a message when a session enters them.
+    pub statuses: Vec<String>,
+    /// Message template (Slack mrkdwn).
+    ///
+    /// Placeholders: `{name}`, `{session_id}`, `{status}`, `{event}`,
+    /// `{working_dir}`, `{elapsed}`. Unknown placeholders are kept verbatim.
+    pub template: String,
+    /// Minimum time between two messages for the same session.
+    pub min_interval: String,
+    /// 5-hour usage percentage that triggers a message when crossed upward.
+    /// `0` disables usage messages.
+    pub usage_threshold: u8,
+}
+
+impl Default for SlackConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: String::new(),
+            channel: String::new(),
+            statuses: vec!["attention".to_string(), "question".to_string()],
+            template: "*{name}* {event} (`{working_dir}`)".to_string(),
+            min_interval: "1m".to_string(),
+            usage_threshold: 0,
+        }
+    }
+}
+
+/// Discord notification configuration.
+///
+/// Posts rich embeds to a channel webhook when a session enters one of
+/// `statuses`, and when 5-hour API usage crosses `usage_threshold`.
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscordConfig {
+    /// Channel webhook URL. Empty disables Discord notifications.
+    pub webhook_url: String,
+    /// Name the webhook posts as. Empty uses the webhook's own name.
+    pub username: String,
+    /// Statuses that trigger a message when a session enters them.
+    pub statuses: Vec<String>,
+    /// Embed title template, with the same placeholders as
+    /// `notifications.slack.template`.
+    pub template: String,
+    /// Minimum time between two messages for the same session.
+    pub min_interval: String,
+    /// 5-hour usage percentage that triggers a message when crossed upward.
+    /// `0` disables usage messages.
+    pub usage_threshold: u8,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: String::new(),
+            username: "acd".to_string(),
+            statuses: vec!["attention".to_string(), "question".to_string()],
+            template: "{name} {event}".to_string(),
+            min_interval: "1m".to_string(),
+            usage_threshold: 0,
+        }
+    }
+}
+
+/// ntfy push notification configuration.
+///
+/// Publishes to an ntfy topic, which phones subscribed to it show as push
+/// notifications, when a session enters one of `statuses` and when 5-hour
+/// API usage crosses `usage_threshold`.
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct NtfyConfig {
+    /// ntfy server URL.
+    pub server: String,
+    /// Topic to publish to. Empty disables ntfy notifications.
+    pub topic: String,
+    /// Access token for protected topics. Empty publishes anonymously.
+    pub token: String,
+    /// Statuses that trigger a notification when a session enters them.
+    pub statuses: Vec<String>,
+    /// Message template, with the same placeholders as
+    /// `notifications.slack.template`.
+    pub template: String,
+    /// ntfy priority (1 = min to 5 = max) per event: a status name, or
+    /// `usage` for usage alerts. Unlisted events use priority 3.
+    pub priorities: BTreeMap<String, u8>,
+    /// Minimum time between two notifications for the same session.
+    pub min_interval: String,
+    /// 5-hour usage percentage that triggers a notification when crossed
+    /// upward. `0` disables usage notifications.
+    pub usage_threshold: u8,
+}
+
+impl Default for NtfyConfig {
+    fn default() -> Self {
+        Self {
+            server: "https://ntfy.sh".to_string(),
+            topic: String::new(),
+            token: String::new(),
+            statuses: vec!["attention".to_string(), "question".to_string()],
+            template: "{name} {event}".to_string(),
+            priorities: BTreeMap::from([
+                ("attention".to_string(), 4),
+                ("question".to_string(), 4),
+                ("usage".to_string(), 4),
+            ]),
+            min_interval: "1m".to_string(),
+            usage_threshold: 0,
+        }
+    }
+}
+
+/// Email digest configuration.
+///
+/// Sends a digest through an SMTP relay when sessions stay in one of
+/// `statuses` for `stuck_after`, and (with `on_auto_stop`) one listing the
+/// remaining sessions before the idle daemon stops and drops them.
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmailConfig {
+    /// SMTP relay host. Empty disables email.
+    pub smtp_host: String,
+    /// SMTP relay port.
+    pub smtp_port: u16,
+    /// Connection security.
+    pub security: SmtpSecurity,
+    /// Username for `AUTH PLAIN`. Empty sends without authenticating.
+    pub username: String,
+    /// Password, used with `username`.
+    pub password: String,
+    /// Sender address. Empty uses `username`.
+    pub from: String,
+    /// Recipient addresses. Empty disables email.
+    pub to: Vec<String>,
+    /// Statuses a session can be stuck in.
+    pub statuses: Vec<String>,
+    /// How long a session stays in one of `statuses` before it is reported.
+    pub stuck_after: String,
+    /// Whether to send a digest before the daemon auto-stops with sessions
+    /// left.
+    pub on_auto_stop: bool,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            smtp_host: String::new(),
+            smtp_port: 587,
+            security: SmtpSecurity::default(),
+            username: String::new(),
+            password: String::new(),
+            from: String::new(),
+            to: Vec::new(),
+            statuses: vec!["attention".to_string(), "question".to_string()],
+            stuck_after: "30m".to_string(),
+            on_auto_stop: true,
+        }
+    }
+}
+
+/// SMTP connection security.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with `STARTTLS` (usually port 587).
+    #[default]
+    Starttls,
+    /// TLS from the start (usually port 465).
+    Tls,
+    /// No encryption, e.g. for a relay on localhost.
+    None,
+}
+
+/// One generic webhook endpoint.
+///
+/// POSTs a JSON event with a `SessionSnapshot` (or usage data) to `url` for
+/// each of `events`: `"session_created"`, `"status_changed"`,
+/// `"usage_threshold"`.
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Endpoint URL. Empty disables the entry.
+    pub url: String,
+    /// Events to send.
+    pub events: Vec<String>,
+    /// Statuses that trigger `status_changed`. Empty means every status.
+    pub statuses: Vec<String>,
+    /// Only send session events for sessions in or below one of these
+    /// directories. Empty means every session.
+    pub working_dirs: Vec<String>,
+    /// 5-hour usage percentage that triggers `usage_threshold`.
+    pub usage_threshold: u8,
+    /// Extra HTTP headers, e.g. `Authorization`.
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            events: vec!["status_changed".to_string()],
+            statuses: Vec::new(),
+            working_dirs: Vec::new(),
+            usage_threshold: 80,
+            headers: BTreeMap::new(),
+        }
+    }
+}
+
+/// MQTT publisher configuration.
+///
+/// Publishes the status of a session (e.g. `attention`) to `status_topic`
+/// when it enters one of `statuses`, and every usage update as JSON to
+/// `usage_topic`, for home-automation setups.
+///
+/// Hot-reloadable: No (restart daemon to apply changes).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MqttConfig {
+    /// Broker URL: `mqtt://host:1883`, or `mqtts://host:8883` for TLS.
+    /// Empty disables MQTT.
+    pub broker: String,
+    /// Client ID presented to the broker.
+    pub client_id: String,
+    /// Username. Empty connects anonymously.
+    pub username: String,
+    /// Password, used with `username`.
+    pub password: String,
+    /// Topic template for session statuses.
+    ///
+    /// Placeholders: `{name}`, `{session_id}`, `{status}`, `{event}`,
+    /// `{working_dir}`, `{elapsed}`. Unknown placeholders are kept verbatim.
+    pub status_topic: String,
+    /// Topic for usage updates. Empty disables usage publishing.
+    pub usage_topic: String,
+    /// Statuses that publish when a session enters them. Empty means every
+    /// status.
+    pub statuses: Vec<String>,
+    /// Quality of service: `0`, `1` or `2`.
+    pub qos: u8,
+    /// Whether messages are retained, so new subscribers get the last value.
+    pub retain: bool,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            broker: String::new(),
+            client_id: "acd".to_string(),
+            username: String::new(),
+            password: String::new(),
+            status_topic: "acd/sessions/{session_id}/status".to_string(),
+            usage_topic: "acd/usage".to_string(),
+            statuses: Vec::new(),
+            qos: 1,
+            retain: true,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Daemon
 // ---------------------------------------------------------------------------
@@ -208,7 +1106,7 @@ impl Default for ZellijConfig {
 /// Named `TomlDaemonConfig` to avoid collision with the runtime
 /// `crate::DaemonConfig` (socket path / daemonize flag).
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct TomlDaemonConfig {
     /// Auto-stop after idle period (default: `"60m"` per D5 amendment).
     /// Hot-reloadable: Yes.
@@ -234,6 +1132,73 @@ impl Default for TomlDaemonConfig {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Telemetry
+// ---------------------------------------------------------------------------
+
+/// OpenTelemetry export of daemon metrics and IPC spans over OTLP/HTTP.
+///
+/// Only used by builds with the `telemetry` feature.
+///
+/// Hot-reloadable: No (restart the daemon).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// Whether the daemon exports telemetry.
+    pub enabled: bool,
+    /// Base URL of the OTLP/HTTP collector; `/v1/traces` and `/v1/metrics`
+    /// are appended.
+    pub endpoint: String,
+    /// `service.name` resource attribute.
+    pub service_name: String,
+    /// How often metrics are exported, as a human-readable duration.
+    pub export_interval: String,
+    /// Extra HTTP headers sent with every export (e.g. authentication).
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318".to_string(),
+            service_name: "acd".to_string(),
+            export_interval: "30s".to_string(),
+            headers: BTreeMap::new(),
+        }
+    }
+}
+
+/// Periodic StatsD (or DogStatsD) metrics over UDP.
+///
+/// Hot-reloadable: No (restart the daemon).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatsdConfig {
+    /// Whether the daemon sends metrics.
+    pub enabled: bool,
+    /// `host:port` of the StatsD server.
+    pub address: String,
+    /// Prefix of every metric name.
+    pub prefix: String,
+    /// How often metrics are sent, as a human-readable duration.
+    pub interval: String,
+    /// Send statuses as DogStatsD tags instead of metric name segments.
+    pub dogstatsd: bool,
+}
+
+impl Default for StatsdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1:8125".to_string(),
+            prefix: "acd".to_string(),
+            interval: "10s".to_string(),
+            dogstatsd: false,
+        }
+    }
+}
+
 /// Log verbosity levels (kebab-case in TOML).
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
@@ -273,6 +1238,16 @@ hooks_path = "/custom/hooks"
 [integrations.zellij]
 enabled = false
 
+[integrations.kitty]
+enabled = false
+
+[integrations.wezterm]
+enabled = false
+
+[integrations.github]
+enabled = false
+refresh_interval = "5m"
+
 [daemon]
 idle_timeout = "30m"
 usage_fetch_interval = "5m"
@@ -286,12 +1261,53 @@ log_file = "/var/log/acd.log"
         assert!(!config.agents.claude_code.enabled);
         assert_eq!(config.agents.claude_code.hooks_path, "/custom/hooks");
         assert!(!config.integrations.zellij.enabled);
+        assert!(!config.integrations.kitty.enabled);
+        assert!(!config.integrations.wezterm.enabled);
+        assert!(!config.integrations.github.enabled);
+        assert_eq!(config.integrations.github.refresh_interval, "5m");
         assert_eq!(config.daemon.idle_timeout, "30m");
         assert_eq!(config.daemon.usage_fetch_interval, "5m");
         assert_eq!(config.daemon.log_level, LogLevel::Debug);
         assert_eq!(config.daemon.log_file, "/var/log/acd.log");
     }
 
+    #[test]
+    fn parse_time_format_and_toggle() {
+        let config: Config =
+            toml::from_str("[tui]\ntime_format = \"absolute\"\n").expect("valid TOML should parse");
+        assert_eq!(config.tui.time_format, TimeFormat::Absolute);
+        assert_eq!(config.tui.time_format.toggled(), TimeFormat::Relative);
+        assert_eq!(TimeFormat::default(), TimeFormat::Relative);
+    }
+
+    #[test]
+    fn parse_integrations_terminal() {
+        let config: Config = toml::from_str("[integrations]\nterminal = \"wezterm\"\n")
+            .expect("valid TOML should parse");
+        assert_eq!(config.integrations.terminal, TerminalChoice::Wezterm);
+        assert_eq!(TerminalChoice::default(), TerminalChoice::Auto);
+        assert!(config.integrations.raise_window);
+        assert!(toml::from_str::<Config>("[integrations]\nterminal = \"iterm\"\n").is_err());
+    }
+
+    #[test]
+    fn parse_tui_commands() {
+        let toml_str = r#"
+[[tui.commands]]
+name = "lazygit"
+command = "lazygit"
+
+[[tui.commands]]
+name = "tests"
+command = "cargo test; read"
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        assert_eq!(config.tui.commands.len(), 2);
+        assert_eq!(config.tui.commands[1].name, "tests");
+        assert_eq!(config.tui.commands[1].command, "cargo test; read");
+        assert!(Config::default().tui.commands.is_empty());
+    }
+
     #[test]
     fn parse_empty_string_uses_all_defaults() {
         let config: Config = toml::from_str("").expect("empty string should parse");
@@ -300,15 +1316,12 @@ log_file = "/var/log/acd.log"
     }
 
     #[test]
-    fn parse_unknown_fields_are_ignored() {
-        let toml_str = r#"
-unknown_key = "hello"
-
-[tui]
-future_field = 42
-"#;
-        let config: Config = toml::from_str(toml_str).expect("unknown fields should be ignored");
-        assert_eq!(config.tui.layout, LayoutPreset::Default);
+    fn parse_unknown_fields_are_rejected() {
+        for toml_str in ["unknown_key = \"hello\"", "[tui]\nfuture_field = 42"] {
+            let result: Result<Config, _> = toml::from_str(toml_str);
+            let err = result.expect_err("unknown fields should be rejected");
+            assert!(err.message().starts_with("unknown field"), "{}", err);
+        }
     }
 
     #[test]
@@ -403,6 +1416,235 @@ future_field = 42
         assert!(config.integrations.zellij.enabled);
     }
 
+    #[test]
+    fn default_kitty_enabled() {
+        let config = Config::default();
+        assert!(config.integrations.kitty.enabled);
+    }
+
+    #[test]
+    fn default_wezterm_enabled() {
+        let config = Config::default();
+        assert!(config.integrations.wezterm.enabled);
+    }
+
+    #[test]
+    fn parse_telemetry() {
+        let config: Config = toml::from_str(
+            r#"
+[telemetry]
+enabled = true
+endpoint = "https://otel.example.com"
+headers = { authorization = "Bearer x" }
+"#,
+        )
+        .expect("valid TOML should parse");
+        assert!(config.telemetry.enabled);
+        assert_eq!(config.telemetry.endpoint, "https://otel.example.com");
+        assert_eq!(config.telemetry.service_name, "acd");
+        assert_eq!(config.telemetry.export_interval, "30s");
+        assert_eq!(config.telemetry.headers["authorization"], "Bearer x");
+        assert!(!Config::default().telemetry.enabled);
+    }
+
+    #[test]
+    fn parse_statsd() {
+        let config: Config = toml::from_str(
+            r#"
+[statsd]
+enabled = true
+address = "statsd.local:9125"
+dogstatsd = true
+"#,
+        )
+        .expect("valid TOML should parse");
+        assert!(config.statsd.enabled);
+        assert_eq!(config.statsd.address, "statsd.local:9125");
+        assert_eq!(config.statsd.prefix, "acd");
+        assert_eq!(config.statsd.interval, "10s");
+        assert!(config.statsd.dogstatsd);
+        assert!(!Config::default().statsd.enabled);
+    }
+
+    #[test]
+    fn default_github_enabled() {
+        let config = Config::default();
+        assert!(config.integrations.github.enabled);
+        assert_eq!(config.integrations.github.refresh_interval, "1m");
+    }
+
+    #[test]
+    fn default_desktop_notifications_disabled() {
+        let config = Config::default();
+        let desktop = &config.notifications.desktop;
+        assert!(!desktop.enabled);
+        assert_eq!(desktop.statuses, vec!["attention", "question"]);
+        assert_eq!(desktop.min_interval, "1m");
+    }
+
+    #[test]
+    fn parse_desktop_notifications() {
+        let toml_str = r#"
+[notifications.desktop]
+enabled = true
+statuses = ["question"]
+min_interval = "30s"
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let desktop = &config.notifications.desktop;
+        assert!(desktop.enabled);
+        assert_eq!(desktop.statuses, vec!["question"]);
+        assert_eq!(desktop.min_interval, "30s");
+    }
+
+    #[test]
+    fn parse_slack_notifications() {
+        let toml_str = r##"
+[notifications.slack]
+webhook_url = "https://hooks.slack.com/services/T/B/X"
+channel = "#agents"
+usage_threshold = 80
+"##;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let slack = &config.notifications.slack;
+        assert_eq!(slack.webhook_url, "https://hooks.slack.com/services/T/B/X");
+        assert_eq!(slack.channel, "#agents");
+        assert_eq!(slack.usage_threshold, 80);
+        assert_eq!(slack.statuses, vec!["attention", "question"]);
+        assert_eq!(slack.template, SlackConfig::default().template);
+    }
+
+    #[test]
+    fn parse_discord_notifications() {
+        let toml_str = r#"
+[notifications.discord]
+webhook_url = "https://discord.com/api/webhooks/1/abc"
+statuses = ["attention"]
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let discord = &config.notifications.discord;
+        assert_eq!(
+            discord.webhook_url,
+            "https://discord.com/api/webhooks/1/abc"
+        );
+        assert_eq!(discord.statuses, vec!["attention"]);
+        assert_eq!(discord.username, "acd");
+        assert_eq!(discord.usage_threshold, 0);
+    }
+
+    #[test]
+    fn parse_ntfy_notifications() {
+        let toml_str = r#"
+[notifications.ntfy]
+topic = "my-agents"
+token = "tk_abc"
+usage_threshold = 90
+
+[notifications.ntfy.priorities]
+attention = 5
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let ntfy = &config.notifications.ntfy;
+        assert_eq!(ntfy.server, "https://ntfy.sh");
+        assert_eq!(ntfy.topic, "my-agents");
+        assert_eq!(ntfy.token, "tk_abc");
+        assert_eq!(ntfy.usage_threshold, 90);
+        assert_eq!(ntfy.statuses, vec!["attention", "question"]);
+        // A priorities table replaces the default mapping
+        assert_eq!(
+            ntfy.priorities,
+            BTreeMap::from([("attention".to_string(), 5)])
+        );
+    }
+
+    #[test]
+    fn parse_email_notifications() {
+        let toml_str = r#"
+[notifications.email]
+smtp_host = "smtp.example.com"
+smtp_port = 465
+security = "tls"
+username = "me@example.com"
+to = ["me@example.com"]
+stuck_after = "2h"
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let email = &config.notifications.email;
+        assert_eq!(email.smtp_host, "smtp.example.com");
+        assert_eq!(email.smtp_port, 465);
+        assert_eq!(email.security, SmtpSecurity::Tls);
+        assert_eq!(email.to, vec!["me@example.com"]);
+        assert_eq!(email.stuck_after, "2h");
+        assert_eq!(email.statuses, vec!["attention", "question"]);
+        assert!(email.on_auto_stop);
+    }
+
+    #[test]
+    fn parse_webhooks() {
+        let toml_str = r#"
+[[notifications.webhooks]]
+url = "https://example.com/acd"
+events = ["session_created", "status_changed"]
+working_dirs = ["/home/u/work"]
+headers = { Authorization = "Bearer t0ken" }
+
+[[notifications.webhooks]]
+url = "https://example.com/usage"
+events = ["usage_threshold"]
+usage_threshold = 90
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let hooks = &config.notifications.webhooks;
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].events, vec!["session_created", "status_changed"]);
+        assert_eq!(hooks[0].working_dirs, vec!["/home/u/work"]);
+        assert!(hooks[0].statuses.is_empty());
+        assert_eq!(hooks[0].headers["Authorization"], "Bearer t0ken");
+        assert_eq!(hooks[1].usage_threshold, 90);
+    }
+
+    #[test]
+    fn parse_mqtt_notifications() {
+        let toml_str = r#"
+[notifications.mqtt]
+broker = "mqtts://broker.local:8883"
+username = "acd"
+statuses = ["attention", "question"]
+qos = 0
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let mqtt = &config.notifications.mqtt;
+        assert_eq!(mqtt.broker, "mqtts://broker.local:8883");
+        assert_eq!(mqtt.username, "acd");
+        assert_eq!(mqtt.statuses, vec!["attention", "question"]);
+        assert_eq!(mqtt.qos, 0);
+        assert!(mqtt.retain);
+        assert_eq!(mqtt.status_topic, "acd/sessions/{session_id}/status");
+        assert_eq!(mqtt.usage_topic, "acd/usage");
+    }
+
+    #[test]
+    fn parse_custom_agents() {
+        let toml_str = r#"
+[[agents.custom]]
+name = "devbot"
+display_name = "DevBot"
+resume = "devbot --resume {session_id}"
+
+[agents.custom.statuses]
+prompt = "working"
+done = "attention"
+"#;
+        let config: Config = toml::from_str(toml_str).expect("valid TOML should parse");
+        let agents = &config.agents.custom;
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].name, "devbot");
+        assert_eq!(agents[0].icon, "");
+        assert_eq!(agents[0].statuses["prompt"], "working");
+        assert_eq!(agents[0].resume, "devbot --resume {session_id}");
+        assert!(config.agents.claude_code.enabled);
+    }
+
     #[test]
     fn default_log_level_is_info() {
         let config = Config::default();
@@ -494,6 +1736,7 @@ timeout = 5
         config.tui.activate_hooks = vec![HookConfig {
             command: "echo \"$ACD_SESSION_ID\"".to_string(),
             timeout: 3,
+            ..HookConfig::default()
         }];
         let toml_str = toml::to_string(&config).expect("serialization should succeed");
         let parsed: Config = toml::from_str(&toml_str).expect("roundtrip should parse");
@@ -511,6 +1754,7 @@ timeout = 5
         config.tui.reopen_hooks = vec![HookConfig {
             command: "zellij action new-tab".to_string(),
             timeout: 5,
+            ..HookConfig::default()
         }];
         let toml_str = toml::to_string(&config).expect("serialization should succeed");
         let parsed: Config = toml::from_str(&toml_str).expect("roundtrip should parse");
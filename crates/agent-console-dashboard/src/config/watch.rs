@@ -0,0 +1,140 @@
+//! Watching the configuration file so the TUI can apply edits while it runs.
+//!
+//! The file's directory is watched rather than the file, because editors that
+//! save atomically replace the file and would end a watch on the old one.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::config::error::ConfigError;
+use crate::config::loader::{env_vars, ConfigLoader};
+use crate::config::schema::Config;
+
+/// Handle for a running watch; watching stops when it is dropped.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    path: PathBuf,
+}
+
+impl ConfigWatcher {
+    /// Configuration file being watched.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Loads the config from `content`, the text of the file at `path`, or the
+/// defaults if the file is gone, with the environment's `ACD_*` overrides
+/// applied as at startup.
+fn load(content: Option<&str>, path: &Path) -> Result<Config, ConfigError> {
+    let config = match content {
+        Some(content) => ConfigLoader::parse_toml(content, path)?,
+        None => Config::default(),
+    };
+    ConfigLoader::apply_env(config, env_vars())
+}
+
+/// Calls `on_change` with the reloaded configuration, or the error that
+/// prevented loading it, whenever the contents of `path` change.
+///
+/// Deleting the file reloads the built-in defaults.
+///
+/// # Errors
+///
+/// Returns an error if the file's directory cannot be watched, for example
+/// because it does not exist.
+pub fn watch<F>(path: PathBuf, mut on_change: F) -> notify::Result<ConfigWatcher>
+where
+    F: FnMut(Result<Config, ConfigError>) + Send + 'static,
+{
+    let dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let file_name = path.file_name().map(|name| name.to_os_string());
+
+    let watched = path.clone();
+    let mut last = fs::read_to_string(&path).ok();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!("config watch error: {}", e);
+                return;
+            }
+        };
+        if event.kind.is_access()
+            || !event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name)
+        {
+            return;
+        }
+
+        // Several events arrive per save; report each new content once
+        let current = fs::read_to_string(&watched).ok();
+        if current == last {
+            return;
+        }
+        last = current;
+        on_change(load(last.as_deref(), &watched));
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    Ok(ConfigWatcher {
+        _watcher: watcher,
+        path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_load_missing_file_uses_defaults() {
+        let path = Path::new("/nonexistent/config.toml");
+        assert_eq!(load(None, path).expect("defaults"), Config::default());
+        let error = load(Some("[tui]\nfooter = 1\n"), path).unwrap_err();
+        assert!(matches!(error, ConfigError::ParseError { line: 2, .. }));
+    }
+
+    #[test]
+    fn test_watch_reports_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "").expect("write");
+        let (tx, rx) = mpsc::channel();
+        let _watcher = watch(path.clone(), move |result| {
+            let _ = tx.send(result.map(|config| config.tui.footer));
+        })
+        .expect("watch");
+
+        // A write may be seen half done (truncated) before its final content
+        let next = |expected: fn(&Result<String, ConfigError>) -> bool| loop {
+            let result = rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("change reported");
+            if expected(&result) {
+                break;
+            }
+        };
+        fs::write(&path, "[tui]\nfooter = \"{version}\"\n").expect("write");
+        next(|result| matches!(result, Ok(footer) if footer == "{version}"));
+        fs::write(&path, "[tui\n").expect("write");
+        next(Result::is_err);
+    }
+}
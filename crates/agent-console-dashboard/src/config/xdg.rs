@@ -2,11 +2,13 @@
 //!
 //! On **Linux**, follows the XDG Base Directory Specification:
 //! - Config: `$XDG_CONFIG_HOME/agent-console-dashboard` or `~/.config/agent-console-dashboard`
-//! - Runtime/socket: `$XDG_RUNTIME_DIR` or `/tmp`
+//! - Runtime: `$XDG_RUNTIME_DIR` or `/tmp`
+//! - Socket: `<runtime>/acd/daemon.sock` (`/tmp/acd-<uid>/daemon.sock` under `/tmp`)
 //!
 //! On **macOS**, uses Apple conventions with XDG env var overrides:
 //! - Config: `$XDG_CONFIG_HOME/agent-console-dashboard` or `~/Library/Application Support/agent-console-dashboard`
-//! - Runtime/socket: `$XDG_RUNTIME_DIR` or `$TMPDIR` or `/tmp`
+//! - Runtime: `$XDG_RUNTIME_DIR` or `$TMPDIR` or `/tmp`
+//! - Socket: `<runtime>/acd/daemon.sock`
 
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -43,11 +45,54 @@ fn platform_config_dir() -> PathBuf {
     }
 }
 
+/// Environment variable naming the active profile. `--profile` sets it, so
+/// the daemons and hooks a command spawns use the same profile.
+pub const PROFILE_ENV: &str = "ACD_PROFILE";
+
+/// Returns the active profile from `$ACD_PROFILE`, or None for the default
+/// configuration.
+///
+/// Names that [`parse_profile`] rejects are ignored with a warning.
+pub fn profile() -> Option<String> {
+    let name = std::env::var(PROFILE_ENV)
+        .ok()
+        .filter(|name| !name.is_empty())?;
+    match parse_profile(&name) {
+        Ok(name) => Some(name),
+        Err(e) => {
+            tracing::warn!("Ignoring {}: {}", PROFILE_ENV, e);
+            None
+        }
+    }
+}
+
+/// Checks that `name` is a usable profile name: ASCII letters, digits, `-`
+/// and `_`, as it becomes part of file names.
+pub fn parse_profile(name: &str) -> Result<String, String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(name.to_string())
+    } else {
+        Err(format!(
+            "invalid profile name '{name}' (use letters, digits, '-' and '_')"
+        ))
+    }
+}
+
 /// Returns the path to the main configuration file.
 ///
-/// Resolves to `config_dir()/config.toml`.
+/// Resolves to `config_dir()/config.toml`, or `config_dir()/profiles/<name>.toml`
+/// for the active [`profile`].
 pub fn config_path() -> PathBuf {
-    config_dir().join("config.toml")
+    match profile() {
+        Some(profile) => config_dir()
+            .join("profiles")
+            .join(format!("{profile}.toml")),
+        None => config_dir().join("config.toml"),
+    }
 }
 
 /// Returns the runtime directory for transient files (sockets, pid files).
@@ -80,11 +125,122 @@ fn platform_runtime_dir() -> PathBuf {
     }
 }
 
+/// Socket path of daemons from before the per-user socket directory.
+pub const LEGACY_SOCKET_PATH: &str = "/tmp/agent-console-dashboard.sock";
+
+/// Returns the per-user directory holding the daemon socket.
+///
+/// Resolves to `runtime_dir()/acd`, except under the shared `/tmp` fallback,
+/// where it is `/tmp/acd-<uid>` so users don't collide. Another user can
+/// create that name first, so check an existing one with
+/// [`check_private_dir`] before binding in it.
+pub fn socket_dir() -> PathBuf {
+    let dir = runtime_dir();
+    if dir == Path::new("/tmp") {
+        return dir.join(format!("acd-{}", current_uid()));
+    }
+    dir.join("acd")
+}
+
+/// Checks that `path` is a directory, not a symlink, owned by the current
+/// user with mode 0700.
+///
+/// # Errors
+///
+/// Returns `PermissionDenied` naming the problem if it is not, or the error
+/// reading its metadata.
+pub fn check_private_dir(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    let meta = fs::symlink_metadata(path)?;
+    let problem = if !meta.is_dir() {
+        Some("is not a directory".to_string())
+    } else if meta.uid() != current_uid() {
+        Some(format!("is owned by uid {}", meta.uid()))
+    } else if meta.permissions().mode() & 0o777 != 0o700 {
+        Some(format!(
+            "has mode {:o} instead of 700",
+            meta.permissions().mode() & 0o777
+        ))
+    } else {
+        None
+    };
+    match problem {
+        Some(problem) => Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("refusing to use {}: it {}", path.display(), problem),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The real user ID of this process.
+pub fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
 /// Returns the path to the Unix domain socket.
 ///
-/// Resolves to `runtime_dir()/agent-console-dashboard.sock`.
+/// Resolves to `socket_dir()/daemon.sock`, or `socket_dir()/<name>.sock` for
+/// the active [`profile`], so each profile runs its own daemon.
 pub fn socket_path() -> PathBuf {
-    runtime_dir().join(format!("{APP_NAME}.sock"))
+    match profile() {
+        Some(profile) => socket_dir().join(format!("{profile}.sock")),
+        None => socket_dir().join("daemon.sock"),
+    }
+}
+
+/// Returns the socket used when `--socket` is not given.
+///
+/// This is `socket_path()`, unless no daemon listens there while an older
+/// daemon still listens on [`LEGACY_SOCKET_PATH`]; clients then keep talking
+/// to that daemon until it stops. Profiles never use the legacy socket.
+pub fn default_socket_path() -> PathBuf {
+    if profile().is_some() {
+        return socket_path();
+    }
+    resolve_socket(socket_path(), Path::new(LEGACY_SOCKET_PATH))
+}
+
+/// `current`, or `legacy` if only `legacy` has a daemon listening.
+fn resolve_socket(current: PathBuf, legacy: &Path) -> PathBuf {
+    let listening = |path: &Path| connect(path).is_ok();
+    if !listening(&current) && listening(legacy) {
+        legacy.to_path_buf()
+    } else {
+        current
+    }
+}
+
+/// Checks the directory of a daemon `socket` before a client connects to it.
+///
+/// Only the per-user [`socket_dir`] is checked, with [`check_private_dir`]:
+/// under `/tmp` another user can create it first and listen there to receive
+/// every hook event. A missing directory passes, as no daemon runs yet.
+///
+/// # Errors
+///
+/// Returns `PermissionDenied` naming the problem if the directory is not
+/// private, or the error reading its metadata.
+pub fn check_socket_dir(socket: &Path) -> std::io::Result<()> {
+    let dir = socket_dir();
+    if socket.parent() != Some(dir.as_path()) {
+        return Ok(());
+    }
+    match check_private_dir(&dir) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Connects to the daemon at `socket` after [`check_socket_dir`].
+///
+/// # Errors
+///
+/// Returns the directory check's error, or the connection error.
+pub fn connect(socket: &Path) -> std::io::Result<std::os::unix::net::UnixStream> {
+    check_socket_dir(socket)?;
+    std::os::unix::net::UnixStream::connect(socket)
 }
 
 /// Expands a leading `~` in a path string to the user's home directory.
@@ -221,13 +377,120 @@ mod tests {
     fn test_socket_path_with_xdg_override() {
         with_env(&[("XDG_RUNTIME_DIR", Some("/run/user/1000"))], || {
             let path = socket_path();
+            assert_eq!(path, PathBuf::from("/run/user/1000/acd/daemon.sock"));
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_profile_selects_config_and_socket() {
+        with_env(
+            &[
+                ("XDG_CONFIG_HOME", Some("/custom/config")),
+                ("XDG_RUNTIME_DIR", Some("/run/user/1000")),
+                (PROFILE_ENV, Some("work")),
+            ],
+            || {
+                assert_eq!(profile().as_deref(), Some("work"));
+                assert_eq!(
+                    config_path(),
+                    PathBuf::from("/custom/config/agent-console-dashboard/profiles/work.toml")
+                );
+                assert_eq!(socket_path(), PathBuf::from("/run/user/1000/acd/work.sock"));
+                assert_eq!(default_socket_path(), socket_path());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_or_empty_profile_env_is_ignored() {
+        for value in ["", "../etc"] {
+            with_env(&[(PROFILE_ENV, Some(value))], || {
+                assert_eq!(profile(), None);
+                assert!(config_path().ends_with("config.toml"));
+            });
+        }
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(parse_profile("work_2-b"), Ok("work_2-b".to_string()));
+        for name in ["", "a/b", "..", "has space"] {
+            assert!(parse_profile(name).is_err(), "{name:?} should be rejected");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_socket_dir_under_tmp_is_per_user() {
+        if cfg!(target_os = "macos") {
+            return;
+        }
+        with_env(&[("XDG_RUNTIME_DIR", None)], || {
             assert_eq!(
-                path,
-                PathBuf::from("/run/user/1000/agent-console-dashboard.sock")
+                socket_dir(),
+                PathBuf::from(format!("/tmp/acd-{}", current_uid()))
             );
         });
     }
 
+    #[test]
+    fn test_check_private_dir() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let private = dir.path().join("private");
+        ensure_dir(&private).expect("failed to create dir");
+        assert!(check_private_dir(&private).is_ok());
+
+        fs::set_permissions(&private, fs::Permissions::from_mode(0o755))
+            .expect("failed to set permissions");
+        let err = check_private_dir(&private).expect_err("mode 755 is refused");
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+        assert!(err.to_string().contains("mode 755"), "{err}");
+
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&private, &link).expect("failed to symlink");
+        assert!(check_private_dir(&link).is_err(), "symlinks are refused");
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_socket_dir_refuses_shared_socket_dir() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let runtime = dir.path().to_str().expect("utf-8 temp dir");
+        with_env(&[("XDG_RUNTIME_DIR", Some(runtime))], || {
+            let socket = socket_dir().join("daemon.sock");
+            assert!(check_socket_dir(&socket).is_ok(), "missing dir passes");
+
+            fs::create_dir(socket_dir()).expect("failed to create dir");
+            fs::set_permissions(socket_dir(), fs::Permissions::from_mode(0o777))
+                .expect("failed to set permissions");
+            let err = connect(&socket).expect_err("shared dir is refused");
+            assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+
+            // An explicit socket elsewhere is the user's choice
+            assert!(check_socket_dir(&dir.path().join("other.sock")).is_ok());
+        });
+    }
+
+    #[test]
+    fn test_resolve_socket_prefers_current_path() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let current = dir.path().join("daemon.sock");
+        let legacy = dir.path().join("legacy.sock");
+        assert_eq!(resolve_socket(current.clone(), &legacy), current);
+
+        let _legacy_daemon =
+            std::os::unix::net::UnixListener::bind(&legacy).expect("failed to bind legacy");
+        assert_eq!(resolve_socket(current.clone(), &legacy), legacy);
+
+        let _daemon =
+            std::os::unix::net::UnixListener::bind(&current).expect("failed to bind current");
+        assert_eq!(resolve_socket(current.clone(), &legacy), current);
+    }
+
     #[test]
     #[serial]
     fn test_config_dir_with_xdg_override() {
@@ -0,0 +1,266 @@
+//! Email digests for unattended runs.
+//!
+//! Checks the store every minute and mails one digest of the sessions that
+//! have just spent `stuck_after` in one of `notifications.email.statuses`.
+//! Each session is reported once per stint in a status. Before the idle
+//! daemon auto-stops, [`send_auto_stop_digest`] mails the sessions it is
+//! about to drop.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::config::schema::EmailConfig;
+use crate::daemon::store::SessionStore;
+use crate::integrations::email::Mailer;
+use crate::integrations::session_placeholder;
+use crate::tui::views::dashboard::format_duration_secs;
+use crate::{Session, Status};
+
+/// How often sessions are checked.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Finds sessions that just became stuck.
+#[derive(Debug)]
+pub(crate) struct StuckTracker {
+    statuses: Vec<Status>,
+    stuck_after: Duration,
+    /// Start of the stint each session was last reported for.
+    reported: HashMap<String, Instant>,
+}
+
+impl StuckTracker {
+    pub(crate) fn new(statuses: Vec<Status>, stuck_after: Duration) -> Self {
+        Self {
+            statuses,
+            stuck_after,
+            reported: HashMap::new(),
+        }
+    }
+
+    /// Returns the sessions stuck at `now` that were not reported yet, and
+    /// records them as reported.
+    pub(crate) fn newly_stuck<'a>(
+        &mut self,
+        sessions: &'a [Session],
+        now: Instant,
+    ) -> Vec<&'a Session> {
+        self.reported
+            .retain(|id, _| sessions.iter().any(|s| &s.session_id == id));
+        let mut stuck = Vec::new();
+        for session in sessions {
+            let is_stuck = !session.closed
+                && self.statuses.contains(&session.status)
+                && now.saturating_duration_since(session.since) >= self.stuck_after;
+            if is_stuck && self.reported.get(&session.session_id) != Some(&session.since) {
+                self.reported
+                    .insert(session.session_id.clone(), session.since);
+                stuck.push(session);
+            }
+        }
+        stuck
+    }
+}
+
+/// One digest line: name, ID, status, time in status and folder.
+fn digest_line(session: &Session, now: Instant) -> String {
+    let name = session_placeholder(session, "name").unwrap_or_default();
+    let mut line = format!(
+        "- {} ({}): {} for {}",
+        name,
+        session.session_id,
+        session.status,
+        format_duration_secs(now.saturating_duration_since(session.since).as_secs())
+    );
+    if let Some(dir) = &session.working_dir {
+        line.push_str(&format!("\n  {}", dir.display()));
+    }
+    line
+}
+
+/// Subject and body of the digest for `stuck` sessions.
+pub(crate) fn stuck_digest(stuck: &[&Session], now: Instant) -> (String, String) {
+    let subject = match stuck.len() {
+        1 => "[acd] 1 session is waiting for you".to_string(),
+        n => format!("[acd] {} sessions are waiting for you", n),
+    };
+    let mut body = String::from("These sessions have been waiting:\n\n");
+    for session in stuck {
+        body.push_str(&digest_line(session, now));
+        body.push('\n');
+    }
+    (subject, body)
+}
+
+/// Subject and body of the digest sent before auto-stop, or `None` if no
+/// open session would be dropped.
+pub(crate) fn auto_stop_digest(
+    sessions: &[Session],
+    idle_timeout: Duration,
+    now: Instant,
+) -> Option<(String, String)> {
+    let open: Vec<&Session> = sessions.iter().filter(|s| !s.closed).collect();
+    if open.is_empty() {
+        return None;
+    }
+    let subject = format!(
+        "[acd] Daemon stopping with {} open session{}",
+        open.len(),
+        if open.len() == 1 { "" } else { "s" }
+    );
+    let mut body = format!(
+        "No session has been active for {}, so the daemon is stopping and\n\
+         dropping these sessions from the dashboard:\n\n",
+        humantime::format_duration(idle_timeout)
+    );
+    for session in open {
+        body.push_str(&digest_line(session, now));
+        body.push('\n');
+    }
+    Some((subject, body))
+}
+
+/// Sends `subject` and `body`, logging failures.
+async fn send(mailer: &Mailer, subject: &str, body: &str) {
+    match mailer.send(subject, body).await {
+        Ok(()) => info!(subject, "sent email digest"),
+        Err(e) => warn!(error = %e, "failed to send email digest"),
+    }
+}
+
+/// Mails digests of stuck sessions until shutdown.
+///
+/// Returns immediately if email is not configured.
+pub(crate) async fn run(
+    store: SessionStore,
+    config: EmailConfig,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    let Some(mailer) = Mailer::from_config(&config) else {
+        return;
+    };
+    let statuses = config
+        .statuses
+        .iter()
+        .filter_map(|name| match Status::from_str(name) {
+            Ok(status) => Some(status),
+            Err(e) => {
+                warn!(section = "notifications.email", status = %name, "ignoring status: {}", e);
+                None
+            }
+        })
+        .collect();
+    let stuck_after = humantime::parse_duration(&config.stuck_after).unwrap_or_else(|e| {
+        warn!(stuck_after = %config.stuck_after, error = %e, "invalid email stuck_after, using 30m");
+        Duration::from_secs(30 * 60)
+    });
+    info!(relay = %config.smtp_host, "email digests enabled");
+
+    let mut tracker = StuckTracker::new(statuses, stuck_after);
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => return,
+            _ = interval.tick() => {
+                let sessions = store.list_all().await;
+                let now = Instant::now();
+                let stuck = tracker.newly_stuck(&sessions, now);
+                if !stuck.is_empty() {
+                    let (subject, body) = stuck_digest(&stuck, now);
+                    send(&mailer, &subject, &body).await;
+                }
+            }
+        }
+    }
+}
+
+/// Mails the open sessions the daemon is about to drop because it has been
+/// idle for `idle_timeout`. Does nothing if email or `on_auto_stop` is off.
+pub(crate) async fn send_auto_stop_digest(
+    store: &SessionStore,
+    config: &EmailConfig,
+    idle_timeout: Duration,
+) {
+    if !config.on_auto_stop {
+        return;
+    }
+    let Some(mailer) = Mailer::from_config(config) else {
+        return;
+    };
+    let sessions = store.list_all().await;
+    if let Some((subject, body)) = auto_stop_digest(&sessions, idle_timeout, Instant::now()) {
+        send(&mailer, &subject, &body).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    fn session(id: &str, status: Status, since: Instant) -> Session {
+        let mut session = Session::new(
+            id.to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from(format!("/work/{}", id))),
+        );
+        session.status = status;
+        session.closed = status == Status::Closed;
+        session.since = since;
+        session
+    }
+
+    #[test]
+    fn test_reports_each_stint_once() {
+        let start = Instant::now();
+        let mut tracker = StuckTracker::new(vec![Status::Attention], Duration::from_secs(60));
+        let mut sessions = vec![
+            session("a", Status::Attention, start),
+            session("w", Status::Working, start),
+        ];
+        let ids = |stuck: Vec<&Session>| -> Vec<String> {
+            stuck.iter().map(|s| s.session_id.clone()).collect()
+        };
+
+        assert!(tracker.newly_stuck(&sessions, start).is_empty());
+        let later = start + Duration::from_secs(61);
+        assert_eq!(ids(tracker.newly_stuck(&sessions, later)), ["a"]);
+        assert!(tracker.newly_stuck(&sessions, later).is_empty());
+
+        // A new stint in attention is reported again once stuck
+        sessions[0].since = later;
+        let much_later = later + Duration::from_secs(61);
+        assert_eq!(ids(tracker.newly_stuck(&sessions, much_later)), ["a"]);
+    }
+
+    #[test]
+    fn test_stuck_digest() {
+        let start = Instant::now();
+        let api = session("api", Status::Question, start);
+        let (subject, body) = stuck_digest(&[&api], start + Duration::from_secs(125));
+        assert_eq!(subject, "[acd] 1 session is waiting for you");
+        assert!(body.contains("- api (api): question for 2m 5s\n  /work/api\n"));
+    }
+
+    #[test]
+    fn test_auto_stop_digest_lists_open_sessions() {
+        let start = Instant::now();
+        let sessions = vec![
+            session("api", Status::Attention, start),
+            session("old", Status::Closed, start),
+        ];
+        let (subject, body) =
+            auto_stop_digest(&sessions, Duration::from_secs(3600), start).expect("digest");
+        assert_eq!(subject, "[acd] Daemon stopping with 1 open session");
+        assert!(body.contains("active for 1h,"));
+        assert!(body.contains("- api (api): attention"));
+        assert!(!body.contains("old"));
+
+        let closed = vec![session("old", Status::Closed, start)];
+        assert!(auto_stop_digest(&closed, Duration::from_secs(3600), start).is_none());
+    }
+}
@@ -32,7 +32,8 @@ pub(super) struct DaemonState {
 
 /// Handles the SET command.
 ///
-/// Expects `cmd.session_id` and `cmd.status`. Optional `cmd.working_dir`.
+/// Expects `cmd.session_id` and `cmd.status`. Optional `cmd.working_dir`,
+/// `cmd.last_message` and `cmd.terminal_pane` (stored only when present).
 /// Creates a new session if it doesn't exist, or updates the status if it does.
 ///
 /// After a successful update, if usage data is `Unavailable`, triggers a
@@ -66,12 +67,24 @@ pub(super) async fn handle_set_command(
         }
     };
 
+    let agent_type: AgentType = match cmd.agent_type.as_deref().map(str::parse) {
+        None => AgentType::default(),
+        Some(Ok(agent_type)) => agent_type,
+        Some(Err(e)) => {
+            return IpcResponse::error(format!(
+                "{} (expected: claudecode, codex, geminicli, aider, or a custom agent name)",
+                e
+            ))
+            .to_json_line();
+        }
+    };
+
     let priority = cmd.priority.unwrap_or(0);
 
-    let session = store
+    let mut session = store
         .get_or_create_session(
             session_id.clone(),
-            AgentType::ClaudeCode,
+            agent_type,
             working_dir,
             None,
             status,
@@ -79,6 +92,17 @@ pub(super) async fn handle_set_command(
         )
         .await;
 
+    if let Some(message) = cmd.last_message.clone() {
+        if let Some(updated) = store.set_last_message(session_id, message).await {
+            session = updated;
+        }
+    }
+    if let Some(pane) = cmd.terminal_pane.clone() {
+        if let Some(updated) = store.set_terminal_pane(session_id, pane).await {
+            session = updated;
+        }
+    }
+
     let short_id = &session_id[..session_id.len().min(8)];
     match &session.working_dir {
         Some(dir) => tracing::info!(
@@ -256,6 +280,9 @@ pub(super) async fn handle_sub_command(
                                     history: vec![],
                                     closed: update.status == Status::Closed,
                                     priority: 0,
+                                    display_name: None,
+                                    last_message: None,
+                                    terminal_pane: None,
                                 };
                                 IpcNotification::session_update(info)
                             };
@@ -322,6 +349,9 @@ pub(super) async fn handle_sub_command(
                             history: vec![],
                             closed: update.status == Status::Closed,
                             priority: 0,
+                            display_name: None,
+                            last_message: None,
+                            terminal_pane: None,
                         };
                         IpcNotification::session_update(info)
                     };
@@ -421,6 +451,41 @@ pub(super) async fn handle_reopen_command(cmd: &IpcCommand, store: &SessionStore
     }
 }
 
+/// Handles the RENAME command.
+///
+/// Expects `cmd.session_id`. Sets the session's display name to
+/// `cmd.display_name` (trimmed). A missing or blank name clears it.
+/// Returns the updated `SessionSnapshot`.
+pub(super) async fn handle_rename_command(cmd: &IpcCommand, store: &SessionStore) -> String {
+    let session_id = match &cmd.session_id {
+        Some(id) => id,
+        None => return IpcResponse::error("RENAME requires session_id").to_json_line(),
+    };
+
+    let display_name = cmd
+        .display_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string);
+
+    match store.rename_session(session_id, display_name).await {
+        Some(session) => {
+            tracing::info!(
+                "RENAME session={} name={:?}",
+                &session_id[..session_id.len().min(8)],
+                session.display_name
+            );
+            let info = SessionSnapshot::from(&session);
+            IpcResponse::success(Some(
+                serde_json::to_value(&info).expect("failed to serialize SessionSnapshot"),
+            ))
+            .to_json_line()
+        }
+        None => IpcResponse::error(format!("session not found: {}", session_id)).to_json_line(),
+    }
+}
+
 /// Handles the STATUS command.
 ///
 /// Returns daemon health information as JSON.
@@ -1,7 +1,7 @@
 use super::*;
 use crate::daemon::store::SessionStore;
 use crate::daemon::usage::{UsageFetcher, UsageState};
-use crate::IpcCommandKind;
+use crate::{IpcCommandKind, Multiplexer, TerminalPane};
 use tokio::sync::broadcast;
 
 fn create_test_state() -> DaemonState {
@@ -27,6 +27,10 @@ async fn test_stop_no_active_sessions_returns_ok() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_stop_command(&cmd, &state).await;
@@ -64,6 +68,10 @@ async fn test_stop_with_active_sessions_requires_confirmation() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_stop_command(&cmd, &state).await;
@@ -100,6 +108,10 @@ async fn test_stop_with_confirmation_returns_ok() {
         working_dir: None,
         confirmed: Some(true),
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_stop_command(&cmd, &state).await;
@@ -137,6 +149,10 @@ async fn test_stop_with_closed_sessions_returns_ok() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_stop_command(&cmd, &state).await;
@@ -185,6 +201,10 @@ async fn test_stop_with_inactive_session_returns_ok_without_confirmation() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_stop_command(&cmd, &state).await;
@@ -228,6 +248,10 @@ async fn test_reopen_command_success() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_reopen_command(&cmd, &state.store).await;
@@ -259,6 +283,10 @@ async fn test_reopen_command_not_found() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_reopen_command(&cmd, &state.store).await;
@@ -297,6 +325,10 @@ async fn test_reopen_command_already_active() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_reopen_command(&cmd, &state.store).await;
@@ -318,6 +350,10 @@ async fn test_reopen_command_missing_session_id() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_reopen_command(&cmd, &state.store).await;
@@ -360,6 +396,10 @@ async fn test_delete_command_success() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_delete_command(&cmd, &state.store).await;
@@ -385,6 +425,10 @@ async fn test_delete_command_not_found() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_delete_command(&cmd, &state.store).await;
@@ -407,6 +451,10 @@ async fn test_delete_command_missing_session_id() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_delete_command(&cmd, &state.store).await;
@@ -442,6 +490,10 @@ async fn test_delete_command_returns_snapshot() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_delete_command(&cmd, &state.store).await;
@@ -510,6 +562,10 @@ async fn test_delete_command_other_sessions_unaffected() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_delete_command(&cmd, &state.store).await;
@@ -562,6 +618,10 @@ async fn test_delete_closed_session() {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
 
     let response = handle_delete_command(&cmd, &state.store).await;
@@ -587,6 +647,10 @@ fn make_set_cmd(session_id: &str, status: &str) -> IpcCommand {
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     }
 }
 
@@ -644,12 +708,16 @@ async fn test_set_command_no_refresh_when_available() {
         five_hour: claude_usage::UsagePeriod {
             utilization: 0.0,
             resets_at: None,
+            extra: Default::default(),
         },
         seven_day: claude_usage::UsagePeriod {
             utilization: 0.0,
             resets_at: None,
+            extra: Default::default(),
         },
         seven_day_sonnet: None,
+        breakdown: Default::default(),
+        extra: Default::default(),
         extra_usage: None,
     };
     *fetcher.state().write().await = UsageState::Available(fake_data);
@@ -668,3 +736,140 @@ async fn test_set_command_no_refresh_when_available() {
         "no usage refresh should occur when state is already Available"
     );
 }
+
+#[tokio::test]
+async fn test_set_command_stores_last_message() {
+    let store = SessionStore::new();
+    let mut cmd = make_set_cmd("with-message", "question");
+    cmd.last_message = Some("Which branch should I use?".to_string());
+    let response = handle_set_command(&cmd, &store, None).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    let info: SessionSnapshot =
+        serde_json::from_value(parsed.data.expect("should have data")).expect("snapshot");
+    assert_eq!(
+        info.last_message.as_deref(),
+        Some("Which branch should I use?")
+    );
+
+    // A later SET without a message keeps the stored one
+    handle_set_command(&make_set_cmd("with-message", "working"), &store, None).await;
+    let stored = store.get("with-message").await.expect("session exists");
+    assert_eq!(
+        stored.last_message.as_deref(),
+        Some("Which branch should I use?")
+    );
+}
+
+#[tokio::test]
+async fn test_set_command_stores_terminal_pane() {
+    let store = SessionStore::new();
+    let pane = TerminalPane {
+        multiplexer: Multiplexer::Tmux,
+        id: "%3".to_string(),
+    };
+    let mut cmd = make_set_cmd("in-tmux", "working");
+    cmd.terminal_pane = Some(pane.clone());
+    let response = handle_set_command(&cmd, &store, None).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    let info: SessionSnapshot =
+        serde_json::from_value(parsed.data.expect("should have data")).expect("snapshot");
+    assert_eq!(info.terminal_pane.as_ref(), Some(&pane));
+
+    // A later SET without a pane keeps the stored one
+    handle_set_command(&make_set_cmd("in-tmux", "attention"), &store, None).await;
+    let stored = store.get("in-tmux").await.expect("session exists");
+    assert_eq!(stored.terminal_pane, Some(pane));
+}
+
+#[tokio::test]
+async fn test_set_command_agent_type() {
+    let store = SessionStore::new();
+    let mut cmd = make_set_cmd("codex-thread", "attention");
+    cmd.agent_type = Some("codex".to_string());
+    let response = handle_set_command(&cmd, &store, None).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    let info: SessionSnapshot =
+        serde_json::from_value(parsed.data.expect("should have data")).expect("snapshot");
+    assert_eq!(info.agent_type, "codex");
+
+    // Without agent_type, sessions are Claude Code
+    handle_set_command(&make_set_cmd("claude", "working"), &store, None).await;
+    let stored = store.get("claude").await.expect("session exists");
+    assert_eq!(stored.agent_type, AgentType::ClaudeCode);
+
+    cmd.agent_type = Some("not an agent".to_string());
+    let response = handle_set_command(&cmd, &store, None).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    assert!(!parsed.ok);
+    assert!(parsed.error.expect("error").contains("invalid agent type"));
+}
+
+fn make_rename_cmd(session_id: Option<&str>, display_name: Option<&str>) -> IpcCommand {
+    IpcCommand {
+        version: 1,
+        cmd: IpcCommandKind::Rename.to_string(),
+        session_id: session_id.map(str::to_string),
+        status: None,
+        working_dir: None,
+        confirmed: None,
+        priority: None,
+        display_name: display_name.map(str::to_string),
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
+    }
+}
+
+#[tokio::test]
+async fn test_rename_command_sets_display_name() {
+    let store = SessionStore::new();
+    handle_set_command(&make_set_cmd("rename-me", "working"), &store, None).await;
+
+    let response = handle_rename_command(
+        &make_rename_cmd(Some("rename-me"), Some("  api work  ")),
+        &store,
+    )
+    .await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    assert!(parsed.ok);
+    let info: SessionSnapshot =
+        serde_json::from_value(parsed.data.expect("should have data")).expect("snapshot");
+    assert_eq!(info.display_name.as_deref(), Some("api work"));
+
+    let stored = store.get("rename-me").await.expect("session exists");
+    assert_eq!(stored.display_name.as_deref(), Some("api work"));
+}
+
+#[tokio::test]
+async fn test_rename_command_blank_name_clears() {
+    let store = SessionStore::new();
+    handle_set_command(&make_set_cmd("clear-me", "working"), &store, None).await;
+    store
+        .rename_session("clear-me", Some("old".to_string()))
+        .await;
+
+    let response =
+        handle_rename_command(&make_rename_cmd(Some("clear-me"), Some("   ")), &store).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    assert!(parsed.ok);
+    let stored = store.get("clear-me").await.expect("session exists");
+    assert!(stored.display_name.is_none());
+}
+
+#[tokio::test]
+async fn test_rename_command_requires_session_id() {
+    let store = SessionStore::new();
+    let response = handle_rename_command(&make_rename_cmd(None, Some("x")), &store).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    assert!(!parsed.ok);
+    assert_eq!(parsed.error.as_deref(), Some("RENAME requires session_id"));
+}
+
+#[tokio::test]
+async fn test_rename_command_unknown_session() {
+    let store = SessionStore::new();
+    let response = handle_rename_command(&make_rename_cmd(Some("ghost"), Some("x")), &store).await;
+    let parsed: IpcResponse = serde_json::from_str(&response).expect("failed to parse response");
+    assert!(!parsed.ok);
+    assert!(parsed.error.unwrap().contains("session not found"));
+}
@@ -16,9 +16,12 @@
 //! AGENT_CONSOLE_DASHBOARD_LOG=agent_console=debug,warn acd daemon
 //! ```
 
+use super::telemetry::Telemetry;
 use std::fs::{self, OpenOptions};
 use std::io;
 use std::path::PathBuf;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{fmt, EnvFilter};
 
 /// Initialize the tracing subscriber.
@@ -30,16 +33,17 @@ use tracing_subscriber::{fmt, EnvFilter};
 ///
 /// * `log_file` - Optional path to a log file. When `Some(path)`, logs are appended to that file.
 ///   When `None`, logs are written to stderr (foreground mode).
+/// * `telemetry` - Running telemetry exporters, which also receive the spans.
 ///
 /// # Panics
 ///
 /// Panics if a global subscriber has already been set (should only be
 /// called once, at daemon startup), or if the log file cannot be opened.
-pub fn init(log_file: Option<PathBuf>) -> io::Result<()> {
+pub fn init(log_file: Option<PathBuf>, telemetry: Option<&Telemetry>) -> io::Result<()> {
     let filter = EnvFilter::try_from_env("AGENT_CONSOLE_DASHBOARD_LOG")
         .unwrap_or_else(|_| EnvFilter::new("info"));
 
-    match log_file {
+    let writer = match log_file {
         Some(path) => {
             // Create parent directory if it doesn't exist
             if let Some(parent) = path.parent() {
@@ -48,22 +52,26 @@ pub fn init(log_file: Option<PathBuf>) -> io::Result<()> {
 
             // Open file in append mode
             let file = OpenOptions::new().create(true).append(true).open(&path)?;
-
-            fmt()
-                .with_env_filter(filter)
-                .with_target(false)
-                .with_writer(file)
-                .init();
-        }
-        None => {
-            fmt()
-                .with_env_filter(filter)
-                .with_target(false)
-                .with_writer(std::io::stderr)
-                .init();
+            BoxMakeWriter::new(file)
         }
-    }
+        None => BoxMakeWriter::new(std::io::stderr),
+    };
+
+    let subscriber = fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(writer)
+        .finish();
+
+    #[cfg(feature = "telemetry")]
+    let subscriber = {
+        use tracing_subscriber::layer::SubscriberExt;
+        subscriber.with(telemetry.map(Telemetry::tracing_layer))
+    };
+    #[cfg(not(feature = "telemetry"))]
+    let _ = telemetry;
 
+    subscriber.init();
     Ok(())
 }
 
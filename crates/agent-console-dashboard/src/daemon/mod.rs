@@ -3,11 +3,15 @@
 //! This module provides process lifecycle management, daemonization, and the
 //! main entry point for running the daemon.
 
+mod email;
 mod handlers;
 pub mod logging;
+mod notifications;
 pub mod server;
 pub mod session;
+mod statsd;
 pub mod store;
+pub mod telemetry;
 pub mod usage;
 
 // Re-export commonly used types for convenience
@@ -139,6 +143,78 @@ pub fn daemonize_process(nochdir: bool, noclose: bool) -> DaemonResult<()> {
     }
 }
 
+/// Reconcile the claude-hooks registry with the user settings file.
+///
+/// An `acd install` or `acd uninstall` interrupted between its settings and
+/// registry writes leaves stale registry entries; this drops them and logs
+/// what changed. Failures are logged and never stop the daemon.
+fn repair_hook_registry() {
+    match claude_hooks::repair(claude_hooks::Scope::User) {
+        Ok(report) => {
+            for entry in &report.removed {
+                info!(event = ?entry.event, command = %entry.command, "dropped stale hook registry entry");
+            }
+            for entry in &report.updated {
+                info!(event = ?entry.event, command = %entry.command, "refreshed hook registry entry from settings");
+            }
+            for entry in &report.orphaned {
+                warn!(event = ?entry.event, command = %entry.command, "hook registry entry belongs to a deleted project");
+            }
+        }
+        Err(e) => warn!(error = %e, "could not repair hook registry"),
+    }
+}
+
+/// Log how many hooks `acd install` put in the user settings file.
+///
+/// Only acd's own hooks are listed, so startup does not scan other tools'
+/// hooks. Zero is normal when hooks come from the Claude Code plugin.
+fn log_installed_hooks() {
+    let filter = claude_hooks::ListFilter {
+        installed_by: Some("acd".to_string()),
+        ..Default::default()
+    };
+    match claude_hooks::list_filtered(filter) {
+        Ok(hooks) => info!(count = hooks.len(), "hooks installed by acd install"),
+        Err(e) => warn!(error = %e, "could not list installed hooks"),
+    }
+}
+
+/// Watch the user settings file and warn when acd's hooks are removed.
+///
+/// Without its hooks the daemon stops receiving status updates with no other
+/// sign of failure. Returns the watcher, which must be kept alive; `None` if
+/// watching could not start.
+fn watch_hook_settings() -> Option<claude_hooks::SettingsWatcher> {
+    let mut warned: Vec<String> = Vec::new();
+    let result = claude_hooks::watch(claude_hooks::Scope::User, move |change| {
+        let missing: Vec<String> = change
+            .missing
+            .iter()
+            .filter(|entry| entry.installed_by == "acd")
+            .map(|entry| format!("{:?} {}", entry.event, entry.command))
+            .collect();
+        // Warn once per distinct set of missing hooks
+        if missing != warned {
+            if !missing.is_empty() {
+                warn!(
+                    path = %change.path.display(),
+                    hooks = ?missing,
+                    "acd hooks were removed from Claude Code settings; run `acd install` to restore status updates"
+                );
+            }
+            warned = missing;
+        }
+    });
+    match result {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            warn!(error = %e, "could not watch Claude Code settings for hook changes");
+            None
+        }
+    }
+}
+
 /// Expands tilde (~) in a path string to the user's home directory.
 fn expand_tilde(path: &str) -> PathBuf {
     if let Some(stripped) = path.strip_prefix("~/") {
@@ -206,6 +282,9 @@ fn resolve_log_file_path() -> Option<PathBuf> {
 /// run_daemon(config).expect("Failed to run daemon");
 /// ```
 pub fn run_daemon(config: DaemonConfig) -> DaemonResult<()> {
+    // Upgrade an old config file while stderr still reaches the terminal
+    crate::config::loader::ConfigLoader::migrate_default();
+
     // CRITICAL: Daemonize BEFORE starting Tokio runtime
     // Forking after Tokio initialization corrupts global state for signal handling
     if config.daemonize {
@@ -216,8 +295,18 @@ pub fn run_daemon(config: DaemonConfig) -> DaemonResult<()> {
     // Resolve log file path before initializing logging
     let log_file_path = resolve_log_file_path();
 
+    // Start telemetry exporters before the runtime: they use a blocking
+    // HTTP client, which must not be created inside Tokio
+    let telemetry_config = crate::config::loader::ConfigLoader::load_default()
+        .map(|toml_config| toml_config.telemetry)
+        .unwrap_or_default();
+    let (telemetry, telemetry_error) = match telemetry::Telemetry::init(&telemetry_config) {
+        Ok(telemetry) => (telemetry, None),
+        Err(e) => (None, Some(e)),
+    };
+
     // Initialize logging after daemonize (stderr may be redirected)
-    logging::init(log_file_path).map_err(|e| {
+    logging::init(log_file_path, telemetry.as_ref()).map_err(|e| {
         Box::new(std::io::Error::other(format!(
             "Failed to initialize logging: {}",
             e
@@ -229,9 +318,16 @@ pub fn run_daemon(config: DaemonConfig) -> DaemonResult<()> {
         daemonize = config.daemonize,
         "agent console daemon starting"
     );
+    if let Some(e) = telemetry_error {
+        warn!("telemetry disabled: {}", e);
+    }
 
     // Hooks are managed by the Claude Code plugin system (.claude-plugin/plugin.json).
     // Plugin installation is handled by `acd service install` or `claude plugin install`.
+    // Hooks installed with `acd install` are tracked in the claude-hooks registry.
+    repair_hook_registry();
+    log_installed_hooks();
+    let _hook_watcher = watch_hook_settings();
 
     // Create Tokio runtime AFTER daemonization
     // Using current_thread runtime for simpler daemon workloads
@@ -289,6 +385,45 @@ pub fn run_daemon(config: DaemonConfig) -> DaemonResult<()> {
         // Clone the store for the idle check loop before moving server
         let store = server.store().clone();
 
+        // Spawn notifications (returns at once when none are configured)
+        let (notifications_config, hook_library) =
+            crate::config::loader::ConfigLoader::load_default()
+                .map(|toml_config| (toml_config.notifications, toml_config.hooks))
+                .unwrap_or_default();
+        let email_config = notifications_config.email.clone();
+        let email_handle = tokio::spawn(email::run(
+            store.clone(),
+            email_config.clone(),
+            shutdown_tx.subscribe(),
+        ));
+        let notifications_handle = tokio::spawn(notifications::run(
+            store.clone(),
+            Arc::clone(&usage_fetcher),
+            notifications_config,
+            hook_library,
+            shutdown_tx.subscribe(),
+        ));
+
+        // Spawn the StatsD emitter (returns at once when disabled)
+        let statsd_config = crate::config::loader::ConfigLoader::load_default()
+            .map(|toml_config| toml_config.statsd)
+            .unwrap_or_default();
+        let statsd_handle = tokio::spawn(statsd::run(
+            store.clone(),
+            Arc::clone(&usage_fetcher),
+            statsd_config,
+            shutdown_tx.subscribe(),
+        ));
+
+        // Sample session counts for telemetry
+        let telemetry_handle = telemetry.as_ref().map(|telemetry| {
+            tokio::spawn(telemetry::sample_sessions(
+                store.clone(),
+                telemetry.export_interval(),
+                shutdown_tx.subscribe(),
+            ))
+        });
+
         // Spawn the usage fetcher
         let usage_shutdown_rx = shutdown_tx.subscribe();
         let usage_handle = tokio::spawn(async move {
@@ -332,6 +467,7 @@ pub fn run_daemon(config: DaemonConfig) -> DaemonResult<()> {
             _ = wait_for_shutdown() => {}
             _ = idle_check_loop(&store, idle_timeout) => {
                 info!("no active sessions for {} seconds, auto-stopping", idle_timeout.as_secs());
+                email::send_auto_stop_digest(&store, &email_config, idle_timeout).await;
             }
         }
 
@@ -339,8 +475,18 @@ pub fn run_daemon(config: DaemonConfig) -> DaemonResult<()> {
         let _ = shutdown_tx.send(());
         let _ = server_handle.await;
         let _ = usage_handle.await;
+        let _ = notifications_handle.await;
+        let _ = email_handle.await;
+        let _ = statsd_handle.await;
+        if let Some(handle) = telemetry_handle {
+            let _ = handle.await;
+        }
     });
 
+    if let Some(telemetry) = telemetry {
+        telemetry.shutdown();
+    }
+
     info!("daemon stopped");
     Ok(())
 }
@@ -0,0 +1,634 @@
+//! Notifications on session status transitions and usage thresholds.
+//!
+//! Subscribes to the [`SessionStore`] broadcast and notifies each configured
+//! channel (desktop, Slack, Discord, ntfy, generic webhooks, MQTT, shell
+//! hooks) when a session enters
+//! one of that channel's statuses. Store broadcasts also fire for priority and
+//! name changes, so the last seen status of each session is tracked and only
+//! real transitions count. Webhook channels can also be told when 5-hour usage
+//! crosses a threshold, and generic webhooks when a session is created. MQTT
+//! publishes every usage update instead of threshold crossings.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use claude_usage::UsageData;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::config::schema::{HookConfig, NotificationsConfig};
+use crate::daemon::store::SessionStore;
+use crate::daemon::usage::{UsageFetcher, UsageState, UsageSubscription};
+use crate::integrations::desktop::{Notification, Notifier};
+use crate::integrations::discord::DiscordNotifier;
+use crate::integrations::hooks::{self, HookInput};
+use crate::integrations::mqtt::{self, MqttPublisher};
+use crate::integrations::ntfy::NtfyNotifier;
+use crate::integrations::slack::SlackNotifier;
+use crate::integrations::webhook::{WebhookEndpoint, WebhookEvent};
+use crate::{Session, SessionUpdate, Status};
+
+/// How often dispatchers drop sessions that are gone from the store.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Every status, for webhooks and MQTT without a `statuses` filter.
+const ALL_STATUSES: [Status; 4] = [
+    Status::Working,
+    Status::Attention,
+    Status::Question,
+    Status::Closed,
+];
+
+/// Decides which session updates deserve a notification.
+#[derive(Debug)]
+pub(crate) struct Dispatcher {
+    /// Statuses that notify when entered.
+    statuses: Vec<Status>,
+    /// Minimum time between notifications for one session.
+    min_interval: Duration,
+    /// Last status seen per session.
+    last_status: HashMap<String, Status>,
+    /// When each session was last notified.
+    last_sent: HashMap<String, Instant>,
+}
+
+impl Dispatcher {
+    /// Builds a dispatcher from a channel's config, skipping unknown status
+    /// names. `section` names the config section in warnings.
+    pub(crate) fn from_config(section: &str, statuses: &[String], min_interval: &str) -> Self {
+        let statuses = statuses
+            .iter()
+            .filter_map(|name| match Status::from_str(name) {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    warn!(section, status = %name, "ignoring notification status: {}", e);
+                    None
+                }
+            })
+            .collect();
+        let min_interval = humantime::parse_duration(min_interval).unwrap_or_else(|e| {
+            warn!(
+                section,
+                min_interval,
+                error = %e,
+                "invalid notification min_interval, using 1m"
+            );
+            Duration::from_secs(60)
+        });
+        Self::new(statuses, min_interval)
+    }
+
+    pub(crate) fn new(statuses: Vec<Status>, min_interval: Duration) -> Self {
+        Self {
+            statuses,
+            min_interval,
+            last_status: HashMap::new(),
+            last_sent: HashMap::new(),
+        }
+    }
+
+    /// Records `update` and returns true if it should be notified at `now`.
+    ///
+    /// A session's history is dropped once it closes, so a closed session
+    /// costs nothing here.
+    pub(crate) fn should_notify(&mut self, update: &SessionUpdate, now: Instant) -> bool {
+        let notify = self.record(update, now);
+        if update.status == Status::Closed {
+            self.forget(&update.session_id);
+        }
+        notify
+    }
+
+    fn record(&mut self, update: &SessionUpdate, now: Instant) -> bool {
+        let previous = self
+            .last_status
+            .insert(update.session_id.clone(), update.status);
+        if previous == Some(update.status) || !self.statuses.contains(&update.status) {
+            return false;
+        }
+        if let Some(sent) = self.last_sent.get(&update.session_id) {
+            if now.duration_since(*sent) < self.min_interval {
+                debug!(session_id = %update.session_id, "notification rate limited");
+                return false;
+            }
+        }
+        self.last_sent.insert(update.session_id.clone(), now);
+        true
+    }
+
+    /// Drops everything recorded for `session_id`.
+    pub(crate) fn forget(&mut self, session_id: &str) {
+        self.last_status.remove(session_id);
+        self.last_sent.remove(session_id);
+    }
+
+    /// Keeps only sessions for which `live` returns true. Deleting a session
+    /// from the store is not broadcast, so this is how those get dropped.
+    pub(crate) fn retain(&mut self, live: impl Fn(&str) -> bool) {
+        self.last_status.retain(|id, _| live(id));
+        self.last_sent.retain(|id, _| live(id));
+    }
+}
+
+/// Tracks whether usage is above a threshold, to alert once per crossing.
+#[derive(Debug)]
+pub(crate) struct UsageThreshold {
+    percent: f64,
+    above: bool,
+}
+
+impl UsageThreshold {
+    pub(crate) fn new(percent: u8) -> Self {
+        Self {
+            percent: f64::from(percent),
+            above: false,
+        }
+    }
+
+    /// Records `utilization` and returns true if it just reached the threshold.
+    pub(crate) fn crossed(&mut self, utilization: f64) -> bool {
+        let was_above = std::mem::replace(&mut self.above, utilization >= self.percent);
+        self.above && !was_above
+    }
+}
+
+/// Where a notification goes.
+#[derive(Clone)]
+enum Channel {
+    Desktop(Notifier),
+    Slack(Arc<SlackNotifier>),
+    Discord(Arc<DiscordNotifier>),
+    Ntfy(Arc<NtfyNotifier>),
+    Webhook(Arc<WebhookEndpoint>),
+    Mqtt(Arc<MqttPublisher>),
+    /// A `[[notifications.hooks]]` entry.
+    Hook(Arc<TransitionHook>),
+}
+
+/// A `[[notifications.hooks]]` entry with the `[hooks]` library it may name.
+#[derive(Debug)]
+struct TransitionHook {
+    /// Name used in logs, e.g. `transition hook notify`.
+    label: String,
+    /// The entry as configured: its own command, or a library name.
+    hook: HookConfig,
+    /// The `[hooks]` library.
+    library: Arc<BTreeMap<String, HookConfig>>,
+}
+
+impl TransitionHook {
+    /// The command to run for `session`, with a library command's
+    /// placeholders filled in.
+    fn prepare(&self, session: &Session) -> Result<hooks::PreparedHook, String> {
+        hooks::prepare(&self.hook, &self.library, session, self.label.clone())
+    }
+}
+
+impl Channel {
+    fn send(&self, session: &Session) {
+        match self {
+            Channel::Desktop(notifier) => {
+                let command = notifier.command(&Notification::for_session(session));
+                if let Err(e) = crate::integrations::spawn(command, "desktop") {
+                    warn!(error = %e, "failed to send desktop notification");
+                }
+            }
+            Channel::Slack(slack) => {
+                let slack = Arc::clone(slack);
+                let payload = slack.session_payload(session);
+                spawn_post("Slack", async move { slack.post(&payload).await });
+            }
+            Channel::Discord(discord) => {
+                let discord = Arc::clone(discord);
+                let payload = discord.session_payload(session);
+                spawn_post("Discord", async move { discord.post(&payload).await });
+            }
+            Channel::Ntfy(ntfy) => {
+                let ntfy = Arc::clone(ntfy);
+                let payload = ntfy.session_payload(session);
+                spawn_post("ntfy", async move { ntfy.post(&payload).await });
+            }
+            Channel::Webhook(hook) => {
+                post_session_event(hook, WebhookEvent::StatusChanged, session)
+            }
+            Channel::Mqtt(publisher) => publisher.publish_status(session),
+            Channel::Hook(hook) => {
+                let prepared = hook.prepare(session);
+                let input = HookInput::new(session);
+                let (Ok(prepared), Ok(input)) = (prepared, input) else {
+                    warn!(hook = %hook.label, "failed to prepare hook");
+                    return;
+                };
+                // Hooks block while they run, so keep them off the runtime
+                tokio::task::spawn_blocking(move || {
+                    if let Err(e) = hooks::run(&prepared, &input) {
+                        warn!(error = %e, "transition hook failed");
+                    }
+                });
+            }
+        }
+    }
+
+    /// Announces that usage crossed the channel's threshold. Only webhook
+    /// channels have usage alerts.
+    fn send_usage(&self, usage: &UsageData) {
+        match self {
+            Channel::Desktop(_) | Channel::Mqtt(_) | Channel::Hook(_) => {}
+            Channel::Slack(slack) => {
+                let slack = Arc::clone(slack);
+                let payload = slack.usage_payload(usage);
+                spawn_post("Slack", async move { slack.post(&payload).await });
+            }
+            Channel::Discord(discord) => {
+                let discord = Arc::clone(discord);
+                let payload = discord.usage_payload(usage);
+                spawn_post("Discord", async move { discord.post(&payload).await });
+            }
+            Channel::Ntfy(ntfy) => {
+                let ntfy = Arc::clone(ntfy);
+                let payload = ntfy.usage_payload(usage);
+                spawn_post("ntfy", async move { ntfy.post(&payload).await });
+            }
+            Channel::Webhook(hook) => {
+                let hook = Arc::clone(hook);
+                let payload = hook.usage_payload(usage);
+                spawn_post("webhook", async move { hook.post(&payload).await });
+            }
+        }
+    }
+}
+
+/// Posts a session `event` to `hook` if the session passes its filters.
+fn post_session_event(hook: &Arc<WebhookEndpoint>, event: WebhookEvent, session: &Session) {
+    if !hook.matches(session) {
+        return;
+    }
+    let hook = Arc::clone(hook);
+    let payload = hook.session_payload(event, session);
+    spawn_post("webhook", async move { hook.post(&payload).await });
+}
+
+/// Posts in the background so a slow webhook never delays other channels.
+fn spawn_post<F>(service: &'static str, post: F)
+where
+    F: std::future::Future<Output = Result<(), reqwest::Error>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        if let Err(e) = post.await {
+            warn!(service, error = %e, "failed to post notification");
+        }
+    });
+}
+
+/// Sends notifications for store and usage updates until shutdown.
+///
+/// Returns immediately if no channel is configured. `hook_library` holds the
+/// `[hooks]` entries that `notifications.hooks` refer to.
+pub(crate) async fn run(
+    store: SessionStore,
+    usage: Arc<UsageFetcher>,
+    config: NotificationsConfig,
+    hook_library: BTreeMap<String, HookConfig>,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    let mut routes: Vec<(Dispatcher, Channel)> = Vec::new();
+    let mut usage_alerts: Vec<(UsageThreshold, Channel)> = Vec::new();
+    let desktop = &config.desktop;
+    if desktop.enabled {
+        match Notifier::detect() {
+            Some(notifier) => {
+                info!(notifier = ?notifier, "desktop notifications enabled");
+                let dispatcher = Dispatcher::from_config(
+                    "notifications.desktop",
+                    &desktop.statuses,
+                    &desktop.min_interval,
+                );
+                routes.push((dispatcher, Channel::Desktop(notifier)));
+            }
+            None => {
+                warn!("desktop notifications enabled but no notifier found (install notify-send)")
+            }
+        }
+    }
+
+    let slack = &config.slack;
+    if let Some(notifier) = SlackNotifier::from_config(slack) {
+        info!("Slack notifications enabled");
+        let dispatcher =
+            Dispatcher::from_config("notifications.slack", &slack.statuses, &slack.min_interval);
+        let channel = Channel::Slack(Arc::new(notifier));
+        if slack.usage_threshold > 0 {
+            usage_alerts.push((UsageThreshold::new(slack.usage_threshold), channel.clone()));
+        }
+        routes.push((dispatcher, channel));
+    }
+
+    let discord = &config.discord;
+    if let Some(notifier) = DiscordNotifier::from_config(discord) {
+        info!("Discord notifications enabled");
+        let dispatcher = Dispatcher::from_config(
+            "notifications.discord",
+            &discord.statuses,
+            &discord.min_interval,
+        );
+        let channel = Channel::Discord(Arc::new(notifier));
+        if discord.usage_threshold > 0 {
+            usage_alerts.push((
+                UsageThreshold::new(discord.usage_threshold),
+                channel.clone(),
+            ));
+        }
+        routes.push((dispatcher, channel));
+    }
+
+    let ntfy = &config.ntfy;
+    if let Some(notifier) = NtfyNotifier::from_config(ntfy) {
+        info!(topic = %ntfy.topic, "ntfy notifications enabled");
+        let dispatcher =
+            Dispatcher::from_config("notifications.ntfy", &ntfy.statuses, &ntfy.min_interval);
+        let channel = Channel::Ntfy(Arc::new(notifier));
+        if ntfy.usage_threshold > 0 {
+            usage_alerts.push((UsageThreshold::new(ntfy.usage_threshold), channel.clone()));
+        }
+        routes.push((dispatcher, channel));
+    }
+
+    let mut created_hooks: Vec<Arc<WebhookEndpoint>> = Vec::new();
+    for (i, config) in config.webhooks.iter().enumerate() {
+        let Some(hook) = WebhookEndpoint::from_config(config).map(Arc::new) else {
+            continue;
+        };
+        info!(url = %hook.url(), "webhook enabled");
+        let channel = Channel::Webhook(Arc::clone(&hook));
+        if hook.wants(WebhookEvent::StatusChanged) {
+            let section = format!("notifications.webhooks[{}]", i);
+            let dispatcher = if config.statuses.is_empty() {
+                Dispatcher::new(ALL_STATUSES.to_vec(), Duration::ZERO)
+            } else {
+                Dispatcher::from_config(&section, &config.statuses, "0s")
+            };
+            routes.push((dispatcher, channel.clone()));
+        }
+        if hook.wants(WebhookEvent::UsageThreshold) {
+            usage_alerts.push((UsageThreshold::new(hook.usage_threshold()), channel));
+        }
+        if hook.wants(WebhookEvent::SessionCreated) {
+            created_hooks.push(hook);
+        }
+    }
+
+    let hook_library = Arc::new(hook_library);
+    for (i, config) in config.hooks.iter().enumerate() {
+        let section = format!("notifications.hooks[{}]", i);
+        let hook = config.to_hook();
+        let Some(resolved) = hook.resolve(&hook_library) else {
+            warn!(hook = %config.hook, "{} names an unknown hook, skipping", section);
+            continue;
+        };
+        if resolved.command.trim().is_empty() {
+            continue;
+        }
+        info!(command = %resolved.command, "transition hook enabled");
+        let dispatcher = if config.statuses.is_empty() {
+            Dispatcher::new(ALL_STATUSES.to_vec(), Duration::ZERO)
+        } else {
+            Dispatcher::from_config(&section, &config.statuses, "0s")
+        };
+        let label = match config.hook.as_str() {
+            "" => format!("transition hook[{}]", i),
+            name => format!("transition hook {}", name),
+        };
+        let hook = TransitionHook {
+            label,
+            hook,
+            library: Arc::clone(&hook_library),
+        };
+        routes.push((dispatcher, Channel::Hook(Arc::new(hook))));
+    }
+
+    let mut usage_publisher = None;
+    match MqttPublisher::from_config(&config.mqtt) {
+        Ok(Some((publisher, eventloop))) => {
+            info!(broker = %config.mqtt.broker, "MQTT publishing enabled");
+            tokio::spawn(mqtt::drive(eventloop));
+            let publisher = Arc::new(publisher);
+            let dispatcher = if config.mqtt.statuses.is_empty() {
+                Dispatcher::new(ALL_STATUSES.to_vec(), Duration::ZERO)
+            } else {
+                Dispatcher::from_config("notifications.mqtt", &config.mqtt.statuses, "0s")
+            };
+            if publisher.publishes_usage() {
+                usage_publisher = Some(Arc::clone(&publisher));
+            }
+            routes.push((dispatcher, Channel::Mqtt(publisher)));
+        }
+        Ok(None) => {}
+        Err(e) => warn!(error = %e, "MQTT publishing disabled"),
+    }
+
+    if routes.is_empty() && usage_alerts.is_empty() && created_hooks.is_empty() {
+        return;
+    }
+
+    // Only subscribe to usage when alerting on or publishing it: subscribing
+    // keeps the fetcher polling the API
+    let mut usage_updates =
+        (!usage_alerts.is_empty() || usage_publisher.is_some()).then(|| usage.subscribe());
+
+    let mut created = (!created_hooks.is_empty()).then(|| store.subscribe_created());
+    let mut updates = store.subscribe();
+    let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => return,
+            _ = prune.tick() => {
+                let live: HashSet<String> =
+                    store.list_all().await.into_iter().map(|s| s.session_id).collect();
+                for (dispatcher, _) in routes.iter_mut() {
+                    dispatcher.retain(|id| live.contains(id));
+                }
+            }
+            update = updates.recv() => match update {
+                Ok(update) => dispatch(&store, &mut routes, &update).await,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!(skipped, "notification subscriber lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            id = recv_created(&mut created) => match id {
+                Ok(id) => {
+                    if let Some(session) = store.get(&id).await {
+                        for hook in &created_hooks {
+                            post_session_event(hook, WebhookEvent::SessionCreated, &session);
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!(skipped, "session created subscriber lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => created = None,
+            },
+            state = recv_usage(&mut usage_updates) => {
+                if let Ok(UsageState::Available(data)) = state {
+                    if let Some(publisher) = &usage_publisher {
+                        publisher.publish_usage(&data);
+                    }
+                    for (threshold, channel) in usage_alerts.iter_mut() {
+                        if threshold.crossed(data.five_hour.utilization) {
+                            channel.send_usage(&data);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Notifies every channel whose dispatcher accepts `update`.
+async fn dispatch(
+    store: &SessionStore,
+    routes: &mut [(Dispatcher, Channel)],
+    update: &SessionUpdate,
+) {
+    let now = Instant::now();
+    let mut session = None;
+    for (dispatcher, channel) in routes.iter_mut() {
+        if !dispatcher.should_notify(update, now) {
+            continue;
+        }
+        if session.is_none() {
+            session = store.get(&update.session_id).await;
+        }
+        if let Some(session) = &session {
+            channel.send(session);
+        }
+    }
+}
+
+/// Next created session ID, or never if no webhook wants creations.
+async fn recv_created(
+    created: &mut Option<broadcast::Receiver<String>>,
+) -> Result<String, broadcast::error::RecvError> {
+    match created {
+        Some(created) => created.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Next usage update, or never if usage alerts are off.
+async fn recv_usage(
+    subscription: &mut Option<UsageSubscription>,
+) -> Result<UsageState, broadcast::error::RecvError> {
+    match subscription {
+        Some(subscription) => subscription.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::TransitionHookConfig;
+
+    fn update(status: Status) -> SessionUpdate {
+        SessionUpdate::new("s1".to_string(), status, 0)
+    }
+
+    #[test]
+    fn test_notifies_on_transition_into_enabled_status() {
+        let mut d = Dispatcher::new(vec![Status::Attention], Duration::ZERO);
+        let now = Instant::now();
+        assert!(!d.should_notify(&update(Status::Working), now));
+        assert!(d.should_notify(&update(Status::Attention), now));
+        // Same status again (e.g. a priority change) is not a transition
+        assert!(!d.should_notify(&update(Status::Attention), now));
+        assert!(!d.should_notify(&update(Status::Question), now));
+    }
+
+    #[test]
+    fn test_rate_limits_per_session() {
+        let mut d = Dispatcher::new(
+            vec![Status::Attention, Status::Question],
+            Duration::from_secs(60),
+        );
+        let start = Instant::now();
+        assert!(d.should_notify(&update(Status::Attention), start));
+        assert!(!d.should_notify(&update(Status::Question), start + Duration::from_secs(10)));
+        assert!(d.should_notify(&update(Status::Attention), start + Duration::from_secs(61)));
+
+        let other = SessionUpdate::new("s2".to_string(), Status::Attention, 0);
+        assert!(d.should_notify(&other, start + Duration::from_secs(62)));
+    }
+
+    #[test]
+    fn test_forgets_closed_and_removed_sessions() {
+        let mut d = Dispatcher::new(vec![Status::Closed], Duration::ZERO);
+        let now = Instant::now();
+        assert!(!d.should_notify(&update(Status::Working), now));
+        assert!(d.should_notify(&update(Status::Closed), now));
+        assert!(d.last_status.is_empty());
+        assert!(d.last_sent.is_empty());
+
+        let other = SessionUpdate::new("s2".to_string(), Status::Working, 0);
+        assert!(!d.should_notify(&update(Status::Working), now));
+        assert!(!d.should_notify(&other, now));
+        d.retain(|id| id == "s2");
+        assert_eq!(d.last_status.keys().collect::<Vec<_>>(), vec!["s2"]);
+    }
+
+    #[test]
+    fn test_from_config_skips_unknown_statuses() {
+        let statuses = vec!["question".to_string(), "bogus".to_string()];
+        let d = Dispatcher::from_config("notifications.desktop", &statuses, "nonsense");
+        assert_eq!(d.statuses, vec![Status::Question]);
+        assert_eq!(d.min_interval, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_transition_hook_fills_in_library_placeholders() {
+        let library = BTreeMap::from([(
+            "notify".to_string(),
+            HookConfig {
+                command: "notify-send {working_dir}".to_string(),
+                ..HookConfig::default()
+            },
+        )]);
+        let hook = |config: TransitionHookConfig| TransitionHook {
+            label: "transition hook".to_string(),
+            hook: config.to_hook(),
+            library: Arc::new(library.clone()),
+        };
+        let session = Session::new(
+            "s1".to_string(),
+            crate::AgentType::ClaudeCode,
+            Some(std::path::PathBuf::from("/work/app")),
+        );
+
+        let named = hook(TransitionHookConfig {
+            hook: "notify".to_string(),
+            ..TransitionHookConfig::default()
+        });
+        let prepared = named.prepare(&session).expect("hook is in the library");
+        assert_eq!(prepared.command, "notify-send '/work/app'");
+
+        // Inline commands run as written
+        let inline = hook(TransitionHookConfig {
+            command: "echo {working_dir}".to_string(),
+            ..TransitionHookConfig::default()
+        });
+        let prepared = inline.prepare(&session).expect("inline hook");
+        assert_eq!(prepared.command, "echo {working_dir}");
+    }
+
+    #[test]
+    fn test_usage_threshold_fires_once_per_crossing() {
+        let mut threshold = UsageThreshold::new(80);
+        assert!(!threshold.crossed(50.0));
+        assert!(threshold.crossed(80.0));
+        assert!(!threshold.crossed(95.0));
+        assert!(!threshold.crossed(10.0));
+        assert!(threshold.crossed(81.0));
+    }
+}
@@ -32,14 +32,16 @@ use std::time::Instant;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::broadcast;
+use tracing::Instrument;
 
 use crate::daemon::store::SessionStore;
+use crate::daemon::telemetry;
 use crate::daemon::usage::UsageFetcher;
 
 use super::handlers::{
     handle_delete_command, handle_dump_command, handle_get_command, handle_list_command,
-    handle_reopen_command, handle_rm_command, handle_set_command, handle_status_command,
-    handle_stop_command, handle_sub_command, DaemonState,
+    handle_rename_command, handle_reopen_command, handle_rm_command, handle_set_command,
+    handle_status_command, handle_stop_command, handle_sub_command, DaemonState,
 };
 
 /// Unix socket server for daemon IPC.
@@ -75,7 +77,7 @@ impl SocketServer {
     /// # Arguments
     ///
     /// * `socket_path` - The filesystem path where the Unix socket will be created.
-    ///   Default is typically `/tmp/agent-console-dashboard.sock`.
+    ///   Default is `crate::config::xdg::socket_path()`.
     ///
     /// # Example
     ///
@@ -180,9 +182,29 @@ impl SocketServer {
     /// Returns an error if:
     /// - Another daemon is already running (socket is in use)
     /// - Cannot remove stale socket file (permission denied)
-    /// - Cannot bind to the socket path (permission denied, directory doesn't exist)
+    /// - Cannot create the socket's missing parent directory
+    /// - The per-user socket directory is not a 0700 directory owned by the
+    ///   current user, e.g. another user created `/tmp/acd-<uid>` first
+    /// - Cannot bind to the socket path (permission denied)
     pub async fn start(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Clean up stale socket file if it exists
+        // Create the per-user socket directory; existing parents keep their
+        // permissions
+        if let Some(parent) = Path::new(&self.socket_path)
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            if !parent.exists() {
+                crate::config::xdg::ensure_dir(parent)?;
+            }
+            // Under /tmp another user can create it first to take over the
+            // socket
+            if parent == crate::config::xdg::socket_dir() {
+                crate::config::xdg::check_private_dir(parent)?;
+            }
+        }
+
+        // Clean up stale socket file if it exists, only once the directory
+        // is known to be ours
         self.cleanup_stale_socket().await?;
 
         // Bind to socket
@@ -405,23 +427,38 @@ async fn handle_client(
             }
         };
 
-        let response = match command_kind {
-            IpcCommandKind::Set => {
-                handle_set_command(&cmd, &state.store, state.usage_fetcher.as_ref()).await
-            }
-            IpcCommandKind::Rm => handle_rm_command(&cmd, &state.store).await,
-            IpcCommandKind::List => handle_list_command(&state.store).await,
-            IpcCommandKind::Get => handle_get_command(&cmd, &state.store).await,
-            IpcCommandKind::Delete => handle_delete_command(&cmd, &state.store).await,
-            IpcCommandKind::Reopen => handle_reopen_command(&cmd, &state.store).await,
-            IpcCommandKind::Status => handle_status_command(state).await,
-            IpcCommandKind::Dump => handle_dump_command(state).await,
-            IpcCommandKind::Stop => handle_stop_command(&cmd, state).await,
-            IpcCommandKind::Sub => {
-                handle_sub_command(&state.store, state.usage_fetcher.as_ref(), &mut writer).await?;
-                break;
+        // SUB keeps the connection for its stream, so it is not timed
+        if command_kind == IpcCommandKind::Sub {
+            handle_sub_command(&state.store, state.usage_fetcher.as_ref(), &mut writer).await?;
+            break;
+        }
+
+        let span = tracing::info_span!(
+            "ipc_command",
+            command = %cmd.cmd,
+            session_id = cmd.session_id.as_deref().unwrap_or_default(),
+        );
+        let started = Instant::now();
+        let response = async {
+            match command_kind {
+                IpcCommandKind::Set => {
+                    handle_set_command(&cmd, &state.store, state.usage_fetcher.as_ref()).await
+                }
+                IpcCommandKind::Rm => handle_rm_command(&cmd, &state.store).await,
+                IpcCommandKind::List => handle_list_command(&state.store).await,
+                IpcCommandKind::Get => handle_get_command(&cmd, &state.store).await,
+                IpcCommandKind::Delete => handle_delete_command(&cmd, &state.store).await,
+                IpcCommandKind::Reopen => handle_reopen_command(&cmd, &state.store).await,
+                IpcCommandKind::Rename => handle_rename_command(&cmd, &state.store).await,
+                IpcCommandKind::Status => handle_status_command(state).await,
+                IpcCommandKind::Dump => handle_dump_command(state).await,
+                IpcCommandKind::Stop => handle_stop_command(&cmd, state).await,
+                IpcCommandKind::Sub => unreachable!("SUB is handled above"),
             }
-        };
+        }
+        .instrument(span)
+        .await;
+        telemetry::record_command(&cmd.cmd, started.elapsed());
 
         writer.write_all(response.as_bytes()).await?;
         writer.flush().await?;
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Instant;
 
-use crate::{Session, Status};
+use crate::{AgentType, Session, Status};
 
 /// Metadata for a closed session available for reopen.
 ///
@@ -26,6 +26,9 @@ use crate::{Session, Status};
 pub struct ClosedSession {
     /// Unique session identifier (matches the original session ID).
     pub session_id: String,
+    /// Agent the session was running, restored on reopen.
+    #[serde(default)]
+    pub agent_type: AgentType,
     /// Working directory the session was using.
     pub working_dir: Option<PathBuf>,
     /// Seconds since daemon start when the session was created.
@@ -67,6 +70,7 @@ impl ClosedSession {
 
         Self {
             session_id: session.session_id.clone(),
+            agent_type: session.agent_type.clone(),
             working_dir: session.working_dir.clone(),
             started_at_elapsed,
             closed_at_elapsed,
@@ -0,0 +1,252 @@
+//! StatsD metrics emitter.
+//!
+//! Every `interval`, sends one UDP packet with:
+//!
+//! - `<prefix>.sessions` - gauge of sessions per status
+//! - `<prefix>.transitions` - counter of sessions entering each status since
+//!   the previous packet
+//! - `<prefix>.usage.five_hour` / `<prefix>.usage.seven_day` - gauges of API
+//!   usage percent, once known
+//!
+//! Plain StatsD appends the status to the name (`acd.sessions.working:2|g`);
+//! DogStatsD sends it as a tag (`acd.sessions:2|g|#status:working`).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use claude_usage::UsageData;
+use tokio::net::UdpSocket;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::config::schema::StatsdConfig;
+use crate::daemon::store::SessionStore;
+use crate::daemon::telemetry::session_counts;
+use crate::daemon::usage::{UsageFetcher, UsageState};
+use crate::{Session, SessionUpdate, Status};
+
+/// Builds StatsD packets from session updates and snapshots.
+#[derive(Debug)]
+pub(crate) struct Emitter {
+    prefix: String,
+    dogstatsd: bool,
+    /// Last status seen per session.
+    last_status: HashMap<String, Status>,
+    /// Sessions that entered each status since the last packet.
+    transitions: Vec<(Status, u64)>,
+}
+
+impl Emitter {
+    pub(crate) fn new(prefix: &str, dogstatsd: bool) -> Self {
+        Self {
+            prefix: prefix.trim_end_matches('.').to_string(),
+            dogstatsd,
+            last_status: HashMap::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Counts `update` if its session entered a new status. Store broadcasts
+    /// also fire for priority and name changes, which do not count.
+    pub(crate) fn observe(&mut self, update: &SessionUpdate) {
+        let previous = self
+            .last_status
+            .insert(update.session_id.clone(), update.status);
+        if previous == Some(update.status) {
+            return;
+        }
+        match self
+            .transitions
+            .iter_mut()
+            .find(|(s, _)| *s == update.status)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.transitions.push((update.status, 1)),
+        }
+    }
+
+    /// Builds the packet for the current `sessions` and `usage`, and starts
+    /// counting transitions anew.
+    pub(crate) fn packet(&mut self, sessions: &[Session], usage: Option<&UsageData>) -> String {
+        let mut lines = Vec::new();
+        for (status, count) in session_counts(sessions) {
+            lines.push(self.metric("sessions", Some(status), count, "g"));
+        }
+        for (status, count) in std::mem::take(&mut self.transitions) {
+            lines.push(self.metric("transitions", Some(status), count, "c"));
+        }
+        if let Some(usage) = usage {
+            lines.push(self.metric("usage.five_hour", None, usage.five_hour.utilization, "g"));
+            lines.push(self.metric("usage.seven_day", None, usage.seven_day.utilization, "g"));
+        }
+
+        // Forget sessions that are gone
+        self.last_status
+            .retain(|id, _| sessions.iter().any(|s| &s.session_id == id));
+        lines.join("\n")
+    }
+
+    /// Formats one metric line.
+    fn metric(
+        &self,
+        name: &str,
+        status: Option<Status>,
+        value: impl std::fmt::Display,
+        kind: &str,
+    ) -> String {
+        match status {
+            Some(status) if self.dogstatsd => format!(
+                "{}.{}:{}|{}|#status:{}",
+                self.prefix, name, value, kind, status
+            ),
+            Some(status) => format!("{}.{}.{}:{}|{}", self.prefix, name, status, value, kind),
+            None => format!("{}.{}:{}|{}", self.prefix, name, value, kind),
+        }
+    }
+}
+
+/// Sends StatsD metrics as configured by `[statsd]` until shutdown.
+///
+/// Returns at once when disabled or misconfigured.
+pub(crate) async fn run(
+    store: SessionStore,
+    usage: Arc<UsageFetcher>,
+    config: StatsdConfig,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    if !config.enabled {
+        return;
+    }
+    let interval = match humantime::parse_duration(&config.interval) {
+        Ok(interval) if !interval.is_zero() => interval,
+        Ok(_) => {
+            warn!("statsd.interval must be positive, StatsD disabled");
+            return;
+        }
+        Err(e) => {
+            warn!(interval = %config.interval, "invalid statsd.interval, StatsD disabled: {}", e);
+            return;
+        }
+    };
+    let socket = match connect(&config.address).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            warn!(address = %config.address, "StatsD disabled: {}", e);
+            return;
+        }
+    };
+    info!(address = %config.address, "StatsD metrics enabled");
+
+    let mut emitter = Emitter::new(&config.prefix, config.dogstatsd);
+    let mut usage_updates = usage.subscribe();
+    let mut latest_usage: Option<UsageData> = None;
+    let mut updates = store.subscribe();
+    let mut ticks = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => return,
+            update = updates.recv() => match update {
+                Ok(update) => emitter.observe(&update),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!(skipped, "StatsD subscriber lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            state = usage_updates.recv() => {
+                if let Ok(UsageState::Available(data)) = state {
+                    latest_usage = Some(data);
+                }
+            }
+            _ = ticks.tick() => {
+                let packet = emitter.packet(&store.list_all().await, latest_usage.as_ref());
+                if let Err(e) = socket.send(packet.as_bytes()).await {
+                    debug!("failed to send StatsD metrics: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Opens a UDP socket connected to `address`.
+async fn connect(address: &str) -> std::io::Result<UdpSocket> {
+    let target = tokio::net::lookup_host(address)
+        .await?
+        .next()
+        .ok_or_else(|| std::io::Error::other("address resolved to nothing"))?;
+    let local = if target.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local).await?;
+    socket.connect(target).await?;
+    Ok(socket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+
+    fn update(id: &str, status: Status) -> SessionUpdate {
+        SessionUpdate::new(id.to_string(), status, 0)
+    }
+
+    fn session(id: &str, status: Status) -> Session {
+        let mut session = Session::new(id.to_string(), AgentType::ClaudeCode, None);
+        session.status = status;
+        session
+    }
+
+    #[test]
+    fn test_packet_counts_sessions_and_transitions() {
+        let mut emitter = Emitter::new("acd", false);
+        emitter.observe(&update("a", Status::Working));
+        emitter.observe(&update("a", Status::Attention));
+        // Priority or name change: same status again
+        emitter.observe(&update("a", Status::Attention));
+        emitter.observe(&update("b", Status::Attention));
+
+        let sessions = [
+            session("a", Status::Attention),
+            session("b", Status::Attention),
+        ];
+        let packet = emitter.packet(&sessions, None);
+        assert_eq!(
+            packet.lines().collect::<Vec<_>>(),
+            [
+                "acd.sessions.working:0|g",
+                "acd.sessions.attention:2|g",
+                "acd.sessions.question:0|g",
+                "acd.sessions.closed:0|g",
+                "acd.transitions.working:1|c",
+                "acd.transitions.attention:2|c",
+            ]
+        );
+
+        // Transitions are counted per packet
+        assert!(!emitter.packet(&sessions, None).contains("transitions"));
+    }
+
+    #[test]
+    fn test_dogstatsd_tags() {
+        let mut emitter = Emitter::new("team.acd.", true);
+        emitter.observe(&update("a", Status::Question));
+        let packet = emitter.packet(&[session("a", Status::Question)], None);
+        assert!(packet.contains("team.acd.sessions:1|g|#status:question"));
+        assert!(packet.contains("team.acd.transitions:1|c|#status:question"));
+    }
+
+    #[test]
+    fn test_gone_sessions_are_forgotten() {
+        let mut emitter = Emitter::new("acd", false);
+        emitter.observe(&update("a", Status::Working));
+        emitter.packet(&[], None);
+        assert!(emitter.last_status.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejects_unresolvable_address() {
+        assert!(connect("not an address").await.is_err());
+    }
+}
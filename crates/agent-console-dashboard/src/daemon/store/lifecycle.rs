@@ -4,7 +4,7 @@
 //! active lifecycle.
 
 use super::SessionStore;
-use crate::{AgentType, Session, Status, StoreError};
+use crate::{AgentType, Session, Status, StoreError, TerminalPane};
 use std::path::PathBuf;
 
 impl SessionStore {
@@ -73,6 +73,7 @@ impl SessionStore {
 
         // Insert and return clone
         sessions.insert(id, session.clone());
+        self.broadcast_session_created(&session.session_id);
         Ok(session)
     }
 
@@ -182,6 +183,7 @@ impl SessionStore {
 
         // Insert and return clone
         sessions.insert(id, session.clone());
+        self.broadcast_session_created(&session.session_id);
         session
     }
 
@@ -246,6 +248,84 @@ impl SessionStore {
         }
     }
 
+    /// Sets or clears a session's display name and returns the updated session.
+    ///
+    /// Always broadcasts an update so subscribers pick up the new name, even
+    /// though the status and priority are unchanged. Does not touch
+    /// `last_activity`: renaming is a user action, not hook activity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The session ID to rename.
+    /// * `display_name` - The new display name, or `None` to clear it.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Session)` with the updated session, or `None` if the session was not found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use agent_console_dashboard::daemon::store::SessionStore;
+    /// use agent_console_dashboard::AgentType;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let store = SessionStore::new();
+    ///     let _ = store.create_session(
+    ///         "session-1".to_string(),
+    ///         AgentType::ClaudeCode,
+    ///         None,
+    ///         None,
+    ///     ).await;
+    ///
+    ///     let renamed = store.rename_session("session-1", Some("api refactor".to_string())).await;
+    ///     assert_eq!(renamed.unwrap().display_name.as_deref(), Some("api refactor"));
+    ///
+    ///     assert!(store.rename_session("nonexistent", None).await.is_none());
+    /// }
+    /// ```
+    pub async fn rename_session(&self, id: &str, display_name: Option<String>) -> Option<Session> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(id)?;
+        session.display_name = display_name;
+        let updated = session.clone();
+        self.broadcast_session_update(&updated);
+        Some(updated)
+    }
+
+    /// Stores the latest assistant message excerpt and returns the updated session.
+    ///
+    /// Broadcasts an update only when the message actually changed, so repeated
+    /// hook events for the same turn do not wake subscribers. Like renaming, this
+    /// does not touch `last_activity`.
+    ///
+    /// Returns `None` if the session was not found.
+    pub async fn set_last_message(&self, id: &str, message: String) -> Option<Session> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(id)?;
+        if session.last_message.as_deref() != Some(message.as_str()) {
+            session.last_message = Some(message);
+            self.broadcast_session_update(session);
+        }
+        Some(session.clone())
+    }
+
+    /// Stores the multiplexer pane a hook reported for the session and returns
+    /// the updated session.
+    ///
+    /// Like [`Self::set_last_message`], broadcasts an update only when the pane
+    /// changed. Returns `None` if the session was not found.
+    pub async fn set_terminal_pane(&self, id: &str, pane: TerminalPane) -> Option<Session> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(id)?;
+        if session.terminal_pane.as_ref() != Some(&pane) {
+            session.terminal_pane = Some(pane);
+            self.broadcast_session_update(session);
+        }
+        Some(session.clone())
+    }
+
     /// Reopens a closed session by moving it from closed queue to active sessions.
     ///
     /// This method finds the session in the closed queue, removes it from there,
@@ -322,10 +402,9 @@ impl SessionStore {
         self.remove_closed(session_id).await;
 
         // Create new active session with status = Attention
-        // Default to ClaudeCode agent type since ClosedSession doesn't track agent_type
         let mut session = Session::new(
             closed_meta.session_id,
-            AgentType::ClaudeCode,
+            closed_meta.agent_type,
             closed_meta.working_dir,
         );
         session.set_status(Status::Attention);
@@ -61,6 +61,8 @@ pub struct SessionStore {
     /// Broadcast channel sender for subscriber notifications.
     /// Subscribers receive [`SessionUpdate`] messages on state changes.
     update_tx: broadcast::Sender<SessionUpdate>,
+    /// Broadcast channel sender for the IDs of newly created sessions.
+    created_tx: broadcast::Sender<String>,
     /// Closed session metadata for reopen, ordered by close time.
     closed: Arc<RwLock<VecDeque<ClosedSession>>>,
     /// Maximum count of closed sessions to retain before evicting oldest.
@@ -99,9 +101,11 @@ impl SessionStore {
     /// ```
     pub fn new() -> Self {
         let (update_tx, _rx) = broadcast::channel(DEFAULT_SUBSCRIBER_CHANNEL_CAPACITY);
+        let (created_tx, _rx) = broadcast::channel(DEFAULT_SUBSCRIBER_CHANNEL_CAPACITY);
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             update_tx,
+            created_tx,
             closed: Arc::new(RwLock::new(VecDeque::new())),
             max_closed_sessions: DEFAULT_MAX_CLOSED_SESSIONS,
             daemon_start: Instant::now(),
@@ -118,22 +122,48 @@ impl SessionStore {
         session: &Session,
     ) {
         if old_status != session.status || old_priority != session.priority {
-            let update = SessionUpdate::new(
-                session.session_id.clone(),
-                session.status,
-                session.since.elapsed().as_secs(),
-            );
-            match self.update_tx.send(update) {
-                Ok(count) => {
-                    tracing::trace!("Broadcast update sent to {} subscribers", count);
-                }
-                Err(_) => {
-                    tracing::debug!("No subscribers for session update broadcast");
-                }
+            self.broadcast_session_update(session);
+        }
+    }
+
+    /// Broadcasts a session update notification to all subscribers unconditionally.
+    ///
+    /// Used for changes that `broadcast_session_change` does not track, such as
+    /// a new display name.
+    pub(super) fn broadcast_session_update(&self, session: &Session) {
+        let update = SessionUpdate::new(
+            session.session_id.clone(),
+            session.status,
+            session.since.elapsed().as_secs(),
+        );
+        match self.update_tx.send(update) {
+            Ok(count) => {
+                tracing::trace!("Broadcast update sent to {} subscribers", count);
+                crate::daemon::telemetry::record_broadcast(count);
             }
+            Err(_) => {
+                tracing::debug!("No subscribers for session update broadcast");
+                crate::daemon::telemetry::record_broadcast(0);
+            }
+        }
+    }
+
+    /// Broadcasts the ID of a newly created session.
+    pub(super) fn broadcast_session_created(&self, session_id: &str) {
+        if self.created_tx.send(session_id.to_string()).is_err() {
+            tracing::trace!("No subscribers for session created broadcast");
         }
     }
 
+    /// Subscribes to session creation notifications.
+    ///
+    /// Returns a receiver of the IDs of sessions created from now on. Creation
+    /// is not a [`SessionUpdate`]: [`subscribe`](Self::subscribe) only reports
+    /// changes to existing sessions.
+    pub fn subscribe_created(&self) -> broadcast::Receiver<String> {
+        self.created_tx.subscribe()
+    }
+
     /// Subscribes to session update notifications.
     ///
     /// Returns a broadcast receiver that will receive [`SessionUpdate`] messages
@@ -35,6 +35,28 @@ async fn test_reopen_session_success() {
     assert!(!session.closed);
 }
 
+#[tokio::test]
+async fn test_reopen_session_keeps_agent_type() {
+    let store = SessionStore::new();
+    let _ = store
+        .get_or_create_session(
+            "codex-thread".to_string(),
+            AgentType::Codex,
+            Some(PathBuf::from("/tmp")),
+            None,
+            Status::Attention,
+            0,
+        )
+        .await;
+    store.close_session("codex-thread").await;
+
+    let session = store
+        .reopen_session("codex-thread")
+        .await
+        .expect("reopen should succeed");
+    assert_eq!(session.agent_type, AgentType::Codex);
+}
+
 #[tokio::test]
 async fn test_reopen_session_not_found() {
     let store = SessionStore::new();
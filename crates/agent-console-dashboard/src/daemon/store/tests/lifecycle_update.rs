@@ -140,3 +140,83 @@ async fn test_update_session_preserves_metadata() {
     assert_eq!(session.working_dir, Some(PathBuf::from("/specific/path")));
     assert_eq!(session.status, Status::Attention);
 }
+
+#[tokio::test]
+async fn test_rename_session_sets_and_clears_display_name() {
+    let store = SessionStore::new();
+
+    let _ = store
+        .create_session(
+            "rename-test".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/tmp/test")),
+            None,
+        )
+        .await;
+
+    let renamed = store
+        .rename_session("rename-test", Some("api refactor".to_string()))
+        .await
+        .expect("session should exist");
+    assert_eq!(renamed.display_name.as_deref(), Some("api refactor"));
+    assert_eq!(renamed.status, Status::Working);
+    assert!(renamed.history.is_empty());
+
+    let cleared = store
+        .rename_session("rename-test", None)
+        .await
+        .expect("session should exist");
+    assert!(cleared.display_name.is_none());
+}
+
+#[tokio::test]
+async fn test_rename_session_not_found() {
+    let store = SessionStore::new();
+    assert!(store
+        .rename_session("nonexistent", Some("name".to_string()))
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_rename_session_broadcasts_update() {
+    let store = SessionStore::new();
+    let _ = store
+        .create_session("rename-sub".to_string(), AgentType::ClaudeCode, None, None)
+        .await;
+    let mut rx = store.subscribe();
+
+    store
+        .rename_session("rename-sub", Some("named".to_string()))
+        .await;
+
+    let update = rx.try_recv().expect("rename should broadcast an update");
+    assert_eq!(update.session_id, "rename-sub");
+    assert_eq!(update.status, Status::Working);
+}
+
+#[tokio::test]
+async fn test_set_last_message_broadcasts_only_on_change() {
+    let store = SessionStore::new();
+    let _ = store
+        .create_session("message-sub".to_string(), AgentType::ClaudeCode, None, None)
+        .await;
+    let mut rx = store.subscribe();
+
+    let updated = store
+        .set_last_message("message-sub", "Run the tests?".to_string())
+        .await
+        .expect("session should exist");
+    assert_eq!(updated.last_message.as_deref(), Some("Run the tests?"));
+    assert!(rx.try_recv().is_ok(), "new message should broadcast");
+
+    store
+        .set_last_message("message-sub", "Run the tests?".to_string())
+        .await;
+    assert!(rx.try_recv().is_err(), "same message should not broadcast");
+
+    assert!(store
+        .set_last_message("nonexistent", "x".to_string())
+        .await
+        .is_none());
+}
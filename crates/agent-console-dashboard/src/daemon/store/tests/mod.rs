@@ -5,7 +5,7 @@
 //! - `lifecycle_*`: Session lifecycle methods
 //!   - `lifecycle_create`: create_session tests
 //!   - `lifecycle_get_or_create`: get_or_create_session tests
-//!   - `lifecycle_update`: update_session tests
+//!   - `lifecycle_update`: update_session and rename_session tests
 //!   - `lifecycle_close`: close_session and remove_session tests
 //!   - `lifecycle_reopen`: reopen_session tests
 //! - `concurrent`: Concurrent access and thread-safety
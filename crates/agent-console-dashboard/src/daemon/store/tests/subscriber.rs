@@ -347,3 +347,43 @@ async fn test_subscriber_update_contains_correct_elapsed_seconds() {
         "Elapsed seconds should be small right after status change"
     );
 }
+
+#[tokio::test]
+async fn test_created_subscriber_receives_new_sessions_only() {
+    let store = SessionStore::new();
+    let mut created = store.subscribe_created();
+
+    let _ = store
+        .create_session(
+            "created-1".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/tmp/test")),
+            None,
+        )
+        .await;
+    let _ = store
+        .get_or_create_session(
+            "created-2".to_string(),
+            AgentType::ClaudeCode,
+            None,
+            None,
+            Status::Attention,
+            0,
+        )
+        .await;
+    // Existing session: an update, not a creation
+    let _ = store
+        .get_or_create_session(
+            "created-1".to_string(),
+            AgentType::ClaudeCode,
+            None,
+            None,
+            Status::Question,
+            0,
+        )
+        .await;
+
+    assert_eq!(created.try_recv().ok().as_deref(), Some("created-1"));
+    assert_eq!(created.try_recv().ok().as_deref(), Some("created-2"));
+    assert!(created.try_recv().is_err());
+}
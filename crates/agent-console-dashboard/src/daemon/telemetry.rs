@@ -0,0 +1,276 @@
+//! OpenTelemetry export of daemon metrics and traces.
+//!
+//! With the `telemetry` feature and `[telemetry] enabled = true`, the daemon
+//! exports over OTLP/HTTP:
+//!
+//! - `acd.sessions` - gauge of sessions per `status`, sampled every
+//!   `export_interval`
+//! - `acd.ipc.command.duration` - histogram of IPC command latency in
+//!   seconds, per `command`
+//! - `acd.broadcast.subscribers` - histogram of how many subscribers each
+//!   session update reached
+//! - an `ipc_command` span per IPC command, with the events logged while
+//!   handling it
+//!
+//! The `record_*` functions are no-ops in builds without the feature, so call
+//! sites need no `cfg`.
+
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use crate::config::schema::TelemetryConfig;
+use crate::daemon::store::SessionStore;
+use crate::{Session, Status};
+
+/// Every status, so statuses without sessions report zero.
+const ALL_STATUSES: [Status; 4] = [
+    Status::Working,
+    Status::Attention,
+    Status::Question,
+    Status::Closed,
+];
+
+/// Counts `sessions` per status, including statuses without sessions.
+pub(crate) fn session_counts(sessions: &[Session]) -> [(Status, u64); 4] {
+    ALL_STATUSES.map(|status| {
+        let count = sessions.iter().filter(|s| s.status == status).count();
+        (status, count as u64)
+    })
+}
+
+/// Running exporters. Flush and stop them with [`Telemetry::shutdown`].
+#[derive(Debug)]
+pub struct Telemetry {
+    export_interval: Duration,
+    #[cfg(feature = "telemetry")]
+    tracer_provider: opentelemetry_sdk::trace::SdkTracerProvider,
+    #[cfg(feature = "telemetry")]
+    meter_provider: opentelemetry_sdk::metrics::SdkMeterProvider,
+}
+
+impl Telemetry {
+    /// Starts the exporters configured by `[telemetry]`, or returns `None` if
+    /// telemetry is disabled.
+    ///
+    /// Call it outside the Tokio runtime: the OTLP exporters use a blocking
+    /// HTTP client.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the configuration is invalid, the exporters cannot
+    /// be built, or acd was built without the `telemetry` feature.
+    pub fn init(config: &TelemetryConfig) -> Result<Option<Self>, String> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let export_interval = humantime::parse_duration(&config.export_interval).map_err(|e| {
+            format!(
+                "invalid telemetry.export_interval '{}': {}",
+                config.export_interval, e
+            )
+        })?;
+        Self::start(config, export_interval).map(Some)
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    fn start(_config: &TelemetryConfig, _export_interval: Duration) -> Result<Self, String> {
+        Err("telemetry is enabled, but acd was built without the telemetry feature".to_string())
+    }
+
+    #[cfg(feature = "telemetry")]
+    fn start(config: &TelemetryConfig, export_interval: Duration) -> Result<Self, String> {
+        use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig, WithHttpConfig};
+        use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+        use opentelemetry_sdk::trace::SdkTracerProvider;
+        use opentelemetry_sdk::Resource;
+
+        let endpoint = config.endpoint.trim_end_matches('/');
+        let headers: std::collections::HashMap<_, _> = config
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let resource = Resource::builder()
+            .with_service_name(config.service_name.clone())
+            .build();
+
+        let spans = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/traces", endpoint))
+            .with_headers(headers.clone())
+            .build()
+            .map_err(|e| format!("failed to create span exporter: {}", e))?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(spans)
+            .with_resource(resource.clone())
+            .build();
+
+        let metrics = MetricExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/metrics", endpoint))
+            .with_headers(headers)
+            .build()
+            .map_err(|e| format!("failed to create metric exporter: {}", e))?;
+        let reader = PeriodicReader::builder(metrics)
+            .with_interval(export_interval)
+            .build();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(reader)
+            .with_resource(resource)
+            .build();
+        opentelemetry::global::set_meter_provider(meter_provider.clone());
+
+        Ok(Self {
+            export_interval,
+            tracer_provider,
+            meter_provider,
+        })
+    }
+
+    /// How often metrics are exported.
+    pub fn export_interval(&self) -> Duration {
+        self.export_interval
+    }
+
+    /// Layer that exports `tracing` spans, for the daemon's subscriber.
+    #[cfg(feature = "telemetry")]
+    pub(crate) fn tracing_layer<S>(
+        &self,
+    ) -> tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>
+    where
+        S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    {
+        use opentelemetry::trace::TracerProvider;
+
+        tracing_opentelemetry::layer().with_tracer(self.tracer_provider.tracer("acd"))
+    }
+
+    /// Exports what is pending and stops the exporters.
+    pub fn shutdown(self) {
+        #[cfg(feature = "telemetry")]
+        {
+            if let Err(e) = self.tracer_provider.shutdown() {
+                tracing::warn!("failed to flush telemetry spans: {}", e);
+            }
+            if let Err(e) = self.meter_provider.shutdown() {
+                tracing::warn!("failed to flush telemetry metrics: {}", e);
+            }
+        }
+    }
+}
+
+/// Records the session counts of `store` every `interval` until shutdown.
+pub(crate) async fn sample_sessions(
+    store: SessionStore,
+    interval: Duration,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    let mut ticks = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => record_sessions(&store.list_all().await),
+            _ = shutdown.recv() => break,
+        }
+    }
+}
+
+/// Records the number of sessions per status.
+pub(crate) fn record_sessions(sessions: &[Session]) {
+    let counts = session_counts(sessions);
+    #[cfg(feature = "telemetry")]
+    for (status, count) in counts {
+        instruments().sessions.record(
+            count,
+            &[opentelemetry::KeyValue::new("status", status.to_string())],
+        );
+    }
+    #[cfg(not(feature = "telemetry"))]
+    let _ = counts;
+}
+
+/// Records how long the IPC `command` took to handle.
+pub(crate) fn record_command(command: &str, elapsed: Duration) {
+    #[cfg(feature = "telemetry")]
+    instruments().command_duration.record(
+        elapsed.as_secs_f64(),
+        &[opentelemetry::KeyValue::new("command", command.to_string())],
+    );
+    #[cfg(not(feature = "telemetry"))]
+    let _ = (command, elapsed);
+}
+
+/// Records how many subscribers a session update was broadcast to.
+pub(crate) fn record_broadcast(subscribers: usize) {
+    #[cfg(feature = "telemetry")]
+    instruments()
+        .broadcast_subscribers
+        .record(subscribers as u64, &[]);
+    #[cfg(not(feature = "telemetry"))]
+    let _ = subscribers;
+}
+
+#[cfg(feature = "telemetry")]
+struct Instruments {
+    sessions: opentelemetry::metrics::Gauge<u64>,
+    command_duration: opentelemetry::metrics::Histogram<f64>,
+    broadcast_subscribers: opentelemetry::metrics::Histogram<u64>,
+}
+
+/// Instruments of the global meter provider, created on first use (after
+/// [`Telemetry::init`] installed the provider).
+#[cfg(feature = "telemetry")]
+fn instruments() -> &'static Instruments {
+    static INSTRUMENTS: std::sync::OnceLock<Instruments> = std::sync::OnceLock::new();
+    INSTRUMENTS.get_or_init(|| {
+        let meter = opentelemetry::global::meter("acd");
+        Instruments {
+            sessions: meter
+                .u64_gauge("acd.sessions")
+                .with_description("Sessions per status")
+                .build(),
+            command_duration: meter
+                .f64_histogram("acd.ipc.command.duration")
+                .with_description("Time to handle an IPC command")
+                .with_unit("s")
+                .build(),
+            broadcast_subscribers: meter
+                .u64_histogram("acd.broadcast.subscribers")
+                .with_description("Subscribers reached by a session update")
+                .build(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_telemetry_starts_nothing() {
+        let telemetry = Telemetry::init(&TelemetryConfig::default()).expect("disabled is valid");
+        assert!(telemetry.is_none());
+    }
+
+    #[test]
+    fn test_invalid_export_interval() {
+        let config = TelemetryConfig {
+            enabled: true,
+            export_interval: "soon".to_string(),
+            ..TelemetryConfig::default()
+        };
+        let err = Telemetry::init(&config).expect_err("interval is invalid");
+        assert!(err.contains("telemetry.export_interval"), "{}", err);
+    }
+
+    #[test]
+    fn test_session_counts_include_empty_statuses() {
+        let sessions = vec![
+            Session::new("a".into(), crate::AgentType::ClaudeCode, None),
+            Session::new("b".into(), crate::AgentType::ClaudeCode, None),
+        ];
+        let counts = session_counts(&sessions);
+        assert_eq!(counts[0], (Status::Working, 2));
+        assert_eq!(counts[3], (Status::Closed, 0));
+    }
+}
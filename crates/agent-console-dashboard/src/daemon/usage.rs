@@ -1,18 +1,24 @@
 //! Usage fetcher module for periodic Claude API usage data retrieval.
 //!
-//! This module provides [`UsageFetcher`], which periodically calls
-//! [`claude_usage::get_usage()`] and broadcasts the results to subscribers
-//! via a tokio broadcast channel. Fetching only occurs when at least one
+//! This module provides [`UsageFetcher`], a thin adapter over the
+//! [`claude_usage::stream::UsageStream`] polling subscription that
+//! broadcasts the results to subscribers via a tokio broadcast channel. Fetching only occurs when at least one
 //! subscriber is listening (conditional fetching per D3 decision).
 //!
 //! The daemon is the single source of truth for usage data (D3). TUIs never
-//! call `claude_usage::get_usage()` directly.
+//! call `claude_usage` directly.
+//!
+//! The fetcher goes through a [`UsageClient`], so tests can swap in
+//! `claude_usage::mock::MockUsageClient` via [`UsageFetcher::with_client`].
 
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use claude_usage::transport::{LiveUsageClient, UsageClient};
+use claude_usage::watcher::{UsageEvent, UsageWatcher};
 use claude_usage::UsageData;
+use futures::StreamExt;
 use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, info, warn};
 
@@ -20,6 +26,8 @@ use tracing::{debug, info, warn};
 const DEFAULT_FETCH_INTERVAL: Duration = Duration::from_secs(180);
 
 /// Snapshot of the current usage state, broadcast to subscribers.
+// Only one state is held at a time, so boxing the data would not save memory
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum UsageState {
     /// Usage data successfully fetched.
@@ -35,13 +43,13 @@ pub enum UsageState {
 
 /// Periodic usage data fetcher.
 ///
-/// Calls `claude_usage::get_usage()` at a configurable interval and broadcasts
+/// Polls usage through `claude_usage`'s event stream at a configurable interval and broadcasts
 /// results to all subscribers. Only fetches when `subscriber_count > 0`.
 ///
 /// # Design
 ///
-/// - Uses `tokio::task::spawn_blocking` because `claude_usage` only provides
-///   a blocking HTTP client.
+/// - Uses the async `claude_usage` client, so polling never ties up a
+///   blocking thread.
 /// - Retains previous data on error (subscribers keep last known good state).
 /// - Errors are logged as warnings; the daemon never crashes on fetch failure.
 pub struct UsageFetcher {
@@ -55,6 +63,8 @@ pub struct UsageFetcher {
     interval: Duration,
     /// Set to true when a 403 Forbidden is received; skips all future fetches.
     blocked: Arc<AtomicBool>,
+    /// Source of usage data (the live API outside tests).
+    client: Arc<dyn UsageClient>,
 }
 
 impl UsageFetcher {
@@ -65,6 +75,11 @@ impl UsageFetcher {
 
     /// Creates a new `UsageFetcher` with a custom fetch interval.
     pub fn with_interval(interval: Duration) -> Self {
+        Self::with_client(interval, Arc::new(LiveUsageClient))
+    }
+
+    /// Creates a new `UsageFetcher` that fetches from `client`.
+    pub fn with_client(interval: Duration, client: Arc<dyn UsageClient>) -> Self {
         let (update_tx, _rx) = broadcast::channel(16);
         Self {
             state: Arc::new(RwLock::new(UsageState::Unavailable)),
@@ -72,6 +87,7 @@ impl UsageFetcher {
             subscriber_count: Arc::new(AtomicUsize::new(0)),
             interval,
             blocked: Arc::new(AtomicBool::new(false)),
+            client,
         }
     }
 
@@ -98,15 +114,22 @@ impl UsageFetcher {
 
     /// Runs the periodic fetch loop until the shutdown receiver fires.
     ///
-    /// This function should be spawned as a tokio task. It fetches usage data
-    /// at the configured interval, but only when subscribers are present.
+    /// This function should be spawned as a tokio task. Polling is done by the
+    /// library's usage stream, gated so it fetches only when subscribers are
+    /// present and the API has not blocked us; this loop just applies the
+    /// resulting events.
     pub async fn run(&self, mut shutdown_rx: broadcast::Receiver<()>) {
-        let mut ticker = tokio::time::interval(self.interval);
+        let blocked = Arc::clone(&self.blocked);
+        let subscriber_count = Arc::clone(&self.subscriber_count);
+        let mut events = UsageWatcher::new()
+            .interval(self.interval)
+            .into_stream(Arc::clone(&self.client))
+            .poll_if(move || should_fetch(&blocked, &subscriber_count));
 
         loop {
             tokio::select! {
-                _ = ticker.tick() => {
-                    self.fetch_once().await;
+                Some(event) = events.next() => {
+                    self.apply(event).await;
                 }
                 _ = shutdown_rx.recv() => {
                     info!("usage fetcher shutting down");
@@ -147,50 +170,71 @@ impl UsageFetcher {
     /// as unavailable (previous data is lost in the shared state but subscribers may
     /// retain their last received value).
     pub(crate) async fn fetch_once(&self) {
-        // Skip permanently if the API blocked us.
-        if self.blocked.load(Ordering::SeqCst) {
-            debug!("usage API is blocked (403), skipping fetch");
+        if !should_fetch(&self.blocked, &self.subscriber_count) {
             return;
         }
+        let event = match self.client.fetch_usage().await {
+            Ok(data) => UsageEvent::Updated(data),
+            Err(e) => UsageEvent::Error(e),
+        };
+        self.apply(event).await;
+    }
 
-        let count = self.subscriber_count.load(Ordering::SeqCst);
-        if count == 0 {
-            debug!("no usage subscribers, skipping fetch");
-            return;
-        }
-
-        debug!(subscriber_count = count, "fetching usage data");
-
-        let result = tokio::task::spawn_blocking(claude_usage::get_usage).await;
-
-        match result {
-            Ok(Ok(data)) => {
+    /// Applies a usage event to the shared state and broadcasts the change.
+    async fn apply(&self, event: UsageEvent) {
+        match event {
+            UsageEvent::Updated(data) => {
                 let new_state = UsageState::Available(data);
                 *self.state.write().await = new_state.clone();
                 // Best-effort broadcast; no subscribers is not an error.
                 let _ = self.update_tx.send(new_state);
                 debug!("usage data fetched and broadcast successfully");
             }
-            Ok(Err(claude_usage::Error::Api(claude_usage::ApiError::Forbidden))) => {
+            UsageEvent::Error(claude_usage::Error::Api(claude_usage::ApiError::Forbidden)) => {
                 warn!("usage API returned 403 Forbidden — OAuth token blocked by Anthropic; disabling usage fetching");
                 self.blocked.store(true, Ordering::SeqCst);
                 *self.state.write().await = UsageState::Blocked;
                 let _ = self.update_tx.send(UsageState::Blocked);
             }
-            Ok(Err(e)) => {
+            UsageEvent::Error(e) => {
                 warn!(error = %e, "usage fetch failed");
                 *self.state.write().await = UsageState::Unavailable;
                 let _ = self.update_tx.send(UsageState::Unavailable);
             }
-            Err(e) => {
-                warn!(error = %e, "usage fetch task panicked");
-                *self.state.write().await = UsageState::Unavailable;
-                let _ = self.update_tx.send(UsageState::Unavailable);
+            UsageEvent::ThresholdCrossed(e) => {
+                info!(
+                    window = %e.window,
+                    threshold = e.threshold,
+                    utilization = e.utilization,
+                    "usage threshold crossed"
+                );
+            }
+            UsageEvent::PeriodReset(e) => {
+                debug!(window = %e.window, "usage period reset");
             }
         }
     }
 }
 
+/// Returns true if a fetch should happen now: the API has not blocked us
+/// and at least one subscriber is listening.
+fn should_fetch(blocked: &AtomicBool, subscriber_count: &AtomicUsize) -> bool {
+    // Skip permanently if the API blocked us.
+    if blocked.load(Ordering::SeqCst) {
+        debug!("usage API is blocked (403), skipping fetch");
+        return false;
+    }
+
+    let count = subscriber_count.load(Ordering::SeqCst);
+    if count == 0 {
+        debug!("no usage subscribers, skipping fetch");
+        return false;
+    }
+
+    debug!(subscriber_count = count, "fetching usage data");
+    true
+}
+
 impl Default for UsageFetcher {
     fn default() -> Self {
         Self::new()
@@ -233,6 +277,7 @@ impl Drop for UsageSubscription {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use claude_usage::mock::{fixtures, MockUsageClient};
 
     #[test]
     fn test_usage_fetcher_default_creates_with_3min_interval() {
@@ -436,12 +481,16 @@ mod tests {
             five_hour: claude_usage::UsagePeriod {
                 utilization: 0.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: claude_usage::UsagePeriod {
                 utilization: 0.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         };
         *fetcher.state.write().await = UsageState::Available(fake_data.clone());
@@ -481,4 +530,86 @@ mod tests {
             "subscriber should receive update after trigger_refresh_if_unavailable"
         );
     }
+
+    // -------------------------------------------------------------------------
+    // Mock client tests
+    // -------------------------------------------------------------------------
+
+    fn mock_fetcher(client: MockUsageClient) -> (UsageFetcher, Arc<MockUsageClient>) {
+        let client = Arc::new(client);
+        let fetcher = UsageFetcher::with_client(DEFAULT_FETCH_INTERVAL, client.clone());
+        (fetcher, client)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_once_broadcasts_available_data() {
+        let (fetcher, client) = mock_fetcher(MockUsageClient::new(fixtures::usage(42.0, 7.0)));
+        let mut sub = fetcher.subscribe();
+
+        fetcher.fetch_once().await;
+
+        assert_eq!(client.calls(), 1);
+        match sub.recv().await.expect("update") {
+            UsageState::Available(data) => assert_eq!(data.five_hour.utilization, 42.0),
+            other => panic!("expected Available, got {:?}", other),
+        }
+        assert!(matches!(
+            *fetcher.state.read().await,
+            UsageState::Available(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_once_forbidden_blocks_future_fetches() {
+        let (fetcher, client) = mock_fetcher(
+            MockUsageClient::new(fixtures::idle()).with_response(Err(fixtures::forbidden())),
+        );
+        let _sub = fetcher.subscribe();
+
+        fetcher.fetch_once().await;
+        assert!(matches!(*fetcher.state.read().await, UsageState::Blocked));
+
+        // The fallback data is never requested once blocked.
+        fetcher.fetch_once().await;
+        assert_eq!(client.calls(), 1);
+        assert!(matches!(*fetcher.state.read().await, UsageState::Blocked));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_once_error_marks_unavailable() {
+        let (fetcher, _client) = mock_fetcher(MockUsageClient::empty());
+        let _sub = fetcher.subscribe();
+        *fetcher.state.write().await = UsageState::Available(fixtures::idle());
+
+        fetcher.fetch_once().await;
+
+        assert!(matches!(
+            *fetcher.state.read().await,
+            UsageState::Unavailable
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_run_applies_stream_events() {
+        let client = Arc::new(MockUsageClient::new(fixtures::usage(12.0, 3.0)));
+        let fetcher = UsageFetcher::with_client(Duration::from_millis(20), client.clone());
+        let mut sub = fetcher.subscribe();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+
+        let handle = tokio::spawn(async move {
+            fetcher.run(shutdown_rx).await;
+        });
+
+        let update = tokio::time::timeout(Duration::from_secs(2), sub.recv())
+            .await
+            .expect("update within timeout")
+            .expect("channel open");
+        assert!(matches!(update, UsageState::Available(_)));
+
+        shutdown_tx
+            .send(())
+            .expect("shutdown signal should be sent");
+        handle.await.expect("run task should not panic");
+        assert!(client.calls() >= 1);
+    }
 }
@@ -0,0 +1,206 @@
+//! Native desktop notifications.
+//!
+//! The daemon sends one when a session enters a configured status (see
+//! `crate::daemon::notifications`). The notifier is picked per platform:
+//!
+//! - Linux: `notify-send`
+//! - macOS: `terminal-notifier` if installed, else `osascript`
+//!
+//! `terminal-notifier` groups notifications by session, so a newer one
+//! replaces an older one for the same session, and clicking raises the
+//! terminal app the daemon was started from (`__CFBundleIdentifier`).
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::{Session, Status};
+
+/// A desktop notification ready to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Summary line, naming the session and what happened.
+    pub title: String,
+    /// Details: the working directory and a click-through hint.
+    pub body: String,
+    /// Session ID, used to group notifications of one session.
+    pub group: String,
+}
+
+impl Notification {
+    /// Builds the notification for `session` entering its current status.
+    pub fn for_session(session: &Session) -> Self {
+        let name = super::pane_name(session).unwrap_or_else(|| short_id(&session.session_id));
+        let what = super::status_phrase(session.status);
+        let mut body = session
+            .working_dir
+            .as_ref()
+            .map(|dir| format!("{}\n", dir.display()))
+            .unwrap_or_default();
+        body.push_str(if session.status == Status::Closed {
+            "Press r on it in acd to resurrect"
+        } else {
+            "Press Enter on it in acd to jump there"
+        });
+        Self {
+            title: format!("{} {}", name, what),
+            body,
+            group: session.session_id.clone(),
+        }
+    }
+}
+
+/// First 8 characters of a session ID, as shown in the TUI.
+fn short_id(session_id: &str) -> String {
+    session_id.chars().take(8).collect()
+}
+
+/// Program used to show notifications.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notifier {
+    /// Linux `notify-send` (libnotify).
+    NotifySend,
+    /// macOS `terminal-notifier`, activating the given app bundle on click.
+    TerminalNotifier {
+        /// Bundle ID of the app to raise on click, if known.
+        activate: Option<String>,
+    },
+    /// macOS `osascript` (`display notification`), always available.
+    Osascript,
+}
+
+impl Notifier {
+    /// Picks the notifier for this platform, or `None` if none is installed.
+    pub fn detect() -> Option<Self> {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let installed = |program: &str| {
+            std::env::split_paths(&path).any(|dir| is_executable(&dir.join(program)))
+        };
+        if cfg!(target_os = "macos") {
+            if installed("terminal-notifier") {
+                let activate = std::env::var("__CFBundleIdentifier").ok();
+                return Some(Self::TerminalNotifier { activate });
+            }
+            return Some(Self::Osascript);
+        }
+        installed("notify-send").then_some(Self::NotifySend)
+    }
+
+    /// Command that shows `notification`.
+    pub fn command(&self, notification: &Notification) -> Command {
+        match self {
+            Self::NotifySend => {
+                let mut command = Command::new("notify-send");
+                command
+                    .arg("--app-name=acd")
+                    .arg(&notification.title)
+                    .arg(&notification.body);
+                command
+            }
+            Self::TerminalNotifier { activate } => {
+                let mut command = Command::new("terminal-notifier");
+                command
+                    .args(["-title", "acd"])
+                    .args(["-subtitle", &notification.title])
+                    .args(["-message", &notification.body])
+                    .args(["-group", &notification.group]);
+                if let Some(bundle) = activate {
+                    command.args(["-activate", bundle]);
+                }
+                command
+            }
+            Self::Osascript => {
+                let script = format!(
+                    "display notification {} with title \"acd\" subtitle {}",
+                    applescript_string(&notification.body),
+                    applescript_string(&notification.title)
+                );
+                let mut command = Command::new("osascript");
+                command.arg("-e").arg(script);
+                command
+            }
+        }
+    }
+}
+
+/// Quotes `text` as an AppleScript string literal.
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::command_line;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    fn notification() -> Notification {
+        let mut session = Session::new(
+            "0123456789abcdef".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        );
+        session.status = Status::Question;
+        Notification::for_session(&session)
+    }
+
+    #[test]
+    fn test_notification_for_session() {
+        assert_eq!(
+            notification(),
+            Notification {
+                title: "api has a question".to_string(),
+                body: "/home/u/api\nPress Enter on it in acd to jump there".to_string(),
+                group: "0123456789abcdef".to_string(),
+            }
+        );
+
+        let mut closed = Session::new("0123456789abcdef".to_string(), AgentType::ClaudeCode, None);
+        closed.status = Status::Closed;
+        let n = Notification::for_session(&closed);
+        assert_eq!(n.title, "01234567 was closed");
+        assert_eq!(n.body, "Press r on it in acd to resurrect");
+    }
+
+    #[test]
+    fn test_notify_send_command() {
+        assert_eq!(
+            command_line(&Notifier::NotifySend.command(&notification())),
+            vec![
+                "notify-send",
+                "--app-name=acd",
+                "api has a question",
+                "/home/u/api\nPress Enter on it in acd to jump there"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_terminal_notifier_groups_and_activates() {
+        let notifier = Notifier::TerminalNotifier {
+            activate: Some("com.mitchellh.ghostty".to_string()),
+        };
+        let line = command_line(&notifier.command(&notification()));
+        assert_eq!(line[0], "terminal-notifier");
+        assert!(line.windows(2).any(|w| w == ["-group", "0123456789abcdef"]));
+        assert!(line
+            .windows(2)
+            .any(|w| w == ["-activate", "com.mitchellh.ghostty"]));
+    }
+
+    #[test]
+    fn test_osascript_escapes_quotes() {
+        let mut n = notification();
+        n.title = r#"say "hi" \ bye"#.to_string();
+        let line = command_line(&Notifier::Osascript.command(&n));
+        assert_eq!(line[..2], ["osascript", "-e"]);
+        assert!(line[2].ends_with(r#"subtitle "say \"hi\" \\ bye""#));
+    }
+}
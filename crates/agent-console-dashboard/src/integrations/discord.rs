@@ -0,0 +1,144 @@
+//! Discord notifications through a channel webhook.
+//!
+//! Session messages are rich embeds: the title comes from
+//! `notifications.discord.template`, the colour from the status, and fields
+//! list the session ID, project, status and time spent in the previous
+//! status. Usage alerts are a single embed.
+
+use claude_usage::UsageData;
+use serde_json::{json, Value};
+
+use crate::config::schema::DiscordConfig;
+use crate::tui::footer::render_placeholders;
+use crate::{Session, Status};
+
+/// Embed colours, matching the TUI status colours.
+fn status_color(status: Status) -> u32 {
+    match status {
+        Status::Attention => 0xE3_B3_41,
+        Status::Question => 0xB1_5E_D8,
+        Status::Working => 0x3F_B9_50,
+        Status::Closed => 0x80_80_80,
+    }
+}
+
+/// Colour of usage alert embeds.
+const USAGE_COLOR: u32 = 0xE5_53_53;
+
+/// Posts messages to one Discord webhook.
+#[derive(Debug, Clone)]
+pub struct DiscordNotifier {
+    client: reqwest::Client,
+    config: DiscordConfig,
+}
+
+impl DiscordNotifier {
+    /// Creates a notifier, or `None` if no webhook URL is configured.
+    pub fn from_config(config: &DiscordConfig) -> Option<Self> {
+        if config.webhook_url.trim().is_empty() {
+            return None;
+        }
+        Some(Self {
+            client: super::webhook_client()?,
+            config: config.clone(),
+        })
+    }
+
+    /// Embed announcing that `session` entered its current status.
+    pub fn session_payload(&self, session: &Session) -> Value {
+        let placeholder = |name: &str| super::session_placeholder(session, name);
+        let title = render_placeholders(&self.config.template, placeholder);
+        let field = |name: &str, key: &str| {
+            let value = placeholder(key).filter(|v| !v.is_empty());
+            json!({
+                "name": name,
+                "value": value.unwrap_or_else(|| "-".to_string()),
+                "inline": key != "working_dir",
+            })
+        };
+        self.payload(json!({
+            "title": title,
+            "color": status_color(session.status),
+            "fields": [
+                field("Session", "session_id"),
+                field("Status", "status"),
+                field("Elapsed", "elapsed"),
+                field("Project", "working_dir"),
+            ],
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        }))
+    }
+
+    /// Embed announcing that 5-hour usage reached `usage_threshold`.
+    pub fn usage_payload(&self, usage: &UsageData) -> Value {
+        self.payload(json!({
+            "title": super::usage_alert_text(usage),
+            "color": USAGE_COLOR,
+        }))
+    }
+
+    fn payload(&self, embed: Value) -> Value {
+        let mut payload = json!({ "embeds": [embed] });
+        if !self.config.username.is_empty() {
+            payload["username"] = json!(self.config.username);
+        }
+        payload
+    }
+
+    /// POSTs `payload` to the webhook.
+    ///
+    /// # Errors
+    ///
+    /// Returns the request error, or an error for a non-2xx response.
+    pub async fn post(&self, payload: &Value) -> Result<(), reqwest::Error> {
+        super::post_webhook(&self.client, &self.config.webhook_url, payload).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    fn notifier() -> DiscordNotifier {
+        DiscordNotifier::from_config(&DiscordConfig {
+            webhook_url: "https://discord.invalid/api/webhooks/1/x".to_string(),
+            ..DiscordConfig::default()
+        })
+        .expect("notifier")
+    }
+
+    #[test]
+    fn test_disabled_without_webhook_url() {
+        assert!(DiscordNotifier::from_config(&DiscordConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_session_embed() {
+        let mut session = Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        );
+        session.status = Status::Question;
+        let payload = notifier().session_payload(&session);
+        assert_eq!(payload["username"], "acd");
+        let embed = &payload["embeds"][0];
+        assert_eq!(embed["title"], "api has a question");
+        assert_eq!(embed["color"], 0xB15ED8);
+        assert_eq!(
+            embed["fields"],
+            json!([
+                { "name": "Session", "value": "abc", "inline": true },
+                { "name": "Status", "value": "question", "inline": true },
+                { "name": "Elapsed", "value": "0s", "inline": true },
+                { "name": "Project", "value": "/home/u/api", "inline": false },
+            ])
+        );
+
+        session.working_dir = None;
+        let payload = notifier().session_payload(&session);
+        assert_eq!(payload["embeds"][0]["fields"][3]["value"], "-");
+    }
+}
@@ -0,0 +1,350 @@
+//! Email through an SMTP relay.
+//!
+//! A minimal SMTP client for daemon digests: `EHLO`, optional `STARTTLS` (or
+//! TLS from the start), `AUTH PLAIN`, then one plain-text message to every
+//! recipient. Messages are UTF-8 `text/plain`, sent as 8-bit.
+
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::Engine;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use crate::config::schema::{EmailConfig, SmtpSecurity};
+
+/// Longest a whole SMTP exchange may take.
+const SEND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Sender address when neither `from` nor `username` is configured.
+const FALLBACK_FROM: &str = "acd@localhost";
+
+/// A plain or TLS connection to the relay.
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+/// Sends email through one SMTP relay.
+#[derive(Debug, Clone)]
+pub struct Mailer {
+    config: EmailConfig,
+}
+
+impl Mailer {
+    /// Creates a mailer, or `None` if no relay or recipient is configured.
+    pub fn from_config(config: &EmailConfig) -> Option<Self> {
+        if config.smtp_host.trim().is_empty() || config.to.is_empty() {
+            return None;
+        }
+        Some(Self {
+            config: config.clone(),
+        })
+    }
+
+    /// Sender address: `from`, else the username.
+    pub fn from_address(&self) -> &str {
+        [&self.config.from, &self.config.username]
+            .into_iter()
+            .find(|address| !address.is_empty())
+            .map_or(FALLBACK_FROM, |address| address.as_str())
+    }
+
+    /// Builds the message with headers, CRLF line endings and dot-stuffing,
+    /// ready to follow `DATA` (without the terminating `.`).
+    pub fn message(&self, subject: &str, body: &str) -> String {
+        let date = chrono::Local::now().to_rfc2822();
+        let mut message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+             Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n",
+            self.from_address(),
+            self.config.to.join(", "),
+            encode_header(subject),
+            date,
+        );
+        for line in body.lines() {
+            if line.starts_with('.') {
+                message.push('.');
+            }
+            message.push_str(line);
+            message.push_str("\r\n");
+        }
+        message
+    }
+
+    /// Sends `body` with `subject` to every recipient.
+    ///
+    /// # Errors
+    ///
+    /// Returns connection and TLS errors, a timeout, or an error naming the
+    /// command the relay rejected.
+    pub async fn send(&self, subject: &str, body: &str) -> io::Result<()> {
+        let message = self.message(subject, body);
+        tokio::time::timeout(SEND_TIMEOUT, self.exchange(&message))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "SMTP exchange timed out"))?
+    }
+
+    async fn exchange(&self, message: &str) -> io::Result<()> {
+        let host = self.config.smtp_host.trim();
+        let tcp = TcpStream::connect((host, self.config.smtp_port)).await?;
+        let mut conn = match self.config.security {
+            SmtpSecurity::Tls => Connection::new(tls(tcp, host).await?),
+            SmtpSecurity::Starttls | SmtpSecurity::None => Connection::new(Box::new(tcp)),
+        };
+        conn.expect(220, "greeting").await?;
+        conn.command("EHLO acd", 250).await?;
+        if self.config.security == SmtpSecurity::Starttls {
+            conn.command("STARTTLS", 220).await?;
+            conn = Connection::new(tls(conn.stream, host).await?);
+            conn.command("EHLO acd", 250).await?;
+        }
+        if !self.config.username.is_empty() {
+            let credentials = format!("\0{}\0{}", self.config.username, self.config.password);
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            conn.command(&format!("AUTH PLAIN {}", encoded), 235)
+                .await
+                .map_err(|_| io::Error::other("SMTP authentication failed"))?;
+        }
+        conn.command(&format!("MAIL FROM:<{}>", self.from_address()), 250)
+            .await?;
+        for to in &self.config.to {
+            conn.command(&format!("RCPT TO:<{}>", to), 250).await?;
+        }
+        conn.command("DATA", 354).await?;
+        conn.stream.write_all(message.as_bytes()).await?;
+        conn.command(".", 250).await?;
+        // The message is accepted; a failed QUIT does not matter
+        let _ = conn.command("QUIT", 221).await;
+        Ok(())
+    }
+}
+
+/// Encodes a header value as an RFC 2047 encoded word if it is not ASCII.
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+    format!(
+        "=?utf-8?B?{}?=",
+        base64::engine::general_purpose::STANDARD.encode(value)
+    )
+}
+
+/// Wraps `stream` in TLS, verifying `host` against the bundled web PKI
+/// roots.
+async fn tls<S: Stream + 'static>(stream: S, host: &str) -> io::Result<Box<dyn Stream>> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let provider = Arc::new(tokio_rustls::rustls::crypto::ring::default_provider());
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let name = ServerName::try_from(host.to_string())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(name, stream)
+        .await?;
+    Ok(Box::new(stream))
+}
+
+/// An SMTP connection: sends commands and reads (multi-line) replies.
+struct Connection {
+    stream: Box<dyn Stream>,
+    buf: Vec<u8>,
+}
+
+impl Connection {
+    fn new(stream: Box<dyn Stream>) -> Self {
+        Self {
+            stream,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Sends `line` and expects a reply with `code`.
+    async fn command(&mut self, line: &str, code: u16) -> io::Result<String> {
+        self.stream
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .await?;
+        let verb = line.split_whitespace().next().unwrap_or(line);
+        self.expect(code, verb).await
+    }
+
+    /// Reads a reply and returns its text if its code is `code`.
+    async fn expect(&mut self, code: u16, step: &str) -> io::Result<String> {
+        let reply = self.reply().await?;
+        let got = reply.get(..3).and_then(|c| c.parse::<u16>().ok());
+        // 251: "user not local; will forward" also accepts a recipient
+        if got == Some(code) || (code == 250 && got == Some(251)) {
+            Ok(reply)
+        } else {
+            Err(io::Error::other(format!(
+                "SMTP {} rejected: {}",
+                step,
+                reply.trim_end()
+            )))
+        }
+    }
+
+    /// Reads one reply; continuation lines (`250-...`) are joined.
+    async fn reply(&mut self) -> io::Result<String> {
+        let mut reply = String::new();
+        loop {
+            let line = self.line().await?;
+            let last = line.as_bytes().get(3) != Some(&b'-');
+            reply.push_str(&line);
+            reply.push('\n');
+            if last {
+                return Ok(reply);
+            }
+        }
+    }
+
+    async fn line(&mut self) -> io::Result<String> {
+        loop {
+            if let Some(end) = self.buf.windows(2).position(|w| w == b"\r\n") {
+                let line = String::from_utf8_lossy(&self.buf[..end]).into_owned();
+                self.buf.drain(..end + 2);
+                return Ok(line);
+            }
+            let mut chunk = [0u8; 512];
+            let n = self.stream.read(&mut chunk).await?;
+            if n == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn config(port: u16) -> EmailConfig {
+        EmailConfig {
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: port,
+            security: SmtpSecurity::None,
+            to: vec!["me@example.com".to_string()],
+            ..EmailConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_without_host_or_recipients() {
+        assert!(Mailer::from_config(&EmailConfig::default()).is_none());
+        let no_recipients = EmailConfig {
+            to: Vec::new(),
+            ..config(25)
+        };
+        assert!(Mailer::from_config(&no_recipients).is_none());
+    }
+
+    #[test]
+    fn test_from_address_falls_back_to_username() {
+        let mut config = config(25);
+        assert_eq!(
+            Mailer::from_config(&config).expect("mailer").from_address(),
+            "acd@localhost"
+        );
+        config.username = "me@example.com".to_string();
+        assert_eq!(
+            Mailer::from_config(&config).expect("mailer").from_address(),
+            "me@example.com"
+        );
+        config.from = "acd@example.com".to_string();
+        assert_eq!(
+            Mailer::from_config(&config).expect("mailer").from_address(),
+            "acd@example.com"
+        );
+    }
+
+    #[test]
+    fn test_message_headers_and_dot_stuffing() {
+        let mailer = Mailer::from_config(&config(25)).expect("mailer");
+        let message = mailer.message("Sessions — stuck", "one\n.two\n");
+        assert!(message.starts_with("From: acd@localhost\r\nTo: me@example.com\r\n"));
+        assert!(message.contains("Subject: =?utf-8?B?"));
+        assert!(message.ends_with("\r\n\r\none\r\n..two\r\n"));
+    }
+
+    /// Fake relay: answers each command from `replies` and returns what the
+    /// client sent.
+    async fn fake_relay(listener: TcpListener, replies: Vec<&'static str>) -> Vec<String> {
+        let (stream, _) = listener.accept().await.expect("accept");
+        let (read, mut write) = stream.into_split();
+        let mut lines = BufReader::new(read).lines();
+        write.write_all(b"220 fake ESMTP\r\n").await.expect("greet");
+        let mut received = Vec::new();
+        let mut replies = replies.into_iter();
+        let mut in_data = false;
+        while let Ok(Some(line)) = lines.next_line().await {
+            received.push(line.clone());
+            if in_data && line != "." {
+                continue;
+            }
+            in_data = line == "DATA";
+            let Some(reply) = replies.next() else { break };
+            write.write_all(reply.as_bytes()).await.expect("reply");
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_send_speaks_smtp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        let relay = tokio::spawn(fake_relay(
+            listener,
+            vec![
+                "250-fake\r\n250 AUTH PLAIN\r\n",
+                "235 ok\r\n",
+                "250 ok\r\n",
+                "250 ok\r\n",
+                "354 go\r\n",
+                "250 queued\r\n",
+                "221 bye\r\n",
+            ],
+        ));
+        let mailer = Mailer::from_config(&EmailConfig {
+            username: "u".to_string(),
+            password: "p".to_string(),
+            ..config(port)
+        })
+        .expect("mailer");
+        mailer.send("Hi", "body").await.expect("send");
+
+        let received = relay.await.expect("relay");
+        assert_eq!(received[0], "EHLO acd");
+        assert_eq!(received[1], "AUTH PLAIN AHUAcA==");
+        assert_eq!(received[2], "MAIL FROM:<u>");
+        assert_eq!(received[3], "RCPT TO:<me@example.com>");
+        assert_eq!(received[4], "DATA");
+        assert!(received.contains(&"Subject: Hi".to_string()));
+        assert_eq!(received[received.len() - 2], ".");
+        assert_eq!(received[received.len() - 1], "QUIT");
+    }
+
+    #[tokio::test]
+    async fn test_send_reports_rejected_command() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        tokio::spawn(fake_relay(
+            listener,
+            vec!["250 fake\r\n", "250 ok\r\n", "550 no such user\r\n"],
+        ));
+        let mailer = Mailer::from_config(&config(port)).expect("mailer");
+        let err = mailer.send("Hi", "body").await.expect_err("rejected");
+        assert_eq!(err.to_string(), "SMTP RCPT rejected: 550 no such user");
+    }
+}
@@ -0,0 +1,359 @@
+//! GitHub pull request and issue lookup through the `gh` CLI.
+//!
+//! A session is associated with the pull request whose head is the branch
+//! checked out in its working directory, and with the issue its branch name
+//! refers to (`123-fix-login`, `fix/123-login`, `issue-123`). `gh` handles
+//! authentication and GitHub Enterprise hosts.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde::Deserialize;
+
+/// Fields requested from `gh pr view`.
+const PR_FIELDS: &str = "number,title,url,state,isDraft,reviewDecision,statusCheckRollup";
+
+/// What GitHub knows about the branch a session works on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchInfo {
+    /// Branch checked out in the working directory.
+    pub branch: String,
+    /// Issue number the branch name refers to.
+    pub issue: Option<u64>,
+    /// Pull request with the branch as its head, if any.
+    pub pull_request: Option<PullRequest>,
+}
+
+/// A pull request and its review state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    /// Pull request number.
+    pub number: u64,
+    /// Pull request title.
+    pub title: String,
+    /// Web URL of the pull request.
+    pub url: String,
+    /// Open, draft, merged or closed.
+    pub state: PrState,
+    /// Combined state of the CI checks on the head commit.
+    pub checks: ChecksState,
+    /// Review decision, or `None` if the repository requires no reviews.
+    pub review: Option<ReviewDecision>,
+}
+
+/// Lifecycle state of a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrState {
+    /// Open and ready for review.
+    Open,
+    /// Open as a draft.
+    Draft,
+    /// Merged.
+    Merged,
+    /// Closed without merging.
+    Closed,
+}
+
+impl PrState {
+    /// Lowercase name for the detail panel.
+    pub fn label(self) -> &'static str {
+        match self {
+            PrState::Open => "open",
+            PrState::Draft => "draft",
+            PrState::Merged => "merged",
+            PrState::Closed => "closed",
+        }
+    }
+}
+
+/// Combined state of a pull request's CI checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksState {
+    /// No checks ran.
+    None,
+    /// At least one check has not finished, and none failed.
+    Pending,
+    /// Every check passed (or was skipped).
+    Passing,
+    /// At least one check failed.
+    Failing,
+}
+
+impl ChecksState {
+    /// Short description for the detail panel, or `None` without checks.
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            ChecksState::None => None,
+            ChecksState::Pending => Some("checks pending"),
+            ChecksState::Passing => Some("checks passing"),
+            ChecksState::Failing => Some("checks failing"),
+        }
+    }
+}
+
+/// Review decision of a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewDecision {
+    /// Approved by the required reviewers.
+    Approved,
+    /// A reviewer requested changes.
+    ChangesRequested,
+    /// Waiting for a required review.
+    ReviewRequired,
+}
+
+impl ReviewDecision {
+    /// Short description for the detail panel.
+    pub fn label(self) -> &'static str {
+        match self {
+            ReviewDecision::Approved => "approved",
+            ReviewDecision::ChangesRequested => "changes requested",
+            ReviewDecision::ReviewRequired => "review required",
+        }
+    }
+}
+
+/// `gh pr view --json` output.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPullRequest {
+    number: u64,
+    title: String,
+    url: String,
+    state: String,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    review_decision: Option<String>,
+    #[serde(default)]
+    status_check_rollup: Vec<GhCheck>,
+}
+
+/// A check run (`status`, `conclusion`) or commit status (`state`).
+#[derive(Deserialize)]
+struct GhCheck {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    conclusion: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+}
+
+/// Parses the output of `gh pr view --json <PR_FIELDS>`.
+///
+/// # Errors
+///
+/// Returns a message if the output is not the expected JSON.
+pub fn parse_pr_view(json: &str) -> Result<PullRequest, String> {
+    let pr: GhPullRequest =
+        serde_json::from_str(json).map_err(|e| format!("unexpected gh output: {}", e))?;
+    let state = match pr.state.as_str() {
+        "MERGED" => PrState::Merged,
+        "CLOSED" => PrState::Closed,
+        _ if pr.is_draft => PrState::Draft,
+        _ => PrState::Open,
+    };
+    let review = match pr.review_decision.as_deref() {
+        Some("APPROVED") => Some(ReviewDecision::Approved),
+        Some("CHANGES_REQUESTED") => Some(ReviewDecision::ChangesRequested),
+        Some("REVIEW_REQUIRED") => Some(ReviewDecision::ReviewRequired),
+        _ => None,
+    };
+    Ok(PullRequest {
+        number: pr.number,
+        title: pr.title,
+        url: pr.url,
+        state,
+        checks: checks_state(&pr.status_check_rollup),
+        review,
+    })
+}
+
+/// Combines the states of individual checks.
+fn checks_state(checks: &[GhCheck]) -> ChecksState {
+    let mut combined = ChecksState::None;
+    for check in checks {
+        let state = match (
+            check.status.as_deref(),
+            check.conclusion.as_deref(),
+            check.state.as_deref(),
+        ) {
+            // Commit statuses
+            (_, _, Some("FAILURE" | "ERROR")) => ChecksState::Failing,
+            (_, _, Some("PENDING" | "EXPECTED")) => ChecksState::Pending,
+            (_, _, Some(_)) => ChecksState::Passing,
+            // Check runs
+            (Some(status), _, _) if status != "COMPLETED" => ChecksState::Pending,
+            (
+                _,
+                Some("FAILURE" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED" | "STARTUP_FAILURE"),
+                _,
+            ) => ChecksState::Failing,
+            _ => ChecksState::Passing,
+        };
+        combined = match (combined, state) {
+            (ChecksState::Failing, _) | (_, ChecksState::Failing) => ChecksState::Failing,
+            (ChecksState::Pending, _) | (_, ChecksState::Pending) => ChecksState::Pending,
+            _ => ChecksState::Passing,
+        };
+    }
+    combined
+}
+
+/// Returns the issue number a branch name refers to.
+///
+/// Looks at the last path component: a leading number (`123-fix-login`,
+/// `fix/123-login`) or an `issue-`/`issues-`/`gh-` prefix (`issue-123`).
+pub fn issue_from_branch(branch: &str) -> Option<u64> {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let name = ["issue-", "issues-", "gh-"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+    let digits: &str = &name[..name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len())];
+    let rest = &name[digits.len()..];
+    if digits.is_empty() || !(rest.is_empty() || rest.starts_with(['-', '_'])) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Looks up the branch checked out in `dir` and its pull request.
+///
+/// Returns `Ok(None)` if `dir` is not in a git repository or HEAD is
+/// detached. Runs `git` and `gh`, so call it off the UI thread.
+///
+/// # Errors
+///
+/// Returns a message if `gh` is missing, not logged in, or fails otherwise.
+pub async fn lookup(dir: &Path) -> Result<Option<BranchInfo>, String> {
+    let branch = tokio::process::Command::new("git")
+        .args(["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    let Some(branch) = branch.filter(|branch| !branch.is_empty()) else {
+        return Ok(None);
+    };
+
+    let output = tokio::process::Command::new("gh")
+        .args(["pr", "view", &branch, "--json", PR_FIELDS])
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "gh is not installed".to_string(),
+            _ => format!("failed to run gh: {}", e),
+        })?;
+    let pull_request = if output.status.success() {
+        Some(parse_pr_view(&String::from_utf8_lossy(&output.stdout))?)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.contains("no pull requests found") {
+            let message = stderr.lines().next().unwrap_or("gh failed").trim();
+            return Err(message.to_string());
+        }
+        None
+    };
+    Ok(Some(BranchInfo {
+        issue: issue_from_branch(&branch),
+        branch,
+        pull_request,
+    }))
+}
+
+/// Command that opens the pull request at `url` in the browser.
+pub fn open_command(url: &str) -> Command {
+    let mut command = Command::new("gh");
+    command.args(["pr", "view", url, "--web"]);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::command_line;
+
+    #[test]
+    fn test_parse_pr_view() {
+        let pr = parse_pr_view(
+            r#"{"number":42,"title":"Fix login","url":"https://github.com/o/r/pull/42",
+                "state":"OPEN","isDraft":false,"reviewDecision":"CHANGES_REQUESTED",
+                "statusCheckRollup":[
+                    {"__typename":"CheckRun","status":"COMPLETED","conclusion":"SUCCESS"},
+                    {"__typename":"StatusContext","state":"SUCCESS"}]}"#,
+        )
+        .expect("valid gh output");
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.state, PrState::Open);
+        assert_eq!(pr.checks, ChecksState::Passing);
+        assert_eq!(pr.review, Some(ReviewDecision::ChangesRequested));
+
+        let draft = parse_pr_view(
+            r#"{"number":7,"title":"WIP","url":"u","state":"OPEN","isDraft":true,
+                "reviewDecision":"","statusCheckRollup":[]}"#,
+        )
+        .expect("valid gh output");
+        assert_eq!(draft.state, PrState::Draft);
+        assert_eq!(draft.checks, ChecksState::None);
+        assert_eq!(draft.review, None);
+
+        assert!(parse_pr_view("not json").is_err());
+    }
+
+    #[test]
+    fn test_checks_state() {
+        let check = |status: Option<&str>, conclusion: Option<&str>, state: Option<&str>| GhCheck {
+            status: status.map(String::from),
+            conclusion: conclusion.map(String::from),
+            state: state.map(String::from),
+        };
+        let passed = check(Some("COMPLETED"), Some("SUCCESS"), None);
+        let running = check(Some("IN_PROGRESS"), None, None);
+        let failed = check(Some("COMPLETED"), Some("FAILURE"), None);
+        let status_error = check(None, None, Some("ERROR"));
+
+        assert_eq!(checks_state(&[]), ChecksState::None);
+        assert_eq!(
+            checks_state(&[check(Some("COMPLETED"), Some("SKIPPED"), None)]),
+            ChecksState::Passing
+        );
+        assert_eq!(
+            checks_state(&[passed, check(Some("IN_PROGRESS"), None, None)]),
+            ChecksState::Pending
+        );
+        assert_eq!(checks_state(&[running, failed]), ChecksState::Failing);
+        assert_eq!(checks_state(&[status_error]), ChecksState::Failing);
+    }
+
+    #[test]
+    fn test_issue_from_branch() {
+        assert_eq!(issue_from_branch("123-fix-login"), Some(123));
+        assert_eq!(issue_from_branch("fix/45_login"), Some(45));
+        assert_eq!(issue_from_branch("issue-9"), Some(9));
+        assert_eq!(issue_from_branch("user/gh-77-cleanup"), Some(77));
+        assert_eq!(issue_from_branch("main"), None);
+        assert_eq!(issue_from_branch("v2-release"), None);
+        assert_eq!(issue_from_branch("2024q1"), None);
+    }
+
+    #[test]
+    fn test_open_command() {
+        assert_eq!(
+            command_line(&open_command("https://github.com/o/r/pull/42")),
+            vec![
+                "gh",
+                "pr",
+                "view",
+                "https://github.com/o/r/pull/42",
+                "--web"
+            ]
+        );
+    }
+}
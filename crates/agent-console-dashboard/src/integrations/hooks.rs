@@ -0,0 +1,272 @@
+//! Shell hooks: the TUI's activate, reopen and key hooks, and the daemon's
+//! transition hooks (`[[notifications.hooks]]`).
+//!
+//! A hook entry either carries its own command or names an entry of the
+//! `[hooks.<name>]` library. [`prepare`] resolves the name and fills in a
+//! library command's `{placeholder}` tokens; inline commands run verbatim,
+//! as they did before the library existed. [`run`] spawns the result via
+//! `sh -c` with the session in its environment (`ACD_SESSION_ID`,
+//! `ACD_WORKING_DIR`, `ACD_STATUS`) and as a JSON `SessionSnapshot` on stdin,
+//! then waits for it up to its timeout.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::schema::HookConfig;
+use crate::tui::palette::render_command;
+use crate::{Session, SessionSnapshot};
+
+/// A hook ready to run for one session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedHook {
+    /// Name used in logs and failure messages, e.g. `activate hook[0]`.
+    pub label: String,
+    /// Command with its placeholders filled in.
+    pub command: String,
+    /// Seconds to wait before killing the hook.
+    pub timeout: u64,
+}
+
+/// What a hook learns about its session.
+#[derive(Debug, Clone)]
+pub struct HookInput {
+    session_id: String,
+    working_dir: String,
+    status: String,
+    payload: String,
+}
+
+impl HookInput {
+    /// Captures `session` for hooks run in the background.
+    pub fn new(session: &Session) -> Result<Self, serde_json::Error> {
+        let snapshot: SessionSnapshot = session.into();
+        Ok(Self {
+            session_id: session.session_id.clone(),
+            working_dir: session
+                .working_dir
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            status: session.status.to_string(),
+            payload: serde_json::to_string(&snapshot)?,
+        })
+    }
+}
+
+/// Resolves `hook` against `library` and renders its command for `session`.
+///
+/// Only commands from the library have their placeholders filled in; a
+/// hook's own `command` is kept as written, so existing commands with
+/// literal braces keep working.
+///
+/// Fails with a message naming the hook if `library` has no entry by the
+/// name `hook` references.
+pub fn prepare(
+    hook: &HookConfig,
+    library: &BTreeMap<String, HookConfig>,
+    session: &Session,
+    label: String,
+) -> Result<PreparedHook, String> {
+    let resolved = hook
+        .resolve(library)
+        .ok_or_else(|| format!("{}: unknown hook `{}`", label, hook.hook))?;
+    let command = if hook.hook.is_empty() {
+        resolved.command.clone()
+    } else {
+        render_command(&resolved.command, session)
+    };
+    Ok(PreparedHook {
+        label,
+        command,
+        timeout: resolved.timeout,
+    })
+}
+
+/// Runs `hook` and waits for it, killing it after its timeout.
+///
+/// Stdout and stderr are logged at debug level, or at warn level if the hook
+/// fails. Returns the failure message to show the user.
+pub fn run(hook: &PreparedHook, input: &HookInput) -> Result<(), String> {
+    let label = &hook.label;
+    tracing::debug!("executing {}: {}", label, hook.command);
+
+    let spawn_result = Command::new("sh")
+        .arg("-c")
+        .arg(&hook.command)
+        .env("ACD_SESSION_ID", &input.session_id)
+        .env("ACD_WORKING_DIR", &input.working_dir)
+        .env("ACD_STATUS", &input.status)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match spawn_result {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!("{} failed to spawn: {}", label, e);
+            return Err(format!("{} failed to start: {}", label, e));
+        }
+    };
+
+    // Write JSON payload to stdin, then close stdin so the hook can read EOF
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Err(e) = stdin.write_all(input.payload.as_bytes()) {
+            tracing::warn!("{} failed to write stdin: {}", label, e);
+        }
+        // stdin dropped here → EOF sent to child
+    }
+
+    // Read stdout/stderr in separate threads to avoid deadlocking on large output
+    let mut stdout_handle = child.stdout.take();
+    let mut stderr_handle = child.stderr.take();
+    let stdout_thread = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(ref mut h) = stdout_handle {
+            let _ = h.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stderr_thread = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(ref mut h) = stderr_handle {
+            let _ = h.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    // Wait with timeout: poll every 50ms up to `timeout` seconds.
+    let deadline = Instant::now() + Duration::from_secs(hook.timeout);
+    let exit = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                tracing::debug!("{} exited with: {}", label, status);
+                break Ok(status);
+            }
+            Ok(None) => {
+                if Instant::now() >= deadline {
+                    tracing::warn!("{} timed out after {}s, killing", label, hook.timeout);
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break Err(format!("timed out after {}s", hook.timeout));
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(e) => {
+                tracing::warn!("{} wait error: {}", label, e);
+                break Err(format!("wait failed: {}", e));
+            }
+        }
+    };
+
+    let stdout_bytes = stdout_thread.join().unwrap_or_default();
+    let stderr_bytes = stderr_thread.join().unwrap_or_default();
+    let stdout = String::from_utf8_lossy(&stdout_bytes);
+    let stderr = String::from_utf8_lossy(&stderr_bytes);
+    let failure = match exit {
+        Ok(status) if status.success() => None,
+        Ok(status) => Some(super::failure_message(status, &stderr)),
+        Err(e) => Some(e),
+    };
+    let Some(failure) = failure else {
+        if !stdout.is_empty() {
+            tracing::debug!("{} stdout: {}", label, stdout.trim());
+        }
+        if !stderr.is_empty() {
+            tracing::debug!("{} stderr: {}", label, stderr.trim());
+        }
+        return Ok(());
+    };
+    tracing::warn!(
+        "{} failed: {}\nstdout: {}\nstderr: {}",
+        label,
+        failure,
+        stdout.trim(),
+        stderr.trim()
+    );
+    Err(format!("{} failed: {}", label, failure))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    fn session() -> Session {
+        Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/it's here")),
+        )
+    }
+
+    fn library() -> BTreeMap<String, HookConfig> {
+        BTreeMap::from([(
+            "open-editor".to_string(),
+            HookConfig {
+                command: "code {working_dir}".to_string(),
+                timeout: 9,
+                ..HookConfig::default()
+            },
+        )])
+    }
+
+    #[test]
+    fn test_prepare_resolves_named_hooks_and_placeholders() {
+        let hook = HookConfig {
+            hook: "open-editor".to_string(),
+            ..HookConfig::default()
+        };
+        let prepared = prepare(&hook, &library(), &session(), "key e".to_string())
+            .expect("hook is in the library");
+        assert_eq!(prepared.command, r#"code '/home/u/it'\''s here'"#);
+        assert_eq!(prepared.timeout, 9);
+    }
+
+    #[test]
+    fn test_prepare_keeps_inline_commands_verbatim() {
+        let hook = HookConfig {
+            command: "awk '{print $1}' {working_dir}".to_string(),
+            ..HookConfig::default()
+        };
+        let prepared = prepare(
+            &hook,
+            &library(),
+            &session(),
+            "activate hook[0]".to_string(),
+        )
+        .expect("inline hook");
+        assert_eq!(prepared.command, "awk '{print $1}' {working_dir}");
+    }
+
+    #[test]
+    fn test_prepare_reports_unknown_names() {
+        let hook = HookConfig {
+            hook: "missing".to_string(),
+            ..HookConfig::default()
+        };
+        let err = prepare(&hook, &library(), &session(), "key e".to_string())
+            .expect_err("hook is not in the library");
+        assert_eq!(err, "key e: unknown hook `missing`");
+    }
+
+    #[test]
+    fn test_run_passes_session_and_reports_failures() {
+        let input = HookInput::new(&session()).expect("session serializes");
+        let hook = |command: &str| PreparedHook {
+            label: "test hook".to_string(),
+            command: command.to_string(),
+            timeout: 5,
+        };
+        assert_eq!(
+            run(&hook(r#"test "$ACD_SESSION_ID" = abc"#), &input),
+            Ok(())
+        );
+        let err = run(&hook("echo boom >&2; exit 3"), &input).expect_err("hook fails");
+        assert!(err.starts_with("test hook failed: "), "{err}");
+        assert!(err.contains("boom"), "{err}");
+    }
+}
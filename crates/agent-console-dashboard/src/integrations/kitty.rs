@@ -0,0 +1,243 @@
+//! kitty backend driven by `kitty @` remote control.
+//!
+//! Panes are kitty windows, listed with `kitty @ ls` and focused by ID, which
+//! also raises the window's tab and OS window. Spawning launches a new kitty
+//! window.
+//!
+//! Remote control must be reachable without a tty, since commands run
+//! detached from the TUI: kitty has to listen on a socket (`listen_on` in
+//! kitty.conf), which it exports to child processes as `KITTY_LISTEN_ON`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use super::{Pane, TerminalBackend};
+
+/// The kitty instance the TUI runs in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Kitty {
+    /// Remote control address from `KITTY_LISTEN_ON`, passed as `--to`.
+    pub listen_on: String,
+}
+
+/// An OS window in `kitty @ ls` output (fields we use).
+#[derive(Debug, Deserialize)]
+struct OsWindow {
+    #[serde(default)]
+    tabs: Vec<Tab>,
+}
+
+/// A tab in `kitty @ ls` output.
+#[derive(Debug, Deserialize)]
+struct Tab {
+    #[serde(default)]
+    windows: Vec<Window>,
+}
+
+/// A window in `kitty @ ls` output.
+#[derive(Debug, Deserialize)]
+struct Window {
+    id: u64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    cwd: String,
+}
+
+impl Kitty {
+    /// Detects kitty from `TERM=xterm-kitty` or `KITTY_WINDOW_ID` (set in
+    /// every kitty window, and kept when `TERM` is overridden), with
+    /// `KITTY_LISTEN_ON` set.
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let in_kitty =
+            env("TERM").as_deref() == Some("xterm-kitty") || env("KITTY_WINDOW_ID").is_some();
+        if !in_kitty {
+            return None;
+        }
+        Self::with_env(env)
+    }
+
+    /// Remote control through `KITTY_LISTEN_ON`, wherever the caller runs;
+    /// None if it is not set.
+    pub fn with_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let listen_on = env("KITTY_LISTEN_ON").filter(|addr| !addr.is_empty())?;
+        Some(Self { listen_on })
+    }
+
+    /// `kitty @ --to <addr> <args...>`
+    fn remote<I, S>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut command = Command::new("kitty");
+        command.arg("@").arg("--to").arg(&self.listen_on).args(args);
+        command
+    }
+}
+
+impl TerminalBackend for Kitty {
+    fn name(&self) -> &'static str {
+        "kitty"
+    }
+
+    fn list_panes(&self) -> Result<Vec<Pane>, String> {
+        let list = super::output(self.remote(["ls"]), self.name())?;
+        parse_ls(&list)
+    }
+
+    fn focus_pane_command(&self, pane: &Pane) -> Command {
+        self.remote(["focus-window", "--match", &format!("id:{}", pane.id)])
+    }
+
+    fn spawn_command(&self, dir: Option<&Path>, title: Option<&str>, argv: &[String]) -> Command {
+        let mut args = vec!["launch".to_string(), "--type=window".to_string()];
+        if let Some(dir) = dir {
+            args.push(format!("--cwd={}", dir.display()));
+        }
+        if let Some(title) = title {
+            args.push(format!("--title={}", title));
+        }
+        args.extend(argv.iter().cloned());
+        self.remote(args)
+    }
+
+    /// `focus-window` already raises the OS window.
+    fn raise_command(&self) -> Option<Command> {
+        None
+    }
+}
+
+/// Windows in `kitty @ ls` output, across OS windows and tabs.
+fn parse_ls(list_json: &str) -> Result<Vec<Pane>, String> {
+    let os_windows: Vec<OsWindow> =
+        serde_json::from_str(list_json).map_err(|e| format!("invalid kitty @ ls output: {}", e))?;
+    Ok(os_windows
+        .into_iter()
+        .flat_map(|os_window| os_window.tabs)
+        .flat_map(|tab| tab.windows)
+        .map(|window| Pane {
+            id: window.id.to_string(),
+            title: window.title,
+            working_dir: (!window.cwd.is_empty()).then(|| PathBuf::from(window.cwd)),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::command_line;
+    use crate::{AgentType, Session};
+
+    fn resume() -> Vec<String> {
+        vec!["claude".into(), "--resume".into(), "abc-123".into()]
+    }
+
+    fn kitty() -> Kitty {
+        Kitty {
+            listen_on: "unix:/tmp/kitty".to_string(),
+        }
+    }
+
+    fn session() -> Session {
+        Session::new(
+            "abc-123".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/my.api")),
+        )
+    }
+
+    #[test]
+    fn test_from_env_requires_kitty_term_and_listen_socket() {
+        let env = |term: &'static str| {
+            move |name: &str| match name {
+                "TERM" => Some(term.to_string()),
+                "KITTY_LISTEN_ON" => Some("unix:/tmp/kitty".to_string()),
+                _ => None,
+            }
+        };
+        assert_eq!(Kitty::from_env(env("xterm-kitty")), Some(kitty()));
+        assert_eq!(Kitty::from_env(env("xterm-256color")), None);
+        let window = |name: &str| match name {
+            "TERM" => Some("xterm-256color".to_string()),
+            "KITTY_WINDOW_ID" => Some("3".to_string()),
+            "KITTY_LISTEN_ON" => Some("unix:/tmp/kitty".to_string()),
+            _ => None,
+        };
+        assert_eq!(Kitty::from_env(window), Some(kitty()));
+        assert_eq!(Kitty::with_env(env("xterm-256color")), Some(kitty()));
+        assert_eq!(
+            Kitty::from_env(|name| (name == "TERM").then(|| "xterm-kitty".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_ls() {
+        let list = r#"[{"id": 1, "tabs": [
+            {"id": 1, "windows": [{"id": 3, "title": "vim", "cwd": "/home/u/my.api"}]},
+            {"id": 2, "windows": [{"id": 5, "title": "shell"}]}
+        ]}]"#;
+        assert_eq!(
+            parse_ls(list).expect("parse"),
+            vec![
+                Pane {
+                    id: "3".to_string(),
+                    title: "vim".to_string(),
+                    working_dir: Some(PathBuf::from("/home/u/my.api")),
+                },
+                Pane {
+                    id: "5".to_string(),
+                    title: "shell".to_string(),
+                    working_dir: None,
+                },
+            ]
+        );
+        assert!(parse_ls("not json").is_err());
+    }
+
+    #[test]
+    fn test_focus_pane_command_matches_window_id() {
+        let pane = Pane {
+            id: "3".to_string(),
+            title: "vim".to_string(),
+            working_dir: None,
+        };
+        assert_eq!(
+            command_line(&kitty().focus_pane_command(&pane)),
+            vec![
+                "kitty",
+                "@",
+                "--to",
+                "unix:/tmp/kitty",
+                "focus-window",
+                "--match",
+                "id:3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resurrect_command_launches_window_in_working_dir() {
+        let command = kitty().resurrect_command(&session(), &resume());
+        assert_eq!(
+            command_line(&command),
+            vec![
+                "kitty",
+                "@",
+                "--to",
+                "unix:/tmp/kitty",
+                "launch",
+                "--type=window",
+                "--cwd=/home/u/my.api",
+                "--title=my.api",
+                "claude",
+                "--resume",
+                "abc-123"
+            ]
+        );
+    }
+}
@@ -0,0 +1,750 @@
+//! Terminal multiplexer, emulator and desktop integrations.
+//!
+//! [`desktop`], [`slack`], [`discord`], [`ntfy`], [`email`], [`webhook`] and
+//! [`mqtt`] send notifications for the daemon, [`github`] looks up pull
+//! requests for the TUI, and [`hooks`] runs the shell hooks of both; the rest
+//! of this module is about terminal backends.
+//!
+//! A [`TerminalBackend`] adapts one terminal multiplexer or emulator (tmux,
+//! Zellij, kitty, WezTerm) to three operations: list its panes, focus one,
+//! and spawn a command in a new pane in a given directory. Focusing a
+//! session's pane and resurrecting a closed session are built on those, so the
+//! TUI, `acd resurrect --exec` and the MCP server do not depend on the
+//! backend. [`detect`] picks it from the environment unless
+//! `integrations.terminal` names one. The TUI uses the detected backend for Enter/double-click and `r`
+//! when no `tui.activate_hooks` / `tui.reopen_hooks` are configured;
+//! configured hooks always take precedence.
+//!
+//! Backends build [`Command`]s and run their CLI only to list panes.
+//! [`spawn`] runs focus commands in the background so the TUI does not wait on
+//! the terminal; resurrecting uses [`run`] so callers can report whether the
+//! pane opened. Both keep the stderr of failed commands: the full text is
+//! logged and its last lines make up the [`failure_message`] callers show.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::config::schema::{IntegrationsConfig, TerminalChoice};
+use crate::{Multiplexer, Session, SessionSnapshot, Status};
+
+pub mod desktop;
+pub mod discord;
+pub mod email;
+pub mod github;
+pub mod hooks;
+pub mod kitty;
+pub mod mqtt;
+pub mod ntfy;
+pub mod slack;
+pub mod tmux;
+pub mod webhook;
+pub mod wezterm;
+pub mod zellij;
+
+pub use kitty::Kitty;
+pub use tmux::Tmux;
+pub use wezterm::WezTerm;
+pub use zellij::Zellij;
+
+/// A pane, window or tab a backend can focus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pane {
+    /// Backend ID used to focus it (tmux/WezTerm pane ID, kitty window ID,
+    /// Zellij tab name).
+    pub id: String,
+    /// Pane, window or tab title.
+    pub title: String,
+    /// Current working directory, if the backend reports one.
+    pub working_dir: Option<PathBuf>,
+}
+
+/// A terminal environment that can list, focus and spawn panes.
+pub trait TerminalBackend: std::fmt::Debug + Send + Sync {
+    /// Human-readable backend name, used in footer messages.
+    fn name(&self) -> &'static str;
+
+    /// Lists the panes that can be focused.
+    ///
+    /// Runs the backend's CLI, so this briefly blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the CLI fails or its output cannot be parsed.
+    fn list_panes(&self) -> Result<Vec<Pane>, String>;
+
+    /// Command that focuses `pane`, one of [`Self::list_panes`].
+    fn focus_pane_command(&self, pane: &Pane) -> Command;
+
+    /// Command that opens a new pane in `dir`, titled `title` where the
+    /// backend supports it, running `argv`.
+    fn spawn_command(&self, dir: Option<&Path>, title: Option<&str>, argv: &[String]) -> Command;
+
+    /// Multiplexer whose [`TerminalPane`] IDs are this backend's pane IDs, if
+    /// any.
+    fn multiplexer(&self) -> Option<Multiplexer> {
+        None
+    }
+
+    /// Command that focuses the pane running `session`: the pane its hooks
+    /// reported, if it still exists, else one found by [`find_session_pane`].
+    ///
+    /// Returns `None` if no pane matches or the panes cannot be listed.
+    fn focus_command(&self, session: &Session) -> Option<Command> {
+        let panes = self
+            .list_panes()
+            .map_err(|e| tracing::warn!("{}: failed to list panes: {}", self.name(), e))
+            .ok()?;
+        let reported = session
+            .terminal_pane
+            .as_ref()
+            .filter(|reported| Some(reported.multiplexer) == self.multiplexer())
+            .and_then(|reported| panes.iter().find(|pane| pane.id == reported.id));
+        reported
+            .or_else(|| find_session_pane(&panes, session))
+            .map(|pane| self.focus_pane_command(pane))
+    }
+
+    /// Command that brings the terminal app to the front after a pane was
+    /// focused (`integrations.raise_window`). Defaults to
+    /// [`raise_app_command`].
+    fn raise_command(&self) -> Option<Command> {
+        raise_app_command(std::env::consts::OS, |name| std::env::var(name).ok())
+    }
+
+    /// Command that opens a new pane in the working directory of the closed
+    /// `session`, named after it, and runs `resume` (the agent's resume
+    /// command) in it.
+    fn resurrect_command(&self, session: &Session, resume: &[String]) -> Command {
+        self.spawn_command(
+            session.working_dir.as_deref(),
+            pane_name(session).as_deref(),
+            resume,
+        )
+    }
+}
+
+/// On macOS, `open -b <bundle ID>` for the app the caller runs in, from the
+/// `__CFBundleIdentifier` macOS sets for apps and their child processes. None
+/// elsewhere, or when the variable is not set.
+pub fn raise_app_command(os: &str, env: impl Fn(&str) -> Option<String>) -> Option<Command> {
+    if os != "macos" {
+        return None;
+    }
+    let bundle_id = env("__CFBundleIdentifier").filter(|id| !id.is_empty())?;
+    let mut command = Command::new("open");
+    command.arg("-b").arg(bundle_id);
+    Some(command)
+}
+
+/// Quotes `arg` for `sh`.
+pub fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// The pane running `session`: the first in its working directory, else the
+/// first titled with its [`pane_name`].
+pub fn find_session_pane<'a>(panes: &'a [Pane], session: &Session) -> Option<&'a Pane> {
+    let in_dir = session.working_dir.as_deref().and_then(|dir| {
+        panes
+            .iter()
+            .find(|pane| pane.working_dir.as_deref() == Some(dir))
+    });
+    in_dir.or_else(|| {
+        let name = pane_name(session)?;
+        panes.iter().find(|pane| pane.title == name)
+    })
+}
+
+/// The terminal backend to use: the one `integrations.terminal` names, or
+/// with `auto`, the one the TUI is running under.
+///
+/// Returns `None` when no supported backend is found or its integration is
+/// disabled in `[integrations]`, or when `integrations.terminal = "none"`.
+pub fn detect(config: &IntegrationsConfig) -> Option<Box<dyn TerminalBackend>> {
+    detect_with(config, |name| std::env::var(name).ok())
+}
+
+/// Detection with an injectable environment lookup (for tests).
+pub(crate) fn detect_with(
+    config: &IntegrationsConfig,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<Box<dyn TerminalBackend>> {
+    match config.terminal {
+        TerminalChoice::Auto => {}
+        TerminalChoice::None => return None,
+        TerminalChoice::Zellij => return Some(Box::new(Zellij::with_env(env))),
+        TerminalChoice::Tmux => {
+            let tmux = Tmux {
+                all_sessions: config.raise_window,
+                ..Tmux::with_env(env)
+            };
+            return Some(Box::new(tmux));
+        }
+        TerminalChoice::Wezterm => return Some(Box::new(WezTerm)),
+        TerminalChoice::Kitty => {
+            let kitty = Kitty::with_env(env);
+            if kitty.is_none() {
+                tracing::warn!(
+                    "integrations.terminal is \"kitty\" but KITTY_LISTEN_ON is not set; \
+                     enable listen_on in kitty.conf"
+                );
+            }
+            return kitty.map(|kitty| Box::new(kitty) as Box<dyn TerminalBackend>);
+        }
+    }
+    // A multiplexer runs inside the terminal emulator, so it is checked first
+    if config.zellij.enabled {
+        if let Some(zellij) = Zellij::from_env(&env) {
+            return Some(Box::new(zellij));
+        }
+    }
+    if config.tmux.enabled {
+        if let Some(tmux) = Tmux::from_env(&env) {
+            return Some(Box::new(Tmux {
+                all_sessions: config.raise_window,
+                ..tmux
+            }));
+        }
+    }
+    if config.kitty.enabled {
+        if let Some(kitty) = Kitty::from_env(&env) {
+            return Some(Box::new(kitty));
+        }
+    }
+    if config.wezterm.enabled {
+        if let Some(wezterm) = WezTerm::from_env(&env) {
+            return Some(Box::new(wezterm));
+        }
+    }
+    None
+}
+
+/// The fields of `snapshot` terminal backends need to build commands.
+pub fn session_from_snapshot(snapshot: &SessionSnapshot) -> Session {
+    let mut session = Session::new(
+        snapshot.session_id.clone(),
+        snapshot.agent_type.parse().unwrap_or_default(),
+        snapshot.working_dir.as_ref().map(PathBuf::from),
+    );
+    session.display_name = snapshot.display_name.clone();
+    session.terminal_pane = snapshot.terminal_pane.clone();
+    session
+}
+
+/// Name for the tab or pane of `session`: its display name, else the last
+/// component of its working directory.
+pub fn pane_name(session: &Session) -> Option<String> {
+    session.display_name.clone().or_else(|| {
+        session
+            .working_dir
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+    })
+}
+
+/// What entering `status` means, for notification text ("needs attention").
+pub fn status_phrase(status: Status) -> &'static str {
+    match status {
+        Status::Attention => "needs attention",
+        Status::Question => "has a question",
+        Status::Working => "is working",
+        Status::Closed => "was closed",
+    }
+}
+
+/// How long `session` spent in the status it just left (or, without a
+/// recorded transition, how long it has been in its current one).
+pub fn previous_status_duration(session: &Session) -> Duration {
+    match session.history.last() {
+        Some(transition) if transition.to == session.status => transition.duration,
+        _ => session.since.elapsed(),
+    }
+}
+
+/// Value of a notification template placeholder for `session`, or None if
+/// `name` is not a placeholder.
+///
+/// Placeholders: `name`, `session_id`, `status`, `event`, `working_dir`,
+/// `elapsed`.
+pub fn session_placeholder(session: &Session, name: &str) -> Option<String> {
+    let value = match name {
+        "name" => pane_name(session).unwrap_or_else(|| session.session_id.clone()),
+        "session_id" => session.session_id.clone(),
+        "status" => session.status.to_string(),
+        "event" => status_phrase(session.status).to_string(),
+        "working_dir" => session
+            .working_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default(),
+        "elapsed" => {
+            let secs = previous_status_duration(session).as_secs();
+            humantime::format_duration(Duration::from_secs(secs)).to_string()
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Usage alert text: the 5-hour utilization and, if known, its reset time.
+pub fn usage_alert_text(usage: &claude_usage::UsageData) -> String {
+    let mut text = format!(
+        "Claude usage at {:.0}% of the 5-hour limit",
+        usage.five_hour.utilization.floor()
+    );
+    if let Some(resets_at) = usage.five_hour.resets_at {
+        text.push_str(&format!(
+            " (resets {})",
+            resets_at.with_timezone(&chrono::Local).format("%H:%M")
+        ));
+    }
+    text
+}
+
+/// Timeout for one webhook request.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HTTP client for webhook notifiers, or `None` if TLS setup fails.
+pub fn webhook_client() -> Option<reqwest::Client> {
+    match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+        Ok(client) => Some(client),
+        Err(e) => {
+            tracing::warn!("failed to create webhook HTTP client: {}", e);
+            None
+        }
+    }
+}
+
+/// POSTs `payload` as JSON to `url`.
+///
+/// # Errors
+///
+/// Returns the request error, or an error for a non-2xx response.
+pub async fn post_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &serde_json::Value,
+) -> Result<(), reqwest::Error> {
+    send_json(client.post(url), payload).await
+}
+
+/// Sends `request` with `payload` as its JSON body.
+async fn send_json(
+    request: reqwest::RequestBuilder,
+    payload: &serde_json::Value,
+) -> Result<(), reqwest::Error> {
+    request
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_string())
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Most stderr lines kept in failure messages.
+const STDERR_TAIL_LINES: usize = 3;
+/// Most characters kept in failure messages.
+const STDERR_TAIL_CHARS: usize = 200;
+
+/// Runs `command` detached from the TUI, logging failures.
+///
+/// Stdout is discarded; the exit status is reaped on a background thread.
+pub fn spawn(command: Command, label: &str) -> std::io::Result<()> {
+    spawn_reporting(command, label, |_| {})
+}
+
+/// As [`spawn`], and calls `report` with a [`failure_message`] if the
+/// command fails.
+///
+/// The full stderr of a failed command is logged.
+pub fn spawn_reporting(
+    mut command: Command,
+    label: &str,
+    report: impl FnOnce(String) + Send + 'static,
+) -> std::io::Result<()> {
+    tracing::debug!("{} integration: {:?}", label, command);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Read stderr on its own thread: a detached grandchild (a browser opened
+    // by `open`) may hold it open long after the command exits
+    let stderr = child.stderr.take().map(|mut stderr| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = std::io::Read::read_to_end(&mut stderr, &mut buf);
+            buf
+        })
+    });
+    let label = label.to_string();
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            let stderr = stderr
+                .and_then(|reader| reader.join().ok())
+                .unwrap_or_default();
+            let stderr = String::from_utf8_lossy(&stderr);
+            tracing::warn!(
+                "{} integration exited with {}: {}",
+                label,
+                status,
+                stderr.trim()
+            );
+            report(failure_message(status, &stderr));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!("{} integration wait error: {}", label, e);
+            report(format!("wait failed: {}", e));
+        }
+    });
+    Ok(())
+}
+
+/// Runs `command` to completion, for callers that report the outcome.
+///
+/// # Errors
+///
+/// Returns the spawn error, or a [`failure_message`] if the command fails.
+pub fn run(command: Command, label: &str) -> Result<(), String> {
+    output(command, label).map(drop)
+}
+
+/// Runs `command` to completion and returns its stdout.
+///
+/// The full output of a failed command is logged.
+///
+/// # Errors
+///
+/// As [`run`].
+pub fn output(mut command: Command, label: &str) -> Result<String, String> {
+    tracing::debug!("{} integration: {:?}", label, command);
+    let output = command
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run {:?}: {}", command.get_program(), e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if output.status.success() {
+        return Ok(stdout);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    tracing::warn!(
+        "{} integration {:?} exited with {}\nstdout: {}\nstderr: {}",
+        label,
+        command.get_program(),
+        output.status,
+        stdout.trim(),
+        stderr.trim()
+    );
+    Err(failure_message(output.status, &stderr))
+}
+
+/// One-line summary of a failed command for the footer or a CLI error: the
+/// last lines of its `stderr`, joined with ` | ` and cut to their last 200
+/// characters, else its exit status.
+pub fn failure_message(status: std::process::ExitStatus, stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.is_empty() {
+        return format!("exited with {}", status);
+    }
+    let tail = lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join(" | ");
+    let count = tail.chars().count();
+    if count <= STDERR_TAIL_CHARS {
+        return tail;
+    }
+    let cut: String = tail.chars().skip(count - STDERR_TAIL_CHARS + 1).collect();
+    format!("…{}", cut)
+}
+
+/// Program and arguments of `command`, for assertions.
+#[cfg(test)]
+pub(crate) fn command_line(command: &Command) -> Vec<String> {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    fn session(dir: Option<&str>) -> Session {
+        Session::new(
+            "abc-123".to_string(),
+            AgentType::ClaudeCode,
+            dir.map(PathBuf::from),
+        )
+    }
+
+    #[test]
+    fn test_detect_requires_zellij_env_and_enabled_config() {
+        let mut config = IntegrationsConfig::default();
+        let inside = |name: &str| (name == "ZELLIJ").then(|| "0".to_string());
+        assert_eq!(
+            detect_with(&config, inside).map(|b| b.name()),
+            Some("Zellij")
+        );
+        assert!(detect_with(&config, |_| None).is_none());
+
+        config.zellij.enabled = false;
+        assert!(detect_with(&config, inside).is_none());
+    }
+
+    #[test]
+    fn test_detect_prefers_zellij_over_kitty() {
+        let mut config = IntegrationsConfig::default();
+        let env = |name: &str| match name {
+            "ZELLIJ" => Some("0".to_string()),
+            "TERM" => Some("xterm-kitty".to_string()),
+            "KITTY_LISTEN_ON" => Some("unix:/tmp/kitty".to_string()),
+            _ => None,
+        };
+        assert_eq!(detect_with(&config, env).map(|b| b.name()), Some("Zellij"));
+
+        config.zellij.enabled = false;
+        assert_eq!(detect_with(&config, env).map(|b| b.name()), Some("kitty"));
+
+        config.kitty.enabled = false;
+        assert!(detect_with(&config, env).is_none());
+    }
+
+    #[test]
+    fn test_detect_tmux_before_emulators() {
+        let mut config = IntegrationsConfig::default();
+        let env = |name: &str| match name {
+            "TMUX" => Some("/tmp/tmux-1000/default,42,0".to_string()),
+            "WEZTERM_PANE" => Some("0".to_string()),
+            _ => None,
+        };
+        assert_eq!(detect_with(&config, env).map(|b| b.name()), Some("tmux"));
+
+        config.tmux.enabled = false;
+        assert_eq!(detect_with(&config, env).map(|b| b.name()), Some("WezTerm"));
+    }
+
+    #[test]
+    fn test_detect_wezterm() {
+        let mut config = IntegrationsConfig::default();
+        let env = |name: &str| (name == "WEZTERM_PANE").then(|| "0".to_string());
+        assert_eq!(detect_with(&config, env).map(|b| b.name()), Some("WezTerm"));
+
+        config.wezterm.enabled = false;
+        assert!(detect_with(&config, env).is_none());
+    }
+
+    #[test]
+    fn test_detect_term_program() {
+        let config = IntegrationsConfig::default();
+        let env = |program: &'static str| {
+            move |name: &str| (name == "TERM_PROGRAM").then(|| program.to_string())
+        };
+        assert_eq!(
+            detect_with(&config, env("tmux")).map(|b| b.name()),
+            Some("tmux")
+        );
+        assert_eq!(
+            detect_with(&config, env("WezTerm")).map(|b| b.name()),
+            Some("WezTerm")
+        );
+        assert!(detect_with(&config, env("Apple_Terminal")).is_none());
+    }
+
+    #[test]
+    fn test_detect_honors_terminal_override() {
+        let mut config = IntegrationsConfig::default();
+        let env = |name: &str| (name == "ZELLIJ").then(|| "0".to_string());
+
+        config.terminal = TerminalChoice::Tmux;
+        assert_eq!(detect_with(&config, env).map(|b| b.name()), Some("tmux"));
+        config.terminal = TerminalChoice::Wezterm;
+        assert_eq!(
+            detect_with(&config, |_| None).map(|b| b.name()),
+            Some("WezTerm")
+        );
+        config.terminal = TerminalChoice::None;
+        assert!(detect_with(&config, env).is_none());
+
+        // kitty cannot be reached without its socket
+        config.terminal = TerminalChoice::Kitty;
+        assert!(detect_with(&config, env).is_none());
+        let socket = |name: &str| (name == "KITTY_LISTEN_ON").then(|| "unix:/tmp/k".to_string());
+        assert_eq!(
+            detect_with(&config, socket).map(|b| b.name()),
+            Some("kitty")
+        );
+    }
+
+    #[test]
+    fn test_run_reports_stderr() {
+        assert_eq!(run(Command::new("true"), "Test"), Ok(()));
+        let mut failing = Command::new("sh");
+        failing.args(["-c", "echo 'no server running' >&2; exit 1"]);
+        assert_eq!(run(failing, "Test"), Err("no server running".to_string()));
+        let error = run(Command::new("/nonexistent/acd-test"), "Test").unwrap_err();
+        assert!(error.starts_with("failed to run"), "{}", error);
+    }
+
+    #[test]
+    fn test_failure_message_keeps_stderr_tail() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let status = std::process::ExitStatus::from_raw(2 << 8);
+        assert_eq!(failure_message(status, " \n"), "exited with exit status: 2");
+        assert_eq!(
+            failure_message(status, "one\ntwo\n\nthree\nfour\n"),
+            "two | three | four"
+        );
+        let long = failure_message(status, &"x".repeat(500));
+        assert_eq!(long.chars().count(), STDERR_TAIL_CHARS);
+        assert!(long.starts_with('…'));
+    }
+
+    #[test]
+    fn test_spawn_reporting_reports_failure() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut failing = Command::new("sh");
+        failing.args(["-c", "echo 'pane not found' >&2; exit 1"]);
+        spawn_reporting(failing, "Test", move |message| {
+            let _ = tx.send(message);
+        })
+        .expect("spawn");
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)),
+            Ok("pane not found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_session_pane_prefers_working_dir() {
+        let pane = |id: &str, title: &str, dir: Option<&str>| Pane {
+            id: id.to_string(),
+            title: title.to_string(),
+            working_dir: dir.map(PathBuf::from),
+        };
+        let panes = vec![
+            pane("1", "api", Some("/home/u/old-api")),
+            pane("2", "shell", Some("/home/u/api")),
+        ];
+        let s = session(Some("/home/u/api"));
+        assert_eq!(
+            find_session_pane(&panes, &s).map(|p| p.id.as_str()),
+            Some("2")
+        );
+
+        let titled = vec![pane("1", "api", None)];
+        assert_eq!(
+            find_session_pane(&titled, &s).map(|p| p.id.as_str()),
+            Some("1")
+        );
+        assert!(find_session_pane(&titled, &session(None)).is_none());
+    }
+
+    /// tmux-like backend with two panes whose focus command echoes the ID.
+    #[derive(Debug)]
+    struct TwoPanes;
+
+    impl TerminalBackend for TwoPanes {
+        fn name(&self) -> &'static str {
+            "Two"
+        }
+
+        fn multiplexer(&self) -> Option<Multiplexer> {
+            Some(Multiplexer::Tmux)
+        }
+
+        fn list_panes(&self) -> Result<Vec<Pane>, String> {
+            Ok(["%1", "%2"]
+                .into_iter()
+                .map(|id| Pane {
+                    id: id.to_string(),
+                    title: String::new(),
+                    working_dir: Some(PathBuf::from("/home/u/api")),
+                })
+                .collect())
+        }
+
+        fn focus_pane_command(&self, pane: &Pane) -> Command {
+            let mut command = Command::new("focus");
+            command.arg(&pane.id);
+            command
+        }
+
+        fn spawn_command(&self, _: Option<&Path>, _: Option<&str>, _: &[String]) -> Command {
+            Command::new("spawn")
+        }
+    }
+
+    #[test]
+    fn test_focus_command_prefers_reported_pane() {
+        let focused = |pane: Option<(Multiplexer, &str)>| {
+            let mut s = session(Some("/home/u/api"));
+            s.terminal_pane = pane.map(|(multiplexer, id)| crate::TerminalPane {
+                multiplexer,
+                id: id.to_string(),
+            });
+            command_line(&TwoPanes.focus_command(&s).expect("pane"))[1].clone()
+        };
+        assert_eq!(focused(Some((Multiplexer::Tmux, "%2"))), "%2");
+        // Stale IDs and other multiplexers' IDs fall back to the working dir
+        assert_eq!(focused(Some((Multiplexer::Tmux, "%9"))), "%1");
+        assert_eq!(focused(Some((Multiplexer::Zellij, "%2"))), "%1");
+        assert_eq!(focused(None), "%1");
+    }
+
+    #[test]
+    fn test_raise_app_command_uses_bundle_id_on_macos() {
+        let env = |name: &str| {
+            (name == "__CFBundleIdentifier").then(|| "com.github.wez.wezterm".to_string())
+        };
+        assert_eq!(
+            raise_app_command("macos", env).map(|c| command_line(&c)),
+            Some(vec![
+                "open".to_string(),
+                "-b".to_string(),
+                "com.github.wez.wezterm".to_string()
+            ])
+        );
+        assert!(raise_app_command("linux", env).is_none());
+        assert!(raise_app_command("macos", |_| None).is_none());
+    }
+
+    #[test]
+    fn test_pane_name_prefers_display_name() {
+        let mut s = session(Some("/home/u/projects/api"));
+        assert_eq!(pane_name(&s).as_deref(), Some("api"));
+        s.display_name = Some("backend".to_string());
+        assert_eq!(pane_name(&s).as_deref(), Some("backend"));
+        assert_eq!(pane_name(&session(None)), None);
+    }
+
+    #[test]
+    fn test_session_placeholders() {
+        let mut s = session(Some("/home/u/api"));
+        s.set_status(Status::Question);
+        assert_eq!(session_placeholder(&s, "name").as_deref(), Some("api"));
+        assert_eq!(
+            session_placeholder(&s, "status").as_deref(),
+            Some("question")
+        );
+        assert_eq!(
+            session_placeholder(&s, "event").as_deref(),
+            Some("has a question")
+        );
+        assert_eq!(
+            session_placeholder(&s, "working_dir").as_deref(),
+            Some("/home/u/api")
+        );
+        assert_eq!(session_placeholder(&s, "elapsed").as_deref(), Some("0s"));
+        assert_eq!(session_placeholder(&s, "bogus"), None);
+    }
+}
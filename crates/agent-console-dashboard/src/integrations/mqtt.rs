@@ -0,0 +1,209 @@
+//! MQTT publishing of session statuses and usage.
+//!
+//! A session's status is published as plain text (`attention`) to the topic
+//! rendered from `notifications.mqtt.status_topic`, and usage data as JSON to
+//! `usage_topic`. Messages are queued without waiting on the broker; the
+//! [`EventLoop`] returned with the publisher sends them and reconnects after
+//! connection errors, and must be driven with [`drive`].
+
+use std::time::Duration;
+
+use claude_usage::UsageData;
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, QoS};
+use tracing::{debug, info, warn};
+
+use crate::config::schema::MqttConfig;
+use crate::tui::footer::render_placeholders;
+use crate::Session;
+
+/// Messages queued while the broker is unreachable before new ones are
+/// dropped.
+const QUEUE_CAPACITY: usize = 64;
+
+/// Delay before reconnecting after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Publishes messages to one MQTT broker.
+#[derive(Debug, Clone)]
+pub struct MqttPublisher {
+    client: AsyncClient,
+    qos: QoS,
+    config: MqttConfig,
+}
+
+impl MqttPublisher {
+    /// Creates a publisher and the event loop that connects it, or `None` if
+    /// no broker is configured. Nothing is sent until the event loop is
+    /// driven.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an invalid broker URL or QoS.
+    pub fn from_config(config: &MqttConfig) -> Result<Option<(Self, EventLoop)>, String> {
+        let broker = config.broker.trim();
+        if broker.is_empty() {
+            return Ok(None);
+        }
+        let qos = match config.qos {
+            0 => QoS::AtMostOnce,
+            1 => QoS::AtLeastOnce,
+            2 => QoS::ExactlyOnce,
+            other => return Err(format!("invalid qos {} (expected 0, 1 or 2)", other)),
+        };
+        // `parse_url` requires a client ID; the configured one is set directly
+        // so it needs no URL encoding
+        let separator = if broker.contains('?') { '&' } else { '?' };
+        let mut options = MqttOptions::parse_url(format!("{}{}client_id=acd", broker, separator))
+            .map_err(|e| format!("invalid broker URL: {}", e))?;
+        options.set_client_id(config.client_id.clone());
+        if !config.username.is_empty() {
+            options.set_credentials(config.username.clone(), config.password.clone());
+        }
+        let (client, eventloop) = AsyncClient::new(options, QUEUE_CAPACITY);
+        Ok(Some((
+            Self {
+                client,
+                qos,
+                config: config.clone(),
+            },
+            eventloop,
+        )))
+    }
+
+    /// Topic `session`'s status is published to.
+    pub fn status_topic(&self, session: &Session) -> String {
+        render_placeholders(&self.config.status_topic, |name| {
+            super::session_placeholder(session, name)
+        })
+    }
+
+    /// Queues `session`'s current status.
+    pub fn publish_status(&self, session: &Session) {
+        let topic = self.status_topic(session);
+        self.publish(topic, session.status.to_string().into_bytes());
+    }
+
+    /// Queues `usage` as JSON, unless usage publishing is off.
+    pub fn publish_usage(&self, usage: &UsageData) {
+        if !self.publishes_usage() {
+            return;
+        }
+        match serde_json::to_vec(usage) {
+            Ok(payload) => self.publish(self.config.usage_topic.clone(), payload),
+            Err(e) => warn!(error = %e, "failed to serialize usage for MQTT"),
+        }
+    }
+
+    /// Returns true if usage updates are published.
+    pub fn publishes_usage(&self) -> bool {
+        !self.config.usage_topic.is_empty()
+    }
+
+    fn publish(&self, topic: String, payload: Vec<u8>) {
+        if let Err(e) = self
+            .client
+            .try_publish(&topic, self.qos, self.config.retain, payload)
+        {
+            debug!(topic, error = %e, "dropped MQTT message");
+        }
+    }
+}
+
+/// Polls `eventloop` forever, sending queued messages and reconnecting
+/// after errors. Connection losses are logged once until the next
+/// successful connection.
+pub async fn drive(mut eventloop: EventLoop) {
+    let mut failing = false;
+    loop {
+        match eventloop.poll().await {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                info!("connected to MQTT broker");
+                failing = false;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                if failing {
+                    debug!(error = %e, "MQTT broker still unreachable");
+                } else {
+                    warn!(error = %e, "MQTT connection failed, retrying");
+                    failing = true;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AgentType, Status};
+    use std::path::PathBuf;
+
+    fn config(broker: &str) -> MqttConfig {
+        MqttConfig {
+            broker: broker.to_string(),
+            ..MqttConfig::default()
+        }
+    }
+
+    fn publisher(config: &MqttConfig) -> MqttPublisher {
+        MqttPublisher::from_config(config)
+            .expect("valid config")
+            .expect("publisher")
+            .0
+    }
+
+    #[test]
+    fn test_disabled_without_broker() {
+        assert!(MqttPublisher::from_config(&MqttConfig::default())
+            .expect("valid config")
+            .is_none());
+    }
+
+    #[test]
+    fn test_rejects_invalid_config() {
+        assert!(MqttPublisher::from_config(&config("broker.local")).is_err());
+        assert!(MqttPublisher::from_config(&config("http://broker.local")).is_err());
+        let mut config = config("mqtt://broker.local");
+        config.qos = 3;
+        assert!(MqttPublisher::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_status_topic_renders_template() {
+        let session = Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        );
+        let default = publisher(&config("mqtt://broker.local:1883"));
+        assert_eq!(default.status_topic(&session), "acd/sessions/abc/status");
+
+        let custom = publisher(&MqttConfig {
+            status_topic: "home/agents/{name}/{unknown}".to_string(),
+            ..config("mqtts://broker.local")
+        });
+        assert_eq!(custom.status_topic(&session), "home/agents/api/{unknown}");
+    }
+
+    #[test]
+    fn test_publishes_usage_unless_topic_empty() {
+        assert!(publisher(&config("mqtt://127.0.0.1:1")).publishes_usage());
+        let no_usage = publisher(&MqttConfig {
+            usage_topic: String::new(),
+            ..config("mqtt://127.0.0.1:1")
+        });
+        assert!(!no_usage.publishes_usage());
+    }
+
+    #[test]
+    fn test_publish_does_not_wait_for_broker() {
+        let mut session = Session::new("abc".to_string(), AgentType::ClaudeCode, None);
+        session.status = Status::Attention;
+        let publisher = publisher(&config("mqtt://127.0.0.1:1"));
+        for _ in 0..QUEUE_CAPACITY * 2 {
+            publisher.publish_status(&session);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! ntfy push notifications.
+//!
+//! Messages are published as JSON to the root of `notifications.ntfy.server`,
+//! with the topic in the body, so phones subscribed to the topic get a push
+//! notification. The text comes from `notifications.ntfy.template`, the
+//! priority from `priorities`, and an emoji tag from the event. Usage alerts
+//! have a fixed format.
+
+use claude_usage::UsageData;
+use serde_json::{json, Value};
+
+use crate::config::schema::NtfyConfig;
+use crate::tui::footer::render_placeholders;
+use crate::{Session, Status};
+
+/// ntfy priority for events without a configured one.
+const DEFAULT_PRIORITY: u8 = 3;
+
+/// Title of every notification.
+const TITLE: &str = "Agent Console Dashboard";
+
+/// Emoji tag (an ntfy shortcode) shown in front of the title.
+fn status_tag(status: Status) -> &'static str {
+    match status {
+        Status::Attention => "warning",
+        Status::Question => "question",
+        Status::Working => "hourglass_flowing_sand",
+        Status::Closed => "checkered_flag",
+    }
+}
+
+/// Publishes messages to one ntfy topic.
+#[derive(Debug, Clone)]
+pub struct NtfyNotifier {
+    client: reqwest::Client,
+    config: NtfyConfig,
+}
+
+impl NtfyNotifier {
+    /// Creates a notifier, or `None` if no topic is configured.
+    pub fn from_config(config: &NtfyConfig) -> Option<Self> {
+        if config.topic.trim().is_empty() {
+            return None;
+        }
+        Some(Self {
+            client: super::webhook_client()?,
+            config: config.clone(),
+        })
+    }
+
+    /// Priority of `event` (a status name or `usage`), clamped to 1-5.
+    fn priority(&self, event: &str) -> u8 {
+        self.config
+            .priorities
+            .get(event)
+            .copied()
+            .unwrap_or(DEFAULT_PRIORITY)
+            .clamp(1, 5)
+    }
+
+    /// Message announcing that `session` entered its current status.
+    pub fn session_payload(&self, session: &Session) -> Value {
+        let message = render_placeholders(&self.config.template, |name| {
+            super::session_placeholder(session, name)
+        });
+        self.payload(
+            message,
+            self.priority(&session.status.to_string()),
+            status_tag(session.status),
+        )
+    }
+
+    /// Message announcing that 5-hour usage reached `usage_threshold`.
+    pub fn usage_payload(&self, usage: &UsageData) -> Value {
+        self.payload(
+            super::usage_alert_text(usage),
+            self.priority("usage"),
+            "chart_with_upwards_trend",
+        )
+    }
+
+    fn payload(&self, message: String, priority: u8, tag: &str) -> Value {
+        json!({
+            "topic": self.config.topic,
+            "title": TITLE,
+            "message": message,
+            "priority": priority,
+            "tags": [tag],
+        })
+    }
+
+    /// Publishes `payload`, with the access token if one is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns the request error, or an error for a non-2xx response.
+    pub async fn post(&self, payload: &Value) -> Result<(), reqwest::Error> {
+        let mut request = self.client.post(&self.config.server);
+        if !self.config.token.is_empty() {
+            request = request.bearer_auth(&self.config.token);
+        }
+        super::send_json(request, payload).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use claude_usage::UsagePeriod;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn notifier(config: NtfyConfig) -> NtfyNotifier {
+        NtfyNotifier::from_config(&NtfyConfig {
+            topic: "agents".to_string(),
+            ..config
+        })
+        .expect("notifier")
+    }
+
+    fn session(status: Status) -> Session {
+        let mut session = Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        );
+        session.status = status;
+        session
+    }
+
+    #[test]
+    fn test_disabled_without_topic() {
+        assert!(NtfyNotifier::from_config(&NtfyConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_session_payload() {
+        let ntfy = notifier(NtfyConfig::default());
+        assert_eq!(
+            ntfy.session_payload(&session(Status::Attention)),
+            json!({
+                "topic": "agents",
+                "title": "Agent Console Dashboard",
+                "message": "api needs attention",
+                "priority": 4,
+                "tags": ["warning"],
+            })
+        );
+        // Unlisted statuses use the default priority
+        assert_eq!(
+            ntfy.session_payload(&session(Status::Working))["priority"],
+            3
+        );
+    }
+
+    #[test]
+    fn test_priorities_are_clamped() {
+        let ntfy = notifier(NtfyConfig {
+            priorities: BTreeMap::from([("attention".to_string(), 9), ("question".to_string(), 0)]),
+            ..NtfyConfig::default()
+        });
+        assert_eq!(ntfy.priority("attention"), 5);
+        assert_eq!(ntfy.priority("question"), 1);
+    }
+
+    #[test]
+    fn test_usage_payload() {
+        let usage = UsageData {
+            five_hour: UsagePeriod {
+                utilization: 92.4,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 10.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            extra_usage: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+        };
+        let payload = notifier(NtfyConfig::default()).usage_payload(&usage);
+        assert_eq!(
+            payload["message"],
+            "Claude usage at 92% of the 5-hour limit"
+        );
+        assert_eq!(payload["priority"], 4);
+        assert_eq!(payload["tags"], json!(["chart_with_upwards_trend"]));
+    }
+}
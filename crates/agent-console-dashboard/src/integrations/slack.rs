@@ -0,0 +1,127 @@
+//! Slack notifications through an incoming webhook.
+//!
+//! Messages are plain `text` payloads rendered from
+//! `notifications.slack.template`, optionally redirected with `channel`
+//! (honoured by legacy webhooks; app webhooks always post to their own
+//! channel). Usage alerts have a fixed format.
+
+use claude_usage::UsageData;
+use serde_json::{json, Value};
+
+use crate::config::schema::SlackConfig;
+use crate::tui::footer::render_placeholders;
+use crate::Session;
+
+/// Posts messages to one Slack webhook.
+#[derive(Debug, Clone)]
+pub struct SlackNotifier {
+    client: reqwest::Client,
+    config: SlackConfig,
+}
+
+impl SlackNotifier {
+    /// Creates a notifier, or `None` if no webhook URL is configured.
+    pub fn from_config(config: &SlackConfig) -> Option<Self> {
+        if config.webhook_url.trim().is_empty() {
+            return None;
+        }
+        Some(Self {
+            client: super::webhook_client()?,
+            config: config.clone(),
+        })
+    }
+
+    /// Payload announcing that `session` entered its current status.
+    pub fn session_payload(&self, session: &Session) -> Value {
+        let text = render_placeholders(&self.config.template, |name| {
+            super::session_placeholder(session, name)
+        });
+        self.payload(text)
+    }
+
+    /// Payload announcing that 5-hour usage reached `usage_threshold`.
+    pub fn usage_payload(&self, usage: &UsageData) -> Value {
+        self.payload(format!(":warning: {}", super::usage_alert_text(usage)))
+    }
+
+    fn payload(&self, text: String) -> Value {
+        let mut payload = json!({ "text": text });
+        if !self.config.channel.is_empty() {
+            payload["channel"] = json!(self.config.channel);
+        }
+        payload
+    }
+
+    /// POSTs `payload` to the webhook.
+    ///
+    /// # Errors
+    ///
+    /// Returns the request error, or an error for a non-2xx response.
+    pub async fn post(&self, payload: &Value) -> Result<(), reqwest::Error> {
+        super::post_webhook(&self.client, &self.config.webhook_url, payload).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AgentType, Status};
+    use claude_usage::UsagePeriod;
+    use std::path::PathBuf;
+
+    fn notifier(channel: &str) -> SlackNotifier {
+        SlackNotifier::from_config(&SlackConfig {
+            webhook_url: "https://hooks.slack.invalid/services/T/B/X".to_string(),
+            channel: channel.to_string(),
+            ..SlackConfig::default()
+        })
+        .expect("notifier")
+    }
+
+    #[test]
+    fn test_disabled_without_webhook_url() {
+        assert!(SlackNotifier::from_config(&SlackConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_session_payload_renders_template() {
+        let mut session = Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        );
+        session.status = Status::Attention;
+        assert_eq!(
+            notifier("").session_payload(&session),
+            json!({ "text": "*api* needs attention (`/home/u/api`)" })
+        );
+        assert_eq!(
+            notifier("#agents").session_payload(&session)["channel"],
+            "#agents"
+        );
+    }
+
+    #[test]
+    fn test_usage_payload() {
+        let usage = UsageData {
+            five_hour: UsagePeriod {
+                utilization: 81.7,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 10.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            extra_usage: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+        };
+        assert_eq!(
+            notifier("").usage_payload(&usage),
+            json!({ "text": ":warning: Claude usage at 81% of the 5-hour limit" })
+        );
+    }
+}
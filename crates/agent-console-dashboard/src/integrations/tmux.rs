@@ -0,0 +1,241 @@
+//! tmux backend driven by the `tmux` command.
+//!
+//! Panes are listed across the windows of the current tmux session
+//! (`tmux list-panes -s`), or of every session with
+//! `integrations.raise_window`, with their window name and working directory.
+//! Spawning opens a new window, named after the session, so it can be matched
+//! by name later. Sessions whose hooks reported a `TMUX_PANE` are focused by
+//! that pane ID.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{Pane, TerminalBackend};
+use crate::Multiplexer;
+
+/// The tmux server the caller runs in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tmux {
+    /// Server socket from `TMUX`, passed as `-S` so commands reach the
+    /// caller's server. None uses the default server.
+    pub socket: Option<String>,
+    /// List panes of every tmux session, and switch the client to the
+    /// focused pane's session (`integrations.raise_window`). Otherwise only
+    /// the current session's panes are listed.
+    pub all_sessions: bool,
+}
+
+impl Tmux {
+    /// Detects tmux from `TMUX` (`<socket>,<pid>,<session>`, set inside
+    /// every tmux pane) or `TERM_PROGRAM=tmux` (tmux 3.2+, kept by tools that
+    /// drop `TMUX`).
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if env("TMUX").is_none() && env("TERM_PROGRAM").as_deref() != Some("tmux") {
+            return None;
+        }
+        Some(Self::with_env(env))
+    }
+
+    /// The server in `TMUX`, or the default server outside tmux.
+    pub fn with_env(env: impl Fn(&str) -> Option<String>) -> Self {
+        let socket = env("TMUX")
+            .and_then(|value| value.split(',').next().map(str::to_string))
+            .filter(|socket| !socket.is_empty());
+        Self {
+            socket,
+            all_sessions: false,
+        }
+    }
+
+    /// `tmux [-S <socket>] <args...>`
+    fn command<I, S>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut command = Command::new("tmux");
+        if let Some(socket) = &self.socket {
+            command.arg("-S").arg(socket);
+        }
+        command.args(args);
+        command
+    }
+}
+
+impl TerminalBackend for Tmux {
+    fn name(&self) -> &'static str {
+        "tmux"
+    }
+
+    fn multiplexer(&self) -> Option<Multiplexer> {
+        Some(Multiplexer::Tmux)
+    }
+
+    fn list_panes(&self) -> Result<Vec<Pane>, String> {
+        let scope = if self.all_sessions { "-a" } else { "-s" };
+        let command = self.command([
+            "list-panes",
+            scope,
+            "-F",
+            "#{pane_id}\t#{window_name}\t#{pane_current_path}",
+        ]);
+        super::output(command, self.name()).map(|list| parse_panes(&list))
+    }
+
+    fn focus_pane_command(&self, pane: &Pane) -> Command {
+        // Select the pane's window, then the pane within it
+        let mut args = vec!["select-window", "-t", &pane.id, ";"];
+        args.extend(["select-pane", "-t", &pane.id]);
+        if self.all_sessions {
+            // The pane may be in another tmux session than the client's
+            args.splice(0..0, ["switch-client", "-t", &pane.id, ";"]);
+        }
+        self.command(args)
+    }
+
+    fn spawn_command(&self, dir: Option<&Path>, title: Option<&str>, argv: &[String]) -> Command {
+        let mut args = vec!["new-window".to_string()];
+        if let Some(dir) = dir {
+            args.push("-c".to_string());
+            args.push(dir.display().to_string());
+        }
+        if let Some(title) = title {
+            args.push("-n".to_string());
+            args.push(title.to_string());
+        }
+        args.extend(argv.iter().cloned());
+        self.command(args)
+    }
+}
+
+/// Parses `list-panes` lines of `<pane id>\t<window name>\t<cwd>`.
+fn parse_panes(list: &str) -> Vec<Pane> {
+    list.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let id = fields.next().filter(|id| !id.is_empty())?;
+            let title = fields.next().unwrap_or_default();
+            let dir = fields.next().unwrap_or_default();
+            Some(Pane {
+                id: id.to_string(),
+                title: title.to_string(),
+                working_dir: (!dir.is_empty()).then(|| PathBuf::from(dir)),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::command_line;
+    use crate::{AgentType, Session};
+
+    fn session() -> Session {
+        Session::new(
+            "abc-123".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        )
+    }
+
+    fn tmux() -> Tmux {
+        Tmux {
+            socket: Some("/tmp/tmux-1000/default".to_string()),
+            all_sessions: false,
+        }
+    }
+
+    #[test]
+    fn test_from_env_reads_socket() {
+        let env =
+            |name: &str| (name == "TMUX").then(|| "/tmp/tmux-1000/default,4242,0".to_string());
+        assert_eq!(Tmux::from_env(env), Some(tmux()));
+        assert_eq!(Tmux::from_env(|_| None), None);
+        let program = |name: &str| (name == "TERM_PROGRAM").then(|| "tmux".to_string());
+        assert_eq!(Tmux::from_env(program).and_then(|tmux| tmux.socket), None);
+    }
+
+    #[test]
+    fn test_parse_panes() {
+        let list = "%0\tapi\t/home/u/api\n%3\tmy shell\t\n\n";
+        assert_eq!(
+            parse_panes(list),
+            vec![
+                Pane {
+                    id: "%0".to_string(),
+                    title: "api".to_string(),
+                    working_dir: Some(PathBuf::from("/home/u/api")),
+                },
+                Pane {
+                    id: "%3".to_string(),
+                    title: "my shell".to_string(),
+                    working_dir: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_focus_pane_command_selects_window_and_pane() {
+        let pane = Pane {
+            id: "%3".to_string(),
+            title: "api".to_string(),
+            working_dir: None,
+        };
+        assert_eq!(
+            command_line(&tmux().focus_pane_command(&pane)),
+            vec![
+                "tmux",
+                "-S",
+                "/tmp/tmux-1000/default",
+                "select-window",
+                "-t",
+                "%3",
+                ";",
+                "select-pane",
+                "-t",
+                "%3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_focus_pane_command_switches_client_across_sessions() {
+        let pane = Pane {
+            id: "%3".to_string(),
+            title: "api".to_string(),
+            working_dir: None,
+        };
+        let tmux = Tmux {
+            socket: None,
+            all_sessions: true,
+        };
+        assert_eq!(
+            command_line(&tmux.focus_pane_command(&pane))[..5],
+            ["tmux", "switch-client", "-t", "%3", ";"]
+        );
+    }
+
+    #[test]
+    fn test_resurrect_command_opens_named_window_in_working_dir() {
+        let resume = vec!["claude".into(), "--resume".into(), "abc-123".into()];
+        let command = tmux().resurrect_command(&session(), &resume);
+        assert_eq!(
+            command_line(&command),
+            vec![
+                "tmux",
+                "-S",
+                "/tmp/tmux-1000/default",
+                "new-window",
+                "-c",
+                "/home/u/api",
+                "-n",
+                "api",
+                "claude",
+                "--resume",
+                "abc-123"
+            ]
+        );
+    }
+}
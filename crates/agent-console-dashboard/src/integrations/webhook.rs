@@ -0,0 +1,227 @@
+//! Generic JSON webhooks.
+//!
+//! Each `[[notifications.webhooks]]` entry is a [`WebhookEndpoint`] that
+//! receives a POST for the [`WebhookEvent`]s it subscribes to. Session events
+//! carry the same [`SessionSnapshot`] the IPC protocol uses:
+//!
+//! ```json
+//! { "event": "status_changed", "timestamp": "2026-01-01T12:00:00+00:00",
+//!   "session": { "session_id": "...", "status": "attention", ... } }
+//! ```
+//!
+//! Usage events carry `threshold` and the raw `usage` data instead of
+//! `session`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use claude_usage::UsageData;
+use serde_json::{json, Value};
+
+use crate::config::schema::WebhookConfig;
+use crate::{Session, SessionSnapshot};
+
+/// Something a webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    /// A new session registered with the daemon.
+    SessionCreated,
+    /// A session entered a new status.
+    StatusChanged,
+    /// 5-hour usage crossed the endpoint's `usage_threshold`.
+    UsageThreshold,
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SessionCreated => "session_created",
+            Self::StatusChanged => "status_changed",
+            Self::UsageThreshold => "usage_threshold",
+        })
+    }
+}
+
+impl FromStr for WebhookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "session_created" => Ok(Self::SessionCreated),
+            "status_changed" => Ok(Self::StatusChanged),
+            "usage_threshold" => Ok(Self::UsageThreshold),
+            _ => Err(format!(
+                "unknown webhook event '{}' (expected session_created, status_changed or usage_threshold)",
+                s
+            )),
+        }
+    }
+}
+
+/// One configured webhook URL with its event and session filters.
+#[derive(Debug, Clone)]
+pub struct WebhookEndpoint {
+    client: reqwest::Client,
+    url: String,
+    headers: BTreeMap<String, String>,
+    events: Vec<WebhookEvent>,
+    working_dirs: Vec<String>,
+    usage_threshold: u8,
+}
+
+impl WebhookEndpoint {
+    /// Creates an endpoint, or `None` if no URL is configured. Unknown event
+    /// names are skipped with a warning.
+    pub fn from_config(config: &WebhookConfig) -> Option<Self> {
+        if config.url.trim().is_empty() {
+            return None;
+        }
+        let events = config
+            .events
+            .iter()
+            .filter_map(|name| match name.parse() {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    tracing::warn!(url = %config.url, "ignoring webhook event: {}", e);
+                    None
+                }
+            })
+            .collect();
+        Some(Self {
+            client: super::webhook_client()?,
+            url: config.url.clone(),
+            headers: config.headers.clone(),
+            events,
+            working_dirs: config.working_dirs.clone(),
+            usage_threshold: config.usage_threshold,
+        })
+    }
+
+    /// URL the endpoint posts to, for logging.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Whether the endpoint subscribes to `event`.
+    pub fn wants(&self, event: WebhookEvent) -> bool {
+        self.events.contains(&event)
+    }
+
+    /// Usage percentage that triggers a [`WebhookEvent::UsageThreshold`].
+    pub fn usage_threshold(&self) -> u8 {
+        self.usage_threshold
+    }
+
+    /// Whether `session` passes the `working_dirs` filter: it runs in or
+    /// below one of them, or no filter is set.
+    pub fn matches(&self, session: &Session) -> bool {
+        if self.working_dirs.is_empty() {
+            return true;
+        }
+        let Some(dir) = &session.working_dir else {
+            return false;
+        };
+        self.working_dirs
+            .iter()
+            .any(|prefix| dir.starts_with(Path::new(prefix)))
+    }
+
+    /// Payload for a session `event`.
+    pub fn session_payload(&self, event: WebhookEvent, session: &Session) -> Value {
+        json!({
+            "event": event.to_string(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "session": SessionSnapshot::from(session),
+        })
+    }
+
+    /// Payload for a usage threshold crossing.
+    pub fn usage_payload(&self, usage: &UsageData) -> Value {
+        json!({
+            "event": WebhookEvent::UsageThreshold.to_string(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "threshold": self.usage_threshold,
+            "usage": usage,
+        })
+    }
+
+    /// POSTs `payload` with the configured headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the request error, or an error for a non-2xx response.
+    pub async fn post(&self, payload: &Value) -> Result<(), reqwest::Error> {
+        let mut request = self.client.post(&self.url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        super::send_json(request, payload).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    fn endpoint(config: WebhookConfig) -> WebhookEndpoint {
+        WebhookEndpoint::from_config(&WebhookConfig {
+            url: "https://hooks.invalid/acd".to_string(),
+            ..config
+        })
+        .expect("endpoint")
+    }
+
+    fn session(dir: Option<&str>) -> Session {
+        Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            dir.map(PathBuf::from),
+        )
+    }
+
+    #[test]
+    fn test_disabled_without_url() {
+        assert!(WebhookEndpoint::from_config(&WebhookConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_events_skip_unknown_names() {
+        let hook = endpoint(WebhookConfig {
+            events: vec!["session_created".to_string(), "bogus".to_string()],
+            ..WebhookConfig::default()
+        });
+        assert!(hook.wants(WebhookEvent::SessionCreated));
+        assert!(!hook.wants(WebhookEvent::StatusChanged));
+        assert!(!hook.wants(WebhookEvent::UsageThreshold));
+    }
+
+    #[test]
+    fn test_working_dir_filter() {
+        let all = endpoint(WebhookConfig::default());
+        assert!(all.matches(&session(None)));
+
+        let work = endpoint(WebhookConfig {
+            working_dirs: vec!["/home/u/work".to_string()],
+            ..WebhookConfig::default()
+        });
+        assert!(work.matches(&session(Some("/home/u/work/api"))));
+        assert!(work.matches(&session(Some("/home/u/work"))));
+        assert!(!work.matches(&session(Some("/home/u/workshop"))));
+        assert!(!work.matches(&session(None)));
+    }
+
+    #[test]
+    fn test_session_payload_embeds_snapshot() {
+        let payload = endpoint(WebhookConfig::default())
+            .session_payload(WebhookEvent::StatusChanged, &session(Some("/home/u/api")));
+        assert_eq!(payload["event"], "status_changed");
+        assert_eq!(payload["session"]["session_id"], "abc");
+        assert_eq!(payload["session"]["status"], "working");
+        assert_eq!(payload["session"]["working_dir"], "/home/u/api");
+        assert!(payload["timestamp"].is_string());
+    }
+}
@@ -0,0 +1,205 @@
+//! WezTerm backend driven by `wezterm cli`.
+//!
+//! Panes are listed with `wezterm cli list --format json` and focused by ID.
+//! Spawning opens a new tab; `wezterm cli spawn` cannot set its title.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use super::{Pane, TerminalBackend};
+
+/// The WezTerm instance the TUI runs in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WezTerm;
+
+/// One entry of `wezterm cli list --format json` (fields we use).
+#[derive(Debug, Deserialize)]
+struct PaneInfo {
+    pane_id: u64,
+    #[serde(default)]
+    title: String,
+    /// `file://host/path` URL, absent for panes without a known cwd
+    #[serde(default)]
+    cwd: String,
+}
+
+impl WezTerm {
+    /// Detects WezTerm from `WEZTERM_PANE` (set in every WezTerm pane) or
+    /// `TERM_PROGRAM=WezTerm`.
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let in_pane = env("WEZTERM_PANE").is_some_and(|pane| !pane.is_empty());
+        (in_pane || env("TERM_PROGRAM").as_deref() == Some("WezTerm")).then_some(Self)
+    }
+
+    /// `wezterm cli <args...>`
+    fn cli<I, S>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut command = Command::new("wezterm");
+        command.arg("cli").args(args);
+        command
+    }
+}
+
+impl TerminalBackend for WezTerm {
+    fn name(&self) -> &'static str {
+        "WezTerm"
+    }
+
+    fn list_panes(&self) -> Result<Vec<Pane>, String> {
+        let list = super::output(self.cli(["list", "--format", "json"]), self.name())?;
+        parse_list(&list)
+    }
+
+    fn focus_pane_command(&self, pane: &Pane) -> Command {
+        self.cli(["activate-pane", "--pane-id", &pane.id])
+    }
+
+    fn spawn_command(&self, dir: Option<&Path>, _title: Option<&str>, argv: &[String]) -> Command {
+        let mut args = vec!["spawn".to_string()];
+        if let Some(dir) = dir {
+            args.push("--cwd".to_string());
+            args.push(dir.display().to_string());
+        }
+        args.push("--".to_string());
+        args.extend(argv.iter().cloned());
+        self.cli(args)
+    }
+}
+
+/// Panes in `wezterm cli list --format json` output.
+fn parse_list(list_json: &str) -> Result<Vec<Pane>, String> {
+    let panes: Vec<PaneInfo> = serde_json::from_str(list_json)
+        .map_err(|e| format!("invalid wezterm cli list output: {}", e))?;
+    Ok(panes
+        .into_iter()
+        .map(|pane| Pane {
+            id: pane.pane_id.to_string(),
+            title: pane.title,
+            working_dir: url_path(&pane.cwd).map(PathBuf::from),
+        })
+        .collect())
+}
+
+/// Filesystem path of a `file://host/path` URL, percent-decoded.
+fn url_path(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("file://")?;
+    let path = &rest[rest.find('/')?..];
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::command_line;
+    use crate::{AgentType, Session};
+
+    fn resume() -> Vec<String> {
+        vec!["claude".into(), "--resume".into(), "abc-123".into()]
+    }
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_from_env_reads_pane_or_term_program() {
+        let env = |name: &str| (name == "WEZTERM_PANE").then(|| "3".to_string());
+        assert_eq!(WezTerm::from_env(env), Some(WezTerm));
+        assert_eq!(WezTerm::from_env(|_| None), None);
+        let program = |name: &str| (name == "TERM_PROGRAM").then(|| "WezTerm".to_string());
+        assert_eq!(WezTerm::from_env(program), Some(WezTerm));
+    }
+
+    #[test]
+    fn test_parse_list_decodes_cwd_url() {
+        let list = r#"[
+            {"window_id": 0, "tab_id": 1, "pane_id": 4, "title": "api", "cwd": "file://host/home/u/my%20api"},
+            {"window_id": 0, "tab_id": 2, "pane_id": 7}
+        ]"#;
+        assert_eq!(
+            parse_list(list).expect("parse"),
+            vec![
+                Pane {
+                    id: "4".to_string(),
+                    title: "api".to_string(),
+                    working_dir: Some(PathBuf::from("/home/u/my api")),
+                },
+                Pane {
+                    id: "7".to_string(),
+                    title: String::new(),
+                    working_dir: None,
+                },
+            ]
+        );
+        assert!(parse_list("not json").is_err());
+    }
+
+    #[test]
+    fn test_focus_pane_command_activates_pane_id() {
+        let pane = Pane {
+            id: "4".to_string(),
+            title: String::new(),
+            working_dir: None,
+        };
+        assert_eq!(
+            command_line(&WezTerm.focus_pane_command(&pane)),
+            vec!["wezterm", "cli", "activate-pane", "--pane-id", "4"]
+        );
+    }
+
+    #[test]
+    fn test_url_path() {
+        assert_eq!(url_path("file:///tmp/a%2Fb").as_deref(), Some("/tmp/a/b"));
+        assert_eq!(
+            url_path("file://mac.local/Users/u").as_deref(),
+            Some("/Users/u")
+        );
+        assert_eq!(url_path("/tmp"), None);
+    }
+
+    #[test]
+    fn test_resurrect_command_spawns_in_working_dir() {
+        let session = Session::new(
+            "abc-123".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        );
+        let command = WezTerm.resurrect_command(&session, &resume());
+        assert_eq!(
+            command_line(&command),
+            vec![
+                "wezterm",
+                "cli",
+                "spawn",
+                "--cwd",
+                "/home/u/api",
+                "--",
+                "claude",
+                "--resume",
+                "abc-123"
+            ]
+        );
+    }
+}
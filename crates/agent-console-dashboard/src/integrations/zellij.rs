@@ -0,0 +1,168 @@
+//! Zellij backend driven by `zellij action`.
+//!
+//! `zellij action` cannot focus individual panes, so the panes listed are the
+//! tabs (`query-tab-names`), matched to sessions by name (see
+//! [`super::pane_name`]), which is how Zellij layouts usually name
+//! per-project tabs. Spawning opens a new pane in the current tab. The
+//! `ZELLIJ_PANE_ID` reported by hooks is not used, as it does not name a tab.
+
+use std::path::Path;
+use std::process::Command;
+
+use super::{Pane, TerminalBackend};
+
+/// The Zellij session the TUI runs in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zellij {
+    /// `ZELLIJ_SESSION_NAME`, passed as `--session` so actions target the
+    /// TUI's own Zellij session. None uses the current session.
+    pub session_name: Option<String>,
+}
+
+impl Zellij {
+    /// Detects Zellij from `ZELLIJ` (set inside every Zellij pane).
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        env("ZELLIJ")?;
+        Some(Self::with_env(env))
+    }
+
+    /// The session in `ZELLIJ_SESSION_NAME`, if set.
+    pub fn with_env(env: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            session_name: env("ZELLIJ_SESSION_NAME").filter(|name| !name.is_empty()),
+        }
+    }
+
+    /// `zellij [--session <name>] action <args...>`
+    fn action<I, S>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut command = Command::new("zellij");
+        if let Some(name) = &self.session_name {
+            command.arg("--session").arg(name);
+        }
+        command.arg("action").args(args);
+        command
+    }
+}
+
+impl TerminalBackend for Zellij {
+    fn name(&self) -> &'static str {
+        "Zellij"
+    }
+
+    fn list_panes(&self) -> Result<Vec<Pane>, String> {
+        let tabs = super::output(self.action(["query-tab-names"]), self.name())?;
+        Ok(tabs
+            .lines()
+            .filter(|name| !name.is_empty())
+            .map(|name| Pane {
+                id: name.to_string(),
+                title: name.to_string(),
+                working_dir: None,
+            })
+            .collect())
+    }
+
+    fn focus_pane_command(&self, pane: &Pane) -> Command {
+        self.action(["go-to-tab-name", &pane.id])
+    }
+
+    fn spawn_command(&self, dir: Option<&Path>, title: Option<&str>, argv: &[String]) -> Command {
+        let mut args = vec!["new-pane".to_string()];
+        if let Some(dir) = dir {
+            args.push("--cwd".to_string());
+            args.push(dir.display().to_string());
+        }
+        if let Some(title) = title {
+            args.push("--name".to_string());
+            args.push(title.to_string());
+        }
+        args.push("--".to_string());
+        args.extend(argv.iter().cloned());
+        self.action(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::command_line;
+    use crate::{AgentType, Session};
+
+    fn resume() -> Vec<String> {
+        vec!["claude".into(), "--resume".into(), "abc-123".into()]
+    }
+    use std::path::PathBuf;
+
+    fn session() -> Session {
+        Session::new(
+            "abc-123".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/api")),
+        )
+    }
+
+    #[test]
+    fn test_from_env_reads_session_name() {
+        let env = |name: &str| match name {
+            "ZELLIJ" => Some("0".to_string()),
+            "ZELLIJ_SESSION_NAME" => Some("work".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            Zellij::from_env(env),
+            Some(Zellij {
+                session_name: Some("work".to_string())
+            })
+        );
+        assert_eq!(Zellij::from_env(|_| None), None);
+    }
+
+    #[test]
+    fn test_focus_pane_command_targets_tab_by_name() {
+        let zellij = Zellij {
+            session_name: Some("work".to_string()),
+        };
+        let tab = Pane {
+            id: "api".to_string(),
+            title: "api".to_string(),
+            working_dir: None,
+        };
+        assert_eq!(
+            command_line(&zellij.focus_pane_command(&tab)),
+            vec![
+                "zellij",
+                "--session",
+                "work",
+                "action",
+                "go-to-tab-name",
+                "api"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resurrect_command_opens_pane_in_working_dir() {
+        let zellij = Zellij { session_name: None };
+        let command = zellij.resurrect_command(&session(), &resume());
+        assert_eq!(
+            command_line(&command),
+            vec![
+                "zellij",
+                "action",
+                "new-pane",
+                "--cwd",
+                "/home/u/api",
+                "--name",
+                "api",
+                "--",
+                "claude",
+                "--resume",
+                "abc-123"
+            ]
+        );
+    }
+}
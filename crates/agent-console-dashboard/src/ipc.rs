@@ -1,6 +1,6 @@
 //! IPC wire types for JSON Lines protocol over Unix domain sockets.
 
-use crate::{AgentType, Session, Status};
+use crate::{AgentType, Session, Status, TerminalPane};
 use std::path::PathBuf;
 use std::time::Instant;
 
@@ -35,6 +35,8 @@ pub enum IpcCommandKind {
     Stop,
     /// Reopen a closed session (REOPEN).
     Reopen,
+    /// Set or clear a session's display name (RENAME).
+    Rename,
 }
 
 impl std::fmt::Display for IpcCommandKind {
@@ -50,6 +52,7 @@ impl std::fmt::Display for IpcCommandKind {
             IpcCommandKind::Status => "STATUS",
             IpcCommandKind::Stop => "STOP",
             IpcCommandKind::Reopen => "REOPEN",
+            IpcCommandKind::Rename => "RENAME",
         };
         write!(f, "{}", s)
     }
@@ -70,6 +73,7 @@ impl std::str::FromStr for IpcCommandKind {
             "STATUS" => Ok(IpcCommandKind::Status),
             "STOP" => Ok(IpcCommandKind::Stop),
             "REOPEN" => Ok(IpcCommandKind::Reopen),
+            "RENAME" => Ok(IpcCommandKind::Rename),
             _ => Err(format!("unknown command: {}", s)),
         }
     }
@@ -83,9 +87,9 @@ impl std::str::FromStr for IpcCommandKind {
 pub struct IpcCommand {
     /// Protocol version (must be [`IPC_VERSION`]).
     pub version: u32,
-    /// Command name (SET, LIST, GET, RM, SUB, STATUS, DUMP, REOPEN, STOP, DELETE).
+    /// Command name (SET, LIST, GET, RM, SUB, STATUS, DUMP, REOPEN, STOP, DELETE, RENAME).
     pub cmd: String,
-    /// Session identifier (for SET, GET, RM, REOPEN, DELETE).
+    /// Session identifier (for SET, GET, RM, REOPEN, DELETE, RENAME).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
     /// Session status string (for SET).
@@ -100,6 +104,38 @@ pub struct IpcCommand {
     /// Session priority (for SET).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u64>,
+    /// Display name (for RENAME). None or empty clears the name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Latest assistant message excerpt (for SET). None keeps the stored message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_message: Option<String>,
+    /// Agent type wire name (for SET, e.g. "codex"). Applies when the session
+    /// is created; None means Claude Code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_type: Option<String>,
+    /// Multiplexer pane the hook ran in (for SET). None keeps the stored pane.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_pane: Option<TerminalPane>,
+}
+
+impl IpcCommand {
+    /// Creates a `kind` command for `session_id` with every other field unset.
+    pub fn new(kind: IpcCommandKind, session_id: Option<&str>) -> Self {
+        Self {
+            version: IPC_VERSION,
+            cmd: kind.to_string(),
+            session_id: session_id.map(str::to_string),
+            status: None,
+            working_dir: None,
+            confirmed: None,
+            priority: None,
+            display_name: None,
+            last_message: None,
+            agent_type: None,
+            terminal_pane: None,
+        }
+    }
 }
 
 /// Response envelope from daemon to client.
@@ -186,6 +222,15 @@ pub struct SessionSnapshot {
     /// Session priority for sorting (higher = ranked higher).
     #[serde(default)]
     pub priority: u64,
+    /// User-assigned display name, or None if the session was never renamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Excerpt of the most recent assistant message, or None if never reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_message: Option<String>,
+    /// Multiplexer pane the session runs in, or None if not reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_pane: Option<TerminalPane>,
 }
 
 /// A single status change in the history, serializable for IPC.
@@ -232,7 +277,7 @@ impl From<&Session> for SessionSnapshot {
 
         Self {
             session_id: session.session_id.clone(),
-            agent_type: format!("{:?}", session.agent_type).to_lowercase(),
+            agent_type: session.agent_type.to_string(),
             status: session.status.to_string(),
             working_dir,
             elapsed_seconds: session.since.elapsed().as_secs(),
@@ -240,6 +285,9 @@ impl From<&Session> for SessionSnapshot {
             history,
             closed: session.closed,
             priority: session.priority,
+            display_name: session.display_name.clone(),
+            last_message: session.last_message.clone(),
+            terminal_pane: session.terminal_pane.clone(),
         }
     }
 }
@@ -411,6 +459,7 @@ mod tests {
         assert_eq!(IpcCommandKind::Status.to_string(), "STATUS");
         assert_eq!(IpcCommandKind::Stop.to_string(), "STOP");
         assert_eq!(IpcCommandKind::Reopen.to_string(), "REOPEN");
+        assert_eq!(IpcCommandKind::Rename.to_string(), "RENAME");
     }
 
     #[test]
@@ -456,6 +505,10 @@ mod tests {
             "reopen".parse::<IpcCommandKind>().unwrap(),
             IpcCommandKind::Reopen
         );
+        assert_eq!(
+            "rename".parse::<IpcCommandKind>().unwrap(),
+            IpcCommandKind::Rename
+        );
     }
 
     #[test]
@@ -480,6 +533,7 @@ mod tests {
             (IpcCommandKind::Status, "STATUS"),
             (IpcCommandKind::Stop, "STOP"),
             (IpcCommandKind::Reopen, "REOPEN"),
+            (IpcCommandKind::Rename, "RENAME"),
         ];
 
         for (kind, expected_wire_format) in commands {
@@ -490,4 +544,21 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_usage_update_keeps_unknown_api_fields() {
+        let json = r#"{
+            "five_hour": {"utilization": 40.0, "resets_at": null, "burst": 3},
+            "seven_day": {"utilization": 30.0, "resets_at": null},
+            "plan_tier": "max"
+        }"#;
+        let data: claude_usage::UsageData = serde_json::from_str(json).expect("usage");
+        let notification = IpcNotification::usage_update(&data);
+        let usage = notification.usage.expect("usage payload");
+        assert_eq!(usage["plan_tier"], "max");
+        assert_eq!(usage["five_hour"]["burst"], 3);
+
+        let received: claude_usage::UsageData = serde_json::from_value(usage).expect("usage");
+        assert_eq!(received, data);
+    }
 }
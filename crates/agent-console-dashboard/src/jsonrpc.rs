@@ -0,0 +1,134 @@
+//! JSON-RPC 2.0 message handling shared by `acd mcp` and `acd rpc`.
+//!
+//! Both servers read one message per line and answer requests in order;
+//! batches are not supported.
+
+use serde_json::{json, Value};
+
+/// Standard JSON-RPC error codes, plus the server-defined ones in use.
+pub(crate) mod code {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// The daemon rejected the command or could not be reached.
+    pub const DAEMON_ERROR: i64 = -32000;
+    /// MCP: `resources/read` for an unknown URI.
+    pub const RESOURCE_NOT_FOUND: i64 = -32002;
+}
+
+/// A JSON-RPC error response body.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RpcError {
+    pub(crate) code: i64,
+    pub(crate) message: String,
+}
+
+impl RpcError {
+    pub(crate) fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(code::INVALID_PARAMS, message)
+    }
+}
+
+/// An incoming request or notification.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Request {
+    /// Request ID; `None` for notifications, which get no response.
+    pub(crate) id: Option<Value>,
+    pub(crate) method: String,
+    /// `params`, or `null` if absent.
+    pub(crate) params: Value,
+}
+
+impl Request {
+    /// Parses one line, or returns the error response to send back.
+    pub(crate) fn parse(line: &str) -> Result<Self, Value> {
+        let message: Value = serde_json::from_str(line).map_err(|e| {
+            let error = RpcError::new(code::PARSE_ERROR, format!("parse error: {}", e));
+            response(Value::Null, Err(error))
+        })?;
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            let error = RpcError::new(code::INVALID_REQUEST, "expected a JSON-RPC request");
+            return Err(response(id.unwrap_or(Value::Null), Err(error)));
+        };
+        Ok(Self {
+            id,
+            method: method.to_string(),
+            params: message.get("params").cloned().unwrap_or(Value::Null),
+        })
+    }
+
+    /// Required string parameter `name`.
+    pub(crate) fn str_param(&self, name: &str) -> Result<&str, RpcError> {
+        str_arg(&self.params, name)
+    }
+}
+
+/// Required string field `name` of `object` (request params or tool
+/// arguments).
+pub(crate) fn str_arg<'a>(object: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    object
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::invalid_params(format!("missing string parameter '{}'", name)))
+}
+
+/// Response to request `id`.
+pub(crate) fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+/// Server-to-client notification.
+pub(crate) fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_and_notification() {
+        let request =
+            Request::parse(r#"{"jsonrpc":"2.0","id":7,"method":"ping"}"#).expect("valid request");
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.method, "ping");
+        assert_eq!(request.params, Value::Null);
+
+        let note = Request::parse(r#"{"jsonrpc":"2.0","method":"x","params":{"a":"b"}}"#)
+            .expect("valid notification");
+        assert_eq!(note.id, None);
+        assert_eq!(note.str_param("a"), Ok("b"));
+        assert_eq!(
+            note.str_param("c").map_err(|e| e.code),
+            Err(code::INVALID_PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_become_responses() {
+        let error = Request::parse("{oops").expect_err("parse error");
+        assert_eq!(error["error"]["code"], code::PARSE_ERROR);
+        assert_eq!(error["id"], Value::Null);
+
+        let error = Request::parse(r#"{"jsonrpc":"2.0","id":"a"}"#).expect_err("no method");
+        assert_eq!(error["error"]["code"], code::INVALID_REQUEST);
+        assert_eq!(error["id"], "a");
+    }
+}
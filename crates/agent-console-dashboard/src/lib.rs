@@ -18,15 +18,27 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::{Duration, Instant};
 
+/// Agent adapters for built-in and config-defined coding agents.
+pub mod agents;
+
 /// Configuration utilities including XDG path resolution.
 pub mod config;
 
 /// Daemon module providing process lifecycle management and daemonization.
 pub mod daemon;
 
+/// Terminal and desktop integrations: session focus/resurrect and notifications.
+pub mod integrations;
+
 /// Layout system for dashboard widget arrangement.
 pub mod layout;
 
+/// Model Context Protocol server exposing sessions to MCP clients.
+pub mod mcp;
+
+/// JSON-RPC 2.0 bridge over the IPC protocol for editor extensions.
+pub mod rpc;
+
 /// TUI module providing the terminal user interface for the dashboard.
 pub mod tui;
 
@@ -36,6 +48,9 @@ pub mod widgets;
 /// Client module for daemon communication with lazy-start capability.
 pub mod client;
 
+/// JSON-RPC 2.0 message handling for the MCP server and the editor bridge.
+mod jsonrpc;
+
 /// IPC wire types for JSON Lines protocol.
 mod ipc;
 pub use ipc::*;
@@ -132,10 +147,79 @@ impl FromStr for Status {
 }
 
 /// Agent type enumeration representing different AI coding agents.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+///
+/// Agents other than the built-in ones are declared in the config file (see
+/// [`agents`]) and identified by their configured name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum AgentType {
     /// Claude Code - Anthropic's AI coding assistant
+    #[default]
     ClaudeCode,
+    /// Codex CLI - OpenAI's terminal coding agent
+    Codex,
+    /// Gemini CLI - Google's terminal coding agent
+    GeminiCli,
+    /// Aider - AI pair programming in the terminal
+    Aider,
+    /// An agent declared under `[[agents.custom]]`, by its configured name
+    Custom(String),
+}
+
+impl AgentType {
+    /// Human-readable name for display (e.g., "Claude Code").
+    ///
+    /// Custom agents show their configured name; see
+    /// [`agents::AgentAdapter::display_name`] for the configured display name.
+    pub fn label(&self) -> &str {
+        match self {
+            AgentType::ClaudeCode => "Claude Code",
+            AgentType::Codex => "Codex",
+            AgentType::GeminiCli => "Gemini CLI",
+            AgentType::Aider => "Aider",
+            AgentType::Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for AgentType {
+    /// Writes the lowercase wire name used in IPC (e.g., "claudecode").
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AgentType::ClaudeCode => "claudecode",
+            AgentType::Codex => "codex",
+            AgentType::GeminiCli => "geminicli",
+            AgentType::Aider => "aider",
+            AgentType::Custom(name) => name,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Error type for parsing AgentType from string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAgentTypeError(pub String);
+
+impl fmt::Display for ParseAgentTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid agent type: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseAgentTypeError {}
+
+impl FromStr for AgentType {
+    type Err = ParseAgentTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "claudecode" | "claude-code" | "claude" => Ok(AgentType::ClaudeCode),
+            "codex" => Ok(AgentType::Codex),
+            "geminicli" | "gemini-cli" | "gemini" => Ok(AgentType::GeminiCli),
+            "aider" => Ok(AgentType::Aider),
+            _ if agents::is_valid_name(s) => Ok(AgentType::Custom(s.to_string())),
+            _ => Err(ParseAgentTypeError(s.to_string())),
+        }
+    }
 }
 
 /// Record of a state transition for tracking session history.
@@ -160,6 +244,65 @@ pub struct ApiUsage {
     pub output_tokens: u64,
 }
 
+impl ApiUsage {
+    /// Estimated API list-price cost in USD for `model`, using `table`.
+    ///
+    /// Returns None if the model has no rates in the table.
+    pub fn estimated_cost(
+        &self,
+        table: &claude_usage::pricing::PricingTable,
+        model: &str,
+    ) -> Option<f64> {
+        table.estimate(model, &(*self).into())
+    }
+}
+
+impl From<ApiUsage> for claude_usage::pricing::TokenUsage {
+    fn from(usage: ApiUsage) -> Self {
+        Self::new(usage.input_tokens, usage.output_tokens)
+    }
+}
+
+/// Terminal multiplexer that reported a [`TerminalPane`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Multiplexer {
+    /// tmux (`TMUX_PANE`).
+    Tmux,
+    /// Zellij (`ZELLIJ_PANE_ID`).
+    Zellij,
+}
+
+/// Multiplexer pane a session runs in, captured from the environment of its
+/// hooks.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TerminalPane {
+    /// Multiplexer the ID belongs to.
+    pub multiplexer: Multiplexer,
+    /// Pane ID: `%3` for tmux, `3` for Zellij.
+    pub id: String,
+}
+
+impl TerminalPane {
+    /// The pane of the calling process, from `ZELLIJ_PANE_ID` or `TMUX_PANE`.
+    ///
+    /// Zellij is checked first since it runs inside tmux rather than the other
+    /// way around, like backend detection.
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let non_empty = |name| env(name).filter(|id: &String| !id.is_empty());
+        if let Some(id) = non_empty("ZELLIJ_PANE_ID") {
+            return Some(Self {
+                multiplexer: Multiplexer::Zellij,
+                id,
+            });
+        }
+        non_empty("TMUX_PANE").map(|id| Self {
+            multiplexer: Multiplexer::Tmux,
+            id,
+        })
+    }
+}
+
 /// Agent session state with history tracking.
 #[derive(Debug, Clone)]
 pub struct Session {
@@ -184,6 +327,17 @@ pub struct Session {
     pub closed: bool,
     /// Session priority for sorting (higher = ranked higher).
     pub priority: u64,
+    /// User-assigned display name (set via RENAME). None uses the default label.
+    pub display_name: Option<String>,
+    /// Excerpt of the most recent assistant message, reported by hooks from
+    /// the session transcript. None until the first report.
+    pub last_message: Option<String>,
+    /// Multiplexer pane the session runs in, reported by hooks. None outside
+    /// tmux and Zellij, or until the first report.
+    pub terminal_pane: Option<TerminalPane>,
+    /// Tags of the session's project (`.acd.toml`), shown in its row. Only
+    /// set by the TUI.
+    pub tags: Vec<String>,
 }
 
 impl Session {
@@ -200,6 +354,10 @@ impl Session {
             api_usage: None,
             closed: false,
             priority: 0,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
+            tags: Vec::new(),
         }
     }
 
@@ -281,6 +439,10 @@ impl Default for Session {
             api_usage: None,
             closed: false,
             priority: 0,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
+            tags: Vec::new(),
         }
     }
 }
@@ -307,7 +469,7 @@ impl DaemonConfig {
 impl Default for DaemonConfig {
     fn default() -> Self {
         Self {
-            socket_path: PathBuf::from("/tmp/agent-console-dashboard.sock"),
+            socket_path: config::xdg::socket_path(),
             daemonize: false,
         }
     }
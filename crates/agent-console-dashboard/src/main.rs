@@ -14,18 +14,41 @@ use agent_console_dashboard::{
 };
 use clap::{Parser, Subcommand, ValueEnum};
 use commands::{
-    is_daemon_running, run_claude_hook_async, run_config_edit_command, run_daemon_stop_command,
-    run_delete_command, run_dump_command, run_install_command, run_status_command,
-    run_uninstall_command, run_update_command, HookInput,
+    is_daemon_running, run_agent_hook_command, run_aider_command, run_aider_hook_command,
+    run_claude_hook_async, run_codex_hook_command, run_config_edit_command,
+    run_daemon_stop_command, run_delete_command, run_dump_command, run_gemini_hook_command,
+    run_install_command, run_list_command, run_mcp_command, run_menubar_command,
+    run_prompt_segment_command, run_resurrect_command, run_rpc_command, run_status_command,
+    run_statusbar_command, run_tmux_status_command, run_uninstall_command, run_update_command,
+    GeminiHookInput, HookInput, ListFormat, MenubarFormat, StatusbarFormat,
 };
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Default `--socket` of every subcommand, resolved once per profile.
+fn default_socket() -> PathBuf {
+    use agent_console_dashboard::config::xdg;
+    static SOCKETS: Mutex<BTreeMap<Option<String>, PathBuf>> = Mutex::new(BTreeMap::new());
+    SOCKETS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(xdg::profile())
+        .or_insert_with(xdg::default_socket_path)
+        .clone()
+}
 
 /// Agent Console Dashboard daemon
 #[derive(Parser)]
 #[command(name = "agent-console-dashboard")]
 #[command(version, about = "Agent Console Dashboard daemon")]
 struct Cli {
+    /// Use a named profile, with its own config file and daemon socket
+    #[arg(long, global = true, value_parser = agent_console_dashboard::config::xdg::parse_profile)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -38,6 +61,8 @@ enum LayoutModeArg {
     Auto,
     /// Full dashboard layout with session list and detail panel.
     Large,
+    /// Session list with condensed header, no detail panel.
+    Medium,
     /// Compact two-line layout for narrow terminals.
     TwoLine,
 }
@@ -48,11 +73,14 @@ enum Commands {
     /// Launch the terminal user interface
     Tui {
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
-        /// Layout mode (large or twoline)
+        /// Layout mode (auto, large, medium, or twoline)
         #[arg(long, value_enum, ignore_case = true)]
         layout: Option<LayoutModeArg>,
+        /// Disable hooks, rename, remove, and resurrect (safe for shared displays)
+        #[arg(long)]
+        read_only: bool,
     },
 
     /// Manage configuration file
@@ -78,7 +106,62 @@ enum Commands {
         /// Status to set: working, attention, question, closed
         status: Status,
         /// Daemon socket path
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Handle Codex CLI notify events (set `notify = ["acd", "codex-hook"]`)
+    CodexHook {
+        /// JSON payload passed by Codex as the last argument
+        payload: String,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Handle Gemini CLI hook events (reads JSON from stdin)
+    GeminiHook {
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Report a lifecycle event of any agent, e.g. one from `[[agents.custom]]`
+    AgentHook {
+        /// Agent name (built-in, or a custom agent's `name`)
+        #[arg(long)]
+        agent: String,
+        /// Working directory of the session
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+        /// Message shown as the session's preview
+        #[arg(long)]
+        message: Option<String>,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+        /// Session ID
+        session_id: String,
+        /// Event name, mapped to a status by the agent's `statuses`
+        event: String,
+    },
+
+    /// Run Aider, tracking its session on the dashboard
+    Aider {
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+        /// Arguments passed on to aider
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Mark an Aider session as waiting for input (run by `acd aider`)
+    AiderHook {
+        /// Session ID assigned by `acd aider`
+        session_id: String,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
     },
 
@@ -87,6 +170,89 @@ enum Commands {
 
     /// Remove ACD hooks from Claude Code settings
     Uninstall,
+
+    /// Serve the Model Context Protocol on stdio (for `claude mcp add`)
+    Mcp {
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Serve the JSON-RPC 2.0 bridge for editor extensions
+    Rpc {
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+        /// Listen on this Unix socket instead of stdio
+        #[arg(long)]
+        listen: Option<PathBuf>,
+    },
+
+    /// List sessions, as text or as Alfred/Raycast Script Filter JSON
+    List {
+        /// Output format (text or script-filter)
+        #[arg(long, value_enum, ignore_case = true, default_value = "text")]
+        format: ListFormat,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Print a compact session summary for shell prompts (e.g. Starship)
+    PromptSegment {
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+        /// Print without ANSI colors
+        #[arg(long)]
+        no_color: bool,
+    },
+
+    /// Print session status for a Waybar or Polybar custom module
+    Statusbar {
+        /// Output format (waybar or polybar)
+        #[arg(long, value_enum, ignore_case = true)]
+        format: StatusbarFormat,
+        /// Print a new line after every daemon notification
+        #[arg(long)]
+        follow: bool,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Print menu bar output for xbar or SketchyBar
+    Menubar {
+        /// Output format (sketchybar or xbar)
+        #[arg(long, value_enum, ignore_case = true)]
+        format: MenubarFormat,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Reopen a closed session and print the command that resumes it
+    Resurrect {
+        /// Session ID
+        session_id: String,
+        /// Resume it in a new terminal pane (tmux, Zellij, kitty, WezTerm), or
+        /// in this terminal if none is detected
+        #[arg(long)]
+        exec: bool,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
+
+    /// Print session counts styled for the tmux status line
+    TmuxStatus {
+        /// Reuse the last output while younger than this (0s disables caching)
+        #[arg(long, default_value = "5s", value_parser = humantime::parse_duration)]
+        max_age: Duration,
+        /// Daemon socket path
+        #[arg(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+    },
 }
 
 /// Session management subcommands
@@ -106,7 +272,7 @@ enum SessionCommands {
         #[arg(long)]
         working_dir: Option<PathBuf>,
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
     },
     /// Delete a session by ID
@@ -114,7 +280,7 @@ enum SessionCommands {
         /// Session ID
         session_id: String,
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
     },
 }
@@ -125,7 +291,7 @@ enum DaemonCommands {
     /// Start the daemon
     Start {
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
         /// Run daemon in background (detach from terminal)
         #[arg(short, long)]
@@ -134,7 +300,7 @@ enum DaemonCommands {
     /// Stop the running daemon
     Stop {
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
         /// Stop without confirmation prompt
         #[arg(short, long)]
@@ -143,7 +309,7 @@ enum DaemonCommands {
     /// Restart the daemon (stop with force, then start)
     Restart {
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
         /// Run daemon in background (detach from terminal)
         #[arg(short, long)]
@@ -152,13 +318,13 @@ enum DaemonCommands {
     /// Check daemon health status
     Status {
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
     },
     /// Dump full daemon state as JSON
     Dump {
         /// Socket path for IPC communication
-        #[arg(long, default_value = "/tmp/agent-console-dashboard.sock")]
+        #[arg(long, default_value_os_t = default_socket())]
         socket: PathBuf,
         /// Output format (only json supported in v0)
         #[arg(long, default_value = "json")]
@@ -181,32 +347,46 @@ enum ConfigAction {
     Validate,
     /// Display current effective configuration
     Show,
-    /// Open configuration file in editor
+    /// Open configuration file in editor, validating it on save
     Edit,
+    /// Upgrade a configuration file written for an older version
+    Migrate,
 }
 
 fn main() -> ExitCode {
     // Parse CLI arguments BEFORE any fork/runtime operations
     // This ensures errors are shown to the user in the terminal
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    if let Some(profile) = &cli.profile {
+        // Spawned daemons and hooks inherit the profile through the env
+        std::env::set_var(agent_console_dashboard::config::xdg::PROFILE_ENV, profile);
+        // Resolve the `--socket` defaults again for the profile
+        cli = Cli::parse();
+    }
 
     match cli.command {
-        Commands::Tui { socket, layout } => {
+        Commands::Tui {
+            socket,
+            layout,
+            read_only,
+        } => {
             let rt =
                 tokio::runtime::Runtime::new().expect("failed to create tokio runtime for TUI");
             if let Err(e) = rt.block_on(async {
                 let layout_mode_override = layout.and_then(|l| match l {
                     LayoutModeArg::Auto => None,
                     LayoutModeArg::Large => Some(LayoutMode::Large),
+                    LayoutModeArg::Medium => Some(LayoutMode::Medium),
                     LayoutModeArg::TwoLine => Some(LayoutMode::TwoLine),
                 });
                 let mut app = App::new(socket, layout_mode_override);
-                // Wire hooks from config if available
+                app.read_only = read_only;
+                app.read_only_flag = read_only;
+                agent_console_dashboard::config::loader::ConfigLoader::migrate_default();
                 if let Ok(config) =
                     agent_console_dashboard::config::loader::ConfigLoader::load_default()
                 {
-                    app.activate_hooks = config.tui.activate_hooks;
-                    app.reopen_hooks = config.tui.reopen_hooks;
+                    app.apply_config(config);
                 }
                 app.run().await
             }) {
@@ -215,7 +395,7 @@ fn main() -> ExitCode {
             }
         }
         Commands::Config { action } => {
-            use agent_console_dashboard::config::{default, loader::ConfigLoader, xdg};
+            use agent_console_dashboard::config::{default, loader, loader::ConfigLoader, xdg};
             let result = match action {
                 ConfigAction::Init { force } => match default::create_default_config(force) {
                     Ok(_path) => {
@@ -262,6 +442,19 @@ fn main() -> ExitCode {
                     Err(e) => Err(e),
                 },
                 ConfigAction::Edit => run_config_edit_command(),
+                ConfigAction::Migrate => {
+                    let path = xdg::config_path();
+                    ConfigLoader::migrate_file(&path).map(|upgraded| match upgraded {
+                        Some((migration, backup)) => {
+                            println!("{}", loader::upgrade_summary(&path, &migration, &backup));
+                        }
+                        None => println!(
+                            "{} is already at config version {}",
+                            path.display(),
+                            agent_console_dashboard::config::migrate::CONFIG_VERSION
+                        ),
+                    })
+                }
             };
             if let Err(e) = result {
                 eprintln!("Config error: {e}");
@@ -359,12 +552,81 @@ fn main() -> ExitCode {
                 tokio::runtime::Runtime::new().expect("failed to create tokio runtime for hook");
             return rt.block_on(run_claude_hook_async(&socket, status, &input));
         }
+        Commands::CodexHook { payload, socket } => {
+            return run_codex_hook_command(&socket, &payload);
+        }
+        Commands::GeminiHook { socket } => {
+            let input: GeminiHookInput = match serde_json::from_reader(std::io::stdin()) {
+                Ok(v) => v,
+                Err(e) => {
+                    // Exit code 2 would block Gemini CLI, so only report it
+                    eprintln!("acd gemini-hook: failed to parse JSON from stdin: {}", e);
+                    return ExitCode::SUCCESS;
+                }
+            };
+            return run_gemini_hook_command(&socket, &input);
+        }
+        Commands::AgentHook {
+            agent,
+            cwd,
+            message,
+            socket,
+            session_id,
+            event,
+        } => {
+            return run_agent_hook_command(
+                &socket,
+                &agent,
+                &session_id,
+                &event,
+                cwd.as_deref(),
+                message.as_deref(),
+            );
+        }
+        Commands::Aider { socket, args } => {
+            return run_aider_command(&socket, &args);
+        }
+        Commands::AiderHook { session_id, socket } => {
+            return run_aider_hook_command(&socket, &session_id);
+        }
         Commands::Install => {
             return run_install_command();
         }
         Commands::Uninstall => {
             return run_uninstall_command();
         }
+        Commands::Mcp { socket } => {
+            return run_mcp_command(&socket);
+        }
+        Commands::Rpc { socket, listen } => {
+            return run_rpc_command(&socket, listen.as_deref());
+        }
+        Commands::List { format, socket } => {
+            return run_list_command(&socket, format);
+        }
+        Commands::PromptSegment { socket, no_color } => {
+            return run_prompt_segment_command(&socket, no_color);
+        }
+        Commands::Statusbar {
+            format,
+            follow,
+            socket,
+        } => {
+            return run_statusbar_command(&socket, format, follow);
+        }
+        Commands::Menubar { format, socket } => {
+            return run_menubar_command(&socket, format);
+        }
+        Commands::Resurrect {
+            session_id,
+            exec,
+            socket,
+        } => {
+            return run_resurrect_command(&socket, &session_id, exec);
+        }
+        Commands::TmuxStatus { max_age, socket } => {
+            return run_tmux_status_command(&socket, max_age);
+        }
     }
 
     ExitCode::SUCCESS
@@ -0,0 +1,153 @@
+//! Model Context Protocol server (`acd mcp`).
+//!
+//! Speaks MCP over stdio (newline-delimited JSON-RPC 2.0) and answers from the
+//! daemon over IPC, so Claude Code or any other MCP client can inspect and
+//! manage the agent sessions the dashboard tracks:
+//!
+//! ```sh
+//! claude mcp add acd -- acd mcp
+//! ```
+//!
+//! Resources (JSON [`SessionSnapshot`]s):
+//! - `acd://sessions` - every session
+//! - `acd://sessions/{session_id}` - one session
+//!
+//! Tools:
+//! - `list_sessions` - every session, for clients without resource support
+//! - `set_status` - set a session's status
+//! - `resurrect` - reopen a closed session and resume it in a new terminal
+//!   pane (Zellij, tmux, kitty or WezTerm, see [`crate::integrations`])
+//!
+//! The daemon is started on demand, like for the TUI.
+//!
+//! [`SessionSnapshot`]: crate::SessionSnapshot
+
+mod resources;
+mod tools;
+
+#[cfg(test)]
+mod tests;
+
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::agents::AgentRegistry;
+use crate::integrations::TerminalBackend;
+use crate::jsonrpc::{code, response, Request, RpcError};
+use crate::IpcCommand;
+
+/// Protocol revisions this server speaks, newest first.
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// MCP server answering from the daemon at `socket`.
+#[derive(Debug)]
+pub struct McpServer {
+    socket: PathBuf,
+    terminal: Option<Box<dyn TerminalBackend>>,
+    agents: AgentRegistry,
+}
+
+impl McpServer {
+    /// Creates a server for the daemon at `socket`. `terminal` is the backend
+    /// `resurrect` opens new panes with; without one, `resurrect` only reopens
+    /// the session and returns the command to resume it.
+    pub fn new(socket: PathBuf, terminal: Option<Box<dyn TerminalBackend>>) -> Self {
+        Self {
+            socket,
+            terminal,
+            agents: AgentRegistry::builtin(),
+        }
+    }
+
+    /// Resumes sessions with the adapters in `agents` (built-in agents only
+    /// by default).
+    pub fn with_agents(mut self, agents: AgentRegistry) -> Self {
+        self.agents = agents;
+        self
+    }
+
+    /// Serves requests from stdin until it is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading stdin or writing stdout fails.
+    pub async fn serve_stdio(&self) -> std::io::Result<()> {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_line(&line).await {
+                stdout.write_all(response.as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles one JSON-RPC message, returning the response line, or `None`
+    /// for notifications.
+    pub async fn handle_line(&self, line: &str) -> Option<String> {
+        let request = match Request::parse(line) {
+            Ok(request) => request,
+            Err(error) => return Some(error.to_string()),
+        };
+        // Notifications (no id) get no response
+        let id = request.id.clone()?;
+        let result = self.handle_request(&request.method, &request.params).await;
+        Some(response(id, result).to_string())
+    }
+
+    async fn handle_request(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(initialize_result(params)),
+            "ping" => Ok(json!({})),
+            "resources/list" => self.list_resources().await,
+            "resources/templates/list" => Ok(resources::templates()),
+            "resources/read" => self.read_resource(params).await,
+            "tools/list" => Ok(tools::definitions()),
+            "tools/call" => self.call_tool(params).await,
+            _ => Err(RpcError::new(
+                code::METHOD_NOT_FOUND,
+                format!("method not found: {}", method),
+            )),
+        }
+    }
+
+    /// Sends one IPC command to the daemon and returns its `data` payload.
+    ///
+    /// Errors are messages for the MCP client (unreachable daemon or the
+    /// daemon's own error).
+    async fn daemon(&self, command: IpcCommand) -> Result<Option<Value>, String> {
+        let response = crate::client::request(&self.socket, &command)
+            .await
+            .map_err(|e| format!("cannot reach the acd daemon: {}", e))?;
+        if response.ok {
+            Ok(response.data)
+        } else {
+            Err(response
+                .error
+                .unwrap_or_else(|| "unknown daemon error".to_string()))
+        }
+    }
+}
+
+/// Result of `initialize`: the client's protocol version if supported, else
+/// the newest one this server speaks.
+fn initialize_result(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|v| PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "resources": {}, "tools": {} },
+        "serverInfo": { "name": "acd", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Sessions are the Claude Code sessions tracked by the \
+            Agent Console Dashboard. Read acd://sessions to see them.",
+    })
+}
@@ -0,0 +1,105 @@
+//! MCP resources: session snapshots as JSON documents.
+
+use serde_json::{json, Value};
+
+use super::McpServer;
+use crate::jsonrpc::{code, RpcError};
+use crate::{IpcCommand, IpcCommandKind, SessionSnapshot};
+
+/// URI of the list of every session.
+pub(super) const SESSIONS_URI: &str = "acd://sessions";
+
+/// URI of one session.
+fn session_uri(session_id: &str) -> String {
+    format!("{}/{}", SESSIONS_URI, session_id)
+}
+
+/// Result of `resources/templates/list`.
+pub(super) fn templates() -> Value {
+    json!({
+        "resourceTemplates": [{
+            "uriTemplate": format!("{}/{{session_id}}", SESSIONS_URI),
+            "name": "session",
+            "description": "One agent session",
+            "mimeType": "application/json",
+        }]
+    })
+}
+
+impl McpServer {
+    /// Result of `resources/list`: the session list plus one entry per
+    /// session.
+    pub(super) async fn list_resources(&self) -> Result<Value, RpcError> {
+        let sessions = self
+            .list_sessions()
+            .await
+            .map_err(|e| RpcError::new(code::INTERNAL_ERROR, e))?;
+        let mut resources = vec![json!({
+            "uri": SESSIONS_URI,
+            "name": "sessions",
+            "description": "Every agent session tracked by acd",
+            "mimeType": "application/json",
+        })];
+        resources.extend(sessions.iter().map(|session| {
+            let name = session
+                .display_name
+                .clone()
+                .unwrap_or_else(|| session.session_id.clone());
+            json!({
+                "uri": session_uri(&session.session_id),
+                "name": name,
+                "description": format!(
+                    "{} session in {}",
+                    session.status,
+                    session.working_dir.as_deref().unwrap_or("an unknown directory")
+                ),
+                "mimeType": "application/json",
+            })
+        }));
+        Ok(json!({ "resources": resources }))
+    }
+
+    /// Result of `resources/read`.
+    pub(super) async fn read_resource(&self, params: &Value) -> Result<Value, RpcError> {
+        let uri = params
+            .get("uri")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::invalid_params("resources/read requires uri"))?;
+        let not_found = || RpcError::new(code::RESOURCE_NOT_FOUND, format!("no resource {}", uri));
+
+        let document = if uri == SESSIONS_URI {
+            let sessions = self
+                .list_sessions()
+                .await
+                .map_err(|e| RpcError::new(code::INTERNAL_ERROR, e))?;
+            serde_json::to_value(sessions).expect("failed to serialize sessions")
+        } else {
+            let session_id = uri
+                .strip_prefix(SESSIONS_URI)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .filter(|id| !id.is_empty())
+                .ok_or_else(not_found)?;
+            self.daemon(IpcCommand::new(IpcCommandKind::Get, Some(session_id)))
+                .await
+                .map_err(|_| not_found())?
+                .ok_or_else(not_found)?
+        };
+        Ok(json!({
+            "contents": [{
+                "uri": uri,
+                "mimeType": "application/json",
+                "text": serde_json::to_string_pretty(&document)
+                    .expect("failed to serialize resource"),
+            }]
+        }))
+    }
+
+    /// Every session, as the daemon's LIST returns them.
+    pub(super) async fn list_sessions(&self) -> Result<Vec<SessionSnapshot>, String> {
+        let data = self
+            .daemon(IpcCommand::new(IpcCommandKind::List, None))
+            .await?
+            .unwrap_or_else(|| json!([]));
+        serde_json::from_value(data).map_err(|e| format!("invalid session list: {}", e))
+    }
+}
@@ -0,0 +1,206 @@
+//! Tests for the MCP server against a real daemon socket.
+
+use super::*;
+use crate::daemon::SocketServer;
+use crate::{AgentType, Status};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Starts a daemon socket server with two sessions ("s-1" working with
+/// priority 5, "s-2" closed) and returns an MCP server connected to it.
+async fn server() -> (McpServer, TempDir) {
+    let dir = TempDir::new().expect("temp dir");
+    let socket = dir.path().join("acd.sock");
+    let mut daemon = SocketServer::new(socket.display().to_string());
+    daemon.start().await.expect("start daemon");
+
+    let store = daemon.store().clone();
+    store
+        .get_or_create_session(
+            "s-1".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/tmp")),
+            None,
+            Status::Working,
+            5,
+        )
+        .await;
+    store
+        .create_session(
+            "s-2".to_string(),
+            AgentType::ClaudeCode,
+            Some(dir.path().to_path_buf()),
+            None,
+        )
+        .await
+        .expect("create s-2");
+    store.close_session("s-2").await;
+
+    tokio::spawn(async move { daemon.run().await });
+    (McpServer::new(socket, None), dir)
+}
+
+/// Sends a request and returns the parsed response.
+async fn call(server: &McpServer, method: &str, params: Value) -> Value {
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let line = server
+        .handle_line(&request.to_string())
+        .await
+        .expect("requests get a response");
+    serde_json::from_str(&line).expect("response is JSON")
+}
+
+#[tokio::test]
+async fn test_initialize_negotiates_protocol_version() {
+    let server = McpServer::new(PathBuf::from("/nonexistent.sock"), None);
+    let response = call(
+        &server,
+        "initialize",
+        json!({ "protocolVersion": "2024-11-05" }),
+    )
+    .await;
+    assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+    assert_eq!(response["result"]["serverInfo"]["name"], "acd");
+
+    let response = call(
+        &server,
+        "initialize",
+        json!({ "protocolVersion": "1999-01-01" }),
+    )
+    .await;
+    assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+}
+
+#[tokio::test]
+async fn test_notifications_and_errors() {
+    let server = McpServer::new(PathBuf::from("/nonexistent.sock"), None);
+    let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert_eq!(server.handle_line(&initialized.to_string()).await, None);
+
+    let response = call(&server, "bogus/method", json!({})).await;
+    assert_eq!(response["error"]["code"], code::METHOD_NOT_FOUND);
+
+    let line = server.handle_line("{not json").await.expect("response");
+    let response: Value = serde_json::from_str(&line).expect("JSON");
+    assert_eq!(response["error"]["code"], code::PARSE_ERROR);
+    assert_eq!(response["id"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_tools_list_names() {
+    let server = McpServer::new(PathBuf::from("/nonexistent.sock"), None);
+    let response = call(&server, "tools/list", json!({})).await;
+    let names: Vec<&str> = response["result"]["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(names, vec!["list_sessions", "set_status", "resurrect"]);
+}
+
+#[tokio::test]
+async fn test_resources_list_and_read() {
+    let (server, _dir) = server().await;
+    let response = call(&server, "resources/list", json!({})).await;
+    let uris: Vec<&str> = response["result"]["resources"]
+        .as_array()
+        .expect("resources")
+        .iter()
+        .filter_map(|r| r["uri"].as_str())
+        .collect();
+    assert!(uris.contains(&"acd://sessions"));
+    assert!(uris.contains(&"acd://sessions/s-1"));
+
+    let response = call(
+        &server,
+        "resources/read",
+        json!({ "uri": "acd://sessions/s-1" }),
+    )
+    .await;
+    let text = response["result"]["contents"][0]["text"]
+        .as_str()
+        .expect("text");
+    let snapshot: Value = serde_json::from_str(text).expect("snapshot JSON");
+    assert_eq!(snapshot["session_id"], "s-1");
+    assert_eq!(snapshot["status"], "working");
+
+    let response = call(
+        &server,
+        "resources/read",
+        json!({ "uri": "acd://sessions/missing" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], code::RESOURCE_NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_set_status_keeps_priority_and_rejects_unknown_sessions() {
+    let (server, _dir) = server().await;
+    let response = call(
+        &server,
+        "tools/call",
+        json!({ "name": "set_status", "arguments": { "session_id": "s-1", "status": "question" } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], false);
+
+    let response = call(
+        &server,
+        "resources/read",
+        json!({ "uri": "acd://sessions/s-1" }),
+    )
+    .await;
+    let text = response["result"]["contents"][0]["text"]
+        .as_str()
+        .expect("text");
+    let snapshot: Value = serde_json::from_str(text).expect("snapshot JSON");
+    assert_eq!(snapshot["status"], "question");
+    assert_eq!(snapshot["priority"], 5);
+
+    // Unknown sessions are a tool error, and are not created
+    let response = call(
+        &server,
+        "tools/call",
+        json!({ "name": "set_status", "arguments": { "session_id": "nope", "status": "working" } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], true);
+    let sessions = server.list_sessions().await.expect("list");
+    assert!(sessions.iter().all(|s| s.session_id != "nope"));
+
+    // A bad status is malformed input
+    let response = call(
+        &server,
+        "tools/call",
+        json!({ "name": "set_status", "arguments": { "session_id": "s-1", "status": "asleep" } }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], code::INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_resurrect_without_terminal_returns_resume_command() {
+    let (server, dir) = server().await;
+    let response = call(
+        &server,
+        "tools/call",
+        json!({ "name": "resurrect", "arguments": { "session_id": "s-2" } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], false);
+    let text = response["result"]["content"][0]["text"]
+        .as_str()
+        .expect("text");
+    assert!(text.contains("claude --resume s-2"), "{}", text);
+    assert!(text.contains(&dir.path().display().to_string()), "{}", text);
+
+    // Already reopened: the daemon's error is passed on as a tool error
+    let response = call(
+        &server,
+        "tools/call",
+        json!({ "name": "resurrect", "arguments": { "session_id": "s-2" } }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], true);
+}
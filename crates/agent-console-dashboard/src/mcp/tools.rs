@@ -0,0 +1,165 @@
+//! MCP tools: listing sessions, setting status, resurrecting.
+//!
+//! Failures of a tool itself (unknown session, daemon error) are tool results
+//! with `isError` set, so the model sees them; only malformed calls are
+//! JSON-RPC errors.
+
+use serde_json::{json, Value};
+
+use super::McpServer;
+use crate::integrations::{run, session_from_snapshot};
+use crate::jsonrpc::{str_arg, RpcError};
+use crate::{IpcCommand, IpcCommandKind, SessionSnapshot, Status};
+
+/// Result of `tools/list`.
+pub(super) fn definitions() -> Value {
+    let session_id = json!({ "type": "string", "description": "Session ID" });
+    json!({
+        "tools": [
+            {
+                "name": "list_sessions",
+                "description": "List every agent session tracked by acd with its status, \
+                    working directory and time in the current status.",
+                "inputSchema": { "type": "object", "properties": {} },
+            },
+            {
+                "name": "set_status",
+                "description": "Set the status of an agent session.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "session_id": session_id,
+                        "status": {
+                            "type": "string",
+                            "enum": ["working", "attention", "question", "closed"],
+                        },
+                    },
+                    "required": ["session_id", "status"],
+                },
+            },
+            {
+                "name": "resurrect",
+                "description": "Reopen a closed agent session and resume it in its \
+                    agent's CLI (`claude --resume`, `codex resume`, `gemini --resume`, \
+                    `aider --restore-chat-history`, or a custom agent's configured command) \
+                    in a new terminal pane.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": { "session_id": session_id },
+                    "required": ["session_id"],
+                },
+            },
+        ]
+    })
+}
+
+/// A successful tool result with `text` content.
+fn text_result(text: impl Into<String>) -> Value {
+    json!({ "content": [{ "type": "text", "text": text.into() }], "isError": false })
+}
+
+/// A failed tool result explaining `message`.
+fn error_result(message: impl Into<String>) -> Value {
+    json!({ "content": [{ "type": "text", "text": message.into() }], "isError": true })
+}
+
+impl McpServer {
+    /// Result of `tools/call`.
+    pub(super) async fn call_tool(&self, params: &Value) -> Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::invalid_params("tools/call requires name"))?;
+        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+        let result = match name {
+            "list_sessions" => self.list_sessions().await.map(|sessions| {
+                serde_json::to_string_pretty(&sessions).expect("failed to serialize sessions")
+            }),
+            "set_status" => {
+                let session_id = str_arg(&arguments, "session_id")?;
+                let status: Status = str_arg(&arguments, "status")?
+                    .parse()
+                    .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+                self.set_status(session_id, status).await
+            }
+            "resurrect" => {
+                let session_id = str_arg(&arguments, "session_id")?;
+                self.resurrect(session_id).await
+            }
+            _ => return Err(RpcError::invalid_params(format!("unknown tool: {}", name))),
+        };
+        Ok(match result {
+            Ok(text) => text_result(text),
+            Err(message) => error_result(message),
+        })
+    }
+
+    /// Sets the status of an existing session, keeping its priority.
+    async fn set_status(&self, session_id: &str, status: Status) -> Result<String, String> {
+        // SET creates unknown sessions and resets the priority, so look the
+        // session up first
+        let current = self.get_session(session_id).await?;
+        let mut command = IpcCommand::new(IpcCommandKind::Set, Some(session_id));
+        command.status = Some(status.to_string());
+        command.priority = Some(current.priority);
+        self.daemon(command).await?;
+        Ok(format!("Session {} is now {}", session_id, status))
+    }
+
+    /// Reopens a closed session and opens a pane resuming it.
+    async fn resurrect(&self, session_id: &str) -> Result<String, String> {
+        let data = self
+            .daemon(IpcCommand::new(IpcCommandKind::Reopen, Some(session_id)))
+            .await?
+            .ok_or_else(|| "daemon returned no session".to_string())?;
+        let snapshot: SessionSnapshot =
+            serde_json::from_value(data).map_err(|e| format!("invalid session: {}", e))?;
+        let session = session_from_snapshot(&snapshot);
+        let agent = self.agents.get(&session.agent_type);
+        let Some(resume) = agent.resume_command(&session) else {
+            return Ok(format!(
+                "Reopened session {}. {} cannot resume sessions; start it again in {}",
+                session_id,
+                agent.display_name(),
+                snapshot.working_dir.as_deref().unwrap_or(".")
+            ));
+        };
+
+        let command = self.terminal.as_ref().map(|terminal| {
+            (
+                terminal.name(),
+                terminal.resurrect_command(&session, &resume),
+            )
+        });
+        match command {
+            Some((name, command)) => {
+                tokio::task::spawn_blocking(move || run(command, name))
+                    .await
+                    .map_err(|e| format!("failed to open pane: {}", e))?
+                    .map_err(|e| format!("failed to open {} pane: {}", name, e))?;
+                Ok(format!(
+                    "Reopened session {} in a new {} pane",
+                    session_id, name
+                ))
+            }
+            None => {
+                let dir = snapshot.working_dir.as_deref().unwrap_or(".");
+                Ok(format!(
+                    "Reopened session {}. No supported terminal found; resume it with \
+                     `cd {} && {}`",
+                    session_id,
+                    dir,
+                    resume.join(" ")
+                ))
+            }
+        }
+    }
+
+    async fn get_session(&self, session_id: &str) -> Result<SessionSnapshot, String> {
+        let data = self
+            .daemon(IpcCommand::new(IpcCommandKind::Get, Some(session_id)))
+            .await?
+            .ok_or_else(|| format!("session not found: {}", session_id))?;
+        serde_json::from_value(data).map_err(|e| format!("invalid session: {}", e))
+    }
+}
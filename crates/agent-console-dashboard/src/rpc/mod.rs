@@ -0,0 +1,301 @@
+//! JSON-RPC 2.0 bridge for editor extensions (`acd rpc`).
+//!
+//! A thin facade over the daemon's IPC protocol for editor plugins (VS Code,
+//! Neovim) that want the dashboard in a sidebar without speaking the IPC
+//! wire format. Messages are newline-delimited JSON-RPC 2.0 on stdio, or on a
+//! Unix socket with `acd rpc --listen <path>`.
+//!
+//! Each method maps to one IPC command, and `subscribe` turns the daemon's
+//! SUB stream into JSON-RPC notifications. The method reference is in
+//! `docs/user/json-rpc.md`.
+
+#[cfg(test)]
+mod tests;
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::client::connect_with_lazy_start;
+use crate::jsonrpc::{code, notification, response, Request, RpcError};
+use crate::{IpcCommand, IpcCommandKind, IpcNotification, IpcResponse};
+
+/// Serves JSON-RPC clients from the daemon at `socket`.
+#[derive(Debug, Clone)]
+pub struct RpcBridge {
+    socket: PathBuf,
+}
+
+impl RpcBridge {
+    /// Creates a bridge to the daemon at `socket`. The daemon is started on
+    /// the first request if it is not running.
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    /// Serves one client on stdin/stdout until stdin closes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading stdin fails.
+    pub async fn serve_stdio(&self) -> std::io::Result<()> {
+        self.serve(tokio::io::stdin(), tokio::io::stdout()).await
+    }
+
+    /// Accepts clients on a Unix socket at `path`, serving each concurrently.
+    /// A stale socket file from an earlier run is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AddrInUse` if another bridge is listening at `path`, or the
+    /// bind/accept error.
+    pub async fn listen(&self, path: &Path) -> std::io::Result<()> {
+        if path.exists() {
+            if UnixStream::connect(path).await.is_ok() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AddrInUse,
+                    format!("another process is listening on {}", path.display()),
+                ));
+            }
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let bridge = self.clone();
+            tokio::spawn(async move {
+                let (reader, writer) = stream.into_split();
+                if let Err(e) = bridge.serve(reader, writer).await {
+                    tracing::debug!("rpc client disconnected: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Serves one client until `reader` reaches EOF.
+    ///
+    /// Requests are answered in order. Notifications from an active
+    /// subscription are interleaved with responses on `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the client fails.
+    pub async fn serve<R, W>(&self, reader: R, writer: W) -> std::io::Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (out_tx, out_rx) = mpsc::unbounded_channel();
+        let writer_task = tokio::spawn(write_messages(out_rx, writer));
+        let mut subscription: Option<JoinHandle<()>> = None;
+
+        let mut lines = BufReader::new(reader).lines();
+        let result = loop {
+            let line = match lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let request = match Request::parse(&line) {
+                Ok(request) => request,
+                Err(error) => {
+                    let _ = out_tx.send(error);
+                    continue;
+                }
+            };
+
+            let reply = |result| {
+                if let Some(id) = request.id.clone() {
+                    let _ = out_tx.send(response(id, result));
+                }
+            };
+            match request.method.as_str() {
+                "subscribe" => match self.subscribe().await {
+                    Ok(connection) => {
+                        // Reply before the first notification can be sent
+                        reply(Ok(json!(null)));
+                        if let Some(old) = subscription.take() {
+                            old.abort();
+                        }
+                        subscription =
+                            Some(tokio::spawn(forward_updates(connection, out_tx.clone())));
+                    }
+                    Err(error) => reply(Err(error)),
+                },
+                "unsubscribe" => {
+                    if let Some(task) = subscription.take() {
+                        task.abort();
+                    }
+                    reply(Ok(json!(null)));
+                }
+                _ => reply(self.call(&request).await),
+            }
+        };
+
+        if let Some(task) = subscription {
+            task.abort();
+        }
+        drop(out_tx);
+        let _ = writer_task.await;
+        result
+    }
+
+    /// Handles a request that maps to one IPC command.
+    async fn call(&self, request: &Request) -> Result<Value, RpcError> {
+        let command = match request.method.as_str() {
+            "sessions/list" => IpcCommand::new(IpcCommandKind::List, None),
+            "sessions/get" => session_command(IpcCommandKind::Get, request)?,
+            "sessions/set" => {
+                let mut command = session_command(IpcCommandKind::Set, request)?;
+                command.status = Some(request.str_param("status")?.to_string());
+                command.working_dir = optional_str(&request.params, "working_dir")?;
+                command.priority = match request.params.get("priority") {
+                    None | Some(Value::Null) => None,
+                    Some(value) => Some(value.as_u64().ok_or_else(|| {
+                        RpcError::invalid_params("'priority' must be a non-negative integer")
+                    })?),
+                };
+                command
+            }
+            "sessions/rename" => {
+                let mut command = session_command(IpcCommandKind::Rename, request)?;
+                command.display_name = optional_str(&request.params, "display_name")?;
+                command
+            }
+            "sessions/close" => session_command(IpcCommandKind::Rm, request)?,
+            "sessions/reopen" => session_command(IpcCommandKind::Reopen, request)?,
+            "sessions/delete" => session_command(IpcCommandKind::Delete, request)?,
+            "daemon/status" => IpcCommand::new(IpcCommandKind::Status, None),
+            "daemon/dump" => IpcCommand::new(IpcCommandKind::Dump, None),
+            "ping" => return Ok(json!({})),
+            method => {
+                return Err(RpcError::new(
+                    code::METHOD_NOT_FOUND,
+                    format!("method not found: {}", method),
+                ))
+            }
+        };
+
+        let response = crate::client::request(&self.socket, &command)
+            .await
+            .map_err(|e| daemon_error(format!("cannot reach the acd daemon: {}", e)))?;
+        if response.ok {
+            Ok(response.data.unwrap_or(Value::Null))
+        } else {
+            Err(daemon_error(
+                response
+                    .error
+                    .unwrap_or_else(|| "unknown daemon error".to_string()),
+            ))
+        }
+    }
+
+    /// Opens a SUB connection and returns it once the daemon has acknowledged
+    /// the subscription.
+    async fn subscribe(&self) -> Result<Subscription, RpcError> {
+        let connection_error = |e: &dyn std::fmt::Display| {
+            daemon_error(format!("cannot subscribe to the acd daemon: {}", e))
+        };
+        let client = connect_with_lazy_start(&self.socket)
+            .await
+            .map_err(|e| connection_error(&e))?;
+        let (reader, mut writer) = client.into_stream().into_split();
+        let json = serde_json::to_string(&IpcCommand::new(IpcCommandKind::Sub, None))
+            .expect("failed to serialize SUB command");
+        writer
+            .write_all(format!("{}\n", json).as_bytes())
+            .await
+            .map_err(|e| connection_error(&e))?;
+
+        let mut lines = BufReader::new(reader).lines();
+        let ack = lines
+            .next_line()
+            .await
+            .map_err(|e| connection_error(&e))?
+            .ok_or_else(|| connection_error(&"connection closed"))?;
+        match serde_json::from_str::<IpcResponse>(&ack) {
+            Ok(response) if response.ok => Ok((lines, writer)),
+            Ok(response) => Err(daemon_error(response.error.unwrap_or_default())),
+            Err(e) => Err(connection_error(&e)),
+        }
+    }
+}
+
+/// An acknowledged SUB connection: its notification lines, and the write half
+/// that keeps the connection open.
+type Subscription = (Lines<BufReader<OwnedReadHalf>>, OwnedWriteHalf);
+
+/// IPC `kind` command for the request's required `session_id`.
+fn session_command(kind: IpcCommandKind, request: &Request) -> Result<IpcCommand, RpcError> {
+    Ok(IpcCommand::new(
+        kind,
+        Some(request.str_param("session_id")?),
+    ))
+}
+
+/// Optional string parameter `name` (absent or null is `None`).
+fn optional_str(params: &Value, name: &str) -> Result<Option<String>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(RpcError::invalid_params(format!(
+            "'{}' must be a string",
+            name
+        ))),
+    }
+}
+
+fn daemon_error(message: impl Into<String>) -> RpcError {
+    RpcError::new(code::DAEMON_ERROR, message)
+}
+
+/// Turns a daemon SUB notification into a JSON-RPC notification.
+///
+/// Returns `None` for unknown notification types.
+pub(crate) fn to_notification(ipc: IpcNotification) -> Option<Value> {
+    let (method, params) = match ipc.notification_type.as_str() {
+        "update" => ("sessions/updated", serde_json::to_value(ipc.session?).ok()?),
+        "usage" => ("usage/updated", ipc.usage?),
+        "usage_blocked" => ("usage/blocked", json!({})),
+        "warn" => ("daemon/warning", json!({ "message": ipc.message? })),
+        _ => return None,
+    };
+    Some(notification(method, params))
+}
+
+/// Forwards SUB notifications to the client until the daemon closes the
+/// stream, then sends `daemon/disconnected`.
+async fn forward_updates((mut lines, _writer): Subscription, out: mpsc::UnboundedSender<Value>) {
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(ipc) = serde_json::from_str::<IpcNotification>(&line) else {
+            continue;
+        };
+        if let Some(message) = to_notification(ipc) {
+            if out.send(message).is_err() {
+                return;
+            }
+        }
+    }
+    let _ = out.send(notification("daemon/disconnected", json!({})));
+}
+
+/// Writes queued messages, one per line, until every sender is dropped.
+async fn write_messages<W>(mut rx: mpsc::UnboundedReceiver<Value>, mut writer: W)
+where
+    W: AsyncWrite + Unpin,
+{
+    while let Some(message) = rx.recv().await {
+        let line = format!("{}\n", message);
+        if writer.write_all(line.as_bytes()).await.is_err() || writer.flush().await.is_err() {
+            return;
+        }
+    }
+}
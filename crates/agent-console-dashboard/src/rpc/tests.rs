@@ -0,0 +1,182 @@
+//! Tests for the JSON-RPC bridge against a real daemon socket.
+
+use super::*;
+use crate::daemon::{SessionStore, SocketServer};
+use crate::{AgentType, SessionSnapshot, Status};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{DuplexStream, ReadHalf, WriteHalf};
+
+/// Client end of a bridge served over an in-memory stream.
+struct TestClient {
+    lines: Lines<BufReader<ReadHalf<DuplexStream>>>,
+    writer: WriteHalf<DuplexStream>,
+    next_id: u64,
+}
+
+impl TestClient {
+    async fn send(&mut self, message: Value) {
+        let line = format!("{}\n", message);
+        self.writer
+            .write_all(line.as_bytes())
+            .await
+            .expect("write request");
+    }
+
+    /// Sends a request and returns its response, skipping notifications.
+    async fn call(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await;
+        loop {
+            let message = self.recv().await;
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    async fn recv(&mut self) -> Value {
+        let line = tokio::time::timeout(Duration::from_secs(5), self.lines.next_line())
+            .await
+            .expect("message within timeout")
+            .expect("read message")
+            .expect("bridge still open");
+        serde_json::from_str(&line).expect("message is JSON")
+    }
+}
+
+/// Starts a daemon with session "s-1" and a bridge client connected to it.
+async fn setup() -> (TestClient, SessionStore, TempDir) {
+    let dir = TempDir::new().expect("temp dir");
+    let socket = dir.path().join("acd.sock");
+    let mut daemon = SocketServer::new(socket.display().to_string());
+    daemon.start().await.expect("start daemon");
+    let store = daemon.store().clone();
+    store
+        .create_session(
+            "s-1".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/tmp")),
+            None,
+        )
+        .await
+        .expect("create s-1");
+    tokio::spawn(async move { daemon.run().await });
+
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let (reader, writer) = tokio::io::split(server);
+    let bridge = RpcBridge::new(socket);
+    tokio::spawn(async move { bridge.serve(reader, writer).await });
+
+    let (reader, writer) = tokio::io::split(client);
+    let client = TestClient {
+        lines: BufReader::new(reader).lines(),
+        writer,
+        next_id: 0,
+    };
+    (client, store, dir)
+}
+
+#[tokio::test]
+async fn test_session_methods_map_to_ipc() {
+    let (mut client, _store, _dir) = setup().await;
+
+    let response = client.call("sessions/list", json!({})).await;
+    let sessions: Vec<SessionSnapshot> =
+        serde_json::from_value(response["result"].clone()).expect("session list");
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].session_id, "s-1");
+
+    let response = client
+        .call(
+            "sessions/set",
+            json!({ "session_id": "s-1", "status": "attention", "priority": 3 }),
+        )
+        .await;
+    assert_eq!(response["result"]["status"], "attention");
+    assert_eq!(response["result"]["priority"], 3);
+
+    let response = client
+        .call(
+            "sessions/rename",
+            json!({ "session_id": "s-1", "display_name": "api" }),
+        )
+        .await;
+    assert_eq!(response["result"]["display_name"], "api");
+
+    let response = client
+        .call("sessions/get", json!({ "session_id": "s-1" }))
+        .await;
+    assert_eq!(response["result"]["display_name"], "api");
+}
+
+#[tokio::test]
+async fn test_errors() {
+    let (mut client, _store, _dir) = setup().await;
+
+    let response = client
+        .call("sessions/get", json!({ "session_id": "missing" }))
+        .await;
+    assert_eq!(response["error"]["code"], code::DAEMON_ERROR);
+    assert!(response["error"]["message"]
+        .as_str()
+        .expect("message")
+        .contains("session not found"));
+
+    let response = client.call("sessions/get", json!({})).await;
+    assert_eq!(response["error"]["code"], code::INVALID_PARAMS);
+
+    let response = client
+        .call(
+            "sessions/set",
+            json!({ "session_id": "s-1", "status": "working", "priority": -1 }),
+        )
+        .await;
+    assert_eq!(response["error"]["code"], code::INVALID_PARAMS);
+
+    let response = client.call("sessions/explode", json!({})).await;
+    assert_eq!(response["error"]["code"], code::METHOD_NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_subscribe_pushes_session_updates() {
+    let (mut client, store, _dir) = setup().await;
+
+    let response = client.call("subscribe", json!({})).await;
+    assert_eq!(response["result"], Value::Null);
+
+    store.update_session("s-1", Status::Question).await;
+    let message = client.recv().await;
+    assert_eq!(message["method"], "sessions/updated");
+    assert_eq!(message["params"]["session_id"], "s-1");
+    assert_eq!(message["params"]["status"], "question");
+    assert!(message.get("id").is_none());
+
+    let response = client.call("unsubscribe", json!({})).await;
+    assert_eq!(response["result"], Value::Null);
+    store.update_session("s-1", Status::Working).await;
+    // The next message is the ping response, not a notification
+    client
+        .send(json!({ "jsonrpc": "2.0", "id": "p", "method": "ping" }))
+        .await;
+    assert_eq!(client.recv().await["id"], "p");
+}
+
+#[test]
+fn test_to_notification() {
+    let warn = IpcNotification::warn("disk full");
+    assert_eq!(
+        to_notification(warn),
+        Some(json!({
+            "jsonrpc": "2.0",
+            "method": "daemon/warning",
+            "params": { "message": "disk full" },
+        }))
+    );
+
+    let mut unknown = IpcNotification::warn("x");
+    unknown.notification_type = "mystery".to_string();
+    assert_eq!(to_notification(unknown), None);
+}
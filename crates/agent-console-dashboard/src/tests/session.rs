@@ -75,6 +75,7 @@ fn test_session_with_all_fields() {
 }
 
 #[test]
+#[allow(clippy::field_reassign_with_default)]
 fn test_session_field_mutability() {
     let mut session = Session::default();
     session.session_id = "updated-id".to_string();
@@ -346,3 +347,30 @@ fn test_session_debug_format() {
     assert!(debug_str.contains("debug-test"));
     assert!(debug_str.contains("ClaudeCode"));
 }
+
+#[test]
+fn test_terminal_pane_from_env() {
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+    assert_eq!(
+        TerminalPane::from_env(env(&[("TMUX_PANE", "%3")])),
+        Some(TerminalPane {
+            multiplexer: Multiplexer::Tmux,
+            id: "%3".to_string(),
+        })
+    );
+    // Zellij running inside tmux inherits TMUX_PANE
+    assert_eq!(
+        TerminalPane::from_env(env(&[("TMUX_PANE", "%3"), ("ZELLIJ_PANE_ID", "2")])),
+        Some(TerminalPane {
+            multiplexer: Multiplexer::Zellij,
+            id: "2".to_string(),
+        })
+    );
+    assert_eq!(TerminalPane::from_env(env(&[("TMUX_PANE", "")])), None);
+}
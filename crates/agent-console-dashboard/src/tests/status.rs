@@ -5,10 +5,7 @@ use crate::*;
 #[test]
 fn test_daemon_config_default() {
     let config = DaemonConfig::default();
-    assert_eq!(
-        config.socket_path,
-        PathBuf::from("/tmp/agent-console-dashboard.sock")
-    );
+    assert_eq!(config.socket_path, config::xdg::socket_path());
     assert!(!config.daemonize);
 }
 
@@ -34,6 +31,31 @@ fn test_agent_type_equality() {
     assert_eq!(AgentType::ClaudeCode, AgentType::ClaudeCode);
 }
 
+#[test]
+fn test_agent_type_wire_names_round_trip() {
+    for agent_type in [
+        AgentType::ClaudeCode,
+        AgentType::Codex,
+        AgentType::GeminiCli,
+        AgentType::Aider,
+        AgentType::Custom("devbot".to_string()),
+    ] {
+        assert_eq!(agent_type.to_string().parse(), Ok(agent_type));
+    }
+    assert_eq!(AgentType::Codex.to_string(), "codex");
+    assert_eq!("Claude-Code".parse(), Ok(AgentType::ClaudeCode));
+    assert_eq!("gemini-cli".parse(), Ok(AgentType::GeminiCli));
+    assert_eq!(
+        "cobol".parse::<AgentType>(),
+        Ok(AgentType::Custom("cobol".to_string()))
+    );
+    assert_eq!(
+        "not an agent".parse::<AgentType>(),
+        Err(ParseAgentTypeError("not an agent".to_string()))
+    );
+    assert_eq!(AgentType::Codex.label(), "Codex");
+}
+
 #[test]
 fn test_state_transition_creation() {
     let transition = StateTransition {
@@ -89,6 +111,22 @@ fn test_api_usage_copy() {
     assert_eq!(copied.output_tokens, usage.output_tokens);
 }
 
+#[test]
+fn test_api_usage_estimated_cost() {
+    use claude_usage::pricing::{ModelPricing, PricingTable};
+
+    let usage = ApiUsage {
+        input_tokens: 2_000_000,
+        output_tokens: 100_000,
+    };
+    let table = PricingTable::empty().with_model("claude-sonnet-4", ModelPricing::new(3.0, 15.0));
+    let cost = usage
+        .estimated_cost(&table, "claude-sonnet-4-5")
+        .expect("known model");
+    assert!((cost - 7.5).abs() < 1e-9, "{cost}");
+    assert_eq!(usage.estimated_cost(&table, "unknown"), None);
+}
+
 #[test]
 fn test_status_copy() {
     let status = Status::Working;
@@ -151,12 +189,11 @@ fn test_status_should_dim_question() {
 }
 
 #[test]
-fn test_agent_type_copy() {
-    let agent = AgentType::ClaudeCode;
-    let copied = agent;
-    // After copy, original should still be usable (Copy trait)
-    assert_eq!(agent, AgentType::ClaudeCode);
-    assert_eq!(copied, AgentType::ClaudeCode);
+fn test_agent_type_clone() {
+    let agent = AgentType::Custom("devbot".to_string());
+    let cloned = agent.clone();
+    assert_eq!(agent, cloned);
+    assert_eq!(cloned.label(), "devbot");
 }
 
 #[test]
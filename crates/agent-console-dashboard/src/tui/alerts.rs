@@ -0,0 +1,150 @@
+//! Sticky alerts for attention and question events.
+//!
+//! A session can flip to `attention` and back to `working` between two
+//! glances at the dashboard. The alert log records every transition into
+//! `attention` or `question` and keeps it until the user acknowledges it,
+//! regardless of what the session's status is now.
+
+use crate::Status;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+
+/// Maximum number of unacknowledged alerts kept; the oldest are dropped first.
+pub const MAX_ALERTS: usize = 50;
+
+/// A recorded attention or question event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    /// Session that raised the alert.
+    pub session_id: String,
+    /// Session label at the time of the event (display name or directory).
+    pub label: String,
+    /// Status the session entered (`Attention` or `Question`).
+    pub status: Status,
+    /// Local wall-clock time of the event.
+    pub raised_at: DateTime<Local>,
+}
+
+/// Unacknowledged alerts, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct AlertLog {
+    alerts: VecDeque<Alert>,
+}
+
+impl AlertLog {
+    /// Creates an empty alert log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if `status` should raise an alert.
+    pub fn is_alert_status(status: Status) -> bool {
+        matches!(status, Status::Attention | Status::Question)
+    }
+
+    /// Records an alert if `status` is an alert status. Returns true if recorded.
+    ///
+    /// When the log is full, the oldest alert is dropped.
+    pub fn record(&mut self, session_id: &str, label: &str, status: Status) -> bool {
+        if !Self::is_alert_status(status) {
+            return false;
+        }
+        if self.alerts.len() == MAX_ALERTS {
+            self.alerts.pop_front();
+        }
+        self.alerts.push_back(Alert {
+            session_id: session_id.to_string(),
+            label: label.to_string(),
+            status,
+            raised_at: Local::now(),
+        });
+        true
+    }
+
+    /// Acknowledges (removes) the alert at `index`. Returns the removed alert.
+    pub fn acknowledge(&mut self, index: usize) -> Option<Alert> {
+        self.alerts.remove(index)
+    }
+
+    /// Acknowledges the oldest alert.
+    pub fn acknowledge_oldest(&mut self) -> Option<Alert> {
+        self.alerts.pop_front()
+    }
+
+    /// Acknowledges every alert. Returns how many were removed.
+    pub fn acknowledge_all(&mut self) -> usize {
+        let count = self.alerts.len();
+        self.alerts.clear();
+        count
+    }
+
+    /// Returns the number of unacknowledged alerts.
+    pub fn len(&self) -> usize {
+        self.alerts.len()
+    }
+
+    /// Returns true if there are no unacknowledged alerts.
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty()
+    }
+
+    /// Iterates over unacknowledged alerts, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_only_alert_statuses() {
+        let mut log = AlertLog::new();
+        assert!(log.record("a", "proj", Status::Attention));
+        assert!(log.record("b", "proj", Status::Question));
+        assert!(!log.record("c", "proj", Status::Working));
+        assert!(!log.record("d", "proj", Status::Closed));
+        assert_eq!(log.len(), 2);
+    }
+
+    #[test]
+    fn test_alerts_survive_repeated_events() {
+        let mut log = AlertLog::new();
+        log.record("a", "proj", Status::Attention);
+        log.record("a", "proj", Status::Attention);
+        assert_eq!(log.len(), 2, "each event is kept until acknowledged");
+    }
+
+    #[test]
+    fn test_acknowledge_oldest_first() {
+        let mut log = AlertLog::new();
+        log.record("a", "first", Status::Attention);
+        log.record("b", "second", Status::Question);
+        let acked = log.acknowledge_oldest().expect("one alert");
+        assert_eq!(acked.session_id, "a");
+        assert_eq!(log.iter().next().map(|a| a.label.as_str()), Some("second"));
+    }
+
+    #[test]
+    fn test_acknowledge_by_index_and_all() {
+        let mut log = AlertLog::new();
+        log.record("a", "a", Status::Attention);
+        log.record("b", "b", Status::Attention);
+        log.record("c", "c", Status::Attention);
+        assert_eq!(log.acknowledge(1).map(|a| a.session_id), Some("b".into()));
+        assert!(log.acknowledge(5).is_none());
+        assert_eq!(log.acknowledge_all(), 2);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut log = AlertLog::new();
+        for i in 0..=MAX_ALERTS {
+            log.record(&format!("s{i}"), "proj", Status::Attention);
+        }
+        assert_eq!(log.len(), MAX_ALERTS);
+        assert_eq!(log.iter().next().map(|a| a.session_id.as_str()), Some("s1"));
+    }
+}
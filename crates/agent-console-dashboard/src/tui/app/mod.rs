@@ -4,19 +4,37 @@
 
 mod update;
 
+use crate::agents::AgentRegistry;
+use crate::config::error::ConfigError;
+use crate::config::schema::{Config, HookConfig, TimeFormat};
+use crate::integrations::github::{self, BranchInfo, PullRequest};
+use crate::integrations::hooks;
+use crate::tui::alerts::AlertLog;
+use crate::tui::embedded::EmbeddedTerminal;
 use crate::tui::event::{handle_key_event, Action, Event, EventHandler};
-use crate::tui::subscription::{subscribe_to_daemon, DaemonMessage};
+use crate::tui::github::GithubLookups;
+use crate::tui::icons::{terminal_title, StatusIcons};
+use crate::tui::palette::{render_command, CommandPalette};
+use crate::tui::projects::ProjectConfigs;
+use crate::tui::subscription::{send_rename, subscribe_to_daemon, DaemonMessage};
+use crate::tui::tabs::{TabState, WorkspaceTabs};
+use crate::tui::text_input::TextInput;
+use crate::tui::theme::Theme;
 use crate::tui::ui::render_dashboard;
-use crate::{AgentType, Session, Status};
+use crate::tui::usage_history::UsageHistory;
+use crate::{Session, Status};
 use claude_usage::UsageData;
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture, EventStream},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
 use ratatui::layout::Rect;
 use ratatui::prelude::{CrosstermBackend, Terminal};
+use std::collections::BTreeMap;
 use std::io::{self, stdout};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
@@ -26,33 +44,66 @@ use tokio::sync::mpsc;
 ///
 /// This throttles background elapsed-time updates to 1 second, reducing CPU
 /// usage at scale (100 sessions × 100 TUIs = 10,000 calculations per tick).
-/// User input events (keyboard, mouse) bypass this throttle and render immediately.
+/// User input events (keyboard, mouse) and daemon messages bypass this throttle
+/// and render immediately. With no sessions shown, the refresh is skipped.
 const ELAPSED_TIME_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
 
+/// Tick interval when `tui.tick_rate` is missing or invalid.
+const DEFAULT_TICK_RATE: Duration = Duration::from_millis(250);
+
 /// Terminal height threshold for TwoLine layout mode.
 ///
 /// When terminal height is less than this value, TwoLine mode is automatically
-/// selected. Otherwise, Large mode is used.
+/// selected.
 pub const TWO_LINE_LAYOUT_HEIGHT_THRESHOLD: u16 = 7;
 
+/// Terminal height threshold for Medium layout mode.
+///
+/// When terminal height is at least `TWO_LINE_LAYOUT_HEIGHT_THRESHOLD` but less
+/// than this value, Medium mode is automatically selected. Otherwise, Large mode
+/// is used. The Large layout needs ~13 rows before its 12-row detail panel stops
+/// crowding out the session list.
+pub const MEDIUM_LAYOUT_HEIGHT_THRESHOLD: u16 = 13;
+
 /// Layout mode for the TUI rendering.
 ///
 /// Controls how the dashboard renders based on available screen space:
 /// - `Large`: Full multi-line layout with header, session list, detail panel, footer
+/// - `Medium`: Condensed header, session list, footer (no detail panel)
 /// - `TwoLine`: Compact layout with session chips (line 1) and API usage (line 2)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LayoutMode {
     /// Full dashboard layout with session list and detail panel.
     Large,
+    /// Session list only, with a condensed header and no detail panel.
+    Medium,
     /// Compact two-line layout for narrow terminals.
     TwoLine,
 }
 
+impl LayoutMode {
+    /// Selects the layout mode for the given terminal height.
+    ///
+    /// - height < `TWO_LINE_LAYOUT_HEIGHT_THRESHOLD` → `TwoLine`
+    /// - height < `MEDIUM_LAYOUT_HEIGHT_THRESHOLD` → `Medium`
+    /// - otherwise → `Large`
+    pub fn for_height(height: u16) -> Self {
+        if height < TWO_LINE_LAYOUT_HEIGHT_THRESHOLD {
+            LayoutMode::TwoLine
+        } else if height < MEDIUM_LAYOUT_HEIGHT_THRESHOLD {
+            LayoutMode::Medium
+        } else {
+            LayoutMode::Large
+        }
+    }
+}
+
 /// Active view state for the TUI.
 ///
-/// Deprecated: detail panel is now always visible. This enum is kept for
-/// backward compatibility but only Dashboard variant is used. The history
-/// scroll offset is tracked directly in App.history_scroll.
+/// The detail panel is always visible in the dashboard, so `Detail` is kept
+/// only for backward compatibility. `UsageChart` replaces the dashboard with
+/// the full-screen usage history chart. The history scroll offset is tracked
+/// directly in App.history_scroll.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum View {
     /// Main dashboard showing session list with always-visible detail panel.
@@ -64,6 +115,20 @@ pub enum View {
         /// Scroll offset for history entries.
         history_scroll: usize,
     },
+    /// Full-screen API usage history chart.
+    UsageChart {
+        /// Selected sample index (0 = oldest), or None to follow the latest.
+        cursor: Option<usize>,
+    },
+}
+
+/// In-progress inline rename of a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameState {
+    /// ID of the session being renamed.
+    pub session_id: String,
+    /// Text input holding the new display name.
+    pub input: TextInput,
 }
 
 /// Target of a mouse click in TwoLine layout mode.
@@ -88,8 +153,17 @@ pub struct App {
     pub socket_path: PathBuf,
     /// Count of ticks processed (useful for testing/diagnostics).
     pub tick_count: u64,
-    /// Active and closed sessions displayed in the dashboard.
+    /// Active and closed sessions displayed in the dashboard (active tab only).
     pub sessions: Vec<Session>,
+    /// Every session received from the daemon, in sort order.
+    ///
+    /// `sessions` is rebuilt from this list whenever it changes or the active
+    /// workspace tab switches.
+    pub all_sessions: Vec<Session>,
+    /// Project workspace tabs, switched with `gt`/`gT`/`g0`-`g9`.
+    pub tabs: WorkspaceTabs,
+    /// Whether `g` was pressed and the next key completes a tab command.
+    pub pending_g: bool,
     /// Currently selected session index in the list.
     pub selected_index: Option<usize>,
     /// Current active view (deprecated, always Dashboard now).
@@ -114,6 +188,35 @@ pub struct App {
     ///
     /// Loaded from `tui.reopen_hooks` in config. Empty means no hook configured.
     pub reopen_hooks: Vec<crate::config::schema::HookConfig>,
+    /// Custom commands listed in the command palette (`tui.commands`).
+    pub session_commands: Vec<crate::config::schema::SessionCommandConfig>,
+    /// Named hooks (`[hooks]`) that hook entries and `key_hooks` refer to.
+    pub hook_library: BTreeMap<String, HookConfig>,
+    /// Extra dashboard keys (`tui.keys`) and the names of the hooks they run.
+    pub key_hooks: BTreeMap<char, String>,
+    /// Open command palette, if any. While set, key input goes to it.
+    pub palette: Option<CommandPalette>,
+    /// Terminal backend used when no activate/reopen hooks are configured.
+    ///
+    /// Detected at startup from the environment and `[integrations]`.
+    pub terminal: Option<Box<dyn crate::integrations::TerminalBackend>>,
+    /// Also bring the terminal app to the front after focusing a session's
+    /// pane (`integrations.raise_window`).
+    pub raise_window: bool,
+    /// Resume closed sessions in the embedded terminal pane when no
+    /// `reopen_hooks` are configured (`tui.embedded_terminal`).
+    pub embedded_terminal: bool,
+    /// Session running in the embedded terminal pane, if any.
+    pub embedded: Option<EmbeddedTerminal>,
+    /// Whether key input goes to the embedded terminal (toggled with Ctrl+]).
+    pub embedded_focused: bool,
+    /// Area of the embedded terminal pane, updated during each render pass.
+    pub embedded_area: Option<Rect>,
+    /// Wakes the event loop when the embedded terminal prints, once connected.
+    embedded_output: Option<mpsc::UnboundedSender<()>>,
+    /// Failure messages of background commands and hooks, shown in the
+    /// footer once connected.
+    command_failures: Option<mpsc::UnboundedSender<String>>,
     /// Temporary status message shown in footer, with expiry time.
     pub status_message: Option<(String, Instant)>,
     /// Last time elapsed-time rendering occurred (for throttling passive updates).
@@ -123,10 +226,25 @@ pub struct App {
     /// Updated during each render pass. Used by mouse click detection to accurately
     /// map click coordinates to session indices. None if the list hasn't been rendered yet.
     pub session_list_inner_area: Option<Rect>,
-    /// Current layout mode (Large or TwoLine).
+    /// Inner area of the alerts panel, or None if it was not rendered.
     ///
-    /// Auto-detected based on terminal height during render. TwoLine mode is used
-    /// when height < TWO_LINE_LAYOUT_HEIGHT_THRESHOLD.
+    /// Updated during each render pass. Used to acknowledge an alert on click.
+    pub alerts_inner_area: Option<Rect>,
+    /// Unacknowledged attention/question events, shown in the alerts panel.
+    pub alerts: AlertLog,
+    /// Usage samples received from the daemon, plotted by the usage chart.
+    pub usage_history: UsageHistory,
+    /// Index of the session row or chip under the mouse pointer, if any.
+    pub hovered_index: Option<usize>,
+    /// Last mouse pointer position as (column, row), used to place the tooltip.
+    pub hover_position: Option<(u16, u16)>,
+    /// Show a tooltip with the full working directory and session ID on hover
+    /// (`tui.hover_tooltips`).
+    pub hover_tooltips: bool,
+    /// Current layout mode (Large, Medium, or TwoLine).
+    ///
+    /// Auto-detected based on terminal height during render via
+    /// [`LayoutMode::for_height`].
     pub layout_mode: LayoutMode,
     /// Layout mode override from CLI flag.
     ///
@@ -142,6 +260,37 @@ pub struct App {
     ///
     /// Used by mouse click detection in TwoLine mode to calculate chip positions.
     pub terminal_width: u16,
+    /// Status icons resolved from `[tui.icons]` config (ASCII by default).
+    pub icons: StatusIcons,
+    /// Color theme resolved from `tui.theme`.
+    pub theme: Theme,
+    /// Agent adapters from `[[agents.custom]]` config, used to resume closed
+    /// sessions.
+    pub agents: AgentRegistry,
+    /// Pull requests of selected sessions' branches, from
+    /// `[integrations.github]`.
+    pub github: GithubLookups,
+    /// Per-project overrides (`.acd.toml`) by working directory.
+    pub projects: ProjectConfigs,
+    /// Active inline rename, if any. While set, key input goes to the text input.
+    pub rename: Option<RenameState>,
+    /// Footer template from `tui.footer`. Empty uses the built-in footer.
+    pub footer_template: String,
+    /// Elapsed-time display mode from `tui.time_format`, toggled with `t`.
+    pub time_format: TimeFormat,
+    /// Read-only mode (`--read-only` or `tui.read_only`).
+    ///
+    /// Hooks, rename, remove, and resurrect are disabled with a footer message.
+    pub read_only: bool,
+    /// Read-only mode from `--read-only`, which config reloads cannot turn off.
+    pub read_only_flag: bool,
+    /// Interval of tick events (`tui.tick_rate`).
+    pub tick_rate: Duration,
+    /// Whether state changed since the last render.
+    ///
+    /// Set by input, daemon messages, resizes, status message expiry, and the
+    /// elapsed-time refresh. The event loop only redraws when this is set.
+    pub dirty: bool,
 }
 
 impl App {
@@ -162,6 +311,9 @@ impl App {
             socket_path,
             tick_count: 0,
             sessions: Vec::new(),
+            all_sessions: Vec::new(),
+            tabs: WorkspaceTabs::new(),
+            pending_g: false,
             selected_index: None,
             view: View::Dashboard,
             history_scroll: 0,
@@ -171,13 +323,43 @@ impl App {
             last_click: None,
             activate_hooks: Vec::new(),
             reopen_hooks: Vec::new(),
+            session_commands: Vec::new(),
+            hook_library: BTreeMap::new(),
+            key_hooks: BTreeMap::new(),
+            palette: None,
+            terminal: None,
+            raise_window: true,
+            embedded_terminal: false,
+            embedded: None,
+            embedded_focused: false,
+            embedded_area: None,
+            embedded_output: None,
+            command_failures: None,
             status_message: None,
             last_elapsed_render: Instant::now(),
             session_list_inner_area: None,
+            alerts_inner_area: None,
+            alerts: AlertLog::new(),
+            usage_history: UsageHistory::new(),
+            hovered_index: None,
+            hover_position: None,
+            hover_tooltips: true,
             layout_mode: initial_mode,
             layout_mode_override,
             compact_scroll_offset: 0,
             terminal_width: 80, // Default, updated during render
+            icons: StatusIcons::default(),
+            theme: Theme::default(),
+            agents: AgentRegistry::default(),
+            github: GithubLookups::default(),
+            projects: ProjectConfigs::default(),
+            rename: None,
+            footer_template: String::new(),
+            time_format: TimeFormat::default(),
+            read_only: false,
+            read_only_flag: false,
+            tick_rate: DEFAULT_TICK_RATE,
+            dirty: true,
         }
     }
 
@@ -190,6 +372,73 @@ impl App {
         };
     }
 
+    /// Rebuilds the project tabs and the active tab's `sessions` from
+    /// `all_sessions`.
+    ///
+    /// The selection keeps its index, clamped to the visible sessions.
+    pub fn sync_tab_sessions(&mut self) {
+        self.tabs.sync(&self.all_sessions);
+        self.rebuild_tab_sessions();
+        if let Some(idx) = self.selected_index {
+            if idx >= self.sessions.len() {
+                self.selected_index = self.sessions.len().checked_sub(1);
+            }
+        }
+    }
+
+    /// Copies the sessions shown in the active tab into `sessions`.
+    fn rebuild_tab_sessions(&mut self) {
+        self.sessions = self
+            .all_sessions
+            .iter()
+            .filter(|s| self.tabs.shows(s))
+            .cloned()
+            .collect();
+    }
+
+    /// Switches to workspace tab `index` (0 = All).
+    ///
+    /// The current tab's selection and chip scroll offset are saved, and the
+    /// target tab's are restored. A tab visited for the first time selects
+    /// its first session. Out-of-range indices are ignored.
+    pub fn switch_tab(&mut self, index: usize) {
+        let current = TabState {
+            selected_session_id: self.selected_session().map(|s| s.session_id.clone()),
+            compact_scroll_offset: self.compact_scroll_offset,
+        };
+        if !self.tabs.switch_to(index, current) {
+            return;
+        }
+        self.rebuild_tab_sessions();
+
+        match self.tabs.saved_state().cloned() {
+            Some(state) => {
+                self.selected_index = state
+                    .selected_session_id
+                    .and_then(|id| self.sessions.iter().position(|s| s.session_id == id));
+                self.compact_scroll_offset = state
+                    .compact_scroll_offset
+                    .min(self.sessions.len().saturating_sub(1));
+            }
+            None => {
+                self.init_selection();
+                self.compact_scroll_offset = 0;
+            }
+        }
+        self.history_scroll = 0;
+        self.hovered_index = None;
+    }
+
+    /// Switches to the next workspace tab, wrapping to "All" after the last.
+    pub fn next_tab(&mut self) {
+        self.switch_tab(self.tabs.next_index());
+    }
+
+    /// Switches to the previous workspace tab, wrapping to the last from "All".
+    pub fn previous_tab(&mut self) {
+        self.switch_tab(self.tabs.previous_index());
+    }
+
     /// Moves the selection down by one, clamped to the last session.
     ///
     /// Resets history scroll when selection changes.
@@ -266,6 +515,61 @@ impl App {
         self.selected_index.and_then(|i| self.sessions.get(i))
     }
 
+    /// Returns the looked-up branch of `session`'s working directory.
+    pub fn branch_info(&self, session: &Session) -> Option<&BranchInfo> {
+        let dir = session.working_dir.as_deref()?;
+        self.github.get(dir)?.as_ref().ok()?.as_ref()
+    }
+
+    /// Returns the pull request of `session`'s branch, if one was found.
+    pub fn pull_request(&self, session: &Session) -> Option<&PullRequest> {
+        self.branch_info(session)?.pull_request.as_ref()
+    }
+
+    /// Opens the pull request at `url` in the browser with `gh`.
+    fn open_pull_request(&mut self, url: &str) {
+        let message = match self.spawn_reported(github::open_command(url), "GitHub") {
+            Ok(()) => "Opening pull request".to_string(),
+            Err(e) => {
+                tracing::warn!("failed to open pull request: {}", e);
+                format!("Open failed: {}", e)
+            }
+        };
+        self.status_message = Some((message, Instant::now() + Duration::from_secs(2)));
+    }
+
+    /// Starts an inline rename of the selected session.
+    ///
+    /// The input is pre-filled with the session's current display name.
+    /// Does nothing if no session is selected; blocked in read-only mode.
+    pub fn start_rename(&mut self) {
+        if self.selected_session().is_none() || self.block_in_read_only("rename") {
+            return;
+        }
+        if let Some(session) = self.selected_session() {
+            let current = session.display_name.clone().unwrap_or_default();
+            self.rename = Some(RenameState {
+                session_id: session.session_id.clone(),
+                input: TextInput::with_value(current),
+            });
+        }
+    }
+
+    /// Applies a display name to the local session immediately.
+    ///
+    /// The name is trimmed; a blank name clears it. The daemon applies the same
+    /// normalization, so the next update from the daemon agrees with this one.
+    pub fn apply_rename(&mut self, session_id: &str, display_name: &str) {
+        let trimmed = display_name.trim();
+        if let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.session_id == session_id)
+        {
+            session.display_name = (!trimmed.is_empty()).then(|| trimmed.to_string());
+        }
+    }
+
     /// Opens the detail view for the session at `index`.
     ///
     /// Deprecated: detail panel is always visible. This method is kept for
@@ -305,32 +609,86 @@ impl App {
     /// - Non-closed sessions → activate_hooks
     /// - Closed sessions → reopen_hooks
     ///
-    /// Hooks run sequentially in order. Each hook is spawned via `sh -c` with session
+    /// With no hooks configured, the detected terminal backend (e.g. Zellij)
+    /// focuses the session's tab or opens a pane resuming it instead. With
+    /// `tui.embedded_terminal`, closed sessions resume in the embedded pane.
+    ///
+    /// Hooks run sequentially in order; entries with `hook = "<name>"` run the
+    /// named `[hooks]` entry. Each hook is spawned via `sh -c` with session
     /// data as environment variables (`ACD_SESSION_ID`, `ACD_WORKING_DIR`, `ACD_STATUS`)
     /// and as a JSON SessionSnapshot on stdin (same pattern as Claude Code hooks).
     ///
     /// Each hook respects its configured `timeout`: the process is killed if it runs
     /// longer than the timeout duration. Stdout/stderr are captured and logged at
-    /// debug level. Failure of one hook does not prevent subsequent hooks from running.
+    /// debug level, or at warn level if the hook fails; failures are also shown in
+    /// the footer. Failure of one hook does not prevent subsequent hooks from running.
     ///
     /// For closed sessions reopened via reopen_hooks, the session status is updated
     /// locally to Attention (TUI-only, no IPC to daemon).
+    ///
+    /// In read-only mode no hook runs; a footer message explains why.
     pub fn execute_hook(&mut self, session_index: usize) {
-        use crate::config::schema::HookConfig;
-        use crate::SessionSnapshot;
-        use std::io::Write;
+        use crate::config::schema::ProjectConfig;
 
+        if session_index >= self.sessions.len() || self.block_in_read_only("hooks") {
+            return;
+        }
         let Some(session) = self.sessions.get(session_index) else {
             return;
         };
 
         let is_closed = session.status == Status::Closed;
-        let hooks: Vec<HookConfig> = if is_closed {
-            self.reopen_hooks.clone()
-        } else {
-            self.activate_hooks.clone()
+        // The session's project file replaces the user config's hooks
+        let project = session
+            .working_dir
+            .as_deref()
+            .and_then(|dir| self.projects.get(dir));
+        if let Some(project) = project.as_deref() {
+            let project_hooks = if is_closed {
+                &project.reopen_hooks
+            } else {
+                &project.activate_hooks
+            };
+            if project_hooks.is_some() && !project.trusted {
+                self.status_message = Some((
+                    format!(
+                        "Add {} to tui.trusted_projects to run its .acd.toml hooks",
+                        project.root.display()
+                    ),
+                    Instant::now() + Duration::from_secs(3),
+                ));
+                return;
+            }
+        }
+        let hooks: Vec<HookConfig> = match (is_closed, project.as_deref()) {
+            (
+                true,
+                Some(ProjectConfig {
+                    reopen_hooks: Some(hooks),
+                    ..
+                }),
+            ) => hooks.clone(),
+            (
+                false,
+                Some(ProjectConfig {
+                    activate_hooks: Some(hooks),
+                    ..
+                }),
+            ) => hooks.clone(),
+            (true, _) => self.reopen_hooks.clone(),
+            (false, _) => self.activate_hooks.clone(),
         };
 
+        if hooks.is_empty() && is_closed && self.embedded_terminal {
+            self.open_embedded_terminal(session_index);
+            return;
+        }
+
+        if hooks.is_empty() && self.terminal.is_some() {
+            self.run_terminal_integration(session_index, is_closed);
+            return;
+        }
+
         if hooks.is_empty() {
             // No hooks configured — show hint message with config path
             let config_path = crate::config::xdg::config_path();
@@ -351,150 +709,358 @@ impl App {
         }
 
         let hook_type = if is_closed { "reopen" } else { "activate" };
+        let labeled = hooks
+            .iter()
+            .enumerate()
+            .map(|(idx, hook)| (format!("{} hook[{}]", hook_type, idx), hook))
+            .collect();
+        if !self.spawn_hooks(session_index, labeled) {
+            return;
+        }
 
-        // Extract env var values before converting session to snapshot (borrow ends here)
-        let session_id = session.session_id.clone();
-        let working_dir_str = session
-            .working_dir
-            .as_ref()
-            .map(|p| p.display().to_string())
-            .unwrap_or_default();
-        let status_str = session.status.to_string();
-
-        // Convert Session to SessionSnapshot and serialize to JSON
-        let snapshot: SessionSnapshot = session.into();
-        let json_payload = match serde_json::to_string(&snapshot) {
-            Ok(json) => json,
+        if is_closed {
+            self.mark_reopened(session_index);
+        }
+
+        self.status_message = Some((
+            "Hook executed".to_string(),
+            Instant::now() + Duration::from_secs(2),
+        ));
+    }
+
+    /// Runs the `[hooks]` entry bound to `key` in `tui.keys` for the session
+    /// at `session_index`.
+    ///
+    /// In read-only mode no hook runs; a footer message explains why.
+    pub fn run_key_hook(&mut self, key: char, session_index: usize) {
+        if session_index >= self.sessions.len() || self.block_in_read_only("hooks") {
+            return;
+        }
+        let Some(name) = self.key_hooks.get(&key).cloned() else {
+            return;
+        };
+        let hook = HookConfig {
+            hook: name.clone(),
+            ..HookConfig::default()
+        };
+        if self.spawn_hooks(session_index, vec![(format!("key {}", key), &hook)]) {
+            self.status_message = Some((
+                format!("Hook {} executed", name),
+                Instant::now() + Duration::from_secs(2),
+            ));
+        }
+    }
+
+    /// Runs `hooks` one after another in a background thread so the TUI stays
+    /// responsive, resolving `hook = "<name>"` references against the
+    /// `[hooks]` library. Failures are reported in the footer.
+    ///
+    /// Returns false, with a footer message, if nothing was started: a hook
+    /// names an unknown library entry or the session cannot be serialized.
+    fn spawn_hooks(&mut self, session_index: usize, hooks: Vec<(String, &HookConfig)>) -> bool {
+        let Some(session) = self.sessions.get(session_index) else {
+            return false;
+        };
+        let prepared: Result<Vec<_>, String> = hooks
+            .into_iter()
+            .map(|(label, hook)| hooks::prepare(hook, &self.hook_library, session, label))
+            .collect();
+        let prepared = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                self.status_message = Some((e, Instant::now() + Duration::from_secs(2)));
+                return false;
+            }
+        };
+        let input = match hooks::HookInput::new(session) {
+            Ok(input) => input,
             Err(e) => {
                 tracing::warn!("failed to serialize SessionSnapshot: {}", e);
-                return;
+                return false;
             }
         };
 
-        // Spawn hooks sequentially in a background thread so the TUI stays responsive.
-        // Each hook's stdout/stderr are captured and logged at debug level.
-        let session_id_clone = session_id.clone();
-        let working_dir_clone = working_dir_str.clone();
-        let status_clone = status_str.clone();
+        let failures = self.command_failures.clone();
         std::thread::spawn(move || {
-            use std::io::Read;
-
-            for (idx, hook) in hooks.iter().enumerate() {
-                let label = format!("{} hook[{}]", hook_type, idx);
-                tracing::debug!("executing {}: {}", label, hook.command);
-
-                let spawn_result = std::process::Command::new("sh")
-                    .arg("-c")
-                    .arg(&hook.command)
-                    .env("ACD_SESSION_ID", &session_id_clone)
-                    .env("ACD_WORKING_DIR", &working_dir_clone)
-                    .env("ACD_STATUS", &status_clone)
-                    .stdin(std::process::Stdio::piped())
-                    .stdout(std::process::Stdio::piped())
-                    .stderr(std::process::Stdio::piped())
-                    .spawn();
-
-                let mut child = match spawn_result {
-                    Ok(c) => c,
-                    Err(e) => {
-                        tracing::warn!("{} failed to spawn: {}", label, e);
-                        continue;
+            for hook in &prepared {
+                if let Err(message) = hooks::run(hook, &input) {
+                    if let Some(failures) = &failures {
+                        let _ = failures.send(message);
                     }
-                };
+                }
+            }
+        });
+        true
+    }
 
-                // Write JSON payload to stdin, then close stdin so the hook can read EOF
-                if let Some(mut stdin) = child.stdin.take() {
-                    if let Err(e) = stdin.write_all(json_payload.as_bytes()) {
-                        tracing::warn!("{} failed to write stdin: {}", label, e);
+    /// Focuses or resurrects the session through the terminal backend.
+    ///
+    /// Focusing runs in the background. Resurrecting waits for the backend to
+    /// open the pane, so the footer reports whether it did.
+    fn run_terminal_integration(&mut self, session_index: usize, is_closed: bool) {
+        let (Some(terminal), Some(session)) = (&self.terminal, self.sessions.get(session_index))
+        else {
+            return;
+        };
+        let name = terminal.name();
+        let message = if is_closed {
+            let agent = self.agents.get(&session.agent_type);
+            let Some(resume) = agent.resume_command(session) else {
+                let message = format!("{} cannot resume sessions", agent.display_name());
+                self.status_message = Some((message, Instant::now() + Duration::from_secs(2)));
+                return;
+            };
+            let command = terminal.resurrect_command(session, &resume);
+            match crate::integrations::run(command, name) {
+                Ok(()) => {
+                    self.mark_reopened(session_index);
+                    format!("Resumed in a new {} pane", name)
+                }
+                Err(e) => {
+                    tracing::warn!("{} integration failed to resurrect: {}", name, e);
+                    format!("{}: {}", name, e)
+                }
+            }
+        } else {
+            match terminal
+                .focus_command(session)
+                .map(|c| self.spawn_reported(c, name))
+            {
+                None => format!("{}: no window found for this session", name),
+                Some(Err(e)) => {
+                    tracing::warn!("{} integration failed to spawn: {}", name, e);
+                    format!("{}: {}", name, e)
+                }
+                Some(Ok(())) => {
+                    if let Some(raise) = terminal.raise_command().filter(|_| self.raise_window) {
+                        if let Err(e) = self.spawn_reported(raise, name) {
+                            tracing::warn!("{} integration failed to raise window: {}", name, e);
+                        }
                     }
-                    // stdin dropped here → EOF sent to child
+                    format!("Focused tab in {}", name)
                 }
+            }
+        };
+        self.status_message = Some((message, Instant::now() + Duration::from_secs(2)));
+    }
 
-                // Take stdout/stderr handles so we can read them into buffers.
-                // These are read in separate threads to avoid deadlocking on large output.
-                let mut stdout_handle = child.stdout.take();
-                let mut stderr_handle = child.stderr.take();
+    /// Applies the TUI settings of `config`: hooks, key hooks, commands, agents, icons,
+    /// theme, footer, time format, read-only mode, tick rate, and the terminal and
+    /// GitHub integrations.
+    ///
+    /// Called at startup and whenever the config file changes. Project
+    /// overrides are read again as sessions next update.
+    pub fn apply_config(&mut self, config: Config) {
+        // Resolved first: the theme reads `[themes]` as well as `[tui]`
+        self.theme = Theme::from_config(&config);
+        self.activate_hooks = config.tui.activate_hooks;
+        self.reopen_hooks = config.tui.reopen_hooks;
+        self.session_commands = config.tui.commands;
+        // The loader only accepts single-character keys
+        self.key_hooks = config
+            .tui
+            .keys
+            .into_iter()
+            .filter_map(|(key, hook)| Some((key.chars().next()?, hook)))
+            .collect();
+        self.hook_library = config.hooks;
+        self.agents = AgentRegistry::from_config(&config.agents);
+        self.icons = StatusIcons::from_config(&config.tui.icons).with_agent_tags(&self.agents);
+        self.footer_template = config.tui.footer;
+        self.time_format = config.tui.time_format;
+        // The CLI flag can only turn read-only on, never off
+        self.read_only = self.read_only_flag || config.tui.read_only;
+        self.hover_tooltips = config.tui.hover_tooltips;
+        self.embedded_terminal = config.tui.embedded_terminal;
+        self.raise_window = config.integrations.raise_window;
+        self.tick_rate = humantime::parse_duration(&config.tui.tick_rate).unwrap_or_else(|e| {
+            tracing::warn!("invalid tui.tick_rate '{}': {}", config.tui.tick_rate, e);
+            DEFAULT_TICK_RATE
+        });
+        self.github.reconfigure(&config.integrations.github);
+        self.terminal = crate::integrations::detect(&config.integrations);
+        self.projects.set_trusted(&config.tui.trusted_projects);
+        self.projects.clear();
+    }
 
-                let stdout_thread = std::thread::spawn(move || {
-                    let mut buf = Vec::new();
-                    if let Some(ref mut h) = stdout_handle {
-                        let _ = h.read_to_end(&mut buf);
-                    }
-                    buf
-                });
-                let stderr_thread = std::thread::spawn(move || {
-                    let mut buf = Vec::new();
-                    if let Some(ref mut h) = stderr_handle {
-                        let _ = h.read_to_end(&mut buf);
+    /// Applies a config reloaded after the file changed, or reports why it
+    /// could not be loaded and keeps the current settings.
+    pub fn reload_config(&mut self, config: Result<Config, ConfigError>) {
+        self.status_message = Some(match config {
+            Ok(config) => {
+                self.apply_config(config);
+                tracing::info!("config reloaded");
+                (
+                    "Config reloaded".to_string(),
+                    Instant::now() + Duration::from_secs(2),
+                )
+            }
+            Err(e) => {
+                tracing::warn!("config reload failed: {}", e);
+                (
+                    format!("Config not reloaded: {}", e),
+                    Instant::now() + Duration::from_secs(5),
+                )
+            }
+        });
+    }
+
+    /// Runs `command` in the background; if it fails, its
+    /// [`failure_message`](crate::integrations::failure_message) is shown in
+    /// the footer, prefixed with `label`.
+    fn spawn_reported(&self, command: std::process::Command, label: &str) -> io::Result<()> {
+        let failures = self.command_failures.clone();
+        let prefix = label.to_string();
+        crate::integrations::spawn_reporting(command, label, move |message| {
+            if let Some(failures) = failures {
+                let _ = failures.send(format!("{}: {}", prefix, message));
+            }
+        })
+    }
+
+    /// Opens the command palette for the selected session.
+    ///
+    /// Shows a footer message instead if no `tui.commands` are configured.
+    /// Does nothing if no session is selected; blocked in read-only mode.
+    pub fn open_command_palette(&mut self) {
+        if self.selected_session().is_none() || self.block_in_read_only("commands") {
+            return;
+        }
+        if self.session_commands.is_empty() {
+            self.status_message = Some((
+                "No commands configured ([[tui.commands]])".to_string(),
+                Instant::now() + Duration::from_secs(2),
+            ));
+            return;
+        }
+        if let Some(session) = self.selected_session() {
+            self.palette = Some(CommandPalette::new(session.session_id.clone()));
+        }
+    }
+
+    /// Runs the highlighted palette command in a new terminal backend pane in
+    /// the session's working directory, and closes the palette.
+    pub fn run_session_command(&mut self) {
+        let Some(palette) = self.palette.take() else {
+            return;
+        };
+        let (Some(command), Some(session)) = (
+            self.session_commands.get(palette.selected),
+            self.sessions
+                .iter()
+                .find(|s| s.session_id == palette.session_id),
+        ) else {
+            return;
+        };
+        let message = match &self.terminal {
+            None => format!("No terminal backend to run {} in", command.name),
+            Some(terminal) => {
+                let name = terminal.name();
+                let argv = [
+                    "sh".to_string(),
+                    "-c".to_string(),
+                    render_command(&command.command, session),
+                ];
+                let spawn = terminal.spawn_command(
+                    session.working_dir.as_deref(),
+                    Some(&command.name),
+                    &argv,
+                );
+                match crate::integrations::run(spawn, name) {
+                    Ok(()) => format!("Running {} in a new {} pane", command.name, name),
+                    Err(e) => {
+                        tracing::warn!(
+                            "{} integration failed to run {}: {}",
+                            name,
+                            command.name,
+                            e
+                        );
+                        format!("{}: {}", name, e)
                     }
-                    buf
-                });
-
-                // Wait with timeout: poll every 50ms up to `timeout` seconds.
-                let timeout_duration = std::time::Duration::from_secs(hook.timeout);
-                let deadline = std::time::Instant::now() + timeout_duration;
-                let timed_out = loop {
-                    match child.try_wait() {
-                        Ok(Some(status)) => {
-                            tracing::debug!("{} exited with: {}", label, status);
-                            break false;
-                        }
-                        Ok(None) => {
-                            if std::time::Instant::now() >= deadline {
-                                tracing::warn!(
-                                    "{} timed out after {}s, killing",
-                                    label,
-                                    hook.timeout
-                                );
-                                let _ = child.kill();
-                                let _ = child.wait();
-                                break true;
-                            }
-                            std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+            }
+        };
+        self.status_message = Some((message, Instant::now() + Duration::from_secs(2)));
+    }
+
+    /// Resumes the closed session in the embedded terminal pane and focuses it.
+    ///
+    /// Only one session runs embedded at a time.
+    fn open_embedded_terminal(&mut self, session_index: usize) {
+        let Some(session) = self.sessions.get(session_index) else {
+            return;
+        };
+        let message = if let Some(embedded) = &self.embedded {
+            format!(
+                "{} is running in the embedded terminal ([Ctrl+]] to focus)",
+                embedded.title()
+            )
+        } else {
+            let agent = self.agents.get(&session.agent_type);
+            match agent
+                .resume_command(session)
+                .filter(|resume| !resume.is_empty())
+            {
+                None => format!("{} cannot resume sessions", agent.display_name()),
+                Some(resume) => {
+                    // Resized to the pane on the next render
+                    let size = (24, self.terminal_width.saturating_sub(2).max(1));
+                    match EmbeddedTerminal::spawn(
+                        session,
+                        &resume,
+                        size,
+                        self.embedded_output.clone(),
+                    ) {
+                        Ok(embedded) => {
+                            let message = format!(
+                                "Resumed {} in the embedded terminal ([Ctrl+]] switches focus)",
+                                embedded.title()
+                            );
+                            self.embedded = Some(embedded);
+                            self.embedded_focused = true;
+                            self.mark_reopened(session_index);
+                            message
                         }
                         Err(e) => {
-                            tracing::warn!("{} wait error: {}", label, e);
-                            break false;
+                            tracing::warn!("embedded terminal failed: {}", e);
+                            format!("Embedded terminal: {}", e)
                         }
                     }
-                };
-
-                // Collect stdout/stderr from reader threads
-                let stdout_bytes = stdout_thread.join().unwrap_or_default();
-                let stderr_bytes = stderr_thread.join().unwrap_or_default();
-
-                if !stdout_bytes.is_empty() {
-                    tracing::debug!(
-                        "{} stdout: {}",
-                        label,
-                        String::from_utf8_lossy(&stdout_bytes).trim()
-                    );
-                }
-                if !stderr_bytes.is_empty() {
-                    tracing::debug!(
-                        "{} stderr: {}",
-                        label,
-                        String::from_utf8_lossy(&stderr_bytes).trim()
-                    );
-                }
-                if timed_out {
-                    tracing::warn!("{} was killed due to timeout", label);
                 }
             }
-        });
+        };
+        self.status_message = Some((message, Instant::now() + Duration::from_secs(3)));
+    }
 
-        // For closed sessions, update local status to Attention (no IPC)
-        if is_closed {
-            if let Some(session) = self.sessions.get_mut(session_index) {
-                session.status = Status::Attention;
-                tracing::debug!("updated local session status to attention");
-            }
+    /// Closes the embedded terminal pane once its process has exited.
+    pub fn poll_embedded_terminal(&mut self) {
+        let Some(status) = self.embedded.as_mut().and_then(|e| e.exit_status()) else {
+            return;
+        };
+        if let Some(embedded) = self.embedded.take() {
+            self.status_message = Some((
+                format!("{} {}", embedded.title(), status),
+                Instant::now() + Duration::from_secs(3),
+            ));
         }
+        self.embedded_focused = false;
+        self.embedded_area = None;
+        self.mark_dirty();
+    }
 
-        self.status_message = Some((
-            "Hook executed".to_string(),
-            Instant::now() + Duration::from_secs(2),
-        ));
+    /// Updates a reopened session's local status to Attention (no IPC).
+    fn mark_reopened(&mut self, session_index: usize) {
+        let session_id = self.sessions[session_index].session_id.clone();
+        for session in self
+            .sessions
+            .iter_mut()
+            .chain(self.all_sessions.iter_mut())
+            .filter(|s| s.session_id == session_id)
+        {
+            session.status = Status::Attention;
+        }
+        tracing::debug!("updated local session status to attention");
     }
 
     /// Calculates which session index was clicked based on mouse row coordinate.
@@ -528,6 +1094,25 @@ impl App {
     ///
     /// In TwoLine layout mode, handles clicks on session chips and overflow indicators.
     fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Action {
+        // A click focuses the embedded terminal pane or the dashboard; other
+        // events over the pane are ignored
+        if let Some(area) = self.embedded_area {
+            let inside = area.contains(ratatui::layout::Position::new(mouse.column, mouse.row));
+            if let MouseEventKind::Down(_) = mouse.kind {
+                self.embedded_focused = inside;
+            }
+            if inside {
+                return Action::None;
+            }
+        }
+        // The usage chart is keyboard-only; don't select hidden sessions
+        if let View::UsageChart { .. } = self.view {
+            return Action::None;
+        }
+        if mouse.kind == MouseEventKind::Moved {
+            self.update_hover(mouse);
+            return Action::None;
+        }
         // Branch based on layout mode
         if self.layout_mode == LayoutMode::TwoLine {
             self.handle_mouse_event_two_line(mouse)
@@ -550,6 +1135,13 @@ impl App {
                     false
                 };
 
+                if let Some(alert_idx) = self.calculate_clicked_alert(mouse.row) {
+                    // Click on an alert row acknowledges it; focus stays put
+                    self.alerts.acknowledge(alert_idx);
+                    self.last_click = None;
+                    return Action::None;
+                }
+
                 if let Some(idx) = self.calculate_clicked_session(mouse.row) {
                     // Reset history scroll when clicking a different session
                     if self.selected_index != Some(idx) {
@@ -659,6 +1251,38 @@ impl App {
         }
     }
 
+    /// Updates hover state from a mouse move.
+    ///
+    /// Only marks the UI dirty when the highlight or tooltip would change, so
+    /// moving the pointer over empty space does not trigger redraws.
+    fn update_hover(&mut self, mouse: MouseEvent) {
+        let hovered = if self.layout_mode == LayoutMode::TwoLine {
+            if mouse.row == 0 {
+                self.chip_at_column(mouse.column)
+            } else {
+                None
+            }
+        } else {
+            self.calculate_clicked_session(mouse.row)
+        };
+        let position = Some((mouse.column, mouse.row));
+        let changed = hovered != self.hovered_index
+            || (hovered.is_some() && self.hover_tooltips && position != self.hover_position);
+        self.hovered_index = hovered;
+        self.hover_position = position;
+        if changed {
+            self.mark_dirty();
+        }
+    }
+
+    /// Returns the session index of the chip at `column` in the TwoLine chips row.
+    pub(crate) fn chip_at_column(&self, column: u16) -> Option<usize> {
+        match self.calculate_clicked_chip(column) {
+            ClickTarget::Chip(idx) => Some(idx),
+            _ => None,
+        }
+    }
+
     /// Calculates max visible chips for the current terminal width.
     ///
     /// Used by click detection to determine chip boundaries. With dynamic chip widths,
@@ -677,8 +1301,8 @@ impl App {
     /// Returns the target based on column position in the session chips row.
     /// With dynamic chip widths, performs linear scan of cumulative positions.
     fn calculate_clicked_chip(&self, column: u16) -> ClickTarget {
-        use crate::tui::ui::OVERFLOW_INDICATOR_WIDTH;
-        use crate::tui::views::dashboard::get_directory_display_name;
+        use crate::tui::ui::{chip_width, OVERFLOW_INDICATOR_WIDTH};
+        use crate::tui::views::dashboard::session_label;
 
         if self.sessions.is_empty() {
             return ClickTarget::None;
@@ -686,16 +1310,6 @@ impl App {
 
         let col = column as usize;
 
-        // Helper: calculates chip display width
-        fn chip_width(name: &str, is_focused: bool) -> usize {
-            let name_len = name.len();
-            if is_focused {
-                4 + name_len // '[' + symbol + ' ' + name + ']'
-            } else {
-                3 + name_len // ' ' + symbol + ' ' + name
-            }
-        }
-
         // Helper: truncates from start
         fn truncate_from_start(name: &str, max_len: usize) -> String {
             if name.len() <= max_len {
@@ -721,14 +1335,15 @@ impl App {
             let session = &self.sessions[i];
             let is_focused = self.selected_index == Some(i);
 
-            let display_name = get_directory_display_name(session);
+            let display_name = session_label(session);
             let label = if display_name == "<error>" {
                 session.session_id.chars().take(8).collect()
             } else {
                 truncate_from_start(&display_name, 12)
             };
 
-            let this_chip_width = chip_width(&label, is_focused);
+            let this_chip_width =
+                chip_width(&label, self.icons.session_symbol(session), is_focused);
             let separator_width = if i > start { 3 } else { 0 };
             let total_needed = accumulated_width + separator_width + this_chip_width;
 
@@ -760,7 +1375,7 @@ impl App {
             let session = &self.sessions[i];
             let is_focused = self.selected_index == Some(i);
 
-            let display_name = get_directory_display_name(session);
+            let display_name = session_label(session);
             let label = if display_name == "<error>" {
                 session.session_id.chars().take(8).collect()
             } else {
@@ -772,7 +1387,8 @@ impl App {
                 cursor += 3; // " | " or "]| " (3 chars)
             }
 
-            let this_chip_width = chip_width(&label, is_focused);
+            let this_chip_width =
+                chip_width(&label, self.icons.session_symbol(session), is_focused);
             let chip_end = cursor + this_chip_width;
 
             if col >= cursor && col < chip_end {
@@ -804,10 +1420,106 @@ impl App {
         if let Some((_, expiry)) = &self.status_message {
             if Instant::now() >= *expiry {
                 self.status_message = None;
+                self.dirty = true;
             }
         }
     }
 
+    /// Switches to the usage history chart, following the latest sample.
+    pub fn open_usage_chart(&mut self) {
+        self.view = View::UsageChart { cursor: None };
+    }
+
+    /// Returns the resolved chart cursor (latest sample when following).
+    ///
+    /// Returns None outside the chart view or when there are no samples.
+    pub fn usage_chart_cursor(&self) -> Option<usize> {
+        let View::UsageChart { cursor } = self.view else {
+            return None;
+        };
+        let last = self.usage_history.len().checked_sub(1)?;
+        Some(cursor.map_or(last, |c| c.min(last)))
+    }
+
+    /// Moves the chart cursor by `delta` samples, clamped to the history.
+    ///
+    /// Landing on the latest sample resumes following new samples.
+    pub fn move_usage_chart_cursor(&mut self, delta: isize) {
+        let Some(current) = self.usage_chart_cursor() else {
+            return;
+        };
+        let last = self.usage_history.len() - 1;
+        let target = current.saturating_add_signed(delta).min(last);
+        self.view = View::UsageChart {
+            cursor: (target != last).then_some(target),
+        };
+    }
+
+    /// Acknowledges the oldest unacknowledged alert, if any.
+    pub fn acknowledge_alert(&mut self) {
+        self.alerts.acknowledge_oldest();
+    }
+
+    /// Acknowledges every alert.
+    pub fn acknowledge_all_alerts(&mut self) {
+        self.alerts.acknowledge_all();
+    }
+
+    /// Returns the alert index for a click at `row`, if it hits an alert row.
+    fn calculate_clicked_alert(&self, row: u16) -> Option<usize> {
+        let area = self.alerts_inner_area?;
+        if row < area.y || row >= area.y + area.height {
+            return None;
+        }
+        let index = (row - area.y) as usize;
+        (index < self.alerts.len()).then_some(index)
+    }
+
+    /// Returns true (and shows a footer message) if `action` must be blocked
+    /// because the dashboard is in read-only mode.
+    pub fn block_in_read_only(&mut self, action: &str) -> bool {
+        if !self.read_only {
+            return false;
+        }
+        self.status_message = Some((
+            format!("Read-only mode: {} disabled", action),
+            Instant::now() + Duration::from_secs(2),
+        ));
+        true
+    }
+
+    /// Switches between relative and absolute elapsed times.
+    ///
+    /// Shows the new mode as a transient footer message.
+    pub fn toggle_time_format(&mut self) {
+        self.time_format = self.time_format.toggled();
+        self.status_message = Some((
+            format!("Time format: {}", self.time_format),
+            Instant::now() + Duration::from_secs(2),
+        ));
+    }
+
+    /// Marks the UI as needing a redraw.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Returns whether a redraw is needed and clears the flag.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Marks time-dependent state dirty: expired status messages and, once per
+    /// [`ELAPSED_TIME_REFRESH_INTERVAL`], elapsed times of visible sessions.
+    pub fn refresh_time_based_state(&mut self) {
+        self.expire_status_message();
+        if !self.sessions.is_empty()
+            && self.last_elapsed_render.elapsed() >= ELAPSED_TIME_REFRESH_INTERVAL
+        {
+            self.dirty = true;
+        }
+    }
+
     /// Runs the TUI application: sets up terminal, enters event loop, restores on exit.
     pub async fn run(&mut self) -> io::Result<()> {
         // Install panic hook that restores terminal before printing panic info
@@ -830,8 +1542,10 @@ impl App {
         let backend = CrosstermBackend::new(stdout());
         let mut terminal =
             Terminal::new(backend).expect("failed to create ratatui terminal instance");
-        let event_handler = EventHandler::new(Duration::from_millis(250));
+        let mut event_handler = EventHandler::new(self.tick_rate);
         let mut reader = EventStream::new();
+        // Last title written to the terminal, to avoid re-sending unchanged titles
+        let mut last_title: Option<String> = None;
 
         // Connect to daemon and subscribe to updates
         let (update_tx, mut update_rx) = mpsc::channel::<DaemonMessage>(64);
@@ -842,24 +1556,70 @@ impl App {
             }
         });
 
+        // Cleared when the subscription task exits, so recv() is not polled again
+        let mut daemon_connected = true;
+
+        let (github_tx, mut github_rx) = mpsc::unbounded_channel();
+        self.github.connect(github_tx);
+
+        let (embedded_tx, mut embedded_rx) = mpsc::unbounded_channel();
+        self.embedded_output = Some(embedded_tx);
+
+        let (failure_tx, mut failure_rx) = mpsc::unbounded_channel();
+        self.command_failures = Some(failure_tx);
+
+        // Kept alive for the whole loop; dropping it stops watching
+        let (config_tx, mut config_rx) = mpsc::unbounded_channel();
+        let config_path = crate::config::xdg::config_path();
+        let _config_watcher = crate::config::watch::watch(config_path, move |config| {
+            let _ = config_tx.send(config);
+        })
+        .map_err(|e| tracing::debug!("not watching config for changes: {}", e))
+        .ok();
+
         loop {
-            // Drain daemon updates before rendering
-            while let Ok(msg) = update_rx.try_recv() {
-                match msg {
-                    DaemonMessage::SessionUpdate(info) => self.apply_update(&info),
-                    DaemonMessage::UsageUpdate(data) => {
-                        self.usage = Some(data);
-                    }
-                    DaemonMessage::UsageBlocked => {
-                        self.usage_blocked = true;
+            // Wake on whichever comes first: a daemon message or a terminal event/tick
+            let event = tokio::select! {
+                msg = update_rx.recv(), if daemon_connected => {
+                    match msg {
+                        Some(msg) => {
+                            self.apply_daemon_message(msg);
+                            while let Ok(msg) = update_rx.try_recv() {
+                                self.apply_daemon_message(msg);
+                            }
+                        }
+                        None => daemon_connected = false,
                     }
+                    None
                 }
-            }
+                Some(lookup) = github_rx.recv() => {
+                    self.github.apply(lookup);
+                    self.mark_dirty();
+                    None
+                }
+                Some(config) = config_rx.recv() => {
+                    self.reload_config(config);
+                    event_handler = EventHandler::new(self.tick_rate);
+                    self.mark_dirty();
+                    None
+                }
+                Some(message) = failure_rx.recv() => {
+                    // Longer than other messages: there may be stderr to read
+                    self.status_message = Some((message, Instant::now() + Duration::from_secs(5)));
+                    self.mark_dirty();
+                    None
+                }
+                Some(()) = embedded_rx.recv() => {
+                    while embedded_rx.try_recv().is_ok() {}
+                    self.poll_embedded_terminal();
+                    self.mark_dirty();
+                    None
+                }
+                event = event_handler.next(&mut reader) => Some(event?),
+            };
 
-            // Handle events first to determine if we should render
-            let event = event_handler.next(&mut reader).await?;
-            let should_render = match event {
-                Event::Key(key) => {
+            match event {
+                Some(Event::Key(key)) => {
                     match handle_key_event(self, key) {
                         Action::Quit => {
                             self.should_quit = true;
@@ -871,7 +1631,12 @@ impl App {
                         }
                         Action::Resurrect(id) => {
                             tracing::debug!("resurrect session {id}");
-                            // TODO: send RESURRECT IPC command to daemon
+                            // Reopen through reopen_hooks or the terminal backend
+                            if let Some(index) =
+                                self.sessions.iter().position(|s| s.session_id == id)
+                            {
+                                self.execute_hook(index);
+                            }
                         }
                         Action::Remove(id) => {
                             tracing::debug!("remove session {id}");
@@ -921,31 +1686,59 @@ impl App {
                                 ));
                             }
                         },
+                        Action::OpenPullRequest(url) => self.open_pull_request(&url),
+                        Action::Rename(session_id, display_name) => {
+                            self.apply_rename(&session_id, &display_name);
+                            let socket_path = self.socket_path.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) =
+                                    send_rename(&socket_path, &session_id, &display_name).await
+                                {
+                                    tracing::warn!("rename failed: {}", e);
+                                }
+                            });
+                        }
                         Action::None => {}
                     }
-                    true // Input events always render immediately
+                    self.mark_dirty(); // Input events always render immediately
                 }
-                Event::Mouse(mouse) => {
+                Some(Event::Mouse(mouse)) => {
                     self.handle_mouse_event(mouse);
-                    true // Input events always render immediately
+                    // Input events render immediately; moves mark dirty on hover change
+                    if mouse.kind != MouseEventKind::Moved {
+                        self.mark_dirty();
+                    }
                 }
-                Event::Tick => {
+                Some(Event::Tick) => {
                     self.tick_count += 1;
-                    self.expire_status_message();
-                    // Passive tick: only render if interval has elapsed
-                    self.last_elapsed_render.elapsed() >= ELAPSED_TIME_REFRESH_INTERVAL
                 }
-                Event::Resize(_, _) => {
-                    true // Resize always renders immediately
+                Some(Event::Resize(_, _)) => {
+                    self.mark_dirty(); // Resize always renders immediately
                 }
-            };
+                None => {}
+            }
 
-            // Render only when needed (input events or throttled passive tick)
-            if should_render {
+            // Checked after every wake-up: frequent daemon messages can starve ticks
+            self.refresh_time_based_state();
+            if let Some(dir) = self
+                .selected_session()
+                .and_then(|session| session.working_dir.clone())
+            {
+                self.github.refresh(&dir);
+            }
+
+            // Render only when something changed since the last frame
+            if self.take_dirty() {
                 terminal.draw(|frame| {
                     render_dashboard(frame, self);
                 })?;
                 self.last_elapsed_render = Instant::now();
+
+                let title = terminal_title(&self.all_sessions, &self.icons);
+                if last_title.as_deref() != Some(title.as_str()) {
+                    execute!(stdout(), SetTitle(&title))?;
+                    last_title = Some(title);
+                }
             }
         }
     }
@@ -293,6 +293,9 @@ fn test_session_sort_by_status_group() {
         history: vec![],
         closed: false,
         priority: 0,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     let working = SessionSnapshot {
@@ -305,6 +308,9 @@ fn test_session_sort_by_status_group() {
         history: vec![],
         closed: false,
         priority: 0,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     let closed = SessionSnapshot {
@@ -317,6 +323,9 @@ fn test_session_sort_by_status_group() {
         history: vec![],
         closed: true,
         priority: 0,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     // Apply in reverse order: closed, working, attention
@@ -346,6 +355,9 @@ fn test_session_sort_by_priority() {
         history: vec![],
         closed: false,
         priority: 1,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     let high_priority = SessionSnapshot {
@@ -358,6 +370,9 @@ fn test_session_sort_by_priority() {
         history: vec![],
         closed: false,
         priority: 10,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     // Apply in wrong order
@@ -388,6 +403,9 @@ fn test_session_sort_by_elapsed_time() {
         history: vec![],
         closed: false,
         priority: 5,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     let long = SessionSnapshot {
@@ -400,6 +418,9 @@ fn test_session_sort_by_elapsed_time() {
         history: vec![],
         closed: false,
         priority: 5,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
     };
 
     // Apply in wrong order
@@ -430,6 +451,9 @@ fn test_session_sort_combined() {
             history: vec![],
             closed: true,
             priority: 100,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
         },
         SessionSnapshot {
             session_id: "attention-low".to_string(),
@@ -441,6 +465,9 @@ fn test_session_sort_combined() {
             history: vec![],
             closed: false,
             priority: 1,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
         },
         SessionSnapshot {
             session_id: "working-high-short".to_string(),
@@ -452,6 +479,9 @@ fn test_session_sort_combined() {
             history: vec![],
             closed: false,
             priority: 10,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
         },
         SessionSnapshot {
             session_id: "working-high-long".to_string(),
@@ -463,6 +493,9 @@ fn test_session_sort_combined() {
             history: vec![],
             closed: false,
             priority: 10,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
         },
     ];
 
@@ -481,3 +514,316 @@ fn test_session_sort_combined() {
     assert_eq!(app.sessions[2].session_id, "working-high-short");
     assert_eq!(app.sessions[3].session_id, "closed-high");
 }
+
+#[test]
+fn test_start_rename_prefills_existing_display_name() {
+    let mut app = make_app_with_sessions(1);
+    app.sessions[0].display_name = Some("api work".to_string());
+    app.start_rename();
+    let rename = app.rename.as_ref().expect("rename should be active");
+    assert_eq!(rename.input.value(), "api work");
+    assert_eq!(rename.input.cursor(), 8);
+}
+
+#[test]
+fn test_apply_rename_trims_and_clears() {
+    let mut app = make_app_with_sessions(1);
+    app.apply_rename("session-0", "  new name  ");
+    assert_eq!(app.sessions[0].display_name.as_deref(), Some("new name"));
+    app.apply_rename("session-0", "   ");
+    assert!(app.sessions[0].display_name.is_none());
+}
+
+#[test]
+fn test_apply_update_sets_display_name() {
+    use crate::SessionSnapshot;
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    let mut info = SessionSnapshot {
+        session_id: "named".to_string(),
+        agent_type: "claudecode".to_string(),
+        status: "working".to_string(),
+        working_dir: None,
+        elapsed_seconds: 0,
+        idle_seconds: 0,
+        history: vec![],
+        closed: false,
+        priority: 0,
+        display_name: Some("first".to_string()),
+        last_message: None,
+        terminal_pane: None,
+    };
+    app.apply_update(&info);
+    assert_eq!(app.sessions[0].display_name.as_deref(), Some("first"));
+
+    info.display_name = None;
+    app.apply_update(&info);
+    assert!(app.sessions[0].display_name.is_none());
+}
+
+#[test]
+fn test_apply_update_copies_last_message() {
+    use crate::SessionSnapshot;
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    let mut info = SessionSnapshot {
+        session_id: "chatty".to_string(),
+        agent_type: "claudecode".to_string(),
+        status: "attention".to_string(),
+        working_dir: None,
+        elapsed_seconds: 0,
+        idle_seconds: 0,
+        history: vec![],
+        closed: false,
+        priority: 0,
+        display_name: None,
+        last_message: Some("All tests pass.".to_string()),
+        terminal_pane: None,
+    };
+    app.apply_update(&info);
+    assert_eq!(
+        app.sessions[0].last_message.as_deref(),
+        Some("All tests pass.")
+    );
+
+    info.last_message = Some("Pushed the branch.".to_string());
+    app.apply_update(&info);
+    assert_eq!(
+        app.sessions[0].last_message.as_deref(),
+        Some("Pushed the branch.")
+    );
+}
+
+#[test]
+fn test_apply_update_records_sticky_alert() {
+    use crate::SessionSnapshot;
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    let mut info = SessionSnapshot {
+        session_id: "alerting".to_string(),
+        agent_type: "claudecode".to_string(),
+        status: "working".to_string(),
+        working_dir: Some("/home/user/alert-proj".to_string()),
+        elapsed_seconds: 0,
+        idle_seconds: 0,
+        history: vec![],
+        closed: false,
+        priority: 0,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+    };
+    app.apply_update(&info);
+    assert!(app.alerts.is_empty(), "working raises no alert");
+
+    info.status = "attention".to_string();
+    app.apply_update(&info);
+    // Same status again is not a new event
+    app.apply_update(&info);
+    info.status = "working".to_string();
+    app.apply_update(&info);
+
+    assert_eq!(app.alerts.len(), 1, "alert survives flip back to working");
+    let alert = app.alerts.iter().next().expect("one alert");
+    assert_eq!(alert.status, Status::Attention);
+    assert_eq!(alert.label, "alert-proj");
+
+    app.acknowledge_alert();
+    assert!(app.alerts.is_empty());
+}
+
+#[test]
+fn test_apply_update_applies_project_config() {
+    use crate::config::loader::PROJECT_CONFIG_FILE;
+    use crate::SessionSnapshot;
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join(PROJECT_CONFIG_FILE),
+        "display_name = \"API\"\ntags = [\"backend\"]\nalerts = false\n\n[[activate_hooks]]\ncommand = \"true\"\n",
+    )
+    .expect("write");
+    let nested = dir.path().join("src");
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    let mut info = SessionSnapshot {
+        session_id: "api".to_string(),
+        agent_type: "claudecode".to_string(),
+        status: "attention".to_string(),
+        working_dir: Some(nested.display().to_string()),
+        elapsed_seconds: 0,
+        idle_seconds: 0,
+        history: vec![],
+        closed: false,
+        priority: 0,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+    };
+    app.apply_update(&info);
+    assert_eq!(app.sessions[0].display_name.as_deref(), Some("API"));
+    assert_eq!(app.sessions[0].tags, ["backend"]);
+    assert!(app.alerts.is_empty(), "project turns alerts off");
+
+    // The project's hooks only run once the project is trusted
+    app.execute_hook(0);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert!(msg.contains("tui.trusted_projects"), "{msg}");
+    let mut config = crate::config::schema::Config::default();
+    config.tui.trusted_projects = vec![dir.path().display().to_string()];
+    app.apply_config(config);
+
+    // The project's hooks replace the (empty) user config hooks
+    app.execute_hook(0);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Hook executed");
+
+    // A rename takes precedence over the project's name
+    info.display_name = Some("payments".to_string());
+    app.apply_update(&info);
+    assert_eq!(app.sessions[0].display_name.as_deref(), Some("payments"));
+}
+
+// --- Workspace tab tests ---
+
+/// Builds an app with sessions "api-0", "api-1" in /nonexistent/api and
+/// "web-0" in /nonexistent/web, applied through daemon updates.
+fn make_tabbed_app() -> App {
+    use crate::SessionSnapshot;
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    for (id, dir) in [
+        ("api-0", "/nonexistent/api"),
+        ("api-1", "/nonexistent/api"),
+        ("web-0", "/nonexistent/web"),
+    ] {
+        app.apply_update(&SessionSnapshot {
+            session_id: id.to_string(),
+            agent_type: "claudecode".to_string(),
+            status: "working".to_string(),
+            working_dir: Some(dir.to_string()),
+            elapsed_seconds: 0,
+            idle_seconds: 0,
+            history: vec![],
+            closed: false,
+            priority: 0,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
+        });
+    }
+    app
+}
+
+fn visible_ids(app: &App) -> Vec<&str> {
+    app.sessions.iter().map(|s| s.session_id.as_str()).collect()
+}
+
+#[test]
+fn test_apply_update_builds_project_tabs() {
+    let app = make_tabbed_app();
+    assert_eq!(app.tabs.titles(), ["All", "api", "web"]);
+    assert_eq!(app.tabs.active(), 0);
+    assert_eq!(app.sessions.len(), 3, "All tab shows every session");
+    assert_eq!(app.all_sessions.len(), 3);
+}
+
+#[test]
+fn test_switch_tab_filters_sessions() {
+    let mut app = make_tabbed_app();
+    app.switch_tab(2);
+    assert_eq!(visible_ids(&app), ["web-0"]);
+    assert_eq!(
+        app.selected_index,
+        Some(0),
+        "first visit selects first session"
+    );
+
+    app.switch_tab(9);
+    assert_eq!(app.tabs.active(), 2, "out-of-range tab is ignored");
+}
+
+#[test]
+fn test_switch_tab_restores_selection_per_tab() {
+    let mut app = make_tabbed_app();
+    app.switch_tab(1);
+    app.select_next();
+    let api_selected = app.selected_session().map(|s| s.session_id.clone());
+
+    app.switch_tab(0);
+    app.selected_index = None;
+    app.switch_tab(1);
+    assert_eq!(
+        app.selected_session().map(|s| s.session_id.clone()),
+        api_selected
+    );
+
+    app.switch_tab(0);
+    assert_eq!(app.selected_index, None, "All tab keeps its own selection");
+}
+
+#[test]
+fn test_next_and_previous_tab_wrap() {
+    let mut app = make_tabbed_app();
+    app.previous_tab();
+    assert_eq!(app.tabs.active(), 2);
+    app.next_tab();
+    assert_eq!(app.tabs.active(), 0);
+}
+
+#[test]
+fn test_update_in_other_tab_keeps_active_tab_filtered() {
+    use crate::SessionSnapshot;
+    let mut app = make_tabbed_app();
+    app.switch_tab(2);
+    app.apply_update(&SessionSnapshot {
+        session_id: "api-2".to_string(),
+        agent_type: "claudecode".to_string(),
+        status: "attention".to_string(),
+        working_dir: Some("/nonexistent/api".to_string()),
+        elapsed_seconds: 0,
+        idle_seconds: 0,
+        history: vec![],
+        closed: false,
+        priority: 0,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+    });
+    assert_eq!(visible_ids(&app), ["web-0"]);
+    assert_eq!(app.all_sessions.len(), 4);
+}
+
+#[test]
+fn test_reload_config_applies_settings() {
+    use crate::config::schema::{Config, HookConfig};
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    app.read_only_flag = true;
+    let mut config = Config::default();
+    config.tui.tick_rate = "100ms".to_string();
+    config.tui.activate_hooks = vec![HookConfig {
+        command: "echo hi".to_string(),
+        timeout: 5,
+        ..HookConfig::default()
+    }];
+    app.reload_config(Ok(config));
+    assert_eq!(app.tick_rate, Duration::from_millis(100));
+    assert_eq!(app.activate_hooks.len(), 1);
+    assert!(app.read_only, "--read-only survives reloads");
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Config reloaded");
+}
+
+#[test]
+fn test_reload_config_error_keeps_settings() {
+    use crate::config::error::ConfigError;
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    app.footer_template = "{version}".to_string();
+    app.reload_config(Err(ConfigError::ParseError {
+        path: PathBuf::from("/home/u/.config/acd/config.toml"),
+        line: 3,
+        column: 1,
+        key: None,
+        message: "expected `]`".to_string(),
+    }));
+    assert_eq!(app.footer_template, "{version}");
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(
+        msg,
+        "Config not reloaded: Invalid configuration at /home/u/.config/acd/config.toml:3:1: expected `]`"
+    );
+}
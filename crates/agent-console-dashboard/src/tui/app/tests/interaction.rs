@@ -273,6 +273,7 @@ fn test_double_click_with_activate_hook() {
     app.activate_hooks = vec![HookConfig {
         command: "echo test".to_string(),
         timeout: 5,
+        ..HookConfig::default()
     }];
     let first_click = make_mouse_event(MouseEventKind::Down(MouseButton::Left), 3, 10);
     app.handle_mouse_event(first_click);
@@ -283,6 +284,35 @@ fn test_double_click_with_activate_hook() {
     assert_eq!(msg, "Hook executed");
 }
 
+#[test]
+fn test_failed_hook_reports_stderr_tail() {
+    use crate::config::schema::HookConfig;
+    let mut app = make_clickable_app(3);
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    app.command_failures = Some(tx);
+    app.activate_hooks = vec![
+        HookConfig {
+            command: "echo 'no such tab' >&2; exit 2".to_string(),
+            timeout: 5,
+            ..HookConfig::default()
+        },
+        HookConfig {
+            command: "sleep 5".to_string(),
+            timeout: 1,
+            ..HookConfig::default()
+        },
+    ];
+    app.execute_hook(0);
+    assert_eq!(
+        rx.blocking_recv().as_deref(),
+        Some("activate hook[0] failed: no such tab")
+    );
+    assert_eq!(
+        rx.blocking_recv().as_deref(),
+        Some("activate hook[1] failed: timed out after 1s")
+    );
+}
+
 #[test]
 fn test_double_click_closed_session_fires_reopen_hook() {
     use crate::config::schema::HookConfig;
@@ -291,6 +321,7 @@ fn test_double_click_closed_session_fires_reopen_hook() {
     app.reopen_hooks = vec![HookConfig {
         command: "echo reopen".to_string(),
         timeout: 5,
+        ..HookConfig::default()
     }];
     let first_click = make_mouse_event(MouseEventKind::Down(MouseButton::Left), 3, 10);
     app.handle_mouse_event(first_click);
@@ -322,6 +353,155 @@ fn test_double_click_closed_session_no_reopen_hook() {
     assert_eq!(app.sessions[0].status, Status::Closed);
 }
 
+/// Backend with a pane in the first test session's folder that runs `true`
+/// for every command.
+#[derive(Debug)]
+struct FakeTerminal;
+
+impl crate::integrations::TerminalBackend for FakeTerminal {
+    fn name(&self) -> &'static str {
+        "Fake"
+    }
+
+    fn list_panes(&self) -> Result<Vec<crate::integrations::Pane>, String> {
+        Ok(vec![crate::integrations::Pane {
+            id: "1".to_string(),
+            title: "project-0".to_string(),
+            working_dir: Some(std::path::PathBuf::from("/home/user/project-0")),
+        }])
+    }
+
+    fn focus_pane_command(&self, _pane: &crate::integrations::Pane) -> std::process::Command {
+        std::process::Command::new("true")
+    }
+
+    fn spawn_command(
+        &self,
+        _dir: Option<&std::path::Path>,
+        _title: Option<&str>,
+        _argv: &[String],
+    ) -> std::process::Command {
+        std::process::Command::new("true")
+    }
+}
+
+#[test]
+fn test_terminal_backend_used_without_hooks() {
+    let mut app = make_clickable_app(3);
+    app.terminal = Some(Box::new(FakeTerminal));
+    app.execute_hook(0);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Focused tab in Fake");
+
+    app.execute_hook(2);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Fake: no window found for this session");
+
+    app.sessions[1].status = Status::Closed;
+    app.execute_hook(1);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Resumed in a new Fake pane");
+    assert_eq!(app.sessions[1].status, Status::Attention);
+}
+
+/// Backend whose commands fail like a tmux without a server.
+#[derive(Debug)]
+struct FailingTerminal;
+
+impl crate::integrations::TerminalBackend for FailingTerminal {
+    fn name(&self) -> &'static str {
+        "Failing"
+    }
+
+    fn list_panes(&self) -> Result<Vec<crate::integrations::Pane>, String> {
+        Err("no server running".to_string())
+    }
+
+    fn focus_pane_command(&self, _pane: &crate::integrations::Pane) -> std::process::Command {
+        std::process::Command::new("false")
+    }
+
+    fn spawn_command(
+        &self,
+        _dir: Option<&std::path::Path>,
+        _title: Option<&str>,
+        _argv: &[String],
+    ) -> std::process::Command {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "echo 'no server running' >&2; exit 1"]);
+        command
+    }
+}
+
+#[test]
+fn test_failed_resurrect_is_reported() {
+    let mut app = make_clickable_app(3);
+    app.terminal = Some(Box::new(FailingTerminal));
+    app.sessions[1].status = Status::Closed;
+    app.execute_hook(1);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Failing: no server running");
+    assert_eq!(app.sessions[1].status, Status::Closed);
+}
+
+#[test]
+fn test_embedded_terminal_resumes_closed_session() {
+    use crate::config::schema::{AgentsConfig, CustomAgentConfig};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    let mut app = make_clickable_app(3);
+    app.terminal = Some(Box::new(FakeTerminal));
+    app.embedded_terminal = true;
+    app.agents = AgentRegistry::from_config(&AgentsConfig {
+        custom: vec![CustomAgentConfig {
+            name: "devbot".to_string(),
+            resume: "sleep 30".to_string(),
+            ..CustomAgentConfig::default()
+        }],
+        ..AgentsConfig::default()
+    });
+    app.sessions[1].agent_type = crate::AgentType::Custom("devbot".to_string());
+    app.sessions[1].status = Status::Closed;
+    app.execute_hook(1);
+
+    assert!(app.embedded.is_some(), "should open the embedded terminal");
+    assert!(app.embedded_focused);
+    assert_eq!(app.sessions[1].status, Status::Attention);
+
+    // Keys go to the pane until Ctrl+] hands focus back
+    let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+    assert_eq!(handle_key_event(&mut app, q), Action::None);
+    let toggle = KeyEvent::new(KeyCode::Char(']'), KeyModifiers::CONTROL);
+    handle_key_event(&mut app, toggle);
+    assert!(!app.embedded_focused);
+    assert_eq!(handle_key_event(&mut app, q), Action::Quit);
+
+    // A second session cannot take over the pane
+    app.sessions[2].status = Status::Closed;
+    app.execute_hook(2);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert!(
+        msg.contains("is running in the embedded terminal"),
+        "{}",
+        msg
+    );
+}
+
+#[test]
+fn test_configured_hooks_take_precedence_over_terminal_backend() {
+    use crate::config::schema::HookConfig;
+    let mut app = make_clickable_app(3);
+    app.terminal = Some(Box::new(FakeTerminal));
+    app.activate_hooks = vec![HookConfig {
+        command: "echo test".to_string(),
+        timeout: 5,
+        ..HookConfig::default()
+    }];
+    app.execute_hook(0);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Hook executed");
+}
+
 #[test]
 fn test_expire_status_message_clears_expired() {
     let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
@@ -347,6 +527,196 @@ fn test_expire_status_message_keeps_fresh() {
     assert!(app.status_message.is_some(), "fresh message should be kept");
 }
 
+// --- Sticky alerts tests ---
+
+#[test]
+fn test_click_on_alert_row_acknowledges_it() {
+    let mut app = make_clickable_app(3);
+    app.alerts
+        .record("session-0", "project-0", Status::Attention);
+    app.alerts
+        .record("session-1", "project-1", Status::Question);
+    app.alerts_inner_area = Some(Rect::new(1, 30, 78, 2));
+    app.selected_index = Some(2);
+
+    app.handle_mouse_event(make_mouse_event(
+        MouseEventKind::Down(MouseButton::Left),
+        31,
+        5,
+    ));
+
+    assert_eq!(app.alerts.len(), 1);
+    assert_eq!(
+        app.alerts.iter().next().map(|a| a.session_id.as_str()),
+        Some("session-0")
+    );
+    assert_eq!(app.selected_index, Some(2), "alert click keeps focus");
+}
+
+#[test]
+fn test_click_below_alerts_is_not_an_alert() {
+    let mut app = make_clickable_app(3);
+    app.alerts
+        .record("session-0", "project-0", Status::Attention);
+    app.alerts_inner_area = Some(Rect::new(1, 30, 78, 2));
+    // Row 31 is inside the panel but past the only alert
+    assert_eq!(app.calculate_clicked_alert(31), None);
+    assert_eq!(app.calculate_clicked_alert(30), Some(0));
+}
+
+// --- Usage chart cursor tests ---
+
+fn make_app_with_usage_samples(count: usize) -> App {
+    use crate::tui::usage_history::UsageSample;
+    let mut app = make_app_with_sessions(1);
+    for i in 0..count {
+        app.usage_history.push(UsageSample {
+            at: chrono::Local::now(),
+            five_hour: i as f64,
+            seven_day: 50.0,
+        });
+    }
+    app
+}
+
+#[test]
+fn test_usage_chart_cursor_follows_latest() {
+    let mut app = make_app_with_usage_samples(3);
+    assert_eq!(app.usage_chart_cursor(), None, "not in chart view");
+    app.open_usage_chart();
+    assert_eq!(app.usage_chart_cursor(), Some(2));
+    app.usage_history
+        .push(app.usage_history.get(0).copied().unwrap());
+    assert_eq!(app.usage_chart_cursor(), Some(3), "new sample is followed");
+}
+
+#[test]
+fn test_usage_chart_cursor_moves_and_clamps() {
+    let mut app = make_app_with_usage_samples(5);
+    app.open_usage_chart();
+    app.move_usage_chart_cursor(-2);
+    assert_eq!(app.usage_chart_cursor(), Some(2));
+    assert_eq!(app.view, View::UsageChart { cursor: Some(2) });
+    app.move_usage_chart_cursor(isize::MIN);
+    assert_eq!(app.usage_chart_cursor(), Some(0));
+    app.move_usage_chart_cursor(100);
+    assert_eq!(
+        app.view,
+        View::UsageChart { cursor: None },
+        "reaching the end resumes following"
+    );
+}
+
+#[test]
+fn test_usage_chart_cursor_without_samples() {
+    let mut app = make_app_with_usage_samples(0);
+    app.open_usage_chart();
+    app.move_usage_chart_cursor(-1);
+    assert_eq!(app.usage_chart_cursor(), None);
+}
+
+// --- Dirty flag (event-driven rendering) tests ---
+
+#[test]
+fn test_new_app_is_dirty_and_take_clears() {
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    assert!(app.take_dirty(), "first frame must render");
+    assert!(!app.take_dirty(), "flag is cleared after take");
+}
+
+#[test]
+fn test_expired_status_message_marks_dirty() {
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    app.take_dirty();
+    app.status_message = Some((
+        "old message".to_string(),
+        Instant::now() - Duration::from_secs(1),
+    ));
+    app.refresh_time_based_state();
+    assert!(app.take_dirty());
+}
+
+#[test]
+fn test_idle_app_without_sessions_stays_clean() {
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    app.take_dirty();
+    app.last_elapsed_render = Instant::now() - Duration::from_secs(5);
+    app.refresh_time_based_state();
+    assert!(!app.take_dirty(), "nothing time-based to redraw");
+}
+
+#[test]
+fn test_elapsed_refresh_marks_dirty_only_after_interval() {
+    let mut app = make_app_with_sessions(1);
+    app.take_dirty();
+    app.last_elapsed_render = Instant::now();
+    app.refresh_time_based_state();
+    assert!(!app.take_dirty(), "interval not yet elapsed");
+
+    app.last_elapsed_render = Instant::now() - ELAPSED_TIME_REFRESH_INTERVAL;
+    app.refresh_time_based_state();
+    assert!(app.take_dirty());
+}
+
+#[test]
+fn test_daemon_message_marks_dirty() {
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    app.take_dirty();
+    app.apply_daemon_message(DaemonMessage::UsageBlocked);
+    assert!(app.usage_blocked);
+    assert!(app.take_dirty());
+}
+
+// --- Hover tests ---
+
+#[test]
+fn test_mouse_move_sets_hovered_row() {
+    let mut app = make_clickable_app(5);
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 5, 10));
+    assert_eq!(app.hovered_index, Some(2));
+    assert_eq!(app.hover_position, Some((10, 5)));
+    assert_eq!(
+        app.selected_index,
+        Some(0),
+        "hover must not change selection"
+    );
+}
+
+#[test]
+fn test_mouse_move_outside_list_clears_hover() {
+    let mut app = make_clickable_app(5);
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 4, 10));
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 0, 10));
+    assert_eq!(app.hovered_index, None);
+}
+
+#[test]
+fn test_mouse_move_marks_dirty_only_on_hover_change() {
+    let mut app = make_clickable_app(5);
+    app.hover_tooltips = false;
+    app.take_dirty();
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 4, 10));
+    assert!(app.take_dirty(), "entering a row redraws");
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 4, 20));
+    assert!(
+        !app.take_dirty(),
+        "same row without tooltip needs no redraw"
+    );
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 1, 20));
+    assert!(app.take_dirty(), "leaving the list redraws");
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 0, 20));
+    assert!(!app.take_dirty(), "moving over empty space needs no redraw");
+}
+
+#[test]
+fn test_mouse_move_within_row_redraws_tooltip() {
+    let mut app = make_clickable_app(5);
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 4, 10));
+    app.take_dirty();
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 4, 20));
+    assert!(app.take_dirty(), "tooltip follows the pointer column");
+}
+
 // --- SessionSnapshot conversion test ---
 
 #[test]
@@ -486,6 +856,7 @@ fn test_two_line_double_click_fires_hook() {
     app.activate_hooks = vec![HookConfig {
         command: "echo test".to_string(),
         timeout: 5,
+        ..HookConfig::default()
     }];
 
     // First click
@@ -590,3 +961,17 @@ fn test_no_selection_renders_no_highlight() {
         }
     }
 }
+
+#[test]
+fn test_two_line_mouse_move_hovers_chip() {
+    let mut app = make_two_line_app(10, 80);
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 0, 25));
+    assert_eq!(app.hovered_index, Some(1));
+    assert_eq!(
+        app.selected_index,
+        Some(0),
+        "hover must not change selection"
+    );
+    app.handle_mouse_event(make_mouse_event(MouseEventKind::Moved, 1, 25));
+    assert_eq!(app.hovered_index, None, "line 2 has no chips");
+}
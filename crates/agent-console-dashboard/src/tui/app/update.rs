@@ -1,12 +1,50 @@
 use super::*;
+use crate::config::schema::ProjectConfig;
+use crate::tui::views::dashboard::session_label;
+
+/// Applies the overrides of the session's project: its display name unless
+/// the session was renamed, and its tags.
+///
+/// Returns whether the project wants sticky alerts.
+fn apply_project(session: &mut Session, project: Option<&ProjectConfig>) -> bool {
+    let Some(project) = project else {
+        session.tags.clear();
+        return true;
+    };
+    if session.display_name.is_none() {
+        session.display_name = project.display_name.clone();
+    }
+    session.tags = project.tags.clone();
+    project.alerts
+}
 
 impl App {
-    /// Applies a daemon update message (full `SessionSnapshot`) to the session list.
+    /// Applies a message from the daemon subscription and marks the UI dirty.
+    pub(super) fn apply_daemon_message(&mut self, msg: DaemonMessage) {
+        match msg {
+            DaemonMessage::SessionUpdate(info) => self.apply_update(&info),
+            DaemonMessage::UsageUpdate(data) => {
+                self.usage_history.record(&data);
+                self.usage = Some(data);
+            }
+            DaemonMessage::UsageBlocked => {
+                self.usage_blocked = true;
+            }
+        }
+        self.mark_dirty();
+    }
+
+    /// Applies a daemon update message (full `SessionSnapshot`) to the session
+    /// list, then refreshes the workspace tabs.
     ///
     /// `elapsed_seconds` is the time since the session entered its current
     /// status, as reported by the daemon. We backdate `session.since` by
     /// subtracting this duration from `Instant::now()` so elapsed time
     /// displays correctly even though `Instant` cannot survive IPC.
+    ///
+    /// Entering `attention` or `question` (or first appearing in one) records a
+    /// sticky alert in [`App::alerts`], unless the session's project turns
+    /// alerts off. Project overrides (`.acd.toml`) are applied on every update.
     pub(super) fn apply_update(&mut self, info: &crate::SessionSnapshot) {
         let status: Status = info.status.parse().unwrap_or(Status::Working);
         let backdated_since = Instant::now()
@@ -16,12 +54,14 @@ impl App {
             .checked_sub(Duration::from_secs(info.idle_seconds))
             .unwrap_or_else(Instant::now);
         let working_dir = info.working_dir.as_ref().map(PathBuf::from);
+        let mut added = false;
 
         if let Some(session) = self
-            .sessions
+            .all_sessions
             .iter_mut()
             .find(|s| s.session_id == info.session_id)
         {
+            let mut status_changed = false;
             // Update working_dir from daemon if Some
             if working_dir.is_some() {
                 session.working_dir = working_dir.clone();
@@ -35,14 +75,27 @@ impl App {
                 });
                 session.status = status;
                 session.since = backdated_since;
+                status_changed = true;
             }
             session.last_activity = backdated_activity;
             session.closed = info.closed;
             session.priority = info.priority;
+            session.display_name = info.display_name.clone();
+            session.last_message = info.last_message.clone();
+            session.terminal_pane = info.terminal_pane.clone();
+            let project = session
+                .working_dir
+                .as_deref()
+                .and_then(|dir| self.projects.get(dir));
+            let alerts = apply_project(session, project.as_deref());
+            if status_changed && alerts {
+                self.alerts
+                    .record(&info.session_id, &session_label(session), status);
+            }
         } else {
             let mut session = Session::new(
                 info.session_id.clone(),
-                AgentType::ClaudeCode,
+                info.agent_type.parse().unwrap_or_default(),
                 working_dir.clone(),
             );
             session.status = status;
@@ -50,6 +103,14 @@ impl App {
             session.last_activity = backdated_activity;
             session.closed = info.closed;
             session.priority = info.priority;
+            session.display_name = info.display_name.clone();
+            session.last_message = info.last_message.clone();
+            session.terminal_pane = info.terminal_pane.clone();
+            let project = session
+                .working_dir
+                .as_deref()
+                .and_then(|dir| self.projects.get(dir));
+            let alerts = apply_project(&mut session, project.as_deref());
             // Reconstruct history from wire StatusChange entries
             let now_secs = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -86,14 +147,16 @@ impl App {
                     duration,
                 });
             }
-            self.sessions.push(session);
-            if self.selected_index.is_none() {
-                self.selected_index = Some(0);
+            if alerts {
+                self.alerts
+                    .record(&info.session_id, &session_label(&session), status);
             }
+            self.all_sessions.push(session);
+            added = true;
         }
 
         // Sort sessions: status group → priority (desc) → elapsed (desc)
-        self.sessions.sort_by(|a, b| {
+        self.all_sessions.sort_by(|a, b| {
             use std::cmp::Reverse;
 
             // Determine status group for sorting
@@ -123,5 +186,10 @@ impl App {
                 Reverse(b_elapsed),
             ))
         });
+
+        self.sync_tab_sessions();
+        if added && self.selected_index.is_none() {
+            self.init_selection();
+        }
     }
 }
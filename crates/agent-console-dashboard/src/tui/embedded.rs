@@ -0,0 +1,341 @@
+//! Embedded terminal pane running a resumed session inside the TUI.
+//!
+//! With `tui.embedded_terminal` enabled, resuming a closed session spawns the
+//! agent's resume command on a pseudo-terminal (`portable-pty`) instead of in
+//! a pane of the surrounding terminal. A reader thread feeds the output into a
+//! `vt100` screen, which [`crate::tui::views::embedded`] draws below the
+//! dashboard, and wakes the event loop over a channel it connects with
+//! [`crate::tui::app::App::run`]. Key presses are encoded as the bytes an
+//! xterm would send and written to the pty while the pane has focus.
+
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use tokio::sync::mpsc;
+
+use crate::integrations::pane_name;
+use crate::Session;
+
+/// Lines of scrollback kept by the screen.
+const SCROLLBACK: usize = 1000;
+
+/// A process running on a pseudo-terminal, with its emulated screen.
+///
+/// Dropping it kills the process.
+pub struct EmbeddedTerminal {
+    session_id: String,
+    title: String,
+    parser: Arc<Mutex<vt100::Parser>>,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    size: (u16, u16),
+}
+
+impl std::fmt::Debug for EmbeddedTerminal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddedTerminal")
+            .field("session_id", &self.session_id)
+            .field("title", &self.title)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EmbeddedTerminal {
+    /// Runs `resume` in the working directory of `session` on a new
+    /// `rows` x `cols` pseudo-terminal.
+    ///
+    /// Every chunk of output is followed by a message on `output`, if given.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the pty cannot be opened or the command cannot
+    /// be started.
+    pub fn spawn(
+        session: &Session,
+        resume: &[String],
+        (rows, cols): (u16, u16),
+        output: Option<mpsc::UnboundedSender<()>>,
+    ) -> Result<Self, String> {
+        let (program, args) = resume
+            .split_first()
+            .ok_or_else(|| "empty resume command".to_string())?;
+        let pair = native_pty_system()
+            .openpty(pty_size(rows, cols))
+            .map_err(|e| format!("failed to open pty: {}", e))?;
+
+        let mut command = CommandBuilder::new(program);
+        command.args(args);
+        if let Some(dir) = &session.working_dir {
+            command.cwd(dir);
+        }
+        command.env("TERM", "xterm-256color");
+        let child = pair
+            .slave
+            .spawn_command(command)
+            .map_err(|e| format!("failed to run {}: {}", program, e))?;
+        // Only the child holds the slave now, so reads end when it exits
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| format!("failed to read pty: {}", e))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| format!("failed to write pty: {}", e))?;
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK)));
+
+        let screen = Arc::clone(&parser);
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = match reader.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                if let Ok(mut parser) = screen.lock() {
+                    parser.process(&buf[..n]);
+                }
+                if let Some(output) = &output {
+                    if output.send(()).is_err() {
+                        break;
+                    }
+                }
+            }
+            // Wake the event loop once more so it notices the exit
+            if let Some(output) = &output {
+                let _ = output.send(());
+            }
+        });
+
+        Ok(Self {
+            session_id: session.session_id.clone(),
+            title: pane_name(session).unwrap_or_else(|| session.session_id.clone()),
+            parser,
+            master: pair.master,
+            writer,
+            child,
+            size: (rows, cols),
+        })
+    }
+
+    /// ID of the session running in the pane.
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Pane title: the session's display or folder name.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Calls `f` with the current screen contents.
+    pub fn with_screen<R>(&self, f: impl FnOnce(&vt100::Screen) -> R) -> R {
+        let parser = self.parser.lock().unwrap_or_else(|e| e.into_inner());
+        f(parser.screen())
+    }
+
+    /// Resizes the pty and screen to `rows` x `cols`, if they changed.
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        if self.size == (rows, cols) || rows == 0 || cols == 0 {
+            return;
+        }
+        self.size = (rows, cols);
+        if let Err(e) = self.master.resize(pty_size(rows, cols)) {
+            tracing::warn!("failed to resize embedded terminal: {}", e);
+        }
+        self.parser
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .set_size(rows, cols);
+    }
+
+    /// Sends `key` to the process.
+    pub fn send_key(&mut self, key: KeyEvent) {
+        let application_cursor = self.with_screen(|screen| screen.application_cursor());
+        let bytes = key_bytes(key, application_cursor);
+        if bytes.is_empty() {
+            return;
+        }
+        if let Err(e) = self
+            .writer
+            .write_all(&bytes)
+            .and_then(|()| self.writer.flush())
+        {
+            tracing::debug!("failed to write to embedded terminal: {}", e);
+        }
+    }
+
+    /// How the process exited, or `None` while it runs.
+    pub fn exit_status(&mut self) -> Option<String> {
+        match self.child.try_wait() {
+            Ok(Some(status)) if status.success() => Some("exited".to_string()),
+            Ok(Some(status)) => Some(format!("exited with code {}", status.exit_code())),
+            Ok(None) => None,
+            Err(e) => Some(format!("wait failed: {}", e)),
+        }
+    }
+}
+
+impl Drop for EmbeddedTerminal {
+    fn drop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(None)) {
+            let _ = self.child.kill();
+        }
+    }
+}
+
+fn pty_size(rows: u16, cols: u16) -> PtySize {
+    PtySize {
+        rows,
+        cols,
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// Whether `key` switches focus between the dashboard and the pane (Ctrl+]).
+pub fn is_focus_toggle(key: &KeyEvent) -> bool {
+    // Terminals without the kitty keyboard protocol send 0x1d, which
+    // crossterm reports as Ctrl+5
+    key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(key.code, KeyCode::Char(']') | KeyCode::Char('5'))
+}
+
+/// Bytes an xterm sends for `key`.
+///
+/// With `application_cursor` (DECCKM), arrows, Home and End use SS3
+/// sequences. Unsupported keys encode to nothing.
+pub fn key_bytes(key: KeyEvent, application_cursor: bool) -> Vec<u8> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let cursor = |c: char| -> Vec<u8> {
+        if application_cursor {
+            format!("\x1bO{}", c).into_bytes()
+        } else {
+            format!("\x1b[{}", c).into_bytes()
+        }
+    };
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if ctrl => match c.to_ascii_lowercase() {
+            c @ 'a'..='z' => vec![c as u8 - b'a' + 1],
+            ' ' | '@' => vec![0],
+            '[' => vec![0x1b],
+            '\\' => vec![0x1c],
+            ']' => vec![0x1d],
+            '^' => vec![0x1e],
+            '_' => vec![0x1f],
+            _ => c.to_string().into_bytes(),
+        },
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => cursor('A'),
+        KeyCode::Down => cursor('B'),
+        KeyCode::Right => cursor('C'),
+        KeyCode::Left => cursor('D'),
+        KeyCode::Home => cursor('H'),
+        KeyCode::End => cursor('F'),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n @ 1..=4) => format!("\x1bO{}", (b'P' + n - 1) as char).into_bytes(),
+        KeyCode::F(n @ 5..=12) => {
+            let code = [15, 17, 18, 19, 20, 21, 23, 24][usize::from(n - 5)];
+            format!("\x1b[{}~", code).into_bytes()
+        }
+        _ => Vec::new(),
+    };
+    if alt && !bytes.is_empty() {
+        bytes.insert(0, 0x1b);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_key_bytes() {
+        let none = KeyModifiers::NONE;
+        assert_eq!(key_bytes(key(KeyCode::Char('a'), none), false), b"a");
+        assert_eq!(
+            key_bytes(key(KeyCode::Char('c'), KeyModifiers::CONTROL), false),
+            [3]
+        );
+        assert_eq!(
+            key_bytes(key(KeyCode::Char('b'), KeyModifiers::ALT), false),
+            b"\x1bb"
+        );
+        assert_eq!(key_bytes(key(KeyCode::Enter, none), false), b"\r");
+        assert_eq!(key_bytes(key(KeyCode::Up, none), false), b"\x1b[A");
+        assert_eq!(key_bytes(key(KeyCode::Up, none), true), b"\x1bOA");
+        assert_eq!(key_bytes(key(KeyCode::F(5), none), false), b"\x1b[15~");
+        assert!(key_bytes(key(KeyCode::CapsLock, none), false).is_empty());
+    }
+
+    #[test]
+    fn test_focus_toggle() {
+        assert!(is_focus_toggle(&key(
+            KeyCode::Char(']'),
+            KeyModifiers::CONTROL
+        )));
+        assert!(!is_focus_toggle(&key(
+            KeyCode::Char(']'),
+            KeyModifiers::NONE
+        )));
+    }
+
+    #[test]
+    fn test_runs_command_in_working_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let session = Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from(dir.path())),
+        );
+        let resume = [
+            "sh".to_string(),
+            "-c".to_string(),
+            "pwd; exit 3".to_string(),
+        ];
+        let mut terminal =
+            EmbeddedTerminal::spawn(&session, &resume, (5, 200), None).expect("spawn");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let status = loop {
+            if let Some(status) = terminal.exit_status() {
+                break status;
+            }
+            assert!(Instant::now() < deadline, "command did not exit");
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(status, "exited with code 3");
+        // The reader thread may still be processing the last output
+        while !terminal
+            .with_screen(|screen| screen.contents())
+            .contains(&dir.path().display().to_string())
+        {
+            assert!(Instant::now() < deadline, "output not shown");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(terminal.session_id(), "abc");
+    }
+}
@@ -10,6 +10,9 @@ use futures::StreamExt;
 use std::time::Duration;
 use tokio::time::interval;
 
+/// Dashboard keys with a built-in action, which `tui.keys` cannot rebind.
+pub const RESERVED_KEYS: &str = "qjkrR:aAugtsSod1234";
+
 /// Application-level event variants.
 #[derive(Debug, Clone, Copy)]
 pub enum Event {
@@ -90,15 +93,71 @@ pub enum Action {
     ScrollHistoryUp,
     /// Copy session ID to clipboard.
     CopySessionId(String),
+    /// Rename the session with the given ID to the given display name
+    /// (blank clears the name).
+    Rename(String, String),
+    /// Open the pull request at the given URL in the browser.
+    OpenPullRequest(String),
+}
+
+impl Action {
+    /// Returns a short name for actions that change daemon or session state.
+    ///
+    /// Used by read-only mode to block the action and explain why.
+    pub fn mutation_name(&self) -> Option<&'static str> {
+        match self {
+            Action::Resurrect(_) => Some("resurrect"),
+            Action::Remove(_) => Some("remove"),
+            Action::Rename(_, _) => Some("rename"),
+            _ => None,
+        }
+    }
 }
 
 /// Handles a key event by dispatching to the appropriate app method or action.
 ///
 /// When the detail view is active, keys are routed to detail-specific handlers
 /// (scroll, resurrect, close, escape). Otherwise, dashboard navigation applies.
+/// In read-only mode, mutating actions are replaced by [`Action::None`] and a
+/// footer message explains why.
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Action {
+    let action = dispatch_key_event(app, key);
+    match action.mutation_name() {
+        Some(name) if app.block_in_read_only(name) => Action::None,
+        _ => action,
+    }
+}
+
+/// Maps a key event to an app update or action, ignoring read-only mode.
+fn dispatch_key_event(app: &mut App, key: KeyEvent) -> Action {
     use crate::tui::app::View;
 
+    // Ctrl+] switches focus; the focused embedded terminal gets every other key
+    if app.embedded.is_some() && crate::tui::embedded::is_focus_toggle(&key) {
+        app.embedded_focused = !app.embedded_focused;
+        return Action::None;
+    }
+    if app.embedded_focused {
+        if let Some(embedded) = app.embedded.as_mut() {
+            embedded.send_key(key);
+            return Action::None;
+        }
+    }
+
+    // Inline rename captures all keys (so 'q' can be typed), except Ctrl+C
+    if app.rename.is_some()
+        && !(key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c'))
+    {
+        return handle_rename_key(app, key);
+    }
+
+    // The command palette captures keys until it closes, except Ctrl+C
+    if app.palette.is_some()
+        && !(key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c'))
+    {
+        return handle_palette_key(app, key);
+    }
+
     // Global: quit always works
     match key.code {
         KeyCode::Char('q') => return Action::Quit,
@@ -111,6 +170,21 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Action {
         return handle_detail_key(app, key, session_index);
     }
 
+    if let View::UsageChart { .. } = app.view {
+        return handle_usage_chart_key(app, key);
+    }
+
+    // `g` prefix: gt/gT cycle workspace tabs, g0-g9 jump to a tab (0 = All)
+    if std::mem::take(&mut app.pending_g) {
+        match key.code {
+            KeyCode::Char('t') => app.next_tab(),
+            KeyCode::Char('T') => app.previous_tab(),
+            KeyCode::Char(c @ '0'..='9') => app.switch_tab(c as usize - '0' as usize),
+            _ => {}
+        }
+        return Action::None;
+    }
+
     // Dashboard view key handling
     match key.code {
         KeyCode::Char('j') | KeyCode::Down => {
@@ -166,6 +240,38 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Action {
             }
             Action::None
         }
+        KeyCode::Char('R') => {
+            // 'R' opens an inline rename on the selected session
+            app.start_rename();
+            Action::None
+        }
+        KeyCode::Char(':') => {
+            // ':' opens the command palette for the selected session
+            app.open_command_palette();
+            Action::None
+        }
+        KeyCode::Char('a') => {
+            // 'a' acknowledges the oldest sticky alert
+            app.acknowledge_alert();
+            Action::None
+        }
+        KeyCode::Char('A') => {
+            app.acknowledge_all_alerts();
+            Action::None
+        }
+        KeyCode::Char('u') => {
+            app.open_usage_chart();
+            Action::None
+        }
+        KeyCode::Char('g') => {
+            app.pending_g = true;
+            Action::None
+        }
+        KeyCode::Char('t') => {
+            // 't' toggles relative/absolute elapsed times
+            app.toggle_time_format();
+            Action::None
+        }
         KeyCode::Char('s') | KeyCode::Char('S') => {
             // 's' copies session ID when session is selected
             if let Some(session) = app.selected_session() {
@@ -174,6 +280,13 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Action {
                 Action::None
             }
         }
+        KeyCode::Char('o') => {
+            // 'o' opens the pull request of the selected session's branch
+            match app.selected_session().and_then(|s| app.pull_request(s)) {
+                Some(pr) => Action::OpenPullRequest(pr.url.clone()),
+                None => Action::None,
+            }
+        }
         KeyCode::Char('d') => {
             if let Some(session) = app.selected_session() {
                 Action::Remove(session.session_id.clone())
@@ -187,10 +300,87 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Action {
             app.selected_index = None;
             Action::None
         }
+        KeyCode::Char(c) if app.key_hooks.contains_key(&c) => {
+            // Keys bound to [hooks] entries in tui.keys
+            if let Some(idx) = app.selected_index {
+                app.run_key_hook(c, idx);
+            }
+            Action::None
+        }
         _ => Action::None,
     }
 }
 
+/// Handles key events while an inline rename is active.
+///
+/// Enter submits the name, Esc cancels, and every other key edits the input.
+fn handle_rename_key(app: &mut App, key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter => match app.rename.take() {
+            Some(rename) => Action::Rename(rename.session_id, rename.input.value().to_string()),
+            None => Action::None,
+        },
+        KeyCode::Esc => {
+            app.rename = None;
+            Action::None
+        }
+        _ => {
+            if let Some(rename) = app.rename.as_mut() {
+                rename.input.handle_key(key);
+            }
+            Action::None
+        }
+    }
+}
+
+/// Handles key events while the command palette is open.
+///
+/// j/k and the arrow keys move the highlight, Enter runs the highlighted
+/// command, 1-9 run the command with that number, and Esc, `q` or `:` close
+/// the palette.
+fn handle_palette_key(app: &mut App, key: KeyEvent) -> Action {
+    let count = app.session_commands.len();
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            if let Some(palette) = app.palette.as_mut() {
+                palette.move_selection(1, count);
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            if let Some(palette) = app.palette.as_mut() {
+                palette.move_selection(-1, count);
+            }
+        }
+        KeyCode::Enter => app.run_session_command(),
+        KeyCode::Char(c @ '1'..='9') => {
+            let index = c as usize - '1' as usize;
+            if let Some(palette) = app.palette.as_mut().filter(|_| index < count) {
+                palette.selected = index;
+                app.run_session_command();
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char(':') => app.palette = None,
+        _ => {}
+    }
+    Action::None
+}
+
+/// Handles key events in the usage history chart.
+///
+/// h/l and the arrow keys move the cursor one sample, Home/End jump to the
+/// oldest/latest sample, and Esc or `u` returns to the dashboard.
+fn handle_usage_chart_key(app: &mut App, key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Left => app.move_usage_chart_cursor(-1),
+        KeyCode::Char('l') | KeyCode::Right => app.move_usage_chart_cursor(1),
+        KeyCode::Home => app.move_usage_chart_cursor(isize::MIN),
+        KeyCode::End => app.move_usage_chart_cursor(isize::MAX),
+        KeyCode::Esc | KeyCode::Char('u') => app.view = crate::tui::app::View::Dashboard,
+        _ => {}
+    }
+    Action::None
+}
+
 /// Handles key events when the detail view is active.
 ///
 /// When a `Resurrect` action is returned, the caller should use hook-based reopen
@@ -223,6 +413,16 @@ fn handle_detail_key(app: &App, key: KeyEvent, session_index: usize) -> Action {
                 Action::None
             }
         }
+        KeyCode::Char('o') | KeyCode::Char('O') => {
+            match app
+                .sessions
+                .get(session_index)
+                .and_then(|s| app.pull_request(s))
+            {
+                Some(pr) => Action::OpenPullRequest(pr.url.clone()),
+                None => Action::None,
+            }
+        }
         KeyCode::Char('j') | KeyCode::Down => Action::ScrollHistoryDown,
         KeyCode::Char('k') | KeyCode::Up => Action::ScrollHistoryUp,
         _ => Action::None,
@@ -147,6 +147,7 @@ fn test_handle_enter_fires_activate_hook() {
     app.activate_hooks = vec![HookConfig {
         command: "echo test".to_string(),
         timeout: 5,
+        ..HookConfig::default()
     }];
     let action = handle_key_event(&mut app, make_key(KeyCode::Enter, KeyModifiers::NONE));
     assert_eq!(action, Action::None);
@@ -155,6 +156,37 @@ fn test_handle_enter_fires_activate_hook() {
     assert_eq!(msg, "Hook executed");
 }
 
+#[test]
+fn test_handle_bound_key_runs_library_hook() {
+    use crate::config::schema::HookConfig;
+    let mut app = make_app_with_sessions(1);
+    app.hook_library.insert(
+        "open-editor".to_string(),
+        HookConfig {
+            command: "echo {working_dir}".to_string(),
+            ..HookConfig::default()
+        },
+    );
+    app.key_hooks.insert('e', "open-editor".to_string());
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('e'), KeyModifiers::NONE));
+    assert_eq!(action, Action::None);
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Hook open-editor executed");
+}
+
+#[test]
+fn test_handle_enter_reports_unknown_library_hook() {
+    use crate::config::schema::HookConfig;
+    let mut app = make_app_with_sessions(1);
+    app.activate_hooks = vec![HookConfig {
+        hook: "missing".to_string(),
+        ..HookConfig::default()
+    }];
+    handle_key_event(&mut app, make_key(KeyCode::Enter, KeyModifiers::NONE));
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "activate hook[0]: unknown hook `missing`");
+}
+
 #[test]
 fn test_handle_enter_no_hook_shows_message() {
     let mut app = make_app_with_sessions(3);
@@ -181,6 +213,7 @@ fn test_handle_enter_closed_session_fires_reopen_hook() {
     app.reopen_hooks = vec![HookConfig {
         command: "echo reopen".to_string(),
         timeout: 5,
+        ..HookConfig::default()
     }];
     let action = handle_key_event(&mut app, make_key(KeyCode::Enter, KeyModifiers::NONE));
     assert_eq!(action, Action::None);
@@ -199,6 +232,7 @@ fn test_handle_r_fires_reopen_hook() {
     app.reopen_hooks = vec![HookConfig {
         command: "echo reopen".to_string(),
         timeout: 5,
+        ..HookConfig::default()
     }];
     let action = handle_key_event(&mut app, make_key(KeyCode::Char('r'), KeyModifiers::NONE));
     assert_eq!(action, Action::None);
@@ -407,3 +441,287 @@ fn test_detail_view_layout_keys_ignored() {
     let action = handle_key_event(&mut app, make_key(KeyCode::Char('1'), KeyModifiers::NONE));
     assert_eq!(action, Action::None);
 }
+
+// --- Inline rename tests ---
+
+#[test]
+fn test_handle_key_shift_r_starts_rename() {
+    let mut app = make_app_with_sessions(2);
+    app.selected_index = Some(1);
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('R'), KeyModifiers::SHIFT));
+    assert_eq!(action, Action::None);
+    let rename = app.rename.as_ref().expect("rename should be active");
+    assert_eq!(rename.session_id, "session-1");
+    assert_eq!(rename.input.value(), "");
+}
+
+#[test]
+fn test_handle_key_shift_r_without_selection_is_noop() {
+    let mut app = make_app_with_sessions(2);
+    app.selected_index = None;
+    handle_key_event(&mut app, make_key(KeyCode::Char('R'), KeyModifiers::SHIFT));
+    assert!(app.rename.is_none());
+}
+
+#[test]
+fn test_handle_key_a_acknowledges_alerts() {
+    let mut app = make_app_with_sessions(1);
+    app.alerts
+        .record("session-0", "p", crate::Status::Attention);
+    app.alerts.record("session-0", "p", crate::Status::Question);
+    app.alerts
+        .record("session-0", "p", crate::Status::Attention);
+    handle_key_event(&mut app, make_key(KeyCode::Char('a'), KeyModifiers::NONE));
+    assert_eq!(app.alerts.len(), 2);
+    handle_key_event(&mut app, make_key(KeyCode::Char('A'), KeyModifiers::SHIFT));
+    assert!(app.alerts.is_empty());
+}
+
+#[test]
+fn test_handle_key_u_opens_and_closes_usage_chart() {
+    use crate::tui::app::View;
+    let mut app = make_app_with_sessions(2);
+    handle_key_event(&mut app, make_key(KeyCode::Char('u'), KeyModifiers::NONE));
+    assert_eq!(app.view, View::UsageChart { cursor: None });
+
+    // Navigation keys drive the chart, not the session list
+    handle_key_event(&mut app, make_key(KeyCode::Char('j'), KeyModifiers::NONE));
+    assert_eq!(app.selected_index, Some(0));
+
+    handle_key_event(&mut app, make_key(KeyCode::Esc, KeyModifiers::NONE));
+    assert_eq!(app.view, View::Dashboard);
+}
+
+#[test]
+fn test_handle_key_t_toggles_time_format() {
+    use crate::config::schema::TimeFormat;
+    let mut app = make_app_with_sessions(1);
+    assert_eq!(app.time_format, TimeFormat::Relative);
+    handle_key_event(&mut app, make_key(KeyCode::Char('t'), KeyModifiers::NONE));
+    assert_eq!(app.time_format, TimeFormat::Absolute);
+    assert!(app.status_message.is_some(), "toggle shows the new mode");
+    handle_key_event(&mut app, make_key(KeyCode::Char('t'), KeyModifiers::NONE));
+    assert_eq!(app.time_format, TimeFormat::Relative);
+}
+
+#[test]
+fn test_read_only_blocks_remove_with_message() {
+    let mut app = make_app_with_sessions(2);
+    app.read_only = true;
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('d'), KeyModifiers::NONE));
+    assert_eq!(action, Action::None);
+    let (msg, _) = app
+        .status_message
+        .as_ref()
+        .expect("toast explains the block");
+    assert!(msg.contains("Read-only"), "got: {msg}");
+}
+
+#[test]
+fn test_read_only_blocks_rename_and_hooks() {
+    let mut app = make_app_with_sessions(2);
+    app.read_only = true;
+    handle_key_event(&mut app, make_key(KeyCode::Char('R'), KeyModifiers::SHIFT));
+    assert!(app.rename.is_none(), "rename input must not open");
+
+    app.status_message = None;
+    handle_key_event(&mut app, make_key(KeyCode::Enter, KeyModifiers::NONE));
+    let (msg, _) = app
+        .status_message
+        .as_ref()
+        .expect("toast explains the block");
+    assert_eq!(msg, "Read-only mode: hooks disabled");
+}
+
+#[test]
+fn test_read_only_allows_navigation_and_copy() {
+    let mut app = make_app_with_sessions(2);
+    app.read_only = true;
+    handle_key_event(&mut app, make_key(KeyCode::Char('j'), KeyModifiers::NONE));
+    assert_eq!(app.selected_index, Some(1));
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('s'), KeyModifiers::NONE));
+    assert_eq!(action, Action::CopySessionId("session-1".to_string()));
+}
+
+#[test]
+fn test_rename_captures_q_instead_of_quitting() {
+    let mut app = make_app_with_sessions(1);
+    app.start_rename();
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('q'), KeyModifiers::NONE));
+    assert_eq!(action, Action::None);
+    assert_eq!(app.rename.as_ref().map(|r| r.input.value()), Some("q"));
+}
+
+#[test]
+fn test_rename_enter_submits_and_closes_input() {
+    let mut app = make_app_with_sessions(1);
+    app.start_rename();
+    for c in "api".chars() {
+        handle_key_event(&mut app, make_key(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    let action = handle_key_event(&mut app, make_key(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(
+        action,
+        Action::Rename("session-0".to_string(), "api".to_string())
+    );
+    assert!(app.rename.is_none());
+}
+
+#[test]
+fn test_rename_esc_cancels_without_action() {
+    let mut app = make_app_with_sessions(1);
+    app.start_rename();
+    handle_key_event(&mut app, make_key(KeyCode::Char('x'), KeyModifiers::NONE));
+    let action = handle_key_event(&mut app, make_key(KeyCode::Esc, KeyModifiers::NONE));
+    assert_eq!(action, Action::None);
+    assert!(app.rename.is_none());
+    assert_eq!(
+        app.selected_index,
+        Some(0),
+        "Esc should not clear selection"
+    );
+}
+
+#[test]
+fn test_rename_ctrl_c_still_quits() {
+    let mut app = make_app_with_sessions(1);
+    app.start_rename();
+    let action = handle_key_event(
+        &mut app,
+        make_key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+    );
+    assert_eq!(action, Action::Quit);
+}
+
+// --- Workspace tab key tests ---
+
+fn make_tabbed_app() -> App {
+    let mut app = App::new(PathBuf::from("/tmp/test.sock"), None);
+    for (id, dir) in [("a", "/nonexistent/api"), ("b", "/nonexistent/web")] {
+        app.all_sessions.push(Session::new(
+            id.to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from(dir)),
+        ));
+    }
+    app.sync_tab_sessions();
+    app.init_selection();
+    app
+}
+
+#[test]
+fn test_gt_and_g_shift_t_cycle_tabs() {
+    let mut app = make_tabbed_app();
+    handle_key_event(&mut app, make_key(KeyCode::Char('g'), KeyModifiers::NONE));
+    handle_key_event(&mut app, make_key(KeyCode::Char('t'), KeyModifiers::NONE));
+    assert_eq!(app.tabs.active(), 1);
+    assert!(!app.pending_g);
+
+    handle_key_event(&mut app, make_key(KeyCode::Char('g'), KeyModifiers::NONE));
+    handle_key_event(&mut app, make_key(KeyCode::Char('T'), KeyModifiers::SHIFT));
+    assert_eq!(app.tabs.active(), 0);
+}
+
+#[test]
+fn test_g_digit_jumps_to_tab() {
+    let mut app = make_tabbed_app();
+    handle_key_event(&mut app, make_key(KeyCode::Char('g'), KeyModifiers::NONE));
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('2'), KeyModifiers::NONE));
+    assert_eq!(action, Action::None, "g2 must not switch layout");
+    assert_eq!(app.tabs.active(), 2);
+    assert_eq!(app.sessions.len(), 1);
+}
+
+#[test]
+fn test_g_then_other_key_is_consumed() {
+    let mut app = make_tabbed_app();
+    handle_key_event(&mut app, make_key(KeyCode::Char('g'), KeyModifiers::NONE));
+    handle_key_event(&mut app, make_key(KeyCode::Char('j'), KeyModifiers::NONE));
+    assert_eq!(app.selected_index, Some(0), "gj is not a navigation");
+    assert!(!app.pending_g);
+    assert_eq!(app.tabs.active(), 0);
+}
+
+#[test]
+fn test_handle_o_opens_pull_request() {
+    use crate::integrations::github::{BranchInfo, ChecksState, PrState, PullRequest};
+
+    let mut app = make_app_with_sessions(1);
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('o'), KeyModifiers::NONE));
+    assert_eq!(action, Action::None);
+
+    let info = BranchInfo {
+        branch: "feature".to_string(),
+        issue: None,
+        pull_request: Some(PullRequest {
+            number: 3,
+            title: "Feature".to_string(),
+            url: "https://github.com/o/r/pull/3".to_string(),
+            state: PrState::Open,
+            checks: ChecksState::None,
+            review: None,
+        }),
+    };
+    app.github
+        .apply((PathBuf::from("/home/user/project-0"), Ok(Some(info))));
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('o'), KeyModifiers::NONE));
+    assert_eq!(
+        action,
+        Action::OpenPullRequest("https://github.com/o/r/pull/3".to_string())
+    );
+}
+
+#[test]
+fn test_colon_opens_command_palette() {
+    use crate::config::schema::SessionCommandConfig;
+
+    let mut app = make_app_with_sessions(2);
+    let colon = make_key(KeyCode::Char(':'), KeyModifiers::NONE);
+    handle_key_event(&mut app, colon);
+    assert!(app.palette.is_none());
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "No commands configured ([[tui.commands]])");
+
+    app.session_commands = ["lazygit", "tests"]
+        .iter()
+        .map(|name| SessionCommandConfig {
+            name: name.to_string(),
+            command: name.to_string(),
+        })
+        .collect();
+    handle_key_event(&mut app, make_key(KeyCode::Char('j'), KeyModifiers::NONE));
+    handle_key_event(&mut app, colon);
+    assert_eq!(
+        app.palette.as_ref().map(|p| p.session_id.as_str()),
+        Some("session-1")
+    );
+
+    // Navigation and `q` stay inside the palette
+    handle_key_event(&mut app, make_key(KeyCode::Char('k'), KeyModifiers::NONE));
+    assert_eq!(app.palette.as_ref().map(|p| p.selected), Some(1));
+    assert_eq!(app.selected_index, Some(1));
+    let action = handle_key_event(&mut app, make_key(KeyCode::Char('q'), KeyModifiers::NONE));
+    assert_eq!(action, Action::None);
+    assert!(app.palette.is_none());
+
+    // Running without a backend closes the palette with a message
+    handle_key_event(&mut app, colon);
+    handle_key_event(&mut app, make_key(KeyCode::Char('2'), KeyModifiers::NONE));
+    assert!(app.palette.is_none());
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "No terminal backend to run tests in");
+}
+
+#[test]
+fn test_read_only_blocks_command_palette() {
+    let mut app = make_app_with_sessions(1);
+    app.read_only = true;
+    app.session_commands = vec![crate::config::schema::SessionCommandConfig {
+        name: "lazygit".to_string(),
+        command: "lazygit".to_string(),
+    }];
+    handle_key_event(&mut app, make_key(KeyCode::Char(':'), KeyModifiers::NONE));
+    assert!(app.palette.is_none());
+    let (msg, _) = app.status_message.as_ref().expect("msg");
+    assert_eq!(msg, "Read-only mode: commands disabled");
+}
@@ -0,0 +1,219 @@
+//! User-configurable footer templates.
+//!
+//! Expands `tui.footer` placeholders such as `{attention_count}` or
+//! `{usage_5h}` into the text shown on the dashboard's bottom line. The
+//! template is re-rendered every frame, so values stay live.
+
+use crate::{Session, Status};
+use claude_usage::UsageData;
+use std::path::Path;
+
+/// Placeholders recognized in footer templates, in documentation order.
+pub const FOOTER_PLACEHOLDERS: &[&str] = &[
+    "keys_hint",
+    "session_count",
+    "attention_count",
+    "working_count",
+    "question_count",
+    "usage_5h",
+    "usage_7d",
+    "socket",
+    "version",
+];
+
+/// Live values substituted into a footer template.
+#[derive(Debug, Clone, Copy)]
+pub struct FooterValues<'a> {
+    /// Sessions currently shown in the dashboard.
+    pub sessions: &'a [Session],
+    /// Latest API usage data, if any.
+    pub usage: Option<&'a UsageData>,
+    /// Whether the usage API is blocked (403).
+    pub usage_blocked: bool,
+    /// Daemon socket path.
+    pub socket: &'a Path,
+    /// Built-in keybinding hints.
+    pub keys_hint: &'a str,
+}
+
+impl FooterValues<'_> {
+    /// Returns the value for a placeholder name, or None if unrecognized.
+    fn lookup(&self, name: &str) -> Option<String> {
+        let count = |status: Status| {
+            self.sessions
+                .iter()
+                .filter(|s| !s.closed && s.status == status)
+                .count()
+                .to_string()
+        };
+        let value = match name {
+            "keys_hint" => self.keys_hint.to_string(),
+            "session_count" => self
+                .sessions
+                .iter()
+                .filter(|s| !s.closed)
+                .count()
+                .to_string(),
+            "attention_count" => count(Status::Attention),
+            "working_count" => count(Status::Working),
+            "question_count" => count(Status::Question),
+            "usage_5h" => self.usage_percent(|u| u.five_hour.utilization),
+            "usage_7d" => self.usage_percent(|u| u.seven_day.utilization),
+            "socket" => self.socket.display().to_string(),
+            "version" => concat!("v", env!("CARGO_PKG_VERSION")).to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Formats a usage percentage like the API usage widget (`42%`, `--`, `blocked`).
+    fn usage_percent(&self, pick: impl Fn(&UsageData) -> f64) -> String {
+        match self.usage {
+            Some(usage) => format!("{:.0}%", pick(usage).floor()),
+            None if self.usage_blocked => "blocked".to_string(),
+            None => "--".to_string(),
+        }
+    }
+}
+
+/// Expands `{placeholder}` tokens in `template` using `values`.
+///
+/// Unknown placeholders and unmatched braces are kept verbatim, so a typo
+/// shows up in the footer instead of silently disappearing.
+pub fn render_footer_template(template: &str, values: &FooterValues<'_>) -> String {
+    render_placeholders(template, |name| values.lookup(name))
+}
+
+/// Expands `{placeholder}` tokens in `template` with values from `lookup`.
+///
+/// Shared by footer and notification templates. Placeholders `lookup` does
+/// not recognize, and unmatched braces, are kept verbatim.
+pub fn render_placeholders(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        match after_open.find('}') {
+            Some(close) => {
+                let name = &after_open[..close];
+                match lookup(name) {
+                    Some(value) => out.push_str(&value),
+                    None => {
+                        out.push('{');
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                }
+                rest = &after_open[close + 1..];
+            }
+            None => {
+                out.push_str(&rest[open..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use claude_usage::UsagePeriod;
+
+    fn session(status: Status) -> Session {
+        let mut s = Session::new("s".to_string(), AgentType::ClaudeCode, None);
+        s.status = status;
+        s.closed = status == Status::Closed;
+        s
+    }
+
+    fn usage(five_hour: f64, seven_day: f64) -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: five_hour,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: seven_day,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    fn values<'a>(sessions: &'a [Session], usage: Option<&'a UsageData>) -> FooterValues<'a> {
+        FooterValues {
+            sessions,
+            usage,
+            usage_blocked: false,
+            socket: Path::new("/tmp/acd.sock"),
+            keys_hint: "[q] Quit",
+        }
+    }
+
+    #[test]
+    fn test_counts_exclude_closed_sessions() {
+        let sessions = vec![
+            session(Status::Attention),
+            session(Status::Attention),
+            session(Status::Working),
+            session(Status::Closed),
+        ];
+        let rendered = render_footer_template(
+            "{session_count} total, {attention_count} need me, {working_count} busy, {question_count} asking",
+            &values(&sessions, None),
+        );
+        assert_eq!(rendered, "3 total, 2 need me, 1 busy, 0 asking");
+    }
+
+    #[test]
+    fn test_usage_placeholders() {
+        let data = usage(42.7, 80.0);
+        let rendered =
+            render_footer_template("5h {usage_5h} 7d {usage_7d}", &values(&[], Some(&data)));
+        assert_eq!(rendered, "5h 42% 7d 80%");
+    }
+
+    #[test]
+    fn test_usage_placeholders_unavailable_and_blocked() {
+        let mut v = values(&[], None);
+        assert_eq!(render_footer_template("{usage_5h}", &v), "--");
+        v.usage_blocked = true;
+        assert_eq!(render_footer_template("{usage_5h}", &v), "blocked");
+    }
+
+    #[test]
+    fn test_socket_and_keys_hint() {
+        let rendered = render_footer_template("{keys_hint} @ {socket}", &values(&[], None));
+        assert_eq!(rendered, "[q] Quit @ /tmp/acd.sock");
+    }
+
+    #[test]
+    fn test_unknown_placeholder_kept_verbatim() {
+        let rendered = render_footer_template("a {nope} b", &values(&[], None));
+        assert_eq!(rendered, "a {nope} b");
+    }
+
+    #[test]
+    fn test_unmatched_brace_kept_verbatim() {
+        let rendered = render_footer_template("x {usage_5h", &values(&[], None));
+        assert_eq!(rendered, "x {usage_5h");
+    }
+
+    #[test]
+    fn test_all_documented_placeholders_are_recognized() {
+        let v = values(&[], None);
+        for name in FOOTER_PLACEHOLDERS {
+            assert!(v.lookup(name).is_some(), "placeholder {name} not handled");
+        }
+    }
+}
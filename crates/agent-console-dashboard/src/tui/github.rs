@@ -0,0 +1,173 @@
+//! Pull requests of the selected sessions' branches, for the detail panel.
+//!
+//! Lookups run `git` and `gh` in background tasks (see
+//! [`crate::integrations::github`]) and report back over a channel the event
+//! loop connects with [`GithubLookups::connect`]. Results are cached per
+//! working directory and refreshed once they are older than the configured
+//! interval.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use crate::config::schema::GithubConfig;
+use crate::integrations::github::{self, BranchInfo};
+
+/// Result of a lookup for one working directory.
+pub type LookupResult = Result<Option<BranchInfo>, String>;
+
+/// A finished lookup, sent from the background task to the event loop.
+pub type LookupMessage = (PathBuf, LookupResult);
+
+/// Cached lookup for one working directory.
+#[derive(Debug)]
+struct Entry {
+    /// When the latest lookup was started.
+    requested: Instant,
+    /// Result of the latest finished lookup, `None` while the first runs.
+    result: Option<LookupResult>,
+}
+
+/// GitHub lookups by working directory.
+#[derive(Debug)]
+pub struct GithubLookups {
+    enabled: bool,
+    refresh_interval: Duration,
+    entries: HashMap<PathBuf, Entry>,
+    results: Option<mpsc::UnboundedSender<LookupMessage>>,
+}
+
+impl GithubLookups {
+    /// Creates lookups configured by `[integrations.github]`.
+    pub fn new(config: &GithubConfig) -> Self {
+        let refresh_interval =
+            humantime::parse_duration(&config.refresh_interval).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "invalid integrations.github.refresh_interval '{}': {}",
+                    config.refresh_interval,
+                    e
+                );
+                Duration::from_secs(60)
+            });
+        Self {
+            enabled: config.enabled,
+            refresh_interval,
+            entries: HashMap::new(),
+            results: None,
+        }
+    }
+
+    /// Applies a reloaded `[integrations.github]`, keeping cached results
+    /// and the connection to the event loop.
+    pub fn reconfigure(&mut self, config: &GithubConfig) {
+        let Self {
+            enabled,
+            refresh_interval,
+            ..
+        } = Self::new(config);
+        self.enabled = enabled;
+        self.refresh_interval = refresh_interval;
+    }
+
+    /// Sends finished lookups to `results`. Lookups are only started once
+    /// connected, so nothing runs outside the event loop.
+    pub fn connect(&mut self, results: mpsc::UnboundedSender<LookupMessage>) {
+        self.results = Some(results);
+    }
+
+    /// Starts a lookup for `dir` if it has none, or its latest is stale.
+    pub fn refresh(&mut self, dir: &Path) {
+        let Some(results) = self.results.clone().filter(|_| self.enabled) else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some(entry) = self.entries.get_mut(dir) {
+            if now.duration_since(entry.requested) < self.refresh_interval {
+                return;
+            }
+            entry.requested = now;
+        } else {
+            self.entries.insert(
+                dir.to_path_buf(),
+                Entry {
+                    requested: now,
+                    result: None,
+                },
+            );
+        }
+        let dir = dir.to_path_buf();
+        tokio::spawn(async move {
+            let result = github::lookup(&dir).await;
+            if let Err(e) = &result {
+                tracing::debug!("github lookup for {} failed: {}", dir.display(), e);
+            }
+            let _ = results.send((dir, result));
+        });
+    }
+
+    /// Records a finished lookup.
+    pub fn apply(&mut self, (dir, result): LookupMessage) {
+        self.entries
+            .entry(dir)
+            .or_insert_with(|| Entry {
+                requested: Instant::now(),
+                result: None,
+            })
+            .result = Some(result);
+    }
+
+    /// Latest lookup result for `dir`, or `None` if none has finished.
+    pub fn get(&self, dir: &Path) -> Option<&LookupResult> {
+        self.entries.get(dir)?.result.as_ref()
+    }
+}
+
+impl Default for GithubLookups {
+    fn default() -> Self {
+        Self::new(&GithubConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookups_only_start_when_connected() {
+        let mut lookups = GithubLookups::default();
+        lookups.refresh(Path::new("/work/api"));
+        assert!(lookups.entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_results_are_cached_until_stale() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut lookups = GithubLookups::default();
+        lookups.connect(tx);
+        let dir = Path::new("/nonexistent/acd-github-test");
+
+        lookups.refresh(dir);
+        assert_eq!(lookups.get(dir), None);
+        lookups.apply((dir.to_path_buf(), Ok(None)));
+        assert_eq!(lookups.get(dir), Some(&Ok(None)));
+
+        // A fresh entry is not looked up again
+        let requested = lookups.entries[dir].requested;
+        lookups.refresh(dir);
+        assert_eq!(lookups.entries[dir].requested, requested);
+    }
+
+    #[test]
+    fn test_disabled_lookups_never_start() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut lookups = GithubLookups::new(&GithubConfig {
+            enabled: false,
+            ..GithubConfig::default()
+        });
+        lookups.connect(tx);
+        lookups.refresh(Path::new("/work/api"));
+        assert!(lookups.entries.is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+//! Status icon sets for the TUI.
+//!
+//! Resolves the `[tui.icons]` config section into the concrete glyphs used for
+//! session rows, compact chips, the condensed header, and the terminal title.
+//! Non-ASCII sets fall back to ASCII when the terminal does not look capable
+//! of rendering them (see [`unicode_supported`]).
+
+use crate::agents::AgentRegistry;
+use crate::config::schema::{IconSet, IconsConfig};
+use crate::{AgentType, Session, Status, INACTIVE_SESSION_THRESHOLD};
+use ratatui::text::Span;
+use std::collections::BTreeMap;
+
+/// Resolved glyphs for every session status (plus the derived inactive state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusIcons {
+    /// Glyph for `Status::Working`.
+    pub working: String,
+    /// Glyph for `Status::Attention`.
+    pub attention: String,
+    /// Glyph for `Status::Question`.
+    pub question: String,
+    /// Glyph for `Status::Closed`.
+    pub closed: String,
+    /// Glyph for inactive sessions (derived, not a status variant).
+    pub inactive: String,
+    /// Row tags of custom agents by name, from `[[agents.custom]]` icons.
+    pub agent_tags: BTreeMap<String, String>,
+}
+
+impl StatusIcons {
+    /// ASCII icons (`*`, `!`, `?`, `x`, `.`). Works in every terminal.
+    pub fn ascii() -> Self {
+        Self::from_glyphs("*", "!", "?", "x", ".")
+    }
+
+    /// Emoji icons. Most emoji render two cells wide.
+    pub fn emoji() -> Self {
+        Self::from_glyphs("🔨", "🔔", "❓", "🏁", "💤")
+    }
+
+    /// Nerd Font icons (Font Awesome range). Requires a patched font.
+    pub fn nerd_font() -> Self {
+        Self::from_glyphs("\u{f013}", "\u{f0f3}", "\u{f059}", "\u{f058}", "\u{f186}")
+    }
+
+    /// Returns the built-in icons for the given set.
+    pub fn for_set(set: &IconSet) -> Self {
+        match set {
+            IconSet::Ascii => Self::ascii(),
+            IconSet::Emoji => Self::emoji(),
+            IconSet::NerdFont => Self::nerd_font(),
+        }
+    }
+
+    /// Resolves icons from config, detecting Unicode support from the environment.
+    pub fn from_config(config: &IconsConfig) -> Self {
+        Self::resolve(config, unicode_supported())
+    }
+
+    /// Resolves icons from config given whether the terminal supports Unicode.
+    ///
+    /// When `config.ascii_fallback` is true and `unicode_supported` is false,
+    /// the ASCII set is used and non-ASCII per-status overrides are ignored.
+    pub fn resolve(config: &IconsConfig, unicode_supported: bool) -> Self {
+        let fallback = config.ascii_fallback && !unicode_supported;
+        let mut icons = if fallback {
+            Self::ascii()
+        } else {
+            Self::for_set(&config.set)
+        };
+
+        let overrides = [
+            (&config.working, &mut icons.working),
+            (&config.attention, &mut icons.attention),
+            (&config.question, &mut icons.question),
+            (&config.closed, &mut icons.closed),
+            (&config.inactive, &mut icons.inactive),
+        ];
+        for (value, slot) in overrides {
+            if let Some(glyph) = value {
+                if !fallback || glyph.is_ascii() {
+                    *slot = glyph.clone();
+                }
+            }
+        }
+        icons
+    }
+
+    /// Adds the row tags of the custom agents in `agents`.
+    pub fn with_agent_tags(mut self, agents: &AgentRegistry) -> Self {
+        for adapter in agents.adapters() {
+            if let (AgentType::Custom(name), Some(icon)) = (adapter.agent_type(), adapter.icon()) {
+                self.agent_tags.insert(name, icon.to_string());
+            }
+        }
+        self
+    }
+
+    /// Returns the glyph for a status.
+    pub fn symbol(&self, status: Status) -> &str {
+        match status {
+            Status::Working => &self.working,
+            Status::Attention => &self.attention,
+            Status::Question => &self.question,
+            Status::Closed => &self.closed,
+        }
+    }
+
+    /// Returns the glyph for a session, using the inactive glyph for idle sessions.
+    pub fn session_symbol(&self, session: &Session) -> &str {
+        if session.is_inactive(INACTIVE_SESSION_THRESHOLD) {
+            &self.inactive
+        } else {
+            self.symbol(session.status)
+        }
+    }
+
+    fn from_glyphs(
+        working: &str,
+        attention: &str,
+        question: &str,
+        closed: &str,
+        inactive: &str,
+    ) -> Self {
+        Self {
+            working: working.to_string(),
+            attention: attention.to_string(),
+            question: question.to_string(),
+            closed: closed.to_string(),
+            inactive: inactive.to_string(),
+            agent_tags: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for StatusIcons {
+    fn default() -> Self {
+        Self::ascii()
+    }
+}
+
+/// Returns the display width of a glyph in terminal cells.
+///
+/// Emoji are usually two cells wide; ASCII and Nerd Font glyphs are one.
+pub fn glyph_width(glyph: &str) -> usize {
+    Span::raw(glyph).width()
+}
+
+/// Returns true if the current terminal likely renders non-ASCII glyphs.
+///
+/// Reads the locale (`LC_ALL`, `LC_CTYPE`, `LANG`, first non-empty wins) and
+/// `TERM` from the environment. See [`unicode_supported_from`].
+pub fn unicode_supported() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|v| !v.is_empty());
+    let term = std::env::var("TERM").ok();
+    unicode_supported_from(locale.as_deref(), term.as_deref())
+}
+
+/// Pure helper for [`unicode_supported`].
+///
+/// Unicode is assumed supported when the locale is UTF-8 and `TERM` is not a
+/// console known to lack wide glyph support (`linux`, `dumb`, `vt100`, `vt220`).
+pub fn unicode_supported_from(locale: Option<&str>, term: Option<&str>) -> bool {
+    let utf8_locale = locale
+        .map(|l| {
+            let l = l.to_ascii_lowercase();
+            l.contains("utf-8") || l.contains("utf8")
+        })
+        .unwrap_or(false);
+    let limited_term = matches!(term, Some("linux" | "dumb" | "vt100" | "vt220"));
+    utf8_locale && !limited_term
+}
+
+/// Builds the terminal window title: `ACD` followed by non-zero status counts.
+///
+/// Example: `ACD ! 2 * 3 ? 1`. Closed and inactive sessions are not counted.
+pub fn terminal_title(sessions: &[Session], icons: &StatusIcons) -> String {
+    let mut title = String::from("ACD");
+    for status in [Status::Attention, Status::Working, Status::Question] {
+        let count = sessions
+            .iter()
+            .filter(|s| {
+                !s.closed && s.status == status && !s.is_inactive(INACTIVE_SESSION_THRESHOLD)
+            })
+            .count();
+        if count > 0 {
+            title.push_str(&format!(" {} {}", icons.symbol(status), count));
+        }
+    }
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+
+    fn make_session(status: Status) -> Session {
+        let mut s = Session::new("s".to_string(), AgentType::ClaudeCode, None);
+        s.status = status;
+        s.closed = status == Status::Closed;
+        s
+    }
+
+    #[test]
+    fn ascii_set_matches_status_symbols_decision() {
+        let icons = StatusIcons::ascii();
+        assert_eq!(icons.symbol(Status::Working), "*");
+        assert_eq!(icons.symbol(Status::Attention), "!");
+        assert_eq!(icons.symbol(Status::Question), "?");
+        assert_eq!(icons.symbol(Status::Closed), "x");
+        assert_eq!(icons.inactive, ".");
+    }
+
+    #[test]
+    fn default_config_resolves_to_ascii() {
+        let icons = StatusIcons::resolve(&IconsConfig::default(), true);
+        assert_eq!(icons, StatusIcons::ascii());
+    }
+
+    #[test]
+    fn emoji_set_used_when_unicode_supported() {
+        let config = IconsConfig {
+            set: IconSet::Emoji,
+            ..Default::default()
+        };
+        assert_eq!(StatusIcons::resolve(&config, true), StatusIcons::emoji());
+    }
+
+    #[test]
+    fn nerd_font_falls_back_to_ascii_without_unicode() {
+        let config = IconsConfig {
+            set: IconSet::NerdFont,
+            ..Default::default()
+        };
+        assert_eq!(StatusIcons::resolve(&config, false), StatusIcons::ascii());
+    }
+
+    #[test]
+    fn fallback_disabled_keeps_requested_set() {
+        let config = IconsConfig {
+            set: IconSet::NerdFont,
+            ascii_fallback: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            StatusIcons::resolve(&config, false),
+            StatusIcons::nerd_font()
+        );
+    }
+
+    #[test]
+    fn per_status_overrides_apply() {
+        let config = IconsConfig {
+            working: Some("W".to_string()),
+            closed: Some("✔".to_string()),
+            ..Default::default()
+        };
+        let icons = StatusIcons::resolve(&config, true);
+        assert_eq!(icons.working, "W");
+        assert_eq!(icons.closed, "✔");
+        assert_eq!(icons.attention, "!");
+    }
+
+    #[test]
+    fn non_ascii_overrides_dropped_on_fallback() {
+        let config = IconsConfig {
+            set: IconSet::Emoji,
+            working: Some("W".to_string()),
+            closed: Some("✔".to_string()),
+            ..Default::default()
+        };
+        let icons = StatusIcons::resolve(&config, false);
+        assert_eq!(icons.working, "W", "ASCII override survives fallback");
+        assert_eq!(icons.closed, "x", "non-ASCII override is dropped");
+    }
+
+    #[test]
+    fn unicode_detection_requires_utf8_locale() {
+        assert!(unicode_supported_from(
+            Some("en_US.UTF-8"),
+            Some("xterm-256color")
+        ));
+        assert!(unicode_supported_from(Some("C.utf8"), None));
+        assert!(!unicode_supported_from(Some("C"), Some("xterm-256color")));
+        assert!(!unicode_supported_from(None, Some("xterm-256color")));
+    }
+
+    #[test]
+    fn unicode_detection_rejects_limited_terminals() {
+        assert!(!unicode_supported_from(Some("en_US.UTF-8"), Some("linux")));
+        assert!(!unicode_supported_from(Some("en_US.UTF-8"), Some("dumb")));
+    }
+
+    #[test]
+    fn session_symbol_uses_status_glyph() {
+        let icons = StatusIcons::ascii();
+        assert_eq!(icons.session_symbol(&make_session(Status::Question)), "?");
+    }
+
+    #[test]
+    fn glyph_width_counts_cells() {
+        assert_eq!(glyph_width("*"), 1);
+        assert_eq!(glyph_width("🔔"), 2);
+        assert_eq!(glyph_width("\u{f0f3}"), 1);
+    }
+
+    #[test]
+    fn terminal_title_counts_active_statuses() {
+        let sessions = vec![
+            make_session(Status::Attention),
+            make_session(Status::Attention),
+            make_session(Status::Working),
+            make_session(Status::Closed),
+        ];
+        assert_eq!(
+            terminal_title(&sessions, &StatusIcons::ascii()),
+            "ACD ! 2 * 1"
+        );
+    }
+
+    #[test]
+    fn terminal_title_empty_sessions() {
+        assert_eq!(terminal_title(&[], &StatusIcons::ascii()), "ACD");
+    }
+}
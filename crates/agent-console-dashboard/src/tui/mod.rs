@@ -3,10 +3,21 @@
 //! Provides a terminal user interface built on ratatui and crossterm,
 //! displaying real-time session status from the daemon.
 
+pub mod alerts;
 pub mod app;
+pub mod embedded;
 pub mod event;
+pub mod footer;
+pub mod github;
+pub mod icons;
+pub mod palette;
+pub mod projects;
 pub mod subscription;
+pub mod tabs;
+pub mod text_input;
+pub mod theme;
 pub mod ui;
+pub mod usage_history;
 pub mod views;
 
 #[cfg(test)]
@@ -0,0 +1,78 @@
+//! Command palette for the custom session commands in `tui.commands`.
+//!
+//! `:` opens the palette for the selected session. Choosing an entry runs its
+//! command through the terminal backend, in a new pane in the session's
+//! working directory (see [`crate::tui::app::App::run_session_command`]).
+
+use crate::integrations::{session_placeholder, shell_quote};
+use crate::tui::footer::render_placeholders;
+use crate::Session;
+
+/// Open command palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandPalette {
+    /// ID of the session the commands run for.
+    pub session_id: String,
+    /// Index of the highlighted command.
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    /// Opens the palette for `session_id` with the first command highlighted.
+    pub fn new(session_id: String) -> Self {
+        Self {
+            session_id,
+            selected: 0,
+        }
+    }
+
+    /// Moves the highlight by `delta` among `len` commands, wrapping around.
+    pub fn move_selection(&mut self, delta: isize, len: usize) {
+        if len == 0 {
+            return;
+        }
+        self.selected = (self.selected as isize + delta).rem_euclid(len as isize) as usize;
+    }
+}
+
+/// `command` with its `{placeholder}` tokens replaced by shell-quoted values
+/// from `session`.
+///
+/// Placeholders are those of notification templates (see
+/// [`session_placeholder`]); unknown ones are kept verbatim.
+pub fn render_command(command: &str, session: &Session) -> String {
+    render_placeholders(command, |name| {
+        session_placeholder(session, name).map(|value| shell_quote(&value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render_command_quotes_values() {
+        let session = Session::new(
+            "abc".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/u/it's here")),
+        );
+        assert_eq!(
+            render_command("code {working_dir} --id={session_id} {bogus}", &session),
+            r#"code '/home/u/it'\''s here' --id='abc' {bogus}"#
+        );
+    }
+
+    #[test]
+    fn test_move_selection_wraps() {
+        let mut palette = CommandPalette::new("abc".to_string());
+        palette.move_selection(-1, 3);
+        assert_eq!(palette.selected, 2);
+        palette.move_selection(1, 3);
+        assert_eq!(palette.selected, 0);
+        palette.move_selection(1, 0);
+        assert_eq!(palette.selected, 0);
+    }
+}
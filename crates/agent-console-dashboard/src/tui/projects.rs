@@ -0,0 +1,98 @@
+//! Per-project overrides of the sessions shown in the TUI.
+//!
+//! Each working directory is resolved to the nearest `.acd.toml` once, when a
+//! session in it first appears, and the result is cached until the user
+//! config reloads (see [`crate::tui::app::App::apply_config`]).
+//!
+//! A project's hooks only run if its root is in `tui.trusted_projects`, since
+//! a cloned repository's `.acd.toml` could otherwise run any command.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::schema::ProjectConfig;
+use crate::config::xdg;
+
+/// Project overrides by working directory.
+#[derive(Debug, Default)]
+pub struct ProjectConfigs {
+    by_dir: HashMap<PathBuf, Option<Arc<ProjectConfig>>>,
+    /// Canonical roots from `tui.trusted_projects`.
+    trusted: Vec<PathBuf>,
+}
+
+impl ProjectConfigs {
+    /// Overrides of the project containing `dir`, loading them on first use.
+    ///
+    /// Invalid files are logged and treated as absent.
+    pub fn get(&mut self, dir: &Path) -> Option<Arc<ProjectConfig>> {
+        if let Some(project) = self.by_dir.get(dir) {
+            return project.clone();
+        }
+        let project = match ConfigLoader::load_project(dir) {
+            Ok(project) => project.map(|mut project| {
+                project.trusted = self.trusted.contains(&canonical(&project.root));
+                Arc::new(project)
+            }),
+            Err(e) => {
+                tracing::warn!("ignoring project config for {}: {}", dir.display(), e);
+                None
+            }
+        };
+        self.by_dir.insert(dir.to_path_buf(), project.clone());
+        project
+    }
+
+    /// Sets the project roots whose hooks may run, from
+    /// `tui.trusted_projects`. Takes effect for files read after the next
+    /// [`clear`](Self::clear).
+    pub fn set_trusted(&mut self, roots: &[String]) {
+        self.trusted = roots
+            .iter()
+            .map(|root| canonical(&xdg::expand_tilde(root)))
+            .collect();
+    }
+
+    /// Forgets every cached lookup, so files are read again.
+    pub fn clear(&mut self) {
+        self.by_dir.clear();
+    }
+}
+
+/// `path` with symlinks resolved, or as given if it does not exist.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader::PROJECT_CONFIG_FILE;
+
+    #[test]
+    fn test_lookups_are_cached_until_cleared() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut projects = ProjectConfigs::default();
+        assert!(projects.get(dir.path()).is_none());
+
+        std::fs::write(dir.path().join(PROJECT_CONFIG_FILE), "tags = [\"web\"]\n").expect("write");
+        assert!(projects.get(dir.path()).is_none(), "cached miss");
+        projects.clear();
+        let project = projects.get(dir.path()).expect("found after clear");
+        assert_eq!(project.tags, ["web"]);
+        assert_eq!(project.root, dir.path());
+        assert!(!project.trusted);
+    }
+
+    #[test]
+    fn test_listed_roots_are_trusted() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join(PROJECT_CONFIG_FILE), "").expect("write");
+        let mut projects = ProjectConfigs::default();
+        projects.set_trusted(&[dir.path().display().to_string()]);
+        let project = projects.get(&dir.path().join("src")).expect("found");
+        assert!(project.trusted);
+    }
+}
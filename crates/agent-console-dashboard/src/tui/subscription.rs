@@ -46,6 +46,10 @@ pub async fn subscribe_to_daemon(
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let list_json = serde_json::to_string(&list_cmd).expect("failed to serialize LIST command");
     writer.write_all(list_json.as_bytes()).await?;
@@ -83,6 +87,10 @@ pub async fn subscribe_to_daemon(
         working_dir: None,
         confirmed: None,
         priority: None,
+        display_name: None,
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
     };
     let sub_json = serde_json::to_string(&sub_cmd).expect("failed to serialize SUB command");
     writer.write_all(sub_json.as_bytes()).await?;
@@ -110,6 +118,51 @@ pub async fn subscribe_to_daemon(
     Ok(())
 }
 
+/// Sends a RENAME command for `session_id` over a fresh daemon connection.
+///
+/// A blank `display_name` clears the name. Returns an error if the daemon
+/// rejects the command (e.g. the session no longer exists).
+pub async fn send_rename(
+    socket_path: &Path,
+    session_id: &str,
+    display_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let client = connect_with_lazy_start(socket_path).await?;
+    let stream = client.into_stream();
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    let rename_cmd = IpcCommand {
+        version: IPC_VERSION,
+        cmd: IpcCommandKind::Rename.to_string(),
+        session_id: Some(session_id.to_string()),
+        status: None,
+        working_dir: None,
+        confirmed: None,
+        priority: None,
+        display_name: Some(display_name.to_string()),
+        last_message: None,
+        terminal_pane: None,
+        agent_type: None,
+    };
+    let json = serde_json::to_string(&rename_cmd).expect("failed to serialize RENAME command");
+    writer.write_all(json.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let resp: IpcResponse = serde_json::from_str(line.trim())?;
+    if resp.ok {
+        Ok(())
+    } else {
+        Err(resp
+            .error
+            .unwrap_or_else(|| "unknown error".to_string())
+            .into())
+    }
+}
+
 /// Parses a single JSON line from the daemon SUB stream into a `DaemonMessage`.
 ///
 /// Returns `None` for unrecognized or malformed lines.
@@ -161,6 +214,9 @@ mod tests {
             history: vec![],
             closed: false,
             priority: 0,
+            display_name: None,
+            last_message: None,
+            terminal_pane: None,
         };
         let notification = IpcNotification::session_update(info);
         serde_json::to_string(&notification).expect("failed to serialize notification")
@@ -187,12 +243,16 @@ mod tests {
             five_hour: claude_usage::UsagePeriod {
                 utilization: 25.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: claude_usage::UsagePeriod {
                 utilization: 50.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         };
         let notification = IpcNotification::usage_update(&data);
@@ -0,0 +1,264 @@
+//! Workspace tabs that group sessions by project.
+//!
+//! A session's project is the git repository containing its working directory
+//! (the nearest ancestor with a `.git` entry), or the working directory itself
+//! outside a repository. Tab 0 is always "All"; project tabs follow in path
+//! order. Each tab remembers its own selection and chip scroll offset, so
+//! switching back lands where the user left off.
+
+use crate::Session;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Title of the tab that shows every session.
+pub const ALL_TAB_TITLE: &str = "All";
+
+/// Returns the project root for `dir`: the nearest ancestor containing
+/// `.git` (a directory, or a file for worktrees), or `dir` itself.
+pub fn project_root(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|p| p.join(".git").exists())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// View state saved when leaving a tab and restored when returning to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TabState {
+    /// ID of the selected session, or None if nothing was selected.
+    pub selected_session_id: Option<String>,
+    /// Horizontal chip scroll offset for the TwoLine layout.
+    pub compact_scroll_offset: usize,
+}
+
+/// Project tabs derived from the current sessions.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceTabs {
+    /// Project roots in tab order; tab N shows `projects[N - 1]`.
+    projects: Vec<PathBuf>,
+    /// Active tab index (0 = All).
+    active: usize,
+    /// Saved view state per tab, keyed by project root (None = All).
+    states: HashMap<Option<PathBuf>, TabState>,
+    /// Working directory → project root, so `.git` lookups run once per dir.
+    roots: HashMap<PathBuf, PathBuf>,
+}
+
+impl WorkspaceTabs {
+    /// Creates tabs with only the "All" tab.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuilds the project list from `sessions`.
+    ///
+    /// The active tab stays on the same project. If that project has no
+    /// sessions left, the "All" tab becomes active.
+    pub fn sync(&mut self, sessions: &[Session]) {
+        let active_root = self.active_root().cloned();
+        let mut projects = Vec::new();
+        for dir in sessions.iter().filter_map(|s| s.working_dir.as_deref()) {
+            let root = self
+                .roots
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| project_root(dir));
+            projects.push(root.clone());
+        }
+        projects.sort();
+        projects.dedup();
+        self.projects = projects;
+        self.active = active_root
+            .and_then(|root| self.projects.iter().position(|p| *p == root))
+            .map_or(0, |i| i + 1);
+    }
+
+    /// Returns the project tab index (1-based) that `session` belongs to.
+    ///
+    /// None for sessions with no working directory, which only appear under
+    /// "All", or whose directory has not been seen by [`sync`](Self::sync).
+    pub fn tab_of(&self, session: &Session) -> Option<usize> {
+        let root = self.roots.get(session.working_dir.as_deref()?)?;
+        self.projects.iter().position(|p| p == root).map(|i| i + 1)
+    }
+
+    /// Returns true if `session` is shown in the active tab.
+    pub fn shows(&self, session: &Session) -> bool {
+        self.active == 0 || self.tab_of(session) == Some(self.active)
+    }
+
+    /// Returns the number of tabs, including "All".
+    pub fn tab_count(&self) -> usize {
+        self.projects.len() + 1
+    }
+
+    /// Returns the number of project tabs.
+    pub fn project_count(&self) -> usize {
+        self.projects.len()
+    }
+
+    /// Returns the active tab index (0 = All).
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Returns the active tab's project root, or None for "All".
+    pub fn active_root(&self) -> Option<&PathBuf> {
+        self.active
+            .checked_sub(1)
+            .and_then(|i| self.projects.get(i))
+    }
+
+    /// Returns tab titles: "All", then each project's directory name.
+    pub fn titles(&self) -> Vec<String> {
+        std::iter::once(ALL_TAB_TITLE.to_string())
+            .chain(self.projects.iter().map(|root| {
+                root.file_name().map_or_else(
+                    || root.display().to_string(),
+                    |n| n.to_string_lossy().into(),
+                )
+            }))
+            .collect()
+    }
+
+    /// Returns the index of the tab after the active one, wrapping around.
+    pub fn next_index(&self) -> usize {
+        (self.active + 1) % self.tab_count()
+    }
+
+    /// Returns the index of the tab before the active one, wrapping around.
+    pub fn previous_index(&self) -> usize {
+        (self.active + self.tab_count() - 1) % self.tab_count()
+    }
+
+    /// Saves `current` as the active tab's state and activates tab `index`.
+    ///
+    /// Returns false (and changes nothing) if `index` is out of range or
+    /// already active.
+    pub fn switch_to(&mut self, index: usize, current: TabState) -> bool {
+        if index >= self.tab_count() || index == self.active {
+            return false;
+        }
+        self.states.insert(self.active_root().cloned(), current);
+        self.active = index;
+        true
+    }
+
+    /// Returns the saved state of the active tab, or None if it was never left.
+    pub fn saved_state(&self) -> Option<&TabState> {
+        self.states.get(&self.active_root().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentType;
+
+    fn session(id: &str, dir: Option<&str>) -> Session {
+        Session::new(
+            id.to_string(),
+            AgentType::ClaudeCode,
+            dir.map(PathBuf::from),
+        )
+    }
+
+    #[test]
+    fn test_project_root_finds_git_ancestor() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let repo = tmp.path().join("repo");
+        let nested = repo.join("crates/core");
+        std::fs::create_dir_all(&nested).expect("create dirs");
+        std::fs::create_dir(repo.join(".git")).expect("create .git");
+        assert_eq!(project_root(&nested), repo);
+    }
+
+    #[test]
+    fn test_project_root_without_git_is_dir_itself() {
+        let dir = Path::new("/nonexistent/acd/project");
+        assert_eq!(project_root(dir), dir);
+    }
+
+    #[test]
+    fn test_sync_builds_sorted_unique_tabs() {
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&[
+            session("a", Some("/nonexistent/web")),
+            session("b", Some("/nonexistent/api")),
+            session("c", Some("/nonexistent/web")),
+            session("d", None),
+        ]);
+        assert_eq!(tabs.titles(), ["All", "api", "web"]);
+        assert_eq!(
+            tabs.tab_of(&session("x", Some("/nonexistent/web"))),
+            Some(2)
+        );
+        assert_eq!(tabs.tab_of(&session("y", None)), None);
+    }
+
+    #[test]
+    fn test_shows_filters_by_active_project() {
+        let sessions = [
+            session("a", Some("/nonexistent/api")),
+            session("b", Some("/nonexistent/web")),
+            session("c", None),
+        ];
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&sessions);
+        assert!(
+            sessions.iter().all(|s| tabs.shows(s)),
+            "All shows everything"
+        );
+
+        assert!(tabs.switch_to(2, TabState::default()));
+        let shown: Vec<_> = sessions.iter().filter(|s| tabs.shows(s)).collect();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].session_id, "b");
+    }
+
+    #[test]
+    fn test_sync_keeps_active_project_and_falls_back_to_all() {
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&[session("b", Some("/nonexistent/web"))]);
+        tabs.switch_to(1, TabState::default());
+
+        // A new project sorting before "web" shifts its index
+        tabs.sync(&[
+            session("a", Some("/nonexistent/api")),
+            session("b", Some("/nonexistent/web")),
+        ]);
+        assert_eq!(tabs.active(), 2);
+
+        tabs.sync(&[session("a", Some("/nonexistent/api"))]);
+        assert_eq!(tabs.active(), 0);
+    }
+
+    #[test]
+    fn test_switch_saves_state_per_tab() {
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&[session("a", Some("/nonexistent/api"))]);
+        let all_state = TabState {
+            selected_session_id: Some("a".into()),
+            compact_scroll_offset: 3,
+        };
+        assert!(tabs.switch_to(1, all_state.clone()));
+        assert_eq!(tabs.saved_state(), None, "project tab never visited");
+        assert!(!tabs.switch_to(1, TabState::default()), "already active");
+        assert!(!tabs.switch_to(5, TabState::default()), "out of range");
+
+        assert!(tabs.switch_to(0, TabState::default()));
+        assert_eq!(tabs.saved_state(), Some(&all_state));
+    }
+
+    #[test]
+    fn test_next_and_previous_wrap() {
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&[
+            session("a", Some("/nonexistent/api")),
+            session("b", Some("/nonexistent/web")),
+        ]);
+        assert_eq!(tabs.next_index(), 1);
+        assert_eq!(tabs.previous_index(), 2);
+        tabs.switch_to(2, TabState::default());
+        assert_eq!(tabs.next_index(), 0);
+    }
+}
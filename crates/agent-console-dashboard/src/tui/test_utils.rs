@@ -3,8 +3,7 @@
 //! Provides helper functions for creating test terminals, extracting buffer
 //! content, asserting colors, and creating test session fixtures.
 
-#![cfg(test)]
-
+use crate::tui::theme::Theme;
 use crate::{AgentType, Session, Status, INACTIVE_SESSION_THRESHOLD};
 use ratatui::{backend::TestBackend, buffer::Buffer, style::Color, Terminal};
 use std::path::PathBuf;
@@ -40,12 +39,7 @@ pub fn row_contains(buffer: &Buffer, row: u16, text: &str) -> bool {
 /// Finds the first row index that contains the given text, or None if not found.
 pub fn find_row_with_text(buffer: &Buffer, text: &str) -> Option<u16> {
     let area = buffer.area();
-    for row in 0..area.height {
-        if row_contains(buffer, row, text) {
-            return Some(row);
-        }
-    }
-    None
+    (0..area.height).find(|&row| row_contains(buffer, row, text))
 }
 
 /// Asserts that the cell at (col, row) has the specified foreground color.
@@ -130,7 +124,15 @@ pub fn render_session_list_to_buffer(
         .draw(|frame| {
             let area = frame.area();
             crate::tui::views::dashboard::render_session_list(
-                frame, area, sessions, selected, width,
+                frame,
+                area,
+                sessions,
+                selected,
+                width,
+                &crate::tui::icons::StatusIcons::ascii(),
+                &Theme::default(),
+                crate::config::schema::TimeFormat::Relative,
+                None,
             );
         })
         .expect("draw failed");
@@ -0,0 +1,283 @@
+//! Single-line text input with cursor handling.
+//!
+//! A small reusable editing buffer for inline prompts (e.g. session rename).
+//! The cursor is tracked as a character index so multi-byte input is edited
+//! safely. Rendering produces spans with the cursor cell shown in reverse video.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+
+/// Single-line editable text buffer with a cursor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    /// Current text.
+    value: String,
+    /// Cursor position as a character index (0..=char count).
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Creates an empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an input pre-filled with `value`, cursor at the end.
+    pub fn with_value(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    /// Returns the current text.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the cursor position as a character index.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Inserts a character at the cursor and advances the cursor.
+    pub fn insert(&mut self, c: char) {
+        let idx = self.byte_index();
+        self.value.insert(idx, c);
+        self.cursor += 1;
+    }
+
+    /// Deletes the character before the cursor (Backspace).
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let idx = self.byte_index();
+        self.value.remove(idx);
+    }
+
+    /// Deletes the character under the cursor (Delete).
+    pub fn delete(&mut self) {
+        if self.cursor < self.char_count() {
+            let idx = self.byte_index();
+            self.value.remove(idx);
+        }
+    }
+
+    /// Moves the cursor one character left.
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Moves the cursor one character right.
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.char_count());
+    }
+
+    /// Moves the cursor to the start of the text.
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Moves the cursor to the end of the text.
+    pub fn move_end(&mut self) {
+        self.cursor = self.char_count();
+    }
+
+    /// Deletes everything before the cursor (Ctrl+U).
+    pub fn clear_to_start(&mut self) {
+        let idx = self.byte_index();
+        self.value.drain(..idx);
+        self.cursor = 0;
+    }
+
+    /// Applies an editing key. Returns true if the key was consumed.
+    ///
+    /// Handles printable characters, Backspace, Delete, Left/Right, Home/End,
+    /// and the readline shortcuts Ctrl+A, Ctrl+E, Ctrl+U. Enter and Esc are
+    /// not consumed; the caller decides what submit and cancel mean.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.move_home(),
+            KeyCode::Char('e') if ctrl => self.move_end(),
+            KeyCode::Char('u') if ctrl => self.clear_to_start(),
+            KeyCode::Char(_) if ctrl => return false,
+            KeyCode::Char(c) => self.insert(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Renders the text as spans, with the cursor cell in reverse video.
+    ///
+    /// When the cursor is at the end, a trailing space is rendered as the
+    /// cursor cell.
+    pub fn spans(&self, style: Style) -> Vec<Span<'static>> {
+        let idx = self.byte_index();
+        let (before, rest) = self.value.split_at(idx);
+        let mut rest_chars = rest.chars();
+        let cursor_char = rest_chars.next().unwrap_or(' ');
+        let after: String = rest_chars.collect();
+
+        vec![
+            Span::styled(before.to_string(), style),
+            Span::styled(
+                cursor_char.to_string(),
+                style.add_modifier(Modifier::REVERSED),
+            ),
+            Span::styled(after, style),
+        ]
+    }
+
+    fn char_count(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn test_with_value_places_cursor_at_end() {
+        let input = TextInput::with_value("abc");
+        assert_eq!(input.value(), "abc");
+        assert_eq!(input.cursor(), 3);
+    }
+
+    #[test]
+    fn test_insert_in_middle() {
+        let mut input = TextInput::with_value("ac");
+        input.move_left();
+        input.insert('b');
+        assert_eq!(input.value(), "abc");
+        assert_eq!(input.cursor(), 2);
+    }
+
+    #[test]
+    fn test_backspace_and_delete() {
+        let mut input = TextInput::with_value("abcd");
+        input.backspace();
+        assert_eq!(input.value(), "abc");
+        input.move_home();
+        input.delete();
+        assert_eq!(input.value(), "bc");
+        input.backspace();
+        assert_eq!(input.value(), "bc", "backspace at start is a no-op");
+        input.move_end();
+        input.delete();
+        assert_eq!(input.value(), "bc", "delete at end is a no-op");
+    }
+
+    #[test]
+    fn test_cursor_bounds() {
+        let mut input = TextInput::with_value("ab");
+        input.move_right();
+        assert_eq!(input.cursor(), 2);
+        input.move_home();
+        input.move_left();
+        assert_eq!(input.cursor(), 0);
+    }
+
+    #[test]
+    fn test_multibyte_editing() {
+        let mut input = TextInput::with_value("héllo");
+        input.move_home();
+        input.move_right();
+        input.move_right();
+        input.backspace();
+        assert_eq!(input.value(), "hllo");
+        input.insert('é');
+        assert_eq!(input.value(), "héllo");
+    }
+
+    #[test]
+    fn test_handle_key_editing_keys() {
+        let mut input = TextInput::new();
+        assert!(input.handle_key(key(KeyCode::Char('x'))));
+        assert!(input.handle_key(key(KeyCode::Char('y'))));
+        assert!(input.handle_key(key(KeyCode::Left)));
+        assert!(input.handle_key(key(KeyCode::Backspace)));
+        assert_eq!(input.value(), "y");
+        assert!(input.handle_key(key(KeyCode::End)));
+        assert_eq!(input.cursor(), 1);
+    }
+
+    #[test]
+    fn test_handle_key_readline_shortcuts() {
+        let mut input = TextInput::with_value("hello world");
+        assert!(input.handle_key(ctrl('a')));
+        assert_eq!(input.cursor(), 0);
+        assert!(input.handle_key(ctrl('e')));
+        assert_eq!(input.cursor(), 11);
+        input.move_left();
+        input.move_left();
+        assert!(input.handle_key(ctrl('u')));
+        assert_eq!(input.value(), "ld");
+        assert_eq!(input.cursor(), 0);
+    }
+
+    #[test]
+    fn test_handle_key_leaves_submit_and_cancel_to_caller() {
+        let mut input = TextInput::new();
+        assert!(!input.handle_key(key(KeyCode::Enter)));
+        assert!(!input.handle_key(key(KeyCode::Esc)));
+        assert!(!input.handle_key(ctrl('c')));
+    }
+
+    #[test]
+    fn test_spans_mark_cursor_cell() {
+        let mut input = TextInput::with_value("abc");
+        input.move_left();
+        let spans = input.spans(Style::default());
+        assert_eq!(spans[0].content, "ab");
+        assert_eq!(spans[1].content, "c");
+        assert!(spans[1].style.add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(spans[2].content, "");
+    }
+
+    #[test]
+    fn test_spans_cursor_at_end_renders_space() {
+        let input = TextInput::with_value("abc");
+        let spans = input.spans(Style::default());
+        assert_eq!(spans[0].content, "abc");
+        assert_eq!(spans[1].content, " ");
+    }
+}
@@ -0,0 +1,277 @@
+//! Color themes for the TUI.
+//!
+//! Resolves `tui.theme` into the semantic styles every view draws with: a
+//! built-in theme, or a `[themes.<name>]` section of the config layered over
+//! one. Style strings are parsed by [`parse_style`], which the config schema
+//! also uses to reject bad colors at load time.
+
+use crate::config::schema::{Config, ThemeConfig};
+use crate::Status;
+use ratatui::style::{Color, Modifier, Style};
+use std::str::FromStr;
+
+/// Names of the built-in themes.
+pub const BUILTIN_THEMES: &[&str] = &["default", "monochrome"];
+
+/// Semantic styles used across the TUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Working sessions.
+    pub working: Style,
+    /// Sessions needing attention.
+    pub attention: Style,
+    /// Sessions asking a question.
+    pub question: Style,
+    /// Closed sessions.
+    pub closed: Style,
+    /// Inactive and dimmed sessions.
+    pub inactive: Style,
+    /// Borders, titles, column headers, and key hints.
+    pub accent: Style,
+    /// Emphasized text, like input being typed.
+    pub text: Style,
+    /// Secondary text: descriptions, placeholders, separators.
+    pub muted: Style,
+    /// Status messages, badges, project tags, and warnings.
+    pub warning: Style,
+    /// Errors and destructive actions.
+    pub error: Style,
+    /// Success indicators, like passing checks.
+    pub success: Style,
+    /// The selected session row.
+    pub selection: Style,
+    /// Rows and chips under the mouse.
+    pub hover: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let fg = |color| Style::new().fg(color);
+        Self {
+            working: fg(Color::Green),
+            attention: fg(Color::Yellow),
+            question: fg(Color::Blue),
+            closed: fg(Color::Gray),
+            inactive: fg(Color::DarkGray),
+            accent: fg(Color::Cyan),
+            text: fg(Color::White),
+            muted: fg(Color::DarkGray),
+            warning: fg(Color::Yellow),
+            error: fg(Color::Red),
+            success: fg(Color::Green),
+            selection: Style::new().bg(Color::DarkGray),
+            hover: Style::new().bg(Color::Indexed(237)),
+        }
+    }
+}
+
+impl Theme {
+    /// No colors; statuses are told apart by modifiers and icons.
+    pub fn monochrome() -> Self {
+        let modifier = |modifier| Style::new().add_modifier(modifier);
+        Self {
+            working: Style::new(),
+            attention: modifier(Modifier::BOLD),
+            question: modifier(Modifier::BOLD | Modifier::ITALIC),
+            closed: modifier(Modifier::DIM),
+            inactive: modifier(Modifier::DIM),
+            accent: modifier(Modifier::BOLD),
+            text: Style::new(),
+            muted: modifier(Modifier::DIM),
+            warning: modifier(Modifier::BOLD),
+            error: modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            success: Style::new(),
+            selection: modifier(Modifier::REVERSED),
+            hover: modifier(Modifier::UNDERLINED),
+        }
+    }
+
+    /// Returns the built-in theme named `name`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Resolves `config.tui.theme`, falling back to the default theme for
+    /// unknown names (the loader rejects those before they get here).
+    pub fn from_config(config: &Config) -> Self {
+        let name = config.tui.theme.as_str();
+        if let Some(theme) = Self::builtin(name) {
+            return theme;
+        }
+        match config.themes.get(name) {
+            Some(theme) => Self::from_theme_config(theme),
+            None => {
+                tracing::warn!("Unknown theme '{}', using the default theme", name);
+                Self::default()
+            }
+        }
+    }
+
+    /// Layers a user-defined theme over its `base` built-in theme.
+    ///
+    /// Styles that fail to parse keep the base style.
+    pub fn from_theme_config(config: &ThemeConfig) -> Self {
+        let mut theme = config
+            .base
+            .as_deref()
+            .and_then(Self::builtin)
+            .unwrap_or_default();
+        let overrides = [
+            (&config.working, &mut theme.working),
+            (&config.attention, &mut theme.attention),
+            (&config.question, &mut theme.question),
+            (&config.closed, &mut theme.closed),
+            (&config.inactive, &mut theme.inactive),
+            (&config.accent, &mut theme.accent),
+            (&config.text, &mut theme.text),
+            (&config.muted, &mut theme.muted),
+            (&config.warning, &mut theme.warning),
+            (&config.error, &mut theme.error),
+            (&config.success, &mut theme.success),
+            (&config.selection, &mut theme.selection),
+            (&config.hover, &mut theme.hover),
+        ];
+        for (spec, slot) in overrides {
+            if let Some(style) = spec.as_deref().and_then(|spec| parse_style(spec).ok()) {
+                *slot = style;
+            }
+        }
+        theme
+    }
+
+    /// Accent as a filled block, like the active tab: black on the accent
+    /// color, or the accent reversed when it has no color.
+    pub fn accent_fill(&self) -> Style {
+        let style = match self.accent.fg {
+            Some(color) => Style::new().fg(Color::Black).bg(color),
+            None => self.accent.add_modifier(Modifier::REVERSED),
+        };
+        style.add_modifier(Modifier::BOLD)
+    }
+
+    /// Style of a session with `status`.
+    pub fn status(&self, status: Status) -> Style {
+        match status {
+            Status::Working => self.working,
+            Status::Attention => self.attention,
+            Status::Question => self.question,
+            Status::Closed => self.closed,
+        }
+    }
+}
+
+/// Parses a style string like `"cyan bold"` or `"white on #303030"`.
+///
+/// Tokens are a foreground color, `on <color>` for the background, and the
+/// modifiers `bold`, `dim`, `italic`, `underlined`, `reversed`. Colors are
+/// names (`red`, `light-blue`), `#rrggbb`, or 256-color indexes (`237`).
+pub fn parse_style(spec: &str) -> Result<Style, String> {
+    let mut style = Style::new();
+    let mut tokens = spec.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let modifier = match token.to_ascii_lowercase().as_str() {
+            "bold" => Some(Modifier::BOLD),
+            "dim" => Some(Modifier::DIM),
+            "italic" => Some(Modifier::ITALIC),
+            "underlined" => Some(Modifier::UNDERLINED),
+            "reversed" => Some(Modifier::REVERSED),
+            _ => None,
+        };
+        style = match (modifier, token) {
+            (Some(modifier), _) => style.add_modifier(modifier),
+            (None, "on") => {
+                let color = tokens
+                    .next()
+                    .ok_or_else(|| format!("missing color after `on` in style `{spec}`"))?;
+                style.bg(parse_color(color, spec)?)
+            }
+            (None, color) => style.fg(parse_color(color, spec)?),
+        };
+    }
+    Ok(style)
+}
+
+/// Parses one color of the style string `spec`.
+fn parse_color(color: &str, spec: &str) -> Result<Color, String> {
+    Color::from_str(color).map_err(|_| {
+        format!(
+            "invalid color `{color}` in style `{spec}`; use a name like `red` or \
+             `light-blue`, `#rrggbb`, or a 0-255 index"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_style_reads_colors_and_modifiers() {
+        assert_eq!(
+            parse_style("cyan bold").expect("valid"),
+            Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            parse_style("white on #303030").expect("valid"),
+            Style::new()
+                .fg(Color::White)
+                .bg(Color::Rgb(0x30, 0x30, 0x30))
+        );
+        assert_eq!(
+            parse_style("on 237").expect("valid"),
+            Style::new().bg(Color::Indexed(237))
+        );
+        assert_eq!(parse_style("").expect("valid"), Style::new());
+    }
+
+    #[test]
+    fn parse_style_rejects_bad_colors() {
+        let err = parse_style("cyn bold").expect_err("bad color");
+        assert!(err.contains("invalid color `cyn`"), "{err}");
+        let err = parse_style("red on").expect_err("missing background");
+        assert!(err.contains("missing color after `on`"), "{err}");
+    }
+
+    #[test]
+    fn from_config_layers_user_theme_over_base() {
+        let mut config = Config::default();
+        config.tui.theme = "mine".to_string();
+        config.themes.insert(
+            "mine".to_string(),
+            ThemeConfig {
+                base: Some("monochrome".to_string()),
+                attention: Some("#ff8800 bold".to_string()),
+                ..ThemeConfig::default()
+            },
+        );
+        let theme = Theme::from_config(&config);
+        assert_eq!(
+            theme.attention,
+            Style::new()
+                .fg(Color::Rgb(0xff, 0x88, 0x00))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(theme.accent, Theme::monochrome().accent);
+    }
+
+    #[test]
+    fn from_config_resolves_builtins_and_unknown_names() {
+        let mut config = Config::default();
+        assert_eq!(Theme::from_config(&config), Theme::default());
+        config.tui.theme = "monochrome".to_string();
+        assert_eq!(Theme::from_config(&config), Theme::monochrome());
+        config.tui.theme = "missing".to_string();
+        assert_eq!(Theme::from_config(&config), Theme::default());
+    }
+
+    #[test]
+    fn status_styles_follow_theme() {
+        let theme = Theme::default();
+        assert_eq!(theme.status(Status::Working).fg, Some(Color::Green));
+        assert_eq!(theme.status(Status::Question).fg, Some(Color::Blue));
+    }
+}
@@ -3,15 +3,25 @@
 //! Provides the top-level `render_dashboard` function that composes
 //! the header, session list, and footer into a cohesive layout.
 
-use crate::tui::app::{App, LayoutMode, TWO_LINE_LAYOUT_HEIGHT_THRESHOLD};
-use crate::tui::views::dashboard::render_session_list;
+use crate::tui::app::{App, LayoutMode, View};
+use crate::tui::footer::{render_footer_template, FooterValues};
+use crate::tui::icons::{glyph_width, StatusIcons};
+use crate::tui::theme::Theme;
+use crate::tui::views::alerts::{alerts_panel_height, render_alerts_panel};
+use crate::tui::views::dashboard::{
+    format_rename_line, render_session_list, render_session_list_condensed,
+};
 use crate::tui::views::detail::{render_detail_placeholder, render_inline_detail};
+use crate::tui::views::embedded::render_embedded_terminal;
+use crate::tui::views::palette::render_command_palette;
+use crate::tui::views::tabs::{format_tab_bar, tab_bar_height};
+use crate::tui::views::usage_chart::render_usage_chart;
 use crate::widgets::{api_usage::ApiUsageWidget, Widget, WidgetContext};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 use std::time::Instant;
@@ -19,9 +29,27 @@ use std::time::Instant;
 /// Header text displayed at the top of the dashboard.
 const HEADER_TEXT: &str = "Agent Console Dashboard";
 
+/// Badge appended to the header title in read-only mode.
+const READ_ONLY_BADGE: &str = " [read-only]";
+
+/// Short header text used by the condensed (Medium layout) header.
+const HEADER_TEXT_SHORT: &str = "ACD";
+
 /// Footer text showing available keybindings.
 const FOOTER_TEXT: &str = "[j/k] Navigate  [Enter] Hook  [s] Copy ID  [r] Resurrect  [q] Quit";
 
+/// Footer text shown in the usage history chart.
+const USAGE_CHART_FOOTER_TEXT: &str = "[h/l] Move  [Home/End] Oldest/Latest  [Esc/u] Back";
+
+/// Footer text shown while the command palette is open.
+const PALETTE_FOOTER_TEXT: &str = "[j/k] Move  [Enter] Run  [1-9] Run #  [Esc] Close";
+
+/// Footer text shown while an inline rename is active.
+const RENAME_FOOTER_TEXT: &str = "[Enter] Save  [Esc] Cancel  (empty name resets)";
+
+/// Share of the screen height taken by the embedded terminal pane.
+const EMBEDDED_TERMINAL_PERCENT: u16 = 60;
+
 /// Version string shown in the header (right-aligned).
 const VERSION_TEXT: &str = concat!("v", env!("CARGO_PKG_VERSION"));
 
@@ -31,57 +59,189 @@ const VERSION_TEXT: &str = concat!("v", env!("CARGO_PKG_VERSION"));
 /// about the currently focused session, or a hint message when no session is focused.
 ///
 /// Layout modes:
-/// - Large (height >= 5): Header, session list, detail panel, footer
-/// - TwoLine (height < 5): Session chips (line 1), API usage (line 2)
+/// - Large (height >= 13): Header, session list, detail panel, footer
+/// - Medium (7 <= height < 13): Condensed header, session list, footer
+/// - TwoLine (height < 7): Session chips (line 1), API usage (line 2)
 ///
 /// When `app.layout_mode_override` is `Some(mode)`, that mode is used regardless of
 /// terminal height. Otherwise, layout mode is auto-detected from terminal height.
 ///
+/// While a session runs in the embedded terminal, its pane takes the bottom
+/// [`EMBEDDED_TERMINAL_PERCENT`] of the screen and the dashboard the rest.
+///
 /// Updates `app.session_list_inner_area` with the inner Rect of the session list
-/// for accurate mouse click detection.
+/// (and `app.alerts_inner_area` with the alerts panel, if shown) for accurate
+/// mouse click detection.
 pub fn render_dashboard(frame: &mut Frame, app: &mut App) {
-    let area = frame.area();
+    let mut area = frame.area();
     let now = Instant::now();
+    app.alerts_inner_area = None;
 
     // Store terminal width for mouse click detection
     app.terminal_width = area.width;
 
-    // Use override if present, otherwise auto-detect from terminal height
-    app.layout_mode = if let Some(override_mode) = app.layout_mode_override {
-        override_mode
-    } else if area.height < TWO_LINE_LAYOUT_HEIGHT_THRESHOLD {
-        LayoutMode::TwoLine
-    } else {
-        LayoutMode::Large
-    };
+    app.embedded_area = None;
+    if let Some(embedded) = app.embedded.as_mut() {
+        let [dashboard, pane] = Layout::vertical([
+            Constraint::Percentage(100 - EMBEDDED_TERMINAL_PERCENT),
+            Constraint::Percentage(EMBEDDED_TERMINAL_PERCENT),
+        ])
+        .areas(area);
+        render_embedded_terminal(frame, embedded, pane, app.embedded_focused, &app.theme);
+        app.embedded_area = Some(pane);
+        area = dashboard;
+    }
+
+    // Use override if present, otherwise auto-detect from terminal height.
+    // Beside the embedded terminal the detail panel would crowd out the list.
+    app.layout_mode =
+        app.layout_mode_override
+            .unwrap_or_else(|| match LayoutMode::for_height(area.height) {
+                LayoutMode::Large if app.embedded_area.is_some() => LayoutMode::Medium,
+                mode => mode,
+            });
+
+    if let View::UsageChart { .. } = app.view {
+        render_usage_chart_view(frame, app, area);
+        return;
+    }
 
     match app.layout_mode {
         LayoutMode::Large => render_large_layout(frame, app, area, now),
+        LayoutMode::Medium => render_medium_layout(frame, app, area),
         LayoutMode::TwoLine => render_two_line_layout(frame, app, area, now),
     }
+
+    if app.layout_mode != LayoutMode::TwoLine {
+        render_list_hover(frame, app, area);
+    }
+
+    if let Some(palette) = &app.palette {
+        if let Some(session) = app
+            .sessions
+            .iter()
+            .find(|s| s.session_id == palette.session_id)
+        {
+            render_command_palette(
+                frame,
+                area,
+                palette,
+                &app.session_commands,
+                &crate::tui::views::dashboard::session_label(session),
+                &app.theme,
+            );
+        }
+    }
 }
 
-/// Renders the Large layout mode: header, session list, detail panel, footer.
+/// Highlights the hovered session row and draws its tooltip.
+///
+/// The selected row keeps its own highlight. The tooltip shows the full
+/// working directory and session ID, which the list truncates, and sits below
+/// the hovered row (above it near the bottom edge).
+fn render_list_hover(frame: &mut Frame, app: &App, area: Rect) {
+    let (Some(idx), Some(inner)) = (app.hovered_index, app.session_list_inner_area) else {
+        return;
+    };
+    let Some(session) = app.sessions.get(idx) else {
+        return;
+    };
+    let row = inner.y + idx as u16;
+    if row >= inner.y + inner.height {
+        return;
+    }
+    if app.selected_index != Some(idx) {
+        frame
+            .buffer_mut()
+            .set_style(Rect::new(inner.x, row, inner.width, 1), app.theme.hover);
+    }
+
+    if !app.hover_tooltips {
+        return;
+    }
+    let lines = [
+        format!("dir: {}", working_dir_text(session)),
+        format!("id:  {}", session.session_id),
+    ];
+    let content_width = lines.iter().map(|l| Span::raw(l.as_str()).width()).max();
+    let width = (content_width.unwrap_or(0) as u16 + 2).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let column = app.hover_position.map_or(inner.x, |(col, _)| col);
+    let x = column.min(area.x + area.width - width);
+    let y = if row + 1 + height <= area.y + area.height {
+        row + 1
+    } else {
+        row.saturating_sub(height).max(area.y)
+    };
+    let tooltip_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, tooltip_area);
+    frame.render_widget(
+        Paragraph::new(lines.map(Line::from).to_vec()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(app.theme.muted),
+        ),
+        tooltip_area,
+    );
+}
+
+/// Returns a session's full working directory, or `<unknown>`.
+fn working_dir_text(session: &crate::Session) -> String {
+    session
+        .working_dir
+        .as_ref()
+        .map_or_else(|| "<unknown>".to_string(), |d| d.display().to_string())
+}
+
+/// Renders the full-screen usage history chart with its key hints footer.
+///
+/// Status messages still take over the footer while active.
+fn render_usage_chart_view(frame: &mut Frame, app: &App, area: ratatui::prelude::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+    let cursor = app.usage_chart_cursor().unwrap_or(0);
+    render_usage_chart(frame, chunks[0], &app.usage_history, cursor, &app.theme);
+
+    let footer = match app.status_message {
+        Some((ref msg, expiry)) if Instant::now() < expiry => {
+            Line::styled(msg.clone(), app.theme.warning)
+        }
+        _ => Line::styled(USAGE_CHART_FOOTER_TEXT, app.theme.muted),
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
+
+/// Renders the Large layout mode: header, workspace tab bar (only with two or
+/// more projects), session list, alerts panel (only while alerts are
+/// unacknowledged), detail panel, footer.
 fn render_large_layout(
     frame: &mut Frame,
     app: &mut App,
     area: ratatui::prelude::Rect,
     now: Instant,
 ) {
-    // Detail panel is always visible
+    // Detail panel is always visible; the alerts panel collapses to zero rows
+    let tabs_height = tab_bar_height(&app.tabs);
+    let alerts_height = alerts_panel_height(app.alerts.len());
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),  // header
-            Constraint::Min(3),     // session list (minimum 3 rows)
-            Constraint::Length(12), // detail panel (always visible)
-            Constraint::Length(1),  // footer
+            Constraint::Length(1),             // header
+            Constraint::Length(tabs_height),   // workspace tabs (single project: hidden)
+            Constraint::Min(3),                // session list (minimum 3 rows)
+            Constraint::Length(alerts_height), // sticky alerts (hidden when empty)
+            Constraint::Length(12),            // detail panel (always visible)
+            Constraint::Length(1),             // footer
         ])
         .split(area);
 
     // Header with title (left) and version (right-aligned)
     let header_width = chunks[0].width as usize;
-    let title_len = HEADER_TEXT.len();
+    let badge = if app.read_only { READ_ONLY_BADGE } else { "" };
+    let title_len = HEADER_TEXT.len() + badge.len();
     let version_len = VERSION_TEXT.len();
 
     // Calculate padding to position version at the right
@@ -90,59 +250,203 @@ fn render_large_layout(
     let padding_len = available_space.saturating_sub(version_len);
 
     let header = Paragraph::new(Line::from(vec![
-        Span::styled(HEADER_TEXT, Style::default().fg(Color::Cyan)),
+        Span::styled(HEADER_TEXT, app.theme.accent),
+        Span::styled(badge, app.theme.warning),
         Span::raw(" ".repeat(padding_len)),
-        Span::styled(VERSION_TEXT, Style::default().fg(Color::DarkGray)),
+        Span::styled(VERSION_TEXT, app.theme.muted),
     ]));
     frame.render_widget(header, chunks[0]);
 
+    if tabs_height > 0 {
+        frame.render_widget(
+            Paragraph::new(format_tab_bar(
+                &app.tabs,
+                &app.all_sessions,
+                &app.icons,
+                &app.theme,
+            )),
+            chunks[1],
+        );
+    }
+
     // Session list - capture inner area for mouse click detection
     let inner_area = render_session_list(
         frame,
-        chunks[1],
+        chunks[2],
         &app.sessions,
         app.selected_index,
         area.width,
+        &app.icons,
+        &app.theme,
+        app.time_format,
+        app.rename.as_ref(),
     );
     app.session_list_inner_area = Some(inner_area);
 
+    if !app.alerts.is_empty() {
+        let alerts_area =
+            render_alerts_panel(frame, chunks[3], &app.alerts, &app.icons, &app.theme);
+        app.alerts_inner_area = Some(alerts_area);
+    }
+
     // Detail panel (always visible — shows focused session or placeholder)
     if let Some(selected_idx) = app.selected_index {
         if let Some(session) = app.sessions.get(selected_idx) {
-            render_inline_detail(frame, session, chunks[2], app.history_scroll, now);
+            render_inline_detail(
+                frame,
+                session,
+                app.branch_info(session),
+                chunks[4],
+                app.history_scroll,
+                now,
+                app.time_format,
+                &app.theme,
+            );
         } else {
-            render_detail_placeholder(frame, chunks[2]);
+            render_detail_placeholder(frame, chunks[4], &app.theme);
         }
     } else {
-        render_detail_placeholder(frame, chunks[2]);
+        render_detail_placeholder(frame, chunks[4], &app.theme);
+    }
+
+    render_footer(frame, app, chunks[5]);
+}
+
+/// Renders the Medium layout mode: condensed header, workspace tab bar (only
+/// with two or more projects), session list, footer.
+///
+/// Drops the detail panel and the session list borders so that every row
+/// between the header and footer is available for sessions.
+fn render_medium_layout(frame: &mut Frame, app: &mut App, area: ratatui::prelude::Rect) {
+    let tabs_height = tab_bar_height(&app.tabs);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),           // condensed header
+            Constraint::Length(tabs_height), // workspace tabs (single project: hidden)
+            Constraint::Min(1),              // session list
+            Constraint::Length(1),           // footer
+        ])
+        .split(area);
+
+    frame.render_widget(
+        Paragraph::new(render_condensed_header(
+            &app.sessions,
+            &app.icons,
+            &app.theme,
+            app.read_only,
+            chunks[0].width,
+        )),
+        chunks[0],
+    );
+
+    if tabs_height > 0 {
+        frame.render_widget(
+            Paragraph::new(format_tab_bar(
+                &app.tabs,
+                &app.all_sessions,
+                &app.icons,
+                &app.theme,
+            )),
+            chunks[1],
+        );
     }
 
-    // Footer (with optional status message overlay)
-    // When status message is active, it overrides the entire footer
-    let footer_text = if let Some((ref msg, expiry)) = app.status_message {
-        if Instant::now() < expiry {
+    let inner_area = render_session_list_condensed(
+        frame,
+        chunks[2],
+        &app.sessions,
+        app.selected_index,
+        area.width,
+        &app.icons,
+        &app.theme,
+        app.time_format,
+        app.rename.as_ref(),
+    );
+    app.session_list_inner_area = Some(inner_area);
+
+    render_footer(frame, app, chunks[3]);
+}
+
+/// Builds the condensed one-line header used by the Medium layout.
+///
+/// Format: `ACD  N sessions  ! M attention` on the left, version on the right,
+/// where `!` is the configured attention icon. The session counts replace the
+/// session list's bordered title. In read-only mode a badge follows the counts.
+fn render_condensed_header(
+    sessions: &[crate::Session],
+    icons: &StatusIcons,
+    theme: &Theme,
+    read_only: bool,
+    width: u16,
+) -> Line<'static> {
+    let attention = sessions
+        .iter()
+        .filter(|s| s.status == crate::Status::Attention)
+        .count();
+    let summary = format!(
+        "  {} sessions  {} {} attention",
+        sessions.len(),
+        icons.attention,
+        attention
+    );
+
+    let badge = if read_only { READ_ONLY_BADGE } else { "" };
+
+    let used = HEADER_TEXT_SHORT.len()
+        + Span::raw(summary.as_str()).width()
+        + badge.len()
+        + VERSION_TEXT.len();
+    let padding_len = (width as usize).saturating_sub(used);
+
+    Line::from(vec![
+        Span::styled(HEADER_TEXT_SHORT, theme.accent),
+        Span::styled(summary, theme.muted),
+        Span::styled(badge, theme.warning),
+        Span::raw(" ".repeat(padding_len)),
+        Span::styled(VERSION_TEXT, theme.muted),
+    ])
+}
+
+/// Renders the footer line (with optional status message overlay).
+///
+/// When a status message is active, it overrides the entire footer. While an
+/// inline rename is active, the footer shows the rename key hints. Otherwise a
+/// non-empty `tui.footer` template replaces the built-in hints and usage.
+fn render_footer(frame: &mut Frame, app: &App, area: ratatui::prelude::Rect) {
+    let theme = &app.theme;
+    let footer_text = match app.status_message {
+        Some((ref msg, expiry)) if Instant::now() < expiry => {
+            Line::from(vec![Span::styled(msg.clone(), theme.warning)])
+        }
+        _ if app.rename.is_some() => {
+            Line::from(vec![Span::styled(RENAME_FOOTER_TEXT, theme.accent)])
+        }
+        _ if app.palette.is_some() => {
+            Line::from(vec![Span::styled(PALETTE_FOOTER_TEXT, theme.accent)])
+        }
+        _ if !app.footer_template.is_empty() => {
+            let values = FooterValues {
+                sessions: &app.sessions,
+                usage: app.usage.as_ref(),
+                usage_blocked: app.usage_blocked,
+                socket: &app.socket_path,
+                keys_hint: FOOTER_TEXT,
+            };
             Line::from(vec![Span::styled(
-                msg.clone(),
-                Style::default().fg(Color::Yellow),
+                render_footer_template(&app.footer_template, &values),
+                theme.muted,
             )])
-        } else {
-            render_footer_normal(
-                &app.sessions,
-                app.usage.as_ref(),
-                app.usage_blocked,
-                chunks[3].width as usize,
-            )
         }
-    } else {
-        render_footer_normal(
+        _ => render_footer_normal(
             &app.sessions,
             app.usage.as_ref(),
             app.usage_blocked,
-            chunks[3].width as usize,
-        )
+            area.width as usize,
+            theme,
+        ),
     };
-    let footer = Paragraph::new(footer_text);
-    frame.render_widget(footer, chunks[3]);
+    frame.render_widget(Paragraph::new(footer_text), area);
 }
 
 /// Renders the TwoLine layout mode: session chips (line 1), API usage (line 2).
@@ -167,6 +471,8 @@ fn render_two_line_layout(
         app.compact_scroll_offset,
         chunks[0].width,
         now,
+        &app.icons,
+        &app.theme,
     );
 
     // Auto-scroll to keep selected chip visible
@@ -176,33 +482,43 @@ fn render_two_line_layout(
     let session_paragraph = Paragraph::new(session_line);
     frame.render_widget(session_paragraph, chunks[0]);
 
-    // Line 2: Status message (if active) or API usage
-    if let Some((ref msg, expiry)) = app.status_message {
-        if now < expiry {
-            // Show status message (yellow)
-            let status_line = Line::from(vec![Span::styled(
-                msg.clone(),
-                Style::default().fg(Color::Yellow),
-            )]);
-            let status_paragraph = Paragraph::new(status_line);
-            frame.render_widget(status_paragraph, chunks[1]);
-        } else {
-            // Status message expired, render API usage
-            let mut ctx = WidgetContext::new(&app.sessions);
-            ctx.now = now;
-            if let Some(ref usage) = app.usage {
-                ctx = ctx.with_usage(usage);
-            }
-            if app.usage_blocked {
-                ctx = ctx.with_usage_blocked();
+    // Highlight the hovered chip (the focused chip keeps its own style)
+    if let Some(hovered) = app.hovered_index.filter(|&i| app.selected_index != Some(i)) {
+        let row = chunks[0];
+        for col in row.x..row.x + row.width {
+            if app.chip_at_column(col) == Some(hovered) {
+                frame
+                    .buffer_mut()
+                    .set_style(Rect::new(col, row.y, 1, 1), app.theme.hover);
             }
-            let api_widget = ApiUsageWidget::new();
-            let api_line = api_widget.render(chunks[1].width, &ctx);
-            let api_paragraph = Paragraph::new(api_line);
-            frame.render_widget(api_paragraph, chunks[1]);
         }
+    }
+
+    // Line 2: Inline rename input, status message (if active), hover tooltip,
+    // or API usage
+    let hovered_session = app
+        .hovered_index
+        .filter(|_| app.hover_tooltips)
+        .and_then(|i| app.sessions.get(i));
+    if let Some(ref rename) = app.rename {
+        let rename_line = format_rename_line(&rename.input, chunks[1].width, &app.theme);
+        frame.render_widget(Paragraph::new(rename_line), chunks[1]);
+    } else if let Some((msg, _)) = app
+        .status_message
+        .as_ref()
+        .filter(|(_, expiry)| now < *expiry)
+    {
+        // Show status message (yellow)
+        let status_line = Line::from(vec![Span::styled(msg.clone(), app.theme.warning)]);
+        frame.render_widget(Paragraph::new(status_line), chunks[1]);
+    } else if let Some(session) = hovered_session {
+        // Chips truncate labels heavily, so the tooltip takes over line 2
+        let tooltip_line = Line::from(vec![
+            Span::raw(working_dir_text(session)),
+            Span::styled(format!("  {}", session.session_id), app.theme.muted),
+        ]);
+        frame.render_widget(Paragraph::new(tooltip_line), chunks[1]);
     } else {
-        // No status message, render API usage
         let mut ctx = WidgetContext::new(&app.sessions);
         ctx.now = now;
         if let Some(ref usage) = app.usage {
@@ -224,7 +540,7 @@ fn render_two_line_layout(
 /// Renders the normal footer layout: keybinding hints left, API usage right.
 ///
 /// The footer is split into two parts:
-/// - LEFT: keybinding hints (DarkGray)
+/// - LEFT: keybinding hints (muted)
 /// - RIGHT: API usage widget in SHORT format (width < 30 to force SHORT)
 ///
 /// If the terminal is too narrow to fit both, only hints are shown.
@@ -233,6 +549,7 @@ fn render_footer_normal(
     usage: Option<&claude_usage::UsageData>,
     usage_blocked: bool,
     footer_width: usize,
+    theme: &Theme,
 ) -> Line<'static> {
     let hints_text = FOOTER_TEXT;
     let hints_len = hints_text.len();
@@ -259,10 +576,7 @@ fn render_footer_normal(
 
     if footer_width < min_width {
         // Not enough space — only show hints
-        return Line::from(vec![Span::styled(
-            hints_text,
-            Style::default().fg(Color::DarkGray),
-        )]);
+        return Line::from(vec![Span::styled(hints_text, theme.muted)]);
     }
 
     // Calculate padding to position API usage on the right
@@ -272,10 +586,7 @@ fn render_footer_normal(
 
     // Build footer: hints (left) + padding + API usage (right)
     // Convert api_usage_line spans to owned Spans with cloned content
-    let mut spans = vec![Span::styled(
-        hints_text,
-        Style::default().fg(Color::DarkGray),
-    )];
+    let mut spans = vec![Span::styled(hints_text, theme.muted)];
     spans.push(Span::raw(" ".repeat(padding_len)));
 
     // Clone api_usage_line spans to owned Spans
@@ -337,14 +648,15 @@ fn truncate_from_start(name: &str, max_len: usize) -> String {
 ///   - focused]| unfocused: `]| `
 ///
 /// The separator is NOT part of the chip width — it's rendered between chips.
-fn chip_width(name: &str, is_focused: bool) -> usize {
-    let name_len = name.len();
+/// The symbol is measured in terminal cells, so wide icons (emoji) count as two.
+pub(crate) fn chip_width(name: &str, symbol: &str, is_focused: bool) -> usize {
+    let content_len = glyph_width(symbol) + 1 + name.len();
     if is_focused {
-        // '[' + symbol + ' ' + name + ']' = 4 + name_len
-        4 + name_len
+        // '[' + symbol + ' ' + name + ']'
+        content_len + 2
     } else {
-        // ' ' + symbol + ' ' + name = 3 + name_len
-        3 + name_len
+        // ' ' + symbol + ' ' + name
+        content_len + 1
     }
 }
 
@@ -364,14 +676,18 @@ fn chip_width(name: &str, is_focused: bool) -> usize {
 /// * `scroll_offset` - Index of leftmost visible session
 /// * `available_width` - Terminal width for this line
 /// * `_now` - Current time for elapsed time calculations (unused for now)
+/// * `icons` - Status icons used for each chip's symbol
+/// * `theme` - Styles for the chips and overflow indicators
 fn render_compact_session_chips(
     sessions: &[crate::Session],
     selected_index: Option<usize>,
     scroll_offset: usize,
     available_width: u16,
     _now: Instant,
+    icons: &StatusIcons,
+    theme: &Theme,
 ) -> Line<'static> {
-    use crate::tui::views::dashboard::{get_directory_display_name, status_color, status_symbol};
+    use crate::tui::views::dashboard::session_label;
 
     if sessions.is_empty() {
         return Line::raw("(no sessions)");
@@ -394,14 +710,14 @@ fn render_compact_session_chips(
         let is_focused = selected_index == Some(i);
 
         // Get display name
-        let display_name = get_directory_display_name(session);
+        let display_name = session_label(session);
         let label = if display_name == "<error>" {
             session.session_id.chars().take(8).collect()
         } else {
             truncate_from_start(&display_name, 12)
         };
 
-        let this_chip_width = chip_width(&label, is_focused);
+        let this_chip_width = chip_width(&label, icons.session_symbol(session), is_focused);
 
         // Add separator width (3 chars: " | ") except for first chip
         let separator_width = if offset > 0 { 3 } else { 0 };
@@ -427,15 +743,9 @@ fn render_compact_session_chips(
 
     // Left overflow indicator (always shown)
     if overflow_left > 0 {
-        spans.push(Span::styled(
-            format!("<- {}+|", overflow_left),
-            Style::default().fg(Color::DarkGray),
-        ));
+        spans.push(Span::styled(format!("<- {}+|", overflow_left), theme.muted));
     } else {
-        spans.push(Span::styled(
-            "<- 0 |".to_string(),
-            Style::default().fg(Color::DarkGray),
-        ));
+        spans.push(Span::styled("<- 0 |".to_string(), theme.muted));
     }
 
     // Visible chips
@@ -446,19 +756,16 @@ fn render_compact_session_chips(
         let inactive = session.is_inactive(crate::INACTIVE_SESSION_THRESHOLD);
         let should_dim = inactive || session.status.should_dim();
 
-        // Use dot symbol for inactive sessions, otherwise use status-specific symbol
-        let (symbol, color) = if should_dim {
-            if inactive {
-                (".", Color::DarkGray)
-            } else {
-                (status_symbol(session.status), Color::DarkGray)
-            }
+        // Inactive sessions use the inactive icon, otherwise the status-specific icon
+        let symbol = icons.session_symbol(session);
+        let color = if should_dim {
+            theme.inactive
         } else {
-            (status_symbol(session.status), status_color(session.status))
+            theme.status(session.status)
         };
 
         // Display name: folder basename, or fallback to short session_id (first 8 chars)
-        let display_name = get_directory_display_name(session);
+        let display_name = session_label(session);
         let label = if display_name == "<error>" {
             session.session_id.chars().take(8).collect()
         } else {
@@ -472,15 +779,9 @@ fn render_compact_session_chips(
             // Otherwise: " |"
             let prev_was_focused = selected_index == Some(global_index - 1);
             if prev_was_focused {
-                spans.push(Span::styled(
-                    "|".to_string(),
-                    Style::default().fg(Color::DarkGray),
-                ));
+                spans.push(Span::styled("|".to_string(), theme.muted));
             } else {
-                spans.push(Span::styled(
-                    " |".to_string(),
-                    Style::default().fg(Color::DarkGray),
-                ));
+                spans.push(Span::styled(" |".to_string(), theme.muted));
             }
         }
 
@@ -488,18 +789,18 @@ fn render_compact_session_chips(
         if is_selected {
             spans.push(Span::styled(
                 "[".to_string(),
-                Style::default().fg(color).add_modifier(Modifier::BOLD),
+                color.add_modifier(Modifier::BOLD),
             ));
         } else {
-            spans.push(Span::styled(" ".to_string(), Style::default().fg(color)));
+            spans.push(Span::styled(" ".to_string(), color));
         }
 
         // Chip content: symbol + space + label
         let chip_content = format!("{} {}", symbol, label);
         let style = if is_selected {
-            Style::default().fg(color).add_modifier(Modifier::BOLD)
+            color.add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(color)
+            color
         };
         spans.push(Span::styled(chip_content, style));
 
@@ -509,7 +810,7 @@ fn render_compact_session_chips(
         if is_selected {
             spans.push(Span::styled(
                 "]".to_string(),
-                Style::default().fg(color).add_modifier(Modifier::BOLD),
+                color.add_modifier(Modifier::BOLD),
             ));
         }
     }
@@ -521,26 +822,20 @@ fn render_compact_session_chips(
         if last_selected {
             spans.push(Span::styled(
                 format!("|{}+ ->", overflow_right),
-                Style::default().fg(Color::DarkGray),
+                theme.muted,
             ));
         } else {
             spans.push(Span::styled(
                 format!(" |{}+ ->", overflow_right),
-                Style::default().fg(Color::DarkGray),
+                theme.muted,
             ));
         }
     } else {
         let last_selected = selected_index == Some(end - 1);
         if last_selected {
-            spans.push(Span::styled(
-                "| 0 ->".to_string(),
-                Style::default().fg(Color::DarkGray),
-            ));
+            spans.push(Span::styled("| 0 ->".to_string(), theme.muted));
         } else {
-            spans.push(Span::styled(
-                " | 0 ->".to_string(),
-                Style::default().fg(Color::DarkGray),
-            ));
+            spans.push(Span::styled(" | 0 ->".to_string(), theme.muted));
         }
     }
 
@@ -551,6 +846,7 @@ fn render_compact_session_chips(
 mod tests {
     use super::*;
     use crate::{AgentType, Session, Status};
+    use ratatui::style::{Color, Style};
     use std::path::PathBuf;
 
     fn make_app() -> App {
@@ -742,7 +1038,7 @@ mod tests {
     // --- Full Dashboard Integration Tests (acd-211) ---
 
     use crate::tui::test_utils::{
-        find_row_with_text, render_dashboard_to_buffer, row_contains, row_text,
+        assert_bg_color, find_row_with_text, render_dashboard_to_buffer, row_contains, row_text,
     };
 
     #[test]
@@ -911,12 +1207,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 8.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         });
 
@@ -943,12 +1243,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 8.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         });
 
@@ -986,6 +1290,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_usage_chart_view_empty_history() {
+        let mut app = make_app_with_sessions(1);
+        app.open_usage_chart();
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 24);
+        assert!(find_row_with_text(&buffer, "API Usage History").is_some());
+        assert!(find_row_with_text(&buffer, "No usage samples yet").is_some());
+        assert!(row_contains(&buffer, 23, "[Esc/u] Back"));
+    }
+
+    #[test]
+    fn test_usage_chart_view_shows_cursor_sample() {
+        use crate::tui::usage_history::UsageSample;
+        let mut app = make_app_with_sessions(1);
+        for (five_hour, seven_day) in [(10.0, 40.0), (42.7, 77.0), (60.0, 80.0)] {
+            app.usage_history.push(UsageSample {
+                at: chrono::Local::now(),
+                five_hour,
+                seven_day,
+            });
+        }
+        app.open_usage_chart();
+        app.move_usage_chart_cursor(-1);
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 24);
+        assert!(
+            find_row_with_text(&buffer, "5h: 42%  7d: 77%  (2/3)").is_some(),
+            "info line shows the sample under the cursor"
+        );
+    }
+
+    #[test]
+    fn test_alerts_panel_shown_only_with_alerts() {
+        let mut app = make_app_with_sessions(2);
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        assert!(find_row_with_text(&buffer, "Alerts (").is_none());
+
+        app.alerts
+            .record("session-1", "project-1", Status::Question);
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        let title_row = find_row_with_text(&buffer, "Alerts (1)").expect("alerts panel title");
+        assert!(row_contains(&buffer, title_row + 1, "project-1  question"));
+        assert_eq!(
+            app.alerts_inner_area.map(|r| r.y),
+            Some(title_row + 1),
+            "inner area recorded for click detection"
+        );
+    }
+
+    #[test]
+    fn test_header_shows_read_only_badge() {
+        let mut app = make_app_with_sessions(1);
+        app.read_only = true;
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 24);
+        assert!(row_contains(&buffer, 0, "[read-only]"));
+
+        app.read_only = false;
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 24);
+        assert!(!row_contains(&buffer, 0, "[read-only]"));
+    }
+
+    #[test]
+    fn test_footer_custom_template_replaces_builtin() {
+        let mut app = make_app_with_sessions(3);
+        app.footer_template = "open {session_count}  5h {usage_5h}".to_string();
+
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 24);
+        let footer_row = buffer.area().height - 1;
+
+        assert!(row_contains(&buffer, footer_row, "open 3  5h --"));
+        assert!(
+            !row_contains(&buffer, footer_row, "[j/k] Navigate"),
+            "Built-in hints should not render unless {{keys_hint}} is used"
+        );
+    }
+
     #[test]
     fn test_footer_narrow_terminal_shows_only_hints() {
         use claude_usage::{UsageData, UsagePeriod};
@@ -994,12 +1373,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 8.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         });
 
@@ -1029,12 +1412,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 8.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         });
         app.status_message = Some((
@@ -1064,18 +1451,122 @@ mod tests {
     // --- Layout mode tests ---
 
     #[test]
-    fn test_layout_mode_auto_detects_large_for_height_7() {
+    fn test_layout_mode_auto_detects_medium_for_height_7() {
         let mut app = make_app_with_sessions(3);
         let buffer = render_dashboard_to_buffer(&mut app, 80, 7);
-        // Height 7 (= threshold) should use Large mode (threshold is < 7)
+        // Height 7 (= TwoLine threshold) should use Medium mode
+        assert_eq!(app.layout_mode, crate::tui::app::LayoutMode::Medium);
+        // Should have the condensed header, not the full title
+        assert!(
+            row_text(&buffer, 0).starts_with("ACD"),
+            "Medium mode should show condensed header"
+        );
+        assert!(
+            find_row_with_text(&buffer, "Agent Console Dashboard").is_none(),
+            "Medium mode should not show the full header"
+        );
+    }
+
+    #[test]
+    fn test_layout_mode_auto_detects_medium_for_height_12() {
+        let mut app = make_app_with_sessions(3);
+        let _buffer = render_dashboard_to_buffer(&mut app, 80, 12);
+        assert_eq!(app.layout_mode, crate::tui::app::LayoutMode::Medium);
+    }
+
+    #[test]
+    fn test_layout_mode_auto_detects_large_for_height_13() {
+        let mut app = make_app_with_sessions(3);
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 13);
+        // Height 13 (= Medium threshold) should use Large mode
         assert_eq!(app.layout_mode, crate::tui::app::LayoutMode::Large);
-        // Should have header
         assert!(
             find_row_with_text(&buffer, "Agent Console Dashboard").is_some(),
             "Large mode should show header"
         );
     }
 
+    #[test]
+    fn test_medium_layout_has_no_detail_panel() {
+        let mut app = make_app_with_sessions(3);
+        app.selected_index = Some(0);
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 10);
+        assert!(
+            find_row_with_text(&buffer, "Status:").is_none(),
+            "Medium mode should not render the detail panel"
+        );
+        assert!(
+            find_row_with_text(&buffer, "Detail").is_none(),
+            "Medium mode should not render the detail placeholder"
+        );
+    }
+
+    #[test]
+    fn test_medium_layout_shows_sessions_and_footer() {
+        let mut app = make_app_with_sessions(3);
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 8);
+        for i in 0..3 {
+            assert!(
+                find_row_with_text(&buffer, &format!("session-{}", i)).is_some(),
+                "Medium mode should list session {}",
+                i
+            );
+        }
+        assert!(
+            row_contains(&buffer, buffer.area().height - 1, "[q] Quit"),
+            "Medium mode should show footer"
+        );
+    }
+
+    #[test]
+    fn test_medium_layout_condensed_header_counts() {
+        let mut app = make_app_with_sessions(4);
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 10);
+        let header = row_text(&buffer, 0);
+        assert!(header.contains("4 sessions"), "header: {}", header);
+        assert!(header.contains("! 1 attention"), "header: {}", header);
+        assert!(header.contains(VERSION_TEXT), "header: {}", header);
+    }
+
+    #[test]
+    fn test_medium_layout_condensed_header_uses_configured_icon() {
+        let mut app = make_app_with_sessions(4);
+        app.icons = StatusIcons::nerd_font();
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 10);
+        let header = row_text(&buffer, 0);
+        assert!(
+            header.contains("\u{f0f3} 1 attention"),
+            "header: {}",
+            header
+        );
+    }
+
+    #[test]
+    fn test_medium_layout_sets_clickable_list_area() {
+        let mut app = make_app_with_sessions(3);
+        let _buffer = render_dashboard_to_buffer(&mut app, 80, 10);
+        let inner = app
+            .session_list_inner_area
+            .expect("Medium mode should record the list area");
+        // Header row + column header row sit above the first session row
+        assert_eq!(inner.y, 2);
+    }
+
+    #[test]
+    fn test_layout_mode_override_forces_medium() {
+        let mut app = App::new(
+            PathBuf::from("/tmp/test.sock"),
+            Some(crate::tui::app::LayoutMode::Medium),
+        );
+        app.sessions.push(Session::new(
+            "test-session".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/tmp")),
+        ));
+        let _buffer = render_dashboard_to_buffer(&mut app, 80, 40);
+        assert_eq!(app.layout_mode, crate::tui::app::LayoutMode::Medium);
+    }
+
     #[test]
     fn test_layout_mode_auto_detects_two_line_for_height_6() {
         let mut app = make_app_with_sessions(3);
@@ -1110,12 +1601,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 42.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         });
         let buffer = render_dashboard_to_buffer(&mut app, 80, 2);
@@ -1147,6 +1642,22 @@ mod tests {
         assert_eq!(TWO_LINE_LAYOUT_HEIGHT_THRESHOLD, 7);
     }
 
+    #[test]
+    fn test_medium_layout_threshold_is_13() {
+        use crate::tui::app::MEDIUM_LAYOUT_HEIGHT_THRESHOLD;
+        assert_eq!(MEDIUM_LAYOUT_HEIGHT_THRESHOLD, 13);
+    }
+
+    #[test]
+    fn test_layout_mode_for_height_boundaries() {
+        use crate::tui::app::LayoutMode;
+        assert_eq!(LayoutMode::for_height(1), LayoutMode::TwoLine);
+        assert_eq!(LayoutMode::for_height(6), LayoutMode::TwoLine);
+        assert_eq!(LayoutMode::for_height(7), LayoutMode::Medium);
+        assert_eq!(LayoutMode::for_height(12), LayoutMode::Medium);
+        assert_eq!(LayoutMode::for_height(13), LayoutMode::Large);
+    }
+
     #[test]
     fn test_layout_mode_override_forces_two_line() {
         // Create app with TwoLine override
@@ -1239,10 +1750,99 @@ mod tests {
     fn test_render_compact_chips_empty_sessions() {
         use std::time::Instant;
         let sessions = vec![];
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         assert_eq!(line.to_string(), "(no sessions)");
     }
 
+    #[test]
+    fn test_render_compact_chips_use_display_name() {
+        use std::time::Instant;
+        let mut session = Session::new(
+            "renamed-session".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/user/myproject")),
+        );
+        session.display_name = Some("api".to_string());
+        let line = render_compact_session_chips(
+            &[session],
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
+        let text = line.to_string();
+        assert!(text.contains("* api"), "got: {}", text);
+        assert!(!text.contains("myproject"), "got: {}", text);
+    }
+
+    #[test]
+    fn test_two_line_rename_replaces_usage_line() {
+        use crate::tui::app::RenameState;
+        use crate::tui::text_input::TextInput;
+
+        let mut app = make_app_with_sessions(2);
+        app.rename = Some(RenameState {
+            session_id: "session-0".to_string(),
+            input: TextInput::with_value("abc"),
+        });
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 2);
+        assert!(row_contains(&buffer, 1, "Rename: abc"));
+    }
+
+    #[test]
+    fn test_footer_shows_rename_hints_while_renaming() {
+        use crate::tui::app::RenameState;
+        use crate::tui::text_input::TextInput;
+
+        let mut app = make_app_with_sessions(2);
+        app.rename = Some(RenameState {
+            session_id: "session-0".to_string(),
+            input: TextInput::new(),
+        });
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 24);
+        let footer_row = buffer.area().height - 1;
+        assert!(row_contains(&buffer, footer_row, "[Esc] Cancel"));
+        assert!(find_row_with_text(&buffer, "Rename: ").is_some());
+    }
+
+    #[test]
+    fn test_render_compact_chips_uses_configured_icons() {
+        use std::time::Instant;
+        let mut session = Session::new(
+            "icon-session".to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from("/home/user/myproject")),
+        );
+        session.status = Status::Attention;
+        let line = render_compact_session_chips(
+            &[session],
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::emoji(),
+            &Theme::default(),
+        );
+        assert!(line.to_string().contains("🔔 myproject"), "got: {}", line);
+    }
+
+    #[test]
+    fn test_chip_width_counts_wide_symbols() {
+        assert_eq!(chip_width("proj", "*", false), 7);
+        assert_eq!(chip_width("proj", "🔔", false), 8);
+        assert_eq!(chip_width("proj", "🔔", true), 9);
+    }
+
     #[test]
     fn test_render_compact_chips_single_session() {
         use std::time::Instant;
@@ -1254,7 +1854,15 @@ mod tests {
         session.status = Status::Working;
         let sessions = vec![session];
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should contain status symbol and folder name
@@ -1273,7 +1881,15 @@ mod tests {
         session.status = Status::Attention;
         let sessions = vec![session];
 
-        let line = render_compact_session_chips(&sessions, Some(0), 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            Some(0),
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Selected chip should have brackets with folder name
@@ -1297,7 +1913,15 @@ mod tests {
             .collect();
 
         // Scroll to position 5 (5 sessions hidden to the left)
-        let line = render_compact_session_chips(&sessions, None, 5, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            5,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should show left overflow indicator with count
@@ -1318,7 +1942,15 @@ mod tests {
             .collect();
 
         // At position 0, with 80 width fitting ~3 chips, should have 7 hidden on right
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should show right overflow indicator
@@ -1339,7 +1971,15 @@ mod tests {
         session.status = Status::Working;
         let sessions = vec![session];
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should fallback to first 8 chars of session_id
@@ -1364,7 +2004,15 @@ mod tests {
         session.status = Status::Working;
         let sessions = vec![session];
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should truncate folder name from start, keeping end with ellipsis
@@ -1390,7 +2038,15 @@ mod tests {
             .collect();
 
         // Wide terminal (80 chars) should fit all 3 sessions
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should NOT have overflow indicators with counts
@@ -1501,7 +2157,15 @@ mod tests {
         s1.status = Status::Working;
         let sessions = vec![s1];
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Short name "src" should not be padded to 18 chars
@@ -1524,7 +2188,15 @@ mod tests {
         session.status = Status::Working;
         let sessions = vec![session];
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should keep end: "...ject-name" (12 chars max)
@@ -1550,7 +2222,15 @@ mod tests {
             })
             .collect();
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should have " | " separators between chips
@@ -1577,7 +2257,15 @@ mod tests {
             .collect();
 
         // Select middle session
-        let line = render_compact_session_chips(&sessions, Some(1), 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            Some(1),
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should have brackets around focused chip only
@@ -1616,7 +2304,15 @@ mod tests {
         // Select the middle session so it is NOT the last visible chip.
         // This exercises the code path where ']' was previously rendered
         // with DarkGray inside the next chip's separator.
-        let line = render_compact_session_chips(&sessions, Some(1), 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            Some(1),
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
 
         // Collect (text, style) pairs for all spans
         let span_pairs: Vec<(&str, Style)> = line
@@ -1683,7 +2379,15 @@ mod tests {
             .collect();
 
         // Scroll to position 5 (5 hidden left, should have overflow on right too)
-        let line = render_compact_session_chips(&sessions, None, 5, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            5,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should show left overflow with format: "<- N+|" (no space before pipe)
@@ -1714,7 +2418,15 @@ mod tests {
             .collect();
 
         // All sessions fit, no overflow
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Should show zero format: "<- 0 |" and "| 0 ->" (with space)
@@ -1743,7 +2455,15 @@ mod tests {
             })
             .collect();
 
-        let line = render_compact_session_chips(&sessions, None, 0, 80, Instant::now());
+        let line = render_compact_session_chips(
+            &sessions,
+            None,
+            0,
+            80,
+            Instant::now(),
+            &StatusIcons::ascii(),
+            &Theme::default(),
+        );
         let text = line.to_string();
 
         // Overflow indicators should always be present (never hidden)
@@ -1758,4 +2478,75 @@ mod tests {
             text
         );
     }
+
+    #[test]
+    fn test_hovered_row_highlighted_with_tooltip() {
+        let mut app = make_app_with_sessions(3);
+        app.selected_index = Some(0);
+        render_dashboard_to_buffer(&mut app, 100, 30);
+        let inner = app.session_list_inner_area.expect("list rendered");
+        app.hovered_index = Some(1);
+        app.hover_position = Some((10, inner.y + 1));
+
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        let row = inner.y + 1;
+        assert_bg_color(&buffer, inner.x + 1, row, Color::Indexed(237));
+        let id_row = find_row_with_text(&buffer, "id:  session-1").expect("tooltip id line");
+        assert!(id_row > row, "tooltip sits below the hovered row");
+        assert!(row_contains(
+            &buffer,
+            id_row - 1,
+            "dir: /home/user/project-1"
+        ));
+    }
+
+    #[test]
+    fn test_hover_without_tooltips_only_highlights() {
+        let mut app = make_app_with_sessions(3);
+        app.selected_index = Some(0);
+        app.hover_tooltips = false;
+        render_dashboard_to_buffer(&mut app, 100, 30);
+        let inner = app.session_list_inner_area.expect("list rendered");
+        app.hovered_index = Some(2);
+        app.hover_position = Some((10, inner.y + 2));
+
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        assert_bg_color(&buffer, inner.x + 1, inner.y + 2, Color::Indexed(237));
+        assert!(find_row_with_text(&buffer, "id:  session-2").is_none());
+    }
+
+    #[test]
+    fn test_two_line_hover_shows_full_dir_and_id() {
+        let mut app = App::new(PathBuf::from("/tmp/test.sock"), Some(LayoutMode::TwoLine));
+        app.sessions = make_app_with_sessions(2).sessions;
+        app.selected_index = Some(0);
+        app.hovered_index = Some(1);
+        let buffer = render_dashboard_to_buffer(&mut app, 80, 2);
+        assert!(row_contains(&buffer, 1, "/home/user/project-1  session-1"));
+    }
+
+    #[test]
+    fn test_tab_bar_shown_with_multiple_projects() {
+        let mut app = make_app();
+        app.all_sessions = make_app_with_sessions(2).sessions;
+        app.sync_tab_sessions();
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        assert!(row_contains(&buffer, 1, " 0 All ! (2)"));
+        assert!(row_contains(&buffer, 1, " 2 project-1 ! (1)"));
+
+        app.switch_tab(1);
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        assert!(find_row_with_text(&buffer, "project-0").is_some());
+        let rows_with_other_project = (0..30)
+            .filter(|&row| row_contains(&buffer, row, "project-1"))
+            .count();
+        assert_eq!(rows_with_other_project, 1, "only the tab bar names it");
+    }
+
+    #[test]
+    fn test_tab_bar_hidden_without_projects() {
+        let mut app = make_app_with_sessions(2);
+        let buffer = render_dashboard_to_buffer(&mut app, 100, 30);
+        assert!(find_row_with_text(&buffer, " 0 All").is_none());
+    }
 }
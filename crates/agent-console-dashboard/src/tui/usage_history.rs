@@ -0,0 +1,118 @@
+//! In-memory history of API usage samples for the usage chart.
+//!
+//! The daemon broadcasts a fresh `UsageData` after every fetch but does not
+//! keep past values, so the TUI records each update it receives. History
+//! therefore starts when the dashboard connects and is lost on exit.
+
+use chrono::{DateTime, Local};
+use claude_usage::UsageData;
+use std::collections::VecDeque;
+
+/// Maximum samples kept (24 hours at the default 3-minute fetch interval).
+pub const MAX_USAGE_SAMPLES: usize = 480;
+
+/// Utilization snapshot at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsageSample {
+    /// Local time the sample was received.
+    pub at: DateTime<Local>,
+    /// 5-hour window utilization (percent).
+    pub five_hour: f64,
+    /// 7-day window utilization (percent).
+    pub seven_day: f64,
+}
+
+/// Bounded list of usage samples, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct UsageHistory {
+    samples: VecDeque<UsageSample>,
+}
+
+impl UsageHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a sample from `usage`, received now.
+    pub fn record(&mut self, usage: &UsageData) {
+        self.push(UsageSample {
+            at: Local::now(),
+            five_hour: usage.five_hour.utilization,
+            seven_day: usage.seven_day.utilization,
+        });
+    }
+
+    /// Appends a sample, dropping the oldest when full.
+    pub fn push(&mut self, sample: UsageSample) {
+        if self.samples.len() == MAX_USAGE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Returns the sample at `index` (0 = oldest).
+    pub fn get(&self, index: usize) -> Option<&UsageSample> {
+        self.samples.get(index)
+    }
+
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns true if no samples have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Iterates over samples, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &UsageSample> {
+        self.samples.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claude_usage::UsagePeriod;
+
+    fn usage(five_hour: f64, seven_day: f64) -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: five_hour,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: seven_day,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    #[test]
+    fn test_record_keeps_order() {
+        let mut history = UsageHistory::new();
+        history.record(&usage(10.0, 50.0));
+        history.record(&usage(20.0, 51.0));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(0).map(|s| s.five_hour), Some(10.0));
+        assert_eq!(history.get(1).map(|s| s.seven_day), Some(51.0));
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut history = UsageHistory::new();
+        for i in 0..=MAX_USAGE_SAMPLES {
+            history.record(&usage(i as f64, 0.0));
+        }
+        assert_eq!(history.len(), MAX_USAGE_SAMPLES);
+        assert_eq!(history.get(0).map(|s| s.five_hour), Some(1.0));
+    }
+}
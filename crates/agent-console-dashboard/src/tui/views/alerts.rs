@@ -0,0 +1,68 @@
+//! Sticky alerts panel.
+//!
+//! Lists unacknowledged attention and question events (see
+//! [`AlertLog`](crate::tui::alerts::AlertLog)), oldest first, above the
+//! detail panel in the Large layout.
+
+use crate::tui::alerts::{Alert, AlertLog};
+use crate::tui::icons::StatusIcons;
+use crate::tui::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Maximum alert rows shown before the panel stops growing.
+pub const MAX_VISIBLE_ALERTS: usize = 5;
+
+/// Returns the panel height (borders included) for the given alert count.
+///
+/// Zero when there are no alerts, so the panel takes no space.
+pub fn alerts_panel_height(alert_count: usize) -> u16 {
+    if alert_count == 0 {
+        0
+    } else {
+        alert_count.min(MAX_VISIBLE_ALERTS) as u16 + 2
+    }
+}
+
+/// Formats one alert row: `! 14:32:05  my-project  attention`.
+pub fn format_alert_line(alert: &Alert, icons: &StatusIcons, theme: &Theme) -> Line<'static> {
+    let style = theme.status(alert.status);
+    Line::from(vec![
+        Span::styled(format!("{} ", icons.symbol(alert.status)), style),
+        Span::styled(alert.raised_at.format("%H:%M:%S").to_string(), theme.muted),
+        Span::raw(format!("  {}  ", alert.label)),
+        Span::styled(alert.status.to_string(), style),
+    ])
+}
+
+/// Renders the alerts panel and returns its inner area (for click detection).
+pub fn render_alerts_panel(
+    frame: &mut Frame,
+    area: Rect,
+    alerts: &AlertLog,
+    icons: &StatusIcons,
+    theme: &Theme,
+) -> Rect {
+    let block = Block::default()
+        .title(format!(
+            "── Alerts ({}) ── [a] ack  [A] ack all ",
+            alerts.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(theme.warning);
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines: Vec<Line> = alerts
+        .iter()
+        .take(inner.height as usize)
+        .map(|alert| format_alert_line(alert, icons, theme))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+    inner
+}
@@ -3,7 +3,12 @@
 //! Provides session list rendering with responsive column layouts
 //! and status-based color coding.
 
-use crate::{Session, Status, INACTIVE_SESSION_THRESHOLD};
+use crate::config::schema::TimeFormat;
+use crate::tui::app::RenameState;
+use crate::tui::icons::StatusIcons;
+use crate::tui::text_input::TextInput;
+use crate::tui::theme::Theme;
+use crate::{AgentType, Session, Status, INACTIVE_SESSION_THRESHOLD};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -13,7 +18,9 @@ use ratatui::{
 };
 use std::time::Instant;
 
-/// Returns the status symbol for a given session status.
+/// Returns the ASCII status symbol for a given session status.
+///
+/// This is the default icon set; see [`StatusIcons`] for configurable icons.
 pub fn status_symbol(status: Status) -> &'static str {
     match status {
         Status::Working => "*",
@@ -23,21 +30,27 @@ pub fn status_symbol(status: Status) -> &'static str {
     }
 }
 
-/// Returns the display color for a given session status.
-pub fn status_color(status: Status) -> Color {
-    match status {
-        Status::Working => Color::Green,
-        Status::Attention => Color::Yellow,
-        Status::Question => Color::Blue,
-        Status::Closed => Color::Gray,
+/// Returns the tag shown before the directory of a session and its color.
+///
+/// Claude Code sessions are the common case and have no tag, so the list only
+/// calls out sessions of other agents. Custom agents are tagged with their
+/// configured icon, else their name.
+pub fn agent_tag<'a>(
+    agent_type: &'a AgentType,
+    icons: &'a StatusIcons,
+) -> Option<(&'a str, Color)> {
+    match agent_type {
+        AgentType::ClaudeCode => None,
+        AgentType::Codex => Some(("codex", Color::Cyan)),
+        AgentType::GeminiCli => Some(("gemini", Color::LightBlue)),
+        AgentType::Aider => Some(("aider", Color::LightGreen)),
+        AgentType::Custom(name) => {
+            let tag = icons.agent_tags.get(name).unwrap_or(name);
+            Some((tag, Color::LightMagenta))
+        }
     }
 }
 
-/// Returns the color for an error status (used for sessions with errors).
-pub fn error_color() -> Color {
-    Color::Red
-}
-
 /// Formats a duration in seconds as a human-readable string.
 ///
 /// Returns "Xh Ym Zs" for durations >= 1 hour, "Xm Ys" for >= 1 minute, or "Xs" for < 1 minute.
@@ -65,6 +78,31 @@ pub fn format_elapsed_seconds(total_seconds: u64) -> String {
     format_duration_secs(total_seconds)
 }
 
+/// Formats the time since `since` in the given display mode.
+///
+/// Relative mode returns a duration (see [`format_duration_secs`]); absolute
+/// mode returns the local wall-clock time (see [`format_clock_since`]).
+pub fn format_since(since: Instant, now: Instant, format: TimeFormat) -> String {
+    let elapsed_secs = now.saturating_duration_since(since).as_secs();
+    match format {
+        TimeFormat::Relative => format_duration_secs(elapsed_secs),
+        TimeFormat::Absolute => format_clock_since(elapsed_secs, chrono::Local::now()),
+    }
+}
+
+/// Formats the wall-clock time `elapsed_secs` before `now`.
+///
+/// Returns "since HH:MM" for times earlier today and "since Mon DD" for
+/// earlier days, so the text fits the 16-column elapsed-time cell.
+pub fn format_clock_since(elapsed_secs: u64, now: chrono::DateTime<chrono::Local>) -> String {
+    let start = now - chrono::Duration::seconds(elapsed_secs.min(i64::MAX as u64) as i64);
+    if start.date_naive() == now.date_naive() {
+        format!("since {}", start.format("%H:%M"))
+    } else {
+        format!("since {}", start.format("%b %d"))
+    }
+}
+
 /// Responsive layout breakpoint threshold.
 const NARROW_THRESHOLD: u16 = 40;
 
@@ -83,6 +121,33 @@ pub fn get_directory_display_name(session: &Session) -> String {
     }
 }
 
+/// Returns the label for a session: its display name if renamed, otherwise
+/// the working directory basename (see [`get_directory_display_name`]).
+pub fn session_label(session: &Session) -> String {
+    session
+        .display_name
+        .clone()
+        .unwrap_or_else(|| get_directory_display_name(session))
+}
+
+/// Collapses an assistant message to one line and truncates it to `max_chars`.
+///
+/// Runs of whitespace (including newlines) become single spaces. Truncated
+/// text ends with `…`.
+pub fn message_snippet(message: &str, max_chars: usize) -> String {
+    let flat = message.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max_chars {
+        flat
+    } else {
+        let mut cut: String = flat.chars().take(max_chars.saturating_sub(1)).collect();
+        cut.push('…');
+        cut
+    }
+}
+
+/// Minimum free columns in the directory cell before a message snippet is shown.
+const MIN_SNIPPET_WIDTH: usize = 12;
+
 /// Computes display names for session directories with basename disambiguation.
 ///
 /// Returns a map from session_id to display name. If multiple sessions share
@@ -186,52 +251,68 @@ pub(crate) fn compute_directory_display_names(
 /// Formats a single session line based on available terminal width.
 ///
 /// Responsive breakpoints:
-/// - `<40` cols: symbol + session ID only
-/// - `>=40` cols: symbol + directory (flex) + status (14) + priority (12) + elapsed (16) + session ID (40)
+/// - `<40` cols: icon + session ID only
+/// - `>=40` cols: directory (flex) + icon and status (14) + priority (12) + elapsed (16) + session ID (40)
+///
+/// Icons come from `icons`, so the configured icon set is used consistently.
+/// The elapsed column follows `time_format` (relative duration or clock time).
+/// When the directory column has room to spare, it ends with a dimmed
+/// one-line snippet of the session's last assistant message.
 ///
-/// If `is_highlighted` is true and the session is inactive or closed, uses black text for readability
-/// against the dark gray highlight background.
+/// If `is_highlighted` is true and the session is inactive or closed, uses the
+/// selection's text color (black by default) for readability against the
+/// selection background.
+#[allow(clippy::too_many_arguments)]
 pub fn format_session_line<'a>(
     session: &Session,
     width: u16,
     dir_display: &str,
     is_highlighted: bool,
+    icons: &StatusIcons,
+    theme: &Theme,
+    time_format: TimeFormat,
 ) -> Line<'a> {
     let inactive = session.is_inactive(INACTIVE_SESSION_THRESHOLD);
     let should_dim = inactive || session.status.should_dim();
-    let (color, symbol, dim, status_text) = if should_dim {
-        // Use black text when highlighted for readability against dark gray background
-        let text_color = if is_highlighted {
-            Color::Black
-        } else {
-            Color::DarkGray
+    let (status_style, symbol, dim, status_text) = if should_dim {
+        // Dimmed text would be unreadable against a selection background
+        let text_style = match theme.selection.bg {
+            Some(_) if is_highlighted => {
+                Style::default().fg(theme.selection.fg.unwrap_or(Color::Black))
+            }
+            _ => theme.inactive,
         };
         let display_status = if inactive {
             "inactive".to_string()
         } else {
             session.status.to_string()
         };
+        let symbol = if inactive {
+            icons.inactive.as_str()
+        } else {
+            icons.symbol(session.status)
+        };
         (
-            Color::DarkGray,
-            ".",
-            Style::default().fg(text_color).add_modifier(Modifier::DIM),
+            theme.inactive,
+            symbol,
+            text_style.add_modifier(Modifier::DIM),
             display_status,
         )
     } else {
         (
-            status_color(session.status),
-            status_symbol(session.status),
+            theme.status(session.status),
+            icons.symbol(session.status),
             Style::default(),
             session.status.to_string(),
         )
     };
-    let elapsed = format_elapsed(session.since);
+    let elapsed = format_since(session.since, Instant::now(), time_format);
     let name = session.session_id.clone();
 
     if width < NARROW_THRESHOLD {
         // Narrow: symbol + session ID only (no column alignment)
         Line::from(vec![
-            Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+            Span::styled(format!("{} ", symbol), status_style),
             Span::styled(name, dim),
         ])
     } else {
@@ -241,44 +322,95 @@ pub fn format_session_line<'a>(
         let fixed_width = 2 + 14 + 12 + 16 + 40;
         let dir_width = (width as usize).saturating_sub(fixed_width).max(1);
 
+        // Sessions of agents other than Claude Code start with a `[tag] `,
+        // followed by the `#tag `s of the session's project that fit in half
+        // the directory column
+        let tag_style = |style: Style| if should_dim { dim } else { style };
+        let mut tags: Vec<Span> = agent_tag(&session.agent_type, icons)
+            .map(|(tag, tag_color)| {
+                Span::styled(
+                    format!("[{}] ", tag),
+                    tag_style(Style::default().fg(tag_color)),
+                )
+            })
+            .into_iter()
+            .collect();
+        let mut tag_width: usize = tags.iter().map(Span::width).sum();
+        for tag in &session.tags {
+            let span = Span::styled(format!("#{} ", tag), tag_style(theme.warning));
+            if tag_width + span.width() > dir_width / 2 {
+                break;
+            }
+            tag_width += span.width();
+            tags.push(span);
+        }
+        let dir_width = dir_width.saturating_sub(tag_width).max(1);
+
         let work_dir_text = truncate_string(dir_display, dir_width);
         let is_error = dir_display == "<error>";
 
-        let work_dir_span = if is_error {
-            Span::styled(
+        let dir_style = if is_error { theme.error } else { dim };
+        // Spare directory-cell columns show a snippet of the last assistant message
+        let label_width = Span::raw(work_dir_text.as_str()).width();
+        let snippet_width = dir_width.saturating_sub(label_width + 2);
+        let dir_spans = match session.last_message.as_deref() {
+            Some(message) if snippet_width >= MIN_SNIPPET_WIDTH => {
+                let snippet = message_snippet(message, snippet_width);
+                vec![
+                    Span::styled(work_dir_text, dir_style),
+                    Span::styled(
+                        format!("  {:<snippet_width$}", snippet),
+                        theme.muted.add_modifier(Modifier::ITALIC),
+                    ),
+                ]
+            }
+            _ => vec![Span::styled(
                 format!("{:<dir_width$}", work_dir_text),
-                Style::default().fg(error_color()),
-            )
-        } else {
-            Span::styled(format!("{:<dir_width$}", work_dir_text), dim)
+                dir_style,
+            )],
         };
 
-        Line::from(vec![
-            work_dir_span,
+        // Pad by display width so wide glyphs (emoji) keep columns aligned.
+        let status_cell = format!("{} {}", symbol, status_text);
+        let status_pad = 14usize.saturating_sub(Span::raw(status_cell.as_str()).width());
+
+        let mut spans = tags;
+        spans.extend(dir_spans);
+        spans.extend([
             Span::styled(
-                format!("{:<14}", status_text),
-                if should_dim {
-                    dim
-                } else {
-                    Style::default().fg(color)
-                },
+                format!("{}{}", status_cell, " ".repeat(status_pad)),
+                if should_dim { dim } else { status_style },
             ),
             Span::styled(format!("{:<12}", session.priority), dim),
             Span::styled(format!("{:<16}", elapsed), dim),
             Span::styled(format!("{:<40}", name), dim),
-        ])
+        ]);
+        Line::from(spans)
     }
 }
 
+/// Formats the inline rename row: a `Rename:` prompt followed by the text input.
+///
+/// The prompt is dropped below `NARROW_THRESHOLD` to leave room for the text.
+pub fn format_rename_line(input: &TextInput, width: u16, theme: &Theme) -> Line<'static> {
+    let mut spans = Vec::new();
+    if width >= NARROW_THRESHOLD {
+        spans.push(Span::styled(
+            "Rename: ",
+            theme.accent.add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.extend(input.spans(theme.text));
+    Line::from(spans)
+}
+
 /// Formats a header line matching the column widths from format_session_line.
 ///
 /// Returns a header row with column titles aligned to their respective columns.
 /// Narrow mode has no headers. Standard and wide modes share the same column
 /// structure (directory, status, priority, time elapsed, session ID).
-pub fn format_header_line(width: u16) -> Line<'static> {
-    let header_style = Style::default()
-        .fg(Color::Cyan)
-        .add_modifier(Modifier::BOLD);
+pub fn format_header_line(width: u16, theme: &Theme) -> Line<'static> {
+    let header_style = theme.accent.add_modifier(Modifier::BOLD);
 
     if width < NARROW_THRESHOLD {
         // Narrow: no headers
@@ -340,12 +472,81 @@ pub(crate) fn debug_ruler_enabled() -> bool {
 ///
 /// Returns the inner Rect of the List widget (excluding block borders),
 /// used for accurate mouse click detection.
+#[allow(clippy::too_many_arguments)]
 pub fn render_session_list(
     frame: &mut Frame,
     area: Rect,
     sessions: &[Session],
     selected_index: Option<usize>,
     width: u16,
+    icons: &StatusIcons,
+    theme: &Theme,
+    time_format: TimeFormat,
+    rename: Option<&RenameState>,
+) -> Rect {
+    let block = Block::default()
+        .borders(Borders::TOP | Borders::BOTTOM)
+        .title(" Sessions ");
+    render_session_list_in_block(
+        frame,
+        area,
+        sessions,
+        selected_index,
+        width,
+        icons,
+        theme,
+        time_format,
+        rename,
+        block,
+    )
+}
+
+/// Renders the session list without the bordered " Sessions " block.
+///
+/// Used by the Medium layout, where vertical space is too scarce to spend two
+/// rows on borders. Returns the inner Rect for mouse click detection.
+#[allow(clippy::too_many_arguments)]
+pub fn render_session_list_condensed(
+    frame: &mut Frame,
+    area: Rect,
+    sessions: &[Session],
+    selected_index: Option<usize>,
+    width: u16,
+    icons: &StatusIcons,
+    theme: &Theme,
+    time_format: TimeFormat,
+    rename: Option<&RenameState>,
+) -> Rect {
+    render_session_list_in_block(
+        frame,
+        area,
+        sessions,
+        selected_index,
+        width,
+        icons,
+        theme,
+        time_format,
+        rename,
+        Block::default(),
+    )
+}
+
+/// Shared session list rendering, wrapping the list in the given block.
+///
+/// When `rename` is set, the row of the session being renamed shows the
+/// inline text input instead of its columns.
+#[allow(clippy::too_many_arguments)]
+fn render_session_list_in_block(
+    frame: &mut Frame,
+    area: Rect,
+    sessions: &[Session],
+    selected_index: Option<usize>,
+    width: u16,
+    icons: &StatusIcons,
+    theme: &Theme,
+    time_format: TimeFormat,
+    rename: Option<&RenameState>,
+    block: Block<'_>,
 ) -> Rect {
     // Split area into header (1 line) + optional ruler (1 line) + list (remaining) if not narrow mode
     let show_ruler = debug_ruler_enabled();
@@ -377,7 +578,7 @@ pub fn render_session_list(
 
     // Render header if not narrow mode
     if let Some(header_rect) = header_area {
-        let header_line = format_header_line(width);
+        let header_line = format_header_line(width, theme);
         let header = Paragraph::new(header_line);
         frame.render_widget(header, header_rect);
     }
@@ -397,9 +598,18 @@ pub fn render_session_list(
         .iter()
         .enumerate()
         .map(|(index, session)| {
-            let dir_display = dir_display_names
-                .get(&session.session_id)
-                .map(|s| s.as_str())
+            if let Some(rename) = rename.filter(|r| r.session_id == session.session_id) {
+                return ListItem::new(format_rename_line(&rename.input, width, theme));
+            }
+            // A user-assigned display name takes the place of the directory label
+            let dir_display = session
+                .display_name
+                .as_deref()
+                .or_else(|| {
+                    dir_display_names
+                        .get(&session.session_id)
+                        .map(|s| s.as_str())
+                })
                 .unwrap_or("<error>");
             let is_highlighted = selected_index == Some(index);
             ListItem::new(format_session_line(
@@ -407,20 +617,19 @@ pub fn render_session_list(
                 width,
                 dir_display,
                 is_highlighted,
+                icons,
+                theme,
+                time_format,
             ))
         })
         .collect();
 
-    let block = Block::default()
-        .borders(Borders::TOP | Borders::BOTTOM)
-        .title(" Sessions ");
-
     // Calculate inner area (excluding block borders) for mouse click detection
     let inner_area = block.inner(list_area);
 
     let list = List::new(items)
         .block(block)
-        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_style(theme.selection)
         .highlight_symbol("▶ ")
         .highlight_spacing(HighlightSpacing::Always);
 
@@ -466,3 +466,56 @@ fn test_closed_session_highlighted_all_columns_black() {
     assert_text_fg_in_row(&buffer, row, "0", Color::Black); // priority
     assert_text_fg_in_row(&buffer, row, "closed-hl", Color::Black); // session_id
 }
+
+#[test]
+fn test_display_name_replaces_directory_label() {
+    let mut session = make_test_session_with_dir(
+        "renamed-sess",
+        Status::Working,
+        Some(PathBuf::from("/home/user/project")),
+    );
+    session.display_name = Some("api refactor".to_string());
+    let buffer = render_session_list_to_buffer(&[session], None, 120, 10);
+    let row = find_row_with_text(&buffer, "renamed-sess").expect("should find session");
+    let row_string = row_text(&buffer, row);
+    assert!(row_string.contains("api refactor"), "got: {}", row_string);
+    assert!(!row_string.contains("project"), "got: {}", row_string);
+}
+
+#[test]
+fn test_rename_row_shows_text_input() {
+    use crate::tui::app::RenameState;
+    use crate::tui::text_input::TextInput;
+
+    let sessions = vec![
+        make_test_session_with_dir("keep-sess", Status::Working, None),
+        make_test_session_with_dir("edit-sess", Status::Working, None),
+    ];
+    let rename = RenameState {
+        session_id: "edit-sess".to_string(),
+        input: TextInput::with_value("new-name"),
+    };
+    let backend = ratatui::backend::TestBackend::new(100, 10);
+    let mut terminal = ratatui::Terminal::new(backend).expect("failed to create test terminal");
+    terminal
+        .draw(|frame| {
+            let area = frame.area();
+            render_session_list(
+                frame,
+                area,
+                &sessions,
+                Some(1),
+                100,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                Some(&rename),
+            );
+        })
+        .expect("draw failed");
+    let buffer = terminal.backend().buffer().clone();
+
+    let row = find_row_with_text(&buffer, "Rename: new-name").expect("should show input");
+    assert!(!row_contains(&buffer, row, "edit-sess"));
+    assert!(find_row_with_text(&buffer, "keep-sess").is_some());
+}
@@ -1,4 +1,5 @@
 pub(crate) use super::*;
+pub(crate) use crate::tui::icons::StatusIcons;
 pub(crate) use crate::{AgentType, Session};
 pub(crate) use std::path::PathBuf;
 
@@ -10,7 +10,15 @@ fn test_format_session_line_unknown_working_dir_shows_error_standard() {
         Some(PathBuf::from("unknown")),
     );
     session.status = Status::Working;
-    let line = format_session_line(&session, 100, "<error>", false);
+    let line = format_session_line(
+        &session,
+        100,
+        "<error>",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Should have 5 spans: work_dir (error), status, priority, elapsed, session ID
     assert_eq!(line.spans.len(), 5);
@@ -24,7 +32,7 @@ fn test_format_session_line_unknown_working_dir_shows_error_standard() {
     );
     assert_eq!(
         work_dir_span.style.fg,
-        Some(error_color()),
+        Theme::default().error.fg,
         "Expected error color (red) for <error> span"
     );
 }
@@ -37,7 +45,15 @@ fn test_format_session_line_unknown_working_dir_shows_error_wide() {
         Some(PathBuf::from("unknown")),
     );
     session.status = Status::Attention;
-    let line = format_session_line(&session, 100, "<error>", false);
+    let line = format_session_line(
+        &session,
+        100,
+        "<error>",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Should have 5 spans: work_dir (error), status, priority, elapsed, session ID
     assert_eq!(line.spans.len(), 5);
@@ -51,7 +67,7 @@ fn test_format_session_line_unknown_working_dir_shows_error_wide() {
     );
     assert_eq!(
         work_dir_span.style.fg,
-        Some(error_color()),
+        Theme::default().error.fg,
         "Expected error color (red) for <error> span"
     );
 }
@@ -63,7 +79,15 @@ fn test_format_session_line_normal_path_unchanged() {
         AgentType::ClaudeCode,
         Some(PathBuf::from("/home/user/project")),
     );
-    let line = format_session_line(&session, 100, "project", false);
+    let line = format_session_line(
+        &session,
+        100,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Should have 5 spans
     assert_eq!(line.spans.len(), 5);
@@ -83,7 +107,7 @@ fn test_format_session_line_normal_path_unchanged() {
     // Should not be red
     assert_ne!(
         work_dir_span.style.fg,
-        Some(error_color()),
+        Theme::default().error.fg,
         "Normal path should not use error color"
     );
 }
@@ -97,7 +121,15 @@ fn test_column_alignment_standard_width() {
         AgentType::ClaudeCode,
         Some(PathBuf::from("/home/user/project")),
     );
-    let line = format_session_line(&session, 100, "project", false);
+    let line = format_session_line(
+        &session,
+        100,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Should have 5 spans: workdir, status, priority, elapsed, session ID
     assert_eq!(line.spans.len(), 5);
@@ -161,7 +193,15 @@ fn test_column_alignment_wide_width() {
         AgentType::ClaudeCode,
         Some(PathBuf::from("/home/user/project")),
     );
-    let line = format_session_line(&session, 120, "project", false);
+    let line = format_session_line(
+        &session,
+        120,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Should have 5 spans: workdir, status, priority, elapsed, session ID
     assert_eq!(line.spans.len(), 5);
@@ -192,11 +232,27 @@ fn test_directory_column_expands_with_terminal_width() {
     );
 
     // Test at standard width (100 cols)
-    let line_100 = format_session_line(&session, 100, "tmp", false);
+    let line_100 = format_session_line(
+        &session,
+        100,
+        "tmp",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
     let dir_span_100 = &line_100.spans[0];
 
     // Test at wider width (150 cols)
-    let line_150 = format_session_line(&session, 150, "tmp", false);
+    let line_150 = format_session_line(
+        &session,
+        150,
+        "tmp",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
     let dir_span_150 = &line_150.spans[0];
 
     // Directory column at 150 should be wider than at 100
@@ -212,14 +268,14 @@ fn test_directory_column_expands_with_terminal_width() {
 
 #[test]
 fn test_header_narrow_mode_no_header() {
-    let line = format_header_line(30);
+    let line = format_header_line(30, &Theme::default());
     // Narrow mode should have no header
     assert_eq!(line.spans.len(), 0, "Narrow mode should have no header");
 }
 
 #[test]
 fn test_header_standard_mode() {
-    let line = format_header_line(60);
+    let line = format_header_line(60, &Theme::default());
     // Standard mode: symbol space + Directory + Status + Priority + Elapsed + Session ID = 6 spans
     assert_eq!(
         line.spans.len(),
@@ -271,7 +327,7 @@ fn test_header_standard_mode() {
 
 #[test]
 fn test_header_wide_mode_same_columns_wider_directory() {
-    let line = format_header_line(100);
+    let line = format_header_line(100, &Theme::default());
     // Wide mode: same 6 spans as standard (symbol space + Directory + Status + Priority + Elapsed + Session ID)
     assert_eq!(line.spans.len(), 6, "Wide mode should have 6 header spans");
 
@@ -304,7 +360,7 @@ fn test_header_wide_mode_same_columns_wider_directory() {
     );
 
     // Wide directory header should be wider than standard
-    let standard_line = format_header_line(60);
+    let standard_line = format_header_line(60, &Theme::default());
     let standard_dir = &standard_line.spans[1]; // Directory span
     let wide_dir = &line.spans[1]; // Directory span
     assert!(
@@ -317,7 +373,7 @@ fn test_header_wide_mode_same_columns_wider_directory() {
 
 #[test]
 fn test_header_labels_are_left_aligned() {
-    let line = format_header_line(60);
+    let line = format_header_line(60, &Theme::default());
 
     // Directory (index 1): check left-aligned (starts with "D", not space)
     let dir_span = &line.spans[1];
@@ -363,13 +419,21 @@ fn test_header_labels_are_left_aligned() {
 #[test]
 fn test_header_alignment_matches_data() {
     // Verify that header columns align with data columns at standard width
-    let header = format_header_line(100);
+    let header = format_header_line(100, &Theme::default());
     let session = Session::new(
         "align-check".to_string(),
         AgentType::ClaudeCode,
         Some(PathBuf::from("/tmp/test")),
     );
-    let data_line = format_session_line(&session, 100, "test", false);
+    let data_line = format_session_line(
+        &session,
+        100,
+        "test",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Header has 6 spans (padding + 5 columns), data has 5 spans (columns only).
     // The header "  " padding aligns with ratatui's highlight symbol space.
@@ -22,31 +22,43 @@ fn test_status_symbol_closed() {
     assert_eq!(status_symbol(Status::Closed), "x");
 }
 
-// --- status_color tests ---
+// --- theme tests ---
 
 #[test]
-fn test_status_color_working() {
-    assert_eq!(status_color(Status::Working), Color::Green);
+fn test_status_style_working() {
+    assert_eq!(
+        Theme::default().status(Status::Working).fg,
+        Some(Color::Green)
+    );
 }
 
 #[test]
-fn test_status_color_attention() {
-    assert_eq!(status_color(Status::Attention), Color::Yellow);
+fn test_status_style_attention() {
+    assert_eq!(
+        Theme::default().status(Status::Attention).fg,
+        Some(Color::Yellow)
+    );
 }
 
 #[test]
-fn test_status_color_question() {
-    assert_eq!(status_color(Status::Question), Color::Blue);
+fn test_status_style_question() {
+    assert_eq!(
+        Theme::default().status(Status::Question).fg,
+        Some(Color::Blue)
+    );
 }
 
 #[test]
-fn test_status_color_closed() {
-    assert_eq!(status_color(Status::Closed), Color::Gray);
+fn test_status_style_closed() {
+    assert_eq!(
+        Theme::default().status(Status::Closed).fg,
+        Some(Color::Gray)
+    );
 }
 
 #[test]
-fn test_error_color() {
-    assert_eq!(error_color(), Color::Red);
+fn test_error_style() {
+    assert_eq!(Theme::default().error.fg, Some(Color::Red));
 }
 
 // --- format_elapsed_seconds tests ---
@@ -76,6 +88,51 @@ fn test_format_elapsed_seconds_exact_hour() {
     assert_eq!(format_elapsed_seconds(3600), "1h 0m 0s");
 }
 
+// --- time format tests ---
+
+#[test]
+fn test_format_since_relative_matches_duration() {
+    let now = Instant::now();
+    let since = now - std::time::Duration::from_secs(125);
+    assert_eq!(format_since(since, now, TimeFormat::Relative), "2m 5s");
+}
+
+#[test]
+fn test_format_clock_since_today_shows_time() {
+    use chrono::TimeZone;
+    let now = chrono::Local
+        .with_ymd_and_hms(2026, 3, 4, 16, 45, 0)
+        .single()
+        .expect("unambiguous local time");
+    assert_eq!(format_clock_since(2 * 3600 + 13 * 60, now), "since 14:32");
+}
+
+#[test]
+fn test_format_clock_since_earlier_day_shows_date() {
+    use chrono::TimeZone;
+    let now = chrono::Local
+        .with_ymd_and_hms(2026, 3, 4, 1, 0, 0)
+        .single()
+        .expect("unambiguous local time");
+    assert_eq!(format_clock_since(3 * 3600, now), "since Mar 03");
+}
+
+#[test]
+fn test_format_session_line_absolute_time() {
+    let session = make_session("abs", Status::Working);
+    let line = format_session_line(
+        &session,
+        100,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Absolute,
+    );
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    assert!(text.contains("since "), "got: '{}'", text);
+}
+
 #[test]
 fn test_format_elapsed_seconds_exact_minute() {
     assert_eq!(format_elapsed_seconds(60), "1m 0s");
@@ -113,7 +170,15 @@ fn test_truncate_string_empty() {
 #[test]
 fn test_format_session_line_narrow() {
     let session = make_session("my-session", Status::Working);
-    let line = format_session_line(&session, 30, "project", false);
+    let line = format_session_line(
+        &session,
+        30,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
     // Should have exactly 2 spans (symbol + session ID)
     assert_eq!(line.spans.len(), 2);
 }
@@ -121,15 +186,98 @@ fn test_format_session_line_narrow() {
 #[test]
 fn test_format_session_line_standard() {
     let session = make_session("my-session", Status::Attention);
-    let line = format_session_line(&session, 60, "project", false);
+    let line = format_session_line(
+        &session,
+        60,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
     // Should have 5 spans (workdir, status, priority, elapsed, session ID) — highlight handled by List widget
     assert_eq!(line.spans.len(), 5);
 }
 
+#[test]
+fn test_format_session_line_tags_other_agents() {
+    let mut session = make_session("thread-1", Status::Attention);
+    session.agent_type = AgentType::Codex;
+    let line = format_session_line(
+        &session,
+        100,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    assert_eq!(line.spans.len(), 6);
+    assert_eq!(line.spans[0].content, "[codex] ");
+    assert!(line.spans[1].content.starts_with("project"));
+    // The tag takes its width from the directory column
+    assert_eq!(line.width(), 100 - 2);
+    let mut icons = StatusIcons::ascii();
+    assert_eq!(agent_tag(&AgentType::ClaudeCode, &icons), None);
+    let devbot = AgentType::Custom("devbot".to_string());
+    assert_eq!(
+        agent_tag(&devbot, &icons),
+        Some(("devbot", Color::LightMagenta))
+    );
+    icons
+        .agent_tags
+        .insert("devbot".to_string(), "bot".to_string());
+    assert_eq!(
+        agent_tag(&devbot, &icons),
+        Some(("bot", Color::LightMagenta))
+    );
+}
+
+#[test]
+fn test_format_session_line_shows_project_tags() {
+    let mut session = make_session("thread-1", Status::Working);
+    session.agent_type = AgentType::Codex;
+    session.tags = vec!["backend".to_string(), "prod".to_string()];
+    let line = format_session_line(
+        &session,
+        140,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    let text: Vec<&str> = line.spans.iter().map(|s| s.content.trim_end()).collect();
+    assert_eq!(text[..4], ["[codex]", "#backend", "#prod", "project"]);
+    assert_eq!(line.spans[1].style.fg, Some(Color::Yellow));
+    assert_eq!(line.width(), 140 - 2);
+
+    // Tags that do not fit in half the directory column are left out
+    let line = format_session_line(
+        &session,
+        100,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    assert!(line.spans[1].content.starts_with("project"));
+    assert_eq!(line.width(), 100 - 2);
+}
+
 #[test]
 fn test_format_session_line_wide() {
     let session = make_session("my-session", Status::Question);
-    let line = format_session_line(&session, 100, "project", false);
+    let line = format_session_line(
+        &session,
+        100,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
     // Wide has same 5 spans as standard (workdir, status, priority, elapsed, session ID)
     assert_eq!(line.spans.len(), 5);
 }
@@ -142,8 +290,24 @@ fn test_format_session_line_wide_uses_wider_directory() {
         Some(PathBuf::from("/home/user/a-long-project-name")),
     );
     let long_name = "a-long-project-name";
-    let standard_line = format_session_line(&session, 60, long_name, false);
-    let wide_line = format_session_line(&session, 100, long_name, false);
+    let standard_line = format_session_line(
+        &session,
+        60,
+        long_name,
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    let wide_line = format_session_line(
+        &session,
+        100,
+        long_name,
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // work_dir span is index 0 in both modes (highlight handled by List widget)
     let standard_dir = &standard_line.spans[0];
@@ -162,7 +326,15 @@ fn test_format_session_line_wide_uses_wider_directory() {
 fn test_format_session_line_shows_full_session_id() {
     let long_id = "very-long-session-identifier-name";
     let session = make_session(long_id, Status::Working);
-    let line = format_session_line(&session, 80, "project", false);
+    let line = format_session_line(
+        &session,
+        80,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
 
     // Session ID span is index 4 (directory, status, priority, elapsed, session_id)
     let name_span = &line.spans[4];
@@ -173,6 +345,55 @@ fn test_format_session_line_shows_full_session_id() {
     );
 }
 
+#[test]
+fn test_format_session_line_shows_icon_in_status_column() {
+    let session = make_session("my-session", Status::Attention);
+    let line = format_session_line(
+        &session,
+        80,
+        "project",
+        false,
+        &StatusIcons::ascii(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    assert!(
+        line.spans[1].content.starts_with("! attention"),
+        "got: '{}'",
+        line.spans[1].content
+    );
+}
+
+#[test]
+fn test_format_session_line_pads_wide_icon_by_display_width() {
+    let session = make_session("my-session", Status::Attention);
+    let line = format_session_line(
+        &session,
+        80,
+        "project",
+        false,
+        &StatusIcons::emoji(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    assert_eq!(line.spans[1].width(), 14);
+}
+
+#[test]
+fn test_format_session_line_narrow_uses_configured_icon() {
+    let session = make_session("my-session", Status::Question);
+    let line = format_session_line(
+        &session,
+        30,
+        "project",
+        false,
+        &StatusIcons::emoji(),
+        &Theme::default(),
+        TimeFormat::Relative,
+    );
+    assert_eq!(line.spans[0].content, "❓ ");
+}
+
 #[test]
 fn test_format_session_line_all_statuses() {
     for status in [
@@ -183,9 +404,33 @@ fn test_format_session_line_all_statuses() {
     ] {
         let session = make_session("test", status);
         // Should not panic at any width
-        let _ = format_session_line(&session, 20, "project", false);
-        let _ = format_session_line(&session, 50, "project", false);
-        let _ = format_session_line(&session, 120, "project", false);
+        let _ = format_session_line(
+            &session,
+            20,
+            "project",
+            false,
+            &StatusIcons::ascii(),
+            &Theme::default(),
+            TimeFormat::Relative,
+        );
+        let _ = format_session_line(
+            &session,
+            50,
+            "project",
+            false,
+            &StatusIcons::ascii(),
+            &Theme::default(),
+            TimeFormat::Relative,
+        );
+        let _ = format_session_line(
+            &session,
+            120,
+            "project",
+            false,
+            &StatusIcons::ascii(),
+            &Theme::default(),
+            TimeFormat::Relative,
+        );
     }
 }
 
@@ -198,7 +443,17 @@ fn test_render_session_list_empty_no_panic() {
     terminal
         .draw(|frame| {
             let area = frame.area();
-            render_session_list(frame, area, &[], None, 80);
+            render_session_list(
+                frame,
+                area,
+                &[],
+                None,
+                80,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                None,
+            );
         })
         .expect("draw should not fail");
 }
@@ -211,7 +466,17 @@ fn test_render_session_list_single_session_no_panic() {
     terminal
         .draw(|frame| {
             let area = frame.area();
-            render_session_list(frame, area, &sessions, Some(0), 80);
+            render_session_list(
+                frame,
+                area,
+                &sessions,
+                Some(0),
+                80,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                None,
+            );
         })
         .expect("draw should not fail");
 }
@@ -226,7 +491,17 @@ fn test_render_session_list_many_sessions_no_panic() {
     terminal
         .draw(|frame| {
             let area = frame.area();
-            render_session_list(frame, area, &sessions, Some(25), 80);
+            render_session_list(
+                frame,
+                area,
+                &sessions,
+                Some(25),
+                80,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                None,
+            );
         })
         .expect("draw should not fail");
 }
@@ -239,7 +514,17 @@ fn test_render_session_list_narrow_terminal_no_panic() {
     terminal
         .draw(|frame| {
             let area = frame.area();
-            render_session_list(frame, area, &sessions, Some(0), 20);
+            render_session_list(
+                frame,
+                area,
+                &sessions,
+                Some(0),
+                20,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                None,
+            );
         })
         .expect("draw should not fail");
 }
@@ -255,7 +540,17 @@ fn test_render_session_list_wide_terminal_no_panic() {
     terminal
         .draw(|frame| {
             let area = frame.area();
-            render_session_list(frame, area, &sessions, None, 200);
+            render_session_list(
+                frame,
+                area,
+                &sessions,
+                None,
+                200,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                None,
+            );
         })
         .expect("draw should not fail");
 }
@@ -269,7 +564,69 @@ fn test_render_session_list_selected_out_of_bounds_no_panic() {
         .draw(|frame| {
             let area = frame.area();
             // selected_index beyond session count
-            render_session_list(frame, area, &sessions, Some(99), 80);
+            render_session_list(
+                frame,
+                area,
+                &sessions,
+                Some(99),
+                80,
+                &StatusIcons::ascii(),
+                &Theme::default(),
+                TimeFormat::Relative,
+                None,
+            );
         })
         .expect("draw should not fail");
 }
+
+// --- message snippet tests ---
+
+#[test]
+fn test_message_snippet_collapses_whitespace() {
+    assert_eq!(
+        message_snippet("Done.\n\n  Tests pass.", 40),
+        "Done. Tests pass."
+    );
+}
+
+#[test]
+fn test_message_snippet_truncates_with_ellipsis() {
+    assert_eq!(message_snippet("Should I continue?", 10), "Should I …");
+    assert_eq!(message_snippet("héllo wörld", 6), "héllo…");
+}
+
+#[test]
+fn test_session_line_shows_snippet_when_wide() {
+    let mut session = make_session("snippet", Status::Question);
+    session.last_message = Some("Which database should I use?".to_string());
+    let icons = StatusIcons::default();
+    let text = |session: &Session, width: u16| -> String {
+        format_session_line(
+            session,
+            width,
+            "project",
+            false,
+            &icons,
+            &Theme::default(),
+            TimeFormat::Relative,
+        )
+        .spans
+        .iter()
+        .map(|s| s.content.as_ref())
+        .collect()
+    };
+
+    let wide = text(&session, 160);
+    assert!(wide.starts_with("project  Which database should I use?"));
+    assert!(
+        !text(&session, 100).contains("Which"),
+        "no room for a snippet"
+    );
+
+    session.last_message = None;
+    assert_eq!(
+        Span::raw(wide.as_str()).width(),
+        Span::raw(text(&session, 160).as_str()).width(),
+        "snippet stays inside the directory column"
+    );
+}
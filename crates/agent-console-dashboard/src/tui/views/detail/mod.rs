@@ -1,9 +1,13 @@
 //! Session detail modal overlay view.
 //!
 //! Renders a centered modal showing comprehensive information about a single
-//! session: status, working directory, session ID, API usage, and state
-//! transition history. Supports scrolling through history entries.
+//! session: status, working directory, session ID, git branch and pull
+//! request, API usage, and state transition history. Supports scrolling
+//! through history entries.
 
+use crate::config::schema::TimeFormat;
+use crate::integrations::github::{BranchInfo, ChecksState, PrState, PullRequest, ReviewDecision};
+use crate::tui::theme::Theme;
 use crate::{Session, Status};
 use ratatui::{
     layout::Rect,
@@ -17,16 +21,23 @@ use std::time::Instant;
 /// Maximum history entries visible without scrolling.
 const MAX_VISIBLE_HISTORY: usize = 5;
 
+/// Maximum wrapped lines of the last assistant message.
+const MAX_MESSAGE_LINES: usize = 3;
+
 /// Renders the session detail modal overlay.
 ///
 /// The modal is centered in the given `area` and displays session metadata,
 /// API usage summary, state history (with scroll support), and action hints.
+#[allow(clippy::too_many_arguments)]
 pub fn render_detail(
     frame: &mut Frame,
     session: &Session,
+    github: Option<&BranchInfo>,
     area: Rect,
     history_scroll: usize,
     now: Instant,
+    time_format: TimeFormat,
+    theme: &Theme,
 ) {
     let modal_width = 50u16.min(area.width.saturating_sub(4));
     let modal_height = 16u16.min(area.height.saturating_sub(2));
@@ -53,12 +64,21 @@ pub fn render_detail(
     let block = Block::default()
         .title(format!("── {} ──", title))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(theme.accent);
 
     let inner = block.inner(modal_area);
     frame.render_widget(block, modal_area);
 
-    let lines = build_detail_lines(session, inner.width, history_scroll, now, true);
+    let lines = build_detail_lines(
+        session,
+        github,
+        inner.width,
+        history_scroll,
+        now,
+        time_format,
+        theme,
+        true,
+    );
 
     let paragraph = Paragraph::new(lines);
     frame.render_widget(paragraph, inner);
@@ -69,12 +89,16 @@ pub fn render_detail(
 /// Unlike `render_detail`, this renders into the given `area` directly
 /// without clearing background or centering. Used for the non-modal layout
 /// where detail appears as a fixed section below the session list.
+#[allow(clippy::too_many_arguments)]
 pub fn render_inline_detail(
     frame: &mut Frame,
     session: &Session,
+    github: Option<&BranchInfo>,
     area: Rect,
     history_scroll: usize,
     now: Instant,
+    time_format: TimeFormat,
+    theme: &Theme,
 ) {
     if area.height < 3 || area.width < 20 {
         return; // Too small to render meaningfully
@@ -90,12 +114,21 @@ pub fn render_inline_detail(
     let block = Block::default()
         .title(format!("── {} ──", title))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(theme.accent);
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let lines = build_detail_lines(session, inner.width, history_scroll, now, false);
+    let lines = build_detail_lines(
+        session,
+        github,
+        inner.width,
+        history_scroll,
+        now,
+        time_format,
+        theme,
+        false,
+    );
 
     let paragraph = Paragraph::new(lines);
     frame.render_widget(paragraph, inner);
@@ -105,7 +138,7 @@ pub fn render_inline_detail(
 ///
 /// Shows a hint message with keybinding guidance to help users understand
 /// how to navigate and interact with sessions.
-pub fn render_detail_placeholder(frame: &mut Frame, area: Rect) {
+pub fn render_detail_placeholder(frame: &mut Frame, area: Rect, theme: &Theme) {
     if area.height < 3 || area.width < 20 {
         return;
     }
@@ -113,7 +146,7 @@ pub fn render_detail_placeholder(frame: &mut Frame, area: Rect) {
     let block = Block::default()
         .title("── Detail ──")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(theme.muted);
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -122,16 +155,16 @@ pub fn render_detail_placeholder(frame: &mut Frame, area: Rect) {
     let lines = vec![
         Line::from(vec![Span::styled(
             "Select a session to see details",
-            Style::default().fg(Color::DarkGray),
+            theme.muted,
         )]),
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled("[j/k] ", Style::default().fg(Color::Cyan)),
-            Span::styled("Navigate  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[Enter] ", Style::default().fg(Color::Cyan)),
-            Span::styled("Hook  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("[q] ", Style::default().fg(Color::Cyan)),
-            Span::styled("Quit", Style::default().fg(Color::DarkGray)),
+            Span::styled("[j/k] ", theme.accent),
+            Span::styled("Navigate  ", theme.muted),
+            Span::styled("[Enter] ", theme.accent),
+            Span::styled("Hook  ", theme.muted),
+            Span::styled("[q] ", theme.accent),
+            Span::styled("Quit", theme.muted),
         ]),
     ];
 
@@ -144,25 +177,24 @@ pub fn render_detail_placeholder(frame: &mut Frame, area: Rect) {
 /// When `show_actions` is true, footer action hints are appended (modal mode).
 /// For inline mode, actions are omitted since keybindings are shown in the
 /// main footer.
+#[allow(clippy::too_many_arguments)]
 fn build_detail_lines<'a>(
     session: &'a Session,
+    github: Option<&BranchInfo>,
     panel_width: u16,
     history_scroll: usize,
     now: Instant,
+    time_format: TimeFormat,
+    theme: &Theme,
     show_actions: bool,
 ) -> Vec<Line<'a>> {
     let mut lines: Vec<Line<'a>> = Vec::new();
 
     // Status line
-    let elapsed = now.duration_since(session.since);
-    let status_color = status_color(session.status);
-    let elapsed_str = super::dashboard::format_duration_secs(elapsed.as_secs());
+    let elapsed_str = super::dashboard::format_since(session.since, now, time_format);
     lines.push(Line::from(vec![
         Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
-        Span::styled(
-            format!("{}", session.status),
-            Style::default().fg(status_color),
-        ),
+        Span::styled(format!("{}", session.status), theme.status(session.status)),
         Span::raw(format!(" ({})", elapsed_str)),
     ]));
 
@@ -178,7 +210,7 @@ fn build_detail_lines<'a>(
         wd
     };
     let wd_style = if is_error {
-        Style::default().fg(Color::Red)
+        theme.error
     } else {
         Style::default()
     };
@@ -199,10 +231,51 @@ fn build_detail_lines<'a>(
         Span::raw(id_display),
     ]));
 
+    lines.push(Line::from(vec![
+        Span::styled("Agent: ", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(session.agent_type.label()),
+    ]));
+
+    // Git branch and its pull request, looked up with `gh`
+    if let Some(info) = github {
+        let mut branch = vec![
+            Span::styled("Branch: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(info.branch.clone()),
+        ];
+        if let Some(issue) = info.issue {
+            branch.push(Span::styled(format!("  issue #{}", issue), theme.muted));
+        }
+        lines.push(Line::from(branch));
+        if let Some(pr) = &info.pull_request {
+            lines.push(pull_request_line(pr, theme));
+            let title_width = (panel_width as usize).saturating_sub(2).max(1);
+            let title = wrap_message(&pr.title, title_width, 1).concat();
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", title),
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+    }
+
+    // Last assistant message, word-wrapped under its label
+    if let Some(message) = session.last_message.as_deref() {
+        lines.push(Line::from(vec![Span::styled(
+            "Last message:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]));
+        let wrap_width = (panel_width as usize).saturating_sub(2).max(1);
+        for text in wrap_message(message, wrap_width, MAX_MESSAGE_LINES) {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", text),
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+    }
+
     // API usage placeholder
     lines.push(Line::from(vec![
         Span::styled("Quota: ", Style::default().add_modifier(Modifier::BOLD)),
-        Span::styled("--", Style::default().fg(Color::DarkGray)),
+        Span::styled("--", theme.muted),
     ]));
 
     // Blank separator
@@ -217,7 +290,7 @@ fn build_detail_lines<'a>(
     if session.history.is_empty() {
         lines.push(Line::from(vec![Span::styled(
             "  (no transitions)",
-            Style::default().fg(Color::DarkGray),
+            theme.muted,
         )]));
     } else {
         let total = session.history.len();
@@ -243,64 +316,127 @@ fn build_detail_lines<'a>(
                 Span::raw(format!("  {}  ", duration_str)),
                 Span::styled(
                     format!("{}", transition.from),
-                    Style::default().fg(status_color_for(transition.from)),
+                    theme.status(transition.from),
                 ),
                 Span::raw(" → "),
-                Span::styled(
-                    format!("{}", transition.to),
-                    Style::default().fg(status_color_for(transition.to)),
-                ),
+                Span::styled(format!("{}", transition.to), theme.status(transition.to)),
             ]));
         }
 
         if total > MAX_VISIBLE_HISTORY {
             let indicator = format!("  [{}/{} entries]", end - start, total);
-            lines.push(Line::from(vec![Span::styled(
-                indicator,
-                Style::default().fg(Color::DarkGray),
-            )]));
+            lines.push(Line::from(vec![Span::styled(indicator, theme.muted)]));
         }
     }
 
     if show_actions {
         // Footer actions (modal mode only)
-        let mut actions = vec![Span::styled(
-            "[ESC] Back",
-            Style::default().fg(Color::DarkGray),
-        )];
+        let mut actions = vec![Span::styled("[ESC] Back", theme.muted)];
         if session.status == Status::Closed {
-            actions.insert(
-                0,
-                Span::styled("[R]esurrect  ", Style::default().fg(Color::Yellow)),
-            );
+            actions.insert(0, Span::styled("[R]esurrect  ", theme.warning));
         }
+        actions.insert(actions.len() - 1, Span::styled("[C]lose  ", theme.error));
         actions.insert(
             actions.len() - 1,
-            Span::styled("[C]lose  ", Style::default().fg(Color::Red)),
-        );
-        actions.insert(
-            actions.len() - 1,
-            Span::styled("[S] Copy ID  ", Style::default().fg(Color::Cyan)),
+            Span::styled("[S] Copy ID  ", theme.accent),
         );
+        if github.is_some_and(|info| info.pull_request.is_some()) {
+            actions.insert(
+                actions.len() - 1,
+                Span::styled("[O]pen PR  ", theme.success),
+            );
+        }
         lines.push(Line::from(actions));
     }
 
     lines
 }
 
-/// Returns the display color for a session status.
-fn status_color(status: Status) -> Color {
-    status_color_for(status)
+/// Formats the pull request summary: number, state, checks and review.
+///
+/// Merged pull requests keep GitHub's purple in every theme.
+fn pull_request_line<'a>(pr: &PullRequest, theme: &Theme) -> Line<'a> {
+    let state_style = match pr.state {
+        PrState::Open => theme.success,
+        PrState::Draft => theme.muted,
+        PrState::Merged => Style::default().fg(Color::Magenta),
+        PrState::Closed => theme.error,
+    };
+    let mut spans = vec![
+        Span::styled("PR: ", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(format!("#{} ", pr.number)),
+        Span::styled(pr.state.label(), state_style),
+    ];
+    if let Some(checks) = pr.checks.label() {
+        let style = match pr.checks {
+            ChecksState::Failing => theme.error,
+            ChecksState::Pending => theme.warning,
+            _ => theme.success,
+        };
+        spans.push(Span::raw(" · "));
+        spans.push(Span::styled(checks, style));
+    }
+    if let Some(review) = pr.review {
+        let style = match review {
+            ReviewDecision::Approved => theme.success,
+            ReviewDecision::ChangesRequested => theme.error,
+            ReviewDecision::ReviewRequired => theme.warning,
+        };
+        spans.push(Span::raw(" · "));
+        spans.push(Span::styled(review.label(), style));
+    }
+    Line::from(spans)
 }
 
-/// Maps a status to its display color.
-fn status_color_for(status: Status) -> Color {
-    match status {
-        Status::Working => Color::Green,
-        Status::Attention => Color::Yellow,
-        Status::Question => Color::Magenta,
-        Status::Closed => Color::DarkGray,
+/// Word-wraps `message` to `width` columns, keeping at most `max_lines`.
+///
+/// Whitespace is collapsed first. When text is cut off, the last line ends
+/// with `…`. Words longer than `width` are split.
+fn wrap_message(message: &str, width: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut truncated = false;
+    for word in message.split_whitespace() {
+        let mut word: String = word.to_string();
+        loop {
+            let used = current.chars().count();
+            let needed = word.chars().count() + usize::from(used > 0);
+            if used + needed <= width {
+                if used > 0 {
+                    current.push(' ');
+                }
+                current.push_str(&word);
+                break;
+            }
+            if used == 0 {
+                // Word alone is too long: split it at the line width
+                let head: String = word.chars().take(width).collect();
+                word = word.chars().skip(width).collect();
+                lines.push(head);
+            } else {
+                lines.push(std::mem::take(&mut current));
+            }
+            if lines.len() == max_lines {
+                truncated = true;
+                break;
+            }
+        }
+        if truncated {
+            break;
+        }
+    }
+    if !truncated && !current.is_empty() {
+        lines.push(current);
     }
+    if truncated {
+        if let Some(last) = lines.last_mut() {
+            if last.chars().count() >= width {
+                last.pop();
+            }
+            last.push('…');
+        }
+    }
+    lines
 }
 
 #[cfg(test)]
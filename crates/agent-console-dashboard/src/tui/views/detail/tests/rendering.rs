@@ -108,7 +108,16 @@ fn test_detail_shows_action_hints() {
     let mut session = make_session("hints-test");
     session.status = Status::Working;
 
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -133,7 +142,16 @@ fn test_detail_closed_session_shows_resurrect() {
     session.status = Status::Closed;
     session.closed = true;
 
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -150,7 +168,16 @@ fn test_detail_closed_session_shows_resurrect() {
 fn test_detail_unknown_dir_shows_error_not_unknown() {
     let session = Session::new("error-dir-test".to_string(), AgentType::ClaudeCode, None);
 
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -173,7 +200,16 @@ fn test_detail_unknown_dir_shows_error_not_unknown() {
 fn test_detail_normal_dir_shows_path() {
     let session = make_session("normal-dir-test");
 
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -196,7 +232,16 @@ fn test_detail_normal_dir_shows_path() {
 fn test_detail_no_history_shows_placeholder() {
     let session = make_session("no-history-test");
 
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -221,7 +266,16 @@ fn test_detail_history_shows_transitions() {
         duration: Duration::from_secs(30),
     });
 
-    let lines = build_detail_lines(&session, 60, 0, now, true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        now,
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -260,7 +314,16 @@ fn test_detail_history_scroll_shows_entry_count() {
         });
     }
 
-    let lines = build_detail_lines(&session, 60, 0, now, true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        now,
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -2,11 +2,21 @@ use super::*;
 use std::time::Instant;
 
 #[test]
-fn test_status_color_mapping() {
-    assert_eq!(status_color_for(Status::Working), Color::Green);
-    assert_eq!(status_color_for(Status::Attention), Color::Yellow);
-    assert_eq!(status_color_for(Status::Question), Color::Magenta);
-    assert_eq!(status_color_for(Status::Closed), Color::DarkGray);
+fn test_status_line_uses_theme_status_style() {
+    let mut session = make_session("theme-test");
+    session.status = Status::Question;
+    let theme = Theme::monochrome();
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &theme,
+        false,
+    );
+    assert_eq!(lines[0].spans[1].style, theme.question);
 }
 
 #[test]
@@ -22,7 +32,16 @@ fn test_history_shows_per_state_duration_not_ago() {
         duration: Duration::from_secs(30),
     });
 
-    let lines = build_detail_lines(&session, 60, 0, now, true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        now,
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -56,7 +75,16 @@ fn test_history_most_recent_shows_dynamic_duration() {
         duration: Duration::from_secs(10), // This duration is ignored for most recent
     });
 
-    let lines = build_detail_lines(&session, 60, 0, now, true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        now,
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -91,7 +119,16 @@ fn test_history_older_transitions_use_stored_duration() {
         duration: Duration::from_secs(150), // This is ignored for most recent
     });
 
-    let lines = build_detail_lines(&session, 80, 0, now, true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        80,
+        0,
+        now,
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     let text: String = lines
         .iter()
         .flat_map(|line| line.spans.iter().map(|span| span.content.as_ref()))
@@ -142,7 +179,16 @@ fn test_history_multiple_transitions_show_correct_durations() {
         duration: Duration::from_secs(0), // Ignored for most recent
     });
 
-    let lines = build_detail_lines(&session, 80, 0, now, true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        80,
+        0,
+        now,
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
 
     // Verify the content contains expected durations
     let text: String = lines
@@ -179,7 +225,16 @@ fn test_render_detail_no_panic_normal() {
     let session = make_session("test-1");
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -191,7 +246,16 @@ fn test_render_detail_no_panic_narrow() {
     let session = make_session("test-narrow");
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -203,7 +267,16 @@ fn test_render_detail_no_panic_too_small() {
     let session = make_session("test-tiny");
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -224,7 +297,16 @@ fn test_render_detail_with_history() {
     }
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, now);
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                now,
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -237,7 +319,16 @@ fn test_render_detail_closed_session() {
     session.status = Status::Closed;
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -252,7 +343,16 @@ fn test_render_detail_long_working_dir() {
     ));
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -273,7 +373,16 @@ fn test_render_detail_history_scroll() {
     }
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 3, now);
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                3,
+                now,
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail with scroll offset");
 }
@@ -287,7 +396,16 @@ fn test_render_inline_detail_no_panic_normal() {
     let session = make_session("test-inline");
     terminal
         .draw(|frame| {
-            render_inline_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_inline_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -299,7 +417,16 @@ fn test_render_inline_detail_too_small_no_panic() {
     let session = make_session("test-tiny-inline");
     terminal
         .draw(|frame| {
-            render_inline_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_inline_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail when too small");
 }
@@ -320,7 +447,16 @@ fn test_render_inline_detail_with_history() {
     }
     terminal
         .draw(|frame| {
-            render_inline_detail(frame, &session, frame.area(), 0, now);
+            render_inline_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                now,
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail");
 }
@@ -333,7 +469,7 @@ fn test_render_detail_placeholder_no_panic() {
     let mut terminal = ratatui::Terminal::new(backend).expect("failed to create test terminal");
     terminal
         .draw(|frame| {
-            render_detail_placeholder(frame, frame.area());
+            render_detail_placeholder(frame, frame.area(), &Theme::default());
         })
         .expect("draw should not fail");
 }
@@ -344,7 +480,7 @@ fn test_render_detail_placeholder_too_small_no_panic() {
     let mut terminal = ratatui::Terminal::new(backend).expect("failed to create test terminal");
     terminal
         .draw(|frame| {
-            render_detail_placeholder(frame, frame.area());
+            render_detail_placeholder(frame, frame.area(), &Theme::default());
         })
         .expect("draw should not fail when too small");
 }
@@ -354,7 +490,16 @@ fn test_render_detail_placeholder_too_small_no_panic() {
 #[test]
 fn test_build_detail_lines_with_actions() {
     let session = make_session("test-lines");
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
     assert!(
         lines.len() >= 7,
         "expected at least 7 lines, got {}",
@@ -365,8 +510,26 @@ fn test_build_detail_lines_with_actions() {
 #[test]
 fn test_build_detail_lines_without_actions() {
     let session = make_session("test-lines-no-actions");
-    let lines_with = build_detail_lines(&session, 60, 0, Instant::now(), true);
-    let lines_without = build_detail_lines(&session, 60, 0, Instant::now(), false);
+    let lines_with = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
+    let lines_without = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        false,
+    );
     assert!(
         lines_without.len() < lines_with.len(),
         "inline mode should have fewer lines than modal"
@@ -379,7 +542,16 @@ fn test_build_detail_lines_without_actions() {
 fn test_build_detail_lines_unknown_working_dir_shows_error() {
     let mut session = Session::new("test-unknown-dir".to_string(), AgentType::ClaudeCode, None);
     session.status = Status::Working;
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
 
     let dir_line = &lines[1];
     let full_text: String = dir_line.spans.iter().map(|s| s.content.as_ref()).collect();
@@ -400,7 +572,16 @@ fn test_build_detail_lines_unknown_working_dir_shows_error() {
 #[test]
 fn test_build_detail_lines_normal_working_dir() {
     let session = make_session("test-normal-dir");
-    let lines = build_detail_lines(&session, 60, 0, Instant::now(), true);
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        true,
+    );
 
     let dir_line = &lines[1];
     let full_text: String = dir_line.spans.iter().map(|s| s.content.as_ref()).collect();
@@ -434,7 +615,16 @@ fn test_render_detail_unknown_working_dir_no_panic() {
     );
     terminal
         .draw(|frame| {
-            render_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail with unknown working_dir");
 }
@@ -450,7 +640,151 @@ fn test_render_inline_detail_unknown_working_dir_no_panic() {
     );
     terminal
         .draw(|frame| {
-            render_inline_detail(frame, &session, frame.area(), 0, Instant::now());
+            render_inline_detail(
+                frame,
+                &session,
+                None,
+                frame.area(),
+                0,
+                Instant::now(),
+                TimeFormat::Relative,
+                &Theme::default(),
+            );
         })
         .expect("draw should not fail with unknown working_dir");
 }
+
+#[test]
+fn test_build_detail_lines_absolute_time_format() {
+    let session = make_session("abs-detail");
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Absolute,
+        &Theme::default(),
+        false,
+    );
+    let status_line: String = lines[0]
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    assert!(
+        status_line.contains("(since "),
+        "Status line should show clock time, got: '{}'",
+        status_line
+    );
+}
+
+#[test]
+fn test_last_message_excerpt_shown_when_present() {
+    let mut session = make_session("message-test");
+    session.last_message = Some("Should I run\nthe migration now?".to_string());
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        false,
+    );
+    let texts: Vec<String> = lines
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect();
+    let label = texts
+        .iter()
+        .position(|t| t == "Last message:")
+        .expect("label line");
+    assert_eq!(texts[label + 1], "  Should I run the migration now?");
+}
+
+#[test]
+fn test_last_message_omitted_when_absent() {
+    let session = make_session("no-message");
+    let lines = build_detail_lines(
+        &session,
+        None,
+        60,
+        0,
+        Instant::now(),
+        TimeFormat::Relative,
+        &Theme::default(),
+        false,
+    );
+    assert!(lines
+        .iter()
+        .all(|line| line.spans.iter().all(|s| s.content != "Last message:")));
+}
+
+#[test]
+fn test_wrap_message_wraps_and_truncates() {
+    assert_eq!(
+        wrap_message("one two three four", 9, 3),
+        ["one two", "three", "four"]
+    );
+    assert_eq!(
+        wrap_message("one two three four five", 9, 2),
+        ["one two", "three…"]
+    );
+    assert_eq!(wrap_message("abcdefghij", 4, 3), ["abcd", "efgh", "ij"]);
+    assert_eq!(wrap_message("abcdefghijklm", 4, 2), ["abcd", "efg…"]);
+}
+
+#[test]
+fn test_detail_shows_branch_and_pull_request() {
+    use crate::integrations::github::{
+        BranchInfo, ChecksState, PrState, PullRequest, ReviewDecision,
+    };
+
+    let session = make_session("pr-test");
+    let mut info = BranchInfo {
+        branch: "fix/42-login".to_string(),
+        issue: Some(42),
+        pull_request: None,
+    };
+    let text = |info: &BranchInfo| -> String {
+        build_detail_lines(
+            &session,
+            Some(info),
+            60,
+            0,
+            Instant::now(),
+            TimeFormat::Relative,
+            &Theme::default(),
+            true,
+        )
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+    };
+
+    let without_pr = text(&info);
+    assert!(without_pr.contains("Branch: fix/42-login  issue #42"));
+    assert!(!without_pr.contains("PR:"));
+    assert!(!without_pr.contains("[O]pen PR"));
+
+    info.pull_request = Some(PullRequest {
+        number: 57,
+        title: "Fix login redirect".to_string(),
+        url: "https://github.com/o/r/pull/57".to_string(),
+        state: PrState::Open,
+        checks: ChecksState::Failing,
+        review: Some(ReviewDecision::Approved),
+    });
+    let with_pr = text(&info);
+    assert!(with_pr.contains("PR: #57 open · checks failing · approved"));
+    assert!(with_pr.contains("  Fix login redirect"));
+    assert!(with_pr.contains("[O]pen PR"));
+}
@@ -0,0 +1,123 @@
+//! Embedded terminal pane.
+//!
+//! Draws the screen of the [`EmbeddedTerminal`] below the dashboard, with a
+//! border that is highlighted while the pane has keyboard focus.
+
+use crate::tui::embedded::EmbeddedTerminal;
+use crate::tui::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders},
+    Frame,
+};
+
+/// Converts a vt100 color to a ratatui color.
+fn color(color: vt100::Color) -> Color {
+    match color {
+        vt100::Color::Default => Color::Reset,
+        vt100::Color::Idx(index) => Color::Indexed(index),
+        vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
+
+/// Style of one screen cell.
+fn cell_style(cell: &vt100::Cell) -> Style {
+    let mut style = Style::default()
+        .fg(color(cell.fgcolor()))
+        .bg(color(cell.bgcolor()));
+    if cell.bold() {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if cell.italic() {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if cell.underline() {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    if cell.inverse() {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    style
+}
+
+/// Copies `screen` into `buf` at `area`, clipped to both.
+pub fn render_screen(screen: &vt100::Screen, area: Rect, buf: &mut Buffer) {
+    let (rows, cols) = screen.size();
+    for row in 0..rows.min(area.height) {
+        for col in 0..cols.min(area.width) {
+            let Some(cell) = screen.cell(row, col) else {
+                continue;
+            };
+            // The wide character in the previous cell covers this one
+            if cell.is_wide_continuation() {
+                continue;
+            }
+            let contents = cell.contents();
+            let target = &mut buf[(area.x + col, area.y + row)];
+            target.set_symbol(if contents.is_empty() { " " } else { &contents });
+            target.set_style(cell_style(cell));
+        }
+    }
+}
+
+/// Renders the pane in `area`, resizing the terminal to fit it.
+///
+/// While `focused`, the border is highlighted and the cursor is placed at the
+/// terminal's cursor.
+pub fn render_embedded_terminal(
+    frame: &mut Frame,
+    terminal: &mut EmbeddedTerminal,
+    area: Rect,
+    focused: bool,
+    theme: &Theme,
+) {
+    let hint = if focused {
+        "[Ctrl+]] Dashboard"
+    } else {
+        "[Ctrl+]] Focus"
+    };
+    let block = Block::default()
+        .title(format!("── {} ── {} ", terminal.title(), hint))
+        .borders(Borders::ALL)
+        .border_style(if focused { theme.accent } else { theme.muted });
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    terminal.resize(inner.height, inner.width);
+    let cursor = terminal.with_screen(|screen| {
+        render_screen(screen, inner, frame.buffer_mut());
+        (!screen.hide_cursor()).then(|| screen.cursor_position())
+    });
+    if let (true, Some((row, col))) = (focused, cursor) {
+        if row < inner.height && col < inner.width {
+            frame.set_cursor_position((inner.x + col, inner.y + row));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_screen_copies_text_and_colors() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process(b"hi \x1b[1;31mred\x1b[0m\r\nnext");
+        let area = Rect::new(1, 1, 6, 2);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 4));
+        render_screen(parser.screen(), area, &mut buf);
+
+        assert_eq!(buf[(1, 1)].symbol(), "h");
+        assert_eq!(buf[(4, 1)].symbol(), "r");
+        assert_eq!(buf[(4, 1)].fg, Color::Indexed(1));
+        assert!(buf[(4, 1)].modifier.contains(Modifier::BOLD));
+        assert_eq!(buf[(1, 2)].symbol(), "n");
+        // Clipped to the area
+        assert_eq!(buf[(7, 1)].symbol(), " ");
+    }
+}
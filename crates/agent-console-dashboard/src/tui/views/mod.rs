@@ -2,5 +2,10 @@
 //!
 //! Each view renders a specific screen or component of the dashboard.
 
+pub mod alerts;
 pub mod dashboard;
 pub mod detail;
+pub mod embedded;
+pub mod palette;
+pub mod tabs;
+pub mod usage_chart;
@@ -0,0 +1,88 @@
+//! Command palette popup.
+//!
+//! Lists the `tui.commands` entries in a bordered box centered over the
+//! dashboard, with the highlighted entry reversed (see
+//! [`CommandPalette`](crate::tui::palette::CommandPalette)).
+
+use crate::config::schema::SessionCommandConfig;
+use crate::tui::palette::CommandPalette;
+use crate::tui::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Widest the popup grows, borders included.
+const MAX_WIDTH: u16 = 60;
+
+/// Popup area for `count` commands centered in `area`, clipped to it.
+pub fn palette_area(area: Rect, count: usize) -> Rect {
+    let width = MAX_WIDTH.min(area.width);
+    let height = (count as u16).saturating_add(2).min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Renders the palette for the session labelled `label` over `area`.
+pub fn render_command_palette(
+    frame: &mut Frame,
+    area: Rect,
+    palette: &CommandPalette,
+    commands: &[SessionCommandConfig],
+    label: &str,
+    theme: &Theme,
+) {
+    let popup = palette_area(area, commands.len());
+    let block = Block::default()
+        .title(format!("── Run in {} ", label))
+        .borders(Borders::ALL)
+        .border_style(theme.accent);
+    let inner = block.inner(popup);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
+
+    // Keep the highlighted entry visible when the list is taller than the box
+    let skip = (palette.selected + 1).saturating_sub(inner.height as usize);
+    let lines: Vec<Line> = commands
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(inner.height as usize)
+        .map(|(index, command)| {
+            let style = if index == palette.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(format!(" {} ", command.name), style),
+                Span::styled(format!(" {}", command.command), theme.muted),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_area_centered_and_clipped() {
+        assert_eq!(
+            palette_area(Rect::new(0, 0, 100, 20), 3),
+            Rect::new(20, 7, 60, 5)
+        );
+        assert_eq!(
+            palette_area(Rect::new(0, 0, 40, 4), 10),
+            Rect::new(0, 0, 40, 4)
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Workspace tab bar.
+//!
+//! A single row under the header listing the "All" tab and one tab per
+//! project (see [`WorkspaceTabs`]), each with its session count. The bar is
+//! hidden while every session belongs to the same project.
+
+use crate::tui::icons::StatusIcons;
+use crate::tui::tabs::WorkspaceTabs;
+use crate::tui::theme::Theme;
+use crate::{Session, Status};
+use ratatui::text::{Line, Span};
+
+/// Returns the tab bar height: one row with two or more projects, else zero.
+pub fn tab_bar_height(tabs: &WorkspaceTabs) -> u16 {
+    u16::from(tabs.project_count() >= 2)
+}
+
+/// Formats the tab bar: ` 0 All (5)  1 api (2)  2 web ! (3) `.
+///
+/// Each tab is prefixed with its `g<N>` jump number. Tabs with a session in
+/// `attention` show the attention icon, and the active tab is highlighted.
+pub fn format_tab_bar(
+    tabs: &WorkspaceTabs,
+    sessions: &[Session],
+    icons: &StatusIcons,
+    theme: &Theme,
+) -> Line<'static> {
+    let mut counts = vec![0usize; tabs.tab_count()];
+    let mut attention = vec![false; tabs.tab_count()];
+    counts[0] = sessions.len();
+    for session in sessions {
+        let needs_attention = !session.closed && session.status == Status::Attention;
+        attention[0] |= needs_attention;
+        if let Some(tab) = tabs.tab_of(session) {
+            counts[tab] += 1;
+            attention[tab] |= needs_attention;
+        }
+    }
+
+    let mut spans = Vec::new();
+    for (i, title) in tabs.titles().into_iter().enumerate() {
+        let marker = if attention[i] {
+            format!(" {}", icons.attention)
+        } else {
+            String::new()
+        };
+        let style = if i == tabs.active() {
+            theme.accent_fill()
+        } else {
+            theme.muted
+        };
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(
+            format!(" {i} {title}{marker} ({}) ", counts[i]),
+            style,
+        ));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::tabs::TabState;
+    use crate::AgentType;
+    use ratatui::style::Color;
+    use std::path::PathBuf;
+
+    fn session(id: &str, dir: &str, status: Status) -> Session {
+        let mut session = Session::new(
+            id.to_string(),
+            AgentType::ClaudeCode,
+            Some(PathBuf::from(dir)),
+        );
+        session.status = status;
+        session
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_tab_bar_hidden_for_single_project() {
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&[session("a", "/nonexistent/api", Status::Working)]);
+        assert_eq!(tab_bar_height(&tabs), 0);
+    }
+
+    #[test]
+    fn test_tab_bar_counts_and_attention() {
+        let sessions = [
+            session("a", "/nonexistent/api", Status::Working),
+            session("b", "/nonexistent/web", Status::Attention),
+            session("c", "/nonexistent/web", Status::Working),
+        ];
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&sessions);
+        assert_eq!(tab_bar_height(&tabs), 1);
+
+        let line = format_tab_bar(&tabs, &sessions, &StatusIcons::default(), &Theme::default());
+        assert_eq!(line_text(&line), " 0 All ! (3)   1 api (1)   2 web ! (2) ");
+    }
+
+    #[test]
+    fn test_tab_bar_highlights_active_tab() {
+        let sessions = [
+            session("a", "/nonexistent/api", Status::Working),
+            session("b", "/nonexistent/web", Status::Working),
+        ];
+        let mut tabs = WorkspaceTabs::new();
+        tabs.sync(&sessions);
+        tabs.switch_to(2, TabState::default());
+
+        let line = format_tab_bar(&tabs, &sessions, &StatusIcons::default(), &Theme::default());
+        let active = line
+            .spans
+            .iter()
+            .find(|s| s.content.contains("web"))
+            .expect("web tab");
+        assert_eq!(active.style.bg, Some(Color::Cyan));
+    }
+}
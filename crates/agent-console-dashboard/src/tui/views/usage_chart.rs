@@ -0,0 +1,141 @@
+//! API usage history chart view.
+//!
+//! Plots 5h and 7d utilization from [`UsageHistory`] as braille lines, with a
+//! movable cursor whose sample is shown in an info line below the chart.
+
+use crate::tui::theme::Theme;
+use crate::tui::usage_history::{UsageHistory, UsageSample};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+/// Color of the 5-hour utilization line.
+const FIVE_HOUR_COLOR: Color = Color::Cyan;
+
+/// Color of the 7-day utilization line.
+const SEVEN_DAY_COLOR: Color = Color::Magenta;
+
+/// Formats the cursor info line: `14:32:05  5h: 42%  7d: 77%  (12/40)`.
+pub fn format_sample_line(
+    sample: &UsageSample,
+    index: usize,
+    total: usize,
+    theme: &Theme,
+) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(format!("{}  ", sample.at.format("%H:%M:%S"))),
+        Span::styled(
+            format!("5h: {:.0}%", sample.five_hour.floor()),
+            Style::default().fg(FIVE_HOUR_COLOR),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            format!("7d: {:.0}%", sample.seven_day.floor()),
+            Style::default().fg(SEVEN_DAY_COLOR),
+        ),
+        Span::styled(format!("  ({}/{})", index + 1, total), theme.muted),
+    ])
+}
+
+/// Renders the usage chart with the cursor at sample `cursor` (0 = oldest).
+pub fn render_usage_chart(
+    frame: &mut Frame,
+    area: Rect,
+    history: &UsageHistory,
+    cursor: usize,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .title("── API Usage History ──")
+        .borders(Borders::ALL)
+        .border_style(theme.accent);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if history.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                "No usage samples yet. Samples arrive with each daemon usage fetch.",
+                theme.muted,
+            )),
+            inner,
+        );
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let cursor = cursor.min(history.len() - 1);
+    let five_hour: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (i as f64, s.five_hour))
+        .collect();
+    let seven_day: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (i as f64, s.seven_day))
+        .collect();
+    let y_max = history
+        .iter()
+        .map(|s| s.five_hour.max(s.seven_day))
+        .fold(100.0, f64::max);
+    let cursor_line = [(cursor as f64, 0.0), (cursor as f64, y_max)];
+    let x_max = (history.len() - 1).max(1) as f64;
+
+    let datasets = vec![
+        Dataset::default()
+            .name("5h")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(FIVE_HOUR_COLOR))
+            .data(&five_hour),
+        Dataset::default()
+            .name("7d")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(SEVEN_DAY_COLOR))
+            .data(&seven_day),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(theme.text)
+            .data(&cursor_line),
+    ];
+
+    let time_label = |index: usize| {
+        history
+            .get(index)
+            .map(|s| s.at.format("%H:%M").to_string())
+            .unwrap_or_default()
+    };
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, x_max])
+                .labels([time_label(0), time_label(history.len() - 1)])
+                .style(theme.muted),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, y_max])
+                .labels(["0%".to_string(), format!("{:.0}%", y_max)])
+                .style(theme.muted),
+        );
+    frame.render_widget(chart, chunks[0]);
+
+    if let Some(sample) = history.get(cursor) {
+        frame.render_widget(
+            Paragraph::new(format_sample_line(sample, cursor, history.len(), theme)),
+            chunks[1],
+        );
+    }
+}
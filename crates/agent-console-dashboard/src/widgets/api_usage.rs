@@ -165,12 +165,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: five_h,
                 resets_at,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: seven_d,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         }
     }
@@ -259,12 +263,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 42.0,
                 resets_at: Some(reset),
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: Some(Utc::now() + chrono::Duration::hours(84)), // 50% elapsed of 7d
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         };
         let sessions: Vec<Session> = vec![];
@@ -291,12 +299,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 42.0,
                 resets_at: Some(Utc::now() + chrono::Duration::hours(1)),
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 77.0,
                 resets_at: Some(Utc::now() + chrono::Duration::hours(84)),
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         };
         let sessions: Vec<Session> = vec![];
@@ -135,12 +135,16 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 25.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 50.0,
                 resets_at: None,
+                extra: Default::default(),
             },
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         }
     }
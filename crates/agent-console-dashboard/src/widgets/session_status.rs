@@ -460,6 +460,7 @@ mod tests {
     // -- Default trait --
 
     #[test]
+    #[allow(clippy::default_constructed_unit_structs)]
     fn test_default_trait() {
         let w = SessionStatusWidget::default();
         assert_eq!(w.id(), "session-status");
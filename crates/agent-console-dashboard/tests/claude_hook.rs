@@ -50,11 +50,13 @@ fn start_daemon(socket: &Path) -> DaemonGuard {
         .stderr(Stdio::null())
         .spawn()
         .expect("failed to spawn daemon");
+    // Guard immediately so the child is reaped even if the socket never appears
+    let guard = DaemonGuard { child };
 
     // Wait for socket to appear (max ~2 s)
     for _ in 0..200 {
         if socket.exists() {
-            return DaemonGuard { child };
+            return guard;
         }
         std::thread::sleep(std::time::Duration::from_millis(10));
     }
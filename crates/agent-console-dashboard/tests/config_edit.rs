@@ -0,0 +1,92 @@
+//! Integration tests for `acd config edit`.
+//!
+//! The editor is a shell command writing to the file, so the tests exercise
+//! the post-save validation and the edit/revert/keep prompt without a
+//! terminal.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const ACD_BIN: &str = env!("CARGO_BIN_EXE_acd");
+
+const VALID: &str = "[tui]\nlayout = \"compact\"\n";
+
+/// A config dir holding a valid config file, and the file's path.
+fn config_home() -> (TempDir, PathBuf) {
+    let home = TempDir::new().expect("failed to create temp dir");
+    let dir = home.path().join("agent-console-dashboard");
+    fs::create_dir_all(&dir).expect("failed to create config dir");
+    let path = dir.join("config.toml");
+    fs::write(&path, VALID).expect("failed to write config");
+    (home, path)
+}
+
+/// `acd config edit` with `editor` as `$EDITOR`.
+fn config_edit(home: &TempDir, editor: &str) -> Command {
+    let mut cmd = Command::new(ACD_BIN);
+    cmd.args(["config", "edit"])
+        .env("XDG_CONFIG_HOME", home.path())
+        .env("EDITOR", editor)
+        .env_remove("VISUAL")
+        .env_remove("ACD_PROFILE");
+    cmd
+}
+
+#[test]
+fn test_valid_edit_is_accepted() {
+    let (home, path) = config_home();
+    config_edit(&home, "echo 'tick_rate = \"100ms\"' >>")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Configuration is valid"));
+    assert!(fs::read_to_string(&path)
+        .expect("config is readable")
+        .contains("tick_rate"));
+}
+
+#[test]
+fn test_invalid_edit_can_be_reverted() {
+    let (home, path) = config_home();
+    config_edit(&home, "echo 'tick_rat = 1' >>")
+        .write_stdin("r\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("did you mean `tick_rate`?"))
+        .stdout(predicate::str::contains("Reverted"));
+    assert_eq!(
+        fs::read_to_string(&path).expect("config is readable"),
+        VALID
+    );
+}
+
+#[test]
+fn test_invalid_edit_can_be_edited_again() {
+    let (home, path) = config_home();
+    // Breaks the file the first time, fixes it the second
+    let editor = "f() { if grep -q bogus \"$1\"; then printf '[tui]\\n' > \"$1\"; \
+                  else echo 'bogus = 1' >> \"$1\"; fi; }; f";
+    config_edit(&home, editor)
+        .write_stdin("e\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Configuration is valid"));
+    assert_eq!(
+        fs::read_to_string(&path).expect("config is readable"),
+        "[tui]\n"
+    );
+}
+
+#[test]
+fn test_invalid_edit_is_kept_without_input() {
+    let (home, path) = config_home();
+    config_edit(&home, "echo 'tick_rat = 1' >>")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("tick_rat"));
+    assert!(fs::read_to_string(&path)
+        .expect("config is readable")
+        .contains("tick_rat"));
+}
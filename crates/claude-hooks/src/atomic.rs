@@ -0,0 +1,104 @@
+//! Platform helpers for the temp-file-then-rename write pattern
+//!
+//! Shared by settings and registry writes. On Windows a file cannot be
+//! flushed through a read-only handle or renamed while a handle is open, and
+//! the rename fails transiently while another process (an editor, Claude
+//! Code, an antivirus scanner) has the destination open.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Resolve the user's home directory
+///
+/// `HOME` wins when set so Unix shells and Git Bash on Windows behave the
+/// same; otherwise `USERPROFILE` (Windows), then the platform lookup.
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    ["HOME", "USERPROFILE"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .find(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::home_dir)
+}
+
+/// Write `contents` to `path` and fsync before returning
+///
+/// The handle is closed on return, so the file can be renamed afterwards.
+pub(crate) fn write_synced(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// Rename `from` over `to`, replacing it
+///
+/// `std::fs::rename` replaces existing files on every platform (on Windows
+/// via `MoveFileExW` with `MOVEFILE_REPLACE_EXISTING`, atomic on NTFS). On
+/// Windows, sharing violations are retried briefly before giving up.
+pub(crate) fn replace(from: &Path, to: &Path) -> io::Result<()> {
+    #[cfg(windows)]
+    {
+        const ATTEMPTS: u32 = 5;
+        let mut attempt = 1;
+        loop {
+            match fs::rename(from, to) {
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied && attempt < ATTEMPTS => {
+                    std::thread::sleep(std::time::Duration::from_millis(20 * u64::from(attempt)));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        fs::rename(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::env;
+    use tempfile::tempdir;
+
+    #[test]
+    #[serial(home)]
+    fn test_home_dir_falls_back_to_userprofile() {
+        let original_home = env::var_os("HOME");
+        let original_profile = env::var_os("USERPROFILE");
+
+        env::set_var("HOME", "");
+        env::set_var("USERPROFILE", "C:\\Users\\test");
+        assert_eq!(home_dir(), Some(PathBuf::from("C:\\Users\\test")));
+
+        env::set_var("HOME", "/home/test");
+        assert_eq!(home_dir(), Some(PathBuf::from("/home/test")));
+
+        match original_home {
+            Some(value) => env::set_var("HOME", value),
+            None => env::remove_var("HOME"),
+        }
+        match original_profile {
+            Some(value) => env::set_var("USERPROFILE", value),
+            None => env::remove_var("USERPROFILE"),
+        }
+    }
+
+    #[test]
+    fn test_replace_overwrites_existing_file() {
+        let dir = tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("settings.json");
+        let temp_path = dir.path().join("settings.json.tmp");
+        fs::write(&path, "old").expect("write failed");
+
+        write_synced(&temp_path, b"new").expect("write_synced failed");
+        replace(&temp_path, &path).expect("replace failed");
+
+        assert_eq!(fs::read_to_string(&path).expect("read failed"), "new");
+        assert!(!temp_path.exists());
+    }
+}
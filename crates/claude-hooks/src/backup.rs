@@ -0,0 +1,134 @@
+//! Timestamped settings backups
+//!
+//! Every settings write first copies the current file into a `backups`
+//! directory: `~/.claude/backups/settings-<ts>.json` for the user scope, and
+//! a per-project directory under the user data dir (e.g.
+//! `~/.local/share/claude-hooks/projects/<project>/backups`) for project and
+//! local scopes, so backups never land in the repository. Only the newest
+//! [`MAX_BACKUPS`] per settings file are kept.
+
+use crate::error::{Result, SettingsError};
+use crate::types::Scope;
+use chrono::Local;
+use std::fs;
+use std::path::PathBuf;
+
+/// Backups kept per settings file; older ones are deleted after each write
+pub const MAX_BACKUPS: usize = 10;
+
+/// A saved copy of a settings file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// Path to the backup file
+    pub path: PathBuf,
+    /// When the backup was taken (yyyyMMdd-hhmmss-SSS, sorts chronologically)
+    pub created_at: String,
+}
+
+/// Directory holding backups of the scope's settings and their file-name prefix
+fn backup_location(scope: &Scope) -> (PathBuf, String) {
+    let dir = scope.state_dir().join("backups");
+    let settings = scope.settings_path();
+    let stem = settings
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "settings".to_string());
+    (dir, format!("{}-", stem))
+}
+
+/// Copy the scope's settings file into its backups directory and rotate
+///
+/// Does nothing if the settings file does not exist yet. Rotation failures
+/// are logged, not returned, since the backup itself succeeded.
+///
+/// # Errors
+///
+/// Returns `SettingsError::Backup` if the copy cannot be made.
+pub(crate) fn snapshot(scope: &Scope) -> Result<Option<Backup>> {
+    let settings = scope.settings_path();
+    if !settings.exists() {
+        return Ok(None);
+    }
+
+    let (dir, prefix) = backup_location(scope);
+    fs::create_dir_all(&dir).map_err(SettingsError::Backup)?;
+    // Writes within the same millisecond get a counter suffix
+    let now = Local::now().format("%Y%m%d-%H%M%S-%3f").to_string();
+    let mut created_at = now.clone();
+    let mut path = dir.join(format!("{}{}.json", prefix, created_at));
+    for n in 1.. {
+        if !path.exists() {
+            break;
+        }
+        created_at = format!("{}-{}", now, n);
+        path = dir.join(format!("{}{}.json", prefix, created_at));
+    }
+    fs::copy(&settings, &path).map_err(SettingsError::Backup)?;
+
+    if let Err(e) = rotate(scope) {
+        log::warn!("Failed to rotate settings backups: {}", e);
+    }
+
+    Ok(Some(Backup { path, created_at }))
+}
+
+/// Delete all but the newest [`MAX_BACKUPS`] backups
+fn rotate(scope: &Scope) -> Result<()> {
+    for old in list_backups(scope)?.into_iter().skip(MAX_BACKUPS) {
+        fs::remove_file(&old.path).map_err(SettingsError::Backup)?;
+    }
+    Ok(())
+}
+
+/// List backups of the scope's settings file, newest first
+///
+/// # Errors
+///
+/// Returns `SettingsError::Backup` if the backups directory cannot be read.
+pub(crate) fn list_backups(scope: &Scope) -> Result<Vec<Backup>> {
+    let (dir, prefix) = backup_location(scope);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(SettingsError::Backup(e).into()),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(SettingsError::Backup)?.path();
+        let name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        // Skip anything that is not a backup of this settings file
+        let created_at = match name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".json"))
+        {
+            Some(ts) if ts.starts_with(|c: char| c.is_ascii_digit()) => ts.to_string(),
+            _ => continue,
+        };
+        backups.push(Backup { path, created_at });
+    }
+
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+/// Replace the scope's settings file with a backup
+///
+/// The backup must parse as JSON. The current settings are backed up first,
+/// so a restore can itself be undone.
+///
+/// # Errors
+///
+/// Returns `SettingsError::Io` if the backup cannot be read,
+/// `SettingsError::Parse` if it is not valid JSON, and any error from the
+/// settings write.
+pub(crate) fn restore_backup(scope: &Scope, backup: &Backup) -> Result<()> {
+    let content = fs::read_to_string(&backup.path).map_err(SettingsError::Io)?;
+    // Validate, then restore the backup byte for byte
+    serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| SettingsError::Parse(e.to_string()))?;
+    crate::settings::write_settings_text(scope, &content)
+}
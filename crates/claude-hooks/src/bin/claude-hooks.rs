@@ -0,0 +1,595 @@
+//! `claude-hooks` command-line tool.
+//!
+//! Manages Claude Code hooks from the shell without writing Rust. Build with
+//! the `cli` feature:
+//!
+//! ```bash
+//! cargo install claude-hooks --features cli
+//! claude-hooks install Stop "/path/to/stop.sh" --timeout 600 --dry-run
+//! claude-hooks install Stop "/path/to/stop.sh" --timeout 600
+//! claude-hooks --scope project --json list
+//! claude-hooks list --installed-by my-tool --event Stop
+//! claude-hooks adopt Stop "/path/to/stop.sh" --installed-by my-tool
+//! claude-hooks uninstall-all my-tool
+//! claude-hooks validate || echo "settings need attention"
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use claude_hooks::{
+    adopt, check_conflicts, disable, enable, install, install_dry_run, list, list_filtered, repair,
+    uninstall, uninstall_all_by, uninstall_dry_run, validate_settings, CommandHandler, Conflict,
+    Diagnostic, DiagnosticLevel, HookEvent, HookHandler, HookMetadata, ListEntry, ListFilter,
+    Matcher, RegistryEntry, RepairReport, Scope, SettingsDiff,
+};
+
+/// Manage Claude Code hooks.
+#[derive(Parser, Debug)]
+#[command(name = "claude-hooks", version, about)]
+struct Cli {
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Settings file to operate on.
+    #[arg(long, global = true, value_enum, default_value_t = ScopeArg::User)]
+    scope: ScopeArg,
+
+    /// Project root for project and local scopes (defaults to the current
+    /// directory).
+    #[arg(long, global = true)]
+    project: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ScopeArg {
+    /// `~/.claude/settings.json`
+    User,
+    /// `<project>/.claude/settings.json`
+    Project,
+    /// `<project>/.claude/settings.local.json`
+    Local,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Add a command hook.
+    Install {
+        /// Hook event, e.g. `Stop` or `PreToolUse`.
+        #[arg(value_parser = parse_event)]
+        event: HookEvent,
+        /// Command to run.
+        command: String,
+        /// Matcher: tool names like `Edit|Write`, or a regex; rejected if
+        /// the regex does not compile.
+        #[arg(long, value_parser = parse_matcher)]
+        matcher: Option<Matcher>,
+        /// Timeout in seconds.
+        #[arg(long)]
+        timeout: Option<u32>,
+        /// Run asynchronously (PostToolUse and PostToolUseFailure only).
+        #[arg(long = "async")]
+        run_async: bool,
+        /// Spinner message shown while the hook runs.
+        #[arg(long)]
+        status_message: Option<String>,
+        /// Installer recorded in the registry.
+        #[arg(long, default_value = "claude-hooks")]
+        installed_by: String,
+        /// Print the settings change instead of writing it.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a hook installed through claude-hooks.
+    Uninstall {
+        /// Hook event.
+        #[arg(value_parser = parse_event)]
+        event: HookEvent,
+        /// Exact command string.
+        command: String,
+        /// Print the settings change instead of writing it.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove every hook recorded as installed by one installer.
+    UninstallAll {
+        /// Installer name, as given to `--installed-by`.
+        installed_by: String,
+    },
+    /// Remove a managed hook from settings, keeping it for `enable`.
+    Disable {
+        /// Hook event.
+        #[arg(value_parser = parse_event)]
+        event: HookEvent,
+        /// Exact command string.
+        command: String,
+    },
+    /// Restore a hook removed with `disable`.
+    Enable {
+        /// Hook event.
+        #[arg(value_parser = parse_event)]
+        event: HookEvent,
+        /// Exact command string.
+        command: String,
+    },
+    /// List hooks with their managed status.
+    List {
+        /// Only hooks on this event.
+        #[arg(long, value_parser = parse_event)]
+        event: Option<HookEvent>,
+        /// Only hooks recorded with this installer.
+        #[arg(long)]
+        installed_by: Option<String>,
+        /// Only hooks installed through claude-hooks.
+        #[arg(long)]
+        managed: bool,
+    },
+    /// Take ownership of a hook already in the settings file.
+    Adopt {
+        /// Hook event.
+        #[arg(value_parser = parse_event)]
+        event: HookEvent,
+        /// Exact command string.
+        command: String,
+        /// Owner recorded in the registry.
+        #[arg(long, default_value = "claude-hooks")]
+        installed_by: String,
+        /// What the hook does.
+        #[arg(long)]
+        description: Option<String>,
+        /// Why the hook is there.
+        #[arg(long)]
+        reason: Option<String>,
+        /// Mark the hook as safe to remove.
+        #[arg(long)]
+        optional: bool,
+    },
+    /// Report hooks from different installers that run for the same tools,
+    /// and commands registered twice.
+    Conflicts,
+    /// Drop registry entries whose hook is gone from settings and report
+    /// entries for deleted projects.
+    Repair,
+    /// Check that settings and registry parse and hooks are well-formed;
+    /// exits 1 if any error is found (warnings are printed only).
+    Validate,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let scope = match resolve_scope(cli.scope, cli.project) {
+        Ok(scope) => scope,
+        Err(e) => {
+            eprintln!("claude-hooks: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match cli.command {
+        Command::Install {
+            event,
+            command,
+            matcher,
+            timeout,
+            run_async,
+            status_message,
+            installed_by,
+            dry_run,
+        } => {
+            let handler = HookHandler::Command(CommandHandler {
+                command: command.clone(),
+                timeout,
+                r#async: run_async.then_some(true),
+                status_message,
+            });
+            if dry_run {
+                install_dry_run(scope, event, handler, matcher)
+                    .map(|diff| render_diff(&diff, cli.json))
+            } else {
+                install(scope.clone(), event, handler, matcher, &installed_by)
+                    .map(|()| render_action("installed", event, &command, &scope, cli.json))
+            }
+        }
+        Command::Uninstall {
+            event,
+            command,
+            dry_run,
+        } => {
+            if dry_run {
+                uninstall_dry_run(scope, event, &command).map(|diff| render_diff(&diff, cli.json))
+            } else {
+                uninstall(scope.clone(), event, &command)
+                    .map(|()| render_action("uninstalled", event, &command, &scope, cli.json))
+            }
+        }
+        Command::UninstallAll { installed_by } => uninstall_all_by(scope, &installed_by)
+            .map(|removed| render_removed(&removed, &installed_by, cli.json)),
+        Command::Disable { event, command } => disable(scope.clone(), event, &command)
+            .map(|()| render_action("disabled", event, &command, &scope, cli.json)),
+        Command::Enable { event, command } => enable(scope.clone(), event, &command)
+            .map(|()| render_action("enabled", event, &command, &scope, cli.json)),
+        Command::List {
+            event,
+            installed_by,
+            managed,
+        } => list_filtered(ListFilter {
+            scope,
+            event,
+            installed_by,
+            managed_only: managed,
+        })
+        .map(|entries| render_list(&entries, cli.json)),
+        Command::Adopt {
+            event,
+            command,
+            installed_by,
+            description,
+            reason,
+            optional,
+        } => adopt(
+            scope.clone(),
+            event,
+            &command,
+            &installed_by,
+            HookMetadata {
+                description,
+                reason,
+                optional: optional.then_some(true),
+            },
+        )
+        .map(|()| render_action("adopted", event, &command, &scope, cli.json)),
+        Command::Conflicts => {
+            check_conflicts(scope).map(|conflicts| render_conflicts(&conflicts, cli.json))
+        }
+        Command::Repair => repair(scope).map(|report| render_repair(&report, cli.json)),
+        Command::Validate => {
+            // Registry problems surface as errors from list
+            let diagnostics = validate_settings(scope.clone())
+                .and_then(|diagnostics| list(scope).map(|_| diagnostics))
+                .unwrap_or_else(|e| {
+                    vec![Diagnostic {
+                        level: DiagnosticLevel::Error,
+                        pointer: String::new(),
+                        message: e.to_string(),
+                    }]
+                });
+            println!("{}", render_diagnostics(&diagnostics, cli.json));
+            return if diagnostics
+                .iter()
+                .any(|d| d.level == DiagnosticLevel::Error)
+            {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+
+    match result {
+        Ok(output) => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("claude-hooks: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parse an event name as it appears in settings.json.
+fn parse_event(name: &str) -> Result<HookEvent, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("unknown hook event '{}'", name))
+}
+
+/// Parse and validate a matcher as it appears in settings.json.
+fn parse_matcher(matcher: &str) -> Result<Matcher, String> {
+    matcher
+        .parse()
+        .map_err(|e: claude_hooks::Error| e.to_string())
+}
+
+/// Build the library scope, defaulting the project root to the current
+/// directory.
+///
+/// The root is canonicalized, so `--project .` and `--project /abs/path`
+/// name the same project; a root that does not exist is an error.
+fn resolve_scope(scope: ScopeArg, project: Option<PathBuf>) -> std::io::Result<Scope> {
+    let root = || {
+        let root = project.map_or_else(std::env::current_dir, Ok)?;
+        std::fs::canonicalize(&root).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("project root {}: {}", root.display(), e))
+        })
+    };
+    Ok(match scope {
+        ScopeArg::User => Scope::User,
+        ScopeArg::Project => Scope::project(root()?),
+        ScopeArg::Local => Scope::local(root()?),
+    })
+}
+
+/// Confirm a change to a single hook.
+fn render_action(
+    action: &str,
+    event: HookEvent,
+    command: &str,
+    scope: &Scope,
+    json: bool,
+) -> String {
+    if json {
+        return serde_json::json!({
+            "action": action,
+            "event": event,
+            "command": command,
+            "scope": scope.name(),
+            "settings_path": scope.settings_path(),
+        })
+        .to_string();
+    }
+    format!("{} {:?} hook: {}", action, event, command)
+}
+
+/// Render the hooks removed by `uninstall-all` as JSON or one line each.
+fn render_removed(removed: &[RegistryEntry], installed_by: &str, json: bool) -> String {
+    if json {
+        return serde_json::to_string(removed).unwrap_or_else(|_| "[]".to_string());
+    }
+    if removed.is_empty() {
+        return format!("no hooks installed by {}", installed_by);
+    }
+    removed
+        .iter()
+        .map(|e| format!("uninstalled {:?} hook: {}", e.event, e.command))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render conflicts as JSON or one line each.
+fn render_conflicts(conflicts: &[Conflict], json: bool) -> String {
+    if json {
+        return serde_json::to_string(conflicts).unwrap_or_else(|_| "[]".to_string());
+    }
+    if conflicts.is_empty() {
+        return "no conflicts".to_string();
+    }
+    conflicts
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render a repair report as JSON or one line per finding.
+fn render_repair(report: &RepairReport, json: bool) -> String {
+    if json {
+        return serde_json::to_string(report).unwrap_or_else(|_| "{}".to_string());
+    }
+    if report.is_clean() {
+        return "registry is consistent with settings".to_string();
+    }
+    let sections = [
+        ("removed", &report.removed),
+        ("updated", &report.updated),
+        ("orphaned", &report.orphaned),
+    ];
+    sections
+        .iter()
+        .flat_map(|(label, entries)| {
+            entries
+                .iter()
+                .map(move |e| format!("{} {:?} hook: {}", label, e.event, e.command))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render a dry-run diff as JSON or a line diff.
+fn render_diff(diff: &SettingsDiff, json: bool) -> String {
+    if json {
+        return serde_json::to_string(diff).unwrap_or_else(|_| "{}".to_string());
+    }
+    diff.render().trim_end().to_string()
+}
+
+/// Render hooks as a JSON array or one line per hook.
+fn render_list(entries: &[ListEntry], json: bool) -> String {
+    if json {
+        return serde_json::to_string(entries).unwrap_or_else(|_| "[]".to_string());
+    }
+    entries
+        .iter()
+        .map(|entry| {
+            let owner = match &entry.metadata {
+                Some(metadata) => format!(" (managed by {})", metadata.installed_by),
+                None => String::new(),
+            };
+            format!("{:?}: {}{}", entry.event, entry.handler.key(), owner)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render validation results as JSON or text.
+fn render_diagnostics(diagnostics: &[Diagnostic], json: bool) -> String {
+    let valid = diagnostics
+        .iter()
+        .all(|d| d.level != DiagnosticLevel::Error);
+    if json {
+        return serde_json::json!({ "valid": valid, "diagnostics": diagnostics }).to_string();
+    }
+    if diagnostics.is_empty() {
+        "ok".to_string()
+    } else {
+        diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claude_hooks::RegistryMetadata;
+
+    fn entry(event: HookEvent, command: &str, managed: bool) -> ListEntry {
+        ListEntry {
+            event,
+            handler: HookHandler::Command(CommandHandler {
+                command: command.to_string(),
+                timeout: None,
+                r#async: None,
+                status_message: None,
+            }),
+            managed,
+            metadata: managed.then(|| RegistryMetadata {
+                added_at: "20260101-000000".to_string(),
+                installed_by: "acd".to_string(),
+                description: None,
+                reason: None,
+                optional: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_subcommands() {
+        let cli = Cli::parse_from([
+            "claude-hooks",
+            "install",
+            "PreToolUse",
+            "/bin/check.sh",
+            "--matcher",
+            "Bash",
+            "--scope",
+            "local",
+            "--json",
+        ]);
+        assert!(cli.json);
+        assert_eq!(cli.scope, ScopeArg::Local);
+        assert!(matches!(
+            cli.command,
+            Command::Install {
+                event: HookEvent::PreToolUse,
+                matcher: Some(_),
+                run_async: false,
+                ..
+            }
+        ));
+        assert!(Cli::try_parse_from(["claude-hooks", "uninstall", "Stopp", "/bin/x"]).is_err());
+        assert!(Cli::try_parse_from([
+            "claude-hooks",
+            "install",
+            "PreToolUse",
+            "/bin/x",
+            "--matcher",
+            "Edit("
+        ])
+        .is_err());
+        assert!(matches!(
+            Cli::parse_from(["claude-hooks", "list", "--event", "Stop", "--managed"]).command,
+            Command::List {
+                event: Some(HookEvent::Stop),
+                installed_by: None,
+                managed: true,
+            }
+        ));
+        assert!(matches!(
+            Cli::parse_from(["claude-hooks", "uninstall-all", "acd"]).command,
+            Command::UninstallAll { ref installed_by } if installed_by == "acd"
+        ));
+        assert!(Cli::try_parse_from(["claude-hooks"]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_scope_uses_canonical_project_root() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let root = std::fs::canonicalize(dir.path()).expect("canonicalize");
+        assert_eq!(
+            resolve_scope(ScopeArg::Project, Some(dir.path().join("."))).expect("scope"),
+            Scope::Project(root.clone())
+        );
+        assert_eq!(
+            resolve_scope(ScopeArg::Local, Some(root.clone())).expect("scope"),
+            Scope::Local(root)
+        );
+        assert!(resolve_scope(ScopeArg::Project, Some(dir.path().join("missing"))).is_err());
+        assert_eq!(
+            resolve_scope(ScopeArg::User, None).expect("scope"),
+            Scope::User
+        );
+    }
+
+    #[test]
+    fn test_render_list() {
+        let entries = vec![
+            entry(HookEvent::Stop, "/bin/stop.sh", true),
+            entry(HookEvent::SessionStart, "/bin/start.sh", false),
+        ];
+        assert_eq!(
+            render_list(&entries, false),
+            "Stop: /bin/stop.sh (managed by acd)\nSessionStart: /bin/start.sh"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_list(&entries, true)).expect("valid JSON");
+        assert_eq!(json[0]["event"], "Stop");
+        assert_eq!(json[0]["metadata"]["installed_by"], "acd");
+        assert_eq!(json[1]["managed"], false);
+    }
+
+    #[test]
+    fn test_render_diagnostics() {
+        let warning = Diagnostic {
+            level: DiagnosticLevel::Warning,
+            pointer: "/hooks/Stop/0/hooks/0/async".to_string(),
+            message: "async is only honored on PostToolUse and PostToolUseFailure".to_string(),
+        };
+        assert_eq!(render_diagnostics(&[], false), "ok");
+        assert_eq!(
+            render_diagnostics(std::slice::from_ref(&warning), false),
+            "warning: /hooks/Stop/0/hooks/0/async: async is only honored on PostToolUse and PostToolUseFailure"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_diagnostics(&[warning], true)).expect("valid JSON");
+        assert_eq!(json["valid"], true);
+        assert_eq!(json["diagnostics"][0]["level"], "warning");
+    }
+
+    #[test]
+    fn test_render_repair() {
+        assert_eq!(
+            render_repair(&RepairReport::default(), false),
+            "registry is consistent with settings"
+        );
+        let stale: claude_hooks::RegistryEntry = serde_json::from_value(serde_json::json!({
+            "event": "Stop",
+            "type": "command",
+            "command": "/bin/gone.sh",
+            "scope": "user",
+            "enabled": true,
+            "added_at": "20260101-000000",
+            "installed_by": "acd"
+        }))
+        .expect("valid entry");
+        let report = RepairReport {
+            removed: vec![stale],
+            ..Default::default()
+        };
+        assert_eq!(
+            render_repair(&report, false),
+            "removed Stop hook: /bin/gone.sh"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_repair(&report, true)).expect("valid JSON");
+        assert_eq!(json["removed"][0]["command"], "/bin/gone.sh");
+    }
+}
@@ -0,0 +1,200 @@
+//! Conflict detection between hook installers
+//!
+//! Claude Code runs every hook whose matcher matches, in parallel and in no
+//! guaranteed order. Two tools hooking the same event with overlapping
+//! matchers therefore cannot rely on running before or after each other, and
+//! the same command registered twice runs twice. This module finds such pairs.
+//!
+//! Matchers are compared with [`Matcher::overlaps`]: tool lists exactly, a
+//! regex against the other side's tool names, and two regexes only when
+//! they are identical.
+
+use serde::Serialize;
+use std::fmt;
+
+use crate::matcher::Matcher;
+use crate::types::HookEvent;
+
+/// Kind of conflict between two hooks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictKind {
+    /// The same command is registered twice for matching tools
+    Duplicate,
+    /// Different installers' hooks run for the same tools in unspecified order
+    Overlap,
+}
+
+/// One side of a conflict
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictingHook {
+    /// Matcher of the group the hook is in (None matches every tool)
+    pub matcher: Option<Matcher>,
+    /// Command string
+    pub command: String,
+    /// Installer recorded in the registry (None for unmanaged hooks)
+    pub installed_by: Option<String>,
+}
+
+/// Two hooks on the same event whose matchers overlap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Conflict {
+    /// Hook event both hooks are on
+    pub event: HookEvent,
+    /// What kind of conflict this is
+    pub kind: ConflictKind,
+    /// Hook found first in settings (or already installed)
+    pub first: ConflictingHook,
+    /// Hook found later (or about to be installed)
+    pub second: ConflictingHook,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let owner = |hook: &ConflictingHook| {
+            hook.installed_by
+                .clone()
+                .unwrap_or_else(|| "unmanaged".to_string())
+        };
+        match self.kind {
+            ConflictKind::Duplicate => write!(
+                f,
+                "{:?}: \"{}\" is registered twice ({}, {})",
+                self.event,
+                self.first.command,
+                owner(&self.first),
+                owner(&self.second)
+            ),
+            ConflictKind::Overlap => write!(
+                f,
+                "{:?}: \"{}\" ({}) and \"{}\" ({}) run for the same tools in no fixed order",
+                self.event,
+                self.first.command,
+                owner(&self.first),
+                self.second.command,
+                owner(&self.second)
+            ),
+        }
+    }
+}
+
+/// Find conflicts among existing hooks, or between planned and existing ones
+///
+/// With no `planned` hooks, every pair of `existing` hooks is checked.
+/// Otherwise only pairs involving a planned hook are reported, and a planned
+/// hook identical to an existing one is left to the installer to reject.
+pub(crate) fn find_conflicts(
+    existing: &[(HookEvent, ConflictingHook)],
+    planned: &[(HookEvent, ConflictingHook)],
+) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    let all: Vec<(&(HookEvent, ConflictingHook), bool)> = existing
+        .iter()
+        .map(|hook| (hook, false))
+        .chain(planned.iter().map(|hook| (hook, true)))
+        .collect();
+
+    for (i, ((event, first), first_planned)) in all.iter().enumerate() {
+        for ((other_event, second), second_planned) in &all[i + 1..] {
+            if !planned.is_empty() && !first_planned && !second_planned {
+                continue;
+            }
+            if event != other_event
+                || !matchers_overlap(first.matcher.as_ref(), second.matcher.as_ref())
+            {
+                continue;
+            }
+            let kind = if first.command == second.command {
+                if *first_planned || *second_planned {
+                    continue;
+                }
+                ConflictKind::Duplicate
+            } else if first.installed_by != second.installed_by {
+                ConflictKind::Overlap
+            } else {
+                continue;
+            };
+            conflicts.push(Conflict {
+                event: *event,
+                kind,
+                first: first.clone(),
+                second: second.clone(),
+            });
+        }
+    }
+    conflicts
+}
+
+fn matchers_overlap(a: Option<&Matcher>, b: Option<&Matcher>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.overlaps(b),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(matcher: Option<&str>, command: &str, owner: Option<&str>) -> ConflictingHook {
+        ConflictingHook {
+            matcher: matcher.map(Matcher::from_settings),
+            command: command.to_string(),
+            installed_by: owner.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_existing_conflicts() {
+        let existing = vec![
+            (
+                HookEvent::PreToolUse,
+                hook(Some("Bash"), "a.sh", Some("acd")),
+            ),
+            (HookEvent::PreToolUse, hook(Some("Bash|Edit"), "b.sh", None)),
+            (
+                HookEvent::PreToolUse,
+                hook(Some("Read"), "c.sh", Some("other")),
+            ),
+            (HookEvent::PreToolUse, hook(None, "a.sh", Some("acd"))),
+            (HookEvent::Stop, hook(None, "b.sh", None)),
+        ];
+        let conflicts = find_conflicts(&existing, &[]);
+        let summary: Vec<_> = conflicts
+            .iter()
+            .map(|c| (c.kind, c.first.command.as_str(), c.second.command.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ConflictKind::Overlap, "a.sh", "b.sh"),
+                (ConflictKind::Duplicate, "a.sh", "a.sh"),
+                (ConflictKind::Overlap, "b.sh", "a.sh"),
+                (ConflictKind::Overlap, "c.sh", "a.sh"),
+            ]
+        );
+        assert_eq!(
+            conflicts[0].to_string(),
+            "PreToolUse: \"a.sh\" (acd) and \"b.sh\" (unmanaged) run for the same tools in no fixed order"
+        );
+    }
+
+    #[test]
+    fn test_planned_conflicts_only() {
+        let existing = vec![
+            (HookEvent::Stop, hook(None, "x.sh", None)),
+            (HookEvent::Stop, hook(None, "y.sh", Some("other"))),
+        ];
+        let planned = vec![
+            (HookEvent::Stop, hook(None, "x.sh", Some("acd"))),
+            (HookEvent::Stop, hook(None, "z.sh", Some("acd"))),
+        ];
+        let conflicts = find_conflicts(&existing, &planned);
+        // x.sh/y.sh is pre-existing and x.sh/x.sh is an install error
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts
+            .iter()
+            .all(|c| c.kind == ConflictKind::Overlap
+                && c.second.installed_by.as_deref() == Some("acd")));
+    }
+}
@@ -0,0 +1,231 @@
+//! Settings diffs for dry runs
+//!
+//! A [`SettingsDiff`] describes what a write would change in a settings file:
+//! a structured list of [`Change`]s addressed by JSON pointer, and a
+//! line-based rendering of the pretty-printed file for showing to users.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Kind of change at one location
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Value present only after the write
+    Added,
+    /// Value present only before the write
+    Removed,
+    /// Value replaced by a different one
+    Modified,
+}
+
+/// One changed location in the settings file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    /// JSON pointer to the location, e.g. `/hooks/Stop/0`
+    pub pointer: String,
+    /// What happened there
+    pub kind: ChangeKind,
+    /// Value before the write (None if added)
+    pub old: Option<Value>,
+    /// Value after the write (None if removed)
+    pub new: Option<Value>,
+}
+
+/// Would-be change to a settings file, computed without writing
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingsDiff {
+    /// Settings file that would be written
+    pub path: PathBuf,
+    /// Current contents
+    pub before: Value,
+    /// Contents after the write
+    pub after: Value,
+    /// Changed locations, in document order
+    pub changes: Vec<Change>,
+}
+
+impl SettingsDiff {
+    /// Compare two versions of a settings file
+    pub fn new(path: PathBuf, before: Value, after: Value) -> Self {
+        let mut changes = Vec::new();
+        diff_values(String::new(), &before, &after, &mut changes);
+        Self {
+            path,
+            before,
+            after,
+            changes,
+        }
+    }
+
+    /// True if the write would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Line diff of the pretty-printed file, `-`/`+` prefixed like `diff -u`
+    /// without hunk headers
+    pub fn render(&self) -> String {
+        let before = pretty(&self.before);
+        let after = pretty(&self.after);
+        let mut out = format!("--- {}\n+++ {}\n", self.path.display(), self.path.display());
+        for (tag, line) in diff_lines(&before, &after) {
+            out.push(tag);
+            out.push_str(line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl fmt::Display for SettingsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Escape a key for use in a JSON pointer (RFC 6901)
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Recursively collect changes; arrays are compared index by index
+fn diff_values(pointer: String, before: &Value, after: &Value, changes: &mut Vec<Change>) {
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child = format!("{}/{}", pointer, escape(key));
+                match new.get(key) {
+                    Some(new_value) => diff_values(child, old_value, new_value, changes),
+                    None => changes.push(Change {
+                        pointer: child,
+                        kind: ChangeKind::Removed,
+                        old: Some(old_value.clone()),
+                        new: None,
+                    }),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    changes.push(Change {
+                        pointer: format!("{}/{}", pointer, escape(key)),
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(new_value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for i in 0..old.len().max(new.len()) {
+                let child = format!("{}/{}", pointer, i);
+                match (old.get(i), new.get(i)) {
+                    (Some(o), Some(n)) => diff_values(child, o, n, changes),
+                    (Some(o), None) => changes.push(Change {
+                        pointer: child,
+                        kind: ChangeKind::Removed,
+                        old: Some(o.clone()),
+                        new: None,
+                    }),
+                    (None, Some(n)) => changes.push(Change {
+                        pointer: child,
+                        kind: ChangeKind::Added,
+                        old: None,
+                        new: Some(n.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if before != after => changes.push(Change {
+            pointer,
+            kind: ChangeKind::Modified,
+            old: Some(before.clone()),
+            new: Some(after.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Longest-common-subsequence line diff; settings files are small enough
+/// for the quadratic table
+fn diff_lines<'a>(before: &'a str, after: &'a str) -> Vec<(char, &'a str)> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(('-', old[i]));
+            i += 1;
+        } else {
+            out.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_structured_changes() {
+        let before = json!({ "hooks": { "Stop": [{ "hooks": [] }] }, "model": "a", "a/b": 1 });
+        let after =
+            json!({ "hooks": { "Stop": [{ "hooks": [] }, { "hooks": [] }] }, "model": "b" });
+        let diff = SettingsDiff::new(PathBuf::from("settings.json"), before, after);
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|c| (c.pointer.as_str(), c.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/a~1b", ChangeKind::Removed),
+                ("/hooks/Stop/1", ChangeKind::Added),
+                ("/model", ChangeKind::Modified),
+            ]
+        );
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_render_marks_changed_lines() {
+        let diff = SettingsDiff::new(
+            PathBuf::from("settings.json"),
+            json!({ "a": 1, "b": 2 }),
+            json!({ "a": 1, "b": 3 }),
+        );
+        assert_eq!(
+            diff.render(),
+            "--- settings.json\n+++ settings.json\n {\n   \"a\": 1,\n-  \"b\": 2\n+  \"b\": 3\n }\n"
+        );
+
+        let same = SettingsDiff::new(PathBuf::from("s"), json!({}), json!({}));
+        assert!(same.is_empty());
+    }
+}
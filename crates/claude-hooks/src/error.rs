@@ -39,6 +39,18 @@ pub enum SettingsError {
     #[error("Failed to parse settings: {0}")]
     Parse(String),
 
+    /// Failed to back up, list or rotate settings backups
+    #[error("Failed to back up settings: {0}")]
+    Backup(#[source] std::io::Error),
+
+    /// Failed to start watching the settings file
+    #[error("Failed to watch settings: {0}")]
+    Watch(String),
+
+    /// Another process held the settings lock for too long
+    #[error("Timed out waiting for settings lock: {0}")]
+    Locked(PathBuf),
+
     /// Failed to write settings atomically
     #[error("Failed to write settings atomically: {path} - Safety copy at: {temp_path}")]
     WriteAtomic {
@@ -63,6 +75,19 @@ pub enum RegistryError {
     /// Failed to write registry
     #[error("Failed to write registry: {0}")]
     Write(String),
+
+    /// Another process held the registry lock for too long
+    #[error("Timed out waiting for registry lock: {0}")]
+    Locked(PathBuf),
+
+    /// Registry was written by a newer version of this crate
+    #[error("Registry schema version {found} is newer than supported version {supported}")]
+    UnsupportedVersion {
+        /// Version found in the file
+        found: u32,
+        /// Newest version this crate can read
+        supported: u32,
+    },
 }
 
 /// Hook logic errors
@@ -86,9 +111,36 @@ pub enum HookError {
         command: String,
     },
 
+    /// Hook not present in the settings file
+    #[error("Hook not found in settings: {event:?} - {command}")]
+    NotFound {
+        /// The hook event
+        event: HookEvent,
+        /// The command string
+        command: String,
+    },
+
     /// Invalid hook handler
     #[error("Invalid hook handler: {0}")]
     InvalidHandler(String),
+
+    /// Matcher that Claude Code would reject
+    #[error("Invalid matcher '{matcher}': {reason}")]
+    InvalidMatcher {
+        /// The matcher as it would be written to settings
+        matcher: String,
+        /// Why it is invalid
+        reason: String,
+    },
+
+    /// Template parameter not supplied
+    #[error("Missing parameter '{param}' for template '{template}'")]
+    MissingTemplateParam {
+        /// The template name
+        template: String,
+        /// The missing parameter
+        param: String,
+    },
 }
 
 /// Result type alias for claude-hooks operations
@@ -36,30 +36,29 @@ fn test_install_list_uninstall_workflow() {
     let _dir = setup_test_env();
 
     // Install hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
+    });
 
-    let result = install(HookEvent::Stop, handler.clone(), None, "test");
+    let result = install(Scope::User, HookEvent::Stop, handler.clone(), None, "test");
     assert!(result.is_ok(), "Install should succeed: {:?}", result.err());
 
     // List hooks - should show as managed
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1, "Should have exactly 1 hook");
     assert!(entries[0].managed, "Hook should be managed");
     assert_eq!(entries[0].event, HookEvent::Stop);
-    assert_eq!(entries[0].handler.command, "/path/to/stop.sh");
+    assert_eq!(entries[0].handler.key(), "/path/to/stop.sh");
     assert!(
         entries[0].metadata.is_some(),
         "Managed hook should have metadata"
     );
 
     // Uninstall hook
-    let result = uninstall(HookEvent::Stop, "/path/to/stop.sh");
+    let result = uninstall(Scope::User, HookEvent::Stop, "/path/to/stop.sh");
     assert!(
         result.is_ok(),
         "Uninstall should succeed: {:?}",
@@ -67,7 +66,7 @@ fn test_install_list_uninstall_workflow() {
     );
 
     // List hooks - should be empty
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 0, "Should have no hooks after uninstall");
 }
 
@@ -76,20 +75,19 @@ fn test_install_list_uninstall_workflow() {
 fn test_install_duplicate_fails() {
     let _dir = setup_test_env();
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
+    });
 
     // First install should succeed
-    let result = install(HookEvent::Stop, handler.clone(), None, "test");
+    let result = install(Scope::User, HookEvent::Stop, handler.clone(), None, "test");
     assert!(result.is_ok(), "First install should succeed");
 
     // Second install should fail with AlreadyExists
-    let result = install(HookEvent::Stop, handler, None, "test");
+    let result = install(Scope::User, HookEvent::Stop, handler, None, "test");
     assert!(result.is_err(), "Second install should fail");
 
     match result.unwrap_err() {
@@ -107,7 +105,7 @@ fn test_uninstall_unmanaged_fails() {
     let _dir = setup_test_env();
 
     // Try to uninstall hook that doesn't exist
-    let result = uninstall(HookEvent::Stop, "/unmanaged/hook.sh");
+    let result = uninstall(Scope::User, HookEvent::Stop, "/unmanaged/hook.sh");
     assert!(result.is_err(), "Uninstall of unmanaged hook should fail");
 
     match result.unwrap_err() {
@@ -125,19 +123,18 @@ fn test_list_shows_unmanaged_hooks() {
     let _dir = setup_test_env();
 
     // Manually add hook to settings.json (not via install)
-    let settings = settings::read_settings().expect("Failed to read settings");
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let settings = settings::read_settings(&Scope::User).expect("Failed to read settings");
+    let handler = HookHandler::Command(CommandHandler {
         command: "/unmanaged/hook.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
     let updated = settings::add_hook(settings, HookEvent::SessionStart, handler, None);
-    settings::write_settings_atomic(updated).expect("Failed to write settings");
+    settings::write_settings_atomic(&Scope::User, updated).expect("Failed to write settings");
 
     // List should show hook as unmanaged
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1, "Should have 1 hook");
     assert!(!entries[0].managed, "Hook should be unmanaged");
     assert_eq!(entries[0].event, HookEvent::SessionStart);
@@ -153,28 +150,33 @@ fn test_install_multiple_hooks() {
     let _dir = setup_test_env();
 
     // Install Stop hook
-    let stop_handler = HookHandler {
-        r#type: "command".to_string(),
+    let stop_handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, stop_handler, None, "test").expect("Stop install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, stop_handler, None, "test")
+        .expect("Stop install should succeed");
 
     // Install SessionStart hook
-    let start_handler = HookHandler {
-        r#type: "command".to_string(),
+    let start_handler = HookHandler::Command(CommandHandler {
         command: "/path/to/start.sh".to_string(),
         timeout: Some(300),
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::SessionStart, start_handler, None, "test")
-        .expect("SessionStart install should succeed");
+    });
+    install(
+        Scope::User,
+        HookEvent::SessionStart,
+        start_handler,
+        None,
+        "test",
+    )
+    .expect("SessionStart install should succeed");
 
     // List should show both hooks
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 2, "Should have 2 hooks");
     assert!(
         entries.iter().all(|e| e.managed),
@@ -193,33 +195,38 @@ fn test_uninstall_preserves_other_hooks() {
     let _dir = setup_test_env();
 
     // Install two hooks
-    let stop_handler = HookHandler {
-        r#type: "command".to_string(),
+    let stop_handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, stop_handler, None, "test").expect("Stop install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, stop_handler, None, "test")
+        .expect("Stop install should succeed");
 
-    let start_handler = HookHandler {
-        r#type: "command".to_string(),
+    let start_handler = HookHandler::Command(CommandHandler {
         command: "/path/to/start.sh".to_string(),
         timeout: Some(300),
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::SessionStart, start_handler, None, "test")
-        .expect("SessionStart install should succeed");
+    });
+    install(
+        Scope::User,
+        HookEvent::SessionStart,
+        start_handler,
+        None,
+        "test",
+    )
+    .expect("SessionStart install should succeed");
 
     // Uninstall Stop hook
-    uninstall(HookEvent::Stop, "/path/to/stop.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/stop.sh").expect("Uninstall should succeed");
 
     // List should show only SessionStart hook
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1, "Should have 1 hook remaining");
     assert_eq!(entries[0].event, HookEvent::SessionStart);
-    assert_eq!(entries[0].handler.command, "/path/to/start.sh");
+    assert_eq!(entries[0].handler.key(), "/path/to/start.sh");
 }
 
 #[test]
@@ -228,21 +235,21 @@ fn test_install_with_optional_fields() {
     let _dir = setup_test_env();
 
     // Install hook with all optional fields
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/async.sh".to_string(),
         timeout: Some(900),
         r#async: Some(true),
         status_message: Some("Running...".to_string()),
-    };
+    });
 
-    install(HookEvent::PostToolUse, handler, None, "test").expect("Install should succeed");
+    install(Scope::User, HookEvent::PostToolUse, handler, None, "test")
+        .expect("Install should succeed");
 
     // List and verify optional fields are preserved
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
-    assert_eq!(entries[0].handler.timeout, Some(900));
-    assert_eq!(entries[0].handler.r#async, Some(true));
+    assert_eq!(entries[0].handler.timeout(), Some(900));
+    assert_eq!(entries[0].handler.is_async(), Some(true));
 }
 
 #[test]
@@ -251,18 +258,24 @@ fn test_metadata_is_preserved() {
     let _dir = setup_test_env();
 
     // Install hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
-    install(HookEvent::Stop, handler, None, "test-installer").expect("Install should succeed");
+    install(
+        Scope::User,
+        HookEvent::Stop,
+        handler,
+        None,
+        "test-installer",
+    )
+    .expect("Install should succeed");
 
     // List and verify metadata
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
 
     let metadata = entries[0].metadata.as_ref().expect("Should have metadata");
@@ -276,30 +289,29 @@ fn test_hook_in_registry_but_not_settings() {
     let _dir = setup_test_env();
 
     // Install hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Manually remove from settings.json (simulate user deletion)
-    let settings = settings::read_settings().expect("Failed to read settings");
+    let settings = settings::read_settings(&Scope::User).expect("Failed to read settings");
     let updated = settings::remove_hook(settings, HookEvent::Stop, "/path/to/test.sh");
-    settings::write_settings_atomic(updated).expect("Failed to write settings");
+    settings::write_settings_atomic(&Scope::User, updated).expect("Failed to write settings");
 
     // Uninstall should still succeed (cleans up registry)
-    let result = uninstall(HookEvent::Stop, "/path/to/test.sh");
+    let result = uninstall(Scope::User, HookEvent::Stop, "/path/to/test.sh");
     assert!(
         result.is_ok(),
         "Uninstall should succeed even if hook not in settings"
     );
 
     // List should be empty
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 0, "Should have no hooks");
 }
 
@@ -309,43 +321,43 @@ fn test_different_commands_same_event() {
     let _dir = setup_test_env();
 
     // Install two hooks for same event but different commands
-    let handler1 = HookHandler {
-        r#type: "command".to_string(),
+    let handler1 = HookHandler::Command(CommandHandler {
         command: "/path/to/stop1.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler1, None, "test").expect("First install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler1, None, "test")
+        .expect("First install should succeed");
 
-    let handler2 = HookHandler {
-        r#type: "command".to_string(),
+    let handler2 = HookHandler::Command(CommandHandler {
         command: "/path/to/stop2.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler2, None, "test").expect("Second install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler2, None, "test")
+        .expect("Second install should succeed");
 
     // List should show both hooks
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 2, "Should have 2 hooks");
 
     // Both should be for Stop event
     assert!(entries.iter().all(|e| e.event == HookEvent::Stop));
 
     // Commands should be different
-    let commands: Vec<&str> = entries.iter().map(|e| e.handler.command.as_str()).collect();
+    let commands: Vec<&str> = entries.iter().map(|e| e.handler.key()).collect();
     assert!(commands.contains(&"/path/to/stop1.sh"));
     assert!(commands.contains(&"/path/to/stop2.sh"));
 
     // Uninstall first hook
-    uninstall(HookEvent::Stop, "/path/to/stop1.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/stop1.sh").expect("Uninstall should succeed");
 
     // List should show only second hook
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1, "Should have 1 hook remaining");
-    assert_eq!(entries[0].handler.command, "/path/to/stop2.sh");
+    assert_eq!(entries[0].handler.key(), "/path/to/stop2.sh");
 }
 
 #[test]
@@ -354,25 +366,849 @@ fn test_install_with_matcher() {
     let _dir = setup_test_env();
 
     // Install PreToolUse hook with Bash matcher
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/pre-bash.sh".to_string(),
         timeout: Some(10),
         r#async: None,
         status_message: None,
-    };
+    });
 
     install(
+        Scope::User,
         HookEvent::PreToolUse,
         handler,
-        Some("Bash".to_string()),
+        Some(Matcher::tool("Bash")),
         "test",
     )
     .expect("Install should succeed");
 
     // List should show the hook
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
     assert_eq!(entries[0].event, HookEvent::PreToolUse);
-    assert_eq!(entries[0].handler.command, "/path/to/pre-bash.sh");
+    assert_eq!(entries[0].handler.key(), "/path/to/pre-bash.sh");
+}
+
+#[test]
+#[serial(home)]
+fn test_adopt_unmanaged_hook() {
+    let dir = setup_test_env();
+
+    // Hook added by hand, with a matcher
+    let settings = serde_json::json!({
+        "hooks": {
+            "PreToolUse": [
+                {
+                    "matcher": "Bash",
+                    "hooks": [{ "type": "command", "command": "/manual/check.sh", "timeout": 30 }]
+                }
+            ]
+        }
+    });
+    fs::write(
+        dir.path().join(".claude/settings.json"),
+        serde_json::to_string_pretty(&settings).expect("Serialize failed"),
+    )
+    .expect("Write failed");
+
+    let missing = adopt(
+        Scope::User,
+        HookEvent::Stop,
+        "/manual/check.sh",
+        "test",
+        HookMetadata::default(),
+    );
+    assert!(matches!(
+        missing,
+        Err(Error::Hook(HookError::NotFound { .. }))
+    ));
+
+    adopt(
+        Scope::User,
+        HookEvent::PreToolUse,
+        "/manual/check.sh",
+        "test",
+        HookMetadata {
+            description: Some("Bash guard".to_string()),
+            reason: None,
+            optional: Some(true),
+        },
+    )
+    .expect("Adopt should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
+    assert!(entries[0].managed);
+    assert_eq!(
+        entries[0]
+            .metadata
+            .as_ref()
+            .map(|m| m.installed_by.as_str()),
+        Some("test")
+    );
+    let metadata = entries[0]
+        .metadata
+        .as_ref()
+        .expect("Managed hook has metadata");
+    assert_eq!(metadata.description.as_deref(), Some("Bash guard"));
+    assert_eq!(metadata.optional, Some(true));
+
+    let registry = registry::read_registry().expect("Registry should read");
+    assert_eq!(registry[0].matcher, Some(Matcher::tool("Bash")));
+    assert_eq!(registry[0].timeout, Some(30));
+
+    // Adopting twice fails; the adopted hook can now be uninstalled
+    assert!(adopt(
+        Scope::User,
+        HookEvent::PreToolUse,
+        "/manual/check.sh",
+        "test",
+        HookMetadata::default()
+    )
+    .is_err());
+    uninstall(Scope::User, HookEvent::PreToolUse, "/manual/check.sh")
+        .expect("Uninstall should succeed");
+    assert!(list(Scope::User).expect("List should succeed").is_empty());
+}
+
+#[test]
+#[serial(home)]
+fn test_disable_enable_preserves_handler() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+
+    let handler = HookHandler::Command(CommandHandler {
+        command: "/path/to/check.sh".to_string(),
+        timeout: Some(30),
+        r#async: None,
+        status_message: Some("Checking...".to_string()),
+    });
+    install(
+        Scope::User,
+        HookEvent::PreToolUse,
+        handler,
+        Some(Matcher::tool("Bash")),
+        "test",
+    )
+    .expect("Install should succeed");
+
+    // A field this crate does not model, added by hand after install
+    let mut value: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&settings_path).expect("Read failed"))
+            .expect("Parse failed");
+    value["hooks"]["PreToolUse"][0]["hooks"][0]["custom"] = serde_json::json!(true);
+    fs::write(&settings_path, value.to_string()).expect("Write failed");
+    let original = value["hooks"]["PreToolUse"][0].clone();
+
+    disable(Scope::User, HookEvent::PreToolUse, "/path/to/check.sh")
+        .expect("Disable should succeed");
+    assert!(list(Scope::User).expect("List should succeed").is_empty());
+    let registry = registry::read_registry().expect("Registry should read");
+    assert!(!registry[0].enabled);
+    assert!(registry[0].stashed.is_some());
+
+    // Idempotent, and the disabled hook still blocks a duplicate install
+    disable(Scope::User, HookEvent::PreToolUse, "/path/to/check.sh")
+        .expect("Second disable should succeed");
+    let duplicate = HookHandler::Command(CommandHandler {
+        command: "/path/to/check.sh".to_string(),
+        timeout: None,
+        r#async: None,
+        status_message: None,
+    });
+    assert!(install(Scope::User, HookEvent::PreToolUse, duplicate, None, "test").is_err());
+
+    enable(Scope::User, HookEvent::PreToolUse, "/path/to/check.sh").expect("Enable should succeed");
+    let restored = read_settings_value(&settings_path);
+    assert_eq!(restored["hooks"]["PreToolUse"][0], original);
+    let entries = list(Scope::User).expect("List should succeed");
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].managed);
+    assert!(registry::read_registry().expect("Registry should read")[0].enabled);
+
+    assert!(disable(Scope::User, HookEvent::Stop, "/path/to/check.sh").is_err());
+}
+
+/// Runs `f` with registry writes failing on this thread
+fn with_failing_registry_writes<T>(f: impl FnOnce() -> T) -> T {
+    registry::FAIL_WRITES.with(|fail| fail.set(true));
+    let result = f();
+    registry::FAIL_WRITES.with(|fail| fail.set(false));
+    result
+}
+
+#[test]
+#[serial(home)]
+fn test_disable_and_enable_roll_back_when_registry_write_fails() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let handler = HookHandler::Command(CommandHandler {
+        command: "/path/to/stop.sh".to_string(),
+        timeout: None,
+        r#async: None,
+        status_message: None,
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    let enabled = read_settings_value(&settings_path);
+
+    let result =
+        with_failing_registry_writes(|| disable(Scope::User, HookEvent::Stop, "/path/to/stop.sh"));
+    assert!(matches!(result, Err(Error::Registry(_))), "{:?}", result);
+    assert_eq!(read_settings_value(&settings_path), enabled);
+    assert!(registry::read_registry().expect("Registry should read")[0].enabled);
+
+    disable(Scope::User, HookEvent::Stop, "/path/to/stop.sh").expect("Disable should succeed");
+    let disabled = read_settings_value(&settings_path);
+    let result =
+        with_failing_registry_writes(|| enable(Scope::User, HookEvent::Stop, "/path/to/stop.sh"));
+    assert!(matches!(result, Err(Error::Registry(_))), "{:?}", result);
+    assert_eq!(read_settings_value(&settings_path), disabled);
+    assert!(!registry::read_registry().expect("Registry should read")[0].enabled);
+
+    // A retry adds the hook once
+    enable(Scope::User, HookEvent::Stop, "/path/to/stop.sh").expect("Enable should succeed");
+    assert_eq!(read_settings_value(&settings_path), enabled);
+    assert_eq!(list(Scope::User).expect("List should succeed").len(), 1);
+}
+
+#[test]
+#[serial(home)]
+fn test_update_and_upsert() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let handler = |command: &str, timeout| {
+        HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout,
+            r#async: None,
+            status_message: None,
+        })
+    };
+
+    // Upsert installs, then updates in place
+    upsert(
+        Scope::User,
+        HookEvent::Stop,
+        handler("/a.sh", None),
+        Some(Matcher::tool("m")),
+        "test",
+    )
+    .expect("Upsert install should succeed");
+    upsert(
+        Scope::User,
+        HookEvent::Stop,
+        handler("/a.sh", Some(30)),
+        None,
+        "other",
+    )
+    .expect("Upsert update should succeed");
+    let value = read_settings_value(&settings_path);
+    assert_eq!(value["hooks"]["Stop"].as_array().map(Vec::len), Some(1));
+    assert_eq!(value["hooks"]["Stop"][0]["matcher"], "m");
+    assert_eq!(value["hooks"]["Stop"][0]["hooks"][0]["timeout"], 30);
+
+    // Renaming the command moves the registry entry with it
+    install(
+        Scope::User,
+        HookEvent::Stop,
+        handler("/b.sh", None),
+        None,
+        "test",
+    )
+    .expect("Install should succeed");
+    assert!(update(
+        Scope::User,
+        HookEvent::Stop,
+        "/a.sh",
+        handler("/b.sh", None)
+    )
+    .is_err());
+    update(
+        Scope::User,
+        HookEvent::Stop,
+        "/a.sh",
+        handler("/c.sh", Some(5)),
+    )
+    .expect("Update should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.managed));
+    let renamed = entries
+        .iter()
+        .find(|e| e.handler.key() == "/c.sh")
+        .expect("Renamed hook should be listed");
+    assert_eq!(renamed.handler.timeout(), Some(5));
+    assert_eq!(
+        renamed.metadata.as_ref().map(|m| m.installed_by.as_str()),
+        Some("test")
+    );
+
+    assert!(update(
+        Scope::User,
+        HookEvent::Stop,
+        "/a.sh",
+        handler("/d.sh", None)
+    )
+    .is_err());
+
+    // An enabled hook that was removed from settings by hand is not re-added
+    let value = settings::remove_hook(
+        read_settings_value(&settings_path),
+        HookEvent::Stop,
+        "/c.sh",
+    );
+    settings::write_settings_atomic(&Scope::User, value.clone()).expect("Write should succeed");
+    let result = update(
+        Scope::User,
+        HookEvent::Stop,
+        "/c.sh",
+        handler("/c.sh", Some(10)),
+    );
+    assert!(
+        matches!(result, Err(Error::Hook(HookError::NotFound { .. }))),
+        "{:?}",
+        result
+    );
+    assert_eq!(read_settings_value(&settings_path), value);
+}
+
+fn read_settings_value(path: &std::path::Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).expect("Read failed")).expect("Parse failed")
+}
+
+#[test]
+#[serial(home)]
+fn test_uninstall_all_by_removes_one_installers_hooks() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let handler = |command: &str| {
+        HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        })
+    };
+    for (event, command, owner) in [
+        (HookEvent::Stop, "/acd/stop.sh", "acd"),
+        (HookEvent::SessionStart, "/acd/start.sh", "acd"),
+        (HookEvent::PreCompact, "/acd/compact.sh", "acd"),
+        (HookEvent::Stop, "/other/stop.sh", "other"),
+    ] {
+        install(Scope::User, event, handler(command), None, owner).expect("Install should succeed");
+    }
+    disable(Scope::User, HookEvent::PreCompact, "/acd/compact.sh").expect("Disable should succeed");
+    let project = tempdir().expect("Failed to create project dir");
+    let project_scope = Scope::Project(project.path().to_path_buf());
+    install(
+        project_scope.clone(),
+        HookEvent::Stop,
+        handler("/acd/stop.sh"),
+        None,
+        "acd",
+    )
+    .expect("Project install should succeed");
+
+    let backups_before = list_backups(Scope::User).expect("List backups").len();
+    let removed = uninstall_all_by(Scope::User, "acd").expect("Uninstall all should succeed");
+    let commands: Vec<&str> = removed.iter().map(|e| e.command.as_str()).collect();
+    assert_eq!(
+        commands,
+        vec!["/acd/stop.sh", "/acd/start.sh", "/acd/compact.sh"]
+    );
+    // One settings write for all of them
+    assert_eq!(
+        list_backups(Scope::User).expect("List backups").len(),
+        backups_before + 1
+    );
+
+    let entries = list(Scope::User).expect("List should succeed");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].handler.key(), "/other/stop.sh");
+    let settings = read_settings_value(&settings_path);
+    assert_eq!(settings["cleanupPeriodDays"], 7);
+
+    // Other scopes keep their hooks, and a second call has nothing to do
+    let registry = registry::read_registry().expect("Read registry");
+    assert_eq!(registry.len(), 2);
+    assert!(registry.iter().any(|e| e.in_scope(&project_scope)));
+    assert!(uninstall_all_by(Scope::User, "acd")
+        .expect("Uninstall all should succeed")
+        .is_empty());
+}
+
+#[test]
+#[serial(home)]
+fn test_list_filtered() {
+    let dir = setup_test_env();
+    let handler = |command: &str| {
+        HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        })
+    };
+    install(
+        Scope::User,
+        HookEvent::Stop,
+        handler("/acd/stop.sh"),
+        None,
+        "acd",
+    )
+    .expect("Install should succeed");
+    install(
+        Scope::User,
+        HookEvent::SessionStart,
+        handler("/acd/start.sh"),
+        None,
+        "acd",
+    )
+    .expect("Install should succeed");
+    install(
+        Scope::User,
+        HookEvent::Stop,
+        handler("/other/stop.sh"),
+        None,
+        "other",
+    )
+    .expect("Install should succeed");
+    let settings = settings::read_settings(&Scope::User).expect("Failed to read settings");
+    let settings = settings::add_hook(settings, HookEvent::Stop, handler("/manual.sh"), None);
+    settings::write_settings_atomic(&Scope::User, settings).expect("Failed to write settings");
+
+    let commands = |filter: ListFilter| -> Vec<String> {
+        list_filtered(filter)
+            .expect("List should succeed")
+            .iter()
+            .map(|e| e.handler.key().to_string())
+            .collect()
+    };
+    assert_eq!(commands(ListFilter::default()).len(), 4);
+    assert_eq!(
+        commands(ListFilter {
+            installed_by: Some("acd".to_string()),
+            ..Default::default()
+        }),
+        vec!["/acd/start.sh", "/acd/stop.sh"]
+    );
+    assert_eq!(
+        commands(ListFilter {
+            event: Some(HookEvent::Stop),
+            managed_only: true,
+            ..Default::default()
+        }),
+        vec!["/acd/stop.sh", "/other/stop.sh"]
+    );
+    assert_eq!(
+        commands(ListFilter {
+            event: Some(HookEvent::Stop),
+            ..Default::default()
+        })
+        .len(),
+        3
+    );
+
+    // With no matching managed hooks, an unreadable settings file is never read
+    fs::write(dir.path().join(".claude/settings.json"), "{ broken").expect("Write failed");
+    assert!(commands(ListFilter {
+        installed_by: Some("nobody".to_string()),
+        ..Default::default()
+    })
+    .is_empty());
+    assert!(list_filtered(ListFilter::default()).is_err());
+}
+
+#[test]
+#[serial(home)]
+fn test_concurrent_installs_keep_every_hook() {
+    let dir = setup_test_env();
+
+    let threads: Vec<_> = (0..8)
+        .map(|i| {
+            std::thread::spawn(move || {
+                install(
+                    Scope::User,
+                    HookEvent::Stop,
+                    HookHandler::Command(CommandHandler {
+                        command: format!("/path/to/hook-{}.sh", i),
+                        timeout: None,
+                        r#async: None,
+                        status_message: None,
+                    }),
+                    None,
+                    "test",
+                )
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread
+            .join()
+            .expect("Thread panicked")
+            .expect("Install should succeed");
+    }
+
+    let settings = read_settings_value(&dir.path().join(".claude/settings.json"));
+    assert_eq!(settings["hooks"]["Stop"].as_array().map(Vec::len), Some(8));
+    assert_eq!(registry::read_registry().expect("Read registry").len(), 8);
+    assert!(dir.path().join(".claude/settings.json.lock").exists());
+}
+
+#[test]
+#[serial(home)]
+fn test_prompt_hooks_and_unknown_types() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+
+    // A handler type this crate does not model, added by hand
+    let webhook = serde_json::json!({
+        "type": "webhook",
+        "url": "https://example.com/hook",
+        "headers": { "X-Token": "abc" }
+    });
+    fs::write(
+        &settings_path,
+        serde_json::json!({ "hooks": { "Stop": [{ "hooks": [webhook.clone()] }] } }).to_string(),
+    )
+    .expect("Write failed");
+
+    let prompt = HookHandler::Prompt(PromptHandler {
+        prompt: "Did the assistant finish the task? $ARGUMENTS".to_string(),
+        model: None,
+        timeout: Some(30),
+        status_message: None,
+    });
+    install(Scope::User, HookEvent::Stop, prompt.clone(), None, "test")
+        .expect("Install should succeed");
+
+    let entries = list(Scope::User).expect("List should succeed");
+    assert_eq!(entries.len(), 2);
+    assert!(!entries[0].managed);
+    assert_eq!(entries[0].handler, HookHandler::Other(webhook.clone()));
+    assert!(entries[1].managed);
+    assert_eq!(entries[1].handler, prompt);
+
+    let registry = registry::read_registry().expect("Read registry failed");
+    assert_eq!(registry[0].r#type, "prompt");
+    assert_eq!(registry[0].handler(), prompt);
+
+    // Rewriting settings keeps the unknown handler intact
+    uninstall(Scope::User, HookEvent::Stop, prompt.key()).expect("Uninstall should succeed");
+    let settings = read_settings_value(&settings_path);
+    assert_eq!(settings["hooks"]["Stop"][0]["hooks"][0], webhook);
+    assert_eq!(settings["hooks"]["Stop"].as_array().map(Vec::len), Some(1));
+
+    // Unknown types cannot be installed: there is nothing to track them by
+    assert!(matches!(
+        install(
+            Scope::User,
+            HookEvent::Stop,
+            HookHandler::Other(webhook),
+            None,
+            "test"
+        ),
+        Err(Error::Hook(HookError::InvalidHandler(_)))
+    ));
+}
+
+#[test]
+#[serial(home)]
+fn test_validate_settings_reports_manual_edits() {
+    let dir = setup_test_env();
+
+    install(
+        Scope::User,
+        HookEvent::Stop,
+        HookHandler::Command(CommandHandler {
+            command: "/path/to/stop.sh".to_string(),
+            timeout: Some(600),
+            r#async: None,
+            status_message: None,
+        }),
+        None,
+        "test",
+    )
+    .expect("Install should succeed");
+    assert!(validate_settings(Scope::User)
+        .expect("Validate should succeed")
+        .is_empty());
+
+    // Hand-edited entry with a typo in the event and a bad timeout
+    let settings_path = dir.path().join(".claude/settings.json");
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&settings_path).expect("Read failed"))
+            .expect("Parse failed");
+    settings["hooks"]["stop"] = serde_json::json!([
+        { "hooks": [{ "type": "command", "command": "x.sh", "timeout": "10" }] }
+    ]);
+    fs::write(&settings_path, settings.to_string()).expect("Write failed");
+
+    let diagnostics = validate_settings(Scope::User).expect("Validate should succeed");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+    assert_eq!(diagnostics[0].pointer, "/hooks/stop");
+
+    fs::write(&settings_path, "{ not json").expect("Write failed");
+    assert!(matches!(
+        validate_settings(Scope::User),
+        Err(Error::Settings(SettingsError::Parse(_)))
+    ));
+}
+
+#[test]
+#[serial(home)]
+fn test_repair_reconciles_registry_with_settings() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let handler = |command: &str, timeout| {
+        HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout,
+            r#async: None,
+            status_message: None,
+        })
+    };
+    for command in ["/gone.sh", "/edited.sh", "/off.sh"] {
+        install(
+            Scope::User,
+            HookEvent::Stop,
+            handler(command, Some(10)),
+            None,
+            "test",
+        )
+        .expect("Install should succeed");
+    }
+    disable(Scope::User, HookEvent::Stop, "/off.sh").expect("Disable should succeed");
+
+    // Project scope whose directory is deleted afterwards
+    let project = tempdir().expect("Failed to create project directory");
+    let project_root = project.path().to_path_buf();
+    install(
+        Scope::Project(project_root.clone()),
+        HookEvent::Stop,
+        handler("/project.sh", None),
+        None,
+        "test",
+    )
+    .expect("Install should succeed");
+    drop(project);
+
+    // Manual edits: one hook deleted, one re-timed
+    let mut settings = read_settings_value(&settings_path);
+    let groups = settings["hooks"]["Stop"]
+        .as_array_mut()
+        .expect("Stop groups");
+    groups.retain(|g| g["hooks"][0]["command"] != "/gone.sh");
+    groups[0]["hooks"][0]["timeout"] = serde_json::json!(99);
+    fs::write(&settings_path, settings.to_string()).expect("Write failed");
+
+    let report = repair(Scope::User).expect("Repair should succeed");
+    let commands = |entries: &[RegistryEntry]| {
+        entries
+            .iter()
+            .map(|e| e.command.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(commands(&report.removed), vec!["/gone.sh"]);
+    assert_eq!(commands(&report.updated), vec!["/edited.sh"]);
+    assert_eq!(report.updated[0].timeout, Some(99));
+    assert_eq!(commands(&report.orphaned), vec!["/project.sh"]);
+
+    // Disabled and orphaned entries are kept; a second run changes nothing
+    let registry = registry::read_registry().expect("Registry should read");
+    assert_eq!(registry.len(), 3);
+    assert!(registry
+        .iter()
+        .any(|e| e.command == "/off.sh" && !e.enabled));
+    let again = repair(Scope::User).expect("Repair should succeed");
+    assert!(again.removed.is_empty() && again.updated.is_empty());
+    assert!(!again.is_clean());
+}
+
+#[test]
+#[serial(home)]
+fn test_check_conflicts_between_installers() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let handler = |command: &str| {
+        HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        })
+    };
+
+    // A hand-written Bash hook, then a managed one on every tool
+    fs::write(
+        &settings_path,
+        serde_json::json!({
+            "hooks": {
+                "PreToolUse": [{ "matcher": "Bash", "hooks": [{ "type": "command", "command": "/manual.sh" }] }]
+            }
+        })
+        .to_string(),
+    )
+    .expect("Write failed");
+    assert!(check_conflicts(Scope::User)
+        .expect("Check should succeed")
+        .is_empty());
+
+    let planned = vec![
+        HookSpec {
+            event: HookEvent::PreToolUse,
+            matcher: Some(Matcher::tools(["Edit", "Write"])),
+            handler: handler("/edit.sh"),
+        },
+        HookSpec {
+            event: HookEvent::PreToolUse,
+            matcher: None,
+            handler: handler("/all.sh"),
+        },
+    ];
+    let conflicts =
+        check_install_conflicts(Scope::User, &planned, "tool-a").expect("Check should succeed");
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].first.command, "/manual.sh");
+    assert_eq!(conflicts[0].second.command, "/all.sh");
+
+    install_all(Scope::User, planned, "tool-a").expect("Install should succeed");
+    let conflicts = check_conflicts(Scope::User).expect("Check should succeed");
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Overlap);
+    assert_eq!(conflicts[0].second.installed_by.as_deref(), Some("tool-a"));
+}
+
+#[cfg(feature = "watch")]
+#[test]
+#[serial(home)]
+fn test_watch_reports_removed_hooks() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    install(
+        Scope::User,
+        HookEvent::Stop,
+        HookHandler::Command(CommandHandler {
+            command: "/path/to/stop.sh".to_string(),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        }),
+        None,
+        "test",
+    )
+    .expect("Install should succeed");
+
+    let (tx, rx) = mpsc::channel();
+    let watcher = watch(Scope::User, move |change| {
+        let _ = tx.send(change);
+    })
+    .expect("Watch should start");
+    assert_eq!(watcher.path(), Scope::User.settings_path());
+
+    // Remove the hook by hand
+    fs::write(&settings_path, r#"{ "hooks": {}, "model": "opus" }"#).expect("Write failed");
+    let change = loop {
+        let change = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Change should be reported");
+        if change.settings["model"] == "opus" {
+            break change;
+        }
+    };
+    assert_eq!(change.missing.len(), 1);
+    assert_eq!(change.missing[0].command, "/path/to/stop.sh");
+
+    drop(watcher);
+}
+
+#[test]
+#[serial(home)]
+fn test_structured_matchers() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let handler = |command: &str| {
+        HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        })
+    };
+
+    // Invalid regexes are rejected before anything is written
+    let before = fs::read_to_string(&settings_path).expect("Read failed");
+    let result = install(
+        Scope::User,
+        HookEvent::PreToolUse,
+        handler("/bad.sh"),
+        Some(Matcher::Regex("Edit(".to_string())),
+        "test",
+    );
+    assert!(matches!(
+        result,
+        Err(Error::Hook(HookError::InvalidMatcher { .. }))
+    ));
+    assert_eq!(
+        fs::read_to_string(&settings_path).expect("Read failed"),
+        before
+    );
+
+    // Settings get the plain string, the registry the structured form
+    install(
+        Scope::User,
+        HookEvent::PreToolUse,
+        handler("/edit.sh"),
+        Some(Matcher::tools(["Edit", "Write"])),
+        "test",
+    )
+    .expect("Install should succeed");
+    let settings = read_settings_value(&settings_path);
+    assert_eq!(settings["hooks"]["PreToolUse"][0]["matcher"], "Edit|Write");
+    // The registry file starts with a comment line
+    let content = fs::read_to_string(registry::registry_path()).expect("Read registry failed");
+    let (header, body) = content.split_once('\n').expect("Registry header");
+    let mut raw: serde_json::Value = serde_json::from_str(body).expect("Parse registry failed");
+    assert_eq!(
+        raw["hooks"][0]["matcher"],
+        serde_json::json!({ "kind": "tools", "value": ["Edit", "Write"] })
+    );
+
+    // Registry entries with a raw matcher string still load
+    raw["hooks"][0]["matcher"] = serde_json::json!("Edit|Write");
+    fs::write(registry::registry_path(), format!("{}\n{}", header, raw)).expect("Write failed");
+    let registry = registry::read_registry().expect("Registry should read");
+    assert_eq!(registry[0].matcher, Some(Matcher::tools(["Edit", "Write"])));
+    assert!(repair(Scope::User)
+        .expect("Repair should succeed")
+        .is_clean());
+}
+
+#[test]
+#[serial(home)]
+fn test_install_preserves_settings_formatting() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let original = "{\n    \"model\": \"opus\",\n    \"env\": { \"Z\": \"1\", \"A\": \"2\" },\n    \"hooks\": {}\n}\n";
+    fs::write(&settings_path, original).expect("Write failed");
+    let handler = HookHandler::Command(CommandHandler {
+        command: "/stop.sh".to_string(),
+        timeout: None,
+        r#async: None,
+        status_message: None,
+    });
+
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    let content = fs::read_to_string(&settings_path).expect("Read failed");
+    assert!(content.starts_with("{\n    \"model\": \"opus\",\n    \"env\": { \"Z\": \"1\", \"A\": \"2\" },\n    \"hooks\": {\n        \"Stop\": ["));
+    assert!(content.ends_with("}\n"));
+
+    // Removing the hook leaves only the emptied event array behind
+    uninstall(Scope::User, HookEvent::Stop, "/stop.sh").expect("Uninstall should succeed");
+    assert_eq!(
+        fs::read_to_string(&settings_path).expect("Read failed"),
+        original.replace("\"hooks\": {}", "\"hooks\": {\n        \"Stop\": []\n    }")
+    );
 }
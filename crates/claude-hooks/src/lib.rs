@@ -1,137 +1,146 @@
 //! Programmatic management of Claude Code hooks
 //!
 //! This crate provides a library API to install, uninstall, and list hooks
-//! in Claude Code's settings.json with atomic safety guarantees and
+//! in Claude Code's settings files with atomic safety guarantees and
 //! ownership tracking.
 //!
 //! # Platform Support
 //!
 //! - macOS: Supported
 //! - Linux: Supported
-//! - Windows: Not supported in v0.1 (deferred to v0.2+)
+//! - Windows: Supported (`%USERPROFILE%\.claude\settings.json`)
 //!
-//! # Scope Limitations (v0.1)
+//! # Scopes
 //!
-//! - User scope only (`~/.claude/settings.json`)
-//! - No multi-scope support (user/project/local)
-//! - Library-only (no CLI binary)
+//! Every operation takes a [`Scope`] selecting the settings file:
+//!
+//! - [`Scope::User`] — `~/.claude/settings.json`
+//! - [`Scope::Project`] — `<root>/.claude/settings.json`
+//! - [`Scope::Local`] — `<root>/.claude/settings.local.json`
+//!
+//! The registry records the scope (and project root) of each installed hook,
+//! so the same command can be managed independently in several scopes.
+//!
+//! # Concurrency
+//!
+//! Operations that change settings hold advisory locks on
+//! `settings.json.lock` (next to the settings file) and on the registry's
+//! lockfile from first read to last write, so concurrent installers do not
+//! overwrite each other's changes. A lock held elsewhere for more than ten
+//! seconds fails with `SettingsError::Locked` or `RegistryError::Locked`.
+//!
+//! # Command-line tool
+//!
+//! The `cli` feature builds a `claude-hooks` binary with `install`,
+//! `uninstall`, `uninstall-all`, `disable`, `enable`, `list`, `adopt`, `repair`,
+//! `conflicts` and `validate` subcommands.
+//!
+//! # Handler types
+//!
+//! [`HookHandler`] models command, prompt and agent handlers. Any other
+//! type is kept as raw JSON in [`HookHandler::Other`], so rewriting settings
+//! never drops fields this crate does not know about. Prompt and agent hooks
+//! are identified by their prompt wherever other hooks use their command.
+//!
+//! # Watching settings
+//!
+//! The `watch` feature adds [`watch`], which calls back whenever a settings
+//! file changes and lists managed hooks that have disappeared from it.
 //!
 //! # Examples
 //!
 //! ```ignore
-//! use claude_hooks::{HookEvent, HookHandler, install};
+//! use claude_hooks::{CommandHandler, HookEvent, HookHandler, Scope, install};
 //!
-//! let handler = HookHandler {
-//!     r#type: "command".to_string(),
+//! let handler = HookHandler::Command(CommandHandler {
 //!     command: "/path/to/stop.sh".to_string(),
 //!     timeout: Some(600),
 //!     r#async: None,
 //!     status_message: None,
-//! };
+//! });
 //!
-//! install(HookEvent::Stop, handler, None, "acd")?;
+//! install(Scope::User, HookEvent::Stop, handler, None, "acd")?;
 //! ```
 
 #![warn(missing_docs)]
 
+mod atomic;
+mod backup;
+mod conflicts;
+mod diff;
 mod error;
+mod lock;
+mod matcher;
 mod registry;
 mod settings;
+pub mod templates;
 mod types;
+mod validate;
+#[cfg(feature = "watch")]
+mod watch;
 
 // Re-export all public types
+pub use backup::{Backup, MAX_BACKUPS};
+pub use conflicts::{Conflict, ConflictKind, ConflictingHook};
+pub use diff::{Change, ChangeKind, SettingsDiff};
 pub use error::{Error, HookError, RegistryError, Result, SettingsError};
-pub use types::{HookEvent, HookHandler, ListEntry, MatcherGroup, RegistryEntry, RegistryMetadata};
+pub use matcher::Matcher;
+pub use registry::REGISTRY_SCHEMA_VERSION;
+pub use types::{
+    CommandHandler, HookEvent, HookHandler, HookMetadata, HookSpec, ListEntry, ListFilter,
+    MatcherGroup, PromptHandler, RegistryEntry, RegistryMetadata, RepairReport, Scope,
+};
+pub use validate::{Diagnostic, DiagnosticLevel};
+#[cfg(feature = "watch")]
+pub use watch::{SettingsChange, SettingsWatcher};
 
 /// Install a hook for the specified event.
 ///
 /// # Arguments
+/// * `scope` - Settings file to install into
 /// * `event` - Hook event (Stop, PreToolUse, etc.)
 /// * `handler` - Hook handler configuration (command, timeout, etc.)
-/// * `matcher` - Optional matcher regex (e.g., "Bash" for PreToolUse hooks)
+/// * `matcher` - Optional matcher (e.g., `Matcher::tool("Bash")` for PreToolUse hooks)
 /// * `installed_by` - Free-form string identifying installer (e.g., "acd")
 ///
 /// # Errors
 /// * `HookError::AlreadyExists` - Hook already exists (in registry or settings)
+/// * `HookError::InvalidHandler` - Handler type is not command, prompt or agent
+/// * `HookError::InvalidMatcher` - Matcher is not a valid tool list or regex
 /// * `SettingsError` - Failed to read or write settings.json
 /// * `RegistryError` - Failed to read registry (write failure is logged but not returned)
 ///
 /// # Example
 /// ```ignore
-/// use claude_hooks::{HookEvent, HookHandler, install};
+/// use claude_hooks::{CommandHandler, HookEvent, HookHandler, Scope, install};
 ///
-/// let handler = HookHandler {
-///     r#type: "command".to_string(),
+/// let handler = HookHandler::Command(CommandHandler {
 ///     command: "/path/to/stop.sh".to_string(),
 ///     timeout: Some(600),
 ///     r#async: None,
 ///     status_message: None,
-/// };
+/// });
 ///
-/// install(HookEvent::Stop, handler, None, "acd")?;
+/// install(Scope::User, HookEvent::Stop, handler, None, "acd")?;
 /// ```
 pub fn install(
+    scope: Scope,
     event: HookEvent,
     handler: HookHandler,
-    matcher: Option<String>,
+    matcher: Option<Matcher>,
     installed_by: &str,
 ) -> Result<()> {
-    use chrono::Local;
-
-    // 1. Read registry
-    let registry_entries = registry::read_registry()?;
-
-    // 2. Check if hook exists in registry
-    if registry_entries
-        .iter()
-        .any(|e| e.matches(event, &handler.command))
-    {
-        return Err(HookError::AlreadyExists {
-            event,
-            command: handler.command.clone(),
-        }
-        .into());
-    }
+    let _lock = lock::acquire(&scope)?;
 
-    // 3. Read settings
-    let settings_value = settings::read_settings()?;
-
-    // 4. Check if hook exists in settings.json using list_hooks
-    let existing_hooks = settings::list_hooks(&settings_value);
-    for (hook_event, _, hook_handler) in &existing_hooks {
-        if *hook_event == event && hook_handler.command == handler.command {
-            return Err(HookError::AlreadyExists {
-                event,
-                command: handler.command.clone(),
-            }
-            .into());
-        }
-    }
-
-    // 5. Add hook to settings
-    let updated_settings =
-        settings::add_hook(settings_value, event, handler.clone(), matcher.clone());
+    // 1-5. Check for duplicates and stage the settings change
+    let (registry_entries, _, updated_settings) =
+        stage_install(&scope, event, &handler, matcher.clone())?;
 
     // 6. Write settings atomically
-    settings::write_settings_atomic(updated_settings)?;
+    settings::write_settings_atomic(&scope, updated_settings)?;
 
     // 7. Create registry entry
-    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
-    let entry = RegistryEntry {
-        event,
-        matcher,
-        r#type: handler.r#type.clone(),
-        command: handler.command.clone(),
-        timeout: handler.timeout,
-        r#async: handler.r#async,
-        scope: "user".to_string(),
-        enabled: true,
-        added_at: timestamp,
-        installed_by: installed_by.to_string(),
-        description: None,
-        reason: None,
-        optional: None,
-    };
+    let entry = registry_entry(&scope, event, &handler, matcher, installed_by);
 
     // 8. Add entry to registry
     let updated_registry = registry::add_entry(registry_entries, entry);
@@ -153,6 +162,7 @@ pub fn install(
 /// Only removes hooks installed via this crate (matched via registry).
 ///
 /// # Arguments
+/// * `scope` - Settings file the hook was installed into
 /// * `event` - Hook event
 /// * `command` - Exact command string
 ///
@@ -163,65 +173,427 @@ pub fn install(
 ///
 /// # Example
 /// ```ignore
-/// use claude_hooks::{HookEvent, uninstall};
+/// use claude_hooks::{HookEvent, Scope, uninstall};
 ///
-/// uninstall(HookEvent::Stop, "/path/to/stop.sh")?;
+/// uninstall(Scope::User, HookEvent::Stop, "/path/to/stop.sh")?;
 /// ```
-pub fn uninstall(event: HookEvent, command: &str) -> Result<()> {
-    // 1. Read registry
-    let registry_entries = registry::read_registry()?;
+pub fn uninstall(scope: Scope, event: HookEvent, command: &str) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
 
-    // 2. Check if hook exists in registry
-    if !registry_entries.iter().any(|e| e.matches(event, command)) {
-        return Err(HookError::NotManaged {
-            event,
-            command: command.to_string(),
-        }
-        .into());
+    // 1-5. Check ownership and stage the settings change
+    let (registry_entries, _, updated_settings) = stage_uninstall(&scope, event, command)?;
+
+    // 6. Write settings atomically
+    settings::write_settings_atomic(&scope, updated_settings)?;
+
+    // 7. Remove entry from registry
+    let updated_registry = registry::remove_entry(registry_entries, &scope, event, command);
+
+    // 8. Write registry (log warning on failure, don't fail operation)
+    if let Err(e) = registry::write_registry(updated_registry) {
+        log::warn!(
+            "Failed to write registry after successful settings write: {}",
+            e
+        );
+        log::warn!("Hook removed but registry dirty. May show as managed until registry fixed.");
     }
 
-    // 3. Read settings
-    let settings_value = settings::read_settings()?;
+    Ok(())
+}
 
-    // 4. Check if hook exists in settings.json using list_hooks
-    let existing_hooks = settings::list_hooks(&settings_value);
-    let hook_in_settings = existing_hooks
-        .iter()
-        .any(|(e, _, h)| *e == event && h.command == command);
+/// Uninstall every hook in the scope attributed to an installer.
+///
+/// Removes them with a single settings write and a single registry write.
+/// Disabled hooks are forgotten along with their stashed handler. Hooks
+/// other installers added, and unmanaged hooks, are left alone.
+///
+/// # Arguments
+/// * `scope` - Settings file to remove hooks from
+/// * `installed_by` - Installer name the hooks were installed with
+///
+/// # Returns
+/// The registry entries that were removed, in registry order (empty if the
+/// installer had no hooks in this scope, in which case nothing is written).
+///
+/// # Errors
+/// * `SettingsError` - Failed to read or write settings.json
+/// * `RegistryError` - Failed to read registry (write failure is logged but not returned)
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{uninstall_all_by, Scope};
+///
+/// for entry in uninstall_all_by(Scope::User, "acd")? {
+///     println!("Removed {:?} -> {}", entry.event, entry.command);
+/// }
+/// ```
+pub fn uninstall_all_by(scope: Scope, installed_by: &str) -> Result<Vec<RegistryEntry>> {
+    let _lock = lock::acquire(&scope)?;
 
-    if !hook_in_settings {
+    // 1. Split the registry into the installer's entries and the rest
+    let (removed, kept): (Vec<RegistryEntry>, Vec<RegistryEntry>) = registry::read_registry()?
+        .into_iter()
+        .partition(|e| e.in_scope(&scope) && e.installed_by == installed_by);
+    if removed.is_empty() {
+        return Ok(removed);
+    }
+
+    // 2. Remove the enabled ones from settings in one write
+    if removed.iter().any(|e| e.enabled) {
+        let settings_value = settings::read_settings(&scope)?;
+        let updated_settings = removed
+            .iter()
+            .filter(|e| e.enabled)
+            .fold(settings_value.clone(), |value, e| {
+                settings::remove_hook(value, e.event, &e.command)
+            });
+        if updated_settings != settings_value {
+            settings::write_settings_atomic(&scope, updated_settings)?;
+        }
+    }
+
+    // 3. Write registry (log warning on failure, don't fail operation)
+    if let Err(e) = registry::write_registry(kept) {
         log::warn!(
-            "Hook in registry but not in settings.json: {:?} - {}",
-            event,
-            command
+            "Failed to write registry after successful settings write: {}",
+            e
         );
-        log::warn!("Removing from registry anyway (user may have manually deleted)");
+        log::warn!("Hooks removed but registry dirty. May show as managed until registry fixed.");
     }
 
-    // 5. Remove hook from settings (if exists)
-    let updated_settings = settings::remove_hook(settings_value, event, command);
+    Ok(removed)
+}
 
-    // 6. Write settings atomically
-    settings::write_settings_atomic(updated_settings)?;
+/// Change a managed hook's handler in place.
+///
+/// Rewrites the handler within its existing matcher group with one settings
+/// write and one registry write, so there is no window where the hook is
+/// missing. The command may change; the registry entry keeps its install
+/// metadata. A disabled hook stays disabled with the new handler stashed.
+///
+/// # Errors
+/// * `HookError::NotManaged` - Hook not found in registry (not managed by us)
+/// * `HookError::NotFound` - Hook is enabled but missing from settings
+/// * `HookError::AlreadyExists` - The new command is already used on this event
+/// * `SettingsError` - Failed to read or write settings.json
+/// * `RegistryError` - Failed to read registry (write failure is logged but not returned)
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{update, HookEvent, Scope};
+///
+/// let mut handler = current_handler.clone();
+/// handler.timeout = Some(30);
+/// update(Scope::User, HookEvent::Stop, "/path/to/stop.sh", handler)?;
+/// ```
+pub fn update(scope: Scope, event: HookEvent, command: &str, handler: HookHandler) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
 
-    // 7. Remove entry from registry
-    let updated_registry = registry::remove_entry(registry_entries, event, command);
+    // 1. Find the registry entry
+    let mut registry_entries = registry::read_registry()?;
+    let enabled = managed_entry(&mut registry_entries, &scope, event, command)?.enabled;
 
-    // 8. Write registry (log warning on failure, don't fail operation)
-    if let Err(e) = registry::write_registry(updated_registry) {
+    // 2. An enabled hook must still be in settings, and a renamed command
+    //    must not collide with another hook
+    let settings_value = settings::read_settings(&scope)?;
+    if enabled && settings::find_hook_value(&settings_value, event, command).is_none() {
+        return Err(HookError::NotFound {
+            event,
+            command: command.to_string(),
+        }
+        .into());
+    }
+    if handler.key() != command {
+        let taken_in_registry = registry_entries
+            .iter()
+            .any(|e| e.in_scope(&scope) && e.matches(event, handler.key()));
+        let taken_in_settings = settings::list_hooks(&settings_value)
+            .iter()
+            .any(|(e, _, h)| *e == event && h.key() == handler.key());
+        if taken_in_registry || taken_in_settings {
+            return Err(HookError::AlreadyExists {
+                event,
+                command: handler.key().to_string(),
+            }
+            .into());
+        }
+    }
+
+    // 3. Update the registry entry
+    let entry = managed_entry(&mut registry_entries, &scope, event, command)?;
+    entry.r#type = handler.type_name().to_string();
+    entry.command = handler.key().to_string();
+    entry.timeout = handler.timeout();
+    entry.r#async = handler.is_async();
+
+    // 4. Rewrite settings (disabled hooks only update the stash)
+    if entry.enabled {
+        let updated_settings = settings::replace_hook(settings_value, event, command, handler);
+        settings::write_settings_atomic(&scope, updated_settings)?;
+    } else {
+        entry.stashed = Some(serde_json::to_value(handler).expect("handler serialization failed"));
+    }
+
+    // 5. Write registry (log warning on failure, don't fail operation)
+    if let Err(e) = registry::write_registry(registry_entries) {
         log::warn!(
             "Failed to write registry after successful settings write: {}",
             e
         );
-        log::warn!("Hook removed but registry dirty. May show as managed until registry fixed.");
+        log::warn!("Hook updated but registry stale. Reinstall if it shows as unmanaged.");
     }
 
     Ok(())
 }
 
-/// List all hooks from settings.json with management status.
+/// Update the managed hook with this command, or install it if there is none.
+///
+/// An existing hook keeps its matcher; `matcher` and `installed_by` apply
+/// only when installing.
+///
+/// # Errors
+/// Same as [`install`] and [`update`]. A hook with the same command that is
+/// in settings but not managed is reported as `HookError::AlreadyExists`.
+pub fn upsert(
+    scope: Scope,
+    event: HookEvent,
+    handler: HookHandler,
+    matcher: Option<Matcher>,
+    installed_by: &str,
+) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
+
+    let managed = registry::read_registry()?
+        .iter()
+        .any(|e| e.in_scope(&scope) && e.matches(event, handler.key()));
+    if managed {
+        let command = handler.key().to_string();
+        update(scope, event, &command, handler)
+    } else {
+        install(scope, event, handler, matcher, installed_by)
+    }
+}
+
+/// Disable a managed hook without forgetting it.
+///
+/// Removes the hook from the settings file and stashes its exact handler
+/// object in the registry, so [`enable`] can put it back unchanged. Disabled
+/// hooks do not appear in [`list`]. Disabling a disabled hook does nothing.
+/// If the stash cannot be written, the settings file is restored, so the
+/// hook is never removed without a way back.
+///
+/// # Errors
+/// * `HookError::NotManaged` - Hook not found in registry (not managed by us)
+/// * `HookError::NotFound` - Hook is enabled but missing from settings
+/// * `SettingsError` - Failed to read or write settings.json
+/// * `RegistryError` - Failed to read or write the registry
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{disable, enable, HookEvent, Scope};
+///
+/// disable(Scope::User, HookEvent::Stop, "/path/to/stop.sh")?;
+/// enable(Scope::User, HookEvent::Stop, "/path/to/stop.sh")?;
+/// ```
+pub fn disable(scope: Scope, event: HookEvent, command: &str) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
+
+    // 1. Find the registry entry
+    let mut registry_entries = registry::read_registry()?;
+    let entry = managed_entry(&mut registry_entries, &scope, event, command)?;
+    if !entry.enabled {
+        return Ok(());
+    }
+
+    // 2. Stash the handler exactly as it is in settings
+    let settings_value = settings::read_settings(&scope)?;
+    let (matcher, handler) = settings::find_hook_value(&settings_value, event, command)
+        .ok_or_else(|| HookError::NotFound {
+            event,
+            command: command.to_string(),
+        })?;
+    entry.enabled = false;
+    entry.matcher = matcher::from_group(matcher.as_deref());
+    entry.stashed = Some(handler);
+
+    // 3. Remove from settings, then record the stash, putting the settings
+    //    back if that fails
+    let updated_settings = settings::remove_hook(settings_value.clone(), event, command);
+    settings::write_settings_atomic(&scope, updated_settings)?;
+    if let Err(e) = registry::write_registry(registry_entries) {
+        if let Err(rollback) = settings::write_settings_atomic(&scope, settings_value) {
+            log::warn!(
+                "Failed to restore settings after the registry write failed: {}",
+                rollback
+            );
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Re-enable a hook disabled with [`disable`].
+///
+/// Restores the stashed handler under its original matcher. Enabling an
+/// enabled hook does nothing. If the registry cannot be updated, the
+/// settings file is restored.
+///
+/// # Errors
+/// * `HookError::NotManaged` - Hook not found in registry (not managed by us)
+/// * `SettingsError` - Failed to read or write settings.json
+/// * `RegistryError` - Failed to read or write the registry
+pub fn enable(scope: Scope, event: HookEvent, command: &str) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
+
+    // 1. Find the registry entry
+    let mut registry_entries = registry::read_registry()?;
+    let entry = managed_entry(&mut registry_entries, &scope, event, command)?;
+    if entry.enabled {
+        return Ok(());
+    }
+
+    // 2. Restore the stashed handler (rebuilt from the registry if missing)
+    let handler = match entry.stashed.take() {
+        Some(handler) => handler,
+        None => serde_json::to_value(entry.handler()).expect("handler serialization failed"),
+    };
+    entry.enabled = true;
+    let settings_value = settings::read_settings(&scope)?;
+    let updated_settings = settings::add_hook_value(
+        settings_value.clone(),
+        event,
+        handler,
+        matcher::to_group(entry.matcher.as_ref()),
+    );
+
+    // 3. Write settings, then mark enabled, putting the settings back if
+    //    that fails so a retry does not add the hook twice
+    settings::write_settings_atomic(&scope, updated_settings)?;
+    if let Err(e) = registry::write_registry(registry_entries) {
+        if let Err(rollback) = settings::write_settings_atomic(&scope, settings_value) {
+            log::warn!(
+                "Failed to restore settings after the registry write failed: {}",
+                rollback
+            );
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Compute the settings change `install` would make, without writing.
+///
+/// Runs the same checks as [`install`], so a dry run fails exactly when the
+/// real install would. Nothing is written to settings or the registry.
+///
+/// # Errors
+/// Same as [`install`].
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{install_dry_run, HookEvent, Scope};
+///
+/// let diff = install_dry_run(Scope::User, HookEvent::Stop, handler, None)?;
+/// println!("{}", diff.render());
+/// ```
+pub fn install_dry_run(
+    scope: Scope,
+    event: HookEvent,
+    handler: HookHandler,
+    matcher: Option<Matcher>,
+) -> Result<SettingsDiff> {
+    let (_, before, after) = stage_install(&scope, event, &handler, matcher)?;
+    Ok(SettingsDiff::new(scope.settings_path(), before, after))
+}
+
+/// Compute the settings change `uninstall` would make, without writing.
+///
+/// # Errors
+/// Same as [`uninstall`].
+pub fn uninstall_dry_run(scope: Scope, event: HookEvent, command: &str) -> Result<SettingsDiff> {
+    let (_, before, after) = stage_uninstall(&scope, event, command)?;
+    Ok(SettingsDiff::new(scope.settings_path(), before, after))
+}
+
+/// Take ownership of a hook that is already in the scope's settings file.
+///
+/// Creates a registry entry for the hook so it shows as managed and can be
+/// updated, disabled or uninstalled through this crate. The entry records the
+/// hook's matcher and handler as found in settings, plus the given metadata.
+/// Settings are not modified.
+///
+/// # Arguments
+/// * `scope` - Settings file the hook lives in
+/// * `event` - Hook event
+/// * `command` - Exact command string
+/// * `installed_by` - Free-form string identifying the new owner
+/// * `metadata` - Description, reason and optional flag to record
+///
+/// # Errors
+/// * `HookError::AlreadyExists` - Hook is already managed in this scope
+/// * `HookError::NotFound` - Hook is not in the settings file
+/// * `SettingsError` - Failed to read settings.json
+/// * `RegistryError` - Failed to read or write the registry
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{adopt, HookEvent, HookMetadata, Scope};
+///
+/// let metadata = HookMetadata {
+///     reason: Some("Added by hand before acd managed hooks".to_string()),
+///     ..Default::default()
+/// };
+/// adopt(Scope::User, HookEvent::Stop, "/path/to/stop.sh", "acd", metadata)?;
+/// ```
+pub fn adopt(
+    scope: Scope,
+    event: HookEvent,
+    command: &str,
+    installed_by: &str,
+    metadata: HookMetadata,
+) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
+
+    // 1. Read registry
+    let registry_entries = registry::read_registry()?;
+
+    // 2. Check if hook is already managed in this scope
+    if registry_entries
+        .iter()
+        .any(|e| e.in_scope(&scope) && e.matches(event, command))
+    {
+        return Err(HookError::AlreadyExists {
+            event,
+            command: command.to_string(),
+        }
+        .into());
+    }
+
+    // 3. Find the hook in settings
+    let settings_value = settings::read_settings(&scope)?;
+    let (matcher, handler) = settings::list_hooks(&settings_value)
+        .into_iter()
+        .find(|(e, _, h)| *e == event && h.key() == command)
+        .map(|(_, matcher, handler)| (matcher::from_group(matcher.as_deref()), handler))
+        .ok_or_else(|| HookError::NotFound {
+            event,
+            command: command.to_string(),
+        })?;
+
+    // 4. Write registry entry (the only write, so failure is returned)
+    let entry = RegistryEntry {
+        description: metadata.description,
+        reason: metadata.reason,
+        optional: metadata.optional,
+        ..registry_entry(&scope, event, &handler, matcher, installed_by)
+    };
+    registry::write_registry(registry::add_entry(registry_entries, entry))
+}
+
+/// List all hooks in the scope's settings file with management status.
 ///
 /// Returns all hooks (managed and unmanaged). Managed hooks include metadata.
+/// A hook counts as managed only if it was installed in this scope.
 ///
 /// # Errors
 /// * `SettingsError` - Failed to read or parse settings.json
@@ -229,51 +601,87 @@ pub fn uninstall(event: HookEvent, command: &str) -> Result<()> {
 ///
 /// # Example
 /// ```ignore
-/// use claude_hooks::list;
+/// use claude_hooks::{list, Scope};
 ///
-/// for entry in list()? {
+/// for entry in list(Scope::User)? {
 ///     if entry.managed {
-///         println!("Managed: {:?} - {}", entry.event, entry.handler.command);
+///         println!("Managed: {:?} - {}", entry.event, entry.handler.key());
 ///     } else {
-///         println!("Unmanaged: {:?} - {}", entry.event, entry.handler.command);
+///         println!("Unmanaged: {:?} - {}", entry.event, entry.handler.key());
 ///     }
 /// }
 /// ```
-pub fn list() -> Result<Vec<ListEntry>> {
-    // 1. Read registry
+pub fn list(scope: Scope) -> Result<Vec<ListEntry>> {
+    list_filtered(ListFilter {
+        scope,
+        ..Default::default()
+    })
+}
+
+/// List the hooks in a settings file that pass a filter.
+///
+/// Like [`list`], but only returns hooks on `filter.event`, managed hooks
+/// (`filter.managed_only`), or hooks recorded with `filter.installed_by`.
+/// When only managed hooks can match and the registry has none that could,
+/// the settings file is not read at all.
+///
+/// # Errors
+/// * `SettingsError` - Failed to read or parse settings.json
+/// * `RegistryError` - Failed to read or parse registry
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{list_filtered, ListFilter};
+///
+/// let ours = list_filtered(ListFilter {
+///     installed_by: Some("acd".to_string()),
+///     ..Default::default()
+/// })?;
+/// ```
+pub fn list_filtered(filter: ListFilter) -> Result<Vec<ListEntry>> {
+    let scope = &filter.scope;
+
+    // 1. Read registry, skipping settings if no managed hook can match
     let registry_entries = registry::read_registry()?;
+    let managed_only = filter.managed_only || filter.installed_by.is_some();
+    if managed_only
+        && !registry_entries
+            .iter()
+            .any(|e| e.in_scope(scope) && e.enabled && filter.matches(e.event, Some(e)))
+    {
+        return Ok(Vec::new());
+    }
 
     // 2. Read settings
-    let settings_value = settings::read_settings()?;
-
-    // 3. Parse hooks from settings.json using list_hooks
-    let hooks = settings::list_hooks(&settings_value);
+    let settings_value = settings::read_settings(scope)?;
 
+    // 3. Parse hooks from settings.json and keep those passing the filter
     let mut results = Vec::new();
+    for (event, _matcher, handler) in settings::list_hooks(&settings_value) {
+        if filter.event.is_some_and(|e| e != event) {
+            continue;
+        }
 
-    for (event, _matcher, handler) in hooks {
         // Check if hook exists in registry
         let registry_entry = registry_entries
             .iter()
-            .find(|e| e.matches(event, &handler.command));
-
-        let (managed, metadata) = if let Some(entry) = registry_entry {
-            let metadata = RegistryMetadata {
-                added_at: entry.added_at.clone(),
-                installed_by: entry.installed_by.clone(),
-                description: entry.description.clone(),
-                reason: entry.reason.clone(),
-                optional: entry.optional,
-            };
-            (true, Some(metadata))
-        } else {
-            (false, None)
-        };
+            .find(|e| e.in_scope(scope) && e.matches(event, handler.key()));
+        if !filter.matches(event, registry_entry) {
+            continue;
+        }
+
+        let metadata = registry_entry.map(|entry| RegistryMetadata {
+            added_at: entry.added_at.clone(),
+            installed_by: entry.installed_by.clone(),
+            description: entry.description.clone(),
+            reason: entry.reason.clone(),
+            optional: entry.optional,
+        });
 
         results.push(ListEntry {
             event,
             handler,
-            managed,
+            managed: metadata.is_some(),
             metadata,
         });
     }
@@ -281,5 +689,564 @@ pub fn list() -> Result<Vec<ListEntry>> {
     Ok(results)
 }
 
+/// Reconcile the registry with the scope's settings file.
+///
+/// The two can drift after a crash between the settings and registry writes
+/// or after manual edits. Managed entries whose hook is gone from settings
+/// are dropped, and entries whose matcher or handler fields no longer match
+/// settings are refreshed. Disabled hooks are expected to be absent and are
+/// kept. Entries whose project directory no longer exists are reported as
+/// orphans but not removed. Settings are never modified.
+///
+/// # Errors
+/// * `SettingsError` - Failed to read or parse settings.json
+/// * `RegistryError` - Failed to read or write the registry
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{repair, Scope};
+///
+/// let report = repair(Scope::User)?;
+/// for entry in &report.removed {
+///     log::info!("dropped stale hook {:?} {}", entry.event, entry.command);
+/// }
+/// ```
+pub fn repair(scope: Scope) -> Result<RepairReport> {
+    let _lock = lock::acquire(&scope)?;
+
+    // 1. Read registry and settings
+    let registry_entries = registry::read_registry()?;
+    let settings_value = settings::read_settings(&scope)?;
+    let hooks = settings::list_hooks(&settings_value);
+
+    // 2. Check each enabled entry in this scope against settings
+    let mut report = RepairReport::default();
+    let mut kept = Vec::with_capacity(registry_entries.len());
+    for mut entry in registry_entries {
+        if let Some(root) = &entry.project_root {
+            if !root.exists() {
+                report.orphaned.push(entry.clone());
+            }
+        }
+        if !entry.in_scope(&scope) || !entry.enabled {
+            kept.push(entry);
+            continue;
+        }
+
+        match hooks
+            .iter()
+            .find(|(event, _, handler)| entry.matches(*event, handler.key()))
+        {
+            None => report.removed.push(entry),
+            Some((_, matcher, handler)) => {
+                let refreshed = RegistryEntry {
+                    matcher: matcher::from_group(matcher.as_deref()),
+                    r#type: handler.type_name().to_string(),
+                    timeout: handler.timeout(),
+                    r#async: handler.is_async(),
+                    ..entry.clone()
+                };
+                if refreshed != entry {
+                    entry = refreshed;
+                    report.updated.push(entry.clone());
+                }
+                kept.push(entry);
+            }
+        }
+    }
+
+    // 3. Write registry only if something changed
+    if !report.removed.is_empty() || !report.updated.is_empty() {
+        registry::write_registry(kept)?;
+    }
+
+    Ok(report)
+}
+
+/// Report hooks in the scope's settings that conflict with each other.
+///
+/// Claude Code runs all hooks matching a tool call in parallel, so hooks from
+/// different installers on the same event with overlapping matchers run in
+/// no fixed order ([`ConflictKind::Overlap`]), and a command registered twice
+/// runs twice ([`ConflictKind::Duplicate`]). Hooks from the same installer
+/// are not reported as overlapping.
+///
+/// # Errors
+/// * `SettingsError` - Failed to read or parse settings.json
+/// * `RegistryError` - Failed to read or parse registry
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{check_conflicts, Scope};
+///
+/// for conflict in check_conflicts(Scope::User)? {
+///     eprintln!("warning: {}", conflict);
+/// }
+/// ```
+pub fn check_conflicts(scope: Scope) -> Result<Vec<Conflict>> {
+    Ok(conflicts::find_conflicts(&existing_hooks(&scope)?, &[]))
+}
+
+/// Report conflicts the given hooks would introduce if installed.
+///
+/// Intended for installers to call before writing. Only conflicts involving
+/// one of `specs` are returned; a spec identical to an installed hook is not
+/// reported, since [`install_all`] rejects it with `HookError::AlreadyExists`.
+///
+/// # Arguments
+/// * `scope` - Settings file the hooks would be installed into
+/// * `specs` - Hooks about to be installed
+/// * `installed_by` - Installer the hooks would be recorded under
+///
+/// # Errors
+/// Same as [`check_conflicts`].
+pub fn check_install_conflicts(
+    scope: Scope,
+    specs: &[HookSpec],
+    installed_by: &str,
+) -> Result<Vec<Conflict>> {
+    let planned: Vec<_> = specs
+        .iter()
+        .map(|spec| {
+            (
+                spec.event,
+                ConflictingHook {
+                    matcher: matcher::normalize(spec.matcher.clone()),
+                    command: spec.handler.key().to_string(),
+                    installed_by: Some(installed_by.to_string()),
+                },
+            )
+        })
+        .collect();
+    Ok(conflicts::find_conflicts(
+        &existing_hooks(&scope)?,
+        &planned,
+    ))
+}
+
+/// Watch the scope's settings file for changes made outside this crate.
+///
+/// `on_change` runs on a background thread each time the file's content
+/// changes, with the new settings and the enabled managed hooks missing from
+/// them (e.g. removed by hand). Unparseable intermediate states are skipped.
+/// Writes made through this crate are reported too. Watching stops when the
+/// returned [`SettingsWatcher`] is dropped.
+///
+/// Requires the `watch` feature.
+///
+/// # Errors
+/// * `SettingsError::Watch` - The settings directory cannot be watched (e.g.
+///   it does not exist)
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{watch, Scope};
+///
+/// let _watcher = watch(Scope::User, |change| {
+///     for entry in &change.missing {
+///         log::warn!("{:?} hook removed: {}", entry.event, entry.command);
+///     }
+/// })?;
+/// ```
+#[cfg(feature = "watch")]
+pub fn watch<F>(scope: Scope, on_change: F) -> Result<SettingsWatcher>
+where
+    F: FnMut(SettingsChange) + Send + 'static,
+{
+    watch::watch(scope, on_change)
+}
+
+/// Install several hooks with a single settings write.
+///
+/// Every hook is validated and staged before anything is written; if any
+/// fails (duplicate, empty command), nothing is installed. Settings and the
+/// registry are each written once, and not at all for an empty `specs`.
+///
+/// # Arguments
+/// * `scope` - Settings file to install into
+/// * `specs` - Hooks to install, in order
+/// * `installed_by` - Free-form string identifying installer
+///
+/// # Errors
+/// * `HookError::InvalidHandler` - A hook is empty or has an unsupported type
+/// * `HookError::InvalidMatcher` - A matcher is not a valid tool list or regex
+/// * `HookError::AlreadyExists` - A hook exists already or appears twice in `specs`
+/// * `SettingsError` - Failed to read or write settings.json
+/// * `RegistryError` - Failed to read registry (write failure is logged but not returned)
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{install_all, HookSpec, Scope};
+///
+/// install_all(Scope::User, specs, "acd")?;
+/// ```
+pub fn install_all(scope: Scope, specs: Vec<HookSpec>, installed_by: &str) -> Result<()> {
+    if specs.is_empty() {
+        return Ok(());
+    }
+
+    let _lock = lock::acquire(&scope)?;
+
+    // 1. Read registry and settings once
+    let mut registry_entries = registry::read_registry()?;
+    let mut settings_value = settings::read_settings(&scope)?;
+    let mut taken: Vec<(HookEvent, String)> = settings::list_hooks(&settings_value)
+        .into_iter()
+        .map(|(event, _, handler)| (event, handler.key().to_string()))
+        .collect();
+
+    // 2. Validate and stage every hook
+    let mut new_entries = Vec::with_capacity(specs.len());
+    for HookSpec {
+        event,
+        matcher,
+        handler,
+    } in specs
+    {
+        check_handler(event, &handler)?;
+        if let Some(matcher) = &matcher {
+            matcher.validate()?;
+        }
+        let in_registry = registry_entries
+            .iter()
+            .any(|e| e.in_scope(&scope) && e.matches(event, handler.key()));
+        let in_settings = taken
+            .iter()
+            .any(|(e, command)| *e == event && *command == handler.key());
+        if in_registry || in_settings {
+            return Err(HookError::AlreadyExists {
+                event,
+                command: handler.key().to_string(),
+            }
+            .into());
+        }
+
+        taken.push((event, handler.key().to_string()));
+        new_entries.push(registry_entry(
+            &scope,
+            event,
+            &handler,
+            matcher.clone(),
+            installed_by,
+        ));
+        settings_value = settings::add_hook(
+            settings_value,
+            event,
+            handler,
+            matcher::to_group(matcher.as_ref()),
+        );
+    }
+
+    // 3. Write settings once
+    settings::write_settings_atomic(&scope, settings_value)?;
+
+    // 4. Write registry once (log warning on failure, don't fail operation)
+    registry_entries.extend(new_entries);
+    if let Err(e) = registry::write_registry(registry_entries) {
+        log::warn!(
+            "Failed to write registry after successful settings write: {}",
+            e
+        );
+        log::warn!(
+            "Hooks installed but not tracked. Remove manually from settings.json if needed."
+        );
+    }
+
+    Ok(())
+}
+
+/// Install every hook in a template.
+///
+/// Installed with [`install_all`], so a template that clashes with an
+/// existing hook installs nothing.
+///
+/// # Arguments
+/// * `scope` - Settings file to install into
+/// * `template` - Built-in (see [`templates::ALL`]) or caller-defined template
+/// * `params` - Values for the template's `{param}` placeholders
+/// * `installed_by` - Free-form string identifying installer
+///
+/// # Errors
+/// * `HookError::MissingTemplateParam` - A template parameter was not supplied
+/// * `HookError::AlreadyExists` - One of the hooks already exists
+/// * `SettingsError` - Failed to read or write settings.json
+/// * `RegistryError` - Failed to read registry
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{install_template, templates, Scope};
+///
+/// let specs = install_template(
+///     Scope::User,
+///     &templates::STATUS_NOTIFIER,
+///     &[("command", "my-status")],
+///     "my-tool",
+/// )?;
+/// ```
+pub fn install_template(
+    scope: Scope,
+    template: &templates::Template,
+    params: &[(&str, &str)],
+    installed_by: &str,
+) -> Result<Vec<HookSpec>> {
+    // 1. Expand placeholders
+    let specs = template.expand(params)?;
+
+    // 2. Install them together
+    install_all(scope, specs.clone(), installed_by)?;
+
+    Ok(specs)
+}
+
+/// Check the scope's settings for hooks Claude Code will reject or ignore.
+///
+/// Reports unknown events, malformed matcher groups and handlers, bad field
+/// types and duplicate handlers, each located by a JSON pointer. An empty
+/// result means the hooks are well-formed.
+///
+/// # Errors
+/// * `SettingsError` - Failed to read settings.json or parse it as JSON
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{validate_settings, DiagnosticLevel, Scope};
+///
+/// for diagnostic in validate_settings(Scope::User)? {
+///     eprintln!("{}", diagnostic); // error: /hooks/Stop/0/matcher: matcher must be a string
+/// }
+/// ```
+pub fn validate_settings(scope: Scope) -> Result<Vec<Diagnostic>> {
+    let settings_value = settings::read_settings(&scope)?;
+    Ok(validate::validate_value(&settings_value))
+}
+
+/// List backups of the scope's settings file, newest first.
+///
+/// A backup is taken before every settings write; the newest
+/// [`MAX_BACKUPS`] are kept.
+///
+/// # Errors
+/// * `SettingsError::Backup` - Failed to read the backups directory
+///
+/// # Example
+/// ```ignore
+/// use claude_hooks::{list_backups, restore_backup, Scope};
+///
+/// // Roll back the last write
+/// if let Some(latest) = list_backups(Scope::User)?.first() {
+///     restore_backup(Scope::User, latest)?;
+/// }
+/// ```
+pub fn list_backups(scope: Scope) -> Result<Vec<Backup>> {
+    backup::list_backups(&scope)
+}
+
+/// Replace the scope's settings file with a backup.
+///
+/// The registry is not touched, so hooks added or removed since the backup
+/// may show the wrong managed status until reinstalled or uninstalled.
+///
+/// # Errors
+/// * `SettingsError::Io` - Failed to read the backup
+/// * `SettingsError::Parse` - Backup is not valid JSON
+/// * `SettingsError` - Failed to back up or write settings.json
+pub fn restore_backup(scope: Scope, backup: &Backup) -> Result<()> {
+    let _lock = lock::acquire(&scope)?;
+    backup::restore_backup(&scope, backup)
+}
+
+/// Registry plus settings before and after adding the hook.
+type Staged = (Vec<RegistryEntry>, serde_json::Value, serde_json::Value);
+
+/// Validate an install and compute the updated settings.
+/// Reject handlers that cannot be managed: unknown types, which have no
+/// key to track them by, and empty commands or prompts.
+fn check_handler(event: HookEvent, handler: &HookHandler) -> Result<()> {
+    if let HookHandler::Other(_) = handler {
+        return Err(unsupported_handler(event, handler));
+    }
+    if handler.key().trim().is_empty() {
+        return Err(HookError::InvalidHandler(format!(
+            "{:?} {} hook is empty",
+            event,
+            handler.type_name()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+fn unsupported_handler(event: HookEvent, handler: &HookHandler) -> Error {
+    HookError::InvalidHandler(format!(
+        "{:?} hook has unsupported type \"{}\"",
+        event,
+        handler.type_name()
+    ))
+    .into()
+}
+
+fn stage_install(
+    scope: &Scope,
+    event: HookEvent,
+    handler: &HookHandler,
+    matcher: Option<Matcher>,
+) -> Result<Staged> {
+    // 1. Read registry
+    if let HookHandler::Other(_) = handler {
+        return Err(unsupported_handler(event, handler));
+    }
+    if let Some(matcher) = &matcher {
+        matcher.validate()?;
+    }
+    let registry_entries = registry::read_registry()?;
+
+    // 2. Check if hook exists in registry for this scope
+    if registry_entries
+        .iter()
+        .any(|e| e.in_scope(scope) && e.matches(event, handler.key()))
+    {
+        return Err(HookError::AlreadyExists {
+            event,
+            command: handler.key().to_string(),
+        }
+        .into());
+    }
+
+    // 3. Read settings
+    let settings_value = settings::read_settings(scope)?;
+
+    // 4. Check if hook exists in settings.json using list_hooks
+    let existing_hooks = settings::list_hooks(&settings_value);
+    for (hook_event, _, hook_handler) in &existing_hooks {
+        if *hook_event == event && hook_handler.key() == handler.key() {
+            return Err(HookError::AlreadyExists {
+                event,
+                command: handler.key().to_string(),
+            }
+            .into());
+        }
+    }
+
+    // 5. Add hook to settings
+    let updated_settings = settings::add_hook(
+        settings_value.clone(),
+        event,
+        handler.clone(),
+        matcher::to_group(matcher.as_ref()),
+    );
+
+    Ok((registry_entries, settings_value, updated_settings))
+}
+
+/// Validate an uninstall and compute the updated settings.
+fn stage_uninstall(scope: &Scope, event: HookEvent, command: &str) -> Result<Staged> {
+    // 1. Read registry
+    let registry_entries = registry::read_registry()?;
+
+    // 2. Check if hook exists in registry for this scope
+    if !registry_entries
+        .iter()
+        .any(|e| e.in_scope(scope) && e.matches(event, command))
+    {
+        return Err(HookError::NotManaged {
+            event,
+            command: command.to_string(),
+        }
+        .into());
+    }
+
+    // 3. Read settings
+    let settings_value = settings::read_settings(scope)?;
+
+    // 4. Check if hook exists in settings.json using list_hooks
+    let existing_hooks = settings::list_hooks(&settings_value);
+    let hook_in_settings = existing_hooks
+        .iter()
+        .any(|(e, _, h)| *e == event && h.key() == command);
+
+    if !hook_in_settings {
+        log::warn!(
+            "Hook in registry but not in settings.json: {:?} - {}",
+            event,
+            command
+        );
+        log::warn!("Removing from registry anyway (user may have manually deleted)");
+    }
+
+    // 5. Remove hook from settings (if exists)
+    let updated_settings = settings::remove_hook(settings_value.clone(), event, command);
+
+    Ok((registry_entries, settings_value, updated_settings))
+}
+
+/// Hooks in the scope's settings with their registry owners, in file order.
+fn existing_hooks(scope: &Scope) -> Result<Vec<(HookEvent, ConflictingHook)>> {
+    let registry_entries = registry::read_registry()?;
+    let settings_value = settings::read_settings(scope)?;
+    Ok(settings::list_hooks(&settings_value)
+        .into_iter()
+        .map(|(event, matcher, handler)| {
+            let installed_by = registry_entries
+                .iter()
+                .find(|e| e.in_scope(scope) && e.matches(event, handler.key()))
+                .map(|e| e.installed_by.clone());
+            (
+                event,
+                ConflictingHook {
+                    matcher: matcher::from_group(matcher.as_deref()),
+                    command: handler.key().to_string(),
+                    installed_by,
+                },
+            )
+        })
+        .collect())
+}
+
+/// The registry entry for a hook managed in `scope`.
+fn managed_entry<'a>(
+    entries: &'a mut [RegistryEntry],
+    scope: &Scope,
+    event: HookEvent,
+    command: &str,
+) -> Result<&'a mut RegistryEntry> {
+    entries
+        .iter_mut()
+        .find(|e| e.in_scope(scope) && e.matches(event, command))
+        .ok_or_else(|| {
+            HookError::NotManaged {
+                event,
+                command: command.to_string(),
+            }
+            .into()
+        })
+}
+
+/// Build the registry entry recording a hook in `scope`.
+fn registry_entry(
+    scope: &Scope,
+    event: HookEvent,
+    handler: &HookHandler,
+    matcher: Option<Matcher>,
+    installed_by: &str,
+) -> RegistryEntry {
+    RegistryEntry {
+        event,
+        matcher: matcher::normalize(matcher),
+        r#type: handler.type_name().to_string(),
+        command: handler.key().to_string(),
+        timeout: handler.timeout(),
+        r#async: handler.is_async(),
+        scope: scope.name().to_string(),
+        project_root: scope.project_root().map(crate::types::canonical_root),
+        enabled: true,
+        added_at: chrono::Local::now().format("%Y%m%d-%H%M%S").to_string(),
+        installed_by: installed_by.to_string(),
+        description: None,
+        reason: None,
+        optional: None,
+        stashed: None,
+    }
+}
+
 #[cfg(test)]
 mod integration_tests;
@@ -0,0 +1,206 @@
+//! Advisory locks around read-modify-write cycles
+//!
+//! Atomic renames keep each file intact, but two installers that both read
+//! settings.json before either writes it still lose one of the changes.
+//! Every mutating operation therefore holds an exclusive lock on a lockfile
+//! for the settings file (`settings.json.lock`) and one next to the registry
+//! (`registry.jsonc.lock`) from its first read to its last write. The user
+//! scope's lockfile sits next to its settings in `~/.claude`; project and
+//! local lockfiles go to the project's directory under the user data dir
+//! (see [`Scope::state_dir`]) rather than into the repository.
+//!
+//! The locks are advisory (`flock` on Unix, `LockFileEx` on Windows), so
+//! only other claude-hooks users honour them. Lockfiles are left in place;
+//! deleting them would race with a process about to lock them. Locks are
+//! reentrant per thread, so operations built on other operations (such as
+//! [`upsert`](crate::upsert)) take them once.
+
+use fs2::FileExt;
+use std::cell::RefCell;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::error::{RegistryError, Result, SettingsError};
+use crate::registry;
+use crate::types::Scope;
+
+/// How long to wait for another process before giving up
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay between attempts while the lock is held elsewhere
+const RETRY_INTERVAL: Duration = Duration::from_millis(25);
+
+thread_local! {
+    /// Lockfiles this thread currently holds
+    static HELD: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An exclusive lock on one lockfile, released on drop
+///
+/// Holds no file if this thread already had the lock when it was taken.
+#[derive(Debug)]
+struct FileLock {
+    held: Option<(File, PathBuf)>,
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        if let Some((file, path)) = self.held.take() {
+            // Qualified: std::fs::File::unlock is newer than our MSRV
+            if let Err(e) = FileExt::unlock(&file) {
+                log::warn!("Failed to unlock {}: {}", path.display(), e);
+            }
+            HELD.with(|held| held.borrow_mut().retain(|p| *p != path));
+        }
+    }
+}
+
+/// Locks on a scope's settings file and the registry
+#[derive(Debug)]
+pub(crate) struct Guard {
+    // Dropped in declaration order: registry first, the reverse of acquisition
+    _registry: FileLock,
+    _settings: FileLock,
+}
+
+/// Lock the scope's settings file, then the registry
+///
+/// The fixed order keeps concurrent operations on different scopes, which
+/// share the registry, from deadlocking.
+///
+/// # Errors
+///
+/// - `SettingsError::Locked` / `RegistryError::Locked` if another process
+///   holds the lock for longer than [`LOCK_TIMEOUT`]
+/// - `SettingsError::Io` / `RegistryError::Io` if a lockfile cannot be
+///   created
+pub(crate) fn acquire(scope: &Scope) -> Result<Guard> {
+    let settings = lock_file(&settings_lock_path(scope))
+        .map_err(|e| lock_error(e, SettingsError::Io, SettingsError::Locked))?;
+    let registry = lock_file(&lock_path(&registry::registry_path()))
+        .map_err(|e| lock_error(e, RegistryError::Io, RegistryError::Locked))?;
+    Ok(Guard {
+        _registry: registry,
+        _settings: settings,
+    })
+}
+
+/// Lockfile guarding the scope's settings, kept in its state directory
+fn settings_lock_path(scope: &Scope) -> PathBuf {
+    scope.state_dir().join(
+        lock_path(&scope.settings_path())
+            .file_name()
+            .unwrap_or_default(),
+    )
+}
+
+/// Lockfile guarding `path`: the same name with `.lock` appended
+pub(crate) fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    path.with_file_name(name)
+}
+
+/// Why a lock could not be taken
+enum LockFailure {
+    Io(io::Error),
+    TimedOut(PathBuf),
+}
+
+fn lock_error<E: Into<crate::error::Error>>(
+    failure: LockFailure,
+    io: impl FnOnce(io::Error) -> E,
+    timed_out: impl FnOnce(PathBuf) -> E,
+) -> crate::error::Error {
+    match failure {
+        LockFailure::Io(e) => io(e).into(),
+        LockFailure::TimedOut(path) => timed_out(path).into(),
+    }
+}
+
+fn lock_file(path: &Path) -> std::result::Result<FileLock, LockFailure> {
+    if HELD.with(|held| held.borrow().iter().any(|p| p == path)) {
+        return Ok(FileLock { held: None });
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(LockFailure::Io)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(LockFailure::Io)?;
+
+    let deadline = Instant::now() + LOCK_TIMEOUT;
+    loop {
+        match file.try_lock_exclusive() {
+            Ok(()) => break,
+            Err(e) if e.kind() == fs2::lock_contended_error().kind() => {
+                if Instant::now() >= deadline {
+                    return Err(LockFailure::TimedOut(path.to_path_buf()));
+                }
+                log::debug!("Waiting for lock on {}", path.display());
+                std::thread::sleep(RETRY_INTERVAL);
+            }
+            Err(e) => return Err(LockFailure::Io(e)),
+        }
+    }
+
+    HELD.with(|held| held.borrow_mut().push(path.to_path_buf()));
+    Ok(FileLock {
+        held: Some((file, path.to_path_buf())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_path_appends_suffix() {
+        assert_eq!(
+            lock_path(Path::new("/home/u/.claude/settings.local.json")),
+            PathBuf::from("/home/u/.claude/settings.local.json.lock")
+        );
+    }
+
+    #[test]
+    fn test_project_lockfiles_stay_out_of_the_repository() {
+        let root = Path::new("/work/repo");
+        let local = settings_lock_path(&Scope::Local(root.to_path_buf()));
+        let project = settings_lock_path(&Scope::Project(root.to_path_buf()));
+        assert!(!local.starts_with(root));
+        assert!(local.ends_with("settings.local.json.lock"));
+        assert_eq!(local.parent(), project.parent());
+        assert!(project.ends_with("settings.json.lock"));
+    }
+
+    #[test]
+    fn test_lock_is_reentrant_and_exclusive_across_threads() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join("settings.json.lock");
+
+        let outer = lock_file(&path).ok().expect("first lock");
+        let inner = lock_file(&path).ok().expect("reentrant lock");
+        assert!(inner.held.is_none());
+        drop(inner);
+
+        // Another thread (a separate open file) has to wait
+        let other = path.clone();
+        let contended = std::thread::spawn(move || {
+            let file = File::open(&other).expect("open lockfile");
+            file.try_lock_exclusive().is_err()
+        });
+        assert!(contended.join().expect("thread panicked"));
+
+        drop(outer);
+        let other = path.clone();
+        let free = std::thread::spawn(move || lock_file(&other).is_ok());
+        assert!(free.join().expect("thread panicked"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Structured hook matchers
+//!
+//! In settings.json a matcher is one string: absent, empty or `*` matches
+//! every tool, a plain `Name|Other` list matches those tool names exactly,
+//! and anything else is a regex. [`Matcher`] models the three cases so they
+//! can be built, validated and compared without string handling, and is
+//! converted back to the settings string when written.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, HookError, Result};
+
+/// Which tools (or notification types, etc.) a hook applies to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "Structured", from = "Stored")]
+pub enum Matcher {
+    /// Every tool; written as no matcher
+    All,
+    /// Exact names, written as `A|B`
+    Tools(Vec<String>),
+    /// Regex pattern, written as is
+    Regex(String),
+}
+
+impl Matcher {
+    /// Match one tool by exact name
+    pub fn tool(name: impl Into<String>) -> Self {
+        Self::Tools(vec![name.into()])
+    }
+
+    /// Match any of several tools by exact name
+    pub fn tools<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Tools(names.into_iter().map(Into::into).collect())
+    }
+
+    /// Match tool names against a regex
+    ///
+    /// # Errors
+    ///
+    /// Returns `HookError::InvalidMatcher` if the pattern does not compile.
+    pub fn regex(pattern: impl Into<String>) -> Result<Self> {
+        let matcher = Self::Regex(pattern.into());
+        matcher.validate()?;
+        Ok(matcher)
+    }
+
+    /// Interpret a matcher string from settings.json without validating it
+    pub fn from_settings(matcher: &str) -> Self {
+        match matcher.trim() {
+            "" | "*" => Self::All,
+            m if is_name_list(m) => Self::tools(m.split('|').filter(|s| !s.is_empty())),
+            m => Self::Regex(m.to_string()),
+        }
+    }
+
+    /// Matcher string to write to settings.json (None for [`Matcher::All`])
+    pub fn to_settings(&self) -> Option<String> {
+        match self {
+            Self::All => None,
+            Self::Tools(names) => Some(names.join("|")),
+            Self::Regex(pattern) => Some(pattern.clone()),
+        }
+    }
+
+    /// Check that Claude Code will accept the matcher
+    ///
+    /// # Errors
+    ///
+    /// Returns `HookError::InvalidMatcher` for an empty tool list, a tool name
+    /// that is not a plain identifier, or a regex that does not compile.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason: String| -> Error {
+            HookError::InvalidMatcher {
+                matcher: self.to_string(),
+                reason,
+            }
+            .into()
+        };
+        match self {
+            Self::All => Ok(()),
+            Self::Tools(names) if names.is_empty() => Err(invalid("no tool names".to_string())),
+            Self::Tools(names) => match names.iter().find(|n| n.is_empty() || !is_name_list(n)) {
+                Some(name) => Err(invalid(format!("\"{}\" is not a tool name", name))),
+                None => Ok(()),
+            },
+            Self::Regex(pattern) => Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|e| invalid(e.to_string())),
+        }
+    }
+
+    /// True if the hook would run for this tool name
+    ///
+    /// Regexes are searched unanchored, like Claude Code does; an invalid
+    /// regex matches nothing.
+    pub fn matches(&self, tool: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Tools(names) => names.iter().any(|n| n == tool),
+            Self::Regex(pattern) => Regex::new(pattern).is_ok_and(|re| re.is_match(tool)),
+        }
+    }
+
+    /// True if some tool could match both matchers
+    ///
+    /// Exact for tool lists; a regex is tested against the other side's
+    /// names, and two regexes only overlap if they are identical.
+    pub fn overlaps(&self, other: &Matcher) -> bool {
+        match (self, other) {
+            (Self::All, _) | (_, Self::All) => true,
+            (Self::Tools(names), m) | (m, Self::Tools(names)) => {
+                names.iter().any(|name| m.matches(name))
+            }
+            (Self::Regex(a), Self::Regex(b)) => a == b,
+        }
+    }
+}
+
+impl fmt::Display for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => f.write_str("*"),
+            Self::Tools(names) => f.write_str(&names.join("|")),
+            Self::Regex(pattern) => f.write_str(pattern),
+        }
+    }
+}
+
+impl FromStr for Matcher {
+    type Err = Error;
+
+    /// Parse a settings-style matcher string and validate it
+    fn from_str(s: &str) -> Result<Self> {
+        let matcher = Self::from_settings(s);
+        matcher.validate()?;
+        Ok(matcher)
+    }
+}
+
+/// Registry form of an optional matcher
+///
+/// [`Matcher::All`] is stored as None, the same as a settings group with no
+/// matcher, so registry and settings compare equal.
+pub(crate) fn normalize(matcher: Option<Matcher>) -> Option<Matcher> {
+    matcher.filter(|m| *m != Matcher::All)
+}
+
+/// Matcher of a settings group, in registry form
+pub(crate) fn from_group(matcher: Option<&str>) -> Option<Matcher> {
+    normalize(matcher.map(Matcher::from_settings))
+}
+
+/// Settings string for an optional matcher
+pub(crate) fn to_group(matcher: Option<&Matcher>) -> Option<String> {
+    matcher.and_then(Matcher::to_settings)
+}
+
+/// `A|B` lists of plain names (tool names, MCP tools, notification types)
+fn is_name_list(s: &str) -> bool {
+    s.chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '|')
+}
+
+/// Registry representation: `{"kind": "tools", "value": ["Edit", "Write"]}`
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+enum Structured {
+    All,
+    Tools(Vec<String>),
+    Regex(String),
+}
+
+/// Accepts the structured form and plain strings written by older versions
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Stored {
+    Structured(Structured),
+    Raw(String),
+}
+
+impl From<Matcher> for Structured {
+    fn from(matcher: Matcher) -> Self {
+        match matcher {
+            Matcher::All => Self::All,
+            Matcher::Tools(names) => Self::Tools(names),
+            Matcher::Regex(pattern) => Self::Regex(pattern),
+        }
+    }
+}
+
+impl From<Stored> for Matcher {
+    fn from(stored: Stored) -> Self {
+        match stored {
+            Stored::Structured(Structured::All) => Self::All,
+            Stored::Structured(Structured::Tools(names)) => Self::Tools(names),
+            Stored::Structured(Structured::Regex(pattern)) => Self::Regex(pattern),
+            Stored::Raw(raw) => Self::from_settings(&raw),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_settings_classifies_strings() {
+        assert_eq!(Matcher::from_settings(""), Matcher::All);
+        assert_eq!(Matcher::from_settings("*"), Matcher::All);
+        assert_eq!(Matcher::from_settings("Bash"), Matcher::tool("Bash"));
+        assert_eq!(
+            Matcher::from_settings("Edit|Write"),
+            Matcher::tools(["Edit", "Write"])
+        );
+        assert_eq!(
+            Matcher::from_settings("mcp__.*"),
+            Matcher::Regex("mcp__.*".to_string())
+        );
+        assert_eq!(
+            Matcher::tools(["Edit", "Write"]).to_settings().as_deref(),
+            Some("Edit|Write")
+        );
+        assert_eq!(Matcher::All.to_settings(), None);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_matchers() {
+        assert!(Matcher::regex("Notebook.*").is_ok());
+        assert!(matches!(
+            Matcher::regex("Edit("),
+            Err(Error::Hook(HookError::InvalidMatcher { .. }))
+        ));
+        assert!(Matcher::Tools(Vec::new()).validate().is_err());
+        assert!(Matcher::tool("Edit Write").validate().is_err());
+        assert!("Bash|Read".parse::<Matcher>().is_ok());
+        assert!("[".parse::<Matcher>().is_err());
+    }
+
+    #[test]
+    fn test_matches_and_overlaps() {
+        let notebook = Matcher::Regex("Notebook.*".to_string());
+        assert!(notebook.matches("NotebookEdit"));
+        assert!(!notebook.matches("Bash"));
+        assert!(Matcher::All.overlaps(&Matcher::tool("Bash")));
+        assert!(Matcher::tools(["Edit", "Write"]).overlaps(&Matcher::tool("Write")));
+        assert!(!Matcher::tool("Bash").overlaps(&Matcher::tool("Read")));
+        assert!(notebook.overlaps(&Matcher::tools(["Bash", "NotebookEdit"])));
+        assert!(!notebook.overlaps(&Matcher::Regex("mcp__.*".to_string())));
+    }
+
+    #[test]
+    fn test_serde_structured_and_legacy() {
+        let json = serde_json::to_value(Matcher::tools(["Edit", "Write"])).expect("serialize");
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "tools", "value": ["Edit", "Write"] })
+        );
+        let back: Matcher = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back, Matcher::tools(["Edit", "Write"]));
+        assert_eq!(
+            serde_json::to_value(Matcher::All).expect("serialize"),
+            serde_json::json!({ "kind": "all" })
+        );
+
+        // Registries written before matchers were structured hold raw strings
+        let legacy: Matcher = serde_json::from_str("\"Bash\"").expect("deserialize");
+        assert_eq!(legacy, Matcher::tool("Bash"));
+    }
+}
@@ -3,11 +3,18 @@
 //! This module manages the local registry in XDG data directory to track
 //! hooks installed by this crate. The registry uses JSONC format (JSON with
 //! comments) and lives in `$XDG_DATA_HOME/claude-hooks/registry.jsonc`.
+//!
+//! The file carries a `schema_version`. Older files are upgraded in memory
+//! on read by running the [`MIGRATIONS`] in order, and saved in the current
+//! layout on the next write. Files from a newer version are rejected rather
+//! than misread.
 
 use crate::error::{RegistryError, Result};
-use crate::types::{HookEvent, RegistryEntry};
+use crate::matcher::Matcher;
+use crate::types::{HookEvent, RegistryEntry, Scope};
 use chrono::Local;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
 use std::path::PathBuf;
 
@@ -19,11 +26,78 @@ struct Registry {
     hooks: Vec<RegistryEntry>,
 }
 
+/// A migration takes a registry file of one version to the next
+type Migration = fn(Value) -> Result<Value>;
+
+/// Migrations in order: `MIGRATIONS[0]` upgrades version 1 to version 2, etc.
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2];
+
+/// Schema version written by this crate
+pub const REGISTRY_SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Version 2 stores matchers structured instead of as settings strings
+///
+/// Version 1 is the layout of claude-hooks 0.1: `"matcher": "Edit|Write"`
+/// becomes `"matcher": {"kind": "tools", "value": ["Edit", "Write"]}`.
+fn migrate_v1_to_v2(mut registry: Value) -> Result<Value> {
+    let hooks = registry
+        .get_mut("hooks")
+        .and_then(Value::as_array_mut)
+        .ok_or_else(|| RegistryError::Parse("\"hooks\" must be an array".to_string()))?;
+    for entry in hooks.iter_mut() {
+        let matcher = match entry.get("matcher").and_then(Value::as_str) {
+            Some(matcher) => Matcher::from_settings(matcher),
+            None => continue,
+        };
+        entry["matcher"] = serde_json::to_value(matcher)
+            .map_err(|e| RegistryError::Parse(format!("Failed to migrate matcher: {}", e)))?;
+    }
+    Ok(registry)
+}
+
+/// Upgrade a parsed registry file to [`REGISTRY_SCHEMA_VERSION`]
+///
+/// Files without a `schema_version` are treated as version 1.
+///
+/// # Errors
+///
+/// - `RegistryError::UnsupportedVersion` if the file is from a newer version
+/// - `RegistryError::Parse` if a migration finds a malformed file
+pub(crate) fn migrate(mut registry: Value) -> Result<Value> {
+    let version = match registry.get("schema_version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| RegistryError::Parse(format!("Invalid schema_version {}", version)))?,
+    };
+    if version > REGISTRY_SCHEMA_VERSION {
+        return Err(RegistryError::UnsupportedVersion {
+            found: version,
+            supported: REGISTRY_SCHEMA_VERSION,
+        }
+        .into());
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        log::info!(
+            "Migrating registry from version {} to {}",
+            from + 1,
+            from + 2
+        );
+        registry = migration(registry)?;
+        registry["schema_version"] = Value::from(from as u32 + 2);
+    }
+    Ok(registry)
+}
+
 /// Returns the path to the registry file
 ///
 /// Uses XDG data directory conventions:
 /// - macOS: `~/Library/Application Support/claude-hooks/registry.jsonc`
 /// - Linux: `~/.local/share/claude-hooks/registry.jsonc` (or `$XDG_DATA_HOME/claude-hooks/registry.jsonc`)
+/// - Windows: `%APPDATA%\claude-hooks\registry.jsonc`
 ///
 /// # Panics
 ///
@@ -42,6 +116,7 @@ pub fn registry_path() -> PathBuf {
 ///
 /// - `RegistryError::Io` if file read fails
 /// - `RegistryError::Parse` if JSONC parsing fails
+/// - `RegistryError::UnsupportedVersion` if a newer claude-hooks wrote the file
 pub fn read_registry() -> Result<Vec<RegistryEntry>> {
     let path = registry_path();
 
@@ -61,12 +136,20 @@ pub fn read_registry() -> Result<Vec<RegistryEntry>> {
     let stripped_str = String::from_utf8(stripped_bytes)
         .map_err(|e| RegistryError::Parse(format!("Invalid UTF-8: {}", e)))?;
 
-    let registry: Registry =
+    let value: Value =
         serde_json::from_str(&stripped_str).map_err(|e| RegistryError::Parse(e.to_string()))?;
+    let registry: Registry =
+        serde_json::from_value(migrate(value)?).map_err(|e| RegistryError::Parse(e.to_string()))?;
 
     Ok(registry.hooks)
 }
 
+#[cfg(test)]
+thread_local! {
+    /// Makes this thread's registry writes fail, to test rollbacks
+    pub(crate) static FAIL_WRITES: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
 /// Write registry file atomically
 ///
 /// Creates directory if missing. Uses atomic rename pattern to ensure
@@ -79,6 +162,11 @@ pub fn read_registry() -> Result<Vec<RegistryEntry>> {
 /// - `RegistryError::Write` if fsync fails
 /// - `RegistryError::Write` if rename fails
 pub fn write_registry(entries: Vec<RegistryEntry>) -> Result<()> {
+    #[cfg(test)]
+    if FAIL_WRITES.with(std::cell::Cell::get) {
+        return Err(RegistryError::Write("injected failure".to_string()).into());
+    }
+
     let path = registry_path();
 
     // Create directory if missing
@@ -88,7 +176,7 @@ pub fn write_registry(entries: Vec<RegistryEntry>) -> Result<()> {
     }
 
     let registry = Registry {
-        schema_version: 1,
+        schema_version: REGISTRY_SCHEMA_VERSION,
         agent_name: "claude-code".to_string(),
         hooks: entries,
     };
@@ -103,17 +191,12 @@ pub fn write_registry(entries: Vec<RegistryEntry>) -> Result<()> {
     // Add header comment
     let content = format!("// claude-hooks registry\n{}", json);
 
-    fs::write(&temp_path, content)
+    // Write and fsync
+    crate::atomic::write_synced(&temp_path, content.as_bytes())
         .map_err(|e| RegistryError::Write(format!("Failed to write temp file: {}", e)))?;
 
-    // Fsync
-    let file = fs::File::open(&temp_path)
-        .map_err(|e| RegistryError::Write(format!("Failed to open temp file for fsync: {}", e)))?;
-    file.sync_all()
-        .map_err(|e| RegistryError::Write(format!("Failed to fsync: {}", e)))?;
-
     // Atomic rename
-    fs::rename(&temp_path, &path).map_err(|e| {
+    crate::atomic::replace(&temp_path, &path).map_err(|e| {
         RegistryError::Write(format!(
             "Failed to rename {} to {}: {}",
             temp_path.display(),
@@ -135,14 +218,15 @@ pub fn add_entry(mut entries: Vec<RegistryEntry>, entry: RegistryEntry) -> Vec<R
 
 /// Remove entry from registry by exact match (pure function, no I/O)
 ///
-/// Removes all entries that match the given event and command.
+/// Removes all entries in `scope` that match the given event and command.
 /// Uses the composite key matching logic from RegistryEntry::matches().
 pub fn remove_entry(
     mut entries: Vec<RegistryEntry>,
+    scope: &Scope,
     event: HookEvent,
     command: &str,
 ) -> Vec<RegistryEntry> {
-    entries.retain(|entry| !entry.matches(event, command));
+    entries.retain(|entry| !(entry.in_scope(scope) && entry.matches(event, command)));
     entries
 }
 
@@ -187,12 +271,14 @@ mod tests {
             timeout: None,
             r#async: None,
             scope: "user".to_string(),
+            project_root: None,
             enabled: true,
             added_at: "20260203-143022".to_string(),
             installed_by: "acd".to_string(),
             description: None,
             reason: None,
             optional: None,
+            stashed: None,
         };
 
         let result = add_entry(entries, entry.clone());
@@ -210,12 +296,14 @@ mod tests {
             timeout: None,
             r#async: None,
             scope: "user".to_string(),
+            project_root: None,
             enabled: true,
             added_at: "20260203-143022".to_string(),
             installed_by: "acd".to_string(),
             description: None,
             reason: None,
             optional: None,
+            stashed: None,
         };
 
         let entry2 = RegistryEntry {
@@ -225,12 +313,54 @@ mod tests {
         };
 
         let entries = vec![entry1, entry2];
-        let result = remove_entry(entries, HookEvent::Stop, "/path/to/stop.sh");
+        let result = remove_entry(entries, &Scope::User, HookEvent::Stop, "/path/to/stop.sh");
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].event, HookEvent::SessionStart);
     }
 
+    #[test]
+    fn test_remove_entry_keeps_other_scopes() {
+        let user = RegistryEntry {
+            event: HookEvent::Stop,
+            matcher: None,
+            r#type: "command".to_string(),
+            command: "/path/to/stop.sh".to_string(),
+            timeout: None,
+            r#async: None,
+            scope: "user".to_string(),
+            project_root: None,
+            enabled: true,
+            added_at: "20260203-143022".to_string(),
+            installed_by: "acd".to_string(),
+            description: None,
+            reason: None,
+            optional: None,
+            stashed: None,
+        };
+        let root = PathBuf::from("/work/app");
+        let project = RegistryEntry {
+            scope: "project".to_string(),
+            project_root: Some(root.clone()),
+            ..user.clone()
+        };
+
+        let result = remove_entry(
+            vec![user, project.clone()],
+            &Scope::User,
+            HookEvent::Stop,
+            "/path/to/stop.sh",
+        );
+        assert_eq!(result, vec![project]);
+        let result = remove_entry(
+            result,
+            &Scope::Project(root),
+            HookEvent::Stop,
+            "/path/to/stop.sh",
+        );
+        assert!(result.is_empty());
+    }
+
     #[test]
     fn test_remove_entry_multiple_matches() {
         let entry1 = RegistryEntry {
@@ -241,12 +371,14 @@ mod tests {
             timeout: None,
             r#async: None,
             scope: "user".to_string(),
+            project_root: None,
             enabled: true,
             added_at: "20260203-143022".to_string(),
             installed_by: "acd".to_string(),
             description: None,
             reason: None,
             optional: None,
+            stashed: None,
         };
 
         let entry2 = entry1.clone();
@@ -257,7 +389,7 @@ mod tests {
         };
 
         let entries = vec![entry1, entry2, entry3];
-        let result = remove_entry(entries, HookEvent::Stop, "/path/to/stop.sh");
+        let result = remove_entry(entries, &Scope::User, HookEvent::Stop, "/path/to/stop.sh");
 
         // Should remove both Stop entries
         assert_eq!(result.len(), 1);
@@ -287,6 +419,79 @@ mod tests {
         assert_eq!(registry.agent_name, "claude-code");
     }
 
+    #[test]
+    fn test_migrate_v1_structures_matchers() {
+        let v1 = serde_json::json!({
+            "schema_version": 1,
+            "agent_name": "claude-code",
+            "hooks": [
+                { "event": "PreToolUse", "matcher": "Edit|Write", "command": "a.sh" },
+                { "event": "Stop", "command": "b.sh" }
+            ]
+        });
+        let migrated = migrate(v1).expect("migration should succeed");
+        assert_eq!(migrated["schema_version"], REGISTRY_SCHEMA_VERSION);
+        assert_eq!(
+            migrated["hooks"][0]["matcher"],
+            serde_json::json!({ "kind": "tools", "value": ["Edit", "Write"] })
+        );
+        assert!(migrated["hooks"][1].get("matcher").is_none());
+
+        // Current files pass through unchanged
+        assert_eq!(migrate(migrated.clone()).expect("no-op"), migrated);
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_and_invalid_versions() {
+        let newer =
+            serde_json::json!({ "schema_version": REGISTRY_SCHEMA_VERSION + 1, "hooks": [] });
+        assert!(matches!(
+            migrate(newer),
+            Err(crate::error::Error::Registry(RegistryError::UnsupportedVersion { found, .. }))
+                if found == REGISTRY_SCHEMA_VERSION + 1
+        ));
+        let invalid = serde_json::json!({ "schema_version": "one", "hooks": [] });
+        assert!(migrate(invalid).is_err());
+    }
+
+    #[test]
+    #[serial(home)]
+    fn test_read_v1_registry_file() {
+        let _dir = setup_test_env();
+        let path = registry_path();
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        // Layout written by claude-hooks 0.1
+        fs::write(
+            &path,
+            r#"// claude-hooks registry
+{
+  "schema_version": 1,
+  "agent_name": "claude-code",
+  "hooks": [
+    {
+      "event": "PreToolUse",
+      "matcher": "Bash",
+      "type": "command",
+      "command": "/path/to/guard.sh",
+      "scope": "user",
+      "enabled": true,
+      "added_at": "20260203-143022",
+      "installed_by": "acd"
+    }
+  ]
+}"#,
+        )
+        .expect("write v1 registry");
+
+        let entries = read_registry().expect("v1 registry should be readable");
+        assert_eq!(entries[0].matcher, Some(Matcher::tool("Bash")));
+        assert!(entries[0].in_scope(&Scope::User));
+
+        write_registry(entries).expect("write should succeed");
+        let content = fs::read_to_string(&path).expect("read back");
+        assert!(content.contains(&format!("\"schema_version\": {}", REGISTRY_SCHEMA_VERSION)));
+    }
+
     #[test]
     #[serial(home)]
     fn test_write_and_read_registry() {
@@ -300,12 +505,14 @@ mod tests {
             timeout: Some(600),
             r#async: None,
             scope: "user".to_string(),
+            project_root: None,
             enabled: true,
             added_at: "20260203-143022".to_string(),
             installed_by: "test".to_string(),
             description: Some("Test hook".to_string()),
             reason: Some("Testing".to_string()),
             optional: Some(false),
+            stashed: None,
         }];
 
         write_registry(entries.clone()).expect("write should succeed");
@@ -344,12 +551,14 @@ mod tests {
                 timeout: Some(600),
                 r#async: Some(false),
                 scope: "user".to_string(),
+                project_root: None,
                 enabled: true,
                 added_at: "20260203-143022".to_string(),
                 installed_by: "test".to_string(),
                 description: Some("Stop hook".to_string()),
                 reason: Some("For testing".to_string()),
                 optional: Some(false),
+                stashed: None,
             },
             RegistryEntry {
                 event: HookEvent::SessionStart,
@@ -359,12 +568,14 @@ mod tests {
                 timeout: None,
                 r#async: None,
                 scope: "user".to_string(),
+                project_root: None,
                 enabled: true,
                 added_at: "20260203-143023".to_string(),
                 installed_by: "test".to_string(),
                 description: None,
                 reason: None,
                 optional: None,
+                stashed: None,
             },
         ];
 
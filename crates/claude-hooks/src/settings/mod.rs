@@ -16,72 +16,106 @@
 //! ```
 
 use crate::error::{Result, SettingsError};
-use crate::types::{HookEvent, HookHandler, MatcherGroup};
+use crate::types::{HookEvent, HookHandler, Scope};
 use chrono::Local;
 use serde_json::{Map, Value};
 use std::fs;
 use std::path::PathBuf;
 
+mod patch;
+
 /// Returns the path to Claude's user settings.json
 ///
-/// Location: `~/.claude/settings.json`
+/// Location: `~/.claude/settings.json` (`%USERPROFILE%\.claude\settings.json`
+/// on Windows)
+///
+/// # Panics
+///
+/// Panics if the home directory cannot be determined
 pub fn settings_path() -> PathBuf {
-    let home = std::env::var("HOME").expect("HOME environment variable not set");
-    PathBuf::from(home).join(".claude").join("settings.json")
+    let home = crate::atomic::home_dir().expect("Failed to determine home directory");
+    home.join(".claude").join("settings.json")
 }
 
-/// Read settings.json and parse as Value (preserves all fields)
+/// Read the scope's settings file and parse as Value (preserves all fields)
 ///
-/// Parses the entire settings.json file as a `serde_json::Value` to preserve
+/// Parses the entire settings file as a `serde_json::Value` to preserve
 /// all top-level keys per D13 (cleanupPeriodDays, env, permissions, etc.).
+/// A missing project or local settings file reads as `{}`, since projects
+/// often have none yet.
 ///
 /// # Errors
 ///
 /// Returns `SettingsError::Io` if file cannot be read.
 /// Returns `SettingsError::Parse` if JSON is malformed.
-pub fn read_settings() -> Result<Value> {
-    let path = settings_path();
-    let content = fs::read_to_string(&path).map_err(SettingsError::Io)?;
+pub fn read_settings(scope: &Scope) -> Result<Value> {
+    let path = scope.settings_path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && *scope != Scope::User => {
+            return Ok(Value::Object(Map::new()));
+        }
+        Err(e) => return Err(SettingsError::Io(e).into()),
+    };
 
     serde_json::from_str(&content).map_err(|e| SettingsError::Parse(e.to_string()).into())
 }
 
-/// Write settings.json atomically with temp-file-then-rename
-///
-/// Implements atomic write pattern (D01):
-/// 1. Write to temp file with timestamp suffix
-/// 2. Fsync to disk
-/// 3. Rename temp to original (atomic operation)
+/// Write the scope's settings file atomically with temp-file-then-rename
 ///
-/// On failure before rename, temp file is preserved as "safety copy".
+/// Only the parts of the file that differ from `value` are rewritten, so
+/// key order, indentation and untouched content keep their original form.
+/// A missing or unparseable file is written out pretty-printed instead.
 ///
 /// # Errors
 ///
+/// Returns `SettingsError::Backup` if the current file cannot be backed up.
 /// Returns `SettingsError::Parse` if value cannot be serialized.
 /// Returns `SettingsError::Io` if file cannot be written or synced.
 /// Returns `SettingsError::WriteAtomic` if rename fails.
-pub fn write_settings_atomic(value: Value) -> Result<()> {
-    let path = settings_path();
+pub fn write_settings_atomic(scope: &Scope, value: Value) -> Result<()> {
+    let json = match fs::read_to_string(scope.settings_path())
+        .ok()
+        .and_then(|current| patch::patch(&current, &value))
+    {
+        Some(json) => json,
+        None => {
+            serde_json::to_string_pretty(&value).map_err(|e| SettingsError::Parse(e.to_string()))?
+        }
+    };
+    write_settings_text(scope, &json)
+}
+
+/// Write raw text to the scope's settings file atomically
+///
+/// Implements atomic write pattern (D01), after backing up the current file:
+/// 1. Write to temp file with timestamp suffix
+/// 2. Fsync to disk
+/// 3. Rename temp to original (atomic operation)
+///
+/// On failure before rename, temp file is preserved as "safety copy".
+pub(crate) fn write_settings_text(scope: &Scope, json: &str) -> Result<()> {
+    let path = scope.settings_path();
     let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
-    let temp_path = path.with_file_name(format!("settings.json.tmp.{}", timestamp));
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!("{}.tmp.{}", file_name, timestamp));
 
     // Ensure .claude directory exists
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(SettingsError::Io)?;
     }
 
-    // Write to temp file
-    let json =
-        serde_json::to_string_pretty(&value).map_err(|e| SettingsError::Parse(e.to_string()))?;
-
-    fs::write(&temp_path, json).map_err(SettingsError::Io)?;
+    // Back up the current file so the write can be rolled back
+    crate::backup::snapshot(scope)?;
 
-    // Fsync (ensure data is on disk)
-    let file = fs::File::open(&temp_path).map_err(SettingsError::Io)?;
-    file.sync_all().map_err(SettingsError::Io)?;
+    // Write to temp file and fsync (ensure data is on disk)
+    crate::atomic::write_synced(&temp_path, json.as_bytes()).map_err(SettingsError::Io)?;
 
     // Atomic rename
-    fs::rename(&temp_path, &path).map_err(|_| SettingsError::WriteAtomic {
+    crate::atomic::replace(&temp_path, &path).map_err(|_| SettingsError::WriteAtomic {
         path: path.clone(),
         temp_path: temp_path.clone(),
     })?;
@@ -101,10 +135,24 @@ pub fn write_settings_atomic(value: Value) -> Result<()> {
 /// * `handler` - The hook handler configuration
 /// * `matcher` - Optional matcher regex (e.g., "Bash" for PreToolUse)
 pub fn add_hook(
-    mut value: Value,
+    value: Value,
     event: HookEvent,
     handler: HookHandler,
     matcher: Option<String>,
+) -> Value {
+    let handler_value = serde_json::to_value(handler).expect("handler serialization failed");
+    add_hook_value(value, event, handler_value, matcher)
+}
+
+/// Add a raw handler object to settings (pure function, no I/O)
+///
+/// Like [`add_hook`], but keeps the handler exactly as given, including
+/// fields [`HookHandler`] does not model.
+pub fn add_hook_value(
+    mut value: Value,
+    event: HookEvent,
+    handler: Value,
+    matcher: Option<String>,
 ) -> Value {
     // Ensure hooks object exists
     let root = value.as_object_mut().expect("settings should be object");
@@ -118,11 +166,7 @@ pub fn add_hook(
         .expect("hooks should be object");
 
     // Get event name as string
-    let event_name = serde_json::to_value(event)
-        .expect("event serialization failed")
-        .as_str()
-        .expect("event should serialize to string")
-        .to_string();
+    let event_name = event_name(event);
 
     // Ensure event array exists
     if !hooks_obj.contains_key(&event_name) {
@@ -135,17 +179,60 @@ pub fn add_hook(
         .expect("event should be array");
 
     // Create matcher group with the handler
-    let group = MatcherGroup {
-        matcher,
-        hooks: vec![handler],
-    };
-
-    let group_value = serde_json::to_value(group).expect("group serialization failed");
-    event_array.push(group_value);
+    let mut group = Map::new();
+    if let Some(matcher) = matcher {
+        group.insert("matcher".to_string(), Value::String(matcher));
+    }
+    group.insert("hooks".to_string(), Value::Array(vec![handler]));
+    event_array.push(Value::Object(group));
 
     value
 }
 
+/// Find a hook's matcher and raw handler object (pure function, no I/O)
+///
+/// Returns the first handler under `event` whose command (or prompt) matches.
+pub fn find_hook_value(
+    value: &Value,
+    event: HookEvent,
+    command: &str,
+) -> Option<(Option<String>, Value)> {
+    let groups = value.get("hooks")?.get(event_name(event))?.as_array()?;
+    groups.iter().find_map(|group| {
+        let handler = group
+            .get("hooks")?
+            .as_array()?
+            .iter()
+            .find(|h| has_key(h, command))?;
+        let matcher = group
+            .get("matcher")
+            .and_then(|m| m.as_str())
+            .map(String::from);
+        Some((matcher, handler.clone()))
+    })
+}
+
+/// True if a raw handler's [`HookHandler::key`] is `key`
+///
+/// Prompt and agent handlers are identified by their prompt, everything
+/// else by its command.
+fn has_key(handler: &Value, key: &str) -> bool {
+    let field = match handler.get("type").and_then(|t| t.as_str()) {
+        Some("prompt") | Some("agent") => "prompt",
+        _ => "command",
+    };
+    handler.get(field).and_then(|k| k.as_str()) == Some(key)
+}
+
+/// Event name as it appears in settings.json
+fn event_name(event: HookEvent) -> String {
+    serde_json::to_value(event)
+        .expect("event serialization failed")
+        .as_str()
+        .expect("event should serialize to string")
+        .to_string()
+}
+
 /// Remove hook from settings by exact match (pure function, no I/O)
 ///
 /// Removes hooks that match the event and command. If the event array becomes
@@ -183,9 +270,7 @@ pub fn remove_hook(mut value: Value, event: HookEvent, command: &str) -> Value {
         match hooks {
             Some(hooks_arr) => {
                 // Keep if no hook matches the command
-                !hooks_arr
-                    .iter()
-                    .any(|h| h.get("command").and_then(|c| c.as_str()) == Some(command))
+                !hooks_arr.iter().any(|h| has_key(h, command))
             }
             None => true, // Keep malformed entries
         }
@@ -194,6 +279,34 @@ pub fn remove_hook(mut value: Value, event: HookEvent, command: &str) -> Value {
     value
 }
 
+/// Replace a hook's handler in place (pure function, no I/O)
+///
+/// The first handler under `event` whose command matches is replaced by
+/// `handler`; its matcher group and position are kept. Returns the value
+/// unchanged if no handler matches.
+pub fn replace_hook(
+    mut value: Value,
+    event: HookEvent,
+    command: &str,
+    handler: HookHandler,
+) -> Value {
+    let handlers = value
+        .get_mut("hooks")
+        .and_then(|h| h.get_mut(event_name(event)))
+        .and_then(|e| e.as_array_mut())
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group.get_mut("hooks").and_then(|h| h.as_array_mut()))
+        .flatten();
+    for existing in handlers {
+        if has_key(existing, command) {
+            *existing = serde_json::to_value(handler).expect("handler serialization failed");
+            break;
+        }
+    }
+    value
+}
+
 /// List all hooks from settings (pure function, no I/O)
 ///
 /// Returns a list of (event, matcher, handler) tuples for all hooks in settings.
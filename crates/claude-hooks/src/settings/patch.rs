@@ -0,0 +1,482 @@
+//! Format-preserving edits to settings.json text
+//!
+//! Re-serializing the whole file would sort its keys and reformat content
+//! the user wrote by hand. Instead, the old and new values are compared and
+//! only the parts that differ are rewritten in the original text:
+//!
+//! - object members that were added, removed or changed (recursively)
+//! - array elements that were appended or removed, or changed in place
+//!
+//! New text copies the surrounding style: the file's indent unit, the
+//! indentation of the line being edited, and single-line layout where the
+//! original is on one line. Anything the patcher cannot line up with the
+//! parsed value (duplicate keys, say) falls back to replacing the smallest
+//! enclosing value.
+
+use serde::Serialize;
+use serde_json::ser::{PrettyFormatter, Serializer};
+use serde_json::{Map, Value};
+
+/// Rewrite `text` so it parses as `new`, changing as little as possible
+///
+/// Returns None if `text` is not a JSON document.
+pub(crate) fn patch(text: &str, new: &Value) -> Option<String> {
+    let old: Value = serde_json::from_str(text).ok()?;
+    let start = skip_ws(text.as_bytes(), 0);
+    let end = value_end(text.as_bytes(), start)?;
+
+    let mut patcher = Patcher {
+        text,
+        unit: indent_unit(text),
+        edits: Vec::new(),
+    };
+    patcher.value(start, end, start, &old, new);
+    Some(patcher.apply())
+}
+
+/// A replacement of `text[start..end]`
+struct Edit {
+    start: usize,
+    end: usize,
+    replacement: String,
+}
+
+/// One object member or array element in the original text
+struct Item {
+    /// Start of the member's key, or of the element
+    start: usize,
+    /// Decoded key (objects only)
+    key: Option<String>,
+    /// End of the key string (objects only)
+    key_end: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+struct Patcher<'a> {
+    text: &'a str,
+    unit: String,
+    edits: Vec<Edit>,
+}
+
+impl Patcher<'_> {
+    /// Patch the value at `start..end`; `line_pos` is where its line's
+    /// indentation is measured from (the member key for object values)
+    fn value(&mut self, start: usize, end: usize, line_pos: usize, old: &Value, new: &Value) {
+        if old == new {
+            return;
+        }
+        let patched = match (old, new) {
+            (Value::Object(old), Value::Object(new)) => self.object(start, end, old, new),
+            (Value::Array(old), Value::Array(new)) => self.array(start, end, old, new),
+            _ => false,
+        };
+        if !patched {
+            let replacement = self.render(new, line_pos);
+            self.edits.push(Edit {
+                start,
+                end,
+                replacement,
+            });
+        }
+    }
+
+    /// Patch an object member by member; false to replace it whole
+    fn object(
+        &mut self,
+        start: usize,
+        end: usize,
+        old: &Map<String, Value>,
+        new: &Map<String, Value>,
+    ) -> bool {
+        let items = match items(self.text.as_bytes(), start, end) {
+            Some(items) => items,
+            None => return false,
+        };
+        let keys: Vec<&str> = items.iter().filter_map(|i| i.key.as_deref()).collect();
+        let mut unique = keys.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        if keys.len() != old.len() || unique.len() != keys.len() {
+            return false;
+        }
+        let kept: Vec<bool> = keys.iter().map(|k| new.contains_key(*k)).collect();
+        let last_kept = match kept.iter().rposition(|k| *k) {
+            Some(last) => &items[last],
+            None => return false,
+        };
+
+        self.remove(&items, &kept);
+        for (item, key) in items.iter().zip(&keys) {
+            if let (Some(old_value), Some(new_value)) = (old.get(*key), new.get(*key)) {
+                self.value(
+                    item.value_start,
+                    item.value_end,
+                    item.start,
+                    old_value,
+                    new_value,
+                );
+            }
+        }
+
+        // New members go after the last one kept, in the same style
+        let colon = &self.text[last_kept.key_end..last_kept.value_start];
+        let separator = self.separator(last_kept.start);
+        let mut inserted = String::new();
+        for (key, value) in new.iter().filter(|(k, _)| !old.contains_key(*k)) {
+            inserted.push(',');
+            inserted.push_str(&separator);
+            inserted.push_str(&serde_json::to_string(key).expect("string serialization failed"));
+            inserted.push_str(colon);
+            inserted.push_str(&self.render(value, last_kept.start));
+        }
+        self.insert(last_kept.value_end, inserted);
+        true
+    }
+
+    /// Patch an array by appending, removing or editing elements in place;
+    /// false to replace it whole
+    fn array(&mut self, start: usize, end: usize, old: &[Value], new: &[Value]) -> bool {
+        let items = match items(self.text.as_bytes(), start, end) {
+            Some(items) if items.len() == old.len() && !items.is_empty() && !new.is_empty() => {
+                items
+            }
+            _ => return false,
+        };
+
+        if old.len() == new.len() {
+            for ((item, old_value), new_value) in items.iter().zip(old).zip(new) {
+                self.value(
+                    item.value_start,
+                    item.value_end,
+                    item.start,
+                    old_value,
+                    new_value,
+                );
+            }
+            return true;
+        }
+
+        if new.len() > old.len() && new[..old.len()] == *old {
+            let last = &items[items.len() - 1];
+            let separator = self.separator(last.start);
+            let mut inserted = String::new();
+            for value in &new[old.len()..] {
+                inserted.push(',');
+                inserted.push_str(&separator);
+                inserted.push_str(&self.render(value, last.start));
+            }
+            self.insert(last.value_end, inserted);
+            return true;
+        }
+
+        // Elements removed, the rest unchanged and in order
+        let mut remaining = new.iter().peekable();
+        let kept: Vec<bool> = old
+            .iter()
+            .map(|value| {
+                let keep = remaining.peek() == Some(&value);
+                if keep {
+                    remaining.next();
+                }
+                keep
+            })
+            .collect();
+        if remaining.next().is_some() {
+            return false;
+        }
+        self.remove(&items, &kept);
+        true
+    }
+
+    /// Delete the items not kept, with their commas; at least one is kept
+    fn remove(&mut self, items: &[Item], kept: &[bool]) {
+        let first_kept = kept.iter().position(|k| *k).unwrap_or(items.len());
+        if first_kept > 0 {
+            // Leading items go up to the first kept one
+            self.edits.push(Edit {
+                start: items[0].start,
+                end: items[first_kept].start,
+                replacement: String::new(),
+            });
+        }
+        for i in first_kept + 1..items.len() {
+            if !kept[i] {
+                // Later items go from the end of the one before
+                self.edits.push(Edit {
+                    start: items[i - 1].value_end,
+                    end: items[i].value_end,
+                    replacement: String::new(),
+                });
+            }
+        }
+    }
+
+    fn insert(&mut self, at: usize, text: String) {
+        if !text.is_empty() {
+            self.edits.push(Edit {
+                start: at,
+                end: at,
+                replacement: text,
+            });
+        }
+    }
+
+    /// Whitespace between a comma and the next item, copied from the item
+    /// at `pos`: a newline and its indentation, or nothing on one line
+    fn separator(&self, pos: usize) -> String {
+        match line_indent(self.text, pos) {
+            Some(indent) => format!("\n{}", indent),
+            None => String::new(),
+        }
+    }
+
+    /// Serialize `value` for the line containing `pos`
+    fn render(&self, value: &Value, pos: usize) -> String {
+        match line_indent(self.text, pos) {
+            None => serde_json::to_string(value).expect("value serialization failed"),
+            Some(indent) => {
+                let mut buf = Vec::new();
+                let formatter = PrettyFormatter::with_indent(self.unit.as_bytes());
+                value
+                    .serialize(&mut Serializer::with_formatter(&mut buf, formatter))
+                    .expect("value serialization failed");
+                // Raw newlines only occur between tokens, never inside strings
+                String::from_utf8(buf)
+                    .expect("serde_json writes UTF-8")
+                    .replace('\n', &format!("\n{}", indent))
+            }
+        }
+    }
+
+    fn apply(mut self) -> String {
+        // Back to front, so earlier offsets stay valid; at the same offset a
+        // removal runs before an insertion
+        self.edits
+            .sort_by(|a, b| b.start.cmp(&a.start).then(b.end.cmp(&a.end)));
+        let mut text = self.text.to_string();
+        for edit in self.edits {
+            text.replace_range(edit.start..edit.end, &edit.replacement);
+        }
+        text
+    }
+}
+
+/// Indentation of the line containing `pos`, if only whitespace precedes
+/// `pos` on that line (None for items sharing a line with other tokens)
+fn line_indent(text: &str, pos: usize) -> Option<&str> {
+    let line_start = text[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let prefix = &text[line_start..pos];
+    if pos > 0 && line_start == 0 {
+        return None;
+    }
+    prefix
+        .chars()
+        .all(|c| c == ' ' || c == '\t')
+        .then_some(prefix)
+}
+
+/// Indent unit of the file: the indentation of its first indented line
+fn indent_unit(text: &str) -> String {
+    text.lines()
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+        .to_string()
+}
+
+/// Members or elements of the object or array at `start..end`
+fn items(bytes: &[u8], start: usize, end: usize) -> Option<Vec<Item>> {
+    let is_object = bytes[start] == b'{';
+    let close = end - 1;
+    let mut items = Vec::new();
+    let mut i = skip_ws(bytes, start + 1);
+    if i == close {
+        return Some(items);
+    }
+    loop {
+        let item_start = i;
+        let (key, key_end) = if is_object {
+            let key_end = string_end(bytes, i)?;
+            let key: String = serde_json::from_slice(&bytes[i..key_end]).ok()?;
+            i = skip_ws(bytes, key_end);
+            if bytes.get(i) != Some(&b':') {
+                return None;
+            }
+            i = skip_ws(bytes, i + 1);
+            (Some(key), key_end)
+        } else {
+            (None, i)
+        };
+        let value_end = value_end(bytes, i)?;
+        items.push(Item {
+            start: item_start,
+            key,
+            key_end,
+            value_start: i,
+            value_end,
+        });
+        i = skip_ws(bytes, value_end);
+        match bytes.get(i) {
+            Some(b',') => i = skip_ws(bytes, i + 1),
+            _ if i == close => return Some(items),
+            _ => return None,
+        }
+    }
+}
+
+fn skip_ws(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    i
+}
+
+/// End (exclusive) of the string starting at `i`
+fn string_end(bytes: &[u8], i: usize) -> Option<usize> {
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    let mut j = i + 1;
+    loop {
+        match bytes.get(j)? {
+            b'\\' => j += 2,
+            b'"' => return Some(j + 1),
+            _ => j += 1,
+        }
+    }
+}
+
+/// End (exclusive) of the value starting at `i`
+fn value_end(bytes: &[u8], i: usize) -> Option<usize> {
+    match bytes.get(i)? {
+        b'"' => string_end(bytes, i),
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut j = i;
+            loop {
+                match bytes.get(j)? {
+                    b'"' => {
+                        j = string_end(bytes, j)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(j + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                j += 1;
+            }
+        }
+        _ => {
+            let mut j = i;
+            while bytes
+                .get(j)
+                .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+            {
+                j += 1;
+            }
+            (j > i).then_some(j)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patched(text: &str, new: Value) -> String {
+        let result = patch(text, &new).expect("patch should succeed");
+        assert_eq!(
+            serde_json::from_str::<Value>(&result).expect("result should parse"),
+            new
+        );
+        result
+    }
+
+    #[test]
+    fn test_unrelated_content_is_untouched() {
+        let text = r#"{
+    "model": "opus",
+    "env": {"B": "2", "A": "1"},
+    "hooks": {
+        "Stop": [
+            {"hooks": [{"type": "command", "command": "a.sh"}]}
+        ]
+    }
+}
+"#;
+        let mut new: Value = serde_json::from_str(text).expect("parse");
+        new["hooks"]["Stop"]
+            .as_array_mut()
+            .expect("array")
+            .push(json!({ "hooks": [{ "type": "command", "command": "b.sh" }] }));
+        assert_eq!(
+            patched(text, new),
+            r#"{
+    "model": "opus",
+    "env": {"B": "2", "A": "1"},
+    "hooks": {
+        "Stop": [
+            {"hooks": [{"type": "command", "command": "a.sh"}]},
+            {
+                "hooks": [
+                    {
+                        "command": "b.sh",
+                        "type": "command"
+                    }
+                ]
+            }
+        ]
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_members_added_and_removed() {
+        let text = "{\n  \"z\": 1,\n  \"hooks\": {\n    \"Stop\": [],\n    \"SessionEnd\": []\n  },\n  \"a\": true\n}";
+        let new = json!({ "z": 1, "a": true, "hooks": { "SessionEnd": [], "PreCompact": [] } });
+        assert_eq!(
+            patched(text, new),
+            "{\n  \"z\": 1,\n  \"hooks\": {\n    \"SessionEnd\": [],\n    \"PreCompact\": []\n  },\n  \"a\": true\n}"
+        );
+
+        let new = json!({ "z": 1, "a": true });
+        assert_eq!(patched(text, new), "{\n  \"z\": 1,\n  \"a\": true\n}");
+
+        let text = "{\"a\":1}";
+        assert_eq!(
+            patched(text, json!({ "a": 1, "hooks": { "Stop": [] } })),
+            "{\"a\":1,\"hooks\":{\"Stop\":[]}}"
+        );
+    }
+
+    #[test]
+    fn test_array_elements_removed_in_place() {
+        let text = "[\n  1,\n  {\"x\": 2},\n  3,\n  4\n]";
+        assert_eq!(
+            patched(text, json!([{ "x": 2 }, 4])),
+            "[\n  {\"x\": 2},\n  4\n]"
+        );
+        assert_eq!(patched(text, json!([1, 3])), "[\n  1,\n  3\n]");
+        assert_eq!(patched(text, json!([])), "[]");
+        assert_eq!(patched("{}", json!({})), "{}");
+    }
+
+    #[test]
+    fn test_falls_back_on_duplicate_keys_and_rejects_invalid_json() {
+        let text = "{\"a\": 1, \"a\": 2, \"b\": 3}";
+        assert_eq!(
+            patched(text, json!({ "a": 2, "b": 4 })),
+            "{\n  \"a\": 2,\n  \"b\": 4\n}"
+        );
+        assert!(patch("{ broken", &json!({})).is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::types::CommandHandler;
 use serde_json::json;
 use serial_test::serial;
 
@@ -14,13 +15,12 @@ fn test_add_hook_creates_structure() {
         "cleanupPeriodDays": 7
     });
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
+    });
 
     let result = add_hook(settings, HookEvent::Stop, handler, None);
 
@@ -54,13 +54,12 @@ fn test_add_hook_with_matcher() {
         "hooks": {}
     });
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/pre-bash.sh".to_string(),
         timeout: Some(10),
         r#async: None,
         status_message: None,
-    };
+    });
 
     let result = add_hook(
         settings,
@@ -94,13 +93,12 @@ fn test_add_hook_to_existing_event() {
         }
     });
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/new/hook.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
     let result = add_hook(settings, HookEvent::Stop, handler, None);
 
@@ -244,15 +242,15 @@ fn test_list_hooks_multiple() {
     assert!(stop.is_some());
     let (_, matcher, handler) = stop.unwrap();
     assert!(matcher.is_none());
-    assert_eq!(handler.command, "/stop.sh");
-    assert_eq!(handler.timeout, Some(15));
+    assert_eq!(handler.key(), "/stop.sh");
+    assert_eq!(handler.timeout(), Some(15));
 
     // Find PreToolUse hook
     let pre = result.iter().find(|(e, _, _)| *e == HookEvent::PreToolUse);
     assert!(pre.is_some());
     let (_, matcher, handler) = pre.unwrap();
     assert_eq!(matcher.as_deref(), Some("Bash"));
-    assert_eq!(handler.command, "/pre-bash.sh");
+    assert_eq!(handler.key(), "/pre-bash.sh");
 }
 
 #[test]
@@ -267,13 +265,12 @@ fn test_roundtrip_preserves_non_hook_keys() {
         "syntaxHighlightingDisabled": false
     });
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
     let result = add_hook(settings, HookEvent::Stop, handler, None);
 
@@ -285,6 +282,67 @@ fn test_roundtrip_preserves_non_hook_keys() {
     assert!(result.get("syntaxHighlightingDisabled").is_some());
 }
 
+#[test]
+fn test_replace_hook_keeps_group_and_position() {
+    let settings = json!({
+        "hooks": {
+            "Stop": [
+                { "hooks": [{ "type": "command", "command": "/first.sh" }] },
+                { "matcher": "m", "hooks": [
+                    { "type": "command", "command": "/old.sh" },
+                    { "type": "command", "command": "/other.sh" }
+                ] }
+            ]
+        }
+    });
+    let handler = HookHandler::Command(CommandHandler {
+        command: "/new.sh".to_string(),
+        timeout: Some(5),
+        r#async: None,
+        status_message: None,
+    });
+
+    let updated = replace_hook(
+        settings.clone(),
+        HookEvent::Stop,
+        "/old.sh",
+        handler.clone(),
+    );
+    assert_eq!(
+        updated["hooks"]["Stop"][1]["hooks"][0],
+        json!({ "type": "command", "command": "/new.sh", "timeout": 5 })
+    );
+    assert_eq!(updated["hooks"]["Stop"][1]["matcher"], "m");
+    assert_eq!(
+        updated["hooks"]["Stop"][1]["hooks"][1]["command"],
+        "/other.sh"
+    );
+
+    let unchanged = replace_hook(settings.clone(), HookEvent::Stop, "/missing.sh", handler);
+    assert_eq!(unchanged, settings);
+}
+
+#[test]
+fn test_find_and_add_hook_value_roundtrip() {
+    let handler = json!({ "type": "command", "command": "/a.sh", "custom": [1, 2] });
+    let settings = add_hook_value(
+        json!({}),
+        HookEvent::PreToolUse,
+        handler.clone(),
+        Some("Bash".to_string()),
+    );
+
+    assert_eq!(
+        find_hook_value(&settings, HookEvent::PreToolUse, "/a.sh"),
+        Some((Some("Bash".to_string()), handler))
+    );
+    assert_eq!(find_hook_value(&settings, HookEvent::Stop, "/a.sh"), None);
+    assert_eq!(
+        find_hook_value(&settings, HookEvent::PreToolUse, "/b.sh"),
+        None
+    );
+}
+
 #[test]
 #[serial(home)]
 fn test_read_valid_settings() {
@@ -312,7 +370,7 @@ fn test_read_valid_settings() {
     )
     .expect("write failed");
 
-    let result = read_settings().expect("read_settings failed");
+    let result = read_settings(&Scope::User).expect("read_settings failed");
     assert_eq!(result.get("cleanupPeriodDays").expect("should exist"), 7);
     assert!(result.get("hooks").is_some());
 }
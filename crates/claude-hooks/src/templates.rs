@@ -0,0 +1,205 @@
+//! Named hook presets
+//!
+//! A [`Template`] declares a set of hooks whose commands contain `{param}`
+//! placeholders. [`Template::expand`] fills them in to produce
+//! [`HookSpec`]s, and [`install_template`](crate::install_template) installs
+//! the whole set. Downstream tools can declare their own templates as
+//! constants alongside the built-in [`ALL`].
+//!
+//! ```ignore
+//! use claude_hooks::{install_template, templates, Scope};
+//!
+//! let template = templates::get("lint-on-stop").expect("built-in template");
+//! install_template(Scope::User, template, &[("command", "cargo clippy")], "my-tool")?;
+//! ```
+
+use crate::error::{HookError, Result};
+use crate::matcher::Matcher;
+use crate::types::{CommandHandler, HookEvent, HookHandler, HookSpec};
+
+/// One hook in a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateHook {
+    /// Hook event
+    pub event: HookEvent,
+    /// Optional matcher in settings syntax, e.g. "Edit|Write"
+    pub matcher: Option<&'static str>,
+    /// Command with `{param}` placeholders
+    pub command: &'static str,
+    /// Optional timeout in seconds
+    pub timeout: Option<u32>,
+}
+
+/// A named set of hooks with parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Template {
+    /// Template name, e.g. "lint-on-stop"
+    pub name: &'static str,
+    /// What the hooks do
+    pub description: &'static str,
+    /// Parameters every expansion must supply
+    pub params: &'static [&'static str],
+    /// Hooks to install
+    pub hooks: &'static [TemplateHook],
+}
+
+impl Template {
+    /// Fill in the placeholders and build the hooks to install
+    ///
+    /// Parameters not declared in [`Template::params`] are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HookError::MissingTemplateParam` if a declared parameter is
+    /// not supplied.
+    pub fn expand(&self, params: &[(&str, &str)]) -> Result<Vec<HookSpec>> {
+        let mut values = Vec::with_capacity(self.params.len());
+        for &param in self.params {
+            let value = params
+                .iter()
+                .find(|(name, _)| *name == param)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| HookError::MissingTemplateParam {
+                    template: self.name.to_string(),
+                    param: param.to_string(),
+                })?;
+            values.push((param, value));
+        }
+
+        Ok(self
+            .hooks
+            .iter()
+            .map(|hook| {
+                let command = values
+                    .iter()
+                    .fold(hook.command.to_string(), |command, (param, value)| {
+                        command.replace(&format!("{{{}}}", param), value)
+                    });
+                HookSpec {
+                    event: hook.event,
+                    matcher: hook.matcher.map(Matcher::from_settings),
+                    handler: HookHandler::Command(CommandHandler {
+                        command,
+                        timeout: hook.timeout,
+                        r#async: None,
+                        status_message: None,
+                    }),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Report session state changes to a status command
+///
+/// Runs `{command} <state>` with state `start`, `working`, `idle`,
+/// `attention` or `end`.
+pub const STATUS_NOTIFIER: Template = Template {
+    name: "status-notifier",
+    description: "Run a command with the session state on every state change",
+    params: &["command"],
+    hooks: &[
+        TemplateHook {
+            event: HookEvent::SessionStart,
+            matcher: None,
+            command: "{command} start",
+            timeout: Some(10),
+        },
+        TemplateHook {
+            event: HookEvent::UserPromptSubmit,
+            matcher: None,
+            command: "{command} working",
+            timeout: Some(10),
+        },
+        TemplateHook {
+            event: HookEvent::Stop,
+            matcher: None,
+            command: "{command} idle",
+            timeout: Some(10),
+        },
+        TemplateHook {
+            event: HookEvent::Notification,
+            matcher: None,
+            command: "{command} attention",
+            timeout: Some(10),
+        },
+        TemplateHook {
+            event: HookEvent::SessionEnd,
+            matcher: None,
+            command: "{command} end",
+            timeout: Some(10),
+        },
+    ],
+};
+
+/// Run a linter whenever Claude finishes a response
+pub const LINT_ON_STOP: Template = Template {
+    name: "lint-on-stop",
+    description: "Run a lint command when Claude finishes responding",
+    params: &["command"],
+    hooks: &[TemplateHook {
+        event: HookEvent::Stop,
+        matcher: None,
+        command: "{command}",
+        timeout: Some(120),
+    }],
+};
+
+/// Check every Bash command before it runs
+pub const BASH_GUARD: Template = Template {
+    name: "bash-guard",
+    description: "Run a checker before every Bash tool call",
+    params: &["command"],
+    hooks: &[TemplateHook {
+        event: HookEvent::PreToolUse,
+        matcher: Some("Bash"),
+        command: "{command}",
+        timeout: Some(30),
+    }],
+};
+
+/// Built-in templates
+pub const ALL: &[Template] = &[STATUS_NOTIFIER, LINT_ON_STOP, BASH_GUARD];
+
+/// Look up a built-in template by name
+pub fn get(name: &str) -> Option<&'static Template> {
+    ALL.iter().find(|template| template.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_expand_fills_placeholders() {
+        let specs = STATUS_NOTIFIER
+            .expand(&[("command", "notify --session"), ("unused", "x")])
+            .expect("expand failed");
+        assert_eq!(specs.len(), 5);
+        assert_eq!(specs[2].event, HookEvent::Stop);
+        assert_eq!(specs[2].handler.key(), "notify --session idle");
+        assert_eq!(specs[2].handler.timeout(), Some(10));
+
+        let specs = BASH_GUARD
+            .expand(&[("command", "/bin/guard")])
+            .expect("expand failed");
+        assert_eq!(specs[0].matcher, Some(Matcher::tool("Bash")));
+    }
+
+    #[test]
+    fn test_expand_requires_params() {
+        let result = LINT_ON_STOP.expand(&[]);
+        assert!(matches!(
+            result,
+            Err(Error::Hook(HookError::MissingTemplateParam { ref param, .. })) if param == "command"
+        ));
+    }
+
+    #[test]
+    fn test_get_by_name() {
+        assert_eq!(get("lint-on-stop"), Some(&LINT_ON_STOP));
+        assert!(get("nope").is_none());
+        assert!(ALL.iter().all(|t| get(t.name).is_some()));
+    }
+}
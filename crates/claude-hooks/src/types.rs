@@ -1,9 +1,12 @@
 //! Core domain types for claude-hooks
 //!
 //! This module defines the types that model Claude Code hooks, including
-//! HookEvent, HookHandler, RegistryEntry, and ListEntry.
+//! HookEvent, HookHandler, Scope, RegistryEntry, and ListEntry.
 
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::matcher::Matcher;
 
 /// Claude Code hook events
 ///
@@ -37,15 +40,27 @@ pub enum HookEvent {
     SessionEnd,
 }
 
-/// Hook handler configuration (matches Claude's settings.json structure)
+/// Hook handler (matches Claude's settings.json structure)
 ///
-/// This is the innermost handler object inside a matcher group's `hooks` array.
+/// This is the innermost handler object inside a matcher group's `hooks`
+/// array, tagged by its `type` field. Types this crate does not model are
+/// kept verbatim in [`HookHandler::Other`] so they survive a round trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookHandler {
+    /// `"type": "command"`: run a shell command
+    Command(CommandHandler),
+    /// `"type": "prompt"`: ask a model to evaluate a prompt
+    Prompt(PromptHandler),
+    /// `"type": "agent"`: run a subagent with a prompt
+    Agent(PromptHandler),
+    /// Any other type, as found in settings
+    Other(serde_json::Value),
+}
+
+/// Fields of a `"type": "command"` handler
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct HookHandler {
-    /// Handler type: "command", "prompt", or "agent"
-    #[serde(rename = "type")]
-    pub r#type: String,
-    /// Full command string with arguments (for type="command")
+pub struct CommandHandler {
+    /// Full command string with arguments
     pub command: String,
     /// Optional timeout in seconds (default 600)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -58,6 +73,228 @@ pub struct HookHandler {
     pub status_message: Option<String>,
 }
 
+/// Fields of a `"type": "prompt"` or `"type": "agent"` handler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptHandler {
+    /// Prompt text; `$ARGUMENTS` is replaced with the hook input
+    pub prompt: String,
+    /// Optional model override
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Optional timeout in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+    /// Optional custom spinner message
+    #[serde(skip_serializing_if = "Option::is_none", rename = "statusMessage")]
+    pub status_message: Option<String>,
+}
+
+impl HookHandler {
+    /// Value of the `type` field ("command", "prompt", "agent", ...)
+    pub fn type_name(&self) -> &str {
+        match self {
+            Self::Command(_) => "command",
+            Self::Prompt(_) => "prompt",
+            Self::Agent(_) => "agent",
+            Self::Other(value) => value.get("type").and_then(|t| t.as_str()).unwrap_or(""),
+        }
+    }
+
+    /// String identifying the hook within its event: the command for
+    /// command handlers, the prompt for prompt and agent handlers
+    ///
+    /// This is what the registry records as `command` and what
+    /// [`uninstall`](crate::uninstall) and friends take. Empty for other
+    /// types, which cannot be managed.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Command(handler) => &handler.command,
+            Self::Prompt(handler) | Self::Agent(handler) => &handler.prompt,
+            Self::Other(_) => "",
+        }
+    }
+
+    /// Command string, for command handlers
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            Self::Command(handler) => Some(&handler.command),
+            _ => None,
+        }
+    }
+
+    /// Timeout in seconds, if set
+    pub fn timeout(&self) -> Option<u32> {
+        match self {
+            Self::Command(handler) => handler.timeout,
+            Self::Prompt(handler) | Self::Agent(handler) => handler.timeout,
+            Self::Other(value) => value
+                .get("timeout")
+                .and_then(|t| t.as_u64())
+                .and_then(|t| u32::try_from(t).ok()),
+        }
+    }
+
+    /// Async flag, for command handlers
+    pub fn is_async(&self) -> Option<bool> {
+        match self {
+            Self::Command(handler) => handler.r#async,
+            _ => None,
+        }
+    }
+}
+
+/// Serialized form of a modeled handler: its fields plus the `type` tag
+#[derive(Serialize)]
+struct Tagged<'a, T> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    #[serde(flatten)]
+    fields: &'a T,
+}
+
+impl Serialize for HookHandler {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let kind = self.type_name();
+        match self {
+            Self::Command(fields) => Tagged { kind, fields }.serialize(serializer),
+            Self::Prompt(fields) | Self::Agent(fields) => {
+                Tagged { kind, fields }.serialize(serializer)
+            }
+            Self::Other(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HookHandler {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match value.get("type").and_then(|t| t.as_str()) {
+            Some("command") => serde_json::from_value(value)
+                .map(Self::Command)
+                .map_err(D::Error::custom),
+            Some("prompt") => serde_json::from_value(value)
+                .map(Self::Prompt)
+                .map_err(D::Error::custom),
+            Some("agent") => serde_json::from_value(value)
+                .map(Self::Agent)
+                .map_err(D::Error::custom),
+            _ => Ok(Self::Other(value)),
+        }
+    }
+}
+
+/// Settings file a hook lives in
+///
+/// Mirrors Claude Code's settings hierarchy. Project and local scopes take
+/// the project root directory (the one containing `.claude/`). Build them
+/// with [`Scope::project`] and [`Scope::local`], which canonicalize the root
+/// so `./app`, `/work/app/` and a symlink to it name the same project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// `~/.claude/settings.json`, applies to every project
+    #[default]
+    User,
+    /// `<root>/.claude/settings.json`, checked in and shared with the team
+    Project(PathBuf),
+    /// `<root>/.claude/settings.local.json`, personal and not checked in
+    Local(PathBuf),
+}
+
+impl Scope {
+    /// Project scope for the project at `root`, canonicalized
+    ///
+    /// A root that cannot be canonicalized (for example, one that does not
+    /// exist yet) is used as given.
+    pub fn project(root: impl Into<PathBuf>) -> Self {
+        Scope::Project(canonical_root(&root.into()))
+    }
+
+    /// Local scope for the project at `root`, canonicalized like
+    /// [`Scope::project`]
+    pub fn local(root: impl Into<PathBuf>) -> Self {
+        Scope::Local(canonical_root(&root.into()))
+    }
+
+    /// Scope name as stored in the registry: "user", "project", or "local"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scope::User => "user",
+            Scope::Project(_) => "project",
+            Scope::Local(_) => "local",
+        }
+    }
+
+    /// Project root for project and local scopes
+    pub fn project_root(&self) -> Option<&Path> {
+        match self {
+            Scope::User => None,
+            Scope::Project(root) | Scope::Local(root) => Some(root),
+        }
+    }
+
+    /// Path to the settings file for this scope
+    pub fn settings_path(&self) -> PathBuf {
+        match self {
+            Scope::User => crate::settings::settings_path(),
+            Scope::Project(root) => root.join(".claude").join("settings.json"),
+            Scope::Local(root) => root.join(".claude").join("settings.local.json"),
+        }
+    }
+
+    /// Directory for claude-hooks' own files about this scope's settings
+    ///
+    /// `~/.claude` for the user scope. Project and local scopes get a
+    /// per-project directory under the user data dir, so backups and
+    /// lockfiles are never written into (or committed with) a repository.
+    pub(crate) fn state_dir(&self) -> PathBuf {
+        match self {
+            Scope::User => self
+                .settings_path()
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            Scope::Project(root) | Scope::Local(root) => crate::registry::registry_path()
+                .with_file_name("projects")
+                .join(project_key(&canonical_root(root))),
+        }
+    }
+}
+
+/// `root` with symlinks and `.`/`..` resolved, or as given if that fails
+pub(crate) fn canonical_root(root: &Path) -> PathBuf {
+    std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf())
+}
+
+/// Directory name for a project root: the readable path plus a hash of it
+///
+/// The hash (64-bit FNV-1a, stable across Rust releases) keeps roots that
+/// sanitize to the same name apart.
+fn project_key(root: &Path) -> String {
+    let path = root.to_string_lossy();
+    let name: String = path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let hash = path.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{}-{:016x}", name.trim_matches('-'), hash)
+}
+
+/// A hook to install: event, optional matcher and handler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookSpec {
+    /// Hook event
+    pub event: HookEvent,
+    /// Optional matcher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<Matcher>,
+    /// Hook handler configuration
+    pub handler: HookHandler,
+}
+
 /// Matcher group in Claude Code hooks structure
 ///
 /// Each event has an array of matcher groups. Each group has an optional
@@ -77,13 +314,15 @@ pub struct RegistryEntry {
     // Identity fields (composite key - D22)
     /// Hook event
     pub event: HookEvent,
-    /// Optional matcher regex (None for hooks without matcher)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub matcher: Option<String>,
+    /// Optional matcher (None for hooks without matcher); version 1
+    /// registries stored the raw settings string instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<Matcher>,
     /// Handler type
     #[serde(rename = "type")]
     pub r#type: String,
-    /// Command string
+    /// Command string ([`HookHandler::key`]: the prompt for prompt and agent
+    /// handlers)
     pub command: String,
 
     // Configuration fields (not part of identity)
@@ -95,8 +334,11 @@ pub struct RegistryEntry {
     pub r#async: Option<bool>,
 
     // Metadata fields
-    /// Scope (e.g., "user" in v0.1)
+    /// Scope name ("user", "project", or "local", see [`Scope::name`])
     pub scope: String,
+    /// Project root for project and local scopes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_root: Option<PathBuf>,
     /// Whether hook is enabled
     pub enabled: bool,
     /// Timestamp when hook was added (yyyyMMdd-hhmmss)
@@ -112,6 +354,9 @@ pub struct RegistryEntry {
     /// Optional flag for whether hook is optional
     #[serde(skip_serializing_if = "Option::is_none")]
     pub optional: Option<bool>,
+    /// Handler exactly as it was in settings, kept while the hook is disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stashed: Option<serde_json::Value>,
 }
 
 impl RegistryEntry {
@@ -121,10 +366,48 @@ impl RegistryEntry {
     pub fn matches(&self, event: HookEvent, command: &str) -> bool {
         self.event == event && self.command == command
     }
+
+    /// Check if this entry was installed in the given scope
+    ///
+    /// Entries written before scopes were tracked have scope "user" and no
+    /// project root, so they belong to [`Scope::User`]. Project roots match
+    /// if they canonicalize to the same directory.
+    pub fn in_scope(&self, scope: &Scope) -> bool {
+        if self.scope != scope.name() {
+            return false;
+        }
+        match (self.project_root.as_deref(), scope.project_root()) {
+            (None, None) => true,
+            (Some(recorded), Some(root)) => {
+                recorded == root || canonical_root(recorded) == canonical_root(root)
+            }
+            _ => false,
+        }
+    }
+
+    /// Handler rebuilt from the recorded type, key and configuration
+    pub fn handler(&self) -> HookHandler {
+        let prompt = || PromptHandler {
+            prompt: self.command.clone(),
+            model: None,
+            timeout: self.timeout,
+            status_message: None,
+        };
+        match self.r#type.as_str() {
+            "prompt" => HookHandler::Prompt(prompt()),
+            "agent" => HookHandler::Agent(prompt()),
+            _ => HookHandler::Command(CommandHandler {
+                command: self.command.clone(),
+                timeout: self.timeout,
+                r#async: self.r#async,
+                status_message: None,
+            }),
+        }
+    }
 }
 
 /// Entry returned by list() function
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ListEntry {
     /// Hook event
     pub event: HookEvent,
@@ -136,8 +419,36 @@ pub struct ListEntry {
     pub metadata: Option<RegistryMetadata>,
 }
 
+/// Which hooks [`list_filtered`](crate::list_filtered) returns
+///
+/// Every condition that is set must hold. The default lists every hook in
+/// the user settings file, like [`list`](crate::list).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFilter {
+    /// Settings file to list
+    pub scope: Scope,
+    /// Only hooks on this event
+    pub event: Option<HookEvent>,
+    /// Only managed hooks recorded with this installer
+    pub installed_by: Option<String>,
+    /// Only hooks installed through this crate
+    pub managed_only: bool,
+}
+
+impl ListFilter {
+    /// True if a hook passes the filter; `entry` is its registry entry
+    pub fn matches(&self, event: HookEvent, entry: Option<&RegistryEntry>) -> bool {
+        self.event.map_or(true, |e| e == event)
+            && (!self.managed_only || entry.is_some())
+            && self
+                .installed_by
+                .as_deref()
+                .map_or(true, |owner| entry.is_some_and(|e| e.installed_by == owner))
+    }
+}
+
 /// Subset of registry metadata for list output
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct RegistryMetadata {
     /// Timestamp when hook was added
     pub added_at: String,
@@ -151,10 +462,52 @@ pub struct RegistryMetadata {
     pub optional: Option<bool>,
 }
 
+/// Optional descriptive metadata recorded with a managed hook
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookMetadata {
+    /// What the hook does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Why the hook was added
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Whether the hook can be removed without breaking its owner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+}
+
+/// What [`repair`](crate::repair) changed or found in the registry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RepairReport {
+    /// Entries dropped because their hook is no longer in settings
+    pub removed: Vec<RegistryEntry>,
+    /// Entries whose matcher, type, timeout or async flag was refreshed from
+    /// settings (new values)
+    pub updated: Vec<RegistryEntry>,
+    /// Entries in any scope whose project directory no longer exists; left in
+    /// the registry because the directory may only be unmounted
+    pub orphaned: Vec<RegistryEntry>,
+}
+
+impl RepairReport {
+    /// True if the registry was already consistent and nothing was flagged
+    pub fn is_clean(&self) -> bool {
+        self.removed.is_empty() && self.updated.is_empty() && self.orphaned.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_project_key_is_readable_and_distinct() {
+        let key = project_key(Path::new("/home/u/my project"));
+        assert!(key.starts_with("home-u-my-project-"));
+        assert_eq!(key, project_key(Path::new("/home/u/my project")));
+        assert_ne!(key, project_key(Path::new("/home/u/my-project")));
+    }
+
     #[test]
     fn test_hook_event_serialization() {
         let event = HookEvent::Stop;
@@ -194,13 +547,12 @@ mod tests {
 
     #[test]
     fn test_hook_handler_roundtrip() {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: "/path/to/stop.sh".to_string(),
             timeout: Some(600),
             r#async: None,
             status_message: None,
-        };
+        });
         let json = serde_json::to_string(&handler).expect("serialization failed");
         let deserialized: HookHandler =
             serde_json::from_str(&json).expect("deserialization failed");
@@ -210,43 +562,77 @@ mod tests {
     #[test]
     fn test_hook_handler_optional_fields() {
         // Test with all optional fields present
-        let handler_full = HookHandler {
-            r#type: "command".to_string(),
+        let handler_full = HookHandler::Command(CommandHandler {
             command: "/path/to/script.sh".to_string(),
             timeout: Some(300),
             r#async: Some(true),
             status_message: Some("Running validation...".to_string()),
-        };
+        });
         let json = serde_json::to_string(&handler_full).expect("serialization failed");
         let deserialized: HookHandler =
             serde_json::from_str(&json).expect("deserialization failed");
         assert_eq!(handler_full, deserialized);
 
         // Test with all optional fields absent
-        let handler_minimal = HookHandler {
-            r#type: "command".to_string(),
+        let handler_minimal = HookHandler::Command(CommandHandler {
             command: "/path/to/script.sh".to_string(),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
+        });
         let json = serde_json::to_string(&handler_minimal).expect("serialization failed");
         let deserialized: HookHandler =
             serde_json::from_str(&json).expect("deserialization failed");
         assert_eq!(handler_minimal, deserialized);
     }
 
+    #[test]
+    fn test_hook_handler_prompt_and_agent() {
+        let json = serde_json::json!({
+            "type": "prompt",
+            "prompt": "Is the task done? $ARGUMENTS",
+            "model": "haiku",
+            "timeout": 30
+        });
+        let handler: HookHandler = serde_json::from_value(json.clone()).expect("deserialize");
+        assert_eq!(handler.type_name(), "prompt");
+        assert_eq!(handler.key(), "Is the task done? $ARGUMENTS");
+        assert_eq!(handler.command(), None);
+        assert_eq!(handler.timeout(), Some(30));
+        assert_eq!(serde_json::to_value(&handler).expect("serialize"), json);
+
+        let agent: HookHandler =
+            serde_json::from_str(r#"{"type": "agent", "prompt": "Review the diff"}"#)
+                .expect("deserialize");
+        assert!(matches!(agent, HookHandler::Agent(ref a) if a.prompt == "Review the diff"));
+    }
+
+    #[test]
+    fn test_hook_handler_unknown_type_preserved() {
+        let json = serde_json::json!({
+            "type": "webhook",
+            "url": "https://example.com/hook",
+            "headers": { "X-Token": "abc" },
+            "timeout": 5
+        });
+        let handler: HookHandler = serde_json::from_value(json.clone()).expect("deserialize");
+        assert!(matches!(handler, HookHandler::Other(_)));
+        assert_eq!(handler.type_name(), "webhook");
+        assert_eq!(handler.key(), "");
+        assert_eq!(handler.timeout(), Some(5));
+        assert_eq!(serde_json::to_value(&handler).expect("serialize"), json);
+    }
+
     #[test]
     fn test_matcher_group_roundtrip() {
         let group = MatcherGroup {
             matcher: Some("Bash".to_string()),
-            hooks: vec![HookHandler {
-                r#type: "command".to_string(),
+            hooks: vec![HookHandler::Command(CommandHandler {
                 command: "/path/to/script.sh".to_string(),
                 timeout: Some(10),
                 r#async: None,
                 status_message: None,
-            }],
+            })],
         };
         let json = serde_json::to_string(&group).expect("serialization failed");
         let deserialized: MatcherGroup =
@@ -258,13 +644,12 @@ mod tests {
     fn test_matcher_group_without_matcher() {
         let group = MatcherGroup {
             matcher: None,
-            hooks: vec![HookHandler {
-                r#type: "command".to_string(),
+            hooks: vec![HookHandler::Command(CommandHandler {
                 command: "/path/to/script.sh".to_string(),
                 timeout: None,
                 r#async: None,
                 status_message: None,
-            }],
+            })],
         };
         let json = serde_json::to_string(&group).expect("serialization failed");
         assert!(
@@ -286,12 +671,14 @@ mod tests {
             timeout: Some(600),
             r#async: None,
             scope: "user".to_string(),
+            project_root: None,
             enabled: true,
             added_at: "20260203-143022".to_string(),
             installed_by: "acd".to_string(),
             description: Some("Test hook".to_string()),
             reason: Some("Testing".to_string()),
             optional: Some(false),
+            stashed: None,
         };
         let json = serde_json::to_string(&entry).expect("serialization failed");
         let deserialized: RegistryEntry =
@@ -309,12 +696,14 @@ mod tests {
             timeout: None,
             r#async: None,
             scope: "user".to_string(),
+            project_root: None,
             enabled: true,
             added_at: "20260203-143022".to_string(),
             installed_by: "acd".to_string(),
             description: None,
             reason: None,
             optional: None,
+            stashed: None,
         };
 
         // Should match same event and command
@@ -329,4 +718,64 @@ mod tests {
         // Should not match both different
         assert!(!entry.matches(HookEvent::SessionStart, "/different/path"));
     }
+
+    #[test]
+    fn test_scope_settings_paths() {
+        let root = PathBuf::from("/work/app");
+        assert_eq!(
+            Scope::Project(root.clone()).settings_path(),
+            PathBuf::from("/work/app/.claude/settings.json")
+        );
+        assert_eq!(
+            Scope::Local(root.clone()).settings_path(),
+            PathBuf::from("/work/app/.claude/settings.local.json")
+        );
+        assert!(Scope::User
+            .settings_path()
+            .ends_with(".claude/settings.json"));
+        assert_eq!(Scope::Local(root).name(), "local");
+    }
+
+    #[test]
+    fn test_registry_entry_in_scope() {
+        // Entries from before scope tracking have no project_root
+        let json = r#"{"event": "Stop", "type": "command", "command": "/stop.sh",
+            "scope": "user", "enabled": true, "added_at": "20260203-143022",
+            "installed_by": "acd"}"#;
+        let mut entry: RegistryEntry = serde_json::from_str(json).expect("deserialization failed");
+        assert!(entry.in_scope(&Scope::User));
+
+        let root = PathBuf::from("/work/app");
+        entry.scope = "project".to_string();
+        entry.project_root = Some(root.clone());
+        assert!(entry.in_scope(&Scope::Project(root.clone())));
+        assert!(!entry.in_scope(&Scope::Local(root)));
+        assert!(!entry.in_scope(&Scope::Project(PathBuf::from("/work/other"))));
+        assert!(!entry.in_scope(&Scope::User));
+    }
+
+    #[test]
+    fn test_scope_constructors_canonicalize_root() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let root = dir.path().join("app");
+        std::fs::create_dir_all(root.join("sub")).expect("Failed to create project dir");
+        let canonical = std::fs::canonicalize(&root).expect("canonicalize");
+
+        let scope = Scope::project(root.join("sub").join(".."));
+        assert_eq!(scope, Scope::Project(canonical.clone()));
+        assert_eq!(Scope::local(&root), Scope::Local(canonical.clone()));
+        // Roots that do not exist are kept as given
+        assert_eq!(
+            Scope::project("/no/such/root"),
+            Scope::Project(PathBuf::from("/no/such/root"))
+        );
+
+        // Entries recorded with another spelling of the root still match
+        let json = r#"{"event": "Stop", "type": "command", "command": "/stop.sh",
+            "scope": "project", "enabled": true, "added_at": "20260203-143022",
+            "installed_by": "acd"}"#;
+        let mut entry: RegistryEntry = serde_json::from_str(json).expect("deserialization failed");
+        entry.project_root = Some(root.join("."));
+        assert!(entry.in_scope(&scope));
+    }
 }
@@ -0,0 +1,358 @@
+//! Settings structure validation
+//!
+//! Walks the `hooks` object of a settings file and reports everything Claude
+//! Code would reject or silently ignore. Each [`Diagnostic`] is located by a
+//! JSON pointer so manual edits can be found and fixed.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+use crate::types::HookEvent;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    /// Probably unintended, but Claude Code will still load the settings
+    Warning,
+    /// Claude Code will reject or ignore this part of the settings
+    Error,
+}
+
+impl fmt::Display for DiagnosticLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// One problem found in a settings file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Severity
+    pub level: DiagnosticLevel,
+    /// JSON pointer to the offending value, e.g. `/hooks/Stop/0/hooks/1`
+    pub pointer: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{}: {}: {}", self.level, pointer, self.message)
+    }
+}
+
+/// Handler types Claude Code understands
+const HANDLER_TYPES: &[&str] = &["command", "prompt", "agent"];
+
+/// Collects diagnostics while walking the settings
+#[derive(Default)]
+struct Report(Vec<Diagnostic>);
+
+impl Report {
+    fn error(&mut self, pointer: &str, message: impl Into<String>) {
+        self.push(DiagnosticLevel::Error, pointer, message.into());
+    }
+
+    fn warning(&mut self, pointer: &str, message: impl Into<String>) {
+        self.push(DiagnosticLevel::Warning, pointer, message.into());
+    }
+
+    fn push(&mut self, level: DiagnosticLevel, pointer: &str, message: String) {
+        self.0.push(Diagnostic {
+            level,
+            pointer: pointer.to_string(),
+            message,
+        });
+    }
+}
+
+/// Validate the hooks structure of a parsed settings file (pure function)
+///
+/// Returns diagnostics in document order; an empty vector means the hooks
+/// are well-formed.
+pub fn validate_value(value: &Value) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+
+    let root = match value.as_object() {
+        Some(root) => root,
+        None => {
+            report.error("", "settings must be a JSON object");
+            return report.0;
+        }
+    };
+    let hooks = match root.get("hooks") {
+        None => return report.0,
+        Some(Value::Object(hooks)) => hooks,
+        Some(_) => {
+            report.error("/hooks", "\"hooks\" must be an object keyed by event name");
+            return report.0;
+        }
+    };
+
+    for (name, groups) in hooks {
+        let pointer = format!("/hooks/{}", name.replace('~', "~0").replace('/', "~1"));
+        let event: HookEvent = match serde_json::from_value(Value::String(name.clone())) {
+            Ok(event) => event,
+            Err(_) => {
+                report.error(&pointer, format!("unknown hook event \"{}\"", name));
+                continue;
+            }
+        };
+        match groups.as_array() {
+            Some(groups) => validate_groups(&mut report, &pointer, event, groups),
+            None => report.error(&pointer, "event must map to an array of matcher groups"),
+        }
+    }
+
+    report.0
+}
+
+fn validate_groups(report: &mut Report, pointer: &str, event: HookEvent, groups: &[Value]) {
+    let mut seen: Vec<&str> = Vec::new();
+
+    for (i, group) in groups.iter().enumerate() {
+        let pointer = format!("{}/{}", pointer, i);
+        let group = match group.as_object() {
+            Some(group) => group,
+            None => {
+                report.error(&pointer, "matcher group must be an object");
+                continue;
+            }
+        };
+
+        for key in group.keys() {
+            if key != "matcher" && key != "hooks" {
+                report.warning(
+                    &format!("{}/{}", pointer, key),
+                    format!("unknown matcher group key \"{}\"", key),
+                );
+            }
+        }
+        if let Some(matcher) = group.get("matcher") {
+            if !matcher.is_string() {
+                report.error(&format!("{}/matcher", pointer), "matcher must be a string");
+            }
+        }
+
+        let handlers = match group.get("hooks") {
+            Some(Value::Array(handlers)) => handlers,
+            Some(_) => {
+                report.error(&format!("{}/hooks", pointer), "\"hooks\" must be an array");
+                continue;
+            }
+            None => {
+                report.error(&pointer, "matcher group is missing \"hooks\"");
+                continue;
+            }
+        };
+
+        for (j, handler) in handlers.iter().enumerate() {
+            let pointer = format!("{}/hooks/{}", pointer, j);
+            if let Some(command) = validate_handler(report, &pointer, event, handler) {
+                if seen.contains(&command) {
+                    report.warning(
+                        &pointer,
+                        format!("duplicate handler for command \"{}\"", command),
+                    );
+                } else {
+                    seen.push(command);
+                }
+            }
+        }
+    }
+}
+
+/// Check one handler; returns its command if it has a string one
+fn validate_handler<'a>(
+    report: &mut Report,
+    pointer: &str,
+    event: HookEvent,
+    handler: &'a Value,
+) -> Option<&'a str> {
+    let handler = match handler.as_object() {
+        Some(handler) => handler,
+        None => {
+            report.error(pointer, "handler must be an object");
+            return None;
+        }
+    };
+
+    match handler.get("type") {
+        Some(Value::String(kind)) if HANDLER_TYPES.contains(&kind.as_str()) => {}
+        Some(Value::String(kind)) => {
+            report.warning(
+                &format!("{}/type", pointer),
+                format!("unknown handler type \"{}\"", kind),
+            );
+        }
+        Some(_) => report.error(&format!("{}/type", pointer), "type must be a string"),
+        None => report.error(pointer, "handler is missing \"type\""),
+    }
+
+    let is_command = handler.get("type").and_then(Value::as_str) == Some("command");
+    let command = match handler.get("command") {
+        Some(Value::String(command)) => {
+            if command.trim().is_empty() {
+                report.error(&format!("{}/command", pointer), "command is empty");
+            }
+            Some(command.as_str())
+        }
+        Some(_) => {
+            report.error(&format!("{}/command", pointer), "command must be a string");
+            None
+        }
+        None if is_command => {
+            report.error(pointer, "command handler is missing \"command\"");
+            None
+        }
+        None => None,
+    };
+
+    if let Some(kind @ ("prompt" | "agent")) = handler.get("type").and_then(Value::as_str) {
+        match handler.get("prompt") {
+            Some(Value::String(prompt)) if prompt.trim().is_empty() => {
+                report.error(&format!("{}/prompt", pointer), "prompt is empty");
+            }
+            Some(Value::String(_)) => {}
+            Some(_) => report.error(&format!("{}/prompt", pointer), "prompt must be a string"),
+            None => report.error(pointer, format!("{} handler is missing \"prompt\"", kind)),
+        }
+    }
+
+    if let Some(timeout) = handler.get("timeout") {
+        if !timeout.as_u64().is_some_and(|t| t > 0) {
+            report.error(
+                &format!("{}/timeout", pointer),
+                "timeout must be a positive number of seconds",
+            );
+        }
+    }
+    match handler.get("async") {
+        None => {}
+        Some(Value::Bool(true))
+            if !matches!(
+                event,
+                HookEvent::PostToolUse | HookEvent::PostToolUseFailure
+            ) =>
+        {
+            report.warning(
+                &format!("{}/async", pointer),
+                "async is only honored on PostToolUse and PostToolUseFailure",
+            );
+        }
+        Some(Value::Bool(_)) => {}
+        Some(_) => report.error(&format!("{}/async", pointer), "async must be a boolean"),
+    }
+    if let Some(message) = handler.get("statusMessage") {
+        if !message.is_string() {
+            report.error(
+                &format!("{}/statusMessage", pointer),
+                "statusMessage must be a string",
+            );
+        }
+    }
+
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pointers(diagnostics: &[Diagnostic]) -> Vec<(DiagnosticLevel, &str)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.level, d.pointer.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_settings_have_no_diagnostics() {
+        let settings = json!({
+            "model": "opus",
+            "hooks": {
+                "PostToolUse": [
+                    { "matcher": "Edit", "hooks": [
+                        { "type": "command", "command": "fmt.sh", "timeout": 30, "async": true }
+                    ] }
+                ],
+                "Stop": [{ "hooks": [{ "type": "prompt", "prompt": "Done?" }] }]
+            }
+        });
+        assert!(validate_value(&settings).is_empty());
+        assert!(validate_value(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_structural_errors_are_located() {
+        let settings = json!({
+            "hooks": {
+                "OnStop": [],
+                "SessionStart": { "hooks": [] },
+                "Stop": [
+                    "not a group",
+                    { "matcher": 3, "hooks": [{ "type": "command", "command": 42 }] },
+                    { "matcher": "" },
+                    { "hooks": [{ "command": "a.sh", "timeout": 0, "async": "yes" }] },
+                    { "hooks": [{ "type": "agent" }] }
+                ]
+            }
+        });
+        assert_eq!(
+            pointers(&validate_value(&settings)),
+            vec![
+                (DiagnosticLevel::Error, "/hooks/OnStop"),
+                (DiagnosticLevel::Error, "/hooks/SessionStart"),
+                (DiagnosticLevel::Error, "/hooks/Stop/0"),
+                (DiagnosticLevel::Error, "/hooks/Stop/1/matcher"),
+                (DiagnosticLevel::Error, "/hooks/Stop/1/hooks/0/command"),
+                (DiagnosticLevel::Error, "/hooks/Stop/2"),
+                (DiagnosticLevel::Error, "/hooks/Stop/3/hooks/0"),
+                (DiagnosticLevel::Error, "/hooks/Stop/3/hooks/0/timeout"),
+                (DiagnosticLevel::Error, "/hooks/Stop/3/hooks/0/async"),
+                (DiagnosticLevel::Error, "/hooks/Stop/4/hooks/0"),
+            ]
+        );
+        assert_eq!(
+            validate_value(&json!([]))[0].to_string(),
+            "error: /: settings must be a JSON object"
+        );
+    }
+
+    #[test]
+    fn test_warnings_for_suspicious_hooks() {
+        let settings = json!({
+            "hooks": {
+                "Stop": [
+                    { "hooks": [{ "type": "command", "command": "a.sh", "async": true }] },
+                    { "extra": 1, "hooks": [
+                        { "type": "command", "command": "a.sh" },
+                        { "type": "webhook", "url": "https://example.com" }
+                    ] }
+                ]
+            }
+        });
+        let diagnostics = validate_value(&settings);
+        assert_eq!(
+            pointers(&diagnostics),
+            vec![
+                (DiagnosticLevel::Warning, "/hooks/Stop/0/hooks/0/async"),
+                (DiagnosticLevel::Warning, "/hooks/Stop/1/extra"),
+                (DiagnosticLevel::Warning, "/hooks/Stop/1/hooks/0"),
+                (DiagnosticLevel::Warning, "/hooks/Stop/1/hooks/1/type"),
+            ]
+        );
+        assert!(diagnostics[2].message.contains("duplicate"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Watching settings files for external changes
+//!
+//! Requires the `watch` feature. The settings file's directory is watched
+//! rather than the file, because atomic writes (ours and most editors')
+//! replace the file and would end a watch on the old one.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
+use crate::error::{Result, SettingsError};
+use crate::types::{RegistryEntry, Scope};
+use crate::{registry, settings};
+
+/// A change to a watched settings file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsChange {
+    /// Settings file that changed
+    pub path: PathBuf,
+    /// Settings after the change (empty object if the file was deleted)
+    pub settings: Value,
+    /// Enabled managed hooks in this scope that are no longer in settings
+    pub missing: Vec<RegistryEntry>,
+}
+
+/// Handle for a running watch; watching stops when it is dropped
+pub struct SettingsWatcher {
+    _watcher: RecommendedWatcher,
+    path: PathBuf,
+}
+
+impl SettingsWatcher {
+    /// Settings file being watched
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+impl std::fmt::Debug for SettingsWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SettingsWatcher")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Current settings, treating a deleted file as empty
+fn current_settings(scope: &Scope) -> Option<Value> {
+    if !scope.settings_path().exists() {
+        return Some(Value::Object(Map::new()));
+    }
+    match settings::read_settings(scope) {
+        Ok(value) => Some(value),
+        // Usually a write in progress; the next event will have the result
+        Err(e) => {
+            log::debug!("Ignoring unreadable settings while watching: {}", e);
+            None
+        }
+    }
+}
+
+/// Enabled managed hooks in `scope` that `settings_value` does not contain
+pub(crate) fn missing_hooks(scope: &Scope, settings_value: &Value) -> Vec<RegistryEntry> {
+    let registry_entries = match registry::read_registry() {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Failed to read registry while watching settings: {}", e);
+            return Vec::new();
+        }
+    };
+    let hooks = settings::list_hooks(settings_value);
+    registry_entries
+        .into_iter()
+        .filter(|entry| entry.in_scope(scope) && entry.enabled)
+        .filter(|entry| {
+            !hooks
+                .iter()
+                .any(|(event, _, handler)| entry.matches(*event, handler.key()))
+        })
+        .collect()
+}
+
+pub(crate) fn watch<F>(scope: Scope, mut on_change: F) -> Result<SettingsWatcher>
+where
+    F: FnMut(SettingsChange) + Send + 'static,
+{
+    let path = scope.settings_path();
+    let dir = path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let file_name = path.file_name().map(|name| name.to_os_string());
+
+    let watched = path.clone();
+    let mut last = current_settings(&scope);
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                log::warn!("Settings watch error: {}", e);
+                return;
+            }
+        };
+        if event.kind.is_access()
+            || !event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name)
+        {
+            return;
+        }
+
+        // Several events arrive per write; report each new content once
+        let current = match current_settings(&scope) {
+            Some(current) => current,
+            None => return,
+        };
+        if last.as_ref() == Some(&current) {
+            return;
+        }
+        last = Some(current.clone());
+
+        on_change(SettingsChange {
+            path: watched.clone(),
+            missing: missing_hooks(&scope, &current),
+            settings: current,
+        });
+    })
+    .map_err(|e| SettingsError::Watch(e.to_string()))?;
+
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| SettingsError::Watch(format!("{}: {}", dir.display(), e)))?;
+
+    Ok(SettingsWatcher {
+        _watcher: watcher,
+        path,
+    })
+}
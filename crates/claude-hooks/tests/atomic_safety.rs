@@ -4,7 +4,7 @@
 //! Validates roundtrip preservation of all keys
 //! Tests write failure scenarios
 
-use claude_hooks::{install, uninstall, HookEvent, HookHandler};
+use claude_hooks::{install, uninstall, CommandHandler, HookEvent, HookHandler, Scope};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -66,17 +66,17 @@ fn test_settings_roundtrip_preserves_all_keys() {
     .expect("Write failed");
 
     // Install hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler.clone(), None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler.clone(), None, "test")
+        .expect("Install should succeed");
 
     // Uninstall hook
-    uninstall(HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
 
     // Verify all keys preserved
     let content =
@@ -162,14 +162,14 @@ fn test_install_preserves_existing_hook_order() {
     .expect("Write failed");
 
     // Install new hook (should append to PreToolUse)
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/third/hook.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::PreToolUse, handler, None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::PreToolUse, handler, None, "test")
+        .expect("Install should succeed");
 
     // Verify structure preserved and new hook added
     let content =
@@ -193,18 +193,18 @@ fn test_uninstall_preserves_remaining_hook_order() {
     // Install multiple hooks
     let commands = vec!["/first.sh", "/second.sh", "/third.sh", "/fourth.sh"];
     for command in &commands {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: command.to_string(),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     // Uninstall middle hook
-    uninstall(HookEvent::Stop, "/second.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/second.sh").expect("Uninstall should succeed");
 
     // Verify remaining hooks
     let content =
@@ -249,14 +249,13 @@ fn test_atomic_write_creates_temp_file() {
     std::thread::sleep(std::time::Duration::from_millis(10));
 
     // Install hook (triggers atomic write)
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Verify file was updated (mtime changed)
     let final_metadata = fs::metadata(&settings_path).expect("Metadata failed");
@@ -296,14 +295,13 @@ fn test_json_formatting_preserved() {
     .expect("Write failed");
 
     // Install hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Verify output is still pretty-formatted
     let content =
@@ -340,15 +338,15 @@ fn test_unicode_and_special_chars_preserved() {
     .expect("Write failed");
 
     // Install and uninstall hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler.clone(), None, "test").expect("Install should succeed");
-    uninstall(HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler.clone(), None, "test")
+        .expect("Install should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
 
     // Verify special chars preserved
     let content =
@@ -398,15 +396,15 @@ fn test_empty_strings_and_nulls_preserved() {
     .expect("Write failed");
 
     // Install and uninstall
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler.clone(), None, "test").expect("Install should succeed");
-    uninstall(HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler.clone(), None, "test")
+        .expect("Install should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
 
     // Verify values preserved
     let content =
@@ -446,14 +444,13 @@ fn test_large_settings_file_handled() {
     .expect("Write failed");
 
     // Install hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Verify all keys still present
     let content =
@@ -478,14 +475,14 @@ fn test_concurrent_operations_sequential() {
 
     // Install multiple hooks sequentially
     for i in 0..10 {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/path/to/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     // Verify all hooks present
@@ -4,7 +4,11 @@
 //! Verifies managed/unmanaged status tracking
 //! Tests multiple hooks and complex scenarios
 
-use claude_hooks::{install, list, uninstall, HookEvent, HookHandler};
+use claude_hooks::{
+    install, install_all, install_dry_run, install_template, list, list_backups, restore_backup,
+    templates, uninstall, uninstall_dry_run, ChangeKind, CommandHandler, HookEvent, HookHandler,
+    HookSpec, Matcher, Scope, MAX_BACKUPS,
+};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -41,28 +45,28 @@ fn setup_test_env() -> tempfile::TempDir {
 fn test_full_install_workflow() {
     let _dir = setup_test_env();
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
+    });
 
     // Install
-    install(HookEvent::Stop, handler.clone(), None, "test").expect("Install should succeed");
+    install(Scope::User, HookEvent::Stop, handler.clone(), None, "test")
+        .expect("Install should succeed");
 
     // Verify in list
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
     assert!(entries[0].managed);
-    assert_eq!(entries[0].handler.command, "/path/to/stop.sh");
+    assert_eq!(entries[0].handler.key(), "/path/to/stop.sh");
 
     // Uninstall
-    uninstall(HookEvent::Stop, "/path/to/stop.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/stop.sh").expect("Uninstall should succeed");
 
     // Verify removed
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 0);
 }
 
@@ -90,18 +94,17 @@ fn test_install_preserves_existing_hooks() {
     .expect("Write failed");
 
     // Install new hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/new/hook.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Verify both hooks exist
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 2);
 
     // One managed, one unmanaged
@@ -135,27 +138,26 @@ fn test_uninstall_preserves_unmanaged_hooks() {
     .expect("Write failed");
 
     // Install managed hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/managed/hook.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Verify both exist
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 2);
 
     // Uninstall managed hook
-    uninstall(HookEvent::Stop, "/managed/hook.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/managed/hook.sh").expect("Uninstall should succeed");
 
     // Verify unmanaged hook preserved
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
     assert!(!entries[0].managed);
-    assert_eq!(entries[0].handler.command, "/unmanaged/hook.sh");
+    assert_eq!(entries[0].handler.key(), "/unmanaged/hook.sh");
 }
 
 #[test]
@@ -171,18 +173,17 @@ fn test_multiple_hooks_different_events() {
     ];
 
     for (event, command) in &events {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: command.to_string(),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(*event, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, *event, handler, None, "test").expect("Install should succeed");
     }
 
     // Verify all hooks present
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 3);
     assert!(entries.iter().all(|e| e.managed));
 
@@ -206,28 +207,28 @@ fn test_multiple_hooks_same_event() {
     ];
 
     for command in &commands {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: command.to_string(),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     // Verify all hooks present
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 3);
     assert!(entries.iter().all(|e| e.event == HookEvent::Stop));
 
     // Uninstall middle one
-    uninstall(HookEvent::Stop, "/path/to/stop2.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/stop2.sh").expect("Uninstall should succeed");
 
     // Verify others preserved
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 2);
-    let found_commands: Vec<&str> = entries.iter().map(|e| e.handler.command.as_str()).collect();
+    let found_commands: Vec<&str> = entries.iter().map(|e| e.handler.key()).collect();
     assert!(found_commands.contains(&"/path/to/stop1.sh"));
     assert!(found_commands.contains(&"/path/to/stop3.sh"));
 }
@@ -237,27 +238,27 @@ fn test_multiple_hooks_same_event() {
 fn test_install_with_all_optional_fields() {
     let _dir = setup_test_env();
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/async.sh".to_string(),
         timeout: Some(900),
         r#async: Some(true),
         status_message: Some("Running...".to_string()),
-    };
+    });
 
     install(
+        Scope::User,
         HookEvent::PostToolUse,
         handler,
-        Some("*.rs".to_string()),
+        Some(Matcher::regex(r".*\.rs").expect("valid regex")),
         "test",
     )
     .expect("Install should succeed");
 
     // Verify all fields preserved
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
-    assert_eq!(entries[0].handler.timeout, Some(900));
-    assert_eq!(entries[0].handler.r#async, Some(true));
+    assert_eq!(entries[0].handler.timeout(), Some(900));
+    assert_eq!(entries[0].handler.is_async(), Some(true));
 }
 
 #[test]
@@ -265,18 +266,18 @@ fn test_install_with_all_optional_fields() {
 fn test_metadata_fields_populated() {
     let _dir = setup_test_env();
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
-    install(HookEvent::Stop, handler, None, "my-installer").expect("Install should succeed");
+    install(Scope::User, HookEvent::Stop, handler, None, "my-installer")
+        .expect("Install should succeed");
 
     // Verify metadata
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 1);
 
     let metadata = entries[0].metadata.as_ref().expect("Should have metadata");
@@ -292,7 +293,7 @@ fn test_list_empty_hooks_object() {
     let _dir = setup_test_env();
 
     // Verify empty list returns empty vector
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 0);
 }
 
@@ -318,15 +319,14 @@ fn test_roundtrip_preserves_settings_keys() {
     .expect("Write failed");
 
     // Install and uninstall hook
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
-    uninstall(HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/test.sh").expect("Uninstall should succeed");
 
     // Verify all non-hook keys preserved
     let content =
@@ -339,3 +339,237 @@ fn test_roundtrip_preserves_settings_keys() {
     assert_eq!(final_settings["customKey"], "should be preserved");
     assert_eq!(final_settings["statusLine"], true);
 }
+
+#[test]
+#[serial(home)]
+fn test_project_and_local_scopes_are_independent() {
+    let dir = setup_test_env();
+    let root = dir.path().join("project");
+    fs::create_dir_all(&root).expect("Failed to create project dir");
+
+    let handler = HookHandler::Command(CommandHandler {
+        command: "/path/to/stop.sh".to_string(),
+        timeout: None,
+        r#async: None,
+        status_message: None,
+    });
+    let project = Scope::Project(root.clone());
+    let local = Scope::Local(root.clone());
+
+    // Missing project settings files are created on install
+    install(
+        project.clone(),
+        HookEvent::Stop,
+        handler.clone(),
+        None,
+        "test",
+    )
+    .expect("Project install should succeed");
+    install(local.clone(), HookEvent::Stop, handler, None, "test")
+        .expect("Same command in local scope should succeed");
+    assert!(root.join(".claude/settings.json").exists());
+    assert!(root.join(".claude/settings.local.json").exists());
+
+    assert!(list(Scope::User).expect("List should succeed").is_empty());
+    let entries = list(project.clone()).expect("List should succeed");
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].managed);
+
+    // Uninstalling from the project scope leaves the local hook managed
+    uninstall(project.clone(), HookEvent::Stop, "/path/to/stop.sh")
+        .expect("Project uninstall should succeed");
+    assert!(list(project).expect("List should succeed").is_empty());
+    let entries = list(local.clone()).expect("List should succeed");
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].managed);
+    assert!(uninstall(Scope::User, HookEvent::Stop, "/path/to/stop.sh").is_err());
+    uninstall(local, HookEvent::Stop, "/path/to/stop.sh").expect("Local uninstall should succeed");
+}
+
+#[test]
+#[serial(home)]
+fn test_dry_run_reports_diff_without_writing() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let original = fs::read_to_string(&settings_path).expect("Read failed");
+
+    let handler = HookHandler::Command(CommandHandler {
+        command: "/path/to/stop.sh".to_string(),
+        timeout: None,
+        r#async: None,
+        status_message: None,
+    });
+
+    let diff = install_dry_run(Scope::User, HookEvent::Stop, handler.clone(), None)
+        .expect("Dry run should succeed");
+    assert_eq!(diff.path, settings_path);
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.changes[0].pointer, "/hooks/Stop");
+    assert_eq!(diff.changes[0].kind, ChangeKind::Added);
+    assert!(diff
+        .render()
+        .lines()
+        .any(|line| line.starts_with('+') && line.contains("/path/to/stop.sh")));
+
+    // Nothing written, and the hook is not managed
+    assert_eq!(
+        fs::read_to_string(&settings_path).expect("Read failed"),
+        original
+    );
+    assert!(uninstall_dry_run(Scope::User, HookEvent::Stop, "/path/to/stop.sh").is_err());
+
+    install(Scope::User, HookEvent::Stop, handler.clone(), None, "test")
+        .expect("Install should succeed");
+    assert!(install_dry_run(Scope::User, HookEvent::Stop, handler, None).is_err());
+    let diff = uninstall_dry_run(Scope::User, HookEvent::Stop, "/path/to/stop.sh")
+        .expect("Dry run should succeed");
+    assert_eq!(diff.changes[0].pointer, "/hooks/Stop/0");
+    assert_eq!(diff.changes[0].kind, ChangeKind::Removed);
+    assert_eq!(list(Scope::User).expect("List should succeed").len(), 1);
+}
+
+#[test]
+#[serial(home)]
+fn test_backups_rotate_and_restore() {
+    let dir = setup_test_env();
+    assert!(list_backups(Scope::User)
+        .expect("List should succeed")
+        .is_empty());
+
+    for i in 0..MAX_BACKUPS + 2 {
+        let handler = HookHandler::Command(CommandHandler {
+            command: format!("/path/to/hook{}.sh", i),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
+    }
+
+    // Newest first, capped, and stored under ~/.claude/backups
+    let backups = list_backups(Scope::User).expect("List should succeed");
+    assert_eq!(backups.len(), MAX_BACKUPS);
+    assert!(backups
+        .windows(2)
+        .all(|pair| pair[0].created_at > pair[1].created_at));
+    assert!(backups[0]
+        .path
+        .starts_with(dir.path().join(".claude").join("backups")));
+
+    // The newest backup is the state before the last install
+    restore_backup(Scope::User, &backups[0]).expect("Restore should succeed");
+    assert_eq!(
+        list(Scope::User).expect("List should succeed").len(),
+        MAX_BACKUPS + 1
+    );
+
+    // Project backups are kept separately, outside the repository
+    let root = dir.path().join("project");
+    let local = Scope::Local(root.clone());
+    assert!(list_backups(local.clone())
+        .expect("List should succeed")
+        .is_empty());
+    for i in 0..2 {
+        let handler = HookHandler::Command(CommandHandler {
+            command: format!("/path/to/local{}.sh", i),
+            timeout: None,
+            r#async: None,
+            status_message: None,
+        });
+        install(local.clone(), HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
+    }
+    let backups = list_backups(local).expect("List should succeed");
+    assert_eq!(backups.len(), 1);
+    assert!(!backups[0].path.starts_with(&root));
+    assert!(!root.join(".claude").join("backups").exists());
+}
+
+#[test]
+#[serial(home)]
+fn test_install_template_is_all_or_nothing() {
+    let _dir = setup_test_env();
+
+    // One of the template's hooks is already present
+    let handler = HookHandler::Command(CommandHandler {
+        command: "notify idle".to_string(),
+        timeout: None,
+        r#async: None,
+        status_message: None,
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "other").expect("Install should succeed");
+
+    let params = [("command", "notify")];
+    assert!(install_template(Scope::User, &templates::STATUS_NOTIFIER, &params, "test").is_err());
+    assert_eq!(list(Scope::User).expect("List should succeed").len(), 1);
+
+    uninstall(Scope::User, HookEvent::Stop, "notify idle").expect("Uninstall should succeed");
+    let specs = install_template(Scope::User, &templates::STATUS_NOTIFIER, &params, "test")
+        .expect("Template install should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
+    assert_eq!(entries.len(), specs.len());
+    assert!(entries.iter().all(|e| e.managed));
+    assert!(entries.iter().any(|e| e.handler.key() == "notify working"));
+}
+
+#[test]
+#[serial(home)]
+fn test_install_all_writes_nothing_on_failure() {
+    let dir = setup_test_env();
+    let settings_path = dir.path().join(".claude/settings.json");
+    let original = fs::read_to_string(&settings_path).expect("Read failed");
+
+    let spec = |event, command: &str| HookSpec {
+        event,
+        matcher: None,
+        handler: HookHandler::Command(CommandHandler {
+            command: command.to_string(),
+            timeout: Some(10),
+            r#async: None,
+            status_message: None,
+        }),
+    };
+
+    // Duplicate within the batch and an empty command both abort the batch
+    let duplicate = vec![
+        spec(HookEvent::SessionStart, "/a.sh"),
+        spec(HookEvent::Stop, "/b.sh"),
+        spec(HookEvent::Stop, "/b.sh"),
+    ];
+    assert!(install_all(Scope::User, duplicate, "test").is_err());
+    let empty = vec![spec(HookEvent::Stop, "/b.sh"), spec(HookEvent::Stop, " ")];
+    assert!(install_all(Scope::User, empty, "test").is_err());
+    assert_eq!(
+        fs::read_to_string(&settings_path).expect("Read failed"),
+        original
+    );
+    assert!(list_backups(Scope::User)
+        .expect("List should succeed")
+        .is_empty());
+
+    let specs = vec![
+        spec(HookEvent::SessionStart, "/a.sh"),
+        spec(HookEvent::Stop, "/a.sh"),
+        spec(HookEvent::Stop, "/b.sh"),
+    ];
+    install_all(Scope::User, specs, "test").expect("Install all should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().all(|e| e.managed));
+    // One write, so one backup
+    assert_eq!(
+        list_backups(Scope::User)
+            .expect("List should succeed")
+            .len(),
+        1
+    );
+
+    // Anything already installed aborts the whole batch
+    let again = vec![
+        spec(HookEvent::PreCompact, "/c.sh"),
+        spec(HookEvent::Stop, "/b.sh"),
+    ];
+    assert!(install_all(Scope::User, again, "test").is_err());
+    assert_eq!(list(Scope::User).expect("List should succeed").len(), 3);
+}
@@ -3,7 +3,7 @@
 //! Tests operation timing against target metrics
 //! Validates that operations complete within acceptable time bounds
 
-use claude_hooks::{install, list, uninstall, HookEvent, HookHandler};
+use claude_hooks::{install, list, uninstall, CommandHandler, HookEvent, HookHandler, Scope};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -36,16 +36,15 @@ fn setup_test_env() -> tempfile::TempDir {
 fn test_install_performance() {
     let _dir = setup_test_env();
 
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
+    });
 
     let start = Instant::now();
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
     let duration = start.elapsed();
 
     println!("Install took: {}ms", duration.as_millis());
@@ -62,18 +61,17 @@ fn test_uninstall_performance() {
     let _dir = setup_test_env();
 
     // Install first
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/stop.sh".to_string(),
         timeout: Some(600),
         r#async: None,
         status_message: None,
-    };
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    });
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
 
     // Measure uninstall
     let start = Instant::now();
-    uninstall(HookEvent::Stop, "/path/to/stop.sh").expect("Uninstall should succeed");
+    uninstall(Scope::User, HookEvent::Stop, "/path/to/stop.sh").expect("Uninstall should succeed");
     let duration = start.elapsed();
 
     println!("Uninstall took: {}ms", duration.as_millis());
@@ -91,19 +89,19 @@ fn test_list_performance_with_10_hooks() {
 
     // Install 10 hooks
     for i in 0..10 {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/path/to/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     // Measure list
     let start = Instant::now();
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     let duration = start.elapsed();
 
     assert_eq!(entries.len(), 10);
@@ -122,7 +120,7 @@ fn test_list_performance_empty() {
 
     // Measure list with empty hooks
     let start = Instant::now();
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     let duration = start.elapsed();
 
     assert_eq!(entries.len(), 0);
@@ -143,14 +141,14 @@ fn test_install_100_hooks_sequentially() {
 
     // Install 100 hooks
     for i in 0..100 {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/path/to/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     let duration = start.elapsed();
@@ -177,14 +175,14 @@ fn test_uninstall_100_hooks_sequentially() {
 
     // Install 100 hooks first
     for i in 0..100 {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/path/to/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     let start = Instant::now();
@@ -192,7 +190,7 @@ fn test_uninstall_100_hooks_sequentially() {
     // Uninstall all hooks
     for i in 0..100 {
         let command = format!("/path/to/hook{}.sh", i);
-        uninstall(HookEvent::Stop, &command).expect("Uninstall should succeed");
+        uninstall(Scope::User, HookEvent::Stop, &command).expect("Uninstall should succeed");
     }
 
     let duration = start.elapsed();
@@ -219,19 +217,19 @@ fn test_list_performance_with_100_hooks() {
 
     // Install 100 hooks
     for i in 0..100 {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/path/to/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     // Measure list
     let start = Instant::now();
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     let duration = start.elapsed();
 
     assert_eq!(entries.len(), 100);
@@ -256,28 +254,28 @@ fn test_mixed_operations_performance() {
     // Mixed operations: install, list, uninstall
     for i in 0..20 {
         // Install
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/path/to/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
 
         // List
-        let entries = list().expect("List should succeed");
+        let entries = list(Scope::User).expect("List should succeed");
         assert_eq!(entries.len(), i + 1);
     }
 
     // Uninstall half
     for i in 0..10 {
         let command = format!("/path/to/hook{}.sh", i);
-        uninstall(HookEvent::Stop, &command).expect("Uninstall should succeed");
+        uninstall(Scope::User, HookEvent::Stop, &command).expect("Uninstall should succeed");
     }
 
     // Final list
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     assert_eq!(entries.len(), 10);
 
     let duration = start.elapsed();
@@ -320,16 +318,15 @@ fn test_install_with_large_settings_file() {
     .expect("Write failed");
 
     // Measure install with large file
-    let handler = HookHandler {
-        r#type: "command".to_string(),
+    let handler = HookHandler::Command(CommandHandler {
         command: "/path/to/test.sh".to_string(),
         timeout: None,
         r#async: None,
         status_message: None,
-    };
+    });
 
     let start = Instant::now();
-    install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+    install(Scope::User, HookEvent::Stop, handler, None, "test").expect("Install should succeed");
     let duration = start.elapsed();
 
     println!(
@@ -376,19 +373,19 @@ fn test_list_with_mixed_managed_unmanaged() {
 
     // Add managed hooks
     for i in 0..8 {
-        let handler = HookHandler {
-            r#type: "command".to_string(),
+        let handler = HookHandler::Command(CommandHandler {
             command: format!("/managed/hook{}.sh", i),
             timeout: None,
             r#async: None,
             status_message: None,
-        };
-        install(HookEvent::Stop, handler, None, "test").expect("Install should succeed");
+        });
+        install(Scope::User, HookEvent::Stop, handler, None, "test")
+            .expect("Install should succeed");
     }
 
     // Measure list (10 total: 2 unmanaged, 8 managed)
     let start = Instant::now();
-    let entries = list().expect("List should succeed");
+    let entries = list(Scope::User).expect("List should succeed");
     let duration = start.elapsed();
 
     assert_eq!(entries.len(), 10);
@@ -4,8 +4,8 @@
 export interface UsagePeriod {
   /** Percentage of quota used (0.0 - 100.0+). */
   utilization: number;
-  /** When this period's quota resets (ISO 8601 format). */
-  resetsAt: string;
+  /** When this period's quota resets (ISO 8601 format), if the API reported it. */
+  resetsAt?: string;
 }
 
 /**
@@ -55,8 +55,9 @@ export function getUsage(): UsageData;
  *
  * @example
  * const usage = getUsage();
- * const fiveHourOnPace = isOnPace(usage.fiveHour.utilization, usage.fiveHour.resetsAt, 5);
- * const sevenDayOnPace = isOnPace(usage.sevenDay.utilization, usage.sevenDay.resetsAt, 168);
+ * if (usage.fiveHour.resetsAt) {
+ *   const fiveHourOnPace = isOnPace(usage.fiveHour.utilization, usage.fiveHour.resetsAt, 5);
+ * }
  */
 export function isOnPace(
   utilization: number,
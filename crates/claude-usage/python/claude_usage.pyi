@@ -0,0 +1,43 @@
+from datetime import datetime, timedelta
+
+class ClaudeUsageError(Exception): ...
+class CredentialError(ClaudeUsageError): ...
+class ApiError(ClaudeUsageError): ...
+
+class UsagePeriod:
+    @property
+    def utilization(self) -> float: ...
+    @property
+    def resets_at(self) -> datetime | None: ...
+    def time_until_reset(self) -> timedelta | None: ...
+    def time_elapsed_percent(self, period_hours: int) -> float | None: ...
+    def is_on_pace(self, period_hours: int) -> bool | None: ...
+
+class ExtraUsage:
+    @property
+    def is_enabled(self) -> bool: ...
+    @property
+    def amount_used(self) -> float | None: ...
+    @property
+    def limit(self) -> float | None: ...
+    def remaining(self) -> float | None: ...
+
+class UsageData:
+    @property
+    def five_hour(self) -> UsagePeriod: ...
+    @property
+    def seven_day(self) -> UsagePeriod: ...
+    @property
+    def seven_day_sonnet(self) -> UsagePeriod | None: ...
+    @property
+    def extra_usage(self) -> ExtraUsage | None: ...
+    def five_hour_on_pace(self) -> bool | None: ...
+    def seven_day_on_pace(self) -> bool | None: ...
+    def five_hour_projected_at_reset(self) -> float | None: ...
+    def seven_day_projected_at_reset(self) -> float | None: ...
+    def estimated_time_until_limit(self) -> timedelta | None: ...
+    def to_json(self) -> str: ...
+
+def get_usage() -> UsageData: ...
+def get_usage_for_profile(name: str) -> UsageData: ...
+def is_on_pace(utilization: float, resets_at: datetime, period_hours: int) -> bool | None: ...
@@ -0,0 +1,130 @@
+//! Request authentication: an OAuth access token or an API key.
+//!
+//! Claude Code logins authenticate with an OAuth bearer token. Accounts on
+//! standard Anthropic API keys send the key in the `x-api-key` header
+//! instead. [`Auth`] holds either and builds the matching headers, so the
+//! clients work with both.
+//!
+//! Anthropic's OAuth usage endpoint only accepts OAuth tokens; an API key
+//! is for gateways that authenticate by key. [`get_usage`](crate::get_usage)
+//! only falls back to `ANTHROPIC_API_KEY` when `CLAUDE_USAGE_BASE_URL` names
+//! such a gateway; otherwise pass one explicitly.
+//!
+//! ```rust,ignore
+//! use claude_usage::auth::Auth;
+//! use claude_usage::client::fetch_usage_raw_with_auth;
+//! use claude_usage::RetryPolicy;
+//!
+//! let json = fetch_usage_raw_with_auth(&Auth::api_key_from_env()?, &RetryPolicy::default())?;
+//! ```
+
+use crate::client::BETA_HEADER;
+use crate::error::CredentialError;
+
+/// Environment variable holding a standard Anthropic API key.
+pub const ENV_VAR_API_KEY: &str = "ANTHROPIC_API_KEY";
+
+/// `anthropic-version` header value sent with API key requests.
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Credentials sent with an API request.
+///
+/// `Debug` output never includes the secret.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Auth {
+    /// OAuth access token, sent as `Authorization: Bearer <token>`.
+    OAuth(String),
+    /// API key, sent as `x-api-key: <key>`.
+    ApiKey(String),
+}
+
+impl std::fmt::Debug for Auth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OAuth(_) => f.write_str("Auth::OAuth(<redacted>)"),
+            Self::ApiKey(_) => f.write_str("Auth::ApiKey(<redacted>)"),
+        }
+    }
+}
+
+impl Auth {
+    /// Reads an API key from `ANTHROPIC_API_KEY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CredentialError::NotFound`] if the variable is unset or empty.
+    pub fn api_key_from_env() -> Result<Self, CredentialError> {
+        std::env::var(ENV_VAR_API_KEY)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(Self::ApiKey)
+            .ok_or(CredentialError::NotFound)
+    }
+
+    /// Authentication and version headers for a request.
+    ///
+    /// OAuth requests carry the OAuth beta header; API key requests carry
+    /// the `anthropic-version` header the key-authenticated APIs require.
+    ///
+    /// # Security
+    ///
+    /// The returned headers contain the secret; do not log them.
+    pub fn headers(&self) -> Vec<(String, String)> {
+        match self {
+            Self::OAuth(token) => vec![
+                ("Authorization".to_string(), format!("Bearer {}", token)),
+                ("anthropic-beta".to_string(), BETA_HEADER.to_string()),
+            ],
+            Self::ApiKey(key) => vec![
+                ("x-api-key".to_string(), key.clone()),
+                (
+                    "anthropic-version".to_string(),
+                    ANTHROPIC_VERSION.to_string(),
+                ),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headers_per_kind() {
+        let names = |auth: Auth| -> Vec<String> {
+            auth.headers().into_iter().map(|(name, _)| name).collect()
+        };
+        assert_eq!(
+            names(Auth::OAuth("tok".to_string())),
+            ["Authorization", "anthropic-beta"]
+        );
+        assert_eq!(
+            names(Auth::ApiKey("key".to_string())),
+            ["x-api-key", "anthropic-version"]
+        );
+        assert_eq!(
+            Auth::ApiKey("sk-ant-api03-x".to_string()).headers()[0].1,
+            "sk-ant-api03-x"
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let debug = format!("{:?}", Auth::ApiKey("sk-ant-api03-secret".to_string()));
+        assert_eq!(debug, "Auth::ApiKey(<redacted>)");
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_api_key_from_env() {
+        std::env::set_var(ENV_VAR_API_KEY, "sk-ant-api03-x");
+        let set = Auth::api_key_from_env();
+        std::env::set_var(ENV_VAR_API_KEY, "");
+        let empty = Auth::api_key_from_env();
+        std::env::remove_var(ENV_VAR_API_KEY);
+
+        assert_eq!(set.ok(), Some(Auth::ApiKey("sk-ant-api03-x".to_string())));
+        assert!(matches!(empty, Err(CredentialError::NotFound)));
+    }
+}
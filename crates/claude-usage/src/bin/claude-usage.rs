@@ -0,0 +1,290 @@
+//! `claude-usage` command-line tool.
+//!
+//! Prints Claude usage for shell scripts and status bars without linking
+//! against the library. Build with the `cli` feature:
+//!
+//! ```bash
+//! cargo install claude-usage --features cli
+//! claude-usage show
+//! claude-usage --json watch --interval 60
+//! claude-usage check --critical-at 85 || pause-agents
+//! ```
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use chrono::TimeDelta;
+use clap::{Parser, Subcommand};
+use claude_usage::{
+    get_usage, get_usage_for_profile, BudgetPolicy, BudgetVerdict, Error, Severity, UsageData,
+    UsagePeriod,
+};
+
+/// Show Claude usage limits.
+#[derive(Parser, Debug)]
+#[command(name = "claude-usage", version, about)]
+struct Cli {
+    /// Print JSON instead of text (one object per line in `watch`).
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Credential profile to use (see the `profile` module docs).
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print current usage once (the default).
+    Show,
+    /// Print usage repeatedly until interrupted.
+    Watch {
+        /// Seconds between updates.
+        #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+    /// Grade usage against budget levels; the exit status is 0 below
+    /// critical, 2 at critical and 3 at hard stop.
+    Check {
+        /// Warning level (percent).
+        #[arg(long, default_value_t = BudgetPolicy::default().warn_at)]
+        warn_at: f64,
+        /// Critical level (percent).
+        #[arg(long, default_value_t = BudgetPolicy::default().critical_at)]
+        critical_at: f64,
+        /// Hard-stop level (percent).
+        #[arg(long, default_value_t = BudgetPolicy::default().hard_stop_at)]
+        hard_stop_at: f64,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command.unwrap_or(Command::Show) {
+        Command::Show => match fetch(cli.profile.as_deref()) {
+            Ok(usage) => {
+                println!("{}", render(&usage, cli.json));
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("claude-usage: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Command::Watch { interval } => loop {
+            // Keep watching through transient failures; a status bar shows the
+            // next successful update.
+            match fetch(cli.profile.as_deref()) {
+                Ok(usage) => println!("{}", render(&usage, cli.json)),
+                Err(e) => eprintln!("claude-usage: {}", e),
+            }
+            std::thread::sleep(Duration::from_secs(interval));
+        },
+        Command::Check {
+            warn_at,
+            critical_at,
+            hard_stop_at,
+        } => match fetch(cli.profile.as_deref()) {
+            Ok(usage) => {
+                let verdict =
+                    BudgetPolicy::new(warn_at, critical_at, hard_stop_at).evaluate(&usage);
+                println!("{}", render_verdict(&verdict, cli.json));
+                ExitCode::from(check_status(verdict.severity))
+            }
+            Err(e) => {
+                eprintln!("claude-usage: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+/// Exit status for `check`; 1 is left for fetch errors.
+fn check_status(severity: Severity) -> u8 {
+    match severity {
+        Severity::Ok | Severity::Warn => 0,
+        Severity::Critical => 2,
+        Severity::HardStop => 3,
+    }
+}
+
+/// Render a budget verdict as compact JSON or one line of text.
+fn render_verdict(verdict: &BudgetVerdict, json: bool) -> String {
+    if json {
+        return serde_json::json!({
+            "severity": verdict.severity.to_string(),
+            "window": verdict.window.to_string(),
+            "utilization": verdict.utilization,
+            "resets_at": verdict.resets_at,
+        })
+        .to_string();
+    }
+    verdict.to_string()
+}
+
+/// Fetch usage for `profile`, or the default credentials.
+fn fetch(profile: Option<&str>) -> Result<UsageData, Error> {
+    match profile {
+        Some(name) => get_usage_for_profile(name).map(|usage| usage.data),
+        None => get_usage(),
+    }
+}
+
+/// Render usage as compact JSON or human-readable lines.
+fn render(usage: &UsageData, json: bool) -> String {
+    if json {
+        return serde_json::to_string(usage).unwrap_or_else(|_| "{}".to_string());
+    }
+    let mut lines = vec![
+        period_line("5h", &usage.five_hour, 5),
+        period_line("7d", &usage.seven_day, 7 * 24),
+    ];
+    if let Some(sonnet) = &usage.seven_day_sonnet {
+        lines.push(period_line("7d sonnet", sonnet, 7 * 24));
+    }
+    if let Some(extra) = usage.extra_usage.as_ref().filter(|e| e.is_enabled) {
+        let used = extra.amount_used.unwrap_or(0.0);
+        lines.push(match extra.limit {
+            Some(limit) => format!("extra: ${:.2} / ${:.2}", used, limit),
+            None => format!("extra: ${:.2}", used),
+        });
+    }
+    lines.join("\n")
+}
+
+/// One line like `5h: 42.0% (resets in 2h 13m, on pace)`.
+fn period_line(label: &str, period: &UsagePeriod, period_hours: u32) -> String {
+    let mut details = Vec::new();
+    if let Some(left) = period.time_until_reset() {
+        details.push(format!("resets in {}", format_duration(left)));
+    }
+    match period.is_on_pace(period_hours) {
+        Some(true) => details.push("on pace".to_string()),
+        Some(false) => details.push("ahead of pace".to_string()),
+        None => {}
+    }
+    if details.is_empty() {
+        format!("{}: {:.1}%", label, period.utilization)
+    } else {
+        format!(
+            "{}: {:.1}% ({})",
+            label,
+            period.utilization,
+            details.join(", ")
+        )
+    }
+}
+
+/// Formats a duration as its two largest units, e.g. `3d 4h` or `12m`.
+fn format_duration(delta: TimeDelta) -> String {
+    let minutes = delta.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", mins),
+        (0, _) => format!("{}h {}m", hours, mins),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use claude_usage::ExtraUsage;
+
+    fn usage() -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: 42.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 12.5,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: Some(ExtraUsage {
+                is_enabled: true,
+                amount_used: Some(1.2),
+                limit: Some(20.0),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_subcommands() {
+        let cli = Cli::parse_from(["claude-usage", "watch", "--interval", "5", "--json"]);
+        assert!(cli.json);
+        assert!(matches!(cli.command, Some(Command::Watch { interval: 5 })));
+        assert!(Cli::try_parse_from(["claude-usage", "watch", "--interval", "0"]).is_err());
+        assert!(Cli::parse_from(["claude-usage"]).command.is_none());
+    }
+
+    #[test]
+    fn test_check_verdict_and_status() {
+        let cli = Cli::parse_from(["claude-usage", "check", "--critical-at", "40"]);
+        let Some(Command::Check {
+            warn_at,
+            critical_at,
+            hard_stop_at,
+        }) = cli.command
+        else {
+            panic!("expected check");
+        };
+        let verdict = BudgetPolicy::new(warn_at, critical_at, hard_stop_at).evaluate(&usage());
+        assert_eq!(check_status(verdict.severity), 2);
+        assert_eq!(
+            render_verdict(&verdict, false),
+            "critical: 5h window at 42.0%"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_verdict(&verdict, true)).expect("valid JSON");
+        assert_eq!(json["window"], "5h");
+        assert_eq!(check_status(Severity::Warn), 0);
+        assert_eq!(check_status(Severity::HardStop), 3);
+    }
+
+    #[test]
+    fn test_render_text() {
+        assert_eq!(
+            render(&usage(), false),
+            "5h: 42.0%\n7d: 12.5%\nextra: $1.20 / $20.00"
+        );
+    }
+
+    #[test]
+    fn test_render_json_is_single_line() {
+        let json = render(&usage(), true);
+        assert!(!json.contains('\n'));
+        let parsed: UsageData = serde_json::from_str(&json).expect("valid JSON");
+        assert_eq!(parsed, usage());
+    }
+
+    #[test]
+    fn test_period_line_with_reset() {
+        let period = UsagePeriod {
+            utilization: 10.0,
+            resets_at: Some(Utc::now() + TimeDelta::minutes(150) + TimeDelta::seconds(30)),
+            extra: Default::default(),
+        };
+        assert_eq!(
+            period_line("5h", &period, 5),
+            "5h: 10.0% (resets in 2h 30m, on pace)"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(TimeDelta::minutes(12)), "12m");
+        assert_eq!(format_duration(TimeDelta::minutes(133)), "2h 13m");
+        assert_eq!(format_duration(TimeDelta::hours(76)), "3d 4h");
+        assert_eq!(format_duration(TimeDelta::minutes(-5)), "0m");
+    }
+}
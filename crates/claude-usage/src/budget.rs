@@ -0,0 +1,213 @@
+//! Budget guard: decide whether to continue, slow down or stop.
+//!
+//! A [`BudgetPolicy`] grades the fullest usage window against three
+//! utilization levels and returns a [`BudgetVerdict`]. Schedulers and CI
+//! scripts can pause agents at [`Severity::Critical`] and refuse new work
+//! at [`Severity::HardStop`] instead of running into the rate limit.
+//!
+//! ```rust,ignore
+//! use claude_usage::budget::{BudgetPolicy, Severity};
+//!
+//! let verdict = BudgetPolicy::default().evaluate(&claude_usage::get_usage()?);
+//! if !verdict.allows_new_work() {
+//!     eprintln!("{}", verdict);
+//!     std::process::exit(1);
+//! }
+//! ```
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::types::UsageData;
+use crate::watcher::UsageWindow;
+
+/// How close usage is to the limit, from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Below every level.
+    Ok,
+    /// At or above [`BudgetPolicy::warn_at`].
+    Warn,
+    /// At or above [`BudgetPolicy::critical_at`].
+    Critical,
+    /// At or above [`BudgetPolicy::hard_stop_at`].
+    HardStop,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Critical => "critical",
+            Self::HardStop => "hard stop",
+        })
+    }
+}
+
+/// Utilization levels (percent) at which usage becomes a concern.
+///
+/// Levels are compared with `>=`, so a window at exactly `warn_at` is
+/// [`Severity::Warn`]. They are expected to be ascending; a lower level
+/// that is above a higher one never applies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetPolicy {
+    /// Start warning (default 75%).
+    pub warn_at: f64,
+    /// Pause non-essential work (default 90%).
+    pub critical_at: f64,
+    /// Refuse new work (default 98%).
+    pub hard_stop_at: f64,
+}
+
+impl Default for BudgetPolicy {
+    fn default() -> Self {
+        Self {
+            warn_at: 75.0,
+            critical_at: 90.0,
+            hard_stop_at: 98.0,
+        }
+    }
+}
+
+impl BudgetPolicy {
+    /// Policy with the given levels.
+    pub fn new(warn_at: f64, critical_at: f64, hard_stop_at: f64) -> Self {
+        Self {
+            warn_at,
+            critical_at,
+            hard_stop_at,
+        }
+    }
+
+    /// Grades a single utilization value.
+    pub fn severity(&self, utilization: f64) -> Severity {
+        if utilization >= self.hard_stop_at {
+            Severity::HardStop
+        } else if utilization >= self.critical_at {
+            Severity::Critical
+        } else if utilization >= self.warn_at {
+            Severity::Warn
+        } else {
+            Severity::Ok
+        }
+    }
+
+    /// Grades every reported window and returns the verdict for the fullest.
+    ///
+    /// Ties go to the window listed first in [`UsageWindow::ALL`], so the
+    /// 5-hour window wins over the 7-day one at equal utilization.
+    pub fn evaluate(&self, usage: &UsageData) -> BudgetVerdict {
+        let (window, period) = UsageWindow::ALL
+            .into_iter()
+            .filter_map(|window| Some((window, window.period(usage)?)))
+            .reduce(|best, next| {
+                if next.1.utilization > best.1.utilization {
+                    next
+                } else {
+                    best
+                }
+            })
+            .unwrap_or((UsageWindow::FiveHour, &usage.five_hour));
+        BudgetVerdict {
+            severity: self.severity(period.utilization),
+            window,
+            utilization: period.utilization,
+            resets_at: period.resets_at,
+        }
+    }
+}
+
+/// Result of [`BudgetPolicy::evaluate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetVerdict {
+    /// Severity of the fullest window.
+    pub severity: Severity,
+    /// The fullest window.
+    pub window: UsageWindow,
+    /// Its utilization (percent).
+    pub utilization: f64,
+    /// When it resets, if known. Work can resume after this.
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+impl BudgetVerdict {
+    /// True below [`Severity::HardStop`].
+    pub fn allows_new_work(&self) -> bool {
+        self.severity < Severity::HardStop
+    }
+
+    /// True at [`Severity::Critical`] or above.
+    pub fn should_pause(&self) -> bool {
+        self.severity >= Severity::Critical
+    }
+}
+
+impl fmt::Display for BudgetVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} window at {:.1}%",
+            self.severity, self.window, self.utilization
+        )?;
+        if let Some(resets_at) = self.resets_at {
+            write!(f, ", resets at {}", resets_at.format("%Y-%m-%d %H:%M UTC"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UsagePeriod;
+
+    fn usage(five_hour: f64, seven_day: f64, sonnet: Option<f64>) -> UsageData {
+        let period = |utilization| UsagePeriod {
+            utilization,
+            resets_at: None,
+            extra: Default::default(),
+        };
+        UsageData {
+            five_hour: period(five_hour),
+            seven_day: period(seven_day),
+            seven_day_sonnet: sonnet.map(period),
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    #[test]
+    fn test_severity_levels_are_inclusive() {
+        let policy = BudgetPolicy::new(50.0, 80.0, 95.0);
+        assert_eq!(policy.severity(49.9), Severity::Ok);
+        assert_eq!(policy.severity(50.0), Severity::Warn);
+        assert_eq!(policy.severity(80.0), Severity::Critical);
+        assert_eq!(policy.severity(95.0), Severity::HardStop);
+        assert_eq!(policy.severity(130.0), Severity::HardStop);
+    }
+
+    #[test]
+    fn test_evaluate_uses_fullest_window() {
+        let policy = BudgetPolicy::default();
+        let verdict = policy.evaluate(&usage(40.0, 92.0, Some(60.0)));
+        assert_eq!(verdict.window, UsageWindow::SevenDay);
+        assert_eq!(verdict.severity, Severity::Critical);
+        assert!(verdict.should_pause());
+        assert!(verdict.allows_new_work());
+
+        let verdict = policy.evaluate(&usage(10.0, 20.0, Some(99.0)));
+        assert_eq!(verdict.window, UsageWindow::SevenDaySonnet);
+        assert!(!verdict.allows_new_work());
+    }
+
+    #[test]
+    fn test_evaluate_tie_prefers_five_hour() {
+        let verdict = BudgetPolicy::default().evaluate(&usage(30.0, 30.0, None));
+        assert_eq!(verdict.window, UsageWindow::FiveHour);
+        assert_eq!(verdict.severity, Severity::Ok);
+        assert_eq!(verdict.to_string(), "ok: 5h window at 30.0%");
+    }
+}
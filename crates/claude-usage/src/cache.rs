@@ -0,0 +1,360 @@
+//! Usage response caching with a time-to-live.
+//!
+//! The usage endpoint is rate limited, and several consumers on one machine
+//! (the dashboard daemon, CLI calls, statusbar scripts) often poll it
+//! independently. [`UsageCache`] keeps the last successful response in memory
+//! and, optionally, in a JSON file shared between processes. Fresh entries are
+//! returned without an API call; when a refresh fails, the last known data is
+//! returned marked as stale instead of an error.
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use claude_usage::get_usage_cached;
+//!
+//! let cached = get_usage_cached(Duration::from_secs(60))?;
+//! if cached.stale {
+//!     eprintln!("showing data from {} (refresh failed)", cached.fetched_at);
+//! }
+//! println!("5h utilization: {}%", cached.data.five_hour.utilization);
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::types::UsageData;
+
+/// Default time-to-live for cached usage data.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Cache file name inside the cache directory.
+const CACHE_FILE_NAME: &str = "usage.json";
+
+/// Usage data together with when it was fetched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedUsage {
+    /// The usage data.
+    pub data: UsageData,
+    /// When the data was fetched from the API.
+    pub fetched_at: DateTime<Utc>,
+    /// True if the data is older than the TTL because a refresh failed.
+    #[serde(skip)]
+    pub stale: bool,
+}
+
+impl CachedUsage {
+    /// Returns how long ago the data was fetched.
+    pub fn age(&self) -> chrono::TimeDelta {
+        Utc::now() - self.fetched_at
+    }
+}
+
+/// In-memory usage cache with an optional on-disk copy.
+///
+/// The on-disk copy lets separate processes share one response. Cache file
+/// errors are ignored: a missing or corrupt file behaves like an empty cache,
+/// and a failed write only loses sharing.
+#[derive(Debug)]
+pub struct UsageCache {
+    /// How long an entry is served without refreshing.
+    ttl: Duration,
+    /// Cache file shared between processes, if any.
+    path: Option<PathBuf>,
+    /// Last entry seen by this process.
+    entry: Mutex<Option<CachedUsage>>,
+}
+
+impl UsageCache {
+    /// Creates a cache that lives only in this process.
+    pub fn in_memory(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            path: None,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Creates a cache backed by the JSON file at `path`.
+    pub fn on_disk(path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            ttl,
+            path: Some(path.into()),
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Returns the configured time-to-live.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cache file path, or None for an in-memory cache.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns cached data if fresh, otherwise calls `fetch` and caches the result.
+    ///
+    /// If `fetch` fails and an older entry exists, that entry is returned with
+    /// [`CachedUsage::stale`] set. The error is only returned when there is
+    /// nothing cached at all.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `fetch` when the cache is empty.
+    pub fn get_or_fetch(
+        &self,
+        fetch: impl FnOnce() -> Result<UsageData, Error>,
+    ) -> Result<CachedUsage, Error> {
+        self.get_or_fetch_at(Utc::now(), self.ttl, fetch)
+    }
+
+    /// [`get_or_fetch`](Self::get_or_fetch) with `ttl` in place of the
+    /// configured one, for a cache shared by callers with different TTLs.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn get_or_fetch_within(
+        &self,
+        ttl: Duration,
+        fetch: impl FnOnce() -> Result<UsageData, Error>,
+    ) -> Result<CachedUsage, Error> {
+        self.get_or_fetch_at(Utc::now(), ttl, fetch)
+    }
+
+    /// [`get_or_fetch`](Self::get_or_fetch) with an explicit current time
+    /// and TTL.
+    fn get_or_fetch_at(
+        &self,
+        now: DateTime<Utc>,
+        ttl: Duration,
+        fetch: impl FnOnce() -> Result<UsageData, Error>,
+    ) -> Result<CachedUsage, Error> {
+        let cached = self.load();
+        if let Some(entry) = &cached {
+            if is_fresh(entry, now, ttl) {
+                return Ok(entry.clone());
+            }
+        }
+
+        match fetch() {
+            Ok(data) => {
+                let entry = CachedUsage {
+                    data,
+                    fetched_at: now,
+                    stale: false,
+                };
+                self.store(&entry);
+                Ok(entry)
+            }
+            Err(e) => match cached {
+                Some(entry) => Ok(CachedUsage {
+                    stale: true,
+                    ..entry
+                }),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Clears the in-memory entry and removes the cache file.
+    pub fn clear(&self) {
+        *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Returns the newest entry from memory or the cache file.
+    fn load(&self) -> Option<CachedUsage> {
+        let memory = self.entry.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let disk = self
+            .path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str::<CachedUsage>(&json).ok());
+        match (memory, disk) {
+            (Some(m), Some(d)) => Some(if d.fetched_at > m.fetched_at { d } else { m }),
+            (m, d) => m.or(d),
+        }
+    }
+
+    /// Saves `entry` in memory and, best effort, to the cache file.
+    fn store(&self, entry: &CachedUsage) {
+        *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = Some(entry.clone());
+        if let Some(path) = &self.path {
+            let _ = write_atomic(path, entry);
+        }
+    }
+}
+
+/// Returns true if `entry` was fetched less than `ttl` before `now`.
+fn is_fresh(entry: &CachedUsage, now: DateTime<Utc>, ttl: Duration) -> bool {
+    (now - entry.fetched_at).to_std().is_ok_and(|age| age < ttl)
+}
+
+/// Writes `entry` to a temporary file next to `path`, then renames it over
+/// `path` so readers in other processes never see a partial file.
+fn write_atomic(path: &Path, entry: &CachedUsage) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Returns the default cache file path.
+///
+/// Uses `$XDG_CACHE_HOME/claude-usage/usage.json`, falling back to
+/// `~/.cache/claude-usage/usage.json`, or `%LOCALAPPDATA%` on Windows.
+/// Returns None if none of these variables are set.
+pub fn default_cache_path() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let dir = non_empty("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))?;
+    Some(dir.join("claude-usage").join(CACHE_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use crate::types::UsagePeriod;
+    use tempfile::TempDir;
+
+    fn usage(five_hour: f64) -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: five_hour,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 10.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    fn network_error() -> Result<UsageData, Error> {
+        Err(Error::Api(ApiError::Network("offline".to_string())))
+    }
+
+    #[test]
+    fn test_fresh_entry_skips_fetch() {
+        let cache = UsageCache::in_memory(Duration::from_secs(60));
+        let now = Utc::now();
+        let first = cache
+            .get_or_fetch_at(now, cache.ttl(), || Ok(usage(5.0)))
+            .expect("first fetch");
+        assert!(!first.stale);
+
+        let later = now + chrono::TimeDelta::seconds(30);
+        let second = cache
+            .get_or_fetch_at(later, cache.ttl(), || {
+                panic!("should not fetch while fresh")
+            })
+            .expect("cached");
+        assert_eq!(second, first);
+    }
+
+    #[test]
+    fn test_expired_entry_refetches() {
+        let cache = UsageCache::in_memory(Duration::from_secs(60));
+        let now = Utc::now();
+        cache
+            .get_or_fetch_at(now, cache.ttl(), || Ok(usage(5.0)))
+            .expect("first fetch");
+        let later = now + chrono::TimeDelta::seconds(61);
+        let refreshed = cache
+            .get_or_fetch_at(later, cache.ttl(), || Ok(usage(7.0)))
+            .expect("refetch");
+        assert_eq!(refreshed.data.five_hour.utilization, 7.0);
+        assert_eq!(refreshed.fetched_at, later);
+    }
+
+    #[test]
+    fn test_ttl_can_be_given_per_call() {
+        let cache = UsageCache::in_memory(Duration::from_secs(60));
+        let now = Utc::now();
+        cache
+            .get_or_fetch_at(now, cache.ttl(), || Ok(usage(5.0)))
+            .expect("first fetch");
+        let later = now + chrono::TimeDelta::seconds(30);
+        let refreshed = cache
+            .get_or_fetch_at(later, Duration::from_secs(10), || Ok(usage(7.0)))
+            .expect("refetch");
+        assert_eq!(refreshed.data.five_hour.utilization, 7.0);
+    }
+
+    #[test]
+    fn test_failed_refresh_returns_stale_entry() {
+        let cache = UsageCache::in_memory(Duration::from_secs(60));
+        let now = Utc::now();
+        cache
+            .get_or_fetch_at(now, cache.ttl(), || Ok(usage(5.0)))
+            .expect("first fetch");
+        let later = now + chrono::TimeDelta::seconds(120);
+        let stale = cache
+            .get_or_fetch_at(later, cache.ttl(), network_error)
+            .expect("stale fallback");
+        assert!(stale.stale);
+        assert_eq!(stale.data.five_hour.utilization, 5.0);
+        assert_eq!(stale.fetched_at, now);
+    }
+
+    #[test]
+    fn test_failed_fetch_with_empty_cache_is_error() {
+        let cache = UsageCache::in_memory(Duration::from_secs(60));
+        assert!(cache.get_or_fetch(network_error).is_err());
+    }
+
+    #[test]
+    fn test_disk_cache_is_shared_between_instances() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("nested").join("usage.json");
+        let writer = UsageCache::on_disk(&path, Duration::from_secs(60));
+        writer
+            .get_or_fetch(|| Ok(usage(42.0)))
+            .expect("first fetch");
+        assert!(path.exists());
+
+        let reader = UsageCache::on_disk(&path, Duration::from_secs(60));
+        let cached = reader
+            .get_or_fetch(|| panic!("should read the shared file"))
+            .expect("cached");
+        assert_eq!(cached.data.five_hour.utilization, 42.0);
+    }
+
+    #[test]
+    fn test_corrupt_cache_file_is_ignored() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("usage.json");
+        std::fs::write(&path, "not json").expect("write");
+        let cache = UsageCache::on_disk(&path, Duration::from_secs(60));
+        let fetched = cache.get_or_fetch(|| Ok(usage(1.0))).expect("fetch");
+        assert!(!fetched.stale);
+    }
+
+    #[test]
+    fn test_clear_removes_entry_and_file() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("usage.json");
+        let cache = UsageCache::on_disk(&path, Duration::from_secs(60));
+        cache.get_or_fetch(|| Ok(usage(1.0))).expect("fetch");
+        cache.clear();
+        assert!(!path.exists());
+        assert!(cache.get_or_fetch(network_error).is_err());
+    }
+}
@@ -1,20 +1,47 @@
 //! HTTP client for the Anthropic usage API.
 //!
 //! This module provides functions to fetch usage data from the Anthropic API.
-//! It handles authentication, headers, and error mapping.
+//! It handles authentication, headers, and error mapping. The blocking client
+//! needs the `blocking` feature; the async client needs the `async` feature.
+//! Both retry transient failures according to a [`RetryPolicy`](crate::RetryPolicy).
+//!
+//! The free functions use Anthropic's endpoint, or the base URL in
+//! `CLAUDE_USAGE_BASE_URL`. [`UsageApiClient`] makes the endpoint, timeout and
+//! retry policy explicit.
+
+use std::time::Duration;
 
+use crate::auth::Auth;
 use crate::error::ApiError;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error::Error;
+use crate::retry::RetryPolicy;
+use crate::transport::HttpRequest;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::types::UsageData;
 
 /// Anthropic OAuth usage API endpoint.
 pub const USAGE_API_URL: &str = "https://api.anthropic.com/api/oauth/usage";
 
+/// Path of the usage endpoint, appended to a custom base URL.
+pub const USAGE_API_PATH: &str = "/api/oauth/usage";
+
+/// Environment variable overriding the usage API base URL
+/// (e.g. `https://llm-gateway.corp.example`).
+pub const ENV_VAR_BASE_URL: &str = "CLAUDE_USAGE_BASE_URL";
+
+/// Default per-request timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Required beta header value for OAuth endpoints.
 pub const BETA_HEADER: &str = "oauth-2025-04-20";
 
 /// Fetch raw usage data from the Anthropic API (blocking).
 ///
 /// This function makes a synchronous HTTP request to the usage API
-/// and returns the raw JSON response body.
+/// and returns the raw JSON response body. Transient failures are retried
+/// with the default [`RetryPolicy`]; use [`fetch_usage_raw_with_retry`] to
+/// configure or disable retries.
 ///
 /// # Arguments
 ///
@@ -34,43 +61,385 @@ pub const BETA_HEADER: &str = "oauth-2025-04-20";
 /// The token is used only for this request and is not stored.
 #[cfg(feature = "blocking")]
 pub fn fetch_usage_raw(token: &str) -> Result<String, ApiError> {
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|_| ApiError::Network("Failed to build HTTP client".to_string()))?;
-
-    let response = client
-        .get(USAGE_API_URL)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("anthropic-beta", BETA_HEADER)
+    fetch_usage_raw_with_retry(token, &RetryPolicy::default())
+}
+
+/// Fetch raw usage data from the Anthropic API (blocking), retrying per `policy`.
+///
+/// Sleeps the calling thread between attempts.
+///
+/// # Errors
+///
+/// Returns the last attempt's [`ApiError`] once `policy` gives up.
+#[cfg(feature = "blocking")]
+pub fn fetch_usage_raw_with_retry(token: &str, policy: &RetryPolicy) -> Result<String, ApiError> {
+    UsageApiClient::new()
+        .retry_policy(policy.clone())
+        .fetch_usage_raw(token)
+}
+
+/// Fetch raw usage data (blocking) authenticating with `auth`, retrying per
+/// `policy`.
+///
+/// Anthropic's usage endpoint only accepts OAuth tokens; [`Auth::ApiKey`]
+/// is for key-authenticated gateways.
+///
+/// # Errors
+///
+/// Returns the last attempt's [`ApiError`] once `policy` gives up.
+#[cfg(feature = "blocking")]
+pub fn fetch_usage_raw_with_auth(auth: &Auth, policy: &RetryPolicy) -> Result<String, ApiError> {
+    UsageApiClient::new()
+        .retry_policy(policy.clone())
+        .fetch_usage_raw_with_auth(auth)
+}
+
+/// Fetch raw usage data from the Anthropic API (async).
+///
+/// Async counterpart of `fetch_usage_raw` for tokio consumers. Uses the
+/// same endpoint, headers, 10 second timeout, error mapping, and default
+/// [`RetryPolicy`].
+///
+/// # Errors
+///
+/// Returns [`ApiError`] under the same conditions as `fetch_usage_raw`.
+///
+/// # Security
+///
+/// The token is used only for this request and is not stored.
+#[cfg(feature = "async")]
+pub async fn fetch_usage_raw_async(token: &str) -> Result<String, ApiError> {
+    fetch_usage_raw_async_with_retry(token, &RetryPolicy::default()).await
+}
+
+/// Fetch raw usage data from the Anthropic API (async), retrying per `policy`.
+///
+/// Waits between attempts with `tokio::time::sleep`.
+///
+/// # Errors
+///
+/// Returns the last attempt's [`ApiError`] once `policy` gives up.
+#[cfg(feature = "async")]
+pub async fn fetch_usage_raw_async_with_retry(
+    token: &str,
+    policy: &RetryPolicy,
+) -> Result<String, ApiError> {
+    UsageApiClient::new()
+        .retry_policy(policy.clone())
+        .fetch_usage_raw_async(token)
+        .await
+}
+
+/// Fetch raw usage data (async) authenticating with `auth`, retrying per
+/// `policy`.
+///
+/// Async counterpart of [`fetch_usage_raw_with_auth`].
+///
+/// # Errors
+///
+/// Returns the last attempt's [`ApiError`] once `policy` gives up.
+#[cfg(feature = "async")]
+pub async fn fetch_usage_raw_async_with_auth(
+    auth: &Auth,
+    policy: &RetryPolicy,
+) -> Result<String, ApiError> {
+    UsageApiClient::new()
+        .retry_policy(policy.clone())
+        .fetch_usage_raw_async_with_auth(auth)
+        .await
+}
+
+/// Usage API client with explicit endpoint, timeout and retry settings.
+///
+/// The free functions in this module use [`UsageApiClient::new()`]. Build
+/// one directly to point at a gateway, regional endpoint or local mock:
+///
+/// ```rust,ignore
+/// use claude_usage::client::UsageApiClient;
+///
+/// let client = UsageApiClient::new()
+///     .base_url("https://llm-gateway.corp.example")
+///     .timeout(std::time::Duration::from_secs(5));
+/// let usage = client.fetch_usage(&token)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageApiClient {
+    /// Full usage endpoint URL.
+    usage_url: String,
+    /// Per-request timeout.
+    timeout: Duration,
+    /// Retry policy for transient failures.
+    retry_policy: RetryPolicy,
+}
+
+impl Default for UsageApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UsageApiClient {
+    /// Creates a client for Anthropic's endpoint, or the base URL in
+    /// [`ENV_VAR_BASE_URL`] if set, with a 10 second timeout and the default
+    /// [`RetryPolicy`].
+    pub fn new() -> Self {
+        let client = Self {
+            usage_url: USAGE_API_URL.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+        };
+        match base_url_from_env() {
+            Some(base) => client.base_url(base),
+            None => client,
+        }
+    }
+
+    /// Sends requests to `base` (scheme and host, optionally a path prefix)
+    /// followed by the usage API path.
+    pub fn base_url(self, base: impl AsRef<str>) -> Self {
+        let url = format!("{}{}", base.as_ref().trim_end_matches('/'), USAGE_API_PATH);
+        self.usage_url(url)
+    }
+
+    /// Sends requests to `url` exactly, for gateways that rename the path.
+    pub fn usage_url(mut self, url: impl Into<String>) -> Self {
+        self.usage_url = url.into();
+        self
+    }
+
+    /// Sets the per-request timeout. Retries each get the full timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the retry policy.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Returns the usage endpoint URL requests are sent to.
+    pub fn endpoint(&self) -> &str {
+        &self.usage_url
+    }
+
+    /// Builds the request for OAuth `token`, for use with a custom
+    /// [`Transport`](crate::transport::Transport).
+    ///
+    /// # Security
+    ///
+    /// The returned request contains the token; do not log its headers.
+    pub fn request(&self, token: &str) -> HttpRequest {
+        self.request_with_auth(&Auth::OAuth(token.to_string()))
+    }
+
+    /// Builds the request authenticated by `auth`, for use with a custom
+    /// [`Transport`](crate::transport::Transport).
+    ///
+    /// # Security
+    ///
+    /// The returned request contains the secret; do not log its headers.
+    pub fn request_with_auth(&self, auth: &Auth) -> HttpRequest {
+        HttpRequest {
+            url: self.usage_url.clone(),
+            headers: auth.headers(),
+        }
+    }
+
+    /// Fetch raw usage data (blocking) with OAuth `token`, retrying per the
+    /// client's policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last attempt's [`ApiError`] once the policy gives up.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_usage_raw(&self, token: &str) -> Result<String, ApiError> {
+        self.fetch_usage_raw_with_auth(&Auth::OAuth(token.to_string()))
+    }
+
+    /// Fetch raw usage data (blocking) authenticated by `auth`, retrying per
+    /// the client's policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last attempt's [`ApiError`] once the policy gives up.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_usage_raw_with_auth(&self, auth: &Auth) -> Result<String, ApiError> {
+        let client = crate::http::blocking_client(self.timeout)?;
+
+        let mut attempt = 1;
+        loop {
+            let mut request = client.get(&self.usage_url);
+            for (name, value) in auth.headers() {
+                request = request.header(name, value);
+            }
+            let result = request
+                .send()
+                // Use generic message to avoid any potential token exposure in error details
+                .map_err(|_| ApiError::Network("Failed to connect to Anthropic API".to_string()))
+                .and_then(map_response);
+
+            match result {
+                Err(e) => match self.retry_policy.delay_for(attempt, &e) {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(e),
+                },
+                ok => return ok,
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Fetch and parse usage data (blocking).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Api`] as [`fetch_usage_raw`](Self::fetch_usage_raw)
+    /// does, or [`Error::Parse`] for an invalid body.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_usage(&self, token: &str) -> Result<UsageData, Error> {
+        let body = self.fetch_usage_raw(token)?;
+        crate::transport::parse_usage(&body)
+    }
+
+    /// Fetch and parse usage data (blocking) authenticated by `auth`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`fetch_usage`](Self::fetch_usage).
+    #[cfg(feature = "blocking")]
+    pub fn fetch_usage_with_auth(&self, auth: &Auth) -> Result<UsageData, Error> {
+        let body = self.fetch_usage_raw_with_auth(auth)?;
+        crate::transport::parse_usage(&body)
+    }
+
+    /// Fetch raw usage data (async) with OAuth `token`, retrying per the
+    /// client's policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last attempt's [`ApiError`] once the policy gives up.
+    #[cfg(feature = "async")]
+    pub async fn fetch_usage_raw_async(&self, token: &str) -> Result<String, ApiError> {
+        self.fetch_usage_raw_async_with_auth(&Auth::OAuth(token.to_string()))
+            .await
+    }
+
+    /// Fetch raw usage data (async) authenticated by `auth`, retrying per
+    /// the client's policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last attempt's [`ApiError`] once the policy gives up.
+    #[cfg(feature = "async")]
+    pub async fn fetch_usage_raw_async_with_auth(&self, auth: &Auth) -> Result<String, ApiError> {
+        let client = crate::http::async_client(self.timeout)?;
+
+        let mut attempt = 1;
+        loop {
+            match fetch_once_async(&client, &self.usage_url, auth).await {
+                Err(e) => match self.retry_policy.delay_for(attempt, &e) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(e),
+                },
+                ok => return ok,
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Fetch and parse usage data (async).
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`fetch_usage`](Self::fetch_usage).
+    #[cfg(feature = "async")]
+    pub async fn fetch_usage_async(&self, token: &str) -> Result<UsageData, Error> {
+        let body = self.fetch_usage_raw_async(token).await?;
+        crate::transport::parse_usage(&body)
+    }
+
+    /// Fetch and parse usage data (async) authenticated by `auth`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`fetch_usage`](Self::fetch_usage).
+    #[cfg(feature = "async")]
+    pub async fn fetch_usage_async_with_auth(&self, auth: &Auth) -> Result<UsageData, Error> {
+        let body = self.fetch_usage_raw_async_with_auth(auth).await?;
+        crate::transport::parse_usage(&body)
+    }
+}
+
+/// The base URL in [`ENV_VAR_BASE_URL`], if set and not blank.
+pub(crate) fn base_url_from_env() -> Option<String> {
+    std::env::var(ENV_VAR_BASE_URL)
+        .ok()
+        .map(|base| base.trim().to_string())
+        .filter(|base| !base.is_empty())
+}
+
+/// Make a single async request to the usage API.
+#[cfg(feature = "async")]
+async fn fetch_once_async(
+    client: &reqwest::Client,
+    url: &str,
+    auth: &Auth,
+) -> Result<String, ApiError> {
+    let mut request = client.get(url);
+    for (name, value) in auth.headers() {
+        request = request.header(name, value);
+    }
+    let response = request
         .send()
+        .await
         // Use generic message to avoid any potential token exposure in error details
         .map_err(|_| ApiError::Network("Failed to connect to Anthropic API".to_string()))?;
 
-    map_response(response)
+    if let Some(err) = status_error(response.status().as_u16(), response.headers()) {
+        return Err(err);
+    }
+    response
+        .text()
+        .await
+        .map_err(|_| ApiError::Network("Failed to read response body".to_string()))
 }
 
 /// Map HTTP response to result, handling error status codes.
 #[cfg(feature = "blocking")]
-fn map_response(response: reqwest::blocking::Response) -> Result<String, ApiError> {
-    let status = response.status().as_u16();
+pub(crate) fn map_response(response: reqwest::blocking::Response) -> Result<String, ApiError> {
+    if let Some(err) = status_error(response.status().as_u16(), response.headers()) {
+        return Err(err);
+    }
+    response
+        .text()
+        .map_err(|_| ApiError::Network("Failed to read response body".to_string()))
+}
 
+/// Map a non-200 status code to its [`ApiError`], or `None` for 200.
+///
+/// Shared by the blocking and async clients.
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub(crate) fn status_error(status: u16, headers: &reqwest::header::HeaderMap) -> Option<ApiError> {
+    let retry_after = headers.get("retry-after").and_then(|v| v.to_str().ok());
+    status_to_error(status, retry_after)
+}
+
+/// Map a non-200 status code and `Retry-After` value to its [`ApiError`],
+/// or `None` for 200.
+///
+/// Independent of the HTTP client, for custom [`Transport`](crate::transport::Transport)s.
+pub(crate) fn status_to_error(status: u16, retry_after: Option<&str>) -> Option<ApiError> {
     match status {
-        200 => response
-            .text()
-            .map_err(|_| ApiError::Network("Failed to read response body".to_string())),
-        401 => Err(ApiError::Unauthorized),
-        403 => Err(ApiError::Forbidden),
-        429 => {
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .map(String::from);
-            Err(ApiError::RateLimited { retry_after })
-        }
-        500..=599 => Err(ApiError::Server(status)),
-        _ => Err(ApiError::Unexpected(status)),
+        200 => None,
+        401 => Some(ApiError::Unauthorized),
+        403 => Some(ApiError::Forbidden),
+        429 => Some(ApiError::RateLimited {
+            retry_after: retry_after.map(String::from),
+        }),
+        500..=599 => Some(ApiError::Server(status)),
+        _ => Some(ApiError::Unexpected(status)),
     }
 }
 
@@ -105,6 +474,42 @@ mod tests {
         assert_ne!(forbidden.to_string(), unauthorized.to_string());
     }
 
+    #[test]
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn test_status_error_maps_codes() {
+        let headers = reqwest::header::HeaderMap::new();
+        assert!(status_error(200, &headers).is_none());
+        assert!(matches!(
+            status_error(401, &headers),
+            Some(ApiError::Unauthorized)
+        ));
+        assert!(matches!(
+            status_error(403, &headers),
+            Some(ApiError::Forbidden)
+        ));
+        assert!(matches!(
+            status_error(503, &headers),
+            Some(ApiError::Server(503))
+        ));
+        assert!(matches!(
+            status_error(418, &headers),
+            Some(ApiError::Unexpected(418))
+        ));
+    }
+
+    #[test]
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn test_status_error_reads_retry_after() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("retry-after", "120".parse().expect("valid header"));
+        match status_error(429, &headers) {
+            Some(ApiError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after.as_deref(), Some("120"));
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+    }
+
     // Integration test - requires valid token
     #[test]
     #[ignore = "requires real API credentials"]
@@ -138,4 +543,57 @@ mod tests {
         let result = fetch_usage_raw("invalid-token");
         assert!(matches!(result, Err(ApiError::Unauthorized)));
     }
+
+    #[test]
+    fn test_base_url_builds_usage_endpoint() {
+        let client = UsageApiClient::new().base_url("https://gateway.example/anthropic/");
+        assert_eq!(
+            client.endpoint(),
+            "https://gateway.example/anthropic/api/oauth/usage"
+        );
+        let client = client.usage_url("http://127.0.0.1:8080/usage");
+        assert_eq!(client.request("tok").url, "http://127.0.0.1:8080/usage");
+    }
+
+    #[test]
+    fn test_request_with_api_key_uses_key_header() {
+        let request = UsageApiClient::new()
+            .base_url("https://gateway.example")
+            .request_with_auth(&Auth::ApiKey("sk-ant-api03-x".to_string()));
+        assert!(request
+            .headers
+            .contains(&("x-api-key".to_string(), "sk-ant-api03-x".to_string())));
+        assert!(!request
+            .headers
+            .iter()
+            .any(|(name, _)| name == "Authorization"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_base_url_env_var() {
+        std::env::set_var(ENV_VAR_BASE_URL, "http://localhost:9000/");
+        let from_env = UsageApiClient::new();
+        std::env::set_var(ENV_VAR_BASE_URL, "  ");
+        let blank = UsageApiClient::new();
+        std::env::remove_var(ENV_VAR_BASE_URL);
+
+        assert_eq!(from_env.endpoint(), "http://localhost:9000/api/oauth/usage");
+        assert_eq!(blank.endpoint(), USAGE_API_URL);
+        assert_eq!(UsageApiClient::new().endpoint(), USAGE_API_URL);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    #[cfg(feature = "blocking")]
+    fn test_unreachable_base_url_is_network_error() {
+        // Nothing listens on port 9 (discard) on test machines
+        let client = UsageApiClient::new()
+            .base_url("http://127.0.0.1:9")
+            .retry_policy(RetryPolicy::disabled());
+        assert!(matches!(
+            client.fetch_usage("tok"),
+            Err(Error::Api(ApiError::Network(_)))
+        ));
+    }
 }
@@ -0,0 +1,282 @@
+//! Credential diagnostics.
+//!
+//! [`diagnose`] probes every credential backend in the order [`get_token`]
+//! consults them and reports what each one holds, so a "doctor" command can
+//! explain why usage cannot be fetched. Reports carry expiry times and
+//! error reasons but never token material.
+//!
+//! [`get_token`]: super::get_token
+
+use std::fmt;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::{parse_oauth_credentials, CredentialSource, ENV_VAR_TOKEN};
+use crate::error::CredentialError;
+
+/// What a probed backend holds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProbeOutcome {
+    /// A token that has not expired.
+    Valid(TokenInfo),
+    /// A token past its expiry. Usable after a refresh if
+    /// [`TokenInfo::has_refresh_token`] is set.
+    Expired(TokenInfo),
+    /// Nothing stored in this backend.
+    NotFound,
+    /// Stored credentials that cannot be used (malformed, unreadable).
+    Rejected(CredentialError),
+}
+
+/// Token metadata safe to display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// When the access token expires, if stored.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether a refresh token is stored alongside it.
+    pub has_refresh_token: bool,
+}
+
+/// One probed backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CredentialProbe {
+    /// The backend.
+    pub source: CredentialSource,
+    /// What it holds.
+    pub outcome: ProbeOutcome,
+}
+
+/// Result of [`diagnose`]: every backend, in lookup order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CredentialReport {
+    /// Probes in the order [`get_token`](super::get_token) consults them.
+    pub probes: Vec<CredentialProbe>,
+}
+
+impl CredentialReport {
+    /// The probe whose credentials are used: the first backend holding
+    /// anything, valid or not.
+    pub fn selected(&self) -> Option<&CredentialProbe> {
+        self.probes
+            .iter()
+            .find(|probe| probe.outcome != ProbeOutcome::NotFound)
+    }
+
+    /// True if the selected credentials can be used now or after a refresh.
+    pub fn is_usable(&self) -> bool {
+        match self.selected().map(|probe| &probe.outcome) {
+            Some(ProbeOutcome::Valid(_)) => true,
+            Some(ProbeOutcome::Expired(info)) => info.has_refresh_token,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env => write!(f, "{} environment variable", ENV_VAR_TOKEN),
+            Self::File(path) => write!(f, "file {}", path.display()),
+            Self::Keychain => f.write_str("macOS Keychain"),
+            Self::CredentialManager => f.write_str("Windows Credential Manager"),
+        }
+    }
+}
+
+impl fmt::Display for ProbeOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expiry = |f: &mut fmt::Formatter<'_>, info: &TokenInfo| -> fmt::Result {
+            if let Some(at) = info.expires_at {
+                write!(f, " at {}", at.format("%Y-%m-%d %H:%M UTC"))?;
+            }
+            if !info.has_refresh_token {
+                f.write_str(", no refresh token")?;
+            }
+            Ok(())
+        };
+        match self {
+            Self::Valid(info) => {
+                f.write_str("valid")?;
+                if info.expires_at.is_some() {
+                    f.write_str(", expires")?;
+                }
+                expiry(f, info)
+            }
+            Self::Expired(info) => {
+                f.write_str("expired")?;
+                expiry(f, info)
+            }
+            Self::NotFound => f.write_str("not found"),
+            Self::Rejected(e) => write!(f, "rejected: {}", e),
+        }
+    }
+}
+
+impl fmt::Display for CredentialReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let selected = self.selected().map(|probe| &probe.source);
+        for probe in &self.probes {
+            let marker = if Some(&probe.source) == selected {
+                "*"
+            } else {
+                " "
+            };
+            writeln!(f, "{} {}: {}", marker, probe.source, probe.outcome)?;
+        }
+        Ok(())
+    }
+}
+
+/// Probe every credential backend without fetching usage.
+///
+/// The `CLAUDE_CODE_OAUTH_TOKEN` environment variable comes first, then the
+/// platform backends: the Keychain on macOS, the credential file on Linux,
+/// and Credential Manager followed by the credential file on Windows.
+pub fn diagnose() -> CredentialReport {
+    let env = match std::env::var(ENV_VAR_TOKEN) {
+        Ok(token) if !token.is_empty() => ProbeOutcome::Valid(TokenInfo {
+            expires_at: None,
+            has_refresh_token: false,
+        }),
+        _ => ProbeOutcome::NotFound,
+    };
+    let mut probes = vec![CredentialProbe {
+        source: CredentialSource::Env,
+        outcome: env,
+    }];
+    probes.extend(
+        platform_probes()
+            .into_iter()
+            .map(|(source, content)| probe(source, content)),
+    );
+    CredentialReport { probes }
+}
+
+/// Raw contents of each platform backend, in lookup order.
+fn platform_probes() -> Vec<(CredentialSource, Result<String, CredentialError>)> {
+    #[cfg(target_os = "macos")]
+    {
+        super::macos::probe_macos()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        super::linux::probe_linux()
+    }
+
+    #[cfg(windows)]
+    {
+        super::windows::probe_windows()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+    {
+        Vec::new()
+    }
+}
+
+/// Classify a backend's raw credential JSON.
+fn probe(source: CredentialSource, content: Result<String, CredentialError>) -> CredentialProbe {
+    let outcome = match content.and_then(|raw| parse_oauth_credentials(&raw)) {
+        Ok(oauth) => {
+            let info = TokenInfo {
+                expires_at: oauth
+                    .expires_at_ms
+                    .and_then(|ms| Utc.timestamp_millis_opt(ms).single()),
+                has_refresh_token: oauth.refresh_token.is_some(),
+            };
+            if oauth.is_expired() {
+                ProbeOutcome::Expired(info)
+            } else {
+                ProbeOutcome::Valid(info)
+            }
+        }
+        Err(CredentialError::NotFound) => ProbeOutcome::NotFound,
+        Err(e) => ProbeOutcome::Rejected(e),
+    };
+    CredentialProbe { source, outcome }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn file() -> CredentialSource {
+        CredentialSource::File(PathBuf::from("/home/u/.claude/.credentials.json"))
+    }
+
+    #[test]
+    fn test_probe_classifies_contents() {
+        let valid = probe(
+            file(),
+            Ok(r#"{"claudeAiOauth": {"accessToken": "sk-ant-oat01-secret",
+                "refreshToken": "sk-ant-ort01-secret", "expiresAt": 9999999999999}}"#
+                .to_string()),
+        );
+        assert!(matches!(
+            valid.outcome,
+            ProbeOutcome::Valid(TokenInfo {
+                expires_at: Some(_),
+                has_refresh_token: true
+            })
+        ));
+
+        let expired = probe(
+            file(),
+            Ok(
+                r#"{"claudeAiOauth": {"accessToken": "sk-ant-oat01-secret", "expiresAt": 1000}}"#
+                    .to_string(),
+            ),
+        );
+        assert!(matches!(expired.outcome, ProbeOutcome::Expired(_)));
+
+        let malformed = probe(file(), Ok(r#"{"claudeAiOauth": {}}"#.to_string()));
+        assert_eq!(
+            malformed.outcome,
+            ProbeOutcome::Rejected(CredentialError::MissingField("accessToken"))
+        );
+
+        let missing = probe(file(), Err(CredentialError::NotFound));
+        assert_eq!(missing.outcome, ProbeOutcome::NotFound);
+    }
+
+    #[test]
+    fn test_report_selects_first_present_backend() {
+        let expired = TokenInfo {
+            expires_at: Some(Utc.timestamp_millis_opt(1000).unwrap()),
+            has_refresh_token: true,
+        };
+        let report = CredentialReport {
+            probes: vec![
+                CredentialProbe {
+                    source: CredentialSource::Env,
+                    outcome: ProbeOutcome::NotFound,
+                },
+                CredentialProbe {
+                    source: file(),
+                    outcome: ProbeOutcome::Expired(expired),
+                },
+            ],
+        };
+        assert_eq!(report.selected().map(|p| &p.source), Some(&file()));
+        assert!(report.is_usable());
+        assert_eq!(
+            report.to_string(),
+            "  CLAUDE_CODE_OAUTH_TOKEN environment variable: not found\n\
+             * file /home/u/.claude/.credentials.json: expired at 1970-01-01 00:00 UTC\n"
+        );
+    }
+
+    #[test]
+    fn test_report_never_contains_tokens() {
+        let report = CredentialReport {
+            probes: vec![probe(
+                file(),
+                Ok(r#"{"claudeAiOauth": {"accessToken": "sk-ant-oat01-secret"}}"#.to_string()),
+            )],
+        };
+        assert!(!report.to_string().contains("secret"));
+        assert!(!format!("{:?}", report).contains("secret"));
+    }
+}
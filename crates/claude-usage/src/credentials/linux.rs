@@ -4,10 +4,9 @@
 //! at `~/.claude/.credentials.json`. This is the standard location used by
 //! Claude Code on Linux systems.
 
-use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
-use super::{parse_credential_json, LINUX_CREDENTIALS_PATH};
+use super::{get_token_from_path, read_credential_file, CredentialSource, LINUX_CREDENTIALS_PATH};
 use crate::error::CredentialError;
 
 /// Retrieve the OAuth access token from the Linux credential file.
@@ -25,28 +24,29 @@ pub fn get_token_linux() -> Result<String, CredentialError> {
     get_token_from_path(&path)
 }
 
-/// Retrieve the OAuth access token from a specific credential file path.
-///
-/// This function is the testable core of credential retrieval, separated from
-/// path resolution to avoid environment variable mutation in tests.
+/// Read the raw credential JSON from the Linux credential file.
 ///
 /// # Errors
 ///
-/// Returns [`CredentialError`] if:
-/// - Credentials file does not exist
-/// - File permissions prevent reading
-/// - Credentials cannot be parsed
-/// - Token is expired
-fn get_token_from_path(creds_path: &Path) -> Result<String, CredentialError> {
-    let content = fs::read_to_string(creds_path).map_err(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => CredentialError::NotFound,
-        std::io::ErrorKind::PermissionDenied => {
-            CredentialError::Permission(creds_path.display().to_string())
-        }
-        _ => CredentialError::Io(e.to_string()),
-    })?;
+/// Returns [`CredentialError`] if HOME is not set or the file cannot be read.
+pub fn read_credentials_linux() -> Result<(String, CredentialSource), CredentialError> {
+    let path = get_credentials_path()?;
+    let content = read_credential_file(&path)?;
+    Ok((content, CredentialSource::File(path)))
+}
 
-    parse_credential_json(&content)
+/// Probe the credential file for diagnostics.
+pub fn probe_linux() -> Vec<(CredentialSource, Result<String, CredentialError>)> {
+    match get_credentials_path() {
+        Ok(path) => {
+            let content = read_credential_file(&path);
+            vec![(CredentialSource::File(path), content)]
+        }
+        Err(e) => vec![(
+            CredentialSource::File(PathBuf::from("~").join(LINUX_CREDENTIALS_PATH)),
+            Err(e),
+        )],
+    }
 }
 
 /// Get the path to the credentials file.
@@ -59,7 +59,7 @@ fn get_credentials_path() -> Result<PathBuf, CredentialError> {
 mod tests {
     use super::*;
     use serial_test::serial;
-    use std::fs::{self, File};
+    use std::fs::File;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -18,7 +18,7 @@
 
 use std::process::Command;
 
-use super::{parse_credential_json, KEYCHAIN_SERVICE};
+use super::{parse_credential_json, CredentialSource, KEYCHAIN_SERVICE};
 use crate::error::CredentialError;
 
 /// Retrieve the OAuth access token from macOS Keychain.
@@ -33,6 +33,25 @@ use crate::error::CredentialError;
 /// - Credentials cannot be parsed
 /// - Token is expired
 pub fn get_token_macos() -> Result<String, CredentialError> {
+    parse_credential_json(&read_keychain()?)
+}
+
+/// Read the raw credential JSON from macOS Keychain.
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if the Keychain item is missing or not UTF-8.
+pub fn read_credentials_macos() -> Result<(String, CredentialSource), CredentialError> {
+    Ok((read_keychain()?, CredentialSource::Keychain))
+}
+
+/// Probe the Keychain item for diagnostics.
+pub fn probe_macos() -> Vec<(CredentialSource, Result<String, CredentialError>)> {
+    vec![(CredentialSource::Keychain, read_keychain())]
+}
+
+/// Run `/usr/bin/security` to print the Keychain item's password.
+fn read_keychain() -> Result<String, CredentialError> {
     let username = get_current_username()?;
 
     // Use /usr/bin/security CLI - it's already authorized in the ACL
@@ -49,12 +68,10 @@ pub fn get_token_macos() -> Result<String, CredentialError> {
         .map_err(|_| CredentialError::NotFound)?;
 
     if output.status.success() {
-        let content = String::from_utf8(output.stdout)
+        Ok(String::from_utf8(output.stdout)
             .map_err(|_| CredentialError::Parse("Invalid UTF-8 in credentials".to_string()))?
             .trim()
-            .to_string();
-
-        parse_credential_json(&content)
+            .to_string())
     } else {
         // Exit code 44 = item not found, other codes are also treated as not found
         Err(CredentialError::NotFound)
@@ -3,6 +3,11 @@
 //! This module provides platform-specific credential retrieval:
 //! - macOS: Reads from Keychain
 //! - Linux: Reads from `~/.claude/.credentials.json`
+//! - Windows: Reads from Credential Manager, falling back to
+//!   `%USERPROFILE%\.claude\.credentials.json`
+//!
+//! [`diagnose`] reports what each of these backends holds, for
+//! troubleshooting.
 //!
 //! # Token Lifecycle
 //!
@@ -70,14 +75,29 @@ mod macos;
 #[cfg(target_os = "linux")]
 mod linux;
 
+#[cfg(windows)]
+mod windows;
+
+mod diagnose;
+
+use std::path::PathBuf;
+
 use crate::error::CredentialError;
 
+pub use diagnose::{diagnose, CredentialProbe, CredentialReport, ProbeOutcome, TokenInfo};
+
 /// Service name used by Claude Code in macOS Keychain.
 pub const KEYCHAIN_SERVICE: &str = "Claude Code-credentials";
 
 /// Path to credentials file on Linux (relative to HOME).
 pub const LINUX_CREDENTIALS_PATH: &str = ".claude/.credentials.json";
 
+/// Generic credential name used by Claude Code in Windows Credential Manager.
+pub const WINDOWS_CREDENTIAL_TARGET: &str = "Claude Code-credentials";
+
+/// Path to the fallback credentials file on Windows (relative to USERPROFILE).
+pub const WINDOWS_CREDENTIALS_PATH: &str = ".claude/.credentials.json";
+
 /// Environment variable that can override file-based credentials.
 pub const ENV_VAR_TOKEN: &str = "CLAUDE_CODE_OAUTH_TOKEN";
 
@@ -85,6 +105,7 @@ pub const ENV_VAR_TOKEN: &str = "CLAUDE_CODE_OAUTH_TOKEN";
 ///
 /// On macOS, this reads from the Keychain.
 /// On Linux, this reads from `~/.claude/.credentials.json`.
+/// On Windows, this reads from Credential Manager, then the credential file.
 ///
 /// The environment variable `CLAUDE_CODE_OAUTH_TOKEN` takes precedence
 /// on all platforms if set.
@@ -114,28 +135,257 @@ pub fn get_token() -> Result<String, CredentialError> {
         linux::get_token_linux()
     }
 
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    #[cfg(windows)]
+    {
+        windows::get_token_windows()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
     {
         Err(CredentialError::NotFound)
     }
 }
 
-/// Parse credential JSON and extract the access token.
+/// Where credentials were read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// The `CLAUDE_CODE_OAUTH_TOKEN` environment variable.
+    Env,
+    /// A credential JSON file.
+    File(PathBuf),
+    /// macOS Keychain.
+    Keychain,
+    /// Windows Credential Manager.
+    CredentialManager,
+}
+
+impl CredentialSource {
+    /// Returns true if a refreshed token can be written back to this source.
+    ///
+    /// Only credential files are written; Keychain and Credential Manager
+    /// items are left to Claude Code.
+    pub fn is_writable(&self) -> bool {
+        matches!(self, Self::File(_))
+    }
+}
+
+/// OAuth fields from Claude Code's `claudeAiOauth` credential entry.
+#[derive(Clone)]
+pub struct OAuthCredentials {
+    /// Access token sent to the usage API.
+    pub access_token: String,
+    /// Refresh token used to obtain a new access token, if stored.
+    pub refresh_token: Option<String>,
+    /// Access token expiry in milliseconds since the epoch, if stored.
+    pub expires_at_ms: Option<i64>,
+}
+
+impl std::fmt::Debug for OAuthCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print tokens
+        f.debug_struct("OAuthCredentials")
+            .field("access_token", &"<redacted>")
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("expires_at_ms", &self.expires_at_ms)
+            .finish()
+    }
+}
+
+impl OAuthCredentials {
+    /// Returns true if the access token expired before `now_ms`.
+    ///
+    /// Credentials without an expiry never expire.
+    pub fn is_expired_at(&self, now_ms: i64) -> bool {
+        self.expires_at_ms.is_some_and(|at| now_ms > at)
+    }
+
+    /// Returns true if the access token has expired.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(chrono::Utc::now().timestamp_millis())
+    }
+}
+
+/// Credentials together with their raw JSON and source, so a refreshed
+/// token can be written back in place.
+#[derive(Debug, Clone)]
+pub struct StoredCredentials {
+    /// Parsed OAuth fields.
+    pub oauth: OAuthCredentials,
+    /// Where the credentials came from.
+    pub source: CredentialSource,
+    /// Raw credential JSON (empty for [`CredentialSource::Env`]).
+    #[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+    pub(crate) raw: String,
+}
+
+/// Load OAuth credentials without checking expiry.
 ///
-/// This function is shared between macOS and Linux implementations.
+/// Unlike [`get_token`], an expired access token is returned as-is so the
+/// caller can refresh it. The environment variable takes precedence and
+/// yields credentials without a refresh token or expiry.
 ///
-/// # Arguments
+/// # Errors
+///
+/// Returns [`CredentialError`] if credentials are missing or cannot be parsed.
+pub fn load_credentials() -> Result<StoredCredentials, CredentialError> {
+    if let Some(stored) = env_credentials(ENV_VAR_TOKEN) {
+        return Ok(stored);
+    }
+
+    let (raw, source) = read_platform_credentials()?;
+    Ok(StoredCredentials {
+        oauth: parse_oauth_credentials(&raw)?,
+        source,
+        raw,
+    })
+}
+
+/// Credentials from environment variable `var`, if it is set and non-empty.
+pub(crate) fn env_credentials(var: &str) -> Option<StoredCredentials> {
+    let token = std::env::var(var).ok().filter(|t| !t.is_empty())?;
+    Some(StoredCredentials {
+        oauth: OAuthCredentials {
+            access_token: token,
+            refresh_token: None,
+            expires_at_ms: None,
+        },
+        source: CredentialSource::Env,
+        raw: String::new(),
+    })
+}
+
+/// Load OAuth credentials from a credential JSON file without checking expiry.
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if the file cannot be read or parsed.
+pub(crate) fn load_credentials_from_file(
+    path: &std::path::Path,
+) -> Result<StoredCredentials, CredentialError> {
+    let raw = read_credential_file(path)?;
+    Ok(StoredCredentials {
+        oauth: parse_oauth_credentials(&raw)?,
+        source: CredentialSource::File(path.to_path_buf()),
+        raw,
+    })
+}
+
+/// Read raw credential JSON from the platform's credential store.
+fn read_platform_credentials() -> Result<(String, CredentialSource), CredentialError> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::read_credentials_macos()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::read_credentials_linux()
+    }
+
+    #[cfg(windows)]
+    {
+        windows::read_credentials_windows()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+    {
+        Err(CredentialError::NotFound)
+    }
+}
+
+/// Write updated credential JSON back to its source.
+///
+/// A credential file is replaced atomically: the JSON goes to a temporary
+/// file next to it, readable only by its owner, which is then renamed over
+/// the original. Claude Code never sees a partial file, and a failed write
+/// leaves the old tokens in place.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::Permission`] if the source is not writable,
+/// or [`CredentialError::Io`] if the write fails.
+#[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+pub(crate) fn save_credentials(
+    source: &CredentialSource,
+    content: &str,
+) -> Result<(), CredentialError> {
+    match source {
+        CredentialSource::File(path) => {
+            write_private_atomic(path, content).map_err(|e| CredentialError::Io(e.to_string()))
+        }
+        other => Err(CredentialError::Permission(format!(
+            "cannot write credentials to {:?}",
+            other
+        ))),
+    }
+}
+
+/// Writes `content` to a temporary file next to `path` with mode 0600, then
+/// renames it over `path`.
+#[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+fn write_private_atomic(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(&tmp).and_then(|mut file| {
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    let result = written.and_then(|()| std::fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Read a credential file, mapping I/O failures to [`CredentialError`].
+pub(crate) fn read_credential_file(
+    creds_path: &std::path::Path,
+) -> Result<String, CredentialError> {
+    std::fs::read_to_string(creds_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => CredentialError::NotFound,
+        std::io::ErrorKind::PermissionDenied => {
+            CredentialError::Permission(creds_path.display().to_string())
+        }
+        _ => CredentialError::Io(e.to_string()),
+    })
+}
+
+/// Retrieve the OAuth access token from a specific credential file path.
 ///
-/// * `content` - The raw JSON content from Keychain or file
+/// This function is the testable core of file-based credential retrieval,
+/// separated from path resolution to avoid environment variable mutation in
+/// tests.
 ///
 /// # Errors
 ///
 /// Returns [`CredentialError`] if:
-/// - JSON parsing fails
-/// - `claudeAiOauth` field is missing
-/// - `accessToken` field is missing
-/// - Token is expired (based on `expiresAt`)
-pub(crate) fn parse_credential_json(content: &str) -> Result<String, CredentialError> {
+/// - Credentials file does not exist
+/// - File permissions prevent reading
+/// - Credentials cannot be parsed
+/// - Token is expired
+#[cfg(target_os = "linux")]
+pub(crate) fn get_token_from_path(creds_path: &std::path::Path) -> Result<String, CredentialError> {
+    parse_credential_json(&read_credential_file(creds_path)?)
+}
+
+/// Parse the `claudeAiOauth` entry of credential JSON without checking expiry.
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if JSON parsing fails or `claudeAiOauth` or
+/// `accessToken` is missing.
+pub(crate) fn parse_oauth_credentials(content: &str) -> Result<OAuthCredentials, CredentialError> {
     let json: serde_json::Value =
         serde_json::from_str(content).map_err(|e| CredentialError::Parse(e.to_string()))?;
 
@@ -143,20 +393,43 @@ pub(crate) fn parse_credential_json(content: &str) -> Result<String, CredentialE
         .get("claudeAiOauth")
         .ok_or(CredentialError::MissingField("claudeAiOauth"))?;
 
-    // Check expiration if expiresAt is present (value is milliseconds since epoch)
-    if let Some(expires_at_ms) = oauth.get("expiresAt").and_then(|v| v.as_i64()) {
-        let now_ms = chrono::Utc::now().timestamp_millis();
-        if now_ms > expires_at_ms {
-            return Err(CredentialError::Expired);
-        }
-    }
-
-    let token = oauth
+    let access_token = oauth
         .get("accessToken")
         .and_then(|v| v.as_str())
         .ok_or(CredentialError::MissingField("accessToken"))?;
 
-    Ok(token.to_string())
+    Ok(OAuthCredentials {
+        access_token: access_token.to_string(),
+        refresh_token: oauth
+            .get("refreshToken")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        // Value is milliseconds since epoch
+        expires_at_ms: oauth.get("expiresAt").and_then(|v| v.as_i64()),
+    })
+}
+
+/// Parse credential JSON and extract the access token.
+///
+/// This function is shared by all platform implementations.
+///
+/// # Arguments
+///
+/// * `content` - The raw JSON content from Keychain, Credential Manager, or file
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if:
+/// - JSON parsing fails
+/// - `claudeAiOauth` field is missing
+/// - `accessToken` field is missing
+/// - Token is expired (based on `expiresAt`)
+pub(crate) fn parse_credential_json(content: &str) -> Result<String, CredentialError> {
+    let oauth = parse_oauth_credentials(content)?;
+    if oauth.is_expired() {
+        return Err(CredentialError::Expired);
+    }
+    Ok(oauth.access_token)
 }
 
 #[cfg(test)]
@@ -233,6 +506,61 @@ mod tests {
         assert_eq!(token, "sk-ant-oat01-no-expiry");
     }
 
+    #[test]
+    fn test_parse_oauth_credentials_keeps_expired_token() {
+        let json = r#"{
+            "claudeAiOauth": {
+                "accessToken": "sk-ant-oat01-old",
+                "refreshToken": "sk-ant-ort01-refresh",
+                "expiresAt": 1000
+            }
+        }"#;
+        let oauth = parse_oauth_credentials(json).expect("expiry is not checked");
+        assert_eq!(oauth.refresh_token.as_deref(), Some("sk-ant-ort01-refresh"));
+        assert!(oauth.is_expired());
+        assert!(!oauth.is_expired_at(999));
+    }
+
+    #[test]
+    fn test_credentials_debug_redacts_tokens() {
+        let oauth = OAuthCredentials {
+            access_token: "sk-ant-oat01-secret".to_string(),
+            refresh_token: Some("sk-ant-ort01-secret".to_string()),
+            expires_at_ms: None,
+        };
+        assert!(!format!("{:?}", oauth).contains("secret"));
+    }
+
+    #[test]
+    fn test_only_files_are_writable() {
+        assert!(CredentialSource::File(PathBuf::from("/tmp/creds.json")).is_writable());
+        assert!(!CredentialSource::Keychain.is_writable());
+        assert!(!CredentialSource::CredentialManager.is_writable());
+        assert!(!CredentialSource::Env.is_writable());
+    }
+
+    #[test]
+    fn test_save_credentials_replaces_file_privately() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join(".credentials.json");
+        std::fs::write(&path, "old").expect("failed to write credentials");
+
+        save_credentials(&CredentialSource::File(path.clone()), "new").expect("should save");
+
+        assert_eq!(std::fs::read_to_string(&path).expect("readable"), "new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path)
+                .expect("metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let leftovers = std::fs::read_dir(dir.path()).expect("readable dir").count();
+        assert_eq!(leftovers, 1, "temporary file is renamed away");
+    }
+
     // Env var tests are combined into one function to avoid parallel test interference
     // since they modify the same environment variable (CLAUDE_CODE_OAUTH_TOKEN)
     #[test]
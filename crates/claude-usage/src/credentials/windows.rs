@@ -0,0 +1,169 @@
+//! Windows credential retrieval.
+//!
+//! This module retrieves Claude Code OAuth credentials from Windows Credential
+//! Manager, where they are stored as a generic credential named
+//! "Claude Code-credentials". If no such credential exists, it falls back to
+//! the credential file at `%USERPROFILE%\.claude\.credentials.json`, which is
+//! what Claude Code writes when Credential Manager is unavailable.
+
+use std::path::PathBuf;
+use std::ptr;
+
+use windows_sys::Win32::Security::Credentials::{
+    CredFree, CredReadW, CREDENTIALW, CRED_TYPE_GENERIC,
+};
+
+use super::{
+    parse_credential_json, read_credential_file, CredentialSource, WINDOWS_CREDENTIALS_PATH,
+    WINDOWS_CREDENTIAL_TARGET,
+};
+use crate::error::CredentialError;
+
+/// Retrieve the OAuth access token on Windows.
+///
+/// Credential Manager is tried first; the credential file is only read when
+/// Credential Manager has no entry.
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if:
+/// - Neither Credential Manager nor the credential file has credentials
+/// - USERPROFILE is not set (file fallback only)
+/// - Credentials cannot be parsed
+/// - Token is expired
+pub fn get_token_windows() -> Result<String, CredentialError> {
+    let (content, _) = read_credentials_windows()?;
+    parse_credential_json(&content)
+}
+
+/// Read the raw credential JSON from Credential Manager, or the fallback file.
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if neither source has credentials.
+pub fn read_credentials_windows() -> Result<(String, CredentialSource), CredentialError> {
+    match read_credential_manager()? {
+        Some(content) => Ok((content, CredentialSource::CredentialManager)),
+        None => {
+            let path = get_credentials_path()?;
+            Ok((read_credential_file(&path)?, CredentialSource::File(path)))
+        }
+    }
+}
+
+/// Probe Credential Manager and the fallback file for diagnostics.
+pub fn probe_windows() -> Vec<(CredentialSource, Result<String, CredentialError>)> {
+    let manager =
+        read_credential_manager().and_then(|content| content.ok_or(CredentialError::NotFound));
+    let file = match get_credentials_path() {
+        Ok(path) => {
+            let content = read_credential_file(&path);
+            (CredentialSource::File(path), content)
+        }
+        Err(e) => (
+            CredentialSource::File(PathBuf::from("%USERPROFILE%").join(WINDOWS_CREDENTIALS_PATH)),
+            Err(e),
+        ),
+    };
+    vec![(CredentialSource::CredentialManager, manager), file]
+}
+
+/// Read the credential blob from Windows Credential Manager.
+///
+/// Returns `Ok(None)` if no credential with the target name exists.
+fn read_credential_manager() -> Result<Option<String>, CredentialError> {
+    let target: Vec<u16> = WINDOWS_CREDENTIAL_TARGET
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut credential: *mut CREDENTIALW = ptr::null_mut();
+
+    // SAFETY: `target` is a NUL-terminated UTF-16 string that outlives the
+    // call, and `credential` is a valid out-pointer.
+    let found = unsafe { CredReadW(target.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) };
+    if found == 0 || credential.is_null() {
+        return Ok(None);
+    }
+
+    // SAFETY: CredReadW succeeded, so `credential` points to a CREDENTIALW
+    // whose blob holds `CredentialBlobSize` bytes. The blob is copied before
+    // the buffer is released with CredFree.
+    let blob = unsafe {
+        let cred = &*credential;
+        let bytes = if cred.CredentialBlob.is_null() {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(cred.CredentialBlob, cred.CredentialBlobSize as usize)
+                .to_vec()
+        };
+        CredFree(credential.cast());
+        bytes
+    };
+
+    decode_blob(&blob).map(Some)
+}
+
+/// Decode a credential blob as UTF-8, or as UTF-16LE when written by tools
+/// that store wide strings.
+fn decode_blob(blob: &[u8]) -> Result<String, CredentialError> {
+    let invalid = || CredentialError::Parse("Invalid encoding in credentials".to_string());
+    let text = if blob.len() >= 2 && blob.len() % 2 == 0 && blob[1] == 0 {
+        let wide: Vec<u16> = blob
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&wide).map_err(|_| invalid())?
+    } else {
+        String::from_utf8(blob.to_vec()).map_err(|_| invalid())?
+    };
+    Ok(text.trim_end_matches('\0').trim().to_string())
+}
+
+/// Get the path to the fallback credentials file.
+fn get_credentials_path() -> Result<PathBuf, CredentialError> {
+    let profile = std::env::var("USERPROFILE").map_err(|_| CredentialError::NoHomeDir)?;
+    Ok(PathBuf::from(profile).join(WINDOWS_CREDENTIALS_PATH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_target_name() {
+        assert_eq!(WINDOWS_CREDENTIAL_TARGET, "Claude Code-credentials");
+    }
+
+    #[test]
+    fn test_decode_utf8_blob() {
+        let decoded = decode_blob(br#"{"claudeAiOauth":{}}"#).expect("should decode");
+        assert_eq!(decoded, r#"{"claudeAiOauth":{}}"#);
+    }
+
+    #[test]
+    fn test_decode_utf16_blob() {
+        let blob: Vec<u8> = "{\"a\":1}"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(decode_blob(&blob).expect("should decode"), "{\"a\":1}");
+    }
+
+    // Integration test - only runs manually when credentials exist
+    #[test]
+    #[ignore = "requires real Windows credentials"]
+    fn env_get_token_windows() {
+        match get_token_windows() {
+            Ok(token) => {
+                assert!(token.starts_with("sk-ant-oat01-"));
+                println!("Token retrieved successfully (first 20 chars hidden)");
+            }
+            Err(CredentialError::NotFound) => {
+                println!("No credentials found - expected if not logged in");
+            }
+            Err(e) => {
+                panic!("Unexpected error: {}", e);
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use thiserror::Error;
 
 /// Errors that can occur when retrieving credentials.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum CredentialError {
     /// Claude Code credentials not found in the platform's secure storage.
     #[error("Claude Code credentials not found. Run `claude` to login.")]
@@ -29,9 +29,13 @@ pub enum CredentialError {
     #[error("I/O error reading credentials: {0}")]
     Io(String),
 
-    /// HOME directory not set (Linux/Unix).
-    #[error("HOME environment variable not set")]
+    /// Home directory not set (HOME on Linux/Unix, USERPROFILE on Windows).
+    #[error("Home directory environment variable not set (HOME or USERPROFILE)")]
     NoHomeDir,
+
+    /// Requested credential profile is not defined.
+    #[error("Unknown credential profile: {0}")]
+    UnknownProfile(String),
 }
 
 /// Errors that can occur when calling the Anthropic API.
@@ -0,0 +1,458 @@
+//! Persistent usage history.
+//!
+//! [`UsageHistoryStore`] appends one timestamped sample per fetched
+//! [`UsageData`] to a JSON Lines file, one compact object per line:
+//!
+//! ```text
+//! {"t":"2026-10-16T09:30:00Z","h5":42.0,"d7":17.5}
+//! ```
+//!
+//! Appending never rewrites the file, so several processes can record to the
+//! same log. Unreadable lines are skipped when reading. Query helpers return
+//! samples in a time range and the peak utilization per calendar day.
+//!
+//! ```rust,ignore
+//! use claude_usage::{get_usage, history::UsageHistoryStore};
+//!
+//! let store = UsageHistoryStore::open_default().expect("data directory");
+//! store.append(&get_usage()?)?;
+//! for day in store.daily_max(&chrono::Local)? {
+//!     println!("{}: 5h peak {}%", day.date, day.five_hour);
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, TimeDelta, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::UsageData;
+
+/// History file name inside the data directory.
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// Utilization recorded at one point in time.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct HistorySample {
+    /// When the sample was fetched.
+    #[serde(rename = "t")]
+    pub at: DateTime<Utc>,
+    /// 5-hour window utilization (percent).
+    #[serde(rename = "h5")]
+    pub five_hour: f64,
+    /// 7-day window utilization (percent).
+    #[serde(rename = "d7")]
+    pub seven_day: f64,
+    /// 7-day Sonnet utilization (percent), if reported.
+    #[serde(rename = "s7", default, skip_serializing_if = "Option::is_none")]
+    pub seven_day_sonnet: Option<f64>,
+}
+
+impl HistorySample {
+    /// Creates a sample from `usage` fetched at `at`.
+    pub fn from_usage(usage: &UsageData, at: DateTime<Utc>) -> Self {
+        Self {
+            at,
+            five_hour: usage.five_hour.utilization,
+            seven_day: usage.seven_day.utilization,
+            seven_day_sonnet: usage.seven_day_sonnet.as_ref().map(|p| p.utilization),
+        }
+    }
+}
+
+/// Peak utilization within one calendar day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyMax {
+    /// The day, in the time zone passed to the query.
+    pub date: NaiveDate,
+    /// Highest 5-hour utilization sampled that day (percent).
+    pub five_hour: f64,
+    /// Highest 7-day utilization sampled that day (percent).
+    pub seven_day: f64,
+    /// Number of samples that day.
+    pub samples: usize,
+}
+
+/// Append-only usage history log on disk.
+#[derive(Debug, Clone)]
+pub struct UsageHistoryStore {
+    /// Path of the JSON Lines log.
+    path: PathBuf,
+}
+
+impl UsageHistoryStore {
+    /// Opens the log at `path`. The file is created on the first append.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Opens the log at [`default_history_path`], or None if no data
+    /// directory can be determined.
+    pub fn open_default() -> Option<Self> {
+        default_history_path().map(Self::open)
+    }
+
+    /// Returns the log file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a sample of `usage`, timestamped now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory or file cannot be written.
+    pub fn append(&self, usage: &UsageData) -> io::Result<HistorySample> {
+        let sample = HistorySample::from_usage(usage, Utc::now());
+        self.append_sample(&sample)?;
+        Ok(sample)
+    }
+
+    /// Appends `sample` as one line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory or file cannot be written.
+    pub fn append_sample(&self, sample: &HistorySample) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(sample).map_err(io::Error::other)?;
+        line.push('\n');
+        // A single write of a short line keeps concurrent appends intact
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Returns all samples in file order (oldest first for a single writer).
+    ///
+    /// A missing file yields no samples; unparseable lines are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read.
+    pub fn samples(&self) -> io::Result<Vec<HistorySample>> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut samples = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(sample) = serde_json::from_str(&line?) {
+                samples.push(sample);
+            }
+        }
+        Ok(samples)
+    }
+
+    /// Returns samples with `from <= at < to`, sorted by time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read.
+    pub fn samples_in_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> io::Result<Vec<HistorySample>> {
+        let mut samples: Vec<_> = self
+            .samples()?
+            .into_iter()
+            .filter(|s| s.at >= from && s.at < to)
+            .collect();
+        samples.sort_by_key(|s| s.at);
+        Ok(samples)
+    }
+
+    /// Returns the peak utilization per day in `tz`, oldest day first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file exists but cannot be read.
+    pub fn daily_max<Tz: TimeZone>(&self, tz: &Tz) -> io::Result<Vec<DailyMax>> {
+        Ok(daily_max(&self.samples()?, tz))
+    }
+
+    /// Rewrites the log without samples older than `cutoff`.
+    ///
+    /// Returns the number of samples removed. Not safe to run while another
+    /// process appends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be read or replaced.
+    pub fn prune_before(&self, cutoff: DateTime<Utc>) -> io::Result<usize> {
+        let samples = self.samples()?;
+        let kept: Vec<_> = samples.iter().filter(|s| s.at >= cutoff).collect();
+        let removed = samples.len() - kept.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+        let mut content = String::new();
+        for sample in kept {
+            content.push_str(&serde_json::to_string(sample).map_err(io::Error::other)?);
+            content.push('\n');
+        }
+        let tmp = self.path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(removed)
+    }
+}
+
+/// Groups `samples` by calendar day in `tz` and returns each day's peaks.
+pub fn daily_max<Tz: TimeZone>(samples: &[HistorySample], tz: &Tz) -> Vec<DailyMax> {
+    let mut days: BTreeMap<NaiveDate, DailyMax> = BTreeMap::new();
+    for sample in samples {
+        let date = sample.at.with_timezone(tz).date_naive();
+        let day = days.entry(date).or_insert(DailyMax {
+            date,
+            five_hour: sample.five_hour,
+            seven_day: sample.seven_day,
+            samples: 0,
+        });
+        day.five_hour = day.five_hour.max(sample.five_hour);
+        day.seven_day = day.seven_day.max(sample.seven_day);
+        day.samples += 1;
+    }
+    days.into_values().collect()
+}
+
+/// Recent growth of one utilization value, in percent per hour.
+///
+/// Uses samples from the `window` before `now`, starting after the most recent
+/// drop in value (a period reset), and returns the slope between the first and
+/// last of them. Returns None if fewer than two samples remain or they span
+/// less than five minutes.
+pub fn recent_rate(
+    samples: &[HistorySample],
+    window: TimeDelta,
+    now: DateTime<Utc>,
+    value: impl Fn(&HistorySample) -> f64,
+) -> Option<f64> {
+    let mut recent: Vec<&HistorySample> = samples
+        .iter()
+        .filter(|s| s.at > now - window && s.at <= now)
+        .collect();
+    recent.sort_by_key(|s| s.at);
+    let start = recent
+        .windows(2)
+        .rposition(|pair| value(pair[1]) < value(pair[0]))
+        .map_or(0, |i| i + 1);
+    let (first, last) = (recent.get(start)?, recent.last()?);
+    let span = last.at - first.at;
+    if span < TimeDelta::minutes(5) {
+        return None;
+    }
+    Some((value(last) - value(first)) / (span.num_seconds() as f64 / 3600.0))
+}
+
+/// Returns the default history file path.
+///
+/// Uses `$XDG_DATA_HOME/claude-usage/history.jsonl`, falling back to
+/// `~/.local/share/claude-usage/history.jsonl`, or `%LOCALAPPDATA%` on
+/// Windows. Returns None if none of these variables are set.
+pub fn default_history_path() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let dir = non_empty("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))?;
+    Some(dir.join("claude-usage").join(HISTORY_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UsagePeriod;
+    use chrono::FixedOffset;
+    use tempfile::TempDir;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn sample(time: &str, five_hour: f64, seven_day: f64) -> HistorySample {
+        HistorySample {
+            at: at(time),
+            five_hour,
+            seven_day,
+            seven_day_sonnet: None,
+        }
+    }
+
+    fn store_in(dir: &TempDir) -> UsageHistoryStore {
+        UsageHistoryStore::open(dir.path().join("nested").join(HISTORY_FILE_NAME))
+    }
+
+    #[test]
+    fn test_sample_serializes_compactly() {
+        let json =
+            serde_json::to_string(&sample("2026-10-16T09:30:00Z", 42.0, 17.5)).expect("serialize");
+        assert_eq!(json, r#"{"t":"2026-10-16T09:30:00Z","h5":42.0,"d7":17.5}"#);
+    }
+
+    #[test]
+    fn test_append_and_read_back() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        assert!(store.samples().expect("missing file is empty").is_empty());
+
+        let usage = UsageData {
+            five_hour: UsagePeriod {
+                utilization: 12.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 3.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: Some(UsagePeriod {
+                utilization: 1.0,
+                resets_at: None,
+                extra: Default::default(),
+            }),
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        };
+        let recorded = store.append(&usage).expect("append");
+        store
+            .append_sample(&sample("2026-10-16T10:00:00Z", 20.0, 4.0))
+            .expect("append");
+
+        let samples = store.samples().expect("read");
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0], recorded);
+        assert_eq!(samples[0].seven_day_sonnet, Some(1.0));
+    }
+
+    #[test]
+    fn test_corrupt_lines_are_skipped() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        store
+            .append_sample(&sample("2026-10-16T09:00:00Z", 1.0, 1.0))
+            .expect("append");
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(store.path())
+            .expect("open");
+        writeln!(file, "{{truncated").expect("write");
+        store
+            .append_sample(&sample("2026-10-16T10:00:00Z", 2.0, 1.0))
+            .expect("append");
+        assert_eq!(store.samples().expect("read").len(), 2);
+    }
+
+    #[test]
+    fn test_samples_in_range_is_half_open_and_sorted() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        for s in [
+            sample("2026-10-16T11:00:00Z", 3.0, 1.0),
+            sample("2026-10-16T09:00:00Z", 1.0, 1.0),
+            sample("2026-10-16T10:00:00Z", 2.0, 1.0),
+            sample("2026-10-16T12:00:00Z", 4.0, 1.0),
+        ] {
+            store.append_sample(&s).expect("append");
+        }
+        let range = store
+            .samples_in_range(at("2026-10-16T09:30:00Z"), at("2026-10-16T12:00:00Z"))
+            .expect("query");
+        let values: Vec<_> = range.iter().map(|s| s.five_hour).collect();
+        assert_eq!(values, [2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_daily_max_groups_by_day_in_time_zone() {
+        let samples = [
+            sample("2026-10-15T23:30:00Z", 50.0, 10.0),
+            sample("2026-10-16T01:00:00Z", 30.0, 12.0),
+            sample("2026-10-16T08:00:00Z", 70.0, 11.0),
+        ];
+        let utc = daily_max(&samples, &Utc);
+        assert_eq!(utc.len(), 2);
+        assert_eq!(utc[1].five_hour, 70.0);
+        assert_eq!(utc[1].seven_day, 12.0);
+        assert_eq!(utc[1].samples, 2);
+
+        // At UTC+2 the first sample already falls on the 16th
+        let plus_two = FixedOffset::east_opt(2 * 3600).expect("valid offset");
+        let local = daily_max(&samples, &plus_two);
+        assert_eq!(local.len(), 1);
+        assert_eq!(local[0].five_hour, 70.0);
+        assert_eq!(local[0].samples, 3);
+    }
+
+    #[test]
+    fn test_prune_before_drops_old_samples() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        for s in [
+            sample("2026-10-01T00:00:00Z", 1.0, 1.0),
+            sample("2026-10-16T00:00:00Z", 2.0, 1.0),
+        ] {
+            store.append_sample(&s).expect("append");
+        }
+        let removed = store
+            .prune_before(at("2026-10-10T00:00:00Z"))
+            .expect("prune");
+        assert_eq!(removed, 1);
+        let left = store.samples().expect("read");
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].five_hour, 2.0);
+    }
+
+    #[test]
+    fn test_recent_rate_uses_slope_within_window() {
+        let samples = [
+            sample("2026-10-16T08:00:00Z", 0.0, 1.0),
+            sample("2026-10-16T09:30:00Z", 10.0, 2.0),
+            sample("2026-10-16T10:00:00Z", 20.0, 3.0),
+        ];
+        let now = at("2026-10-16T10:00:00Z");
+        let rate = recent_rate(&samples, TimeDelta::hours(1), now, |s| s.five_hour);
+        assert_eq!(rate, Some(20.0), "8:00 sample is outside the window");
+    }
+
+    #[test]
+    fn test_recent_rate_ignores_samples_before_reset() {
+        let samples = [
+            sample("2026-10-16T09:00:00Z", 90.0, 1.0),
+            sample("2026-10-16T09:10:00Z", 2.0, 1.0),
+            sample("2026-10-16T09:40:00Z", 7.0, 1.0),
+        ];
+        let now = at("2026-10-16T10:00:00Z");
+        let rate = recent_rate(&samples, TimeDelta::hours(1), now, |s| s.five_hour);
+        assert_eq!(rate, Some(10.0));
+    }
+
+    #[test]
+    fn test_recent_rate_needs_enough_span() {
+        let samples = [
+            sample("2026-10-16T09:58:00Z", 1.0, 1.0),
+            sample("2026-10-16T10:00:00Z", 2.0, 1.0),
+        ];
+        let now = at("2026-10-16T10:00:00Z");
+        assert_eq!(
+            recent_rate(&samples, TimeDelta::hours(1), now, |s| s.five_hour),
+            None
+        );
+        assert_eq!(
+            recent_rate(&[], TimeDelta::hours(1), now, |s| s.five_hour),
+            None
+        );
+    }
+}
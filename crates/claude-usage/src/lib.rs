@@ -18,11 +18,39 @@
 //!
 //! ## Features
 //!
-//! - **Cross-platform credentials**: macOS Keychain, Linux credential file
+//! - **Cross-platform credentials**: macOS Keychain, Linux credential file,
+//!   Windows Credential Manager
 //! - **Typed responses**: [`UsageData`], [`UsagePeriod`], [`ExtraUsage`]
 //! - **Secure handling**: Tokens are read, used, and immediately discarded
 //! - **Helper methods**: Check if usage is on-pace, time until reset
+//! - **Usage history**: Append samples to an on-disk log and query them
+//!   ([`history::UsageHistoryStore`])
+//! - **Multiple accounts**: Named credential profiles via
+//!   [`get_usage_for_profile`]
+//! - **Organization reports**: Per-workspace token usage and cost via the
+//!   Admin API ([`organization`])
+//! - **Cost estimates**: Token counts to dollars with overridable per-model
+//!   rates ([`pricing::PricingTable`])
+//! - **Token refresh**: Expired access tokens are refreshed and saved back
+//!   ([`RefreshMode`])
+//! - **Proxies and custom CAs**: `HTTPS_PROXY`/`NO_PROXY` are honored;
+//!   explicit proxies, extra root certificates or a preconfigured reqwest
+//!   client can be installed via `http`
+//! - **Automatic retries**: jittered exponential backoff honoring `Retry-After`
+//!   ([`RetryPolicy`])
+//! - **Threshold alerts**: Callbacks when utilization crosses 50/80/95% or a
+//!   window resets ([`watcher::UsageWatcher`])
+//! - **Response caching**: [`get_usage_cached`] and [`UsageCache`] with a TTL
+//! - **API key authentication**: [`Auth`] sends an OAuth token or an
+//!   `x-api-key`, for key-authenticated gateways
+//! - **Async client**: `get_usage_async()` via the `async` feature, and
+//!   `subscribe(interval)` for a stream of usage events
+//! - **WebAssembly**: Without the HTTP client features, the types and parsing
+//!   build for `wasm32-unknown-unknown` with a caller-supplied [`transport::Transport`]
+//! - **Testing**: [`transport::UsageClient`] with a canned-response mock
+//!   behind the `mock` feature
 //! - **Node.js bindings**: Available via the `napi` feature
+//! - **Python bindings**: Available via the `pyo3` feature
 //!
 //! ## Platform Support
 //!
@@ -30,7 +58,7 @@
 //! |----------|-------------------|--------|
 //! | macOS | Keychain ("Claude Code-credentials") | ✅ |
 //! | Linux | `~/.claude/.credentials.json` | ✅ |
-//! | Windows | - | ❌ |
+//! | Windows | Credential Manager ("Claude Code-credentials"), then `%USERPROFILE%\.claude\.credentials.json` | ✅ |
 //!
 //! ## Usage Examples
 //!
@@ -112,40 +140,105 @@
 //! export CLAUDE_CODE_OAUTH_TOKEN="sk-ant-oat01-..."
 //! ```
 //!
+//! `CLAUDE_USAGE_BASE_URL` points the usage API at a gateway or mock server
+//! instead of `https://api.anthropic.com`.
+//!
+//! With `CLAUDE_USAGE_BASE_URL` set and no OAuth credentials,
+//! `ANTHROPIC_API_KEY` is sent as an `x-api-key` header instead, for gateways
+//! that authenticate by key. Anthropic's own endpoint only accepts OAuth
+//! tokens, so the key is never sent to it:
+//!
+//! ```bash
+//! export ANTHROPIC_API_KEY="sk-ant-api03-..."
+//! export CLAUDE_USAGE_BASE_URL="https://llm-gateway.corp.example"
+//! ```
+//!
 //! ## Module Overview
 //!
-//! - [`client`]: HTTP client for the Anthropic usage API
+//! - [`auth`]: OAuth token or API key request authentication ([`Auth`])
+//! - [`budget`]: Warn, critical and hard-stop levels for pausing work
+//! - [`cache`]: TTL cache shared between processes ([`UsageCache`])
+//! - [`client`]: HTTP client for the Anthropic usage API ([`UsageApiClient`])
+//! - `http`: Proxy and TLS settings for the built-in HTTP clients
+//! - [`history`]: Persistent usage history with range and daily-peak queries
+//! - [`organization`]: Admin API usage and cost reports per workspace
+//! - [`pricing`]: Per-model rates and dollar estimates from token counts
+//! - [`profile`]: Named credential profiles for multiple accounts
+//! - [`refresh`]: OAuth access token refresh
+//! - [`retry`]: Retry policy for transient API failures
 //! - [`credentials`]: Platform-specific credential retrieval
+//! - `stream`: Polling subscription as an async stream (requires `async` feature)
+//! - [`transport`]: Pluggable HTTP transport (e.g. for `wasm32` builds)
+//! - [`watcher`]: Threshold and reset callbacks for polled usage
 //! - [`types`]: Response types ([`UsageData`], [`UsagePeriod`], [`ExtraUsage`])
 //! - [`error`]: Error types ([`Error`], [`CredentialError`], [`ApiError`])
+//! - `mock`: Canned usage data for tests (requires `mock` feature)
 //! - `napi`: Node.js bindings (requires `napi` feature)
+//! - `python`: Python bindings (requires `pyo3` feature)
 //!
 //! ## Security
 //!
 //! This crate follows strict security practices:
 //!
 //! 1. Tokens are read from secure storage, used once, and immediately discarded
-//! 2. Tokens are never stored in memory, logged, or passed to other modules
+//! 2. Tokens are never logged or passed to other modules. The only write is
+//!    a refreshed token pair saved back to the credential file it came from,
+//!    atomically and readable only by its owner
 //! 3. Error messages use generic text to prevent credential exposure
 
+pub mod auth;
+pub mod budget;
+pub mod cache;
 pub mod client;
 pub mod credentials;
 pub mod error;
+pub mod history;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub mod http;
+#[cfg(feature = "mock")]
+pub mod mock;
 #[cfg(feature = "napi")]
 pub mod napi;
+pub mod organization;
+pub mod pricing;
+pub mod profile;
+#[cfg(feature = "pyo3")]
+pub mod python;
+pub mod refresh;
+pub mod retry;
+#[cfg(feature = "async")]
+pub mod stream;
+pub mod transport;
 pub mod types;
+pub mod watcher;
 
+pub use auth::Auth;
+pub use budget::{BudgetPolicy, BudgetVerdict, Severity};
+pub use cache::{CachedUsage, UsageCache, DEFAULT_CACHE_TTL};
+pub use client::UsageApiClient;
 #[cfg(feature = "blocking")]
-pub use client::fetch_usage_raw;
-pub use credentials::get_token;
+pub use client::{fetch_usage_raw, fetch_usage_raw_with_auth, fetch_usage_raw_with_retry};
+#[cfg(feature = "async")]
+pub use client::{
+    fetch_usage_raw_async, fetch_usage_raw_async_with_auth, fetch_usage_raw_async_with_retry,
+};
+pub use credentials::{get_token, load_credentials};
 pub use error::{ApiError, CredentialError, Error};
-pub use types::{ExtraUsage, UsageData, UsagePeriod};
+pub use profile::{Profile, ProfileUsage};
+pub use refresh::RefreshMode;
+pub use retry::RetryPolicy;
+pub use types::{
+    BucketUsage, ExtraUsage, PeriodDelta, UsageBreakdown, UsageData, UsageDelta, UsagePeriod,
+};
 
 /// Fetch current Claude API usage data.
 ///
 /// This is the main entry point for the crate. It:
-/// 1. Retrieves credentials from platform-specific storage
-/// 2. Calls the Anthropic usage API
+/// 1. Retrieves credentials from platform-specific storage, refreshing an
+///    expired access token per [`RefreshMode::Persist`], or a gateway API
+///    key (see [`get_auth`])
+/// 2. Calls the Anthropic usage API, retrying transient failures with the
+///    default [`RetryPolicy`]
 /// 3. Returns typed usage data
 ///
 /// # Example
@@ -166,20 +259,482 @@ pub use types::{ExtraUsage, UsageData, UsagePeriod};
 /// - Response parsing fails
 #[cfg(feature = "blocking")]
 pub fn get_usage() -> Result<UsageData, Error> {
-    let token = credentials::get_token()?;
-    let response = client::fetch_usage_raw(&token)?;
-    let usage: UsageData =
-        serde_json::from_str(&response).map_err(|e| Error::Parse(e.to_string()))?;
-    Ok(usage)
+    get_usage_with_retry(&RetryPolicy::default())
+}
+
+/// Fetch current Claude API usage data, retrying per `policy`.
+///
+/// Pass [`RetryPolicy::disabled()`] to make a single attempt.
+///
+/// # Errors
+///
+/// Returns [`Error`] under the same conditions as [`get_usage`].
+#[cfg(feature = "blocking")]
+pub fn get_usage_with_retry(policy: &RetryPolicy) -> Result<UsageData, Error> {
+    let auth = get_auth(RefreshMode::default())?;
+    UsageApiClient::new()
+        .retry_policy(policy.clone())
+        .fetch_usage_with_auth(&auth)
+}
+
+/// Retrieve the credentials to authenticate with: the OAuth access token,
+/// refreshed per `mode`.
+///
+/// Without OAuth credentials, the API key in `ANTHROPIC_API_KEY` is used
+/// only if `CLAUDE_USAGE_BASE_URL` points the usage API at a gateway;
+/// Anthropic's endpoint rejects API keys, so it never receives one.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::NotFound`] if there are neither, otherwise
+/// the errors of [`get_token_refreshing`].
+#[cfg(feature = "blocking")]
+pub fn get_auth(mode: RefreshMode) -> Result<Auth, Error> {
+    match get_token_refreshing(mode) {
+        Ok(token) => Ok(Auth::OAuth(token)),
+        Err(Error::Credential(CredentialError::NotFound)) => gateway_api_key(),
+        Err(e) => Err(e),
+    }
+}
+
+/// Retrieve a valid access token, refreshing an expired one per `mode`.
+///
+/// With [`RefreshMode::Persist`], the refreshed tokens are written back to
+/// the credential file so Claude Code picks them up too.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::Expired`] if the token has expired and cannot
+/// be refreshed under `mode`, or the refresh request's error.
+#[cfg(feature = "blocking")]
+pub fn get_token_refreshing(mode: RefreshMode) -> Result<String, Error> {
+    refresh_stored(credentials::load_credentials()?, mode)
+}
+
+/// Returns the access token from `stored`, refreshing it per `mode`.
+#[cfg(feature = "blocking")]
+fn refresh_stored(
+    stored: credentials::StoredCredentials,
+    mode: RefreshMode,
+) -> Result<String, Error> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    match refresh::plan(&stored, mode, now_ms)? {
+        None => Ok(stored.oauth.access_token),
+        Some(refresh_token) => {
+            let refreshed = refresh::refresh_access_token(refresh_token)?;
+            Ok(refresh::keep(&stored, refreshed, mode))
+        }
+    }
+}
+
+/// Async counterpart of `get_token_refreshing`.
+///
+/// # Errors
+///
+/// Returns the same errors as `get_token_refreshing`.
+#[cfg(feature = "async")]
+pub async fn get_token_refreshing_async(mode: RefreshMode) -> Result<String, Error> {
+    refresh_stored_async(credentials::load_credentials()?, mode).await
+}
+
+/// Async counterpart of `get_auth`.
+///
+/// # Errors
+///
+/// Returns the same errors as `get_auth`.
+#[cfg(feature = "async")]
+pub async fn get_auth_async(mode: RefreshMode) -> Result<Auth, Error> {
+    match get_token_refreshing_async(mode).await {
+        Ok(token) => Ok(Auth::OAuth(token)),
+        Err(Error::Credential(CredentialError::NotFound)) => gateway_api_key(),
+        Err(e) => Err(e),
+    }
+}
+
+/// The API key fallback of `get_auth`: `ANTHROPIC_API_KEY`, if
+/// `CLAUDE_USAGE_BASE_URL` is set.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn gateway_api_key() -> Result<Auth, Error> {
+    if client::base_url_from_env().is_none() {
+        return Err(CredentialError::NotFound.into());
+    }
+    Ok(Auth::api_key_from_env()?)
+}
+
+/// Async counterpart of `refresh_stored`.
+#[cfg(feature = "async")]
+async fn refresh_stored_async(
+    stored: credentials::StoredCredentials,
+    mode: RefreshMode,
+) -> Result<String, Error> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    match refresh::plan(&stored, mode, now_ms)? {
+        None => Ok(stored.oauth.access_token),
+        Some(refresh_token) => {
+            let refreshed = refresh::refresh_access_token_async(refresh_token).await?;
+            Ok(refresh::keep(&stored, refreshed, mode))
+        }
+    }
+}
+
+/// Fetch usage data for the named credential profile.
+///
+/// The profile is looked up with [`profile::find_profile`]: `default` uses
+/// the same credentials as [`get_usage`], other names come from the profiles
+/// file or a `CLAUDE_CODE_OAUTH_TOKEN_<NAME>` environment variable. Expired
+/// tokens are refreshed and retries applied as in [`get_usage`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use claude_usage::get_usage_for_profile;
+///
+/// let usage = get_usage_for_profile("work")?;
+/// println!("{}: {}%", usage.profile, usage.data.five_hour.utilization);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CredentialError::UnknownProfile`] if the profile is not defined,
+/// otherwise the same errors as [`get_usage`].
+#[cfg(feature = "blocking")]
+pub fn get_usage_for_profile(name: &str) -> Result<ProfileUsage, Error> {
+    let profile = profile::find_profile(name)?;
+    let data = fetch_profile_usage(&profile)?;
+    Ok(ProfileUsage {
+        profile: profile.name,
+        data,
+    })
+}
+
+/// Fetch usage for every configured profile at once.
+///
+/// Profiles come from [`profile::list_profiles`] and are fetched in
+/// parallel threads. One profile failing does not affect the others: each
+/// gets its own result, keyed by profile name. The `default` profile is
+/// always included, so it reports an error if the platform store is empty.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// for (profile, usage) in claude_usage::get_usage_all()? {
+///     match usage {
+///         Ok(data) => println!("{}: {}%", profile, data.five_hour.utilization),
+///         Err(e) => println!("{}: {}", profile, e),
+///     }
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error`] only if the profiles file cannot be read; per-profile
+/// failures are in the map.
+#[cfg(feature = "blocking")]
+pub fn get_usage_all() -> Result<std::collections::BTreeMap<String, Result<UsageData, Error>>, Error>
+{
+    let profiles = profile::list_profiles()?;
+    Ok(std::thread::scope(|scope| {
+        let handles: Vec<_> = profiles
+            .iter()
+            .map(|profile| (&profile.name, scope.spawn(|| fetch_profile_usage(profile))))
+            .collect();
+        handles
+            .into_iter()
+            .map(|(name, handle)| {
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                (name.clone(), result)
+            })
+            .collect()
+    }))
+}
+
+/// Fetches usage with `profile`'s credentials, refreshing and retrying as
+/// [`get_usage`] does.
+#[cfg(feature = "blocking")]
+fn fetch_profile_usage(profile: &Profile) -> Result<UsageData, Error> {
+    let token = refresh_stored(profile.load_credentials()?, RefreshMode::default())?;
+    let response = client::fetch_usage_raw_with_retry(&token, &RetryPolicy::default())?;
+    transport::parse_usage(&response)
+}
+
+/// Fetch organization usage and cost per workspace.
+///
+/// Reads the Admin API key from `ANTHROPIC_ADMIN_KEY`, fetches the messages
+/// usage and cost reports for `query`, and sums them per workspace with
+/// [`organization::workspace_breakdown`].
+///
+/// # Errors
+///
+/// Returns [`CredentialError::NotFound`] if no admin key is set, otherwise
+/// the errors of [`organization::fetch_usage_report`].
+#[cfg(feature = "blocking")]
+pub fn get_workspace_usage(
+    query: &organization::ReportQuery,
+) -> Result<Vec<organization::WorkspaceUsage>, Error> {
+    let key = organization::admin_key()?;
+    let usage = organization::fetch_usage_report(&key, query)?;
+    let costs = organization::fetch_cost_report(&key, query)?;
+    Ok(organization::workspace_breakdown(&usage, &costs))
+}
+
+/// Fetch usage data through the shared on-disk cache.
+///
+/// Returns the cached response if it is younger than `ttl`, otherwise calls
+/// [`get_usage`] and caches the result. If that call fails, the last cached
+/// response is returned with [`CachedUsage::stale`] set. The cache file lives
+/// at [`cache::default_cache_path`], so separate processes share one
+/// response; without a cache directory, one in-memory cache is shared by
+/// every call in the process.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use claude_usage::get_usage_cached;
+///
+/// let cached = get_usage_cached(Duration::from_secs(60))?;
+/// println!("5h: {}% (stale: {})", cached.data.five_hour.utilization, cached.stale);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error`] if the cache is empty or unreadable and [`get_usage`] fails.
+#[cfg(feature = "blocking")]
+pub fn get_usage_cached(ttl: std::time::Duration) -> Result<CachedUsage, Error> {
+    /// Cache shared by every call when there is no cache directory
+    static MEMORY: std::sync::OnceLock<UsageCache> = std::sync::OnceLock::new();
+
+    match cache::default_cache_path() {
+        Some(path) => UsageCache::on_disk(path, ttl).get_or_fetch(get_usage),
+        None => MEMORY
+            .get_or_init(|| UsageCache::in_memory(ttl))
+            .get_or_fetch_within(ttl, get_usage),
+    }
+}
+
+/// Fetch current Claude API usage data without blocking (async).
+///
+/// Async counterpart of `get_usage()` for tokio consumers, so callers don't
+/// need `spawn_blocking`. Credential retrieval still reads the platform
+/// store synchronously; it is a short local read.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use claude_usage::get_usage_async;
+///
+/// let usage = get_usage_async().await?;
+/// println!("5h utilization: {}%", usage.five_hour.utilization);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error`] under the same conditions as `get_usage()`.
+#[cfg(feature = "async")]
+pub async fn get_usage_async() -> Result<UsageData, Error> {
+    get_usage_async_with_retry(&RetryPolicy::default()).await
+}
+
+/// Fetch current Claude API usage data without blocking, retrying per `policy`.
+///
+/// # Errors
+///
+/// Returns [`Error`] under the same conditions as `get_usage()`.
+#[cfg(feature = "async")]
+pub async fn get_usage_async_with_retry(policy: &RetryPolicy) -> Result<UsageData, Error> {
+    let auth = get_auth_async(RefreshMode::default()).await?;
+    UsageApiClient::new()
+        .retry_policy(policy.clone())
+        .fetch_usage_async_with_auth(&auth)
+        .await
+}
+
+/// Poll usage every `interval` as a stream of events (async).
+///
+/// Yields [`UsageEvent::Updated`](watcher::UsageEvent::Updated) or
+/// [`UsageEvent::Error`](watcher::UsageEvent::Error) for each poll, followed
+/// by threshold crossings (at the default 50/80/95%) and period resets. The
+/// first poll happens when the stream is first polled. Use
+/// [`UsageWatcher::into_stream`](watcher::UsageWatcher::into_stream) for other
+/// thresholds or a different client.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use futures::StreamExt;
+///
+/// let mut events = claude_usage::subscribe(std::time::Duration::from_secs(60));
+/// while let Some(event) = events.next().await {
+///     println!("{:?}", event);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub fn subscribe(interval: std::time::Duration) -> stream::UsageStream {
+    watcher::UsageWatcher::new()
+        .interval(interval)
+        .into_stream(std::sync::Arc::new(transport::LiveUsageClient))
+}
+
+/// Async counterpart of `get_usage_for_profile()`.
+///
+/// # Errors
+///
+/// Returns the same errors as `get_usage_for_profile()`.
+#[cfg(feature = "async")]
+pub async fn get_usage_for_profile_async(name: &str) -> Result<ProfileUsage, Error> {
+    let profile = profile::find_profile(name)?;
+    let data = fetch_profile_usage_async(&profile).await?;
+    Ok(ProfileUsage {
+        profile: profile.name,
+        data,
+    })
+}
+
+/// Async counterpart of `get_usage_all()`. The profiles are fetched
+/// concurrently on the calling task.
+///
+/// # Errors
+///
+/// Returns the same errors as `get_usage_all()`.
+#[cfg(feature = "async")]
+pub async fn get_usage_all_async(
+) -> Result<std::collections::BTreeMap<String, Result<UsageData, Error>>, Error> {
+    let profiles = profile::list_profiles()?;
+    let results = join_all(profiles.iter().map(fetch_profile_usage_async).collect()).await;
+    Ok(profiles
+        .into_iter()
+        .map(|profile| profile.name)
+        .zip(results)
+        .collect())
+}
+
+/// Async counterpart of `fetch_profile_usage`.
+#[cfg(feature = "async")]
+async fn fetch_profile_usage_async(profile: &Profile) -> Result<UsageData, Error> {
+    let stored = profile.load_credentials()?;
+    let token = refresh_stored_async(stored, RefreshMode::default()).await?;
+    let response =
+        client::fetch_usage_raw_async_with_retry(&token, &RetryPolicy::default()).await?;
+    transport::parse_usage(&response)
+}
+
+/// Polls `futures` together until all complete, returning their outputs in
+/// order. Avoids requiring a spawning runtime.
+#[cfg(feature = "async")]
+async fn join_all<F: std::future::Future>(futures: Vec<F>) -> Vec<F::Output> {
+    use std::task::Poll;
+
+    let mut pending: Vec<_> = futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut outputs: Vec<Option<F::Output>> = pending.iter().map(|_| None).collect();
+    std::future::poll_fn(|cx| {
+        let mut done = true;
+        for (slot, output) in pending.iter_mut().zip(outputs.iter_mut()) {
+            if let Some(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => {
+                        *output = Some(value);
+                        *slot = None;
+                    }
+                    Poll::Pending => done = false,
+                }
+            }
+        }
+        if done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+    outputs
+        .into_iter()
+        .map(|output| output.expect("every future completed"))
+        .collect()
+}
+
+/// Async counterpart of `get_workspace_usage()`.
+///
+/// # Errors
+///
+/// Returns the same errors as `get_workspace_usage()`.
+#[cfg(feature = "async")]
+pub async fn get_workspace_usage_async(
+    query: &organization::ReportQuery,
+) -> Result<Vec<organization::WorkspaceUsage>, Error> {
+    let key = organization::admin_key()?;
+    let usage = organization::fetch_usage_report_async(&key, query).await?;
+    let costs = organization::fetch_cost_report_async(&key, query).await?;
+    Ok(organization::workspace_breakdown(&usage, &costs))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "blocking"))]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn test_join_all_runs_concurrently_in_order() {
+        use std::time::Duration;
+
+        let start = tokio::time::Instant::now();
+        let outputs = join_all(
+            [30, 10, 20]
+                .into_iter()
+                .map(|secs| async move {
+                    tokio::time::sleep(Duration::from_secs(secs)).await;
+                    secs
+                })
+                .collect(),
+        )
+        .await;
+        assert_eq!(outputs, vec![30, 10, 20]);
+        assert_eq!(start.elapsed(), Duration::from_secs(30));
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", feature = "blocking"))]
+    #[serial_test::serial]
+    fn test_get_auth_uses_api_key_only_with_base_url() {
+        let home = tempfile::tempdir().expect("failed to create temp dir");
+        let original_home = std::env::var_os("HOME");
+        let original_token = std::env::var_os(credentials::ENV_VAR_TOKEN);
+        std::env::set_var("HOME", home.path());
+        std::env::remove_var(credentials::ENV_VAR_TOKEN);
+        std::env::set_var(auth::ENV_VAR_API_KEY, "sk-ant-api03-x");
+
+        std::env::remove_var(client::ENV_VAR_BASE_URL);
+        let without_base_url = get_auth(RefreshMode::Disabled);
+        std::env::set_var(client::ENV_VAR_BASE_URL, "https://llm-gateway.example");
+        let with_base_url = get_auth(RefreshMode::Disabled);
+        std::env::remove_var(auth::ENV_VAR_API_KEY);
+        let without_key = get_auth(RefreshMode::Disabled);
+
+        std::env::remove_var(client::ENV_VAR_BASE_URL);
+        match original_home {
+            Some(value) => std::env::set_var("HOME", value),
+            None => std::env::remove_var("HOME"),
+        }
+        if let Some(value) = original_token {
+            std::env::set_var(credentials::ENV_VAR_TOKEN, value);
+        }
+        assert!(matches!(
+            without_base_url,
+            Err(Error::Credential(CredentialError::NotFound))
+        ));
+        assert_eq!(
+            with_base_url.expect("API key and base URL are set"),
+            Auth::ApiKey("sk-ant-api03-x".to_string())
+        );
+        assert!(matches!(
+            without_key,
+            Err(Error::Credential(CredentialError::NotFound))
+        ));
+    }
+
     #[test]
     #[ignore = "requires real credentials"]
-    #[cfg(feature = "blocking")]
     fn env_get_usage() {
         let result = get_usage();
         match result {
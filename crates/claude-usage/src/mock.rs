@@ -0,0 +1,221 @@
+//! Canned usage data for testing consumers of this crate.
+//!
+//! [`MockUsageClient`] implements [`UsageClient`] by serving queued
+//! responses instead of calling the API, so threshold, rendering and error
+//! handling logic can be tested without credentials or network access. The
+//! [`fixtures`] module builds typical [`UsageData`] values. Requires the
+//! `mock` feature; enable it from `[dev-dependencies]`.
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use claude_usage::mock::{fixtures, MockUsageClient};
+//! use claude_usage::transport::UsageClient;
+//!
+//! let client = MockUsageClient::new(fixtures::usage(40.0, 10.0))
+//!     .with_response(Ok(fixtures::near_limit()))
+//!     .with_response(Err(fixtures::forbidden()));
+//! let client: Arc<dyn UsageClient> = Arc::new(client);
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::error::{ApiError, Error};
+use crate::transport::{UsageClient, UsageFuture};
+use crate::types::UsageData;
+
+/// [`UsageClient`] that serves canned responses.
+///
+/// Queued responses are served first, in order. Once the queue is empty the
+/// fallback data is served on every call, or an [`ApiError::Network`] error
+/// if there is none.
+#[derive(Debug, Default)]
+pub struct MockUsageClient {
+    /// Responses served before the fallback.
+    queue: Mutex<VecDeque<Result<UsageData, Error>>>,
+    /// Served when the queue is empty.
+    fallback: Option<UsageData>,
+    /// Number of fetches so far.
+    calls: AtomicUsize,
+}
+
+impl MockUsageClient {
+    /// Creates a client that always serves `usage`.
+    pub fn new(usage: UsageData) -> Self {
+        Self {
+            fallback: Some(usage),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a client that serves only queued responses.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Creates a client that always serves a usage API response body.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Parse`] if `json` is not valid usage JSON.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        crate::transport::parse_usage(json).map(Self::new)
+    }
+
+    /// Queues `response`; builder form of [`push`](Self::push).
+    pub fn with_response(self, response: Result<UsageData, Error>) -> Self {
+        self.push(response);
+        self
+    }
+
+    /// Queues `response` to be served before the fallback.
+    pub fn push(&self, response: Result<UsageData, Error>) {
+        self.lock_queue().push_back(response);
+    }
+
+    /// Returns how many times usage was fetched.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Serves the next response without an async runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns a queued error, or [`ApiError::Network`] when nothing is left
+    /// to serve.
+    pub fn next_usage(&self) -> Result<UsageData, Error> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if let Some(response) = self.lock_queue().pop_front() {
+            return response;
+        }
+        self.fallback.clone().ok_or_else(|| {
+            ApiError::Network("mock client has no responses left".to_string()).into()
+        })
+    }
+
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, VecDeque<Result<UsageData, Error>>> {
+        // A panicking test thread must not hide the queue from the others
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl UsageClient for MockUsageClient {
+    fn fetch_usage(&self) -> UsageFuture<'_> {
+        let response = self.next_usage();
+        Box::pin(std::future::ready(response))
+    }
+}
+
+/// Typical usage values for tests.
+pub mod fixtures {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::error::{ApiError, Error};
+    use crate::types::{UsageData, UsagePeriod};
+
+    /// Usage at the given 5-hour and 7-day utilization, with each window
+    /// halfway to its reset.
+    pub fn usage(five_hour: f64, seven_day: f64) -> UsageData {
+        let now = Utc::now();
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: five_hour,
+                resets_at: Some(now + TimeDelta::minutes(150)),
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: seven_day,
+                resets_at: Some(now + TimeDelta::hours(84)),
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    /// No usage yet: 0% and no reset times, as the API reports idle windows.
+    pub fn idle() -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: 0.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 0.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    /// 5-hour window at 92%, well ahead of pace.
+    pub fn near_limit() -> UsageData {
+        usage(92.0, 35.0)
+    }
+
+    /// The error returned when the API rejects the OAuth token.
+    pub fn forbidden() -> Error {
+        ApiError::Forbidden.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::tests::block_on;
+
+    #[test]
+    fn test_queue_then_fallback() {
+        let client = MockUsageClient::new(fixtures::idle())
+            .with_response(Ok(fixtures::near_limit()))
+            .with_response(Err(fixtures::forbidden()));
+
+        let first = client.next_usage().expect("queued usage");
+        assert_eq!(first.five_hour.utilization, 92.0);
+        assert!(matches!(
+            client.next_usage(),
+            Err(Error::Api(ApiError::Forbidden))
+        ));
+        assert_eq!(client.next_usage().expect("fallback"), fixtures::idle());
+        assert_eq!(client.next_usage().expect("fallback"), fixtures::idle());
+        assert_eq!(client.calls(), 4);
+    }
+
+    #[test]
+    fn test_empty_client_errors_when_exhausted() {
+        let client = MockUsageClient::empty();
+        assert!(matches!(
+            client.next_usage(),
+            Err(Error::Api(ApiError::Network(_)))
+        ));
+    }
+
+    #[test]
+    fn test_from_json_and_trait_object() {
+        let client = MockUsageClient::from_json(
+            r#"{"five_hour": {"utilization": 12.0, "resets_at": null},
+                "seven_day": {"utilization": 3.0, "resets_at": null}}"#,
+        )
+        .expect("valid fixture");
+        let client: &dyn UsageClient = &client;
+        let usage = block_on(client.fetch_usage()).expect("usage");
+        assert_eq!(usage.five_hour.utilization, 12.0);
+        assert!(MockUsageClient::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_fixture_usage_is_on_pace_at_half() {
+        let usage = fixtures::usage(40.0, 60.0);
+        assert_eq!(usage.five_hour_on_pace(), Some(true));
+        assert_eq!(usage.seven_day_on_pace(), Some(false));
+    }
+}
@@ -20,8 +20,9 @@ use napi_derive::napi;
 pub struct JsUsagePeriod {
     /// Percentage of quota used (0.0 - 100.0+).
     pub utilization: f64,
-    /// When this period's quota resets (ISO 8601 format).
-    pub resets_at: String,
+    /// When this period's quota resets (ISO 8601 format), if the API
+    /// reported it.
+    pub resets_at: Option<String>,
 }
 
 /// Extra usage billing information.
@@ -52,7 +53,7 @@ impl From<&crate::types::UsagePeriod> for JsUsagePeriod {
     fn from(period: &crate::types::UsagePeriod) -> Self {
         Self {
             utilization: period.utilization,
-            resets_at: period.resets_at.to_rfc3339(),
+            resets_at: period.resets_at.map(|reset| reset.to_rfc3339()),
         }
     }
 }
@@ -106,7 +107,9 @@ pub fn is_on_pace(utilization: f64, resets_at: String, period_hours: u32) -> nap
 
     let period = crate::types::UsagePeriod {
         utilization,
-        resets_at: parsed.with_timezone(&chrono::Utc),
+        resets_at: Some(parsed.with_timezone(&chrono::Utc)),
+        extra: Default::default(),
     };
-    Ok(period.is_on_pace(period_hours))
+    // Only `None` without a reset time, which is always set here
+    Ok(period.is_on_pace(period_hours).unwrap_or(true))
 }
@@ -0,0 +1,567 @@
+//! Organization usage and cost reports from the Anthropic Admin API.
+//!
+//! Unlike the OAuth usage endpoint, these reports cover API consumption of a
+//! whole organization and can be broken down per workspace. They require an
+//! Admin API key (`sk-ant-admin...`), read from `ANTHROPIC_ADMIN_KEY`; OAuth
+//! tokens from Claude Code are not accepted.
+//!
+//! ```rust,ignore
+//! use claude_usage::organization::ReportQuery;
+//! use claude_usage::get_workspace_usage;
+//!
+//! let since = chrono::Utc::now() - chrono::TimeDelta::days(7);
+//! for workspace in get_workspace_usage(&ReportQuery::since(since))? {
+//!     println!(
+//!         "{}: {} output tokens, ${:.2}",
+//!         workspace.workspace_id.as_deref().unwrap_or("default"),
+//!         workspace.output_tokens,
+//!         workspace.cost_cents / 100.0
+//!     );
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::auth::Auth;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::error::ApiError;
+use crate::error::{CredentialError, Error};
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::retry::RetryPolicy;
+
+/// Messages usage report endpoint.
+pub const USAGE_REPORT_URL: &str =
+    "https://api.anthropic.com/v1/organizations/usage_report/messages";
+
+/// Cost report endpoint.
+pub const COST_REPORT_URL: &str = "https://api.anthropic.com/v1/organizations/cost_report";
+
+/// API version header value for the Admin API.
+pub use crate::auth::ANTHROPIC_VERSION;
+
+/// Environment variable holding the Admin API key.
+pub const ENV_VAR_ADMIN_KEY: &str = "ANTHROPIC_ADMIN_KEY";
+
+/// Retrieve the Admin API key from `ANTHROPIC_ADMIN_KEY`.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::NotFound`] if the variable is unset or empty.
+pub fn admin_key() -> Result<String, CredentialError> {
+    std::env::var(ENV_VAR_ADMIN_KEY)
+        .ok()
+        .filter(|key| !key.is_empty())
+        .ok_or(CredentialError::NotFound)
+}
+
+/// Time bucket size of the usage report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BucketWidth {
+    /// One-minute buckets.
+    Minute,
+    /// One-hour buckets.
+    Hour,
+    /// One-day buckets.
+    #[default]
+    Day,
+}
+
+impl BucketWidth {
+    /// Value of the `bucket_width` query parameter.
+    pub fn as_param(self) -> &'static str {
+        match self {
+            Self::Minute => "1m",
+            Self::Hour => "1h",
+            Self::Day => "1d",
+        }
+    }
+}
+
+/// Time range and bucketing of a report request.
+///
+/// Both reports are grouped by workspace. The cost report only supports
+/// daily buckets, so [`bucket_width`](Self::bucket_width) applies to the
+/// usage report alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportQuery {
+    /// Start of the range (inclusive).
+    pub starting_at: DateTime<Utc>,
+    /// End of the range (exclusive); the API default when None.
+    pub ending_at: Option<DateTime<Utc>>,
+    /// Bucket size of the usage report.
+    pub bucket_width: BucketWidth,
+}
+
+impl ReportQuery {
+    /// Query from `starting_at` until now in daily buckets.
+    pub fn since(starting_at: DateTime<Utc>) -> Self {
+        Self {
+            starting_at,
+            ending_at: None,
+            bucket_width: BucketWidth::Day,
+        }
+    }
+
+    /// Query parameters for one page of the usage report.
+    #[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+    fn usage_params(&self, page: Option<&str>) -> Vec<(&'static str, String)> {
+        let mut params = self.common_params(page);
+        params.push(("bucket_width", self.bucket_width.as_param().to_string()));
+        params
+    }
+
+    /// Query parameters for one page of the cost report.
+    #[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+    fn cost_params(&self, page: Option<&str>) -> Vec<(&'static str, String)> {
+        self.common_params(page)
+    }
+
+    /// Range, grouping and page parameters shared by both reports.
+    fn common_params(&self, page: Option<&str>) -> Vec<(&'static str, String)> {
+        let time = |at: DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut params = vec![
+            ("starting_at", time(self.starting_at)),
+            ("group_by[]", "workspace_id".to_string()),
+        ];
+        if let Some(ending_at) = self.ending_at {
+            params.push(("ending_at", time(ending_at)));
+        }
+        if let Some(page) = page {
+            params.push(("page", page.to_string()));
+        }
+        params
+    }
+}
+
+/// One page of a report.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+struct ReportPage<T> {
+    data: Vec<ReportBucket<T>>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    next_page: Option<String>,
+}
+
+/// Results of a report for one time bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportBucket<T> {
+    /// Start of the bucket.
+    pub starting_at: DateTime<Utc>,
+    /// End of the bucket.
+    pub ending_at: DateTime<Utc>,
+    /// One entry per workspace with activity in the bucket.
+    pub results: Vec<T>,
+}
+
+/// Token counts of one workspace in one usage report bucket.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageUsage {
+    /// Workspace ID, or None for the default workspace.
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// Input tokens not read from or written to the cache.
+    #[serde(default)]
+    pub uncached_input_tokens: u64,
+    /// Input tokens read from the cache.
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    /// Input tokens written to the cache.
+    #[serde(default)]
+    pub cache_creation: CacheCreation,
+    /// Output tokens.
+    #[serde(default)]
+    pub output_tokens: u64,
+}
+
+/// Cache write tokens by cache lifetime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CacheCreation {
+    /// Tokens written to the 1-hour cache.
+    #[serde(default)]
+    pub ephemeral_1h_input_tokens: u64,
+    /// Tokens written to the 5-minute cache.
+    #[serde(default)]
+    pub ephemeral_5m_input_tokens: u64,
+}
+
+impl CacheCreation {
+    /// Total tokens written to either cache.
+    pub fn total(&self) -> u64 {
+        self.ephemeral_1h_input_tokens + self.ephemeral_5m_input_tokens
+    }
+}
+
+/// Cost of one workspace in one cost report bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CostItem {
+    /// Workspace ID, or None for the default workspace.
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    /// Currency code, e.g. `USD`.
+    pub currency: String,
+    /// Cost in the lowest currency unit (cents), as a decimal string.
+    pub amount: String,
+}
+
+impl CostItem {
+    /// Parses [`amount`](Self::amount), or None if it is not a number.
+    pub fn amount_cents(&self) -> Option<f64> {
+        self.amount.parse().ok()
+    }
+}
+
+/// Aggregate usage and cost of one workspace over a report range.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceUsage {
+    /// Workspace ID, or None for the default workspace.
+    pub workspace_id: Option<String>,
+    /// Uncached input tokens.
+    pub input_tokens: u64,
+    /// Input tokens written to the cache.
+    pub cache_creation_input_tokens: u64,
+    /// Input tokens read from the cache.
+    pub cache_read_input_tokens: u64,
+    /// Output tokens.
+    pub output_tokens: u64,
+    /// Cost in cents; 0 if no cost report was included.
+    pub cost_cents: f64,
+}
+
+/// Sums usage and cost buckets into one entry per workspace.
+///
+/// Entries are sorted by workspace ID, with the default workspace first.
+/// Cost amounts that fail to parse count as zero.
+pub fn workspace_breakdown(
+    usage: &[ReportBucket<MessageUsage>],
+    costs: &[ReportBucket<CostItem>],
+) -> Vec<WorkspaceUsage> {
+    let mut workspaces: BTreeMap<Option<String>, WorkspaceUsage> = BTreeMap::new();
+    for result in usage.iter().flat_map(|bucket| &bucket.results) {
+        let ws = workspace_entry(&mut workspaces, &result.workspace_id);
+        ws.input_tokens += result.uncached_input_tokens;
+        ws.cache_creation_input_tokens += result.cache_creation.total();
+        ws.cache_read_input_tokens += result.cache_read_input_tokens;
+        ws.output_tokens += result.output_tokens;
+    }
+    for item in costs.iter().flat_map(|bucket| &bucket.results) {
+        let ws = workspace_entry(&mut workspaces, &item.workspace_id);
+        ws.cost_cents += item.amount_cents().unwrap_or(0.0);
+    }
+    workspaces.into_values().collect()
+}
+
+/// Returns the aggregate for `id`, inserting an empty one if needed.
+fn workspace_entry<'a>(
+    workspaces: &'a mut BTreeMap<Option<String>, WorkspaceUsage>,
+    id: &Option<String>,
+) -> &'a mut WorkspaceUsage {
+    workspaces
+        .entry(id.clone())
+        .or_insert_with(|| WorkspaceUsage {
+            workspace_id: id.clone(),
+            ..WorkspaceUsage::default()
+        })
+}
+
+/// Parses one report page.
+#[cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+fn parse_page<T: DeserializeOwned>(body: &str) -> Result<ReportPage<T>, Error> {
+    serde_json::from_str(body).map_err(|e| Error::Parse(e.to_string()))
+}
+
+/// Fetch the messages usage report, following all pages (blocking).
+///
+/// # Errors
+///
+/// Returns [`ApiError`] if a request fails after retries (401 for a missing
+/// or non-admin key), or [`Error::Parse`] for an unexpected response.
+#[cfg(feature = "blocking")]
+pub fn fetch_usage_report(
+    admin_key: &str,
+    query: &ReportQuery,
+) -> Result<Vec<ReportBucket<MessageUsage>>, Error> {
+    fetch_all_pages(USAGE_REPORT_URL, admin_key, |page| query.usage_params(page))
+}
+
+/// Fetch the cost report, following all pages (blocking).
+///
+/// # Errors
+///
+/// Returns the same errors as [`fetch_usage_report`].
+#[cfg(feature = "blocking")]
+pub fn fetch_cost_report(
+    admin_key: &str,
+    query: &ReportQuery,
+) -> Result<Vec<ReportBucket<CostItem>>, Error> {
+    fetch_all_pages(COST_REPORT_URL, admin_key, |page| query.cost_params(page))
+}
+
+/// GET every page of a report, retrying each request per the default policy.
+#[cfg(feature = "blocking")]
+fn fetch_all_pages<T: DeserializeOwned>(
+    url: &str,
+    admin_key: &str,
+    params: impl Fn(Option<&str>) -> Vec<(&'static str, String)>,
+) -> Result<Vec<ReportBucket<T>>, Error> {
+    let client = crate::http::blocking_client(std::time::Duration::from_secs(30))?;
+    let auth = Auth::ApiKey(admin_key.to_string());
+    let policy = RetryPolicy::default();
+
+    let mut buckets = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let mut attempt = 1;
+        let body = loop {
+            let mut request = client.get(url).query(&params(page.as_deref()));
+            for (name, value) in auth.headers() {
+                request = request.header(name, value);
+            }
+            let result = request
+                .send()
+                // Generic message so the key never appears in error details
+                .map_err(|_| ApiError::Network("Failed to connect to Anthropic API".to_string()))
+                .and_then(crate::client::map_response);
+            match result {
+                Ok(body) => break body,
+                Err(e) => match policy.delay_for(attempt, &e) {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(e.into()),
+                },
+            }
+            attempt += 1;
+        };
+        let report = parse_page::<T>(&body)?;
+        buckets.extend(report.data);
+        match report.next_page.filter(|_| report.has_more) {
+            Some(next) => page = Some(next),
+            None => return Ok(buckets),
+        }
+    }
+}
+
+/// Fetch the messages usage report, following all pages (async).
+///
+/// # Errors
+///
+/// Returns the same errors as `fetch_usage_report`.
+#[cfg(feature = "async")]
+pub async fn fetch_usage_report_async(
+    admin_key: &str,
+    query: &ReportQuery,
+) -> Result<Vec<ReportBucket<MessageUsage>>, Error> {
+    fetch_all_pages_async(USAGE_REPORT_URL, admin_key, |page| query.usage_params(page)).await
+}
+
+/// Fetch the cost report, following all pages (async).
+///
+/// # Errors
+///
+/// Returns the same errors as `fetch_usage_report`.
+#[cfg(feature = "async")]
+pub async fn fetch_cost_report_async(
+    admin_key: &str,
+    query: &ReportQuery,
+) -> Result<Vec<ReportBucket<CostItem>>, Error> {
+    fetch_all_pages_async(COST_REPORT_URL, admin_key, |page| query.cost_params(page)).await
+}
+
+/// Async counterpart of `fetch_all_pages`.
+#[cfg(feature = "async")]
+async fn fetch_all_pages_async<T: DeserializeOwned>(
+    url: &str,
+    admin_key: &str,
+    params: impl Fn(Option<&str>) -> Vec<(&'static str, String)>,
+) -> Result<Vec<ReportBucket<T>>, Error> {
+    let client = crate::http::async_client(std::time::Duration::from_secs(30))?;
+    let auth = Auth::ApiKey(admin_key.to_string());
+    let policy = RetryPolicy::default();
+
+    let mut buckets = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let mut attempt = 1;
+        let body = loop {
+            let mut request = client.get(url).query(&params(page.as_deref()));
+            for (name, value) in auth.headers() {
+                request = request.header(name, value);
+            }
+            match fetch_once_async(request).await {
+                Ok(body) => break body,
+                Err(e) => match policy.delay_for(attempt, &e) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(e.into()),
+                },
+            }
+            attempt += 1;
+        };
+        let report = parse_page::<T>(&body)?;
+        buckets.extend(report.data);
+        match report.next_page.filter(|_| report.has_more) {
+            Some(next) => page = Some(next),
+            None => return Ok(buckets),
+        }
+    }
+}
+
+/// Send one async report request and read the body.
+#[cfg(feature = "async")]
+async fn fetch_once_async(request: reqwest::RequestBuilder) -> Result<String, ApiError> {
+    let response = request
+        .send()
+        .await
+        // Generic message so the key never appears in error details
+        .map_err(|_| ApiError::Network("Failed to connect to Anthropic API".to_string()))?;
+    if let Some(err) = crate::client::status_error(response.status().as_u16(), response.headers()) {
+        return Err(err);
+    }
+    response
+        .text()
+        .await
+        .map_err(|_| ApiError::Network("Failed to read response body".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    const USAGE_PAGE: &str = r#"{
+        "data": [{
+            "starting_at": "2026-10-15T00:00:00Z",
+            "ending_at": "2026-10-16T00:00:00Z",
+            "results": [
+                {
+                    "uncached_input_tokens": 100,
+                    "cache_creation": {
+                        "ephemeral_1h_input_tokens": 5,
+                        "ephemeral_5m_input_tokens": 10
+                    },
+                    "cache_read_input_tokens": 200,
+                    "output_tokens": 50,
+                    "server_tool_use": {"web_search_requests": 0},
+                    "api_key_id": null,
+                    "workspace_id": "wrkspc_a",
+                    "model": null,
+                    "service_tier": null,
+                    "context_window": null
+                },
+                {
+                    "uncached_input_tokens": 1,
+                    "cache_read_input_tokens": 0,
+                    "output_tokens": 2,
+                    "workspace_id": null
+                }
+            ]
+        }],
+        "has_more": true,
+        "next_page": "page_2"
+    }"#;
+
+    #[test]
+    fn test_parse_usage_page() {
+        let page = parse_page::<MessageUsage>(USAGE_PAGE).expect("valid page");
+        assert!(page.has_more);
+        assert_eq!(page.next_page.as_deref(), Some("page_2"));
+        let results = &page.data[0].results;
+        assert_eq!(results[0].workspace_id.as_deref(), Some("wrkspc_a"));
+        assert_eq!(results[0].cache_creation.total(), 15);
+        assert_eq!(results[1].workspace_id, None);
+        assert_eq!(results[1].cache_creation, CacheCreation::default());
+    }
+
+    #[test]
+    fn test_parse_cost_page() {
+        let body = r#"{
+            "data": [{
+                "starting_at": "2026-10-15T00:00:00Z",
+                "ending_at": "2026-10-16T00:00:00Z",
+                "results": [{"currency": "USD", "amount": "123.45", "workspace_id": "wrkspc_a"}]
+            }],
+            "has_more": false,
+            "next_page": null
+        }"#;
+        let page = parse_page::<CostItem>(body).expect("valid page");
+        assert!(!page.has_more);
+        assert_eq!(page.data[0].results[0].amount_cents(), Some(123.45));
+    }
+
+    #[test]
+    fn test_parse_error_is_reported() {
+        assert!(matches!(
+            parse_page::<CostItem>("{\"type\": \"error\"}"),
+            Err(Error::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_workspace_breakdown_sums_per_workspace() {
+        let usage = parse_page::<MessageUsage>(USAGE_PAGE)
+            .expect("valid page")
+            .data;
+        let day = |results| ReportBucket {
+            starting_at: at("2026-10-15T00:00:00Z"),
+            ending_at: at("2026-10-16T00:00:00Z"),
+            results,
+        };
+        let cost = |workspace: Option<&str>, amount: &str| CostItem {
+            workspace_id: workspace.map(String::from),
+            currency: "USD".to_string(),
+            amount: amount.to_string(),
+        };
+        let costs = vec![
+            day(vec![cost(Some("wrkspc_a"), "100.5"), cost(None, "1")]),
+            day(vec![
+                cost(Some("wrkspc_a"), "50"),
+                cost(Some("wrkspc_b"), "n/a"),
+            ]),
+        ];
+
+        let breakdown = workspace_breakdown(&usage, &costs);
+        assert_eq!(breakdown.len(), 3, "default, wrkspc_a, wrkspc_b");
+        assert_eq!(breakdown[0].workspace_id, None);
+        assert_eq!(breakdown[0].output_tokens, 2);
+        assert_eq!(breakdown[0].cost_cents, 1.0);
+        let a = &breakdown[1];
+        assert_eq!(a.input_tokens, 100);
+        assert_eq!(a.cache_creation_input_tokens, 15);
+        assert_eq!(a.cache_read_input_tokens, 200);
+        assert_eq!(a.cost_cents, 150.5);
+        assert_eq!(
+            breakdown[2].cost_cents, 0.0,
+            "unparseable amount counts as zero"
+        );
+    }
+
+    #[test]
+    fn test_query_params() {
+        let query = ReportQuery {
+            starting_at: at("2026-10-01T00:00:00Z"),
+            ending_at: Some(at("2026-10-08T00:00:00Z")),
+            bucket_width: BucketWidth::Hour,
+        };
+        assert_eq!(
+            query.usage_params(Some("page_2")),
+            vec![
+                ("starting_at", "2026-10-01T00:00:00Z".to_string()),
+                ("group_by[]", "workspace_id".to_string()),
+                ("ending_at", "2026-10-08T00:00:00Z".to_string()),
+                ("page", "page_2".to_string()),
+                ("bucket_width", "1h".to_string()),
+            ]
+        );
+        let since = ReportQuery::since(at("2026-10-01T00:00:00Z"));
+        assert_eq!(since.cost_params(None).len(), 2, "no bucket width or page");
+    }
+}
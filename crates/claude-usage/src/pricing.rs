@@ -0,0 +1,214 @@
+//! Dollar cost estimates from token counts.
+//!
+//! [`PricingTable::default()`] holds published per-model API rates in USD per
+//! million tokens. Models are matched by the longest name prefix, so dated
+//! IDs like `claude-sonnet-4-5-20250929` resolve to their family's rates.
+//! Rates change; callers can override or add models with
+//! [`PricingTable::set`].
+//!
+//! Estimates are list-price API costs. For subscription (OAuth) accounts
+//! they indicate value consumed, not an amount billed.
+//!
+//! ```rust,ignore
+//! use claude_usage::pricing::{PricingTable, TokenUsage};
+//!
+//! let table = PricingTable::default();
+//! let cost = table.estimate("claude-sonnet-4-5", &TokenUsage::new(120_000, 8_000));
+//! println!("~${:.2}", cost.unwrap_or(0.0));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Token counts to price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Uncached input tokens.
+    pub input_tokens: u64,
+    /// Output tokens.
+    pub output_tokens: u64,
+    /// Input tokens written to the prompt cache.
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+    /// Input tokens read from the prompt cache.
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Token counts without prompt caching.
+    pub fn new(input_tokens: u64, output_tokens: u64) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            ..Self::default()
+        }
+    }
+}
+
+/// Rates for one model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Uncached input rate.
+    pub input: f64,
+    /// Output rate.
+    pub output: f64,
+    /// Prompt cache write rate.
+    pub cache_write: f64,
+    /// Prompt cache read rate.
+    pub cache_read: f64,
+}
+
+impl ModelPricing {
+    /// Rates with the standard cache multipliers: writes cost 1.25x and
+    /// reads 0.1x the input rate.
+    pub fn new(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write: input * 1.25,
+            cache_read: input * 0.1,
+        }
+    }
+
+    /// Cost of `usage` in USD.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let per_token = |count: u64, rate: f64| count as f64 * rate / 1_000_000.0;
+        per_token(usage.input_tokens, self.input)
+            + per_token(usage.output_tokens, self.output)
+            + per_token(usage.cache_creation_input_tokens, self.cache_write)
+            + per_token(usage.cache_read_input_tokens, self.cache_read)
+    }
+}
+
+/// Built-in rates as `(model prefix, input, output)`.
+const DEFAULT_RATES: &[(&str, f64, f64)] = &[
+    ("claude-opus-4-5", 5.0, 25.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-haiku-4-5", 1.0, 5.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-3-5-sonnet", 3.0, 15.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("claude-3-opus", 15.0, 75.0),
+    ("claude-3-haiku", 0.25, 1.25),
+];
+
+/// Per-model rates looked up by model name prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTable {
+    /// `(prefix, rates)` pairs with unique prefixes.
+    models: Vec<(String, ModelPricing)>,
+}
+
+impl Default for PricingTable {
+    /// Built-in rates for current and recent Claude models.
+    fn default() -> Self {
+        Self {
+            models: DEFAULT_RATES
+                .iter()
+                .map(|&(prefix, input, output)| {
+                    (prefix.to_string(), ModelPricing::new(input, output))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl PricingTable {
+    /// Creates a table without any models.
+    pub fn empty() -> Self {
+        Self { models: Vec::new() }
+    }
+
+    /// Sets the rates for models starting with `prefix`, replacing any
+    /// existing entry for the same prefix.
+    pub fn set(&mut self, prefix: impl Into<String>, pricing: ModelPricing) {
+        let prefix = prefix.into();
+        self.models.retain(|(p, _)| *p != prefix);
+        self.models.push((prefix, pricing));
+    }
+
+    /// Builder form of [`set`](Self::set).
+    pub fn with_model(mut self, prefix: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.set(prefix, pricing);
+        self
+    }
+
+    /// Returns the rates of the longest prefix matching `model`.
+    pub fn get(&self, model: &str) -> Option<ModelPricing> {
+        self.models
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, pricing)| *pricing)
+    }
+
+    /// Estimated cost of `usage` on `model` in USD, or None for an unknown
+    /// model.
+    pub fn estimate(&self, model: &str, usage: &TokenUsage) -> Option<f64> {
+        self.get(model).map(|pricing| pricing.cost(usage))
+    }
+}
+
+/// Estimated cost of `usage` on `model` with the built-in rates.
+///
+/// Returns None for models not in [`PricingTable::default()`].
+pub fn estimate_cost(model: &str, usage: &TokenUsage) -> Option<f64> {
+    PricingTable::default().estimate(model, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_cost_includes_cache_rates() {
+        let pricing = ModelPricing::new(3.0, 15.0);
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_creation_input_tokens: 1_000_000,
+            cache_read_input_tokens: 1_000_000,
+        };
+        // 3 + 1.5 + 3.75 + 0.3
+        assert!(
+            approx(pricing.cost(&usage), 8.55),
+            "{}",
+            pricing.cost(&usage)
+        );
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let table = PricingTable::default();
+        let opus_45 = table.get("claude-opus-4-5-20251101").expect("known");
+        let opus_41 = table.get("claude-opus-4-1-20250805").expect("known");
+        assert_eq!(opus_45.input, 5.0);
+        assert_eq!(opus_41.input, 15.0);
+        assert_eq!(table.get("gpt-4"), None);
+    }
+
+    #[test]
+    fn test_override_and_add_models() {
+        let table = PricingTable::default()
+            .with_model("claude-sonnet-4", ModelPricing::new(2.0, 10.0))
+            .with_model("custom-model", ModelPricing::new(1.0, 1.0));
+        let usage = TokenUsage::new(1_000_000, 0);
+        assert_eq!(table.estimate("claude-sonnet-4-5", &usage), Some(2.0));
+        assert_eq!(table.estimate("custom-model-v2", &usage), Some(1.0));
+        assert_eq!(
+            PricingTable::empty().estimate("claude-sonnet-4", &usage),
+            None
+        );
+    }
+
+    #[test]
+    fn test_estimate_cost_uses_default_table() {
+        let cost = estimate_cost("claude-haiku-4-5", &TokenUsage::new(500_000, 200_000));
+        assert!(approx(cost.expect("known model"), 1.5));
+    }
+}
@@ -0,0 +1,425 @@
+//! Named credential profiles for multiple Claude accounts.
+//!
+//! The `default` profile uses the platform credential store, like
+//! [`get_usage()`](crate::get_usage). Other profiles are defined in a JSON
+//! file mapping profile names to a credential file, typically one written by
+//! Claude Code run with a separate `CLAUDE_CONFIG_DIR`:
+//!
+//! ```json
+//! {
+//!   "work": { "credentials_file": "~/.claude-work/.credentials.json" }
+//! }
+//! ```
+//!
+//! Each profile can be overridden with its own environment variable:
+//! `CLAUDE_CODE_OAUTH_TOKEN` for `default`, `CLAUDE_CODE_OAUTH_TOKEN_WORK`
+//! for `work` (see [`Profile::env_var`]).
+//!
+//! ```rust,ignore
+//! use claude_usage::get_usage_for_profile;
+//!
+//! let work = get_usage_for_profile("work")?;
+//! println!("{}: {}%", work.profile, work.data.five_hour.utilization);
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::credentials::{self, StoredCredentials, ENV_VAR_TOKEN};
+use crate::error::CredentialError;
+use crate::types::UsageData;
+
+/// Name of the profile backed by the platform credential store.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profiles file name inside the config directory.
+const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// A named set of credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// Profile name, e.g. `work`.
+    pub name: String,
+    /// Credential JSON file for this profile. When unset, the `default`
+    /// profile reads the platform store and other profiles only their
+    /// environment variable.
+    pub credentials_file: Option<PathBuf>,
+}
+
+/// One entry of the profiles file.
+#[derive(Debug, Deserialize)]
+struct ProfileEntry {
+    credentials_file: Option<String>,
+}
+
+impl Profile {
+    /// Creates a profile without a credential file.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            credentials_file: None,
+        }
+    }
+
+    /// Sets the credential file for this profile.
+    pub fn with_credentials_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.credentials_file = Some(path.into());
+        self
+    }
+
+    /// Returns true for the profile named [`DEFAULT_PROFILE`].
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_PROFILE
+    }
+
+    /// Environment variable overriding this profile's credentials.
+    ///
+    /// `CLAUDE_CODE_OAUTH_TOKEN` for the default profile, otherwise the
+    /// same name suffixed with the upper-cased profile name, with characters
+    /// other than ASCII letters and digits replaced by `_`.
+    pub fn env_var(&self) -> String {
+        if self.is_default() {
+            return ENV_VAR_TOKEN.to_string();
+        }
+        let suffix: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}_{}", ENV_VAR_TOKEN, suffix)
+    }
+
+    /// Load this profile's credentials without checking expiry.
+    ///
+    /// The profile's environment variable takes precedence, then its
+    /// credential file, then (for the default profile only) the platform
+    /// store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CredentialError::NotFound`] if a non-default profile has
+    /// neither an environment variable nor a credential file, or the error
+    /// from reading its credentials.
+    pub fn load_credentials(&self) -> Result<StoredCredentials, CredentialError> {
+        if let Some(stored) = credentials::env_credentials(&self.env_var()) {
+            return Ok(stored);
+        }
+        match &self.credentials_file {
+            Some(path) => credentials::load_credentials_from_file(path),
+            None if self.is_default() => credentials::load_credentials(),
+            None => Err(CredentialError::NotFound),
+        }
+    }
+}
+
+/// Usage data together with the profile it was fetched for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProfileUsage {
+    /// Name of the profile whose credentials were used.
+    pub profile: String,
+    /// The usage data.
+    pub data: UsageData,
+}
+
+/// Read profile definitions from the JSON file at `path`.
+///
+/// A missing file yields no profiles. A leading `~/` in credential paths is
+/// expanded to the home directory.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::Parse`] if the file is not valid profile JSON,
+/// or [`CredentialError::Io`] if it exists but cannot be read.
+pub fn load_profiles(path: &Path) -> Result<Vec<Profile>, CredentialError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(CredentialError::Io(e.to_string())),
+    };
+    let entries: BTreeMap<String, ProfileEntry> =
+        serde_json::from_str(&content).map_err(|e| CredentialError::Parse(e.to_string()))?;
+    Ok(entries
+        .into_iter()
+        .map(|(name, entry)| Profile {
+            name,
+            credentials_file: entry.credentials_file.as_deref().map(expand_home),
+        })
+        .collect())
+}
+
+/// Look up a profile by name in the default profiles file.
+///
+/// The default profile exists even without a profiles file. Any other profile
+/// whose environment variable is set also resolves, so a token alone is
+/// enough to define one.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::UnknownProfile`] if no profile has this name,
+/// or the error from reading the profiles file.
+pub fn find_profile(name: &str) -> Result<Profile, CredentialError> {
+    let profiles = match default_profiles_path() {
+        Some(path) => load_profiles(&path)?,
+        None => Vec::new(),
+    };
+    find_in(profiles, name)
+}
+
+/// List every configured profile: `default`, the profiles file entries, and
+/// profiles defined only by a `CLAUDE_CODE_OAUTH_TOKEN_<NAME>` variable
+/// (named by the lower-cased suffix).
+///
+/// # Errors
+///
+/// Returns the error from reading the profiles file.
+pub fn list_profiles() -> Result<Vec<Profile>, CredentialError> {
+    let profiles = match default_profiles_path() {
+        Some(path) => load_profiles(&path)?,
+        None => Vec::new(),
+    };
+    // Skip variables that are not Unicode; `std::env::vars` panics on them
+    let vars = std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)));
+    Ok(with_default_and_env(profiles, vars))
+}
+
+/// Adds the default profile and env-only profiles from `vars` to `profiles`.
+fn with_default_and_env(
+    profiles: Vec<Profile>,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Vec<Profile> {
+    let mut all: BTreeMap<String, Profile> = BTreeMap::new();
+    all.insert(DEFAULT_PROFILE.to_string(), Profile::new(DEFAULT_PROFILE));
+    let prefix = format!("{}_", ENV_VAR_TOKEN);
+    for (var, value) in vars {
+        match var.strip_prefix(&prefix) {
+            Some(suffix) if !suffix.is_empty() && !value.is_empty() => {
+                let name = suffix.to_ascii_lowercase();
+                all.insert(name.clone(), Profile::new(name));
+            }
+            _ => {}
+        }
+    }
+    for profile in profiles {
+        all.insert(profile.name.clone(), profile);
+    }
+    all.into_values().collect()
+}
+
+/// Resolve `name` among `profiles`, falling back to a bare profile for the
+/// default name or a set environment variable.
+fn find_in(profiles: Vec<Profile>, name: &str) -> Result<Profile, CredentialError> {
+    if let Some(profile) = profiles.into_iter().find(|p| p.name == name) {
+        return Ok(profile);
+    }
+    let bare = Profile::new(name);
+    let has_env = std::env::var(bare.env_var()).is_ok_and(|v| !v.is_empty());
+    if bare.is_default() || has_env {
+        Ok(bare)
+    } else {
+        Err(CredentialError::UnknownProfile(name.to_string()))
+    }
+}
+
+/// Returns the default profiles file path.
+///
+/// Uses `$XDG_CONFIG_HOME/claude-usage/profiles.json`, falling back to
+/// `~/.config/claude-usage/profiles.json`, or `%APPDATA%` on Windows.
+/// Returns None if none of these variables are set.
+pub fn default_profiles_path() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let dir = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| non_empty("APPDATA").map(PathBuf::from))?;
+    Some(dir.join("claude-usage").join(PROFILES_FILE_NAME))
+}
+
+/// Expands a leading `~/` using HOME (or USERPROFILE).
+fn expand_home(path: &str) -> PathBuf {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok();
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::CredentialSource;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    const CREDENTIALS: &str =
+        r#"{"claudeAiOauth": {"accessToken": "sk-ant-oat01-work", "expiresAt": 1000}}"#;
+
+    #[test]
+    fn test_env_var_names() {
+        assert_eq!(Profile::new("default").env_var(), "CLAUDE_CODE_OAUTH_TOKEN");
+        assert_eq!(
+            Profile::new("work").env_var(),
+            "CLAUDE_CODE_OAUTH_TOKEN_WORK"
+        );
+        assert_eq!(
+            Profile::new("side-project").env_var(),
+            "CLAUDE_CODE_OAUTH_TOKEN_SIDE_PROJECT"
+        );
+    }
+
+    #[test]
+    fn test_load_profiles_from_file() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join(PROFILES_FILE_NAME);
+        std::fs::write(
+            &path,
+            r#"{"work": {"credentials_file": "/srv/work.json"}, "bare": {}}"#,
+        )
+        .expect("write");
+        let profiles = load_profiles(&path).expect("valid profiles");
+        assert_eq!(
+            profiles,
+            vec![
+                Profile::new("bare"),
+                Profile::new("work").with_credentials_file("/srv/work.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_profiles_file_is_empty() {
+        let dir = TempDir::new().expect("tempdir");
+        let profiles = load_profiles(&dir.path().join("none.json")).expect("missing is ok");
+        assert!(profiles.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_profiles_file_is_parse_error() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join(PROFILES_FILE_NAME);
+        std::fs::write(&path, "[1, 2]").expect("write");
+        assert!(matches!(
+            load_profiles(&path),
+            Err(CredentialError::Parse(_))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_expand_home() {
+        let original = std::env::var("HOME").ok();
+        std::env::set_var("HOME", "/home/tester");
+        assert_eq!(
+            expand_home("~/.claude-work/.credentials.json"),
+            PathBuf::from("/home/tester/.claude-work/.credentials.json")
+        );
+        assert_eq!(expand_home("/abs/path"), PathBuf::from("/abs/path"));
+        match original {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_find_profile_resolution() {
+        let work = Profile::new("work").with_credentials_file("/srv/work.json");
+        assert_eq!(find_in(vec![work.clone()], "work").expect("defined"), work);
+        assert_eq!(
+            find_in(Vec::new(), "default").expect("always exists"),
+            Profile::new("default")
+        );
+        assert!(matches!(
+            find_in(Vec::new(), "nope"),
+            Err(CredentialError::UnknownProfile(name)) if name == "nope"
+        ));
+
+        std::env::set_var("CLAUDE_CODE_OAUTH_TOKEN_ENVONLY", "sk-ant-oat01-env");
+        let env_only = find_in(Vec::new(), "envonly");
+        std::env::remove_var("CLAUDE_CODE_OAUTH_TOKEN_ENVONLY");
+        assert_eq!(env_only.expect("env defines it"), Profile::new("envonly"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_profile_loads_file_then_env_override() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("work.json");
+        std::fs::write(&path, CREDENTIALS).expect("write");
+        let profile = Profile::new("filetest").with_credentials_file(&path);
+
+        let stored = profile.load_credentials().expect("file credentials");
+        assert_eq!(stored.oauth.access_token, "sk-ant-oat01-work");
+        assert_eq!(stored.source, CredentialSource::File(path.clone()));
+
+        std::env::set_var(profile.env_var(), "sk-ant-oat01-override");
+        let overridden = profile.load_credentials();
+        std::env::remove_var(profile.env_var());
+        let overridden = overridden.expect("env credentials");
+        assert_eq!(overridden.oauth.access_token, "sk-ant-oat01-override");
+        assert_eq!(overridden.source, CredentialSource::Env);
+    }
+
+    #[test]
+    fn test_list_includes_default_and_env_profiles() {
+        let work = Profile::new("work").with_credentials_file("/srv/work.json");
+        let vars = [
+            ("CLAUDE_CODE_OAUTH_TOKEN_SIDE".to_string(), "sk".to_string()),
+            ("CLAUDE_CODE_OAUTH_TOKEN_WORK".to_string(), "sk".to_string()),
+            ("CLAUDE_CODE_OAUTH_TOKEN_EMPTY".to_string(), String::new()),
+            ("CLAUDE_CODE_OAUTH_TOKEN".to_string(), "sk".to_string()),
+        ];
+        assert_eq!(
+            with_default_and_env(vec![work.clone()], vars),
+            vec![Profile::new("default"), Profile::new("side"), work]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    #[serial]
+    fn test_list_skips_non_unicode_env() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = TempDir::new().expect("tempdir");
+        let original = std::env::var_os("XDG_CONFIG_HOME");
+        std::env::set_var("XDG_CONFIG_HOME", dir.path());
+        std::env::set_var("CLAUDE_CODE_OAUTH_TOKEN_LISTED", "sk");
+        std::env::set_var(
+            "CLAUDE_CODE_OAUTH_TOKEN_BINARY",
+            std::ffi::OsStr::from_bytes(b"\xff"),
+        );
+
+        let profiles = list_profiles();
+        std::env::remove_var("CLAUDE_CODE_OAUTH_TOKEN_LISTED");
+        std::env::remove_var("CLAUDE_CODE_OAUTH_TOKEN_BINARY");
+        match original {
+            Some(value) => std::env::set_var("XDG_CONFIG_HOME", value),
+            None => std::env::remove_var("XDG_CONFIG_HOME"),
+        }
+
+        let names: Vec<String> = profiles
+            .expect("no profiles file")
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert!(names.contains(&"listed".to_string()), "{names:?}");
+        assert!(!names.contains(&"binary".to_string()), "{names:?}");
+    }
+
+    #[test]
+    fn test_non_default_profile_without_source_is_not_found() {
+        let result = Profile::new("nosource").load_credentials();
+        assert!(matches!(result, Err(CredentialError::NotFound)));
+    }
+}
@@ -0,0 +1,255 @@
+//! Python bindings via pyo3.
+//!
+//! This module exposes [`get_usage`](crate::get_usage) and the typed usage
+//! structs to Python. Enable the `pyo3` feature and build the extension
+//! module with [maturin](https://www.maturin.rs/) (see `pyproject.toml`).
+//!
+//! ## Usage from Python
+//!
+//! ```python
+//! import claude_usage
+//!
+//! usage = claude_usage.get_usage()
+//! print(f"5h utilization: {usage.five_hour.utilization}%")
+//! print(f"resets at: {usage.five_hour.resets_at}")  # datetime (UTC) or None
+//! if not usage.five_hour_on_pace():
+//!     print("ahead of pace")
+//! ```
+//!
+//! Errors are raised as `claude_usage.CredentialError` or
+//! `claude_usage.ApiError`, both subclasses of `claude_usage.ClaudeUsageError`.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use crate::types::{ExtraUsage, UsageData, UsagePeriod};
+
+create_exception!(
+    claude_usage,
+    ClaudeUsageError,
+    PyException,
+    "Base class of claude_usage errors."
+);
+create_exception!(
+    claude_usage,
+    CredentialError,
+    ClaudeUsageError,
+    "Credentials are missing, expired, or unreadable. Run `claude` to log in."
+);
+create_exception!(
+    claude_usage,
+    ApiError,
+    ClaudeUsageError,
+    "The usage API request failed."
+);
+
+/// Maps a crate error to the matching Python exception.
+fn to_py_err(error: crate::Error) -> PyErr {
+    match error {
+        crate::Error::Credential(e) => CredentialError::new_err(e.to_string()),
+        crate::Error::Api(e) => ApiError::new_err(e.to_string()),
+        crate::Error::Parse(_) => ClaudeUsageError::new_err(error.to_string()),
+    }
+}
+
+/// Usage data for a specific time period.
+#[pyclass(name = "UsagePeriod", module = "claude_usage", frozen)]
+#[derive(Clone)]
+pub struct PyUsagePeriod(UsagePeriod);
+
+#[pymethods]
+impl PyUsagePeriod {
+    /// Percentage of quota used (0.0 - 100.0+).
+    #[getter]
+    fn utilization(&self) -> f64 {
+        self.0.utilization
+    }
+
+    /// When this period's quota resets (UTC datetime), or None.
+    #[getter]
+    fn resets_at(&self) -> Option<DateTime<Utc>> {
+        self.0.resets_at
+    }
+
+    /// Time remaining until reset as a timedelta, or None.
+    fn time_until_reset(&self) -> Option<TimeDelta> {
+        self.0.time_until_reset()
+    }
+
+    /// Percentage of the period elapsed, or None.
+    fn time_elapsed_percent(&self, period_hours: u32) -> Option<f64> {
+        self.0.time_elapsed_percent(period_hours)
+    }
+
+    /// True if utilization is at or below the elapsed percentage, or None.
+    fn is_on_pace(&self, period_hours: u32) -> Option<bool> {
+        self.0.is_on_pace(period_hours)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "UsagePeriod(utilization={}, resets_at={})",
+            self.0.utilization,
+            self.0
+                .resets_at
+                .map_or("None".to_string(), |at| format!("'{}'", at.to_rfc3339()))
+        )
+    }
+}
+
+/// Extra usage billing information.
+#[pyclass(name = "ExtraUsage", module = "claude_usage", frozen)]
+#[derive(Clone)]
+pub struct PyExtraUsage(ExtraUsage);
+
+#[pymethods]
+impl PyExtraUsage {
+    /// Whether extra usage billing is enabled.
+    #[getter]
+    fn is_enabled(&self) -> bool {
+        self.0.is_enabled
+    }
+
+    /// Amount of extra usage consumed in dollars, or None.
+    #[getter]
+    fn amount_used(&self) -> Option<f64> {
+        self.0.amount_used
+    }
+
+    /// Extra usage spending limit in dollars, or None.
+    #[getter]
+    fn limit(&self) -> Option<f64> {
+        self.0.limit
+    }
+
+    /// Dollars left before the limit, or None.
+    fn remaining(&self) -> Option<f64> {
+        self.0.remaining()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ExtraUsage(is_enabled={}, amount_used={:?}, limit={:?})",
+            if self.0.is_enabled { "True" } else { "False" },
+            self.0.amount_used,
+            self.0.limit
+        )
+    }
+}
+
+/// Usage data returned by `get_usage()`.
+#[pyclass(name = "UsageData", module = "claude_usage", frozen)]
+pub struct PyUsageData(UsageData);
+
+#[pymethods]
+impl PyUsageData {
+    /// 5-hour rolling window usage.
+    #[getter]
+    fn five_hour(&self) -> PyUsagePeriod {
+        PyUsagePeriod(self.0.five_hour.clone())
+    }
+
+    /// 7-day rolling window usage.
+    #[getter]
+    fn seven_day(&self) -> PyUsagePeriod {
+        PyUsagePeriod(self.0.seven_day.clone())
+    }
+
+    /// 7-day Sonnet-specific usage, or None.
+    #[getter]
+    fn seven_day_sonnet(&self) -> Option<PyUsagePeriod> {
+        self.0.seven_day_sonnet.clone().map(PyUsagePeriod)
+    }
+
+    /// Extra usage billing information, or None.
+    #[getter]
+    fn extra_usage(&self) -> Option<PyExtraUsage> {
+        self.0.extra_usage.clone().map(PyExtraUsage)
+    }
+
+    /// True if 5-hour usage is on pace, or None without a reset time.
+    fn five_hour_on_pace(&self) -> Option<bool> {
+        self.0.five_hour_on_pace()
+    }
+
+    /// True if 7-day usage is on pace, or None without a reset time.
+    fn seven_day_on_pace(&self) -> Option<bool> {
+        self.0.seven_day_on_pace()
+    }
+
+    /// Projected 5-hour utilization at reset at the average rate so far.
+    fn five_hour_projected_at_reset(&self) -> Option<f64> {
+        self.0.five_hour_projected_at_reset()
+    }
+
+    /// Projected 7-day utilization at reset at the average rate so far.
+    fn seven_day_projected_at_reset(&self) -> Option<f64> {
+        self.0.seven_day_projected_at_reset()
+    }
+
+    /// Time until the first limit is reached as a timedelta, or None.
+    fn estimated_time_until_limit(&self) -> Option<TimeDelta> {
+        self.0.estimated_time_until_limit()
+    }
+
+    /// The usage data as a JSON string in the API's format.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.0).map_err(|e| ClaudeUsageError::new_err(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "UsageData(five_hour={}, seven_day={})",
+            self.five_hour().__repr__(),
+            self.seven_day().__repr__()
+        )
+    }
+}
+
+/// Fetch current Claude usage data.
+///
+/// Releases the GIL during the network request.
+#[pyfunction]
+fn get_usage(py: Python<'_>) -> PyResult<PyUsageData> {
+    py.allow_threads(crate::get_usage)
+        .map(PyUsageData)
+        .map_err(to_py_err)
+}
+
+/// Fetch usage data for a named credential profile.
+#[pyfunction]
+fn get_usage_for_profile(py: Python<'_>, name: &str) -> PyResult<PyUsageData> {
+    py.allow_threads(|| crate::get_usage_for_profile(name))
+        .map(|usage| PyUsageData(usage.data))
+        .map_err(to_py_err)
+}
+
+/// Check if `utilization` is on pace for a period resetting at `resets_at`.
+#[pyfunction]
+fn is_on_pace(utilization: f64, resets_at: DateTime<Utc>, period_hours: u32) -> Option<bool> {
+    UsagePeriod {
+        utilization,
+        resets_at: Some(resets_at),
+        extra: Default::default(),
+    }
+    .is_on_pace(period_hours)
+}
+
+/// The `claude_usage` Python module.
+#[pymodule]
+#[pyo3(name = "claude_usage")]
+fn claude_usage_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("ClaudeUsageError", py.get_type::<ClaudeUsageError>())?;
+    m.add("CredentialError", py.get_type::<CredentialError>())?;
+    m.add("ApiError", py.get_type::<ApiError>())?;
+    m.add_class::<PyUsagePeriod>()?;
+    m.add_class::<PyExtraUsage>()?;
+    m.add_class::<PyUsageData>()?;
+    m.add_function(wrap_pyfunction!(get_usage, m)?)?;
+    m.add_function(wrap_pyfunction!(get_usage_for_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(is_on_pace, m)?)?;
+    Ok(())
+}
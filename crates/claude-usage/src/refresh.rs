@@ -0,0 +1,405 @@
+//! OAuth access token refresh.
+//!
+//! Claude Code stores a refresh token next to the 8-hour access token. When
+//! the access token has expired, [`get_usage`](crate::get_usage) exchanges the
+//! refresh token for a new access token instead of returning
+//! [`CredentialError::Expired`].
+//!
+//! The token endpoint may rotate the refresh token, which invalidates the one
+//! Claude Code has stored. [`RefreshMode::Persist`] (the default) therefore
+//! only refreshes when the new tokens can be written back to the credential
+//! file, so Claude Code keeps working. Keychain and Credential Manager items
+//! are not written; those users still get [`CredentialError::Expired`] unless
+//! they opt into [`RefreshMode::InMemory`].
+
+// Without an HTTP client feature nothing calls the refresh helpers
+#![cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
+
+use crate::credentials::{self, StoredCredentials};
+use crate::error::{CredentialError, Error};
+
+/// Anthropic OAuth token endpoint.
+pub const TOKEN_URL: &str = "https://console.anthropic.com/v1/oauth/token";
+
+/// OAuth client ID of Claude Code, which issued the stored tokens.
+pub const CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
+
+/// Whether and how an expired access token is refreshed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RefreshMode {
+    /// Never refresh; expired tokens yield [`CredentialError::Expired`].
+    Disabled,
+    /// Refresh and use the new token without saving it. If the refresh token
+    /// is rotated, Claude Code will need a new login.
+    InMemory,
+    /// Refresh only when the new tokens can be saved back to the credential
+    /// file, and save them.
+    #[default]
+    Persist,
+}
+
+/// Tokens returned by a successful refresh.
+#[derive(Clone)]
+pub struct RefreshedToken {
+    /// New access token.
+    pub access_token: String,
+    /// New refresh token, if the endpoint rotated it.
+    pub refresh_token: Option<String>,
+    /// New access token expiry in milliseconds since the epoch.
+    pub expires_at_ms: i64,
+}
+
+impl std::fmt::Debug for RefreshedToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print tokens
+        f.debug_struct("RefreshedToken")
+            .field("access_token", &"<redacted>")
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("expires_at_ms", &self.expires_at_ms)
+            .finish()
+    }
+}
+
+/// Returns the refresh token to use, or None if the access token is still valid.
+///
+/// # Errors
+///
+/// Returns [`CredentialError::Expired`] if the access token has expired and
+/// `mode` does not allow refreshing these credentials.
+pub(crate) fn plan(
+    stored: &StoredCredentials,
+    mode: RefreshMode,
+    now_ms: i64,
+) -> Result<Option<&str>, CredentialError> {
+    if !stored.oauth.is_expired_at(now_ms) {
+        return Ok(None);
+    }
+    let allowed = match mode {
+        RefreshMode::Disabled => false,
+        RefreshMode::InMemory => true,
+        RefreshMode::Persist => stored.source.is_writable(),
+    };
+    match stored.oauth.refresh_token.as_deref() {
+        Some(token) if allowed => Ok(Some(token)),
+        _ => Err(CredentialError::Expired),
+    }
+}
+
+/// Saves `refreshed` back to the credential source when `mode` is
+/// [`RefreshMode::Persist`].
+///
+/// # Errors
+///
+/// Returns [`CredentialError`] if the stored JSON cannot be updated or written.
+pub(crate) fn finish(
+    stored: &StoredCredentials,
+    refreshed: &RefreshedToken,
+    mode: RefreshMode,
+) -> Result<(), CredentialError> {
+    if mode != RefreshMode::Persist {
+        return Ok(());
+    }
+    let updated = apply_to_json(&stored.raw, refreshed)?;
+    credentials::save_credentials(&stored.source, &updated)
+}
+
+/// Saves `refreshed` as [`finish`] does and returns its access token.
+///
+/// A failed save is logged instead of returned: the endpoint may already have
+/// rotated the old refresh token, so dropping the new tokens would leave the
+/// caller with nothing that works.
+pub(crate) fn keep(
+    stored: &StoredCredentials,
+    refreshed: RefreshedToken,
+    mode: RefreshMode,
+) -> String {
+    if let Err(e) = finish(stored, &refreshed, mode) {
+        log::warn!("could not save refreshed credentials: {}", e);
+    }
+    refreshed.access_token
+}
+
+/// Returns `raw` credential JSON with the `claudeAiOauth` tokens replaced.
+///
+/// Other fields (scopes, subscription type, unrelated entries) are kept.
+fn apply_to_json(raw: &str, refreshed: &RefreshedToken) -> Result<String, CredentialError> {
+    let mut json: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| CredentialError::Parse(e.to_string()))?;
+    let oauth = json
+        .get_mut("claudeAiOauth")
+        .and_then(|v| v.as_object_mut())
+        .ok_or(CredentialError::MissingField("claudeAiOauth"))?;
+    oauth.insert("accessToken".into(), refreshed.access_token.clone().into());
+    if let Some(refresh_token) = &refreshed.refresh_token {
+        oauth.insert("refreshToken".into(), refresh_token.clone().into());
+    }
+    oauth.insert("expiresAt".into(), refreshed.expires_at_ms.into());
+    serde_json::to_string(&json).map_err(|e| CredentialError::Parse(e.to_string()))
+}
+
+/// JSON body of a refresh token grant.
+fn request_body(refresh_token: &str) -> serde_json::Value {
+    serde_json::json!({
+        "grant_type": "refresh_token",
+        "refresh_token": refresh_token,
+        "client_id": CLIENT_ID,
+    })
+}
+
+/// Maps the token endpoint's status to an error, or None for success.
+///
+/// 400 and 401 mean the refresh token itself was rejected, so the user has
+/// to log in again.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn refresh_status_error(status: u16, headers: &reqwest::header::HeaderMap) -> Option<Error> {
+    match status {
+        400 | 401 => Some(Error::Credential(CredentialError::Expired)),
+        _ => crate::client::status_error(status, headers).map(Error::Api),
+    }
+}
+
+/// Parses a token endpoint response received at `now_ms`.
+fn parse_response(body: &str, now_ms: i64) -> Result<RefreshedToken, Error> {
+    #[derive(serde::Deserialize)]
+    struct Response {
+        access_token: String,
+        refresh_token: Option<String>,
+        expires_in: i64,
+    }
+    let response: Response = serde_json::from_str(body).map_err(|e| Error::Parse(e.to_string()))?;
+    Ok(RefreshedToken {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token,
+        expires_at_ms: now_ms + response.expires_in * 1000,
+    })
+}
+
+/// Exchange a refresh token for a new access token (blocking).
+///
+/// # Errors
+///
+/// Returns [`CredentialError::Expired`] if the refresh token was rejected,
+/// or [`Error::Api`]/[`Error::Parse`] for network and response failures.
+///
+/// # Security
+///
+/// The tokens are used only for this request and are not logged.
+#[cfg(feature = "blocking")]
+pub fn refresh_access_token(refresh_token: &str) -> Result<RefreshedToken, Error> {
+    use crate::error::ApiError;
+
+    let client = crate::http::blocking_client(std::time::Duration::from_secs(10))?;
+    let response = client
+        .post(TOKEN_URL)
+        .header("Content-Type", "application/json")
+        .body(request_body(refresh_token).to_string())
+        .send()
+        // Use generic message to avoid any potential token exposure in error details
+        .map_err(|_| ApiError::Network("Failed to connect to Anthropic OAuth".to_string()))?;
+
+    if let Some(err) = refresh_status_error(response.status().as_u16(), response.headers()) {
+        return Err(err);
+    }
+    let body = response
+        .text()
+        .map_err(|_| ApiError::Network("Failed to read response body".to_string()))?;
+    parse_response(&body, chrono::Utc::now().timestamp_millis())
+}
+
+/// Exchange a refresh token for a new access token (async).
+///
+/// # Errors
+///
+/// Returns the same errors as `refresh_access_token`.
+#[cfg(feature = "async")]
+pub async fn refresh_access_token_async(refresh_token: &str) -> Result<RefreshedToken, Error> {
+    use crate::error::ApiError;
+
+    let client = crate::http::async_client(std::time::Duration::from_secs(10))?;
+    let response = client
+        .post(TOKEN_URL)
+        .header("Content-Type", "application/json")
+        .body(request_body(refresh_token).to_string())
+        .send()
+        .await
+        // Use generic message to avoid any potential token exposure in error details
+        .map_err(|_| ApiError::Network("Failed to connect to Anthropic OAuth".to_string()))?;
+
+    if let Some(err) = refresh_status_error(response.status().as_u16(), response.headers()) {
+        return Err(err);
+    }
+    let body = response
+        .text()
+        .await
+        .map_err(|_| ApiError::Network("Failed to read response body".to_string()))?;
+    parse_response(&body, chrono::Utc::now().timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::{CredentialSource, OAuthCredentials};
+    use std::path::PathBuf;
+
+    const RAW: &str = r#"{"claudeAiOauth":{"accessToken":"old-access","refreshToken":"old-refresh","expiresAt":1000,"scopes":["user:inference"]},"other":1}"#;
+
+    fn stored(source: CredentialSource, refresh_token: Option<&str>) -> StoredCredentials {
+        StoredCredentials {
+            oauth: OAuthCredentials {
+                access_token: "old-access".to_string(),
+                refresh_token: refresh_token.map(String::from),
+                expires_at_ms: Some(1000),
+            },
+            source,
+            raw: RAW.to_string(),
+        }
+    }
+
+    fn file_source() -> CredentialSource {
+        CredentialSource::File(PathBuf::from("/nonexistent/.credentials.json"))
+    }
+
+    #[test]
+    fn test_plan_valid_token_needs_no_refresh() {
+        let creds = stored(file_source(), Some("old-refresh"));
+        assert_eq!(
+            plan(&creds, RefreshMode::Persist, 500).expect("valid"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_plan_expired_file_credentials_refresh() {
+        let creds = stored(file_source(), Some("old-refresh"));
+        assert_eq!(
+            plan(&creds, RefreshMode::Persist, 2000).expect("refreshable"),
+            Some("old-refresh")
+        );
+    }
+
+    #[test]
+    fn test_plan_persist_skips_unwritable_sources() {
+        let creds = stored(CredentialSource::Keychain, Some("old-refresh"));
+        assert!(matches!(
+            plan(&creds, RefreshMode::Persist, 2000),
+            Err(CredentialError::Expired)
+        ));
+        assert_eq!(
+            plan(&creds, RefreshMode::InMemory, 2000).expect("in-memory refresh"),
+            Some("old-refresh")
+        );
+    }
+
+    #[test]
+    fn test_plan_disabled_or_missing_refresh_token_is_expired() {
+        let creds = stored(file_source(), Some("old-refresh"));
+        assert!(matches!(
+            plan(&creds, RefreshMode::Disabled, 2000),
+            Err(CredentialError::Expired)
+        ));
+        let creds = stored(file_source(), None);
+        assert!(matches!(
+            plan(&creds, RefreshMode::InMemory, 2000),
+            Err(CredentialError::Expired)
+        ));
+    }
+
+    #[test]
+    fn test_apply_to_json_replaces_tokens_and_keeps_other_fields() {
+        let refreshed = RefreshedToken {
+            access_token: "new-access".to_string(),
+            refresh_token: Some("new-refresh".to_string()),
+            expires_at_ms: 99_000,
+        };
+        let updated = apply_to_json(RAW, &refreshed).expect("should update");
+        let json: serde_json::Value = serde_json::from_str(&updated).expect("valid JSON");
+        let oauth = &json["claudeAiOauth"];
+        assert_eq!(oauth["accessToken"], "new-access");
+        assert_eq!(oauth["refreshToken"], "new-refresh");
+        assert_eq!(oauth["expiresAt"], 99_000);
+        assert_eq!(oauth["scopes"][0], "user:inference");
+        assert_eq!(json["other"], 1);
+    }
+
+    #[test]
+    fn test_apply_to_json_keeps_refresh_token_when_not_rotated() {
+        let refreshed = RefreshedToken {
+            access_token: "new-access".to_string(),
+            refresh_token: None,
+            expires_at_ms: 99_000,
+        };
+        let updated = apply_to_json(RAW, &refreshed).expect("should update");
+        assert!(updated.contains(r#""refreshToken":"old-refresh""#));
+    }
+
+    #[test]
+    fn test_finish_persists_to_file() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let path = dir.path().join(".credentials.json");
+        std::fs::write(&path, RAW).expect("write");
+        let creds = stored(CredentialSource::File(path.clone()), Some("old-refresh"));
+        let refreshed = RefreshedToken {
+            access_token: "new-access".to_string(),
+            refresh_token: None,
+            expires_at_ms: i64::MAX,
+        };
+
+        finish(&creds, &refreshed, RefreshMode::InMemory).expect("no-op");
+        assert_eq!(std::fs::read_to_string(&path).expect("read"), RAW);
+
+        finish(&creds, &refreshed, RefreshMode::Persist).expect("persist");
+        let saved = std::fs::read_to_string(&path).expect("read");
+        assert_eq!(
+            credentials::parse_credential_json(&saved).expect("valid token"),
+            "new-access"
+        );
+    }
+
+    #[test]
+    fn test_keep_returns_token_when_save_fails() {
+        let creds = stored(file_source(), Some("old-refresh"));
+        let refreshed = RefreshedToken {
+            access_token: "new-access".to_string(),
+            refresh_token: Some("new-refresh".to_string()),
+            expires_at_ms: i64::MAX,
+        };
+
+        assert!(finish(&creds, &refreshed, RefreshMode::Persist).is_err());
+        assert_eq!(keep(&creds, refreshed, RefreshMode::Persist), "new-access");
+    }
+
+    #[test]
+    fn test_parse_response_computes_expiry() {
+        let body =
+            r#"{"access_token":"a","refresh_token":"r","expires_in":28800,"token_type":"Bearer"}"#;
+        let token = parse_response(body, 1_000).expect("should parse");
+        assert_eq!(token.access_token, "a");
+        assert_eq!(token.refresh_token.as_deref(), Some("r"));
+        assert_eq!(token.expires_at_ms, 1_000 + 28_800_000);
+    }
+
+    #[test]
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn test_rejected_refresh_token_means_expired() {
+        let headers = reqwest::header::HeaderMap::new();
+        assert!(matches!(
+            refresh_status_error(400, &headers),
+            Some(Error::Credential(CredentialError::Expired))
+        ));
+        assert!(refresh_status_error(200, &headers).is_none());
+    }
+
+    #[test]
+    fn test_debug_redacts_tokens() {
+        let refreshed = RefreshedToken {
+            access_token: "secret-access".to_string(),
+            refresh_token: Some("secret-refresh".to_string()),
+            expires_at_ms: 0,
+        };
+        let debug = format!("{:?}", refreshed);
+        assert!(!debug.contains("secret"));
+    }
+}
@@ -0,0 +1,226 @@
+//! Retry policy for usage API requests.
+//!
+//! Transient failures (network errors, 429 Too Many Requests, 5xx responses)
+//! are retried with exponential backoff. A 429 response's `Retry-After` header
+//! is honored: the client waits at least that long, and gives up instead if
+//! the server asks for more than [`RetryPolicy::max_delay`]. Authentication
+//! errors (401, 403) and unexpected status codes are never retried.
+//!
+//! ```rust,ignore
+//! use claude_usage::{get_usage_with_retry, RetryPolicy};
+//!
+//! // Fail fast, e.g. for a statusbar script with its own refresh loop
+//! let usage = get_usage_with_retry(&RetryPolicy::disabled())?;
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::ApiError;
+
+/// How failed usage API requests are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first request. 1 disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each following retry.
+    pub base_delay: Duration,
+    /// Upper bound for a single delay. A `Retry-After` longer than this
+    /// ends retrying.
+    pub max_delay: Duration,
+    /// Randomize each backoff delay between half and full length, so
+    /// several clients don't retry in lockstep.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 500ms base delay, 30s maximum delay, with jitter.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that makes a single attempt and never retries.
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Returns how long to wait before retrying after `error`, or None to
+    /// give up.
+    ///
+    /// `attempt` is the number of attempts made so far (1 after the first
+    /// request failed).
+    pub fn delay_for(&self, attempt: u32, error: &ApiError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !is_retryable(error) {
+            return None;
+        }
+        if let ApiError::RateLimited {
+            retry_after: Some(value),
+        } = error
+        {
+            if let Some(wait) = parse_retry_after(value, Utc::now()) {
+                return (wait <= self.max_delay).then_some(wait);
+            }
+        }
+        let backoff = self.backoff(attempt);
+        Some(if self.jitter {
+            jittered(backoff)
+        } else {
+            backoff
+        })
+    }
+
+    /// Exponential backoff before retry number `attempt` (1-based), capped
+    /// at `max_delay`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// Returns true for errors that may succeed on a later attempt.
+pub fn is_retryable(error: &ApiError) -> bool {
+    matches!(
+        error,
+        ApiError::Network(_) | ApiError::RateLimited { .. } | ApiError::Server(_)
+    )
+}
+
+/// Parses a `Retry-After` header value: delay seconds or an HTTP date.
+///
+/// Dates in the past yield a zero delay. Returns None for unparseable values.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Returns a random duration between half of `delay` and `delay`.
+fn jittered(delay: Duration) -> Duration {
+    // RandomState is seeded randomly per instance; enough entropy for jitter
+    // without pulling in a random number crate.
+    let random = RandomState::new().build_hasher().finish();
+    let fraction = 0.5 + (random % 1000) as f64 / 2000.0;
+    delay.mul_f64(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_jitter() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_until_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+            jitter: false,
+        };
+        let err = ApiError::Server(503);
+        let delays: Vec<_> = (1..=4)
+            .map(|n| policy.delay_for(n, &err).expect("retryable"))
+            .collect();
+        assert_eq!(
+            delays,
+            [1, 2, 4, 5].map(Duration::from_secs),
+            "capped at max_delay"
+        );
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let policy = no_jitter();
+        let err = ApiError::Network("timeout".to_string());
+        assert!(policy.delay_for(2, &err).is_some());
+        assert!(policy.delay_for(3, &err).is_none());
+        assert!(RetryPolicy::disabled().delay_for(1, &err).is_none());
+    }
+
+    #[test]
+    fn test_auth_errors_are_not_retried() {
+        let policy = no_jitter();
+        assert!(policy.delay_for(1, &ApiError::Unauthorized).is_none());
+        assert!(policy.delay_for(1, &ApiError::Forbidden).is_none());
+        assert!(policy.delay_for(1, &ApiError::Unexpected(404)).is_none());
+    }
+
+    #[test]
+    fn test_retry_after_seconds_is_honored() {
+        let policy = no_jitter();
+        let err = ApiError::RateLimited {
+            retry_after: Some("7".to_string()),
+        };
+        assert_eq!(policy.delay_for(1, &err), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_retry_after_beyond_max_delay_gives_up() {
+        let policy = no_jitter();
+        let err = ApiError::RateLimited {
+            retry_after: Some("3600".to_string()),
+        };
+        assert_eq!(policy.delay_for(1, &err), None);
+    }
+
+    #[test]
+    fn test_rate_limited_without_header_uses_backoff() {
+        let policy = no_jitter();
+        let err = ApiError::RateLimited { retry_after: None };
+        assert_eq!(policy.delay_for(1, &err), Some(policy.base_delay));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .expect("valid date")
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:00:00 GMT", now),
+            Some(Duration::ZERO),
+            "past dates mean retry now"
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_half_to_full() {
+        let delay = Duration::from_secs(2);
+        for _ in 0..50 {
+            let d = jittered(delay);
+            assert!(d >= Duration::from_secs(1) && d <= delay, "{d:?}");
+        }
+    }
+}
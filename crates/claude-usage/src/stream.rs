@@ -0,0 +1,233 @@
+//! Polling subscription as an async stream.
+//!
+//! [`subscribe`](crate::subscribe) polls the usage API on an interval and
+//! yields [`UsageEvent`]s: every result as [`Updated`](UsageEvent::Updated)
+//! or [`Error`](UsageEvent::Error), plus threshold crossings and period
+//! resets detected by a [`UsageWatcher`]. Requires the `async` feature.
+//!
+//! ```rust,ignore
+//! use futures::StreamExt;
+//! use claude_usage::watcher::UsageEvent;
+//!
+//! let mut events = claude_usage::subscribe(std::time::Duration::from_secs(180));
+//! while let Some(event) = events.next().await {
+//!     match event {
+//!         UsageEvent::Updated(usage) => render(&usage),
+//!         UsageEvent::ThresholdCrossed(e) => notify(&format!("{} at {}%", e.window, e.threshold)),
+//!         UsageEvent::PeriodReset(e) => notify(&format!("{} reset", e.window)),
+//!         UsageEvent::Error(e) => eprintln!("usage poll failed: {}", e),
+//!     }
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+
+use crate::error::Error;
+use crate::transport::{UsageClient, UsageFuture};
+use crate::types::UsageData;
+use crate::watcher::{UsageEvent, UsageWatcher};
+
+/// Stream of [`UsageEvent`]s from [`UsageWatcher::into_stream`] or
+/// [`subscribe`](crate::subscribe).
+///
+/// Polls only while the stream is polled; drop it to stop. Ticks missed
+/// while the consumer was busy are skipped rather than fetched in a burst.
+pub struct UsageStream {
+    watcher: UsageWatcher,
+    client: Arc<dyn UsageClient>,
+    /// Gate checked at each tick; `None` polls every tick.
+    should_poll: Option<Box<dyn FnMut() -> bool + Send>>,
+    /// Created on first poll, so the stream can be built outside a runtime.
+    ticker: Option<tokio::time::Interval>,
+    fetch: Option<UsageFuture<'static>>,
+    /// Events from the last poll not yet yielded.
+    pending: VecDeque<UsageEvent>,
+}
+
+impl UsageStream {
+    /// Creates a stream that polls `client` and feeds `watcher`.
+    pub(crate) fn new(watcher: UsageWatcher, client: Arc<dyn UsageClient>) -> Self {
+        Self {
+            watcher,
+            client,
+            should_poll: None,
+            ticker: None,
+            fetch: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Skips ticks for which `should_poll` returns false, for example while
+    /// nobody is displaying the data.
+    pub fn poll_if(mut self, should_poll: impl FnMut() -> bool + Send + 'static) -> Self {
+        self.should_poll = Some(Box::new(should_poll));
+        self
+    }
+
+    /// Records the result of a finished fetch as events.
+    fn finish_fetch(&mut self, result: Result<UsageData, Error>) {
+        match result {
+            Ok(usage) => {
+                let events = self.watcher.observe(&usage);
+                self.pending.push_back(UsageEvent::Updated(usage));
+                self.pending.extend(events);
+            }
+            Err(e) => {
+                self.watcher.report_error(&e);
+                self.pending.push_back(UsageEvent::Error(e));
+            }
+        }
+    }
+}
+
+impl fmt::Debug for UsageStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsageStream")
+            .field("watcher", &self.watcher)
+            .field("fetching", &self.fetch.is_some())
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for UsageStream {
+    type Item = UsageEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<UsageEvent>> {
+        let this = &mut *self;
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+            if let Some(fetch) = &mut this.fetch {
+                let result = ready!(fetch.as_mut().poll(cx));
+                this.fetch = None;
+                this.finish_fetch(result);
+                continue;
+            }
+            let interval = this.watcher.poll_interval();
+            let ticker = this.ticker.get_or_insert_with(|| {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                ticker
+            });
+            ready!(ticker.poll_tick(cx));
+            if this
+                .should_poll
+                .as_mut()
+                .map_or(true, |should_poll| should_poll())
+            {
+                let client = Arc::clone(&this.client);
+                this.fetch = Some(Box::pin(async move { client.fetch_usage().await }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::error::ApiError;
+    use crate::types::UsagePeriod;
+    use crate::watcher::UsageWindow;
+
+    /// Serves scripted responses, then keeps failing.
+    struct Scripted(std::sync::Mutex<VecDeque<Result<UsageData, Error>>>);
+
+    impl UsageClient for Scripted {
+        fn fetch_usage(&self) -> UsageFuture<'_> {
+            let next = self.0.lock().unwrap().pop_front();
+            Box::pin(std::future::ready(next.unwrap_or_else(|| {
+                Err(ApiError::Network("done".to_string()).into())
+            })))
+        }
+    }
+
+    fn usage(five_hour: f64) -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: five_hour,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 1.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    fn stream(responses: Vec<Result<UsageData, Error>>) -> UsageStream {
+        UsageWatcher::new()
+            .interval(Duration::from_secs(60))
+            .into_stream(Arc::new(Scripted(std::sync::Mutex::new(responses.into()))))
+    }
+
+    async fn next(stream: &mut UsageStream) -> Option<UsageEvent> {
+        std::future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_yields_updates_thresholds_and_errors() {
+        let mut events = stream(vec![Ok(usage(10.0)), Ok(usage(55.0))]);
+
+        let started = tokio::time::Instant::now();
+        assert!(
+            matches!(next(&mut events).await, Some(UsageEvent::Updated(u)) if u.five_hour.utilization == 10.0)
+        );
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        assert!(matches!(
+            next(&mut events).await,
+            Some(UsageEvent::Updated(_))
+        ));
+        assert_eq!(started.elapsed(), Duration::from_secs(60));
+        match next(&mut events).await {
+            Some(UsageEvent::ThresholdCrossed(e)) => {
+                assert_eq!((e.window, e.threshold), (UsageWindow::FiveHour, 50.0));
+            }
+            other => panic!("expected ThresholdCrossed, got {:?}", other),
+        }
+
+        assert!(matches!(
+            next(&mut events).await,
+            Some(UsageEvent::Error(Error::Api(ApiError::Network(_))))
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_poll_if_skips_ticks() {
+        let allowed = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let gate = Arc::clone(&allowed);
+        let mut events = stream(vec![Ok(usage(1.0))])
+            .poll_if(move || gate.load(std::sync::atomic::Ordering::SeqCst));
+
+        let started = tokio::time::Instant::now();
+        let allow_later = async {
+            tokio::time::sleep(Duration::from_secs(150)).await;
+            allowed.store(true, std::sync::atomic::Ordering::SeqCst);
+            std::future::pending::<()>().await;
+        };
+        tokio::select! {
+            event = next(&mut events) => {
+                assert!(matches!(event, Some(UsageEvent::Updated(_))));
+            }
+            _ = allow_later => unreachable!(),
+        }
+        // Ticks at 0s, 60s and 120s were skipped
+        assert_eq!(started.elapsed(), Duration::from_secs(180));
+    }
+}
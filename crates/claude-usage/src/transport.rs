@@ -0,0 +1,311 @@
+//! Pluggable HTTP transport for the usage API.
+//!
+//! The built-in clients use reqwest, which needs the `blocking` or `async`
+//! feature. Without them, this crate builds for `wasm32-unknown-unknown`, and
+//! a [`Transport`] supplied by the caller (for example one wrapping the
+//! browser's `fetch`) performs the request instead. Request building, status
+//! mapping and parsing stay here, so browser code shares the response models
+//! and helper methods of [`UsageData`].
+//!
+//! Credential lookup is not part of this layer; the caller passes the token.
+//!
+//! One level up, [`UsageClient`] abstracts "get the current usage" as a
+//! whole. Consumers that hold a `dyn UsageClient` can run against
+//! [`LiveUsageClient`] in production and `mock::MockUsageClient` (`mock`
+//! feature) in tests, without credentials or network.
+//!
+//! ```rust,ignore
+//! use claude_usage::transport::{fetch_usage_with, HttpRequest, HttpResponse, Transport};
+//! use claude_usage::ApiError;
+//!
+//! struct BrowserFetch;
+//!
+//! impl Transport for BrowserFetch {
+//!     fn send(
+//!         &self,
+//!         request: HttpRequest,
+//!     ) -> impl std::future::Future<Output = Result<HttpResponse, ApiError>> {
+//!         async move { /* call window.fetch via web-sys */ }
+//!     }
+//! }
+//!
+//! let usage = fetch_usage_with(&BrowserFetch, &token).await?;
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::client::{status_to_error, UsageApiClient};
+use crate::error::{ApiError, Error};
+use crate::types::UsageData;
+
+/// An HTTP GET request to send.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// Absolute request URL.
+    pub url: String,
+    /// Header names and values.
+    pub headers: Vec<(String, String)>,
+}
+
+impl std::fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the bearer token or API key
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                if name.eq_ignore_ascii_case("authorization")
+                    || name.eq_ignore_ascii_case("x-api-key")
+                {
+                    (name.as_str(), "<redacted>")
+                } else {
+                    (name.as_str(), value.as_str())
+                }
+            })
+            .collect();
+        f.debug_struct("HttpRequest")
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .finish()
+    }
+}
+
+/// The parts of an HTTP response the usage API client needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Value of the `Retry-After` header, if present.
+    pub retry_after: Option<String>,
+    /// Response body.
+    pub body: String,
+}
+
+/// Sends HTTP requests on behalf of [`fetch_usage_with`].
+///
+/// The returned future has no `Send` bound, so single-threaded browser
+/// futures can be used.
+pub trait Transport {
+    /// Sends `request` and returns the response, whatever its status.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::Network`] if no response was received.
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, ApiError>>;
+}
+
+/// Builds the usage API request for `token`, honoring
+/// [`ENV_VAR_BASE_URL`](crate::client::ENV_VAR_BASE_URL).
+///
+/// # Security
+///
+/// The returned request contains the token; do not log its headers.
+pub fn usage_request(token: &str) -> HttpRequest {
+    UsageApiClient::new().request(token)
+}
+
+/// Parses a usage API response body.
+///
+/// # Errors
+///
+/// Returns [`Error::Parse`] if the body is not valid usage JSON.
+pub fn parse_usage(body: &str) -> Result<UsageData, Error> {
+    serde_json::from_str(body).map_err(|e| Error::Parse(e.to_string()))
+}
+
+/// Maps a usage API response to usage data or an error.
+///
+/// # Errors
+///
+/// Returns [`ApiError`] for non-200 statuses, mapped like the built-in
+/// clients, or [`Error::Parse`] for an invalid body.
+pub fn parse_usage_response(response: &HttpResponse) -> Result<UsageData, Error> {
+    if let Some(err) = status_to_error(response.status, response.retry_after.as_deref()) {
+        return Err(err.into());
+    }
+    parse_usage(&response.body)
+}
+
+/// Fetches usage data for `token` through `transport`.
+///
+/// Makes a single attempt; use [`RetryPolicy::delay_for`](crate::RetryPolicy::delay_for)
+/// with the returned [`ApiError`] to retry with the transport's own timer.
+///
+/// # Errors
+///
+/// Returns the transport's error, or the errors of [`parse_usage_response`].
+pub async fn fetch_usage_with<T: Transport>(
+    transport: &T,
+    token: &str,
+) -> Result<UsageData, Error> {
+    let response = transport.send(usage_request(token)).await?;
+    parse_usage_response(&response)
+}
+
+/// Boxed future returned by [`UsageClient::fetch_usage`].
+pub type UsageFuture<'a> = Pin<Box<dyn Future<Output = Result<UsageData, Error>> + Send + 'a>>;
+
+/// A source of usage data.
+///
+/// Object safe, so it can be stored as `Arc<dyn UsageClient>` and replaced
+/// in tests.
+pub trait UsageClient: Send + Sync {
+    /// Fetches the current usage.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`get_usage_async`](crate::get_usage_async).
+    fn fetch_usage(&self) -> UsageFuture<'_>;
+}
+
+/// [`UsageClient`] backed by [`get_usage_async`](crate::get_usage_async).
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LiveUsageClient;
+
+#[cfg(feature = "async")]
+impl UsageClient for LiveUsageClient {
+    fn fetch_usage(&self) -> UsageFuture<'_> {
+        Box::pin(crate::get_usage_async())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::client::{BETA_HEADER, USAGE_API_URL};
+    use std::cell::RefCell;
+    use std::pin::pin;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    /// Polls a future that completes without waiting.
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        fn raw() -> RawWaker {
+            fn clone(_: *const ()) -> RawWaker {
+                raw()
+            }
+            fn noop(_: *const ()) {}
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        // SAFETY: the vtable functions ignore the null data pointer.
+        let waker = unsafe { Waker::from_raw(raw()) };
+        let mut cx = Context::from_waker(&waker);
+        match pin!(future).as_mut().poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("test future should not wait"),
+        }
+    }
+
+    /// Returns a canned response (None: network error) and records the request.
+    struct Canned {
+        response: Option<HttpResponse>,
+        seen: RefCell<Option<HttpRequest>>,
+    }
+
+    impl Canned {
+        fn new(response: Option<HttpResponse>) -> Self {
+            Self {
+                response,
+                seen: RefCell::new(None),
+            }
+        }
+    }
+
+    impl Transport for Canned {
+        fn send(
+            &self,
+            request: HttpRequest,
+        ) -> impl Future<Output = Result<HttpResponse, ApiError>> {
+            *self.seen.borrow_mut() = Some(request);
+            std::future::ready(
+                self.response
+                    .clone()
+                    .ok_or_else(|| ApiError::Network("offline".to_string())),
+            )
+        }
+    }
+
+    fn ok(body: &str) -> HttpResponse {
+        HttpResponse {
+            status: 200,
+            retry_after: None,
+            body: body.to_string(),
+        }
+    }
+
+    const BODY: &str = r#"{
+        "five_hour": {"utilization": 8.0, "resets_at": null},
+        "seven_day": {"utilization": 77.0, "resets_at": null}
+    }"#;
+
+    // The URL follows CLAUDE_USAGE_BASE_URL, which other tests set
+    #[test]
+    #[serial_test::serial]
+    fn test_fetch_usage_with_sends_auth_headers() {
+        let transport = Canned::new(Some(ok(BODY)));
+        let usage = block_on(fetch_usage_with(&transport, "tok")).expect("usage");
+        assert_eq!(usage.seven_day.utilization, 77.0);
+
+        let request = transport.seen.borrow().clone().expect("request sent");
+        assert_eq!(request.url, USAGE_API_URL);
+        assert!(request
+            .headers
+            .contains(&("Authorization".to_string(), "Bearer tok".to_string())));
+        assert!(request
+            .headers
+            .contains(&("anthropic-beta".to_string(), BETA_HEADER.to_string())));
+    }
+
+    #[test]
+    fn test_transport_error_is_returned() {
+        let transport = Canned::new(None);
+        let result = block_on(fetch_usage_with(&transport, "tok"));
+        assert!(matches!(result, Err(Error::Api(ApiError::Network(_)))));
+    }
+
+    #[test]
+    fn test_status_codes_map_like_builtin_clients() {
+        let limited = HttpResponse {
+            status: 429,
+            retry_after: Some("30".to_string()),
+            body: String::new(),
+        };
+        match parse_usage_response(&limited) {
+            Err(Error::Api(ApiError::RateLimited { retry_after })) => {
+                assert_eq!(retry_after.as_deref(), Some("30"));
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+        let forbidden = HttpResponse {
+            status: 403,
+            ..ok("")
+        };
+        assert!(matches!(
+            parse_usage_response(&forbidden),
+            Err(Error::Api(ApiError::Forbidden))
+        ));
+        assert!(matches!(
+            parse_usage_response(&ok("not json")),
+            Err(Error::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_request_debug_redacts_token() {
+        let debug = format!("{:?}", usage_request("sk-ant-oat01-secret"));
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("anthropic-beta"));
+    }
+
+    #[test]
+    fn test_request_debug_redacts_api_key() {
+        let request = UsageApiClient::new().request_with_auth(&crate::auth::Auth::ApiKey(
+            "sk-ant-api03-secret".to_string(),
+        ));
+        let debug = format!("{:?}", request);
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("x-api-key"));
+    }
+}
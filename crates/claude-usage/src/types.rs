@@ -3,13 +3,23 @@
 //! This module defines the structures that map to the JSON response
 //! from the Anthropic OAuth usage API.
 
-use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::history::{recent_rate, HistorySample};
+
+/// How far `resets_at` must move forward to count as a new period. The API
+/// reports the same reset time with small variations between calls.
+const ROLLOVER_TOLERANCE: TimeDelta = TimeDelta::seconds(60);
 
 /// Main usage data returned by [`get_usage()`](crate::get_usage).
 ///
 /// Contains utilization data for different time periods.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "RawUsageData")]
 pub struct UsageData {
     /// 5-hour rolling window usage.
     pub five_hour: UsagePeriod,
@@ -24,6 +34,90 @@ pub struct UsageData {
     /// Extra usage billing information (if enabled).
     #[serde(default)]
     pub extra_usage: Option<ExtraUsage>,
+
+    /// Other per-model or per-bucket windows in the response.
+    #[serde(flatten)]
+    pub breakdown: UsageBreakdown,
+
+    /// Response fields this version does not know about, kept so that
+    /// re-serialized usage matches what the API sent.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Wire form of [`UsageData`], before unknown fields are split into
+/// windows and the rest.
+#[derive(Deserialize)]
+struct RawUsageData {
+    five_hour: UsagePeriod,
+    seven_day: UsagePeriod,
+    #[serde(default)]
+    seven_day_sonnet: Option<UsagePeriod>,
+    #[serde(default)]
+    extra_usage: Option<ExtraUsage>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl From<RawUsageData> for UsageData {
+    fn from(raw: RawUsageData) -> Self {
+        let mut breakdown = UsageBreakdown::default();
+        let mut extra = Map::new();
+        for (name, value) in raw.other {
+            match UsagePeriod::deserialize(&value) {
+                Ok(period) => {
+                    breakdown.windows.insert(name, period);
+                }
+                Err(_) => {
+                    extra.insert(name, value);
+                }
+            }
+        }
+        Self {
+            five_hour: raw.five_hour,
+            seven_day: raw.seven_day,
+            seven_day_sonnet: raw.seven_day_sonnet,
+            extra_usage: raw.extra_usage,
+            breakdown,
+            extra,
+        }
+    }
+}
+
+/// Usage windows reported besides `five_hour`, `seven_day` and
+/// `seven_day_sonnet`, such as `seven_day_opus` or `seven_day_oauth_apps`.
+///
+/// Keyed by the API field name. In [`UsageData`], response fields that are
+/// null or not shaped like a [`UsagePeriod`] go to
+/// [`UsageData::extra`] instead.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UsageBreakdown {
+    /// Windows by API field name.
+    pub windows: BTreeMap<String, UsagePeriod>,
+}
+
+impl UsageBreakdown {
+    /// Returns the window named `name` (e.g. `"seven_day_opus"`).
+    pub fn get(&self, name: &str) -> Option<&UsagePeriod> {
+        self.windows.get(name)
+    }
+
+    /// True if the response had no extra windows.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+}
+
+/// One window's usage for a model or bucket, from [`UsageData::buckets`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketUsage<'a> {
+    /// Window name: `"five_hour"` or `"seven_day"`.
+    pub window: &'a str,
+    /// Model family or bucket, e.g. `"sonnet"`, `"opus"` or `"oauth_apps"`.
+    pub bucket: &'a str,
+    /// Usage in this window.
+    pub period: &'a UsagePeriod,
 }
 
 /// Usage data for a specific time period.
@@ -39,6 +133,10 @@ pub struct UsagePeriod {
     /// May be `None` if the reset time is not available from the API.
     #[serde(default)]
     pub resets_at: Option<DateTime<Utc>>,
+
+    /// Fields this version does not know about, kept for re-serialization.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Extra usage billing information.
@@ -56,6 +154,27 @@ pub struct ExtraUsage {
     pub limit: Option<f64>,
 }
 
+impl ExtraUsage {
+    /// Dollars left before the extra usage limit, never negative.
+    ///
+    /// Returns `None` if extra usage is disabled or has no limit.
+    pub fn remaining(&self) -> Option<f64> {
+        if !self.is_enabled {
+            return None;
+        }
+        let limit = self.limit?;
+        Some((limit - self.amount_used.unwrap_or(0.0)).max(0.0))
+    }
+
+    /// Whether an estimated cost in dollars (see [`crate::pricing`]) fits
+    /// in the remaining extra usage.
+    ///
+    /// Returns `None` if extra usage is disabled or has no limit.
+    pub fn covers(&self, cost: f64) -> Option<bool> {
+        self.remaining().map(|remaining| cost <= remaining)
+    }
+}
+
 impl UsagePeriod {
     /// Calculate time remaining until this period resets.
     ///
@@ -99,6 +218,64 @@ impl UsagePeriod {
         self.time_elapsed_percent(period_hours)
             .map(|elapsed| self.utilization <= elapsed)
     }
+
+    /// Average utilization growth since the period started, in percent per hour.
+    ///
+    /// Returns `None` if reset time is not available or the period has only
+    /// just started (less than a minute elapsed).
+    ///
+    /// # Arguments
+    ///
+    /// * `period_hours` - Total duration of the period in hours
+    pub fn burn_rate(&self, period_hours: u32) -> Option<f64> {
+        let remaining = self.time_until_reset()?.num_seconds() as f64 / 3600.0;
+        let elapsed = period_hours as f64 - remaining;
+        (elapsed >= 1.0 / 60.0).then(|| self.utilization / elapsed)
+    }
+
+    /// Project utilization at reset time if usage continues at `rate_per_hour`.
+    ///
+    /// Returns `None` if reset time is not available.
+    pub fn projected_at_reset(&self, rate_per_hour: f64) -> Option<f64> {
+        let remaining = self.time_until_reset()?.num_seconds().max(0) as f64 / 3600.0;
+        Some(self.utilization + rate_per_hour.max(0.0) * remaining)
+    }
+
+    /// Estimate when utilization reaches 100% at `rate_per_hour`.
+    ///
+    /// Returns `Some(zero)` if the limit is already reached, and `None` if
+    /// reset time is not available or the limit would not be reached before
+    /// the period resets.
+    pub fn time_until_limit(&self, rate_per_hour: f64) -> Option<chrono::TimeDelta> {
+        let remaining = self.time_until_reset()?;
+        if self.utilization >= 100.0 {
+            return Some(chrono::TimeDelta::zero());
+        }
+        if rate_per_hour <= 0.0 {
+            return None;
+        }
+        let hours = (100.0 - self.utilization) / rate_per_hour;
+        let until = chrono::TimeDelta::seconds((hours * 3600.0) as i64);
+        (until < remaining).then_some(until)
+    }
+
+    /// Compare this period with the same window in an earlier snapshot.
+    ///
+    /// The window rolled over if `resets_at` moved forward by more than a
+    /// minute, or was cleared after being set (the API clears it for idle
+    /// windows).
+    pub fn diff(&self, previous: &UsagePeriod) -> PeriodDelta {
+        let rolled_over = match (previous.resets_at, self.resets_at) {
+            (Some(before), Some(now)) => now - before > ROLLOVER_TOLERANCE,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        PeriodDelta {
+            previous: previous.utilization,
+            current: self.utilization,
+            rolled_over,
+        }
+    }
 }
 
 impl UsageData {
@@ -117,6 +294,227 @@ impl UsageData {
     pub fn seven_day_on_pace(&self) -> Option<bool> {
         self.seven_day.is_on_pace(7 * 24)
     }
+
+    /// Project 5-hour utilization at reset, assuming the average rate so far
+    /// continues.
+    ///
+    /// Returns `None` if reset time is not available or the period just started.
+    pub fn five_hour_projected_at_reset(&self) -> Option<f64> {
+        let rate = self.five_hour.burn_rate(5)?;
+        self.five_hour.projected_at_reset(rate)
+    }
+
+    /// Project 7-day utilization at reset, assuming the average rate so far
+    /// continues.
+    ///
+    /// Returns `None` if reset time is not available or the period just started.
+    pub fn seven_day_projected_at_reset(&self) -> Option<f64> {
+        let rate = self.seven_day.burn_rate(7 * 24)?;
+        self.seven_day.projected_at_reset(rate)
+    }
+
+    /// Estimate time until either the 5-hour or 7-day limit is reached at the
+    /// average rate so far.
+    ///
+    /// Returns the sooner of the two, or `None` if neither limit is reached
+    /// before its period resets.
+    pub fn estimated_time_until_limit(&self) -> Option<chrono::TimeDelta> {
+        self.time_until_limit_at(
+            self.five_hour.burn_rate(5),
+            self.seven_day.burn_rate(7 * 24),
+        )
+    }
+
+    /// Like [`five_hour_projected_at_reset`](Self::five_hour_projected_at_reset),
+    /// but using the recent slope from `history` when it has enough samples.
+    ///
+    /// The slope covers the last hour of samples in the current window (see
+    /// [`history::recent_rate`](crate::history::recent_rate)).
+    pub fn five_hour_projected_at_reset_with_history(
+        &self,
+        history: &[HistorySample],
+    ) -> Option<f64> {
+        let rate = self.five_hour_rate(history)?;
+        self.five_hour.projected_at_reset(rate)
+    }
+
+    /// Like [`seven_day_projected_at_reset`](Self::seven_day_projected_at_reset),
+    /// but using the slope of the last day of `history` when available.
+    pub fn seven_day_projected_at_reset_with_history(
+        &self,
+        history: &[HistorySample],
+    ) -> Option<f64> {
+        let rate = self.seven_day_rate(history)?;
+        self.seven_day.projected_at_reset(rate)
+    }
+
+    /// Like [`estimated_time_until_limit`](Self::estimated_time_until_limit),
+    /// but using recent slopes from `history` when available.
+    pub fn estimated_time_until_limit_with_history(
+        &self,
+        history: &[HistorySample],
+    ) -> Option<chrono::TimeDelta> {
+        self.time_until_limit_at(self.five_hour_rate(history), self.seven_day_rate(history))
+    }
+
+    /// Recent 5-hour slope from `history`, falling back to the average rate.
+    fn five_hour_rate(&self, history: &[HistorySample]) -> Option<f64> {
+        recent_rate(history, chrono::TimeDelta::hours(1), Utc::now(), |s| {
+            s.five_hour
+        })
+        .or_else(|| self.five_hour.burn_rate(5))
+    }
+
+    /// Recent 7-day slope from `history`, falling back to the average rate.
+    fn seven_day_rate(&self, history: &[HistorySample]) -> Option<f64> {
+        recent_rate(history, chrono::TimeDelta::days(1), Utc::now(), |s| {
+            s.seven_day
+        })
+        .or_else(|| self.seven_day.burn_rate(7 * 24))
+    }
+
+    /// Sooner of the two periods' time until limit at the given rates.
+    fn time_until_limit_at(
+        &self,
+        five_hour_rate: Option<f64>,
+        seven_day_rate: Option<f64>,
+    ) -> Option<chrono::TimeDelta> {
+        let five = five_hour_rate.and_then(|r| self.five_hour.time_until_limit(r));
+        let seven = seven_day_rate.and_then(|r| self.seven_day.time_until_limit(r));
+        five.into_iter().chain(seven).min()
+    }
+
+    /// Per-model and per-bucket windows, highest utilization first.
+    ///
+    /// Includes `seven_day_sonnet` and every [`breakdown`](Self::breakdown)
+    /// window named `five_hour_<bucket>` or `seven_day_<bucket>`.
+    pub fn buckets(&self) -> Vec<BucketUsage<'_>> {
+        let sonnet = self.seven_day_sonnet.as_ref().map(|period| BucketUsage {
+            window: "seven_day",
+            bucket: "sonnet",
+            period,
+        });
+        let others = self.breakdown.windows.iter().filter_map(|(name, period)| {
+            ["five_hour", "seven_day"].into_iter().find_map(|window| {
+                let bucket = name.strip_prefix(window)?.strip_prefix('_')?;
+                Some(BucketUsage {
+                    window,
+                    bucket,
+                    period,
+                })
+            })
+        });
+        let mut buckets: Vec<_> = sonnet.into_iter().chain(others).collect();
+        buckets.sort_by(|a, b| b.period.utilization.total_cmp(&a.period.utilization));
+        buckets
+    }
+
+    /// Compare this snapshot with an earlier one of the same account.
+    ///
+    /// `seven_day_sonnet` is compared only when both snapshots report it.
+    pub fn diff(&self, previous: &UsageData) -> UsageDelta {
+        let amount = |usage: &UsageData| {
+            usage
+                .extra_usage
+                .as_ref()
+                .filter(|e| e.is_enabled)
+                .and_then(|e| e.amount_used)
+                .unwrap_or(0.0)
+        };
+        let (before, now) = (amount(previous), amount(self));
+        UsageDelta {
+            five_hour: self.five_hour.diff(&previous.five_hour),
+            seven_day: self.seven_day.diff(&previous.seven_day),
+            seven_day_sonnet: self
+                .seven_day_sonnet
+                .as_ref()
+                .zip(previous.seven_day_sonnet.as_ref())
+                .map(|(current, previous)| current.diff(previous)),
+            extra_usage_change: now - before,
+            overage_started: before <= 0.0 && now > 0.0,
+        }
+    }
+}
+
+/// Change between two usage snapshots, from [`UsageData::diff`].
+///
+/// ```rust,ignore
+/// let delta = usage.diff(&previous);
+/// if let Some(rate) = delta.five_hour.per_hour(fetched_at - previous_fetched_at) {
+///     if delta.five_hour.change() >= 10.0 {
+///         println!("5h usage jumped {:.0}% ({:.0}%/h)", delta.five_hour.change(), rate);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageDelta {
+    /// 5-hour window change.
+    pub five_hour: PeriodDelta,
+    /// 7-day window change.
+    pub seven_day: PeriodDelta,
+    /// 7-day Sonnet window change, if both snapshots report it.
+    pub seven_day_sonnet: Option<PeriodDelta>,
+    /// Change in extra usage spent, in dollars (0.0 without extra usage).
+    pub extra_usage_change: f64,
+    /// True if extra usage charges started since the previous snapshot.
+    pub overage_started: bool,
+}
+
+impl UsageDelta {
+    /// True if any window started a new period.
+    pub fn rolled_over(&self) -> bool {
+        self.periods().any(|p| p.rolled_over)
+    }
+
+    /// Largest utilization increase across the windows (percentage points).
+    pub fn max_change(&self) -> f64 {
+        self.periods().map(PeriodDelta::change).fold(0.0, f64::max)
+    }
+
+    fn periods(&self) -> impl Iterator<Item = &PeriodDelta> {
+        [&self.five_hour, &self.seven_day]
+            .into_iter()
+            .chain(self.seven_day_sonnet.as_ref())
+    }
+}
+
+/// Change in one window between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeriodDelta {
+    /// Utilization in the earlier snapshot (percent).
+    pub previous: f64,
+    /// Utilization now (percent).
+    pub current: f64,
+    /// True if the window reset in between.
+    pub rolled_over: bool,
+}
+
+impl PeriodDelta {
+    /// Utilization gained (percentage points).
+    ///
+    /// After a rollover this is the utilization of the new period, since
+    /// the old period's usage no longer counts. Negative only when the API
+    /// revises a value down within a period.
+    pub fn change(&self) -> f64 {
+        if self.rolled_over {
+            self.current
+        } else {
+            self.current - self.previous
+        }
+    }
+
+    /// Change per hour given the time between the snapshots.
+    ///
+    /// Returns `None` if `elapsed` is not positive.
+    pub fn per_hour(&self, elapsed: TimeDelta) -> Option<f64> {
+        let hours = elapsed.num_milliseconds() as f64 / 3_600_000.0;
+        (hours > 0.0).then(|| self.change() / hours)
+    }
+
+    /// True if the window reached 100% since the previous snapshot.
+    pub fn reached_limit(&self) -> bool {
+        self.current >= 100.0 && (self.rolled_over || self.previous < 100.0)
+    }
 }
 
 #[cfg(test)]
@@ -128,6 +526,7 @@ mod tests {
         UsagePeriod {
             utilization,
             resets_at: Some(Utc::now() + Duration::hours(hours_until_reset)),
+            extra: Default::default(),
         }
     }
 
@@ -215,6 +614,7 @@ mod tests {
         let period = UsagePeriod {
             utilization: 50.0,
             resets_at: Some(Utc::now() + Duration::minutes(150)), // 2.5 hours
+            extra: Default::default(),
         };
         let elapsed = period
             .time_elapsed_percent(5)
@@ -231,6 +631,7 @@ mod tests {
         let period = UsagePeriod {
             utilization: 50.0,
             resets_at: None,
+            extra: Default::default(),
         };
         assert!(period.time_elapsed_percent(5).is_none());
     }
@@ -241,6 +642,7 @@ mod tests {
         let period = UsagePeriod {
             utilization: 30.0,
             resets_at: Some(Utc::now() + Duration::minutes(150)), // 50% remaining
+            extra: Default::default(),
         };
         assert!(
             period.is_on_pace(5).expect("reset time available"),
@@ -254,6 +656,7 @@ mod tests {
         let period = UsagePeriod {
             utilization: 70.0,
             resets_at: Some(Utc::now() + Duration::minutes(150)), // 50% remaining
+            extra: Default::default(),
         };
         assert!(
             !period.is_on_pace(5).expect("reset time available"),
@@ -267,6 +670,8 @@ mod tests {
             five_hour: sample_usage_period(10.0, 4), // 10% used, ~20% time elapsed
             seven_day: sample_usage_period(50.0, 84), // 50% used, 50% time elapsed
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         };
         assert!(usage.five_hour_on_pace().expect("reset time available"));
@@ -278,6 +683,8 @@ mod tests {
             five_hour: sample_usage_period(80.0, 1),
             seven_day: sample_usage_period(40.0, 84), // ~50% time remaining, 40% used
             seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: None,
         };
         assert!(usage.seven_day_on_pace().expect("reset time available"));
@@ -290,15 +697,20 @@ mod tests {
             five_hour: UsagePeriod {
                 utilization: 42.5,
                 resets_at: Some(now),
+                extra: Default::default(),
             },
             seven_day: UsagePeriod {
                 utilization: 88.0,
                 resets_at: Some(now),
+                extra: Default::default(),
             },
             seven_day_sonnet: Some(UsagePeriod {
                 utilization: 0.0,
                 resets_at: Some(now),
+                extra: Default::default(),
             }),
+            breakdown: Default::default(),
+            extra: Default::default(),
             extra_usage: Some(ExtraUsage {
                 is_enabled: true,
                 amount_used: Some(10.0),
@@ -331,4 +743,290 @@ mod tests {
         assert!(usage.five_hour.resets_at.is_none());
         assert!(usage.seven_day.resets_at.is_none());
     }
+
+    #[test]
+    fn test_burn_rate_and_projection() {
+        // 2 of 5 hours elapsed at 20% -> 10%/h, 3 more hours -> 50%
+        let period = sample_usage_period(20.0, 3);
+        let rate = period.burn_rate(5).expect("rate");
+        assert!((rate - 10.0).abs() < 0.1, "{rate}");
+        let projected = period.projected_at_reset(rate).expect("projection");
+        assert!((projected - 50.0).abs() < 0.5, "{projected}");
+    }
+
+    #[test]
+    fn test_burn_rate_none_at_period_start() {
+        assert_eq!(sample_usage_period(0.0, 5).burn_rate(5), None);
+        let no_reset = UsagePeriod {
+            utilization: 10.0,
+            resets_at: None,
+            extra: Default::default(),
+        };
+        assert_eq!(no_reset.burn_rate(5), None);
+        assert_eq!(no_reset.projected_at_reset(1.0), None);
+    }
+
+    #[test]
+    fn test_time_until_limit() {
+        let period = sample_usage_period(60.0, 3);
+        let until = period.time_until_limit(20.0).expect("hits limit");
+        assert_eq!(until.num_hours(), 2);
+        assert_eq!(period.time_until_limit(10.0), None, "resets first");
+        assert_eq!(period.time_until_limit(0.0), None);
+        assert_eq!(
+            sample_usage_period(100.0, 1).time_until_limit(0.0),
+            Some(chrono::TimeDelta::zero())
+        );
+    }
+
+    #[test]
+    fn test_usage_data_projections() {
+        let usage = UsageData {
+            five_hour: sample_usage_period(80.0, 1),
+            seven_day: sample_usage_period(10.0, 6 * 24),
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        };
+        // 4 hours elapsed at 80% -> 20%/h, limit in one hour, right at reset
+        let five = usage.five_hour_projected_at_reset().expect("5h projection");
+        assert!((five - 100.0).abs() < 0.5, "{five}");
+        let seven = usage.seven_day_projected_at_reset().expect("7d projection");
+        assert!((seven - 70.0).abs() < 0.5, "{seven}");
+        assert_eq!(usage.estimated_time_until_limit(), None);
+    }
+
+    #[test]
+    fn test_history_slope_overrides_average_rate() {
+        let usage = UsageData {
+            five_hour: sample_usage_period(40.0, 3),
+            seven_day: sample_usage_period(10.0, 6 * 24),
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        };
+        // Average rate is 20%/h (projects 100%), but the last half hour ran
+        // at 40%/h, reaching the limit in about 1.5 hours.
+        let now = Utc::now();
+        let history = [
+            HistorySample {
+                at: now - Duration::minutes(30),
+                five_hour: 20.0,
+                seven_day: 10.0,
+                seven_day_sonnet: None,
+            },
+            HistorySample {
+                at: now,
+                five_hour: 40.0,
+                seven_day: 10.0,
+                seven_day_sonnet: None,
+            },
+        ];
+        let projected = usage
+            .five_hour_projected_at_reset_with_history(&history)
+            .expect("projection");
+        assert!((projected - 160.0).abs() < 1.0, "{projected}");
+        let until = usage
+            .estimated_time_until_limit_with_history(&history)
+            .expect("hits limit");
+        assert_eq!(until.num_minutes(), 90);
+        assert_eq!(
+            usage.estimated_time_until_limit_with_history(&[]),
+            None,
+            "falls back to the average rate"
+        );
+    }
+
+    #[test]
+    fn test_extra_usage_remaining() {
+        let extra = ExtraUsage {
+            is_enabled: true,
+            amount_used: Some(12.5),
+            limit: Some(20.0),
+        };
+        assert_eq!(extra.remaining(), Some(7.5));
+        assert_eq!(extra.covers(7.5), Some(true));
+        assert_eq!(extra.covers(8.0), Some(false));
+
+        let over = ExtraUsage {
+            amount_used: Some(25.0),
+            ..extra.clone()
+        };
+        assert_eq!(over.remaining(), Some(0.0));
+
+        let disabled = ExtraUsage {
+            is_enabled: false,
+            ..extra
+        };
+        assert_eq!(disabled.remaining(), None);
+        assert_eq!(disabled.covers(1.0), None);
+    }
+
+    fn snapshot(five_hour: UsagePeriod, amount_used: Option<f64>) -> UsageData {
+        UsageData {
+            five_hour,
+            seven_day: sample_usage_period(20.0, 48),
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: Some(ExtraUsage {
+                is_enabled: true,
+                amount_used,
+                limit: Some(50.0),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_diff_within_period() {
+        let resets_at = Some(Utc::now() + Duration::hours(2));
+        let before = snapshot(
+            UsagePeriod {
+                utilization: 30.0,
+                resets_at,
+                extra: Default::default(),
+            },
+            None,
+        );
+        let after = snapshot(
+            UsagePeriod {
+                utilization: 42.0,
+                resets_at: resets_at.map(|t| t + Duration::seconds(3)),
+                extra: Default::default(),
+            },
+            Some(1.5),
+        );
+
+        let delta = after.diff(&before);
+        assert!(!delta.rolled_over());
+        assert_eq!(delta.five_hour.change(), 12.0);
+        assert_eq!(delta.max_change(), 12.0);
+        assert_eq!(delta.five_hour.per_hour(Duration::minutes(10)), Some(72.0));
+        assert_eq!(delta.five_hour.per_hour(Duration::zero()), None);
+        assert!(delta.overage_started);
+        assert_eq!(delta.extra_usage_change, 1.5);
+        assert_eq!(delta.seven_day_sonnet, None);
+    }
+
+    #[test]
+    fn test_diff_rollover_counts_new_period_only() {
+        let now = Utc::now();
+        let before = UsagePeriod {
+            utilization: 85.0,
+            resets_at: Some(now),
+            extra: Default::default(),
+        };
+        let after = UsagePeriod {
+            utilization: 4.0,
+            resets_at: Some(now + Duration::hours(5)),
+            extra: Default::default(),
+        };
+        let delta = after.diff(&before);
+        assert!(delta.rolled_over);
+        assert_eq!(delta.change(), 4.0);
+
+        let idle = UsagePeriod {
+            utilization: 0.0,
+            resets_at: None,
+            extra: Default::default(),
+        };
+        assert!(idle.diff(&before).rolled_over);
+        assert!(!before.diff(&idle).rolled_over);
+    }
+
+    #[test]
+    fn test_diff_reached_limit_and_ongoing_overage() {
+        let before = snapshot(sample_usage_period(97.0, 1), Some(2.0));
+        let after = snapshot(sample_usage_period(100.0, 1), Some(3.0));
+        let delta = after.diff(&before);
+        assert!(delta.five_hour.reached_limit());
+        assert!(!delta.overage_started);
+        assert_eq!(delta.extra_usage_change, 1.0);
+        assert!(!after.diff(&after).five_hour.reached_limit());
+    }
+
+    #[test]
+    fn test_breakdown_keeps_only_period_fields() {
+        let json = r#"{
+            "five_hour": {"utilization": 40.0, "resets_at": null},
+            "seven_day": {"utilization": 30.0, "resets_at": null},
+            "seven_day_sonnet": {"utilization": 10.0, "resets_at": null},
+            "seven_day_opus": {"utilization": 25.0, "resets_at": "2026-01-30T12:00:00Z"},
+            "seven_day_oauth_apps": null,
+            "five_hour_opus": {"utilization": 5.0, "resets_at": null},
+            "iguana_necktie": "unknown"
+        }"#;
+
+        let usage: UsageData = serde_json::from_str(json).expect("should parse");
+        assert_eq!(usage.breakdown.windows.len(), 2);
+        let opus = usage.breakdown.get("seven_day_opus").expect("opus window");
+        assert_eq!(opus.utilization, 25.0);
+        assert!(opus.resets_at.is_some());
+
+        let buckets: Vec<_> = usage
+            .buckets()
+            .into_iter()
+            .map(|b| (b.window, b.bucket, b.period.utilization))
+            .collect();
+        assert_eq!(
+            buckets,
+            vec![
+                ("seven_day", "opus", 25.0),
+                ("seven_day", "sonnet", 10.0),
+                ("five_hour", "opus", 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_breakdown_round_trip() {
+        let json = r#"{
+            "five_hour": {"utilization": 1.0, "resets_at": null},
+            "seven_day": {"utilization": 2.0, "resets_at": null},
+            "seven_day_opus": {"utilization": 3.0, "resets_at": null}
+        }"#;
+        let usage: UsageData = serde_json::from_str(json).expect("should parse");
+        let encoded = serde_json::to_string(&usage).expect("should serialize");
+        let decoded: UsageData = serde_json::from_str(&encoded).expect("should parse");
+        assert_eq!(decoded, usage);
+        assert!(!decoded.breakdown.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let json = r#"{
+            "five_hour": {"utilization": 40.0, "resets_at": null, "burst_credits": 12},
+            "seven_day": {"utilization": 30.0, "resets_at": null},
+            "seven_day_oauth_apps": null,
+            "plan": {"tier": "max", "seats": 5}
+        }"#;
+
+        let usage: UsageData = serde_json::from_str(json).expect("should parse");
+        assert_eq!(usage.five_hour.extra["burst_credits"], 12);
+        assert_eq!(usage.extra["plan"]["tier"], "max");
+        assert!(usage.extra["seven_day_oauth_apps"].is_null());
+        assert!(usage.breakdown.is_empty());
+
+        let encoded = serde_json::to_value(&usage).expect("should serialize");
+        let original: serde_json::Value = serde_json::from_str(json).expect("valid JSON");
+        for (key, value) in original.as_object().expect("object") {
+            assert_eq!(&encoded[key], value, "field {key}");
+        }
+        let decoded: UsageData = serde_json::from_value(encoded).expect("should parse");
+        assert_eq!(decoded, usage);
+    }
+
+    #[test]
+    fn test_known_fields_not_duplicated_in_extra() {
+        let json = r#"{
+            "five_hour": {"utilization": 1.0, "resets_at": null},
+            "seven_day": {"utilization": 2.0, "resets_at": null},
+            "extra_usage": {"is_enabled": false}
+        }"#;
+        let usage: UsageData = serde_json::from_str(json).expect("should parse");
+        assert!(usage.extra.is_empty());
+        assert!(usage.five_hour.extra.is_empty());
+    }
 }
@@ -0,0 +1,518 @@
+//! Threshold and reset notifications for polled usage.
+//!
+//! [`UsageWatcher`] compares each usage snapshot with the previous one and
+//! calls back when a window's utilization reaches a threshold (50%, 80% and
+//! 95% by default) or when a window resets. A threshold fires once and is
+//! re-armed only after utilization falls below it by the hysteresis margin,
+//! or the window resets, so values hovering around a threshold do not repeat
+//! the notification.
+//!
+//! The watcher can poll on its own ([`UsageWatcher::spawn`], or
+//! `run_async` with the `async` feature) or be fed snapshots fetched
+//! elsewhere with [`UsageWatcher::observe`]. With the `async` feature it can
+//! also be turned into a `stream::UsageStream` of [`UsageEvent`]s, which is what
+//! [`subscribe`](crate::subscribe) returns.
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use claude_usage::watcher::UsageWatcher;
+//!
+//! let handle = UsageWatcher::new()
+//!     .interval(Duration::from_secs(120))
+//!     .on_threshold(|event| {
+//!         println!("{} at {:.0}% (crossed {}%)", event.window, event.utilization, event.threshold);
+//!     })
+//!     .on_reset(|event| println!("{} reset", event.window))
+//!     .spawn();
+//! // ...
+//! handle.stop();
+//! ```
+
+use std::fmt;
+#[cfg(feature = "async")]
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::Error;
+#[cfg(feature = "async")]
+use crate::stream::UsageStream;
+#[cfg(feature = "async")]
+use crate::transport::UsageClient;
+use crate::types::{UsageData, UsagePeriod};
+
+/// Default utilization thresholds (percent).
+pub const DEFAULT_THRESHOLDS: &[f64] = &[50.0, 80.0, 95.0];
+
+/// Default re-arm margin below a threshold (percentage points).
+pub const DEFAULT_HYSTERESIS: f64 = 2.0;
+
+/// Default time between polls.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A usage window tracked by the watcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageWindow {
+    /// 5-hour rolling window.
+    FiveHour,
+    /// 7-day rolling window.
+    SevenDay,
+    /// 7-day Sonnet-specific window.
+    SevenDaySonnet,
+}
+
+impl UsageWindow {
+    /// All windows, in display order.
+    pub const ALL: [UsageWindow; 3] = [Self::FiveHour, Self::SevenDay, Self::SevenDaySonnet];
+
+    /// Returns this window's period in `usage`, if reported.
+    pub fn period(self, usage: &UsageData) -> Option<&UsagePeriod> {
+        match self {
+            Self::FiveHour => Some(&usage.five_hour),
+            Self::SevenDay => Some(&usage.seven_day),
+            Self::SevenDaySonnet => usage.seven_day_sonnet.as_ref(),
+        }
+    }
+}
+
+impl fmt::Display for UsageWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::FiveHour => "5h",
+            Self::SevenDay => "7d",
+            Self::SevenDaySonnet => "7d sonnet",
+        })
+    }
+}
+
+/// A window's utilization reached a threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdEvent {
+    /// The window that crossed.
+    pub window: UsageWindow,
+    /// The threshold reached (percent).
+    pub threshold: f64,
+    /// Current utilization (percent).
+    pub utilization: f64,
+    /// When the window resets, if known.
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+/// A window started a new period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResetEvent {
+    /// The window that reset.
+    pub window: UsageWindow,
+    /// Utilization in the last snapshot before the reset (percent).
+    pub previous_utilization: f64,
+    /// Utilization now (percent).
+    pub utilization: f64,
+}
+
+/// A notification from [`UsageWatcher::observe`] or a usage stream.
+#[derive(Debug)]
+pub enum UsageEvent {
+    /// A poll succeeded (streams only).
+    Updated(UsageData),
+    /// See [`ThresholdEvent`].
+    ThresholdCrossed(ThresholdEvent),
+    /// See [`ResetEvent`].
+    PeriodReset(ResetEvent),
+    /// A poll failed (streams only). Polling continues.
+    Error(Error),
+}
+
+/// What the watcher remembers about one window.
+#[derive(Debug, Clone, Default)]
+struct WindowState {
+    /// Last observed period, if any.
+    last: Option<UsagePeriod>,
+    /// Thresholds (by index) that fired and are not yet re-armed.
+    fired: Vec<bool>,
+}
+
+type Callback<E> = Box<dyn FnMut(&E) + Send>;
+
+/// Polls usage and calls back on threshold crossings and window resets.
+///
+/// Built with chained setters; see the [module docs](self).
+pub struct UsageWatcher {
+    thresholds: Vec<f64>,
+    hysteresis: f64,
+    interval: Duration,
+    windows: [WindowState; 3],
+    on_threshold: Option<Callback<ThresholdEvent>>,
+    on_reset: Option<Callback<ResetEvent>>,
+    on_error: Option<Callback<Error>>,
+}
+
+impl fmt::Debug for UsageWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsageWatcher")
+            .field("thresholds", &self.thresholds)
+            .field("hysteresis", &self.hysteresis)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for UsageWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UsageWatcher {
+    /// Creates a watcher with the default thresholds, hysteresis and interval.
+    pub fn new() -> Self {
+        Self {
+            thresholds: DEFAULT_THRESHOLDS.to_vec(),
+            hysteresis: DEFAULT_HYSTERESIS,
+            interval: DEFAULT_INTERVAL,
+            windows: Default::default(),
+            on_threshold: None,
+            on_reset: None,
+            on_error: None,
+        }
+    }
+
+    /// Sets the utilization thresholds (percent). Duplicates are dropped.
+    pub fn thresholds(mut self, thresholds: &[f64]) -> Self {
+        let mut thresholds = thresholds.to_vec();
+        thresholds.sort_by(f64::total_cmp);
+        thresholds.dedup();
+        self.thresholds = thresholds;
+        self.windows = Default::default();
+        self
+    }
+
+    /// Sets how far (percentage points) utilization must fall below a fired
+    /// threshold before it can fire again. Negative values are treated as 0.
+    pub fn hysteresis(mut self, points: f64) -> Self {
+        self.hysteresis = points.max(0.0);
+        self
+    }
+
+    /// Sets the time between polls for [`spawn`](Self::spawn),
+    /// `run_async` and streams.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the time between polls.
+    pub fn poll_interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Calls `callback` when a window reaches a threshold.
+    pub fn on_threshold(mut self, callback: impl FnMut(&ThresholdEvent) + Send + 'static) -> Self {
+        self.on_threshold = Some(Box::new(callback));
+        self
+    }
+
+    /// Calls `callback` when a window resets.
+    pub fn on_reset(mut self, callback: impl FnMut(&ResetEvent) + Send + 'static) -> Self {
+        self.on_reset = Some(Box::new(callback));
+        self
+    }
+
+    /// Calls `callback` when a poll fails. Polling continues either way.
+    pub fn on_error(mut self, callback: impl FnMut(&Error) + Send + 'static) -> Self {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Compares `usage` with the previous snapshot, calls the matching
+    /// callbacks and returns the [`ThresholdCrossed`](UsageEvent::ThresholdCrossed)
+    /// and [`PeriodReset`](UsageEvent::PeriodReset) events in the order they
+    /// fired.
+    ///
+    /// Thresholds already exceeded in the first snapshot fire immediately.
+    /// Resets are detected as in [`UsagePeriod::diff`].
+    pub fn observe(&mut self, usage: &UsageData) -> Vec<UsageEvent> {
+        let mut events = Vec::new();
+        for (window, state) in UsageWindow::ALL.into_iter().zip(self.windows.iter_mut()) {
+            let Some(period) = window.period(usage) else {
+                continue;
+            };
+            if state.fired.len() != self.thresholds.len() {
+                state.fired = vec![false; self.thresholds.len()];
+            }
+
+            if let Some(last) = &state.last {
+                if period.diff(last).rolled_over {
+                    state.fired.fill(false);
+                    events.push(UsageEvent::PeriodReset(ResetEvent {
+                        window,
+                        previous_utilization: last.utilization,
+                        utilization: period.utilization,
+                    }));
+                }
+            }
+
+            // Report only the highest newly reached threshold, but mark every
+            // threshold below it as fired too
+            let mut crossed = None;
+            for (&threshold, fired) in self.thresholds.iter().zip(state.fired.iter_mut()) {
+                if period.utilization >= threshold {
+                    if !*fired {
+                        *fired = true;
+                        crossed = Some(threshold);
+                    }
+                } else if period.utilization < threshold - self.hysteresis {
+                    *fired = false;
+                }
+            }
+            if let Some(threshold) = crossed {
+                events.push(UsageEvent::ThresholdCrossed(ThresholdEvent {
+                    window,
+                    threshold,
+                    utilization: period.utilization,
+                    resets_at: period.resets_at,
+                }));
+            }
+
+            state.last = Some(period.clone());
+        }
+
+        for event in &events {
+            match event {
+                UsageEvent::ThresholdCrossed(e) => {
+                    if let Some(callback) = &mut self.on_threshold {
+                        callback(e);
+                    }
+                }
+                UsageEvent::PeriodReset(e) => {
+                    if let Some(callback) = &mut self.on_reset {
+                        callback(e);
+                    }
+                }
+                UsageEvent::Updated(_) | UsageEvent::Error(_) => {}
+            }
+        }
+        events
+    }
+
+    /// Passes a failed poll to the error callback.
+    pub(crate) fn report_error(&mut self, error: &Error) {
+        if let Some(callback) = &mut self.on_error {
+            callback(error);
+        }
+    }
+
+    /// Polls with `fetch` on a background thread until the returned handle
+    /// is stopped or dropped.
+    pub fn spawn_with(
+        mut self,
+        mut fetch: impl FnMut() -> Result<UsageData, Error> + Send + 'static,
+    ) -> WatcherHandle {
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || loop {
+            match fetch() {
+                Ok(usage) => {
+                    self.observe(&usage);
+                }
+                Err(e) => self.report_error(&e),
+            }
+            match stopped.recv_timeout(self.interval) {
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                _ => break,
+            }
+        });
+        WatcherHandle {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Polls [`get_usage`](crate::get_usage) on a background thread until
+    /// the returned handle is stopped or dropped.
+    #[cfg(feature = "blocking")]
+    pub fn spawn(self) -> WatcherHandle {
+        self.spawn_with(crate::get_usage)
+    }
+
+    /// Turns the watcher into a stream that polls `client` every interval,
+    /// starting immediately.
+    ///
+    /// Each successful poll yields [`UsageEvent::Updated`] followed by any
+    /// threshold and reset events; a failed poll yields [`UsageEvent::Error`].
+    /// Callbacks set on the watcher still run.
+    #[cfg(feature = "async")]
+    pub fn into_stream(self, client: Arc<dyn UsageClient>) -> UsageStream {
+        UsageStream::new(self, client)
+    }
+
+    /// Polls [`get_usage_async`](crate::get_usage_async) forever. Drop the
+    /// future (for example via `tokio::select!`) to stop.
+    #[cfg(feature = "async")]
+    pub async fn run_async(mut self) {
+        loop {
+            match crate::get_usage_async().await {
+                Ok(usage) => {
+                    self.observe(&usage);
+                }
+                Err(e) => self.report_error(&e),
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+}
+
+/// Stops a watcher started with [`UsageWatcher::spawn`] when stopped or
+/// dropped.
+#[derive(Debug)]
+pub struct WatcherHandle {
+    stop: Option<std::sync::mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl WatcherHandle {
+    /// Stops polling and waits for an in-progress poll to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        // Dropping the sender wakes the polling thread
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WatcherHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use std::sync::{Arc, Mutex};
+
+    fn usage(five_hour: f64, resets_at: Option<DateTime<Utc>>) -> UsageData {
+        UsageData {
+            five_hour: UsagePeriod {
+                utilization: five_hour,
+                resets_at,
+                extra: Default::default(),
+            },
+            seven_day: UsagePeriod {
+                utilization: 10.0,
+                resets_at: None,
+                extra: Default::default(),
+            },
+            seven_day_sonnet: None,
+            breakdown: Default::default(),
+            extra: Default::default(),
+            extra_usage: None,
+        }
+    }
+
+    fn thresholds(events: &[UsageEvent]) -> Vec<f64> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                UsageEvent::ThresholdCrossed(t) => Some(t.threshold),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_crossing_fires_once_with_hysteresis() {
+        let mut watcher = UsageWatcher::new().thresholds(&[80.0]).hysteresis(5.0);
+        assert!(watcher.observe(&usage(79.0, None)).is_empty());
+        assert_eq!(thresholds(&watcher.observe(&usage(80.5, None))), vec![80.0]);
+        // Flapping inside the margin does not repeat
+        assert!(watcher.observe(&usage(77.0, None)).is_empty());
+        assert!(watcher.observe(&usage(81.0, None)).is_empty());
+        // Falling below the margin re-arms
+        assert!(watcher.observe(&usage(74.0, None)).is_empty());
+        assert_eq!(thresholds(&watcher.observe(&usage(82.0, None))), vec![80.0]);
+    }
+
+    #[test]
+    fn test_jump_reports_highest_threshold_only() {
+        let mut watcher = UsageWatcher::new();
+        assert_eq!(thresholds(&watcher.observe(&usage(96.0, None))), vec![95.0]);
+        assert!(watcher.observe(&usage(97.0, None)).is_empty());
+    }
+
+    #[test]
+    fn test_reset_rearms_thresholds() {
+        let first = Utc::now() + TimeDelta::hours(1);
+        let mut watcher = UsageWatcher::new().thresholds(&[50.0]);
+        assert_eq!(
+            thresholds(&watcher.observe(&usage(60.0, Some(first)))),
+            vec![50.0]
+        );
+        // Small drift in resets_at is not a reset
+        let drift = first + TimeDelta::seconds(2);
+        assert!(watcher.observe(&usage(61.0, Some(drift))).is_empty());
+
+        let events = watcher.observe(&usage(55.0, Some(first + TimeDelta::hours(5))));
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            UsageEvent::PeriodReset(reset) => assert_eq!(
+                *reset,
+                ResetEvent {
+                    window: UsageWindow::FiveHour,
+                    previous_utilization: 61.0,
+                    utilization: 55.0,
+                }
+            ),
+            other => panic!("expected PeriodReset, got {:?}", other),
+        }
+        assert_eq!(thresholds(&events), vec![50.0]);
+    }
+
+    #[test]
+    fn test_cleared_reset_time_is_a_reset() {
+        let mut watcher = UsageWatcher::new();
+        watcher.observe(&usage(30.0, Some(Utc::now())));
+        let events = watcher.observe(&usage(0.0, None));
+        assert!(matches!(
+            events.as_slice(),
+            [UsageEvent::PeriodReset(ResetEvent {
+                window: UsageWindow::FiveHour,
+                ..
+            })]
+        ));
+    }
+
+    #[test]
+    fn test_callbacks_receive_events() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let mut watcher = UsageWatcher::new().on_threshold(move |e| {
+            sink.lock().unwrap().push((e.window, e.threshold));
+        });
+        watcher.observe(&usage(51.0, None));
+        assert_eq!(*seen.lock().unwrap(), vec![(UsageWindow::FiveHour, 50.0)]);
+    }
+
+    #[test]
+    fn test_spawn_with_polls_until_stopped() {
+        let errors = Arc::new(Mutex::new(0));
+        let sink = Arc::clone(&errors);
+        let (polled, poll) = std::sync::mpsc::channel();
+        let handle = UsageWatcher::new()
+            .interval(Duration::from_secs(3600))
+            .on_error(move |_| *sink.lock().unwrap() += 1)
+            .spawn_with(move || {
+                let _ = polled.send(());
+                Err(Error::Parse("offline".to_string()))
+            });
+        // The first poll runs immediately; stop interrupts the long interval
+        poll.recv().expect("first poll");
+        handle.stop();
+        assert_eq!(*errors.lock().unwrap(), 1);
+    }
+}